@@ -0,0 +1,51 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use tantivy::fastfield::{FastFieldCodec, FastFieldReader};
+
+const NUM_VALS: u64 = 1_000_000;
+
+fn bench_codec(c: &mut Criterion, name: &str, vals: Vec<u64>, expected_codec: FastFieldCodec) {
+    let num_vals = vals.len();
+    let expected_sum: u64 = vals.iter().fold(0u64, |sum, &val| sum.wrapping_add(val));
+    let reader = FastFieldReader::<u64>::from(vals);
+    assert_eq!(reader.codec(), expected_codec);
+    c.bench_function(&format!("fastfield-{}-get", name), |b| {
+        b.iter(|| {
+            let sum = (0..num_vals as u32).fold(0u64, |sum, doc| sum.wrapping_add(reader.get(doc)));
+            assert_eq!(sum, expected_sum);
+        })
+    });
+    // Documents visited in a pseudo random order, as when sorting the hits of a query
+    // by a fast field.
+    c.bench_function(&format!("fastfield-{}-get-random", name), |b| {
+        b.iter(|| {
+            let sum = (0..num_vals as u64).fold(0u64, |sum, i| {
+                let doc = (i * 2_654_435_761) % num_vals as u64;
+                sum.wrapping_add(reader.get(doc as u32))
+            });
+            assert_eq!(sum, expected_sum);
+        })
+    });
+    let mut output = vec![0u64; num_vals];
+    c.bench_function(&format!("fastfield-{}-get-range", name), |b| {
+        b.iter(|| {
+            reader.get_range(0, &mut output[..]);
+            let sum = output.iter().fold(0u64, |sum, &val| sum.wrapping_add(val));
+            assert_eq!(sum, expected_sum);
+        })
+    });
+}
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    // Pseudo random values, spread over 2^32: they are bitpacked.
+    let random_vals: Vec<u64> = (0..NUM_VALS)
+        .map(|i| i.wrapping_mul(2_654_435_761) % (1u64 << 32))
+        .collect();
+    bench_codec(c, "bitpacked", random_vals, FastFieldCodec::Bitpacked);
+    // Timestamps of documents indexed in order, spread over 2^30:
+    // they are delta-encoded.
+    let timestamps: Vec<u64> = (0..NUM_VALS).map(|i| i * 1_000 + i % 7).collect();
+    bench_codec(c, "delta-zigzag", timestamps, FastFieldCodec::DeltaZigzag);
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
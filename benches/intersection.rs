@@ -0,0 +1,48 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use tantivy::collector::Count;
+use tantivy::query::{BooleanQuery, Occur, Query, TermQuery};
+use tantivy::schema::{IndexRecordOption, Schema, STRING};
+use tantivy::{Document, Index, Term};
+
+const NUM_DOCS: u64 = 1_000_000;
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let mut schema_builder = Schema::builder();
+    let text = schema_builder.add_text_field("text", STRING);
+    let index = Index::create_in_ram(schema_builder.build());
+    let mut index_writer = index.writer_with_num_threads(1, 100_000_000).unwrap();
+    for doc_id in 0..NUM_DOCS {
+        let mut doc = Document::default();
+        if doc_id % 2 == 0 {
+            doc.add_text(text, "even");
+        }
+        if doc_id % 3 == 0 {
+            doc.add_text(text, "triple");
+        }
+        index_writer.add_document(doc);
+    }
+    index_writer.commit().unwrap();
+    let searcher = index.reader().unwrap().searcher();
+    let term_query = |text_value: &str| -> Box<dyn Query> {
+        Box::new(TermQuery::new(
+            Term::from_field_text(text, text_value),
+            IndexRecordOption::Basic,
+        ))
+    };
+    let query = BooleanQuery::new(vec![
+        (Occur::Must, term_query("even")),
+        (Occur::Must, term_query("triple")),
+    ]);
+    let expected_count = (0..NUM_DOCS).filter(|doc_id| doc_id % 6 == 0).count();
+    // Intersects whole blocks of postings, with SIMD instructions if available.
+    c.bench_function("intersection-count-blocks", |b| {
+        b.iter(|| assert_eq!(query.count(&searcher).unwrap(), expected_count))
+    });
+    // Intersects the postings one document at a time.
+    c.bench_function("intersection-count-collector", |b| {
+        b.iter(|| assert_eq!(searcher.search(&query, &Count).unwrap(), expected_count))
+    });
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,72 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use tantivy::collector::TopDocs;
+use tantivy::fastfield::FastFieldCodec;
+use tantivy::query::{Query, RangeQuery};
+use tantivy::schema::{Schema, FAST, INDEXED};
+use tantivy::{doc, Index, Searcher};
+
+const NUM_DOCS: u64 = 1_000_000;
+
+fn count(searcher: &Searcher, query: &dyn Query, scoring_enabled: bool) -> u32 {
+    let weight = query.weight(searcher, scoring_enabled).unwrap();
+    searcher
+        .segment_readers()
+        .iter()
+        .map(|segment_reader| weight.count(segment_reader).unwrap())
+        .sum()
+}
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let mut schema_builder = Schema::builder();
+    let price = schema_builder.add_u64_field("price", INDEXED | FAST);
+    let timestamp = schema_builder.add_u64_field("timestamp", FAST);
+    let index = Index::create_in_ram(schema_builder.build());
+    let mut index_writer = index.writer_with_num_threads(1, 100_000_000).unwrap();
+    for doc_id in 0..NUM_DOCS {
+        index_writer.add_document(doc!(
+            price => (doc_id * 7_919) % 100_000,
+            timestamp => 1_000_000 + doc_id * 1_000 + doc_id % 7,
+        ));
+    }
+    index_writer.commit().unwrap();
+    let searcher = index.reader().unwrap().searcher();
+    for segment_reader in searcher.segment_readers() {
+        let fast_fields = segment_reader.fast_fields();
+        assert_eq!(
+            fast_fields.u64(price).unwrap().codec(),
+            FastFieldCodec::Bitpacked
+        );
+        assert_eq!(
+            fast_fields.u64(timestamp).unwrap().codec(),
+            FastFieldCodec::DeltaZigzag
+        );
+    }
+    for &(name, upper) in &[("narrow", 1_001u64), ("wide", 50_000u64)] {
+        let query = RangeQuery::new_u64(price, 1..upper);
+        let expected_count = count(&searcher, &query, true);
+        // Scoring requires the postings of each of the terms within the range.
+        c.bench_function(&format!("range-{}-postings", name), |b| {
+            b.iter(|| assert_eq!(count(&searcher, &query, true), expected_count))
+        });
+        // Filtering scans the fast field column if the range is dense.
+        c.bench_function(&format!("range-{}-filter", name), |b| {
+            b.iter(|| assert_eq!(count(&searcher, &query, false), expected_count))
+        });
+        // Sorting reads the fast field value of each of the hits.
+        for &(sort_name, sort_field) in &[("price", price), ("timestamp", timestamp)] {
+            let collector = TopDocs::with_limit(10).order_by_u64_field(sort_field);
+            c.bench_function(&format!("range-{}-sort-by-{}", name, sort_name), |b| {
+                b.iter(|| assert_eq!(searcher.search(&query, &collector).unwrap().len(), 10))
+            });
+        }
+    }
+    // The timestamp field is not indexed: the range is filtered with its column.
+    let query = RangeQuery::new_u64(timestamp, 1_000_000..500_000_000);
+    let expected_count = count(&searcher, &query, false);
+    c.bench_function("range-timestamp-filter", |b| {
+        b.iter(|| assert_eq!(count(&searcher, &query, false), expected_count))
+    });
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -1,15 +1,79 @@
 mod occur;
 mod query_grammar;
 mod user_input_ast;
+use combine::easy;
 use combine::parser::Parser;
+use combine::stream::position::{IndexPositioner, Stream};
 
 pub use crate::occur::Occur;
 use crate::query_grammar::parse_to_ast;
 pub use crate::user_input_ast::{UserInputAST, UserInputBound, UserInputLeaf, UserInputLiteral};
 
-pub struct Error;
+/// Error returned when the query is not valid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    /// Byte offset in the query at which the parsing failed.
+    pub position: usize,
+    /// Description of what was expected at `position`, like `"digit"` or `"')'"`.
+    pub expected: Vec<String>,
+    /// Character found at `position`, or `None` if the end of the query was reached.
+    pub got: Option<char>,
+}
+
+impl Error {
+    fn from_easy_errors(query: &str, errors: easy::Errors<char, &str, usize>) -> Error {
+        let position = query
+            .char_indices()
+            .nth(errors.position)
+            .map(|(offset, _)| offset)
+            .unwrap_or_else(|| query.len());
+        let mut expected = Vec::new();
+        for error in &errors.errors {
+            if let easy::Error::Expected(info) = error {
+                let description = info.to_string();
+                if !expected.contains(&description) {
+                    expected.push(description);
+                }
+            }
+        }
+        Error {
+            position,
+            expected,
+            got: query[position..].chars().next(),
+        }
+    }
+}
 
 pub fn parse_query(query: &str) -> Result<UserInputAST, Error> {
-    let (user_input_ast, _remaining) = parse_to_ast().parse(query).map_err(|_| Error)?;
+    let stream = easy::Stream(Stream::with_positioner(query, IndexPositioner::default()));
+    let (user_input_ast, _remaining) = parse_to_ast()
+        .parse(stream)
+        .map_err(|errors| Error::from_easy_errors(query, errors))?;
     Ok(user_input_ast)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_query, Error};
+
+    #[test]
+    fn test_parse_query_error_position() {
+        let error = parse_query("title:abc)").unwrap_err();
+        assert_eq!(error.position, 9);
+        assert_eq!(error.got, Some(')'));
+        assert_eq!(error.expected, vec!["end of input".to_string()]);
+        let error = parse_query("(abc").unwrap_err();
+        assert_eq!(error.position, 4);
+        assert_eq!(error.got, None);
+        assert!(error.expected.contains(&"`)`".to_string()));
+        // The position is a byte offset.
+        assert_eq!(
+            parse_query("é ^x").unwrap_err(),
+            Error {
+                position: 3,
+                expected: vec!["end of input".to_string()],
+                got: Some('^'),
+            }
+        );
+    }
+}
@@ -1,24 +1,40 @@
 use super::user_input_ast::{UserInputAST, UserInputBound, UserInputLeaf, UserInputLiteral};
 use crate::Occur;
-use combine::error::StringStreamError;
+use combine::error::{ParseError, StreamError};
 use combine::parser::char::{char, digit, letter, space, spaces, string};
 use combine::parser::Parser;
+use combine::stream::{Stream, StreamErrorFor};
 use combine::{
     attempt, choice, eof, many, many1, one_of, optional, parser, satisfy, skip_many1, value,
 };
 
-fn field<'a>() -> impl Parser<&'a str, Output = String> {
+fn field_name<Input>() -> impl Parser<Input, Output = String>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<char, Input::Range, Input::Position>,
+{
     (
         (letter().or(char('_'))),
         many(satisfy(|c: char| {
-            c.is_alphanumeric() || c == '_' || c == '-'
+            c.is_alphanumeric() || c == '_' || c == '-' || c == '.'
         })),
     )
-        .skip(char(':'))
         .map(|(s1, s2): (char, String)| format!("{}{}", s1, s2))
 }
 
-fn word<'a>() -> impl Parser<&'a str, Output = String> {
+fn field<Input>() -> impl Parser<Input, Output = String>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<char, Input::Range, Input::Position>,
+{
+    field_name().skip(char(':'))
+}
+
+fn word<Input>() -> impl Parser<Input, Output = String>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<char, Input::Range, Input::Position>,
+{
     (
         satisfy(|c: char| {
             !c.is_whitespace()
@@ -30,35 +46,101 @@ fn word<'a>() -> impl Parser<&'a str, Output = String> {
     )
         .map(|(s1, s2): (char, String)| format!("{}{}", s1, s2))
         .and_then(|s: String| match s.as_str() {
-            "OR" | "AND " | "NOT" => Err(StringStreamError::UnexpectedParse),
+            "OR" | "AND " | "NOT" => Err(StreamErrorFor::<Input>::unexpected_static_message(
+                "operator",
+            )),
             _ => Ok(s),
         })
 }
 
-fn term_val<'a>() -> impl Parser<&'a str, Output = String> {
-    let phrase = char('"').with(many1(satisfy(|c| c != '"'))).skip(char('"'));
-    phrase.or(word())
+/// Function that parses the slop of a phrase, like the `~2` of `"big wolf"~2`.
+///
+/// The slop must be a non-negative integer.
+fn slop<Input>() -> impl Parser<Input, Output = u32>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<char, Input::Range, Input::Position>,
+{
+    char('~')
+        .with(many1(satisfy(|c: char| {
+            !c.is_whitespace() && ![':', '^', '{', '}', '"', '[', ']', '(', ')'].contains(&c)
+        })))
+        .and_then(|slop: String| {
+            slop.parse::<u32>()
+                .map_err(|_| StreamErrorFor::<Input>::expected_static_message("integer slop"))
+        })
 }
 
-fn term_query<'a>() -> impl Parser<&'a str, Output = UserInputLiteral> {
-    let term_val_with_field = negative_number().or(term_val());
-    (field(), term_val_with_field).map(|(field_name, phrase)| UserInputLiteral {
+/// Function that parses a term value, returning the value and its slop.
+///
+/// Only quoted phrases may have a slop. It defaults to `0`.
+fn term_val<Input>() -> impl Parser<Input, Output = (String, u32)>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<char, Input::Range, Input::Position>,
+{
+    let phrase = (
+        char('"').with(many1(satisfy(|c| c != '"'))).skip(char('"')),
+        optional(slop()).map(Option::unwrap_or_default),
+    );
+    phrase.or(word().map(|word| (word, 0)))
+}
+
+fn term_query<Input>() -> impl Parser<Input, Output = UserInputLiteral>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<char, Input::Range, Input::Position>,
+{
+    let term_val_with_field = negative_number().map(|number| (number, 0)).or(term_val());
+    (field(), term_val_with_field).map(|(field_name, (phrase, slop))| UserInputLiteral {
         field_name: Some(field_name),
         phrase,
+        slop,
     })
 }
 
-fn literal<'a>() -> impl Parser<&'a str, Output = UserInputLeaf> {
-    let term_default_field = term_val().map(|phrase| UserInputLiteral {
+/// Function that parses a term query whose field is boosted,
+/// like `title^3:coffee`.
+///
+/// The boost applies to the term query, as if it were written `title:coffee^3`.
+fn field_boosted_term_query<Input>() -> impl Parser<Input, Output = UserInputAST>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<char, Input::Range, Input::Position>,
+{
+    let term_val_with_field = negative_number().map(|number| (number, 0)).or(term_val());
+    (field_name(), boost().skip(char(':')), term_val_with_field).map(
+        |(field_name, boost, (phrase, slop))| {
+            let leaf = UserInputAST::from(UserInputLeaf::from(UserInputLiteral {
+                field_name: Some(field_name),
+                phrase,
+                slop,
+            }));
+            apply_boost(leaf, boost)
+        },
+    )
+}
+
+fn literal<Input>() -> impl Parser<Input, Output = UserInputLeaf>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<char, Input::Range, Input::Position>,
+{
+    let term_default_field = term_val().map(|(phrase, slop)| UserInputLiteral {
         field_name: None,
         phrase,
+        slop,
     });
     attempt(term_query())
         .or(term_default_field)
         .map(UserInputLeaf::from)
 }
 
-fn negative_number<'a>() -> impl Parser<&'a str, Output = String> {
+fn negative_number<Input>() -> impl Parser<Input, Output = String>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<char, Input::Range, Input::Position>,
+{
     (
         char('-'),
         many1(digit()),
@@ -73,7 +155,11 @@ fn negative_number<'a>() -> impl Parser<&'a str, Output = String> {
         })
 }
 
-fn spaces1<'a>() -> impl Parser<&'a str, Output = ()> {
+fn spaces1<Input>() -> impl Parser<Input, Output = ()>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<char, Input::Range, Input::Position>,
+{
     skip_many1(space())
 }
 
@@ -81,7 +167,11 @@ fn spaces1<'a>() -> impl Parser<&'a str, Output = ()> {
 /// Supports ranges like:
 /// [5 TO 10], {5 TO 10}, [* TO 10], [10 TO *], {10 TO *], >5, <=10
 /// [a TO *], [a TO c], [abc TO bcd}
-fn range<'a>() -> impl Parser<&'a str, Output = UserInputLeaf> {
+fn range<Input>() -> impl Parser<Input, Output = UserInputLeaf>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<char, Input::Range, Input::Position>,
+{
     let range_term_val = || {
         word()
             .or(negative_number())
@@ -152,37 +242,73 @@ fn range<'a>() -> impl Parser<&'a str, Output = UserInputLeaf> {
     })
 }
 
+/// Function that parses a wildcard term out of a Stream.
+///
+/// A wildcard term is an unquoted word containing `*` or `?`,
+/// like `prog*` or `te?t`. A lone `*` is not a wildcard term.
+fn wildcard<Input>() -> impl Parser<Input, Output = UserInputLeaf>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<char, Input::Range, Input::Position>,
+{
+    (optional(attempt(field())), word()).and_then(|(field, pattern): (Option<String>, String)| {
+        if pattern != "*" && pattern.contains(&['*', '?'][..]) {
+            Ok(UserInputLeaf::Wildcard { field, pattern })
+        } else {
+            Err(StreamErrorFor::<Input>::expected_static_message("wildcard"))
+        }
+    })
+}
+
 fn negate(expr: UserInputAST) -> UserInputAST {
     expr.unary(Occur::MustNot)
 }
 
-fn leaf<'a>() -> impl Parser<&'a str, Output = UserInputAST> {
+fn leaf<Input>() -> impl Parser<Input, Output = UserInputAST>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<char, Input::Range, Input::Position>,
+{
     parser(|input| {
         char('(')
             .with(ast())
             .skip(char(')'))
-            .or(char('*').map(|_| UserInputAST::from(UserInputLeaf::All)))
             .or(attempt(
                 string("NOT").skip(spaces1()).with(leaf()).map(negate),
             ))
+            .or(attempt(field_boosted_term_query()))
             .or(attempt(range().map(UserInputAST::from)))
+            .or(attempt(wildcard().map(UserInputAST::from)))
+            .or(char('*').map(|_| UserInputAST::from(UserInputLeaf::All)))
             .or(literal().map(UserInputAST::from))
             .parse_stream(input)
             .into_result()
     })
 }
 
-fn occur_symbol<'a>() -> impl Parser<&'a str, Output = Occur> {
+fn occur_symbol<Input>() -> impl Parser<Input, Output = Occur>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<char, Input::Range, Input::Position>,
+{
     char('-')
         .map(|_| Occur::MustNot)
         .or(char('+').map(|_| Occur::Must))
 }
 
-fn occur_leaf<'a>() -> impl Parser<&'a str, Output = (Option<Occur>, UserInputAST)> {
+fn occur_leaf<Input>() -> impl Parser<Input, Output = (Option<Occur>, UserInputAST)>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<char, Input::Range, Input::Position>,
+{
     (optional(occur_symbol()), boosted_leaf())
 }
 
-fn positive_float_number<'a>() -> impl Parser<&'a str, Output = f64> {
+fn positive_float_number<Input>() -> impl Parser<Input, Output = f64>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<char, Input::Range, Input::Position>,
+{
     (many1(digit()), optional((char('.'), many1(digit())))).map(
         |(int_part, decimal_part_opt): (String, Option<(char, String)>)| {
             let mut float_str = int_part;
@@ -195,16 +321,30 @@ fn positive_float_number<'a>() -> impl Parser<&'a str, Output = f64> {
     )
 }
 
-fn boost<'a>() -> impl Parser<&'a str, Output = f64> {
+fn boost<Input>() -> impl Parser<Input, Output = f64>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<char, Input::Range, Input::Position>,
+{
     (char('^'), positive_float_number()).map(|(_, boost)| boost)
 }
 
-fn boosted_leaf<'a>() -> impl Parser<&'a str, Output = UserInputAST> {
+fn apply_boost(ast: UserInputAST, boost: f64) -> UserInputAST {
+    if (boost - 1.0).abs() > f64::EPSILON {
+        UserInputAST::Boost(Box::new(ast), boost)
+    } else {
+        ast
+    }
+}
+
+fn boosted_leaf<Input>() -> impl Parser<Input, Output = UserInputAST>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<char, Input::Range, Input::Position>,
+{
     (leaf(), optional(boost())).map(|(leaf, boost_opt)| match boost_opt {
-        Some(boost) if (boost - 1.0).abs() > std::f64::EPSILON => {
-            UserInputAST::Boost(Box::new(leaf), boost)
-        }
-        _ => leaf,
+        Some(boost) => apply_boost(leaf, boost),
+        None => leaf,
     })
 }
 
@@ -214,7 +354,11 @@ enum BinaryOperand {
     And,
 }
 
-fn binary_operand<'a>() -> impl Parser<&'a str, Output = BinaryOperand> {
+fn binary_operand<Input>() -> impl Parser<Input, Output = BinaryOperand>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<char, Input::Range, Input::Position>,
+{
     string("AND")
         .with(value(BinaryOperand::And))
         .or(string("OR").with(value(BinaryOperand::Or)))
@@ -245,14 +389,22 @@ fn aggregate_binary_expressions(
     }
 }
 
-fn operand_leaf<'a>() -> impl Parser<&'a str, Output = (BinaryOperand, UserInputAST)> {
+fn operand_leaf<Input>() -> impl Parser<Input, Output = (BinaryOperand, UserInputAST)>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<char, Input::Range, Input::Position>,
+{
     (
         binary_operand().skip(spaces()),
         boosted_leaf().skip(spaces()),
     )
 }
 
-pub fn ast<'a>() -> impl Parser<&'a str, Output = UserInputAST> {
+pub fn ast<Input>() -> impl Parser<Input, Output = UserInputAST>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<char, Input::Range, Input::Position>,
+{
     let boolean_expr = (boosted_leaf().skip(spaces()), many1(operand_leaf()))
         .map(|(left, right)| aggregate_binary_expressions(left, right));
     let whitespace_separated_leaves = many1(occur_leaf().skip(spaces().silent())).map(
@@ -272,7 +424,11 @@ pub fn ast<'a>() -> impl Parser<&'a str, Output = UserInputAST> {
     spaces().with(expr).skip(spaces())
 }
 
-pub fn parse_to_ast<'a>() -> impl Parser<&'a str, Output = UserInputAST> {
+pub fn parse_to_ast<Input>() -> impl Parser<Input, Output = UserInputAST>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<char, Input::Range, Input::Position>,
+{
     spaces()
         .with(optional(ast()).skip(eof()))
         .map(|opt_ast| opt_ast.unwrap_or_else(UserInputAST::empty_query))
@@ -284,6 +440,7 @@ mod test {
     type TestParseResult = Result<(), StringStreamError>;
 
     use super::*;
+    use combine::error::StringStreamError;
     use combine::parser::Parser;
 
     pub fn nearly_equals(a: f64, b: f64) -> bool {
@@ -365,6 +522,43 @@ mod test {
         test_parse_query_to_ast_helper("a^1", "\"a\"");
     }
 
+    #[test]
+    fn test_field_boosting() {
+        assert!(parse_to_ast().parse("title^:a").is_err());
+        test_parse_query_to_ast_helper("title^3:a", "(title:\"a\")^3");
+        test_parse_query_to_ast_helper("title^1:a", "title:\"a\"");
+        test_parse_query_to_ast_helper("title^3:\"a b\"", "(title:\"a b\")^3");
+        test_parse_query_to_ast_helper("title^3:a body:a", "(*(title:\"a\")^3 *body:\"a\")");
+        test_parse_query_to_ast_helper("title^3:a^2", "((title:\"a\")^3)^2");
+    }
+
+    #[test]
+    fn test_phrase_slop() {
+        test_parse_query_to_ast_helper("\"a b\"~2", "\"a b\"~2");
+        test_parse_query_to_ast_helper("\"a b\"~0", "\"a b\"");
+        test_parse_query_to_ast_helper("title:\"a b\"~1", "title:\"a b\"~1");
+        test_parse_query_to_ast_helper("title^3:\"a b\"~1", "(title:\"a b\"~1)^3");
+        test_parse_query_to_ast_helper("\"a b\"~2^3", "(\"a b\"~2)^3");
+        test_parse_query_to_ast_helper("(\"a b\"~2)", "\"a b\"~2");
+        test_parse_query_to_ast_helper("a~2", "\"a~2\"");
+        test_is_parse_err("\"a b\"~");
+        test_is_parse_err("\"a b\"~-1");
+        test_is_parse_err("\"a b\"~1.5");
+        test_is_parse_err("\"a b\"~b");
+        test_is_parse_err("title:\"a b\"~-1");
+    }
+
+    #[test]
+    fn test_phrase_slop_round_trip() {
+        for query in &["\"a b\"~2", "title:\"a b c\"~10", "title:\"a b\""] {
+            let ast = parse_to_ast().parse(*query).unwrap().0;
+            let ast_str = format!("{:?}", ast);
+            assert_eq!(&ast_str, query);
+            let reparsed_ast = parse_to_ast().parse(&ast_str[..]).unwrap().0;
+            assert_eq!(format!("{:?}", reparsed_ast), ast_str);
+        }
+    }
+
     #[test]
     fn test_parse_query_to_ast_binary_op() {
         test_parse_query_to_ast_helper("a AND b", "(+\"a\" +\"b\")");
@@ -431,6 +625,10 @@ mod test {
             super::field().parse("_my_field:a")?,
             ("_my_field".to_string(), "a")
         );
+        assert_eq!(
+            super::field().parse("attributes.author.name:a")?,
+            ("attributes.author.name".to_string(), "a")
+        );
         Ok(())
     }
 
@@ -510,6 +708,14 @@ mod test {
         test_parse_query_to_ast_helper("+(a b) +d", "(+(*\"a\" *\"b\") +\"d\")");
     }
 
+    #[test]
+    fn test_parse_query_occur_on_groups() {
+        test_parse_query_to_ast_helper(
+            "+(coffee OR tea) -(milk sugar) cake",
+            "(+(?\"coffee\" ?\"tea\") -(*\"milk\" *\"sugar\") *\"cake\")",
+        );
+    }
+
     #[test]
     fn test_parse_test_query_other() {
         test_parse_query_to_ast_helper("(+a +b) d", "(*(+\"a\" +\"b\") *\"d\")");
@@ -531,4 +737,18 @@ mod test {
         test_parse_query_to_ast_helper("foo:[1.1 TO *}", "foo:[\"1.1\" TO \"*\"}");
         test_is_parse_err("abc +    ");
     }
+
+    #[test]
+    fn test_parse_query_with_wildcard() {
+        test_parse_query_to_ast_helper("prog*", "prog*");
+        test_parse_query_to_ast_helper("te?t", "te?t");
+        test_parse_query_to_ast_helper("*gram", "*gram");
+        test_parse_query_to_ast_helper("title:prog*", "title:prog*");
+        test_parse_query_to_ast_helper("-prog* a", "(-prog* *\"a\")");
+        test_parse_query_to_ast_helper("prog*^2", "(prog*)^2");
+        test_parse_query_to_ast_helper("\"prog*\"", "\"prog*\"");
+        test_parse_query_to_ast_helper("*", "*");
+        test_parse_query_to_ast_helper("title:*", "title:\"*\"");
+        test_parse_query_to_ast_helper("foo:[a TO *}", "foo:[\"a\" TO \"*\"}");
+    }
 }
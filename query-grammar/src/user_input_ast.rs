@@ -12,6 +12,10 @@ pub enum UserInputLeaf {
         lower: UserInputBound,
         upper: UserInputBound,
     },
+    Wildcard {
+        field: Option<String>,
+        pattern: String,
+    },
 }
 
 impl Debug for UserInputLeaf {
@@ -32,6 +36,15 @@ impl Debug for UserInputLeaf {
                 Ok(())
             }
             UserInputLeaf::All => write!(formatter, "*"),
+            UserInputLeaf::Wildcard {
+                ref field,
+                ref pattern,
+            } => {
+                if let Some(ref field) = field {
+                    write!(formatter, "{}:", field)?;
+                }
+                write!(formatter, "{}", pattern)
+            }
         }
     }
 }
@@ -40,14 +53,21 @@ impl Debug for UserInputLeaf {
 pub struct UserInputLiteral {
     pub field_name: Option<String>,
     pub phrase: String,
+    /// Maximum number of positions by which the terms of the phrase
+    /// may be apart from their positions in the phrase.
+    pub slop: u32,
 }
 
 impl fmt::Debug for UserInputLiteral {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         match self.field_name {
-            Some(ref field_name) => write!(formatter, "{}:\"{}\"", field_name, self.phrase),
-            None => write!(formatter, "\"{}\"", self.phrase),
+            Some(ref field_name) => write!(formatter, "{}:\"{}\"", field_name, self.phrase)?,
+            None => write!(formatter, "\"{}\"", self.phrase)?,
+        }
+        if self.slop > 0 {
+            write!(formatter, "~{}", self.slop)?;
         }
+        Ok(())
     }
 }
 
@@ -0,0 +1,383 @@
+use super::has_one_value_at_most;
+use crate::collector::{Collector, SegmentCollector};
+use crate::fastfield::{BytesFastFieldReader, FastFieldReader};
+use crate::schema::{Field, FieldType};
+use crate::{DocId, Score, SegmentLocalId, SegmentReader, TantivyError};
+use roaring::RoaringBitmap;
+use std::hash::Hasher;
+use std::sync::Arc;
+
+const DEFAULT_PRECISION_BITS: u8 = 14;
+const MIN_PRECISION_BITS: u8 = 4;
+const MAX_PRECISION_BITS: u8 = 18;
+
+/// Cardinalities under which the linear counting estimate is used,
+/// for each precision from 4 to 18, as given by the HyperLogLog++ paper.
+const LINEAR_COUNTING_THRESHOLDS: [f64; 15] = [
+    10.0, 20.0, 40.0, 80.0, 220.0, 400.0, 900.0, 1_800.0, 3_100.0, 6_500.0, 11_500.0, 20_000.0,
+    50_000.0, 120_000.0, 350_000.0,
+];
+
+/// Collector estimating the number of distinct values of a fast field
+/// among the matching documents.
+///
+/// The field must be a single-valued numeric fast field (`u64`, `i64`, `f64` or date),
+/// or a bytes fast field. Documents with an empty bytes value are not counted.
+///
+/// The values are hashed into a [HyperLogLog](https://research.google/pubs/pub40671/)
+/// sketch of `2^precision_bits` registers, using 64 bits hashes. Small cardinalities
+/// are estimated by linear counting, as in HyperLogLog++. The sketches of the segments
+/// are merged before computing the estimate.
+///
+/// The relative standard error of the estimate is about `1.04 / sqrt(2^precision_bits)`:
+/// with the default precision of 14 bits, it is `0.8%`, for 16KB of memory per segment.
+///
+/// ```rust
+/// use tantivy::collector::CardinalityAggregation;
+/// use tantivy::query::AllQuery;
+/// use tantivy::schema::{Schema, FAST};
+/// use tantivy::{doc, Index};
+///
+/// # fn main() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let user_id = schema_builder.add_u64_field("user_id", FAST);
+/// let schema = schema_builder.build();
+/// let index = Index::create_in_ram(schema);
+///
+/// let mut index_writer = index.writer(3_000_000)?;
+/// for event_id in 0..1_000u64 {
+///     index_writer.add_document(doc!(user_id => event_id % 100));
+/// }
+/// index_writer.commit()?;
+///
+/// let searcher = index.reader()?.searcher();
+/// let num_users = searcher.search(&AllQuery, &CardinalityAggregation::new(user_id))?;
+/// assert!((95..=105).contains(&num_users));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct CardinalityAggregation {
+    field: Field,
+    precision_bits: u8,
+}
+
+impl CardinalityAggregation {
+    /// Creates a `CardinalityAggregation` over `field`, with a precision of 14 bits.
+    pub fn new(field: Field) -> CardinalityAggregation {
+        CardinalityAggregation {
+            field,
+            precision_bits: DEFAULT_PRECISION_BITS,
+        }
+    }
+
+    /// Sets the number of bits of the hashes used to pick a register of the sketch.
+    ///
+    /// Each additional bit doubles the memory, and divides the error by `sqrt(2)`.
+    /// Returns an error if `precision_bits` is not within `[4, 18]`.
+    pub fn set_precision_bits(mut self, precision_bits: u8) -> crate::Result<Self> {
+        if !(MIN_PRECISION_BITS..=MAX_PRECISION_BITS).contains(&precision_bits) {
+            return Err(TantivyError::InvalidArgument(format!(
+                "The precision of a cardinality aggregation must be within [{}, {}], got {}.",
+                MIN_PRECISION_BITS, MAX_PRECISION_BITS, precision_bits
+            )));
+        }
+        self.precision_bits = precision_bits;
+        Ok(self)
+    }
+}
+
+impl Collector for CardinalityAggregation {
+    type Fruit = u64;
+
+    type Child = CardinalitySegmentCollector;
+
+    fn for_segment(
+        &self,
+        _: SegmentLocalId,
+        reader: &SegmentReader,
+    ) -> crate::Result<CardinalitySegmentCollector> {
+        let reader = HashedFastFieldReader::open(reader, self.field)?;
+        Ok(CardinalitySegmentCollector {
+            reader,
+            sketch: HyperLogLog::new(self.precision_bits),
+        })
+    }
+
+    fn requires_scoring(&self) -> bool {
+        false
+    }
+
+    fn merge_fruits(&self, segment_sketches: Vec<HyperLogLog>) -> crate::Result<u64> {
+        let mut sketch = HyperLogLog::new(self.precision_bits);
+        for segment_sketch in &segment_sketches {
+            sketch.merge(segment_sketch);
+        }
+        Ok(sketch.estimate())
+    }
+}
+
+/// Segment collector of the `CardinalityAggregation`.
+pub struct CardinalitySegmentCollector {
+    reader: HashedFastFieldReader,
+    sketch: HyperLogLog,
+}
+
+impl SegmentCollector for CardinalitySegmentCollector {
+    type Fruit = HyperLogLog;
+
+    fn collect(&mut self, doc: DocId, _: Score) {
+        if let Some(hash) = self.reader.hash(doc) {
+            self.sketch.add_hash(hash);
+        }
+    }
+
+    fn harvest(self) -> HyperLogLog {
+        self.sketch
+    }
+}
+
+enum HashedValues {
+    U64(FastFieldReader<u64>),
+    I64(FastFieldReader<i64>),
+    F64(FastFieldReader<f64>),
+    Date(FastFieldReader<crate::DateTime>),
+    Bytes(BytesFastFieldReader),
+}
+
+/// Reads the values of a fast field as 64 bits hashes.
+struct HashedFastFieldReader {
+    values: HashedValues,
+    // Documents having a value, for optional fast fields.
+    null_bitmap: Option<Arc<RoaringBitmap>>,
+}
+
+impl HashedFastFieldReader {
+    fn open(segment_reader: &SegmentReader, field: Field) -> crate::Result<Self> {
+        let fast_fields = segment_reader.fast_fields();
+        let field_entry = segment_reader.schema().get_field_entry(field);
+        let values = match field_entry.field_type() {
+            FieldType::U64(options) if has_one_value_at_most(options) => {
+                HashedValues::U64(fast_fields.typed_fast_field_reader(field)?)
+            }
+            FieldType::I64(options) if has_one_value_at_most(options) => {
+                HashedValues::I64(fast_fields.typed_fast_field_reader(field)?)
+            }
+            FieldType::F64(options) if has_one_value_at_most(options) => {
+                HashedValues::F64(fast_fields.typed_fast_field_reader(field)?)
+            }
+            FieldType::Date(options) if has_one_value_at_most(options) => {
+                HashedValues::Date(fast_fields.typed_fast_field_reader(field)?)
+            }
+            FieldType::Bytes(_) => HashedValues::Bytes(fast_fields.bytes(field)?),
+            _ => {
+                return Err(TantivyError::SchemaError(format!(
+                    "Field {:?} is not a single-valued numeric or bytes fast field.",
+                    field_entry.name()
+                )))
+            }
+        };
+        Ok(HashedFastFieldReader {
+            values,
+            null_bitmap: fast_fields.null_bitmap(field)?,
+        })
+    }
+
+    fn hash(&self, doc: DocId) -> Option<u64> {
+        if let Some(null_bitmap) = self.null_bitmap.as_ref() {
+            if !null_bitmap.contains(doc) {
+                return None;
+            }
+        }
+        let value = match &self.values {
+            HashedValues::U64(reader) => reader.get(doc),
+            HashedValues::I64(reader) => reader.get(doc) as u64,
+            HashedValues::F64(reader) => reader.get(doc).to_bits(),
+            HashedValues::Date(reader) => reader.get(doc).timestamp() as u64,
+            HashedValues::Bytes(reader) => {
+                let bytes = reader.get_bytes(doc);
+                if bytes.is_empty() {
+                    return None;
+                }
+                let mut hasher = fnv::FnvHasher::default();
+                hasher.write(bytes);
+                hasher.finish()
+            }
+        };
+        Some(mix(value))
+    }
+}
+
+/// Finalizer of splitmix64, spreading the entropy of the value over all of the bits.
+fn mix(value: u64) -> u64 {
+    let mut hash = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    hash = (hash ^ (hash >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    hash = (hash ^ (hash >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    hash ^ (hash >> 31)
+}
+
+/// HyperLogLog sketch of the values collected on a segment by the
+/// [`CardinalityAggregation`](./struct.CardinalityAggregation.html).
+#[derive(Clone, Debug)]
+pub struct HyperLogLog {
+    precision_bits: u8,
+    registers: Vec<u8>,
+}
+
+impl HyperLogLog {
+    fn new(precision_bits: u8) -> HyperLogLog {
+        HyperLogLog {
+            precision_bits,
+            registers: vec![0u8; 1 << precision_bits],
+        }
+    }
+
+    fn add_hash(&mut self, hash: u64) {
+        let register_id = (hash >> (64 - u32::from(self.precision_bits))) as usize;
+        let remaining_bits = 64 - u32::from(self.precision_bits);
+        let rank = ((hash << self.precision_bits).leading_zeros()).min(remaining_bits) + 1;
+        let register = &mut self.registers[register_id];
+        *register = (*register).max(rank as u8);
+    }
+
+    fn merge(&mut self, other: &HyperLogLog) {
+        for (register, &other_register) in self.registers.iter_mut().zip(&other.registers) {
+            *register = (*register).max(other_register);
+        }
+    }
+
+    fn estimate(&self) -> u64 {
+        let num_registers = self.registers.len() as f64;
+        let alpha = match self.registers.len() {
+            16 => 0.673,
+            32 => 0.697,
+            64 => 0.709,
+            _ => 0.7213 / (1.0 + 1.079 / num_registers),
+        };
+        let inverse_sum: f64 = self
+            .registers
+            .iter()
+            .map(|&register| 2f64.powi(-i32::from(register)))
+            .sum();
+        let raw_estimate = alpha * num_registers * num_registers / inverse_sum;
+        let num_empty_registers = self
+            .registers
+            .iter()
+            .filter(|&&register| register == 0)
+            .count();
+        if num_empty_registers > 0 {
+            let linear_counting_estimate =
+                num_registers * (num_registers / num_empty_registers as f64).ln();
+            let threshold =
+                LINEAR_COUNTING_THRESHOLDS[usize::from(self.precision_bits - MIN_PRECISION_BITS)];
+            if linear_counting_estimate <= threshold {
+                return linear_counting_estimate.round() as u64;
+            }
+        }
+        raw_estimate.round() as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{mix, CardinalityAggregation, HyperLogLog};
+    use crate::collector::Count;
+    use crate::query::{AllQuery, TermQuery};
+    use crate::schema::{IndexRecordOption, Schema, FAST, STRING};
+    use crate::{Index, Term};
+
+    fn sketch_of(values: std::ops::Range<u64>, precision_bits: u8) -> HyperLogLog {
+        let mut sketch = HyperLogLog::new(precision_bits);
+        for value in values {
+            sketch.add_hash(mix(value));
+        }
+        sketch
+    }
+
+    fn assert_relative_error(estimate: u64, expected: u64, max_error: f64) {
+        let error = (estimate as f64 - expected as f64).abs() / expected as f64;
+        assert!(
+            error <= max_error,
+            "estimate={} expected={}",
+            estimate,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_hyperloglog_accuracy() {
+        assert_eq!(HyperLogLog::new(14).estimate(), 0);
+        assert_eq!(sketch_of(0..1, 14).estimate(), 1);
+        assert_relative_error(sketch_of(0..1_000, 14).estimate(), 1_000, 0.01);
+        for &num_values in &[50_000, 200_000, 1_000_000] {
+            // Three times the standard error of 0.8%.
+            assert_relative_error(sketch_of(0..num_values, 14).estimate(), num_values, 0.025);
+        }
+        assert_relative_error(sketch_of(0..200_000, 18).estimate(), 200_000, 0.01);
+        assert_relative_error(sketch_of(0..10_000, 4).estimate(), 10_000, 0.8);
+    }
+
+    #[test]
+    fn test_hyperloglog_merge() {
+        let mut sketch = sketch_of(0..60_000, 14);
+        sketch.merge(&sketch_of(40_000..100_000, 14));
+        assert_relative_error(sketch.estimate(), 100_000, 0.025);
+        let mut sketch = sketch_of(0..50_000, 14);
+        sketch.merge(&sketch_of(0..50_000, 14));
+        assert_eq!(sketch.estimate(), sketch_of(0..50_000, 14).estimate());
+    }
+
+    #[test]
+    fn test_cardinality_aggregation() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let country = schema_builder.add_text_field("country", STRING);
+        let user_id = schema_builder.add_i64_field("user_id", FAST);
+        let session = schema_builder.add_bytes_field("session", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        for i in 0..10i64 {
+            index_writer.add_document(doc!(
+                country => "fr",
+                user_id => -(i % 4),
+                session => format!("session-{}", i % 6).into_bytes()
+            ));
+            if i == 4 {
+                index_writer.commit()?;
+            }
+        }
+        index_writer.add_document(doc!(country => "de", user_id => 100i64, session => Vec::new()));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 2);
+
+        let france = TermQuery::new(
+            Term::from_field_text(country, "fr"),
+            IndexRecordOption::Basic,
+        );
+        let (count, num_users) =
+            searcher.search(&france, &(Count, CardinalityAggregation::new(user_id)))?;
+        assert_eq!(count, 10);
+        assert_eq!(num_users, 4);
+        assert_eq!(
+            searcher.search(&AllQuery, &CardinalityAggregation::new(user_id))?,
+            5
+        );
+        let sessions = CardinalityAggregation::new(session).set_precision_bits(10)?;
+        assert_eq!(searcher.search(&AllQuery, &sessions)?, 6);
+        assert!(searcher
+            .search(&AllQuery, &CardinalityAggregation::new(country))
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_cardinality_aggregation_invalid_precision() {
+        let mut schema_builder = Schema::builder();
+        let user_id = schema_builder.add_u64_field("user_id", FAST);
+        let aggregation = CardinalityAggregation::new(user_id);
+        assert!(aggregation.clone().set_precision_bits(3).is_err());
+        assert!(aggregation.clone().set_precision_bits(19).is_err());
+        assert!(aggregation.clone().set_precision_bits(4).is_ok());
+        assert!(aggregation.set_precision_bits(18).is_ok());
+    }
+}
@@ -0,0 +1,446 @@
+use super::NumericFastFieldReader;
+use crate::collector::{
+    AggregationResult, Collector, MetricAggregation, MetricStats, SegmentCollector,
+};
+use crate::schema::Field;
+use crate::{DocId, Score, SegmentLocalId, SegmentReader};
+use chrono::{Datelike, FixedOffset, NaiveDate};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+const SECONDS_PER_HOUR: i64 = 3_600;
+const SECONDS_PER_DAY: i64 = 24 * SECONDS_PER_HOUR;
+const SECONDS_PER_WEEK: i64 = 7 * SECONDS_PER_DAY;
+// The 1st of January 1970 was a Thursday, 3 days after the start of its week.
+const EPOCH_DAYS_SINCE_MONDAY: i64 = 3;
+const EPOCH_DAYS_FROM_CE: i64 = 719_163;
+
+/// Calendar interval of the buckets of a
+/// [`DateHistogramCollector`](./struct.DateHistogramCollector.html).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DateInterval {
+    /// Calendar years.
+    Year,
+    /// Calendar quarters, starting in January, April, July and October.
+    Quarter,
+    /// Calendar months.
+    Month,
+    /// Weeks, starting on Monday.
+    Week,
+    /// Days.
+    Day,
+    /// Hours.
+    Hour,
+}
+
+/// A bucket of the [`DateHistogramCollector`](./struct.DateHistogramCollector.html).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DateBucket {
+    /// Start of the bucket, as a Unix timestamp in seconds.
+    pub timestamp_secs: i64,
+    /// Number of matching documents whose date falls in the bucket.
+    pub count: u64,
+    /// Result of the sub-aggregation over the documents of the bucket, if the collector
+    /// has one. (See [`DateHistogramCollector::with_sub_aggregation`](./struct.DateHistogramCollector.html#method.with_sub_aggregation))
+    pub sub_result: Option<AggregationResult>,
+}
+
+/// Collector counting the matching documents in calendar intervals
+/// of a date fast field.
+///
+/// The field must be a single-valued date fast field, or a numeric fast field
+/// holding Unix timestamps in seconds.
+///
+/// The buckets follow the calendar of the time zone given with
+/// [`set_timezone`](#method.set_timezone), UTC by default: with an offset of `+02:00`,
+/// the daily buckets start at midnight in this time zone, that is at 22:00 UTC.
+/// The buckets are sorted by timestamp, and go from the bucket of the oldest date
+/// to the bucket of the most recent date, including the empty buckets in between.
+///
+/// A [`MetricAggregation`](./enum.MetricAggregation.html) can also be computed
+/// over the documents of each bucket with
+/// [`with_sub_aggregation`](#method.with_sub_aggregation).
+///
+/// ```rust
+/// use tantivy::collector::{DateBucket, DateHistogramCollector, DateInterval};
+/// use tantivy::query::AllQuery;
+/// use tantivy::schema::{Schema, FAST};
+/// use tantivy::{doc, DateTime, Index};
+/// use std::str::FromStr;
+///
+/// # fn main() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let timestamp = schema_builder.add_date_field("timestamp", FAST);
+/// let schema = schema_builder.build();
+/// let index = Index::create_in_ram(schema);
+///
+/// let mut index_writer = index.writer(3_000_000)?;
+/// for date in &["2021-01-30T10:00:00Z", "2021-01-31T23:00:00Z", "2021-03-02T08:00:00Z"] {
+///     index_writer.add_document(doc!(timestamp => DateTime::from_str(date).unwrap()));
+/// }
+/// index_writer.commit()?;
+///
+/// let searcher = index.reader()?.searcher();
+/// let date_histogram = DateHistogramCollector::new(timestamp, DateInterval::Month);
+/// let buckets: Vec<DateBucket> = searcher.search(&AllQuery, &date_histogram)?;
+/// assert_eq!(
+///     buckets
+///         .iter()
+///         .map(|bucket| (bucket.timestamp_secs, bucket.count))
+///         .collect::<Vec<_>>(),
+///     vec![
+///         (1_609_459_200, 2), // 2021-01
+///         (1_612_137_600, 0), // 2021-02
+///         (1_614_556_800, 1), // 2021-03
+///     ]
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct DateHistogramCollector {
+    field: Field,
+    interval: DateInterval,
+    timezone: FixedOffset,
+    sub_aggregation: Option<MetricAggregation>,
+}
+
+impl DateHistogramCollector {
+    /// Creates a `DateHistogramCollector` over `field`, with buckets of `interval`
+    /// in UTC.
+    pub fn new(field: Field, interval: DateInterval) -> DateHistogramCollector {
+        DateHistogramCollector {
+            field,
+            interval,
+            timezone: FixedOffset::east_opt(0).unwrap(),
+            sub_aggregation: None,
+        }
+    }
+
+    /// Sets the time zone whose calendar defines the bucket boundaries.
+    pub fn set_timezone(mut self, timezone: FixedOffset) -> DateHistogramCollector {
+        self.timezone = timezone;
+        self
+    }
+
+    /// Computes `aggregation` over the documents of each bucket.
+    ///
+    /// Its result is returned in the `sub_result` of the buckets, including
+    /// the empty ones: their average, minimum and maximum are then `NaN`.
+    pub fn with_sub_aggregation(
+        mut self,
+        aggregation: MetricAggregation,
+    ) -> DateHistogramCollector {
+        self.sub_aggregation = Some(aggregation);
+        self
+    }
+
+    fn offset_secs(&self) -> i64 {
+        i64::from(self.timezone.local_minus_utc())
+    }
+
+    /// Returns the id of the bucket of the timestamp, or `None` if
+    /// the timestamp is outside of the supported range of dates.
+    fn bucket_id(&self, timestamp_secs: i64) -> Option<i64> {
+        let local_secs = timestamp_secs.checked_add(self.offset_secs())?;
+        let bucket_id = match self.interval {
+            DateInterval::Hour => local_secs.div_euclid(SECONDS_PER_HOUR),
+            DateInterval::Day => local_secs.div_euclid(SECONDS_PER_DAY),
+            DateInterval::Week => local_secs
+                .checked_add(EPOCH_DAYS_SINCE_MONDAY * SECONDS_PER_DAY)?
+                .div_euclid(SECONDS_PER_WEEK),
+            DateInterval::Month | DateInterval::Quarter | DateInterval::Year => {
+                let days_from_ce = local_secs
+                    .div_euclid(SECONDS_PER_DAY)
+                    .checked_add(EPOCH_DAYS_FROM_CE)?;
+                let local_date =
+                    NaiveDate::from_num_days_from_ce_opt(i32::try_from(days_from_ce).ok()?)?;
+                let year = i64::from(local_date.year());
+                let month0 = i64::from(local_date.month0());
+                match self.interval {
+                    DateInterval::Month => year * 12 + month0,
+                    DateInterval::Quarter => year * 4 + month0 / 3,
+                    _ => year,
+                }
+            }
+        };
+        Some(bucket_id)
+    }
+
+    /// Returns the start of the bucket, as a Unix timestamp in seconds.
+    fn bucket_timestamp_secs(&self, bucket_id: i64) -> i64 {
+        let local_secs = match self.interval {
+            DateInterval::Hour => bucket_id * SECONDS_PER_HOUR,
+            DateInterval::Day => bucket_id * SECONDS_PER_DAY,
+            DateInterval::Week => {
+                bucket_id * SECONDS_PER_WEEK - EPOCH_DAYS_SINCE_MONDAY * SECONDS_PER_DAY
+            }
+            DateInterval::Month | DateInterval::Quarter | DateInterval::Year => {
+                let (year, month0) = match self.interval {
+                    DateInterval::Month => (bucket_id.div_euclid(12), bucket_id.rem_euclid(12)),
+                    DateInterval::Quarter => (bucket_id.div_euclid(4), bucket_id.rem_euclid(4) * 3),
+                    _ => (bucket_id, 0),
+                };
+                let bucket_date = NaiveDate::from_ymd_opt(year as i32, month0 as u32 + 1, 1)
+                    .expect("Bucket ids are only built from valid dates.");
+                (i64::from(bucket_date.num_days_from_ce()) - EPOCH_DAYS_FROM_CE) * SECONDS_PER_DAY
+            }
+        };
+        local_secs - self.offset_secs()
+    }
+}
+
+impl Collector for DateHistogramCollector {
+    type Fruit = Vec<DateBucket>;
+
+    type Child = DateHistogramSegmentCollector;
+
+    fn for_segment(
+        &self,
+        _: SegmentLocalId,
+        reader: &SegmentReader,
+    ) -> crate::Result<DateHistogramSegmentCollector> {
+        let sub_aggregation_reader = self
+            .sub_aggregation
+            .and_then(|aggregation| aggregation.field())
+            .map(|field| NumericFastFieldReader::open(reader, field))
+            .transpose()?;
+        let reader = NumericFastFieldReader::open(reader, self.field)?;
+        Ok(DateHistogramSegmentCollector {
+            date_histogram: self.clone(),
+            reader,
+            sub_aggregation_reader,
+            buckets: HashMap::new(),
+        })
+    }
+
+    fn requires_scoring(&self) -> bool {
+        false
+    }
+
+    fn merge_fruits(
+        &self,
+        segment_buckets: Vec<HashMap<i64, (u64, MetricStats)>>,
+    ) -> crate::Result<Vec<DateBucket>> {
+        let mut buckets: HashMap<i64, (u64, MetricStats)> = HashMap::new();
+        for segment_buckets in segment_buckets {
+            for (bucket_id, (count, stats)) in segment_buckets {
+                let bucket = buckets.entry(bucket_id).or_default();
+                bucket.0 += count;
+                bucket.1.merge(&stats);
+            }
+        }
+        let (first_bucket_id, last_bucket_id) = match (buckets.keys().min(), buckets.keys().max()) {
+            (Some(&first_bucket_id), Some(&last_bucket_id)) => (first_bucket_id, last_bucket_id),
+            _ => return Ok(Vec::new()),
+        };
+        Ok((first_bucket_id..=last_bucket_id)
+            .map(|bucket_id| {
+                let (count, stats) = buckets.get(&bucket_id).cloned().unwrap_or_default();
+                DateBucket {
+                    timestamp_secs: self.bucket_timestamp_secs(bucket_id),
+                    count,
+                    sub_result: self
+                        .sub_aggregation
+                        .map(|aggregation| stats.result(&aggregation)),
+                }
+            })
+            .collect())
+    }
+}
+
+/// Segment collector of the `DateHistogramCollector`.
+///
+/// For each bucket, it keeps the number of documents and
+/// the statistics of the sub-aggregation.
+pub struct DateHistogramSegmentCollector {
+    date_histogram: DateHistogramCollector,
+    reader: NumericFastFieldReader,
+    sub_aggregation_reader: Option<NumericFastFieldReader>,
+    buckets: HashMap<i64, (u64, MetricStats)>,
+}
+
+impl SegmentCollector for DateHistogramSegmentCollector {
+    type Fruit = HashMap<i64, (u64, MetricStats)>;
+
+    fn collect(&mut self, doc: DocId, _: Score) {
+        let value = match self.reader.get(doc) {
+            Some(value) if !value.is_nan() => value,
+            _ => return,
+        };
+        if let Some(bucket_id) = self.date_histogram.bucket_id(value.floor() as i64) {
+            let (count, stats) = self.buckets.entry(bucket_id).or_default();
+            *count += 1;
+            if self.date_histogram.sub_aggregation.is_some() {
+                let sub_value = match self.sub_aggregation_reader.as_ref() {
+                    Some(reader) => reader.get(doc),
+                    None => Some(0.0),
+                };
+                if let Some(sub_value) = sub_value {
+                    stats.collect(sub_value);
+                }
+            }
+        }
+    }
+
+    fn harvest(self) -> HashMap<i64, (u64, MetricStats)> {
+        self.buckets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DateBucket, DateHistogramCollector, DateInterval};
+    use crate::collector::{Count, MetricAggregation};
+    use crate::query::{AllQuery, TermQuery};
+    use crate::schema::{IndexRecordOption, Schema, FAST, STRING};
+    use crate::{DateTime, Index, Term};
+    use chrono::FixedOffset;
+    use std::str::FromStr;
+
+    fn timestamp(date: &str) -> i64 {
+        DateTime::from_str(date).unwrap().timestamp()
+    }
+
+    fn bucket_timestamps(
+        date_histogram: &DateHistogramCollector,
+        dates: &[&str],
+    ) -> Vec<(i64, i64)> {
+        dates
+            .iter()
+            .map(|date| {
+                let bucket_id = date_histogram.bucket_id(timestamp(date)).unwrap();
+                (bucket_id, date_histogram.bucket_timestamp_secs(bucket_id))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_date_histogram_intervals() {
+        let field = Schema::builder().add_date_field("date", FAST);
+        let date = "2021-05-19T13:45:30Z";
+        let expected_starts = [
+            (DateInterval::Year, "2021-01-01T00:00:00Z"),
+            (DateInterval::Quarter, "2021-04-01T00:00:00Z"),
+            (DateInterval::Month, "2021-05-01T00:00:00Z"),
+            (DateInterval::Week, "2021-05-17T00:00:00Z"),
+            (DateInterval::Day, "2021-05-19T00:00:00Z"),
+            (DateInterval::Hour, "2021-05-19T13:00:00Z"),
+        ];
+        for &(interval, expected_start) in &expected_starts {
+            let date_histogram = DateHistogramCollector::new(field, interval);
+            let (_, start) = bucket_timestamps(&date_histogram, &[date])[0];
+            assert_eq!(start, timestamp(expected_start), "{:?}", interval);
+        }
+        let date_histogram = DateHistogramCollector::new(field, DateInterval::Week);
+        let (_, start) = bucket_timestamps(&date_histogram, &["1969-12-28T12:00:00Z"])[0];
+        assert_eq!(start, timestamp("1969-12-22T00:00:00Z"));
+    }
+
+    #[test]
+    fn test_date_histogram_timezone() {
+        let field = Schema::builder().add_date_field("date", FAST);
+        let date_histogram = DateHistogramCollector::new(field, DateInterval::Day)
+            .set_timezone(FixedOffset::east_opt(2 * 3600).unwrap());
+        let buckets = bucket_timestamps(
+            &date_histogram,
+            &["2021-05-18T21:59:59Z", "2021-05-18T22:00:00Z"],
+        );
+        assert_eq!(buckets[0].1, timestamp("2021-05-17T22:00:00Z"));
+        assert_eq!(buckets[1].1, timestamp("2021-05-18T22:00:00Z"));
+        assert_eq!(buckets[1].0, buckets[0].0 + 1);
+
+        let date_histogram = DateHistogramCollector::new(field, DateInterval::Year)
+            .set_timezone(FixedOffset::west_opt(5 * 3600).unwrap());
+        let buckets = bucket_timestamps(&date_histogram, &["2021-01-01T03:00:00Z"]);
+        assert_eq!(buckets[0].1, timestamp("2020-01-01T05:00:00Z"));
+    }
+
+    #[test]
+    fn test_date_histogram_collector() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let level = schema_builder.add_text_field("level", STRING);
+        let date = schema_builder.add_date_field("date", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        let log = |level_value: &str, date_value: &str| doc!(level => level_value, date => DateTime::from_str(date_value).unwrap());
+        index_writer.add_document(log("error", "2021-05-19T13:45:30Z"));
+        index_writer.add_document(log("info", "2021-05-19T14:10:00Z"));
+        index_writer.commit()?;
+        index_writer.add_document(log("error", "2021-05-19T13:01:00Z"));
+        index_writer.add_document(log("error", "2021-05-19T16:00:00Z"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 2);
+
+        let date_histogram = DateHistogramCollector::new(date, DateInterval::Hour);
+        let errors = TermQuery::new(
+            Term::from_field_text(level, "error"),
+            IndexRecordOption::Basic,
+        );
+        let (count, error_buckets) = searcher.search(&errors, &(Count, date_histogram.clone()))?;
+        assert_eq!(count, 3);
+        let bucket = |date_value: &str, count: u64| DateBucket {
+            timestamp_secs: timestamp(date_value),
+            count,
+            sub_result: None,
+        };
+        assert_eq!(
+            error_buckets,
+            vec![
+                bucket("2021-05-19T13:00:00Z", 2),
+                bucket("2021-05-19T14:00:00Z", 0),
+                bucket("2021-05-19T15:00:00Z", 0),
+                bucket("2021-05-19T16:00:00Z", 1),
+            ]
+        );
+        assert_eq!(
+            searcher.search(&AllQuery, &date_histogram)?[1],
+            bucket("2021-05-19T14:00:00Z", 1)
+        );
+        let empty_index = Index::create_in_ram(index.schema());
+        assert!(empty_index
+            .reader()?
+            .searcher()
+            .search(&AllQuery, &date_histogram)?
+            .is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_date_histogram_sub_aggregation() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let date = schema_builder.add_date_field("date", FAST);
+        let latency = schema_builder.add_u64_field("latency", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        let request = |date_value: &str, latency_value: u64| doc!(date => DateTime::from_str(date_value).unwrap(), latency => latency_value);
+        index_writer.add_document(request("2021-05-17T10:00:00Z", 10));
+        index_writer.add_document(request("2021-05-17T23:00:00Z", 30));
+        index_writer.commit()?;
+        index_writer.add_document(request("2021-05-17T12:00:00Z", 50));
+        index_writer.add_document(request("2021-05-19T08:00:00Z", 7));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 2);
+
+        let date_histogram = DateHistogramCollector::new(date, DateInterval::Day)
+            .with_sub_aggregation(MetricAggregation::Avg(latency));
+        let buckets = searcher.search(&AllQuery, &date_histogram)?;
+        assert_eq!(buckets.len(), 3);
+        assert_eq!(buckets[0].timestamp_secs, timestamp("2021-05-17T00:00:00Z"));
+        assert_eq!(buckets[0].count, 3);
+        assert_eq!(buckets[0].sub_result.unwrap().value, 30.0);
+        assert_eq!(buckets[1].count, 0);
+        assert!(buckets[1].sub_result.unwrap().value.is_nan());
+        assert_eq!(buckets[2].count, 1);
+        assert_eq!(buckets[2].sub_result.unwrap().value, 7.0);
+
+        let date_histogram = DateHistogramCollector::new(date, DateInterval::Week)
+            .with_sub_aggregation(MetricAggregation::Count);
+        let buckets = searcher.search(&AllQuery, &date_histogram)?;
+        assert_eq!(buckets.len(), 1);
+        assert_eq!(buckets[0].sub_result.unwrap().value, 4.0);
+        Ok(())
+    }
+}
@@ -0,0 +1,266 @@
+use super::NumericFastFieldReader;
+use crate::collector::{Collector, SegmentCollector};
+use crate::schema::Field;
+use crate::{DocId, Score, SegmentLocalId, SegmentReader, TantivyError};
+use std::collections::HashMap;
+
+/// A bucket of the [`HistogramAggregation`](./struct.HistogramAggregation.html).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HistogramBucket {
+    /// Lower bound of the bucket. The bucket contains the values
+    /// in `[key, key + interval)`.
+    pub key: f64,
+    /// Number of matching documents whose value falls in the bucket.
+    pub count: u64,
+}
+
+/// Collector counting the matching documents in equal-width buckets
+/// of the values of a numeric fast field.
+///
+/// The field must be a single-valued numeric fast field (`u64`, `i64`, `f64` or date).
+/// Dates are bucketed by timestamp, in seconds.
+///
+/// The value `v` falls in the bucket whose key is `floor(v / interval) * interval`.
+/// The buckets are sorted by key, and go from the bucket of the smallest value
+/// to the bucket of the largest value, including the empty buckets in between.
+/// The range of the buckets can be widened with
+/// [`set_extended_bounds`](#method.set_extended_bounds).
+///
+/// ```rust
+/// use tantivy::collector::{HistogramAggregation, HistogramBucket};
+/// use tantivy::query::AllQuery;
+/// use tantivy::schema::{Schema, FAST};
+/// use tantivy::{doc, Index};
+///
+/// # fn main() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let price = schema_builder.add_f64_field("price", FAST);
+/// let schema = schema_builder.build();
+/// let index = Index::create_in_ram(schema);
+///
+/// let mut index_writer = index.writer(3_000_000)?;
+/// index_writer.add_document(doc!(price => 3.0));
+/// index_writer.add_document(doc!(price => 7.5));
+/// index_writer.add_document(doc!(price => 28.0));
+/// index_writer.commit()?;
+///
+/// let searcher = index.reader()?.searcher();
+/// let histogram = HistogramAggregation::new(price, 10.0)?;
+/// let buckets = searcher.search(&AllQuery, &histogram)?;
+/// assert_eq!(
+///     buckets,
+///     vec![
+///         HistogramBucket { key: 0.0, count: 2 },
+///         HistogramBucket { key: 10.0, count: 0 },
+///         HistogramBucket { key: 20.0, count: 1 },
+///     ]
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct HistogramAggregation {
+    field: Field,
+    interval: f64,
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+impl HistogramAggregation {
+    /// Creates a `HistogramAggregation` over `field`, with buckets of width `interval`.
+    ///
+    /// Returns an error if `interval` is not strictly positive.
+    pub fn new(field: Field, interval: f64) -> crate::Result<HistogramAggregation> {
+        if !(interval > 0.0 && interval.is_finite()) {
+            return Err(TantivyError::InvalidArgument(format!(
+                "The interval of a histogram must be positive, got {}.",
+                interval
+            )));
+        }
+        Ok(HistogramAggregation {
+            field,
+            interval,
+            min: None,
+            max: None,
+        })
+    }
+
+    /// Extends the range of the buckets, so that it covers `[min, max]`,
+    /// even if no document has a value at its ends.
+    ///
+    /// The buckets with no documents between `min` and `max` are returned
+    /// with a count of 0. The range of the buckets is not restricted to
+    /// `[min, max]`: buckets outside of it are returned if some documents fall in them.
+    pub fn set_extended_bounds(mut self, min: f64, max: f64) -> HistogramAggregation {
+        self.min = Some(min);
+        self.max = Some(max);
+        self
+    }
+
+    fn bucket_id(&self, value: f64) -> i64 {
+        (value / self.interval).floor() as i64
+    }
+}
+
+impl Collector for HistogramAggregation {
+    type Fruit = Vec<HistogramBucket>;
+
+    type Child = HistogramAggregationSegmentCollector;
+
+    fn for_segment(
+        &self,
+        _: SegmentLocalId,
+        reader: &SegmentReader,
+    ) -> crate::Result<HistogramAggregationSegmentCollector> {
+        let reader = NumericFastFieldReader::open(reader, self.field)?;
+        Ok(HistogramAggregationSegmentCollector {
+            histogram: self.clone(),
+            reader,
+            counts: HashMap::new(),
+        })
+    }
+
+    fn requires_scoring(&self) -> bool {
+        false
+    }
+
+    fn merge_fruits(
+        &self,
+        segment_counts: Vec<HashMap<i64, u64>>,
+    ) -> crate::Result<Vec<HistogramBucket>> {
+        let mut counts: HashMap<i64, u64> = HashMap::new();
+        for segment_counts in segment_counts {
+            for (bucket_id, count) in segment_counts {
+                *counts.entry(bucket_id).or_insert(0) += count;
+            }
+        }
+        let bucket_ids = counts
+            .keys()
+            .cloned()
+            .chain(self.min.map(|min| self.bucket_id(min)))
+            .chain(self.max.map(|max| self.bucket_id(max)));
+        let first_bucket_id = bucket_ids.clone().min();
+        let last_bucket_id = bucket_ids.max();
+        let (first_bucket_id, last_bucket_id) = match (first_bucket_id, last_bucket_id) {
+            (Some(first_bucket_id), Some(last_bucket_id)) => (first_bucket_id, last_bucket_id),
+            _ => return Ok(Vec::new()),
+        };
+        Ok((first_bucket_id..=last_bucket_id)
+            .map(|bucket_id| HistogramBucket {
+                key: bucket_id as f64 * self.interval,
+                count: counts.get(&bucket_id).cloned().unwrap_or(0),
+            })
+            .collect())
+    }
+}
+
+/// Segment collector of the `HistogramAggregation`.
+pub struct HistogramAggregationSegmentCollector {
+    histogram: HistogramAggregation,
+    reader: NumericFastFieldReader,
+    counts: HashMap<i64, u64>,
+}
+
+impl SegmentCollector for HistogramAggregationSegmentCollector {
+    type Fruit = HashMap<i64, u64>;
+
+    fn collect(&mut self, doc: DocId, _: Score) {
+        let value = match self.reader.get(doc) {
+            Some(value) if !value.is_nan() => value,
+            _ => return,
+        };
+        let bucket_id = self.histogram.bucket_id(value);
+        *self.counts.entry(bucket_id).or_insert(0) += 1;
+    }
+
+    fn harvest(self) -> HashMap<i64, u64> {
+        self.counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HistogramAggregation, HistogramBucket};
+    use crate::collector::Count;
+    use crate::query::{AllQuery, TermQuery};
+    use crate::schema::{IndexRecordOption, Schema, FAST, STRING};
+    use crate::{Index, Term};
+
+    fn buckets(keys_and_counts: &[(f64, u64)]) -> Vec<HistogramBucket> {
+        keys_and_counts
+            .iter()
+            .map(|&(key, count)| HistogramBucket { key, count })
+            .collect()
+    }
+
+    #[test]
+    fn test_histogram_aggregation() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let color = schema_builder.add_text_field("color", STRING);
+        let balance = schema_builder.add_i64_field("balance", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(color => "red", balance => -3i64));
+        index_writer.add_document(doc!(color => "red", balance => 4i64));
+        index_writer.add_document(doc!(color => "blue", balance => 7i64));
+        index_writer.commit()?;
+        index_writer.add_document(doc!(color => "red", balance => 5i64));
+        index_writer.add_document(doc!(color => "red", balance => 16i64));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 2);
+        let red = TermQuery::new(
+            Term::from_field_text(color, "red"),
+            IndexRecordOption::Basic,
+        );
+        let histogram = HistogramAggregation::new(balance, 5.0)?;
+        let (count, red_buckets) = searcher.search(&red, &(Count, histogram.clone()))?;
+        assert_eq!(count, 4);
+        assert_eq!(
+            red_buckets,
+            buckets(&[(-5.0, 1), (0.0, 1), (5.0, 1), (10.0, 0), (15.0, 1)])
+        );
+        assert_eq!(
+            searcher.search(&AllQuery, &histogram)?,
+            buckets(&[(-5.0, 1), (0.0, 1), (5.0, 2), (10.0, 0), (15.0, 1)])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_histogram_aggregation_extended_bounds() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let price = schema_builder.add_f64_field("price", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(price => 2.5f64));
+        index_writer.add_document(doc!(price => 31.0f64));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let histogram = HistogramAggregation::new(price, 10.0)?.set_extended_bounds(-10.0, 20.0);
+        assert_eq!(
+            searcher.search(&AllQuery, &histogram)?,
+            buckets(&[(-10.0, 0), (0.0, 1), (10.0, 0), (20.0, 0), (30.0, 1)])
+        );
+        let empty_index = Index::create_in_ram(index.schema());
+        let empty_searcher = empty_index.reader()?.searcher();
+        assert!(empty_searcher
+            .search(&AllQuery, &HistogramAggregation::new(price, 10.0)?)?
+            .is_empty());
+        assert_eq!(
+            empty_searcher.search(&AllQuery, &histogram)?,
+            buckets(&[(-10.0, 0), (0.0, 0), (10.0, 0), (20.0, 0)])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_histogram_aggregation_invalid_interval() {
+        let mut schema_builder = Schema::builder();
+        let price = schema_builder.add_f64_field("price", FAST);
+        assert!(HistogramAggregation::new(price, 0.0).is_err());
+        assert!(HistogramAggregation::new(price, -1.0).is_err());
+        assert!(HistogramAggregation::new(price, f64::NAN).is_err());
+        assert!(HistogramAggregation::new(price, 0.5).is_ok());
+    }
+}
@@ -0,0 +1,393 @@
+use super::NumericFastFieldReader;
+use crate::collector::{Collector, SegmentCollector};
+use crate::schema::Field;
+use crate::{DocId, Score, SegmentLocalId, SegmentReader};
+use std::collections::HashMap;
+
+/// A metric computed by the [`AggregationCollector`](./struct.AggregationCollector.html).
+///
+/// The fields must be single-valued numeric fast fields (`u64`, `i64`, `f64` or date).
+/// Dates are aggregated as timestamps, in seconds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MetricAggregation {
+    /// Sum of the values of the field.
+    Sum(Field),
+    /// Smallest value of the field.
+    Min(Field),
+    /// Largest value of the field.
+    Max(Field),
+    /// Average of the values of the field.
+    Avg(Field),
+    /// Number of matching documents.
+    Count,
+}
+
+impl MetricAggregation {
+    pub(crate) fn field(&self) -> Option<Field> {
+        match *self {
+            MetricAggregation::Sum(field)
+            | MetricAggregation::Min(field)
+            | MetricAggregation::Max(field)
+            | MetricAggregation::Avg(field) => Some(field),
+            MetricAggregation::Count => None,
+        }
+    }
+}
+
+/// The result of a metric aggregation.
+///
+/// The `value` of the minimum, the maximum and the average
+/// is `NaN` when no document matched.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AggregationResult {
+    /// Value of the metric.
+    pub value: f64,
+}
+
+/// Statistics collected on a segment for one metric aggregation.
+///
+/// They are merged across segments before computing the
+/// final [`AggregationResult`](./struct.AggregationResult.html).
+#[derive(Clone, Copy, Debug)]
+pub struct MetricStats {
+    count: u64,
+    sum: f64,
+    min: f64,
+    max: f64,
+}
+
+impl Default for MetricStats {
+    fn default() -> MetricStats {
+        MetricStats {
+            count: 0,
+            sum: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+}
+
+impl MetricStats {
+    pub(crate) fn collect(&mut self, value: f64) {
+        self.count += 1;
+        self.sum += value;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+    }
+
+    pub(crate) fn merge(&mut self, other: &MetricStats) {
+        self.count += other.count;
+        self.sum += other.sum;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+
+    pub(crate) fn result(&self, aggregation: &MetricAggregation) -> AggregationResult {
+        let value = match aggregation {
+            MetricAggregation::Count => self.count as f64,
+            MetricAggregation::Sum(_) => self.sum,
+            _ if self.count == 0 => f64::NAN,
+            MetricAggregation::Min(_) => self.min,
+            MetricAggregation::Max(_) => self.max,
+            MetricAggregation::Avg(_) => self.sum / self.count as f64,
+        };
+        AggregationResult { value }
+    }
+}
+
+/// Collector computing some named metrics over the matching documents.
+///
+/// The values are read from fast fields.
+/// The results are returned in a `HashMap`, keyed by the name of the aggregations.
+///
+/// ```rust
+/// use tantivy::collector::{AggregationCollector, MetricAggregation, TopDocs};
+/// use tantivy::query::QueryParser;
+/// use tantivy::schema::{Schema, FAST, TEXT};
+/// use tantivy::{doc, Index};
+///
+/// # fn main() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let title = schema_builder.add_text_field("title", TEXT);
+/// let price = schema_builder.add_f64_field("price", FAST);
+/// let schema = schema_builder.build();
+/// let index = Index::create_in_ram(schema);
+///
+/// let mut index_writer = index.writer(3_000_000)?;
+/// index_writer.add_document(doc!(title => "Red sofa", price => 300.0));
+/// index_writer.add_document(doc!(title => "Blue sofa", price => 500.0));
+/// index_writer.add_document(doc!(title => "Red chair", price => 50.0));
+/// index_writer.commit()?;
+///
+/// let searcher = index.reader()?.searcher();
+/// let query = QueryParser::for_index(&index, vec![title]).parse_query("sofa")?;
+///
+/// let mut aggregation_collector = AggregationCollector::default();
+/// aggregation_collector.add_aggregation("avg_price", MetricAggregation::Avg(price));
+/// aggregation_collector.add_aggregation("max_price", MetricAggregation::Max(price));
+/// aggregation_collector.add_aggregation("count", MetricAggregation::Count);
+///
+/// let (top_docs, aggregations) =
+///     searcher.search(&query, &(TopDocs::with_limit(1), aggregation_collector))?;
+/// assert_eq!(top_docs.len(), 1);
+/// assert_eq!(aggregations["avg_price"].value, 400.0);
+/// assert_eq!(aggregations["max_price"].value, 500.0);
+/// assert_eq!(aggregations["count"].value, 2.0);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct AggregationCollector {
+    aggregations: Vec<(String, MetricAggregation)>,
+}
+
+impl AggregationCollector {
+    /// Adds an aggregation, whose result will be returned under `name`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an aggregation named `name` was already added.
+    pub fn add_aggregation(&mut self, name: &str, aggregation: MetricAggregation) {
+        assert!(
+            self.aggregations
+                .iter()
+                .all(|(aggregation_name, _)| aggregation_name != name),
+            "Aggregation {:?} was added twice.",
+            name
+        );
+        self.aggregations.push((name.to_string(), aggregation));
+    }
+}
+
+impl Collector for AggregationCollector {
+    type Fruit = HashMap<String, AggregationResult>;
+
+    type Child = AggregationSegmentCollector;
+
+    fn for_segment(
+        &self,
+        _: SegmentLocalId,
+        reader: &SegmentReader,
+    ) -> crate::Result<AggregationSegmentCollector> {
+        let mut metrics = Vec::with_capacity(self.aggregations.len());
+        for (_, aggregation) in &self.aggregations {
+            let fast_field_reader = aggregation
+                .field()
+                .map(|field| NumericFastFieldReader::open(reader, field))
+                .transpose()?;
+            metrics.push((fast_field_reader, MetricStats::default()));
+        }
+        Ok(AggregationSegmentCollector { metrics })
+    }
+
+    fn requires_scoring(&self) -> bool {
+        false
+    }
+
+    fn merge_fruits(
+        &self,
+        segment_stats: Vec<Vec<MetricStats>>,
+    ) -> crate::Result<HashMap<String, AggregationResult>> {
+        let mut stats = vec![MetricStats::default(); self.aggregations.len()];
+        for segment_stats in &segment_stats {
+            for (stats, segment_stats) in stats.iter_mut().zip(segment_stats) {
+                stats.merge(segment_stats);
+            }
+        }
+        Ok(self
+            .aggregations
+            .iter()
+            .zip(stats)
+            .map(|((name, aggregation), stats)| (name.clone(), stats.result(aggregation)))
+            .collect())
+    }
+}
+
+/// Segment collector of the `AggregationCollector`.
+pub struct AggregationSegmentCollector {
+    metrics: Vec<(Option<NumericFastFieldReader>, MetricStats)>,
+}
+
+impl SegmentCollector for AggregationSegmentCollector {
+    type Fruit = Vec<MetricStats>;
+
+    fn collect(&mut self, doc: DocId, _: Score) {
+        for (fast_field_reader, stats) in &mut self.metrics {
+            let value = match fast_field_reader {
+                Some(reader) => reader.get(doc),
+                None => Some(0.0),
+            };
+            // Documents without a value do not count.
+            if let Some(value) = value {
+                stats.collect(value);
+            }
+        }
+    }
+
+    fn harvest(self) -> Vec<MetricStats> {
+        self.metrics.into_iter().map(|(_, stats)| stats).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AggregationCollector, MetricAggregation};
+    use crate::collector::Count;
+    use crate::query::{AllQuery, TermQuery};
+    use crate::schema::{Cardinality, IntOptions};
+    use crate::schema::{IndexRecordOption, Schema, FAST, INDEXED, STRING, TEXT};
+    use crate::{DateTime, Index, Term};
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn test_metric_aggregations() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let category = schema_builder.add_text_field("category", STRING);
+        let quantity = schema_builder.add_u64_field("quantity", FAST);
+        let balance = schema_builder.add_i64_field("balance", FAST);
+        let price = schema_builder.add_f64_field("price", FAST);
+        let date = schema_builder.add_date_field("date", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        let timestamp = |secs| -> DateTime { Utc.timestamp_opt(secs, 0).unwrap() };
+        index_writer.add_document(doc!(
+            category => "a",
+            quantity => 3u64,
+            balance => -10i64,
+            price => 1.5f64,
+            date => timestamp(100),
+        ));
+        index_writer.add_document(doc!(
+            category => "a",
+            quantity => 1u64,
+            balance => 4i64,
+            price => 2.5f64,
+            date => timestamp(300),
+        ));
+        index_writer.commit()?;
+        // A second segment.
+        index_writer.add_document(doc!(
+            category => "b",
+            quantity => 8u64,
+            balance => 20i64,
+            price => 10.0f64,
+            date => timestamp(200),
+        ));
+        index_writer.add_document(doc!(
+            category => "a",
+            quantity => 2u64,
+            balance => 6i64,
+            price => 4.0f64,
+            date => timestamp(50),
+        ));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 2);
+
+        let mut collector = AggregationCollector::default();
+        collector.add_aggregation("sum_quantity", MetricAggregation::Sum(quantity));
+        collector.add_aggregation("min_balance", MetricAggregation::Min(balance));
+        collector.add_aggregation("max_price", MetricAggregation::Max(price));
+        collector.add_aggregation("avg_price", MetricAggregation::Avg(price));
+        collector.add_aggregation("min_date", MetricAggregation::Min(date));
+        collector.add_aggregation("count", MetricAggregation::Count);
+
+        let query = TermQuery::new(
+            Term::from_field_text(category, "a"),
+            IndexRecordOption::Basic,
+        );
+        let (count, aggregations) = searcher.search(&query, &(Count, collector.clone()))?;
+        assert_eq!(count, 3);
+        assert_eq!(aggregations.len(), 6);
+        assert_eq!(aggregations["sum_quantity"].value, 6.0);
+        assert_eq!(aggregations["min_balance"].value, -10.0);
+        assert_eq!(aggregations["max_price"].value, 4.0);
+        assert_eq!(aggregations["avg_price"].value, 8.0 / 3.0);
+        assert_eq!(aggregations["min_date"].value, 50.0);
+        assert_eq!(aggregations["count"].value, 3.0);
+
+        let aggregations = searcher.search(&AllQuery, &collector)?;
+        assert_eq!(aggregations["sum_quantity"].value, 14.0);
+        assert_eq!(aggregations["max_price"].value, 10.0);
+        assert_eq!(aggregations["count"].value, 4.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_metric_aggregations_no_match() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let price = schema_builder.add_f64_field("price", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title => "sofa", price => 1.0f64));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let mut collector = AggregationCollector::default();
+        collector.add_aggregation("sum", MetricAggregation::Sum(price));
+        collector.add_aggregation("min", MetricAggregation::Min(price));
+        collector.add_aggregation("avg", MetricAggregation::Avg(price));
+        collector.add_aggregation("count", MetricAggregation::Count);
+        let query = TermQuery::new(
+            Term::from_field_text(title, "chair"),
+            IndexRecordOption::Basic,
+        );
+        let aggregations = searcher.search(&query, &collector)?;
+        assert_eq!(aggregations["sum"].value, 0.0);
+        assert!(aggregations["min"].value.is_nan());
+        assert!(aggregations["avg"].value.is_nan());
+        assert_eq!(aggregations["count"].value, 0.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_metric_aggregations_skip_null_values() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let rating = schema_builder.add_i64_field(
+            "rating",
+            IntOptions::default().set_fast(Cardinality::Optional),
+        );
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(rating => 4i64));
+        index_writer.add_document(doc!());
+        index_writer.add_document(doc!(rating => 1i64));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let mut collector = AggregationCollector::default();
+        collector.add_aggregation("min", MetricAggregation::Min(rating));
+        collector.add_aggregation("avg", MetricAggregation::Avg(rating));
+        collector.add_aggregation("count", MetricAggregation::Count);
+        let aggregations = searcher.search(&AllQuery, &collector)?;
+        assert_eq!(aggregations["min"].value, 1.0);
+        assert_eq!(aggregations["avg"].value, 2.5);
+        assert_eq!(aggregations["count"].value, 3.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_metric_aggregation_requires_numeric_fast_field() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let not_fast = schema_builder.add_u64_field("not_fast", INDEXED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title => "sofa", not_fast => 1u64));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        for &field in &[title, not_fast] {
+            let mut collector = AggregationCollector::default();
+            collector.add_aggregation("sum", MetricAggregation::Sum(field));
+            assert!(searcher.search(&AllQuery, &collector).is_err());
+        }
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "was added twice")]
+    fn test_metric_aggregation_duplicate_name() {
+        let mut collector = AggregationCollector::default();
+        collector.add_aggregation("count", MetricAggregation::Count);
+        collector.add_aggregation("count", MetricAggregation::Count);
+    }
+}
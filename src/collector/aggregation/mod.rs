@@ -0,0 +1,137 @@
+/*!
+Aggregations compute statistics over the documents matching a query.
+
+They rely on fast fields, so that their cost is proportional to the number
+of matching documents, and never requires to fetch the documents from the doc store.
+
+Like any collector, aggregation collectors can be combined with other collectors,
+e.g. to get the top documents and some statistics about all of the matching
+documents in a single search.
+
+- [`AggregationCollector`](./struct.AggregationCollector.html) computes metrics
+  (sum, min, max, average, count) over numeric fast fields.
+- [`TermsAggregation`](./struct.TermsAggregation.html) returns the most
+  frequent values of a bytes fast field.
+- [`HistogramAggregation`](./struct.HistogramAggregation.html) counts the
+  documents in equal-width buckets of the values of a numeric fast field.
+- [`DateHistogramCollector`](./struct.DateHistogramCollector.html) counts the
+  documents in calendar intervals of a date fast field.
+- [`PercentilesAggregation`](./struct.PercentilesAggregation.html) estimates
+  percentiles of the values of a numeric fast field.
+- [`CardinalityAggregation`](./struct.CardinalityAggregation.html) estimates
+  the number of distinct values of a numeric or bytes fast field.
+- [`SignificantTermsAggregation`](./struct.SignificantTermsAggregation.html) returns
+  the terms of a text field that are unusually frequent among the matching documents.
+*/
+
+mod cardinality_aggregation;
+mod date_histogram_collector;
+mod histogram_aggregation;
+mod metric_aggregation;
+mod percentiles_aggregation;
+mod significant_terms_aggregation;
+mod terms_aggregation;
+
+pub use self::cardinality_aggregation::{
+    CardinalityAggregation, CardinalitySegmentCollector, HyperLogLog,
+};
+pub use self::date_histogram_collector::{
+    DateBucket, DateHistogramCollector, DateHistogramSegmentCollector, DateInterval,
+};
+pub use self::histogram_aggregation::{
+    HistogramAggregation, HistogramAggregationSegmentCollector, HistogramBucket,
+};
+pub use self::metric_aggregation::{
+    AggregationCollector, AggregationResult, AggregationSegmentCollector, MetricAggregation,
+    MetricStats,
+};
+pub use self::percentiles_aggregation::{
+    PercentilesAggregation, PercentilesResult, PercentilesSegmentCollector, TDigest,
+};
+pub use self::significant_terms_aggregation::{
+    MatchingDocs, SignificanceHeuristic, SignificantTerm, SignificantTermsAggregation,
+    SignificantTermsSegmentCollector,
+};
+pub use self::terms_aggregation::{
+    TermsAggregation, TermsAggregationSegmentCollector, TermsBucket,
+};
+
+use crate::fastfield::FastFieldReader;
+use crate::schema::{Cardinality, Field, FieldType, IntOptions};
+use crate::{DateTime, DocId, SegmentReader, TantivyError};
+use roaring::RoaringBitmap;
+use std::sync::Arc;
+
+fn has_one_value_at_most(options: &IntOptions) -> bool {
+    matches!(
+        options.get_fastfield_cardinality(),
+        Some(Cardinality::SingleValue) | Some(Cardinality::Optional)
+    )
+}
+
+enum NumericValues {
+    U64(FastFieldReader<u64>),
+    I64(FastFieldReader<i64>),
+    F64(FastFieldReader<f64>),
+    Date(FastFieldReader<DateTime>),
+}
+
+/// Reads the values of a single-valued, or optional, numeric fast field as `f64`.
+///
+/// Dates are read as their timestamp, in seconds.
+pub(crate) struct NumericFastFieldReader {
+    values: NumericValues,
+    // Documents having a value, for optional fast fields.
+    null_bitmap: Option<Arc<RoaringBitmap>>,
+}
+
+impl NumericFastFieldReader {
+    /// Opens the reader of `field` in the segment.
+    ///
+    /// Returns an error if `field` is not a single-valued or optional numeric fast field.
+    pub fn open(segment_reader: &SegmentReader, field: Field) -> crate::Result<Self> {
+        let fast_fields = segment_reader.fast_fields();
+        let field_entry = segment_reader.schema().get_field_entry(field);
+        let values = match field_entry.field_type() {
+            FieldType::U64(options) if has_one_value_at_most(options) => {
+                NumericValues::U64(fast_fields.typed_fast_field_reader(field)?)
+            }
+            FieldType::I64(options) if has_one_value_at_most(options) => {
+                NumericValues::I64(fast_fields.typed_fast_field_reader(field)?)
+            }
+            FieldType::F64(options) if has_one_value_at_most(options) => {
+                NumericValues::F64(fast_fields.typed_fast_field_reader(field)?)
+            }
+            FieldType::Date(options) if has_one_value_at_most(options) => {
+                NumericValues::Date(fast_fields.typed_fast_field_reader(field)?)
+            }
+            _ => {
+                return Err(TantivyError::SchemaError(format!(
+                    "Field {:?} is not a single-valued numeric fast field.",
+                    field_entry.name()
+                )))
+            }
+        };
+        Ok(NumericFastFieldReader {
+            values,
+            null_bitmap: fast_fields.null_bitmap(field)?,
+        })
+    }
+
+    /// Returns the value of the document `doc`, or `None` if the
+    /// document does not have a value.
+    pub fn get(&self, doc: DocId) -> Option<f64> {
+        if let Some(null_bitmap) = self.null_bitmap.as_ref() {
+            if !null_bitmap.contains(doc) {
+                return None;
+            }
+        }
+        let value = match &self.values {
+            NumericValues::U64(reader) => reader.get(doc) as f64,
+            NumericValues::I64(reader) => reader.get(doc) as f64,
+            NumericValues::F64(reader) => reader.get(doc),
+            NumericValues::Date(reader) => reader.get(doc).timestamp() as f64,
+        };
+        Some(value)
+    }
+}
@@ -0,0 +1,408 @@
+use super::NumericFastFieldReader;
+use crate::collector::{Collector, SegmentCollector};
+use crate::schema::Field;
+use crate::{DocId, Score, SegmentLocalId, SegmentReader, TantivyError};
+use std::cmp::Ordering;
+use std::f64::consts::PI;
+
+const DEFAULT_COMPRESSION: f64 = 100.0;
+
+/// The percentiles computed by the
+/// [`PercentilesAggregation`](./struct.PercentilesAggregation.html).
+#[derive(Clone, Debug, PartialEq)]
+pub struct PercentilesResult {
+    /// Pairs of a requested percentile and of its estimated value, in the order
+    /// the percentiles were requested.
+    ///
+    /// The estimates are `NaN` when no document matched.
+    pub values: Vec<(f64, f64)>,
+}
+
+/// Collector estimating percentiles of the values of a numeric fast field.
+///
+/// The field must be a single-valued numeric fast field (`u64`, `i64`, `f64` or date).
+/// Dates are aggregated as timestamps, in seconds.
+///
+/// The values are summarized in a [t-digest](https://arxiv.org/abs/1902.04023),
+/// whose size does not depend on the number of matching documents. The digests
+/// of the segments are merged into one before estimating the percentiles.
+/// The estimates are more accurate for the extreme percentiles, like the 99th,
+/// than for the median. Their accuracy is controlled by the
+/// [compression](#method.set_compression) of the digest.
+///
+/// ```rust
+/// use tantivy::collector::PercentilesAggregation;
+/// use tantivy::query::AllQuery;
+/// use tantivy::schema::{Schema, FAST};
+/// use tantivy::{doc, Index};
+///
+/// # fn main() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let latency = schema_builder.add_u64_field("latency", FAST);
+/// let schema = schema_builder.build();
+/// let index = Index::create_in_ram(schema);
+///
+/// let mut index_writer = index.writer(3_000_000)?;
+/// for latency_ms in 1..=100u64 {
+///     index_writer.add_document(doc!(latency => latency_ms));
+/// }
+/// index_writer.commit()?;
+///
+/// let searcher = index.reader()?.searcher();
+/// let percentiles = PercentilesAggregation::new(latency, vec![50.0, 99.0])?;
+/// let result = searcher.search(&AllQuery, &percentiles)?;
+/// let (percentile, p99) = result.values[1];
+/// assert_eq!(percentile, 99.0);
+/// assert!((98.0..=100.0).contains(&p99));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct PercentilesAggregation {
+    field: Field,
+    percentiles: Vec<f64>,
+    compression: f64,
+}
+
+impl PercentilesAggregation {
+    /// Creates a `PercentilesAggregation` estimating the `percentiles` of `field`.
+    ///
+    /// Returns an error if one of the percentiles is not within `[0, 100]`.
+    pub fn new(field: Field, percentiles: Vec<f64>) -> crate::Result<PercentilesAggregation> {
+        if let Some(percentile) = percentiles
+            .iter()
+            .find(|percentile| !(0.0..=100.0).contains(*percentile))
+        {
+            return Err(TantivyError::InvalidArgument(format!(
+                "Percentiles must be within [0, 100], got {}.",
+                percentile
+            )));
+        }
+        Ok(PercentilesAggregation {
+            field,
+            percentiles,
+            compression: DEFAULT_COMPRESSION,
+        })
+    }
+
+    /// Sets the compression of the digest. It defaults to `100`.
+    ///
+    /// The digest keeps fewer than `compression` centroids. Higher values
+    /// give more accurate estimates, for more memory and cpu.
+    ///
+    /// Returns an error if `compression` is smaller than `1`.
+    pub fn set_compression(mut self, compression: f64) -> crate::Result<PercentilesAggregation> {
+        if !(compression >= 1.0 && compression.is_finite()) {
+            return Err(TantivyError::InvalidArgument(format!(
+                "The compression of a t-digest must be at least 1, got {}.",
+                compression
+            )));
+        }
+        self.compression = compression;
+        Ok(self)
+    }
+}
+
+impl Collector for PercentilesAggregation {
+    type Fruit = PercentilesResult;
+
+    type Child = PercentilesSegmentCollector;
+
+    fn for_segment(
+        &self,
+        _: SegmentLocalId,
+        reader: &SegmentReader,
+    ) -> crate::Result<PercentilesSegmentCollector> {
+        let reader = NumericFastFieldReader::open(reader, self.field)?;
+        Ok(PercentilesSegmentCollector {
+            reader,
+            digest: TDigest::new(self.compression),
+        })
+    }
+
+    fn requires_scoring(&self) -> bool {
+        false
+    }
+
+    fn merge_fruits(&self, segment_digests: Vec<TDigest>) -> crate::Result<PercentilesResult> {
+        let mut digest = TDigest::new(self.compression);
+        for segment_digest in &segment_digests {
+            digest.merge(segment_digest);
+        }
+        digest.compress();
+        let values = self
+            .percentiles
+            .iter()
+            .map(|&percentile| (percentile, digest.quantile(percentile / 100.0)))
+            .collect();
+        Ok(PercentilesResult { values })
+    }
+}
+
+/// Segment collector of the `PercentilesAggregation`.
+pub struct PercentilesSegmentCollector {
+    reader: NumericFastFieldReader,
+    digest: TDigest,
+}
+
+impl SegmentCollector for PercentilesSegmentCollector {
+    type Fruit = TDigest;
+
+    fn collect(&mut self, doc: DocId, _: Score) {
+        if let Some(value) = self.reader.get(doc) {
+            if !value.is_nan() {
+                self.digest.add(value);
+            }
+        }
+    }
+
+    fn harvest(mut self) -> TDigest {
+        self.digest.compress();
+        self.digest
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Centroid {
+    mean: f64,
+    weight: f64,
+}
+
+/// Summary of the values collected on a segment by the
+/// [`PercentilesAggregation`](./struct.PercentilesAggregation.html).
+///
+/// It is a merging t-digest: the values are buffered, and periodically merged into
+/// centroids, whose maximum weight is small near the extreme quantiles and larger
+/// around the median.
+#[derive(Clone, Debug)]
+pub struct TDigest {
+    compression: f64,
+    centroids: Vec<Centroid>,
+    buffer: Vec<f64>,
+    min: f64,
+    max: f64,
+}
+
+impl TDigest {
+    fn new(compression: f64) -> TDigest {
+        TDigest {
+            compression,
+            centroids: Vec::new(),
+            buffer: Vec::new(),
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+
+    fn add(&mut self, value: f64) {
+        self.buffer.push(value);
+        if self.buffer.len() as f64 >= 8.0 * self.compression {
+            self.compress();
+        }
+    }
+
+    fn merge(&mut self, other: &TDigest) {
+        self.centroids.extend_from_slice(&other.centroids);
+        self.buffer.extend_from_slice(&other.buffer);
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.compress();
+    }
+
+    /// Merges the buffered values and the centroids into fewer centroids.
+    fn compress(&mut self) {
+        for &value in &self.buffer {
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+        }
+        let mut centroids: Vec<Centroid> = self
+            .buffer
+            .drain(..)
+            .map(|mean| Centroid { mean, weight: 1.0 })
+            .chain(self.centroids.drain(..))
+            .collect();
+        if centroids.is_empty() {
+            return;
+        }
+        centroids.sort_by(|left, right| {
+            left.mean
+                .partial_cmp(&right.mean)
+                .unwrap_or(Ordering::Equal)
+        });
+        let total_weight: f64 = centroids.iter().map(|centroid| centroid.weight).sum();
+        // A centroid may only span a unit of the scale function, which is steeper
+        // near the extreme quantiles.
+        let scale = |q: f64| self.compression / (2.0 * PI) * (2.0 * q - 1.0).asin();
+        let mut weight_so_far = 0.0;
+        let mut current_scale_start = scale(0.0);
+        let mut merged_centroids = Vec::new();
+        let mut current = centroids[0];
+        for &next in &centroids[1..] {
+            let merged_weight = current.weight + next.weight;
+            let q_end = (weight_so_far + merged_weight) / total_weight;
+            if scale(q_end) - current_scale_start <= 1.0 {
+                current.mean += (next.mean - current.mean) * next.weight / merged_weight;
+                current.weight = merged_weight;
+            } else {
+                weight_so_far += current.weight;
+                current_scale_start = scale(weight_so_far / total_weight);
+                merged_centroids.push(current);
+                current = next;
+            }
+        }
+        merged_centroids.push(current);
+        self.centroids = merged_centroids;
+    }
+
+    /// Estimates the value of the quantile `q`, within `[0, 1]`.
+    ///
+    /// The digest is expected to be compressed.
+    fn quantile(&self, q: f64) -> f64 {
+        debug_assert!(self.buffer.is_empty());
+        if self.centroids.is_empty() {
+            return f64::NAN;
+        }
+        if q <= 0.0 {
+            return self.min;
+        }
+        if q >= 1.0 {
+            return self.max;
+        }
+        let total_weight: f64 = self.centroids.iter().map(|centroid| centroid.weight).sum();
+        let target = q * total_weight;
+        // Each centroid is considered to be centered on the middle of its weight,
+        // and the values are interpolated linearly between the centers.
+        let mut weight_so_far = 0.0;
+        let mut previous: Option<(f64, f64)> = None;
+        for centroid in &self.centroids {
+            let center = weight_so_far + centroid.weight / 2.0;
+            if target < center {
+                let (previous_center, previous_mean) = previous.unwrap_or((0.0, self.min));
+                return interpolate(
+                    previous_mean,
+                    centroid.mean,
+                    (target - previous_center) / (center - previous_center),
+                );
+            }
+            previous = Some((center, centroid.mean));
+            weight_so_far += centroid.weight;
+        }
+        let (last_center, last_mean) = previous.unwrap_or((0.0, self.min));
+        interpolate(
+            last_mean,
+            self.max,
+            (target - last_center) / (total_weight - last_center),
+        )
+    }
+}
+
+fn interpolate(start: f64, end: f64, ratio: f64) -> f64 {
+    start + (end - start) * ratio
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PercentilesAggregation, TDigest};
+    use crate::collector::Count;
+    use crate::query::{AllQuery, TermQuery};
+    use crate::schema::{IndexRecordOption, Schema, FAST, STRING};
+    use crate::{Index, Term};
+
+    fn digest_of(values: impl Iterator<Item = f64>, compression: f64) -> TDigest {
+        let mut digest = TDigest::new(compression);
+        for value in values {
+            digest.add(value);
+        }
+        digest.compress();
+        digest
+    }
+
+    #[test]
+    fn test_tdigest_accuracy() {
+        let num_values = 100_000;
+        // The values are added in a shuffled order.
+        let values = (0..num_values).map(|i| ((i * 7_919) % num_values) as f64);
+        let digest = digest_of(values, 100.0);
+        assert!(digest.centroids.len() <= 100);
+        assert_eq!(digest.quantile(0.0), 0.0);
+        assert_eq!(digest.quantile(1.0), (num_values - 1) as f64);
+        for &(q, max_error) in &[(0.5, 0.01), (0.95, 0.005), (0.99, 0.001), (0.999, 0.0005)] {
+            let estimate = digest.quantile(q);
+            let expected = q * num_values as f64;
+            assert!(
+                (estimate - expected).abs() <= max_error * num_values as f64,
+                "q={} estimate={} expected={}",
+                q,
+                estimate,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_tdigest_merge() {
+        let mut digest = digest_of((0..5_000).map(|i| i as f64), 100.0);
+        digest.merge(&digest_of((5_000..10_000).map(|i| i as f64), 100.0));
+        assert!((digest.quantile(0.5) - 5_000.0).abs() < 100.0);
+        assert!((digest.quantile(0.9) - 9_000.0).abs() < 50.0);
+        assert_eq!(digest.quantile(1.0), 9_999.0);
+        assert!(TDigest::new(100.0).quantile(0.5).is_nan());
+        assert_eq!(digest_of(std::iter::once(3.0), 100.0).quantile(0.5), 3.0);
+    }
+
+    #[test]
+    fn test_percentiles_aggregation() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let status = schema_builder.add_text_field("status", STRING);
+        let latency = schema_builder.add_f64_field("latency", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        for i in 0..1_000 {
+            index_writer.add_document(doc!(status => "ok", latency => f64::from(i)));
+            if i == 500 {
+                index_writer.commit()?;
+            }
+        }
+        index_writer.add_document(doc!(status => "error", latency => 5_000.0));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 2);
+
+        let percentiles = PercentilesAggregation::new(latency, vec![99.0, 50.0, 0.0])?;
+        let ok = TermQuery::new(
+            Term::from_field_text(status, "ok"),
+            IndexRecordOption::Basic,
+        );
+        let (count, result) = searcher.search(&ok, &(Count, percentiles.clone()))?;
+        assert_eq!(count, 1_000);
+        let percentile_values: Vec<f64> = result.values.iter().map(|&(p, _)| p).collect();
+        assert_eq!(percentile_values, vec![99.0, 50.0, 0.0]);
+        assert!((result.values[0].1 - 990.0).abs() < 2.0);
+        assert!((result.values[1].1 - 500.0).abs() < 10.0);
+        assert_eq!(result.values[2].1, 0.0);
+
+        let result = searcher.search(&AllQuery, &percentiles.set_compression(20.0)?)?;
+        assert!(result.values[0].1 > 980.0);
+
+        let empty_index = Index::create_in_ram(index.schema());
+        let result = empty_index.reader()?.searcher().search(
+            &AllQuery,
+            &PercentilesAggregation::new(latency, vec![50.0])?,
+        )?;
+        assert!(result.values[0].1.is_nan());
+        Ok(())
+    }
+
+    #[test]
+    fn test_percentiles_aggregation_invalid_arguments() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let latency = schema_builder.add_f64_field("latency", FAST);
+        assert!(PercentilesAggregation::new(latency, vec![50.0, 101.0]).is_err());
+        assert!(PercentilesAggregation::new(latency, vec![-1.0]).is_err());
+        assert!(PercentilesAggregation::new(latency, vec![f64::NAN]).is_err());
+        let percentiles = PercentilesAggregation::new(latency, vec![0.0, 100.0])?;
+        assert!(percentiles.clone().set_compression(0.5).is_err());
+        assert!(percentiles.set_compression(10.0).is_ok());
+        Ok(())
+    }
+}
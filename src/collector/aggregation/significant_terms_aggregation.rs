@@ -0,0 +1,456 @@
+use crate::collector::{Collector, SegmentCollector};
+use crate::common::BitSet;
+use crate::postings::TermInfo;
+use crate::schema::{Field, FieldType, IndexRecordOption};
+use crate::termdict::TermMerger;
+use crate::{DocId, DocSet, InvertedIndexReader, Score, SegmentLocalId, SegmentReader};
+use crate::{TantivyError, TERMINATED};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::sync::Arc;
+
+/// A term returned by the [`SignificantTermsAggregation`](./struct.SignificantTermsAggregation.html).
+#[derive(Clone, Debug, PartialEq)]
+pub struct SignificantTerm {
+    /// Text of the term.
+    pub term: String,
+    /// Significance of the term, as computed by the `SignificanceHeuristic`.
+    pub score: f64,
+    /// Number of matching documents containing the term.
+    pub doc_count: u64,
+    /// Number of documents of the index containing the term.
+    pub bg_count: u64,
+}
+
+/// Measure of how much more frequent a term is among the matching documents
+/// than among all of the documents of the index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SignificanceHeuristic {
+    /// The JLH score: the absolute change of the frequency of the term,
+    /// multiplied by its relative change.
+    ///
+    /// It favours the terms whose frequency increases a lot, even if they are rare.
+    Jlh,
+    /// The mutual information between the term and the set of matching documents,
+    /// in bits.
+    ///
+    /// It favours the frequent terms over the rare ones.
+    MutualInformation,
+}
+
+impl SignificanceHeuristic {
+    /// Returns the score of a term found in `doc_count` of the `subset_size` matching
+    /// documents, and in `bg_count` of the `superset_size` documents of the index,
+    /// or `None` if the term is not more frequent among the matching documents.
+    fn score(
+        self,
+        doc_count: u64,
+        subset_size: u64,
+        bg_count: u64,
+        superset_size: u64,
+    ) -> Option<f64> {
+        if doc_count == 0 || subset_size == 0 || superset_size == 0 {
+            return None;
+        }
+        let subset_freq = doc_count as f64 / subset_size as f64;
+        let superset_freq = bg_count as f64 / superset_size as f64;
+        if subset_freq <= superset_freq {
+            return None;
+        }
+        match self {
+            SignificanceHeuristic::Jlh => {
+                Some((subset_freq - superset_freq) * (subset_freq / superset_freq))
+            }
+            SignificanceHeuristic::MutualInformation => Some(mutual_information(
+                doc_count,
+                subset_size,
+                bg_count,
+                superset_size,
+            )),
+        }
+    }
+}
+
+fn mutual_information(doc_count: u64, subset_size: u64, bg_count: u64, superset_size: u64) -> f64 {
+    let n = superset_size as f64;
+    // Number of documents with (1) or without (0) the term...
+    let n1_ = bg_count as f64;
+    let n0_ = n - n1_;
+    // ... and in (1) or out of (0) the matching documents.
+    let n_1 = subset_size as f64;
+    let n_0 = n - n_1;
+    let n11 = doc_count as f64;
+    let n01 = n_1 - n11;
+    let n10 = n1_ - n11;
+    let n00 = n0_ - n01;
+    let term = |n_xy: f64, n_x: f64, n_y: f64| {
+        if n_xy <= 0.0 {
+            0.0
+        } else {
+            n_xy / n * (n * n_xy / (n_x * n_y)).log2()
+        }
+    };
+    term(n11, n1_, n_1) + term(n01, n0_, n_1) + term(n10, n1_, n_0) + term(n00, n0_, n_0)
+}
+
+/// Collector returning the terms of a text field that are unusually frequent among
+/// the matching documents, compared to all of the documents of the index.
+///
+/// This is the equivalent of the `significant_terms` aggregation of Elasticsearch.
+/// Like any collector, it can be combined with the query of a drill-down,
+/// to explain what the matching documents have in common.
+///
+/// The background count of a term is its document frequency in the index,
+/// and the background size is the number of documents of the index, both
+/// including the deleted documents. Only the terms that are more frequent
+/// among the matching documents than in the background are returned,
+/// by decreasing score and then by term.
+///
+/// The term dictionaries of the segments are walked in order once the
+/// documents have been collected, and only the `size` best terms are kept
+/// in memory, at the cost of reading the postings of all of the terms.
+///
+/// ```rust
+/// use tantivy::collector::SignificantTermsAggregation;
+/// use tantivy::query::QueryParser;
+/// use tantivy::schema::{Schema, TEXT};
+/// use tantivy::{doc, Index};
+///
+/// # fn main() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let body = schema_builder.add_text_field("body", TEXT);
+/// let schema = schema_builder.build();
+/// let index = Index::create_in_ram(schema);
+///
+/// let mut index_writer = index.writer(3_000_000)?;
+/// index_writer.add_document(doc!(body => "the cat purrs"));
+/// index_writer.add_document(doc!(body => "the cat purrs and sleeps"));
+/// index_writer.add_document(doc!(body => "the cat sleeps"));
+/// index_writer.add_document(doc!(body => "the dog sleeps"));
+/// index_writer.add_document(doc!(body => "the dog barks"));
+/// index_writer.commit()?;
+///
+/// let searcher = index.reader()?.searcher();
+/// let query = QueryParser::for_index(&index, vec![body]).parse_query("cat")?;
+/// let terms = searcher.search(&query, &SignificantTermsAggregation::new(body, 2))?;
+/// assert_eq!(terms.len(), 2);
+/// assert_eq!(terms[0].term, "cat");
+/// assert_eq!(terms[1].term, "purrs");
+/// assert_eq!(terms[1].doc_count, 2);
+/// assert_eq!(terms[1].bg_count, 2);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct SignificantTermsAggregation {
+    field: Field,
+    size: usize,
+    heuristic: SignificanceHeuristic,
+}
+
+impl SignificantTermsAggregation {
+    /// Creates a `SignificantTermsAggregation` returning the `size` most significant
+    /// terms of `field`, scored with the JLH heuristic.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn new(field: Field, size: usize) -> SignificantTermsAggregation {
+        assert!(
+            size > 0,
+            "The size of a significant terms aggregation must be at least 1."
+        );
+        SignificantTermsAggregation {
+            field,
+            size,
+            heuristic: SignificanceHeuristic::Jlh,
+        }
+    }
+
+    /// Sets the heuristic used to score the terms.
+    pub fn set_heuristic(mut self, heuristic: SignificanceHeuristic) -> Self {
+        self.heuristic = heuristic;
+        self
+    }
+}
+
+impl Collector for SignificantTermsAggregation {
+    type Fruit = Vec<SignificantTerm>;
+
+    type Child = SignificantTermsSegmentCollector;
+
+    fn for_segment(
+        &self,
+        _: SegmentLocalId,
+        reader: &SegmentReader,
+    ) -> crate::Result<SignificantTermsSegmentCollector> {
+        let field_entry = reader.schema().get_field_entry(self.field);
+        match field_entry.field_type() {
+            FieldType::Str(_) if field_entry.is_indexed() => {}
+            _ => {
+                return Err(TantivyError::SchemaError(format!(
+                    "Field {:?} is not an indexed text field.",
+                    field_entry.name()
+                )));
+            }
+        }
+        Ok(SignificantTermsSegmentCollector {
+            matching_docs: MatchingDocs {
+                inverted_index: reader.inverted_index(self.field)?,
+                docs: BitSet::with_max_value(reader.max_doc()),
+                num_docs: 0,
+                max_doc: reader.max_doc(),
+            },
+        })
+    }
+
+    fn requires_scoring(&self) -> bool {
+        false
+    }
+
+    fn merge_fruits(
+        &self,
+        segment_matching_docs: Vec<MatchingDocs>,
+    ) -> crate::Result<Vec<SignificantTerm>> {
+        let subset_size: u64 = segment_matching_docs
+            .iter()
+            .map(|matching_docs| matching_docs.num_docs)
+            .sum();
+        let superset_size: u64 = segment_matching_docs
+            .iter()
+            .map(|matching_docs| u64::from(matching_docs.max_doc))
+            .sum();
+        let streams = segment_matching_docs
+            .iter()
+            .map(|matching_docs| matching_docs.inverted_index.terms().stream())
+            .collect::<std::io::Result<Vec<_>>>()?;
+        let mut term_merger = TermMerger::new(streams);
+        // The top of the heap is the worst term kept so far.
+        let mut heap: BinaryHeap<ScoredTerm> = BinaryHeap::with_capacity(self.size);
+        while term_merger.advance() {
+            let mut doc_count = 0u64;
+            let mut bg_count = 0u64;
+            for heap_item in term_merger.current_kvs() {
+                let term_info = heap_item.streamer.value();
+                bg_count += u64::from(term_info.doc_freq);
+                doc_count += segment_matching_docs[heap_item.segment_ord].count(term_info)?;
+            }
+            let score = match self
+                .heuristic
+                .score(doc_count, subset_size, bg_count, superset_size)
+            {
+                Some(score) => score,
+                None => continue,
+            };
+            if heap.len() == self.size {
+                let worst_term = heap.peek().unwrap();
+                if worst_term.cmp_score_and_term(score, term_merger.key()) != Ordering::Greater {
+                    continue;
+                }
+                heap.pop();
+            }
+            heap.push(ScoredTerm {
+                score,
+                term: term_merger.key().to_vec(),
+                doc_count,
+                bg_count,
+            });
+        }
+        Ok(heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|scored_term| SignificantTerm {
+                term: String::from_utf8_lossy(&scored_term.term).into_owned(),
+                score: scored_term.score,
+                doc_count: scored_term.doc_count,
+                bg_count: scored_term.bg_count,
+            })
+            .collect())
+    }
+}
+
+/// Term being ranked by the `SignificantTermsAggregation`.
+///
+/// The ordering puts the best terms first: by decreasing score, then by term.
+struct ScoredTerm {
+    score: f64,
+    term: Vec<u8>,
+    doc_count: u64,
+    bg_count: u64,
+}
+
+impl ScoredTerm {
+    fn cmp_score_and_term(&self, score: f64, term: &[u8]) -> Ordering {
+        score
+            .partial_cmp(&self.score)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| self.term[..].cmp(term))
+    }
+}
+
+impl PartialEq for ScoredTerm {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for ScoredTerm {}
+
+impl PartialOrd for ScoredTerm {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ScoredTerm {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_score_and_term(other.score, &other.term)
+    }
+}
+
+/// Segment collector of the `SignificantTermsAggregation`.
+pub struct SignificantTermsSegmentCollector {
+    matching_docs: MatchingDocs,
+}
+
+impl SegmentCollector for SignificantTermsSegmentCollector {
+    type Fruit = MatchingDocs;
+
+    fn collect(&mut self, doc: DocId, _: Score) {
+        self.matching_docs.docs.insert(doc);
+        self.matching_docs.num_docs += 1;
+    }
+
+    fn harvest(self) -> MatchingDocs {
+        self.matching_docs
+    }
+}
+
+/// Documents of a segment collected by the
+/// [`SignificantTermsAggregation`](./struct.SignificantTermsAggregation.html),
+/// along with the inverted index of its field.
+pub struct MatchingDocs {
+    inverted_index: Arc<InvertedIndexReader>,
+    docs: BitSet,
+    num_docs: u64,
+    max_doc: DocId,
+}
+
+impl MatchingDocs {
+    /// Returns the number of matching documents in the postings of a term.
+    fn count(&self, term_info: &TermInfo) -> crate::Result<u64> {
+        if self.num_docs == 0 {
+            return Ok(0);
+        }
+        let mut postings = self
+            .inverted_index
+            .read_postings_from_terminfo(term_info, IndexRecordOption::Basic)?;
+        let mut count = 0u64;
+        let mut doc = postings.doc();
+        while doc != TERMINATED {
+            if self.docs.contains(doc) {
+                count += 1;
+            }
+            doc = postings.advance();
+        }
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SignificanceHeuristic, SignificantTermsAggregation};
+    use crate::collector::Count;
+    use crate::query::{AllQuery, TermQuery};
+    use crate::schema::{IndexRecordOption, Schema, FAST, STRING, TEXT};
+    use crate::{Index, Term};
+
+    #[test]
+    fn test_significance_heuristics() {
+        let jlh = SignificanceHeuristic::Jlh;
+        // 50% of the subset against 10% of the superset.
+        crate::assert_nearly_equals!(jlh.score(5, 10, 10, 100).unwrap(), 0.4 * 5.0);
+        assert!(jlh.score(1, 10, 10, 100).is_none());
+        assert!(jlh.score(0, 10, 10, 100).is_none());
+        let mutual_information = SignificanceHeuristic::MutualInformation;
+        // The term is in exactly the matching documents: one bit of information.
+        crate::assert_nearly_equals!(mutual_information.score(50, 50, 50, 100).unwrap(), 1.0);
+        assert!(mutual_information.score(10, 100, 10, 100).is_none());
+        let rare = mutual_information.score(2, 10, 2, 1000).unwrap();
+        let frequent = mutual_information.score(8, 10, 100, 1000).unwrap();
+        assert!(rare < frequent);
+        assert!(jlh.score(2, 10, 2, 1000).unwrap() > jlh.score(8, 10, 100, 1000).unwrap());
+    }
+
+    #[test]
+    fn test_significant_terms_aggregation() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let topic = schema_builder.add_text_field("topic", STRING);
+        let body = schema_builder.add_text_field("body", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        for i in 0..20 {
+            if i % 4 == 0 {
+                index_writer
+                    .add_document(doc!(topic => "space", body => "the rocket and the moon"));
+            } else if i % 4 == 1 {
+                index_writer.add_document(doc!(topic => "space", body => "the orbit of the moon"));
+            } else {
+                index_writer.add_document(doc!(topic => "garden", body => "the rose and the moon"));
+            }
+            if i == 9 {
+                index_writer.commit()?;
+            }
+        }
+        // Only in the second segment, where it is never matched.
+        index_writer.add_document(doc!(topic => "garden", body => "the orbit of a beetle"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 2);
+
+        let space = TermQuery::new(
+            Term::from_field_text(topic, "space"),
+            IndexRecordOption::Basic,
+        );
+        let (count, terms) =
+            searcher.search(&space, &(Count, SignificantTermsAggregation::new(body, 10)))?;
+        assert_eq!(count, 10);
+        let names: Vec<&str> = terms.iter().map(|term| term.term.as_str()).collect();
+        // "of" and "orbit" have the same counts, and are sorted by term.
+        assert_eq!(names, vec!["rocket", "of", "orbit", "moon"]);
+        assert_eq!(terms[2].doc_count, 5);
+        assert_eq!(terms[2].bg_count, 6);
+        assert_eq!(
+            terms[2].bg_count,
+            searcher.doc_freq(&Term::from_field_text(body, "orbit"))?
+        );
+        crate::assert_nearly_equals!(terms[0].score, (0.5 - 5.0 / 21.0) * (0.5 * 21.0 / 5.0));
+
+        let terms = searcher.search(
+            &space,
+            &SignificantTermsAggregation::new(body, 1)
+                .set_heuristic(SignificanceHeuristic::MutualInformation),
+        )?;
+        assert_eq!(terms.len(), 1);
+        assert_eq!(terms[0].term, "rocket");
+
+        // Nothing is significant among all of the documents.
+        let terms = searcher.search(&AllQuery, &SignificantTermsAggregation::new(body, 10))?;
+        assert!(terms.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_significant_terms_aggregation_requires_text_field() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let price = schema_builder.add_u64_field("price", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(price => 3u64));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let aggregation = SignificantTermsAggregation::new(price, 10);
+        assert!(searcher.search(&AllQuery, &aggregation).is_err());
+        Ok(())
+    }
+}
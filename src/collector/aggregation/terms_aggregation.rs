@@ -0,0 +1,259 @@
+use crate::collector::{Collector, SegmentCollector};
+use crate::fastfield::BytesFastFieldReader;
+use crate::schema::Field;
+use crate::{DocId, Score, SegmentLocalId, SegmentReader};
+use std::collections::{BinaryHeap, HashMap};
+
+/// A bucket of the [`TermsAggregation`](./struct.TermsAggregation.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TermsBucket {
+    /// Value of the field.
+    pub value: String,
+    /// Number of matching documents having this value.
+    pub doc_count: u64,
+}
+
+/// Collector returning the most frequent values of a field
+/// among the matching documents.
+///
+/// This is the equivalent of the `terms` aggregation of Elasticsearch.
+///
+/// The values are read from a bytes fast field, and returned as strings
+/// (invalid UTF-8 sequences are replaced by `U+FFFD`). The text fields
+/// cannot be fast fields, so the values to aggregate on have to be indexed
+/// in a separate bytes field. Documents with an empty value are not counted.
+///
+/// The buckets are sorted by decreasing document count,
+/// and then by value.
+///
+/// ```rust
+/// use tantivy::collector::{Count, TermsAggregation};
+/// use tantivy::query::QueryParser;
+/// use tantivy::schema::{Schema, FAST, TEXT};
+/// use tantivy::{doc, Index};
+///
+/// # fn main() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let title = schema_builder.add_text_field("title", TEXT);
+/// let category = schema_builder.add_bytes_field("category", FAST);
+/// let schema = schema_builder.build();
+/// let index = Index::create_in_ram(schema);
+///
+/// let mut index_writer = index.writer(3_000_000)?;
+/// index_writer.add_document(doc!(title => "Red sofa", category => b"sofa".as_ref()));
+/// index_writer.add_document(doc!(title => "Red couch", category => b"sofa".as_ref()));
+/// index_writer.add_document(doc!(title => "Red chair", category => b"chair".as_ref()));
+/// index_writer.add_document(doc!(title => "Blue chair", category => b"chair".as_ref()));
+/// index_writer.add_document(doc!(title => "Red lamp", category => b"lamp".as_ref()));
+/// index_writer.commit()?;
+///
+/// let searcher = index.reader()?.searcher();
+/// let query = QueryParser::for_index(&index, vec![title]).parse_query("red")?;
+/// let (count, buckets) = searcher.search(&query, &(Count, TermsAggregation::new(category, 2)))?;
+/// assert_eq!(count, 4);
+/// assert_eq!(buckets.len(), 2);
+/// assert_eq!(buckets[0].value, "sofa");
+/// assert_eq!(buckets[0].doc_count, 2);
+/// assert_eq!(buckets[1].value, "chair");
+/// assert_eq!(buckets[1].doc_count, 1);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct TermsAggregation {
+    field: Field,
+    size: usize,
+}
+
+impl TermsAggregation {
+    /// Creates a `TermsAggregation` returning the `size` most frequent values of `field`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn new(field: Field, size: usize) -> TermsAggregation {
+        assert!(
+            size > 0,
+            "The size of a terms aggregation must be at least 1."
+        );
+        TermsAggregation { field, size }
+    }
+}
+
+impl Collector for TermsAggregation {
+    type Fruit = Vec<TermsBucket>;
+
+    type Child = TermsAggregationSegmentCollector;
+
+    fn for_segment(
+        &self,
+        _: SegmentLocalId,
+        reader: &SegmentReader,
+    ) -> crate::Result<TermsAggregationSegmentCollector> {
+        let reader = reader.fast_fields().bytes(self.field)?;
+        Ok(TermsAggregationSegmentCollector {
+            reader,
+            doc_counts: HashMap::new(),
+        })
+    }
+
+    fn requires_scoring(&self) -> bool {
+        false
+    }
+
+    fn merge_fruits(
+        &self,
+        segment_doc_counts: Vec<HashMap<Vec<u8>, u64>>,
+    ) -> crate::Result<Vec<TermsBucket>> {
+        let mut segment_doc_counts_it = segment_doc_counts.into_iter();
+        let mut doc_counts = segment_doc_counts_it.next().unwrap_or_default();
+        for segment_doc_counts in segment_doc_counts_it {
+            for (value, doc_count) in segment_doc_counts {
+                *doc_counts.entry(value).or_insert(0) += doc_count;
+            }
+        }
+        // The top of the heap is the worst bucket kept so far:
+        // the one with the lowest count and, among those, the highest value.
+        let mut heap: BinaryHeap<(std::cmp::Reverse<u64>, Vec<u8>)> =
+            BinaryHeap::with_capacity(self.size);
+        for (value, doc_count) in doc_counts {
+            let bucket = (std::cmp::Reverse(doc_count), value);
+            if heap.len() < self.size {
+                heap.push(bucket);
+            } else if let Some(mut worst_bucket) = heap.peek_mut() {
+                if bucket < *worst_bucket {
+                    *worst_bucket = bucket;
+                }
+            }
+        }
+        Ok(heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|(doc_count, value)| TermsBucket {
+                value: String::from_utf8_lossy(&value).into_owned(),
+                doc_count: doc_count.0,
+            })
+            .collect())
+    }
+}
+
+/// Segment collector of the `TermsAggregation`.
+pub struct TermsAggregationSegmentCollector {
+    reader: BytesFastFieldReader,
+    doc_counts: HashMap<Vec<u8>, u64>,
+}
+
+impl SegmentCollector for TermsAggregationSegmentCollector {
+    type Fruit = HashMap<Vec<u8>, u64>;
+
+    fn collect(&mut self, doc: DocId, _: Score) {
+        let value = self.reader.get_bytes(doc);
+        if value.is_empty() {
+            return;
+        }
+        if let Some(doc_count) = self.doc_counts.get_mut(value) {
+            *doc_count += 1;
+        } else {
+            self.doc_counts.insert(value.to_vec(), 1);
+        }
+    }
+
+    fn harvest(self) -> HashMap<Vec<u8>, u64> {
+        self.doc_counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TermsAggregation, TermsBucket};
+    use crate::collector::TopDocs;
+    use crate::query::{AllQuery, TermQuery};
+    use crate::schema::{IndexRecordOption, Schema, FAST, STRING};
+    use crate::{Index, Term};
+
+    fn bucket(value: &str, doc_count: u64) -> TermsBucket {
+        TermsBucket {
+            value: value.to_string(),
+            doc_count,
+        }
+    }
+
+    #[test]
+    fn test_terms_aggregation() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let color = schema_builder.add_text_field("color", STRING);
+        let category = schema_builder.add_bytes_field("category", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        let docs = [
+            ("red", "sofa"),
+            ("red", "chair"),
+            ("blue", "chair"),
+            ("red", "lamp"),
+            ("red", "sofa"),
+            ("red", ""),
+            ("red", "chair"),
+            ("red", "bed"),
+        ];
+        for (doc_id, (doc_color, doc_category)) in docs.iter().enumerate() {
+            index_writer.add_document(doc!(
+                color => *doc_color,
+                category => doc_category.as_bytes()
+            ));
+            if doc_id == 3 {
+                // Splits the documents into two segments.
+                index_writer.commit()?;
+            }
+        }
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 2);
+        let red = TermQuery::new(
+            Term::from_field_text(color, "red"),
+            IndexRecordOption::Basic,
+        );
+        assert_eq!(
+            searcher.search(&red, &TermsAggregation::new(category, 3))?,
+            vec![bucket("chair", 2), bucket("sofa", 2), bucket("bed", 1)]
+        );
+        assert_eq!(
+            searcher.search(&AllQuery, &TermsAggregation::new(category, 10))?,
+            vec![
+                bucket("chair", 3),
+                bucket("sofa", 2),
+                bucket("bed", 1),
+                bucket("lamp", 1)
+            ]
+        );
+        let (top_docs, buckets) = searcher.search(
+            &red,
+            &(TopDocs::with_limit(2), TermsAggregation::new(category, 1)),
+        )?;
+        assert_eq!(top_docs.len(), 2);
+        assert_eq!(buckets, vec![bucket("chair", 2)]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_terms_aggregation_requires_bytes_fast_field() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let color = schema_builder.add_text_field("color", STRING);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(color => "red"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert!(searcher
+            .search(&AllQuery, &TermsAggregation::new(color, 10))
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "at least 1")]
+    fn test_terms_aggregation_size_zero() {
+        let mut schema_builder = Schema::builder();
+        let category = schema_builder.add_bytes_field("category", FAST);
+        TermsAggregation::new(category, 0);
+    }
+}
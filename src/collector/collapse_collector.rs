@@ -0,0 +1,274 @@
+use crate::collector::{Collector, SegmentCollector, TopDocs};
+use crate::fastfield::{BytesFastFieldReader, FastFieldReader};
+use crate::schema::{Field, FieldType};
+use crate::{DocAddress, DocId, Score, SegmentLocalId, SegmentReader};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+/// The best document of a group, and the number of documents in the group.
+type Group = (Score, DocAddress, u64);
+
+/// The top documents of a [`CollapseCollector`](./struct.CollapseCollector.html).
+#[derive(Clone, Debug, PartialEq)]
+pub struct CollapseResult {
+    /// The best document of each of the top groups, sorted by decreasing score.
+    pub top_docs: Vec<(Score, DocAddress)>,
+    /// The number of matching documents in the group of each of the `top_docs`.
+    pub group_doc_counts: Vec<u64>,
+}
+
+/// Collector returning the top documents, keeping at most one document
+/// per value of a fast field.
+///
+/// This is the equivalent of the `collapse` parameter of Elasticsearch:
+/// matching documents are grouped by the value of `field`, and each group
+/// is represented by its best scoring document. The groups are then ranked
+/// by the score of their representative, with the limit and the offset of `top_docs`.
+///
+/// The field must be a `u64` fast field or a bytes fast field.
+/// With a bytes field, the documents without value are grouped together.
+///
+/// ```rust
+/// use tantivy::collector::{CollapseCollector, TopDocs};
+/// use tantivy::query::QueryParser;
+/// use tantivy::schema::{Schema, FAST, TEXT};
+/// use tantivy::{doc, DocAddress, Index};
+///
+/// # fn main() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let title = schema_builder.add_text_field("title", TEXT);
+/// let product = schema_builder.add_u64_field("product", FAST);
+/// let schema = schema_builder.build();
+/// let index = Index::create_in_ram(schema);
+///
+/// let mut index_writer = index.writer(3_000_000)?;
+/// index_writer.add_document(doc!(title => "Red sofa", product => 1u64));
+/// index_writer.add_document(doc!(title => "Red sofa, red cushions", product => 1u64));
+/// index_writer.add_document(doc!(title => "Red chair", product => 2u64));
+/// index_writer.commit()?;
+///
+/// let searcher = index.reader()?.searcher();
+/// let query = QueryParser::for_index(&index, vec![title]).parse_query("red")?;
+/// let collapse_collector = CollapseCollector::new(product, TopDocs::with_limit(10));
+/// let collapse_result = searcher.search(&query, &collapse_collector)?;
+/// assert_eq!(collapse_result.top_docs.len(), 2);
+/// assert_eq!(collapse_result.top_docs[0].1, DocAddress(0, 1));
+/// assert_eq!(collapse_result.group_doc_counts[0], 2);
+/// assert_eq!(collapse_result.top_docs[1].1, DocAddress(0, 2));
+/// assert_eq!(collapse_result.group_doc_counts[1], 1);
+/// # Ok(())
+/// # }
+/// ```
+pub struct CollapseCollector {
+    field: Field,
+    top_docs: TopDocs,
+}
+
+impl CollapseCollector {
+    /// Creates a `CollapseCollector` grouping documents by the value of `field`.
+    pub fn new(field: Field, top_docs: TopDocs) -> CollapseCollector {
+        CollapseCollector { field, top_docs }
+    }
+}
+
+enum GroupReader {
+    U64(FastFieldReader<u64>),
+    Bytes(BytesFastFieldReader),
+}
+
+impl Collector for CollapseCollector {
+    type Fruit = CollapseResult;
+
+    type Child = CollapseSegmentCollector;
+
+    fn for_segment(
+        &self,
+        segment_ord: SegmentLocalId,
+        reader: &SegmentReader,
+    ) -> crate::Result<CollapseSegmentCollector> {
+        let fast_fields = reader.fast_fields();
+        let group_reader = match reader.schema().get_field_entry(self.field).field_type() {
+            FieldType::Bytes(_) => GroupReader::Bytes(fast_fields.bytes(self.field)?),
+            _ => GroupReader::U64(fast_fields.u64(self.field)?),
+        };
+        Ok(CollapseSegmentCollector {
+            segment_ord,
+            group_reader,
+            groups: HashMap::new(),
+        })
+    }
+
+    fn requires_scoring(&self) -> bool {
+        true
+    }
+
+    fn merge_fruits(
+        &self,
+        segment_groups: Vec<HashMap<Vec<u8>, Group>>,
+    ) -> crate::Result<CollapseResult> {
+        let mut segment_groups_it = segment_groups.into_iter();
+        let mut groups = segment_groups_it.next().unwrap_or_default();
+        for segment_groups in segment_groups_it {
+            for (group_value, segment_group) in segment_groups {
+                match groups.entry(group_value) {
+                    Entry::Occupied(mut entry) => merge_group(entry.get_mut(), segment_group),
+                    Entry::Vacant(entry) => {
+                        entry.insert(segment_group);
+                    }
+                }
+            }
+        }
+        let mut group_doc_count_per_doc = HashMap::with_capacity(groups.len());
+        let mut best_docs = Vec::with_capacity(groups.len());
+        for (_, (score, doc_address, group_doc_count)) in groups {
+            group_doc_count_per_doc.insert(doc_address, group_doc_count);
+            best_docs.push((score, doc_address));
+        }
+        let top_docs = self.top_docs.0.merge_fruits(vec![best_docs])?;
+        let group_doc_counts = top_docs
+            .iter()
+            .map(|(_, doc_address)| group_doc_count_per_doc[doc_address])
+            .collect();
+        Ok(CollapseResult {
+            top_docs,
+            group_doc_counts,
+        })
+    }
+}
+
+/// Merges `other` into `group`, keeping the best document of the two
+/// and breaking ties by `DocAddress`.
+fn merge_group(group: &mut Group, other: Group) {
+    let (other_score, other_doc_address, other_count) = other;
+    group.2 += other_count;
+    if other_score > group.0 || (other_score == group.0 && other_doc_address < group.1) {
+        group.0 = other_score;
+        group.1 = other_doc_address;
+    }
+}
+
+/// Segment collector of the `CollapseCollector`.
+pub struct CollapseSegmentCollector {
+    segment_ord: SegmentLocalId,
+    group_reader: GroupReader,
+    groups: HashMap<Vec<u8>, Group>,
+}
+
+impl SegmentCollector for CollapseSegmentCollector {
+    type Fruit = HashMap<Vec<u8>, Group>;
+
+    fn collect(&mut self, doc: DocId, score: Score) {
+        let u64_bytes;
+        let group_value: &[u8] = match &self.group_reader {
+            GroupReader::U64(reader) => {
+                u64_bytes = reader.get(doc).to_be_bytes();
+                &u64_bytes[..]
+            }
+            GroupReader::Bytes(reader) => reader.get_bytes(doc),
+        };
+        let group = (score, DocAddress(self.segment_ord, doc), 1);
+        if let Some(best_group) = self.groups.get_mut(group_value) {
+            merge_group(best_group, group);
+        } else {
+            self.groups.insert(group_value.to_vec(), group);
+        }
+    }
+
+    fn harvest(self) -> HashMap<Vec<u8>, Group> {
+        self.groups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CollapseCollector;
+    use crate::collector::{Count, TopDocs};
+    use crate::query::{AllQuery, QueryParser};
+    use crate::schema::{Schema, FAST, TEXT};
+    use crate::{DocAddress, Index};
+
+    #[test]
+    fn test_collapse_collector_u64() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let product = schema_builder.add_u64_field("product", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title => "sofa", product => 1u64));
+        index_writer.add_document(doc!(title => "sofa sofa sofa", product => 2u64));
+        index_writer.add_document(doc!(title => "sofa sofa", product => 1u64));
+        index_writer.commit()?;
+        index_writer.add_document(doc!(title => "sofa sofa sofa sofa", product => 1u64));
+        index_writer.add_document(doc!(title => "sofa", product => 3u64));
+        index_writer.add_document(doc!(title => "chair", product => 3u64));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 2);
+        let query = QueryParser::for_index(&index, vec![title]).parse_query("sofa")?;
+
+        let collapse_collector = CollapseCollector::new(product, TopDocs::with_limit(10));
+        let (count, collapse_result) = searcher.search(&query, &(Count, collapse_collector))?;
+        assert_eq!(count, 5);
+        let doc_addresses: Vec<DocAddress> = collapse_result
+            .top_docs
+            .iter()
+            .map(|(_, doc_address)| *doc_address)
+            .collect();
+        assert_eq!(
+            doc_addresses,
+            vec![DocAddress(1, 0), DocAddress(0, 1), DocAddress(1, 1)]
+        );
+        assert_eq!(collapse_result.group_doc_counts, vec![3, 1, 1]);
+        let top_docs = searcher.search(&query, &TopDocs::with_limit(1))?;
+        assert_eq!(collapse_result.top_docs[0], top_docs[0]);
+
+        let collapse_collector =
+            CollapseCollector::new(product, TopDocs::with_limit(1).and_offset(1));
+        let collapse_result = searcher.search(&query, &collapse_collector)?;
+        assert_eq!(collapse_result.top_docs.len(), 1);
+        assert_eq!(collapse_result.top_docs[0].1, DocAddress(0, 1));
+        assert_eq!(collapse_result.group_doc_counts, vec![1]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_collapse_collector_bytes() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let group = schema_builder.add_bytes_field("group", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        for group_value in &["a", "b", "a", "", "a", ""] {
+            index_writer.add_document(doc!(group => group_value.as_bytes()));
+        }
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let collapse_collector = CollapseCollector::new(group, TopDocs::with_limit(10));
+        let collapse_result = searcher.search(&AllQuery, &collapse_collector)?;
+        // All of the scores are equal: groups are sorted by the address of their first document.
+        let doc_addresses: Vec<DocAddress> = collapse_result
+            .top_docs
+            .iter()
+            .map(|(_, doc_address)| *doc_address)
+            .collect();
+        assert_eq!(
+            doc_addresses,
+            vec![DocAddress(0, 0), DocAddress(0, 1), DocAddress(0, 3)]
+        );
+        assert_eq!(collapse_result.group_doc_counts, vec![3, 1, 2]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_collapse_collector_requires_fast_field() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title => "sofa"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let collapse_collector = CollapseCollector::new(title, TopDocs::with_limit(10));
+        assert!(searcher.search(&AllQuery, &collapse_collector).is_err());
+        Ok(())
+    }
+}
@@ -25,7 +25,7 @@ impl<'a> Eq for Hit<'a> {}
 
 impl<'a> PartialEq<Hit<'a>> for Hit<'a> {
     fn eq(&self, other: &Hit<'_>) -> bool {
-        self.count == other.count
+        self.count == other.count && self.facet == other.facet
     }
 }
 
@@ -37,7 +37,10 @@ impl<'a> PartialOrd<Hit<'a>> for Hit<'a> {
 
 impl<'a> Ord for Hit<'a> {
     fn cmp(&self, other: &Self) -> Ordering {
-        other.count.cmp(&self.count)
+        other
+            .count
+            .cmp(&self.count)
+            .then_with(|| self.facet.cmp(other.facet))
     }
 }
 
@@ -76,6 +79,11 @@ fn facet_depth(facet_bytes: &[u8]) -> usize {
 /// This implementation assumes you are working with a number of facets that
 /// is much hundreds of time lower than your number of documents.
 ///
+/// To count the values of a flat keyword field rather than a hierarchy of
+/// facets, index them in a bytes fast field and use
+/// [`TermsAggregation`](./struct.TermsAggregation.html), which returns
+/// the most frequent values among the matching documents.
+///
 ///
 /// ```rust
 /// use tantivy::collector::FacetCollector;
@@ -420,6 +428,9 @@ impl FacetCounts {
     }
 
     /// Returns a vector of top `k` facets with their counts, sorted highest-to-lowest by counts.
+    /// Facets with the same count are sorted by facet.
+    ///
+    /// The facets are selected with a heap of size `k`, in `O(n log k)` for `n` children.
     /// See the documentation for `FacetCollector` for a usage example.
     pub fn top_k<T>(&self, facet: T, k: usize) -> Vec<(&Facet, u64)>
     where
@@ -657,6 +668,44 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_facet_collector_topk_ties() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let facet_field = schema_builder.add_facet_field("facet");
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        for (facet, count) in &[("d", 2), ("b", 1), ("a", 2), ("c", 1), ("e", 3)] {
+            for _ in 0..*count {
+                index_writer
+                    .add_document(doc!(facet_field => Facet::from(&format!("/facet/{}", facet))));
+            }
+        }
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let mut facet_collector = FacetCollector::for_field(facet_field);
+        facet_collector.add_facet("/facet");
+        let counts: FacetCounts = searcher.search(&AllQuery, &facet_collector)?;
+        let top_facets = |k: usize| -> Vec<(String, u64)> {
+            counts
+                .top_k("/facet", k)
+                .into_iter()
+                .map(|(facet, count)| (facet.to_string(), count))
+                .collect()
+        };
+        assert_eq!(
+            top_facets(4),
+            vec![
+                ("/facet/e".to_string(), 3),
+                ("/facet/a".to_string(), 2),
+                ("/facet/d".to_string(), 2),
+                ("/facet/b".to_string(), 1),
+            ]
+        );
+        assert_eq!(top_facets(10).len(), 5);
+        assert!(top_facets(0).is_empty());
+        Ok(())
+    }
 }
 
 #[cfg(all(test, feature = "unstable"))]
@@ -0,0 +1,240 @@
+use crate::collector::{CustomScorer, CustomSegmentScorer};
+use crate::fastfield::FastFieldReader;
+use crate::schema::{Field, GeoPoint, EARTH_RADIUS_KM};
+use crate::{DocId, SegmentReader};
+use std::cmp::Ordering;
+
+/// Formula used to compute the distance between two geo points.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DistanceFormula {
+    /// Great-circle distance, computed with the haversine formula.
+    Haversine,
+    /// Equirectangular approximation.
+    ///
+    /// It is cheaper to compute than the haversine formula, and its error
+    /// is negligible for distances of up to a few hundred kilometers,
+    /// away from the poles.
+    Equirectangular,
+}
+
+impl Default for DistanceFormula {
+    /// The distance is computed with the haversine formula by default.
+    fn default() -> DistanceFormula {
+        DistanceFormula::Haversine
+    }
+}
+
+impl DistanceFormula {
+    /// Returns the distance between `left` and `right`, in meters.
+    pub fn distance_meters(self, left: &GeoPoint, right: &GeoPoint) -> f64 {
+        match self {
+            DistanceFormula::Haversine => left.distance_km(right) * 1_000.0,
+            DistanceFormula::Equirectangular => {
+                let mut delta_lon = right.lon - left.lon;
+                if delta_lon > 180.0 {
+                    delta_lon -= 360.0;
+                } else if delta_lon < -180.0 {
+                    delta_lon += 360.0;
+                }
+                let mean_lat = ((left.lat + right.lat) / 2.0).to_radians();
+                let x = delta_lon.to_radians() * mean_lat.cos();
+                let y = (right.lat - left.lat).to_radians();
+                EARTH_RADIUS_KM * 1_000.0 * (x * x + y * y).sqrt()
+            }
+        }
+    }
+}
+
+/// Distance of a document to the origin of a
+/// [`GeoDistanceSortField`](./struct.GeoDistanceSortField.html).
+///
+/// `GeoDistance`s are ordered so that the closest document is the greatest:
+/// [`TopDocs`](./struct.TopDocs.html) then returns the closest documents first.
+/// Documents without a geo point are sorted last.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GeoDistance(Option<f64>);
+
+impl GeoDistance {
+    /// Returns the distance in meters, or `None` if the document does not have a geo point.
+    pub fn meters(&self) -> Option<f64> {
+        self.0
+    }
+}
+
+impl PartialOrd for GeoDistance {
+    fn partial_cmp(&self, other: &GeoDistance) -> Option<Ordering> {
+        match (self.0, other.0) {
+            (Some(left), Some(right)) => right.partial_cmp(&left),
+            (Some(_), None) => Some(Ordering::Greater),
+            (None, Some(_)) => Some(Ordering::Less),
+            (None, None) => Some(Ordering::Equal),
+        }
+    }
+}
+
+/// Sorts documents by their distance to an origin point, the closest first.
+///
+/// The field must be a geo point fast field. `GeoDistanceSortField` is a
+/// [`CustomScorer`](./trait.CustomScorer.html), to be passed to
+/// [`TopDocs::custom_score`](./struct.TopDocs.html#method.custom_score).
+///
+/// ```rust
+/// use tantivy::collector::{GeoDistanceSortField, TopDocs};
+/// use tantivy::query::AllQuery;
+/// use tantivy::schema::{GeoPoint, Schema, FAST};
+/// use tantivy::{doc, DocAddress, Index};
+///
+/// # fn main() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let location = schema_builder.add_geo_point_field("location", FAST);
+/// let index = Index::create_in_ram(schema_builder.build());
+/// let mut index_writer = index.writer(3_000_000)?;
+/// index_writer.add_document(doc!(location => GeoPoint::new(51.5074, -0.1278)));
+/// index_writer.add_document(doc!(location => GeoPoint::new(50.8503, 4.3517)));
+/// index_writer.commit()?;
+///
+/// let searcher = index.reader()?.searcher();
+/// let paris = GeoPoint::new(48.8566, 2.3522);
+/// let top_docs = TopDocs::with_limit(2).custom_score(GeoDistanceSortField::new(location, paris));
+/// let closest_docs = searcher.search(&AllQuery, &top_docs)?;
+/// assert_eq!(closest_docs[0].1, DocAddress(0, 1));
+/// let distance_km = closest_docs[0].0.meters().unwrap() / 1_000.0;
+/// assert!((distance_km - 264.0).abs() < 1.0);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct GeoDistanceSortField {
+    field: Field,
+    origin: GeoPoint,
+    formula: DistanceFormula,
+}
+
+impl GeoDistanceSortField {
+    /// Creates a `GeoDistanceSortField` sorting documents by the distance
+    /// between their point in `field` and `origin`.
+    ///
+    /// Distances are computed with the haversine formula.
+    pub fn new(field: Field, origin: GeoPoint) -> GeoDistanceSortField {
+        GeoDistanceSortField {
+            field,
+            origin,
+            formula: DistanceFormula::default(),
+        }
+    }
+
+    /// Sets the formula used to compute the distances.
+    pub fn with_formula(mut self, formula: DistanceFormula) -> GeoDistanceSortField {
+        self.formula = formula;
+        self
+    }
+
+    /// Field to sort by.
+    pub fn field(&self) -> Field {
+        self.field
+    }
+
+    /// The point distances are computed from.
+    pub fn origin(&self) -> GeoPoint {
+        self.origin
+    }
+}
+
+impl CustomScorer<GeoDistance> for GeoDistanceSortField {
+    type Child = GeoDistanceSegmentScorer;
+
+    fn segment_scorer(&self, segment_reader: &SegmentReader) -> crate::Result<Self::Child> {
+        Ok(GeoDistanceSegmentScorer {
+            fast_field_reader: segment_reader.fast_fields().geo_point(self.field)?,
+            origin: self.origin,
+            formula: self.formula,
+        })
+    }
+}
+
+/// Segment scorer of a [`GeoDistanceSortField`](./struct.GeoDistanceSortField.html).
+pub struct GeoDistanceSegmentScorer {
+    fast_field_reader: FastFieldReader<u64>,
+    origin: GeoPoint,
+    formula: DistanceFormula,
+}
+
+impl CustomSegmentScorer<GeoDistance> for GeoDistanceSegmentScorer {
+    fn score(&mut self, doc: DocId) -> GeoDistance {
+        let distance = GeoPoint::from_u64(self.fast_field_reader.get(doc))
+            .map(|geo_point| self.formula.distance_meters(&self.origin, &geo_point));
+        GeoDistance(distance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DistanceFormula, GeoDistance, GeoDistanceSortField};
+    use crate::collector::TopDocs;
+    use crate::query::AllQuery;
+    use crate::schema::{GeoPoint, Schema, FAST, STORED, STRING};
+    use crate::Index;
+
+    #[test]
+    fn test_geo_distance_ordering() {
+        assert!(GeoDistance(Some(1.0)) > GeoDistance(Some(2.0)));
+        assert!(GeoDistance(Some(1_000_000.0)) > GeoDistance(None));
+        assert!(GeoDistance(None) <= GeoDistance(None));
+    }
+
+    #[test]
+    fn test_distance_formulas() {
+        let paris = GeoPoint::new(48.8566, 2.3522);
+        let versailles = GeoPoint::new(48.8049, 2.1204);
+        let haversine = DistanceFormula::Haversine.distance_meters(&paris, &versailles);
+        let equirectangular = DistanceFormula::Equirectangular.distance_meters(&paris, &versailles);
+        assert!((haversine - 17_915.0).abs() < 1.0);
+        assert!((haversine - equirectangular).abs() < 1.0);
+        let suva = GeoPoint::new(-18.1416, 178.4419);
+        let apia = GeoPoint::new(-13.8333, -171.7667);
+        let haversine = DistanceFormula::Haversine.distance_meters(&suva, &apia);
+        let equirectangular = DistanceFormula::Equirectangular.distance_meters(&suva, &apia);
+        assert!((haversine - equirectangular).abs() / haversine < 0.01);
+    }
+
+    #[test]
+    fn test_geo_distance_sort() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let name = schema_builder.add_text_field("name", STRING | STORED);
+        let location = schema_builder.add_geo_point_field("location", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer
+            .add_document(doc!(name => "new_york", location => GeoPoint::new(40.7128, -74.0060)));
+        index_writer.add_document(doc!(name => "nowhere"));
+        index_writer
+            .add_document(doc!(name => "london", location => GeoPoint::new(51.5074, -0.1278)));
+        index_writer.commit()?;
+        index_writer
+            .add_document(doc!(name => "brussels", location => GeoPoint::new(50.8503, 4.3517)));
+        index_writer
+            .add_document(doc!(name => "paris", location => GeoPoint::new(48.8566, 2.3522)));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let paris = GeoPoint::new(48.8566, 2.3522);
+        for &formula in &[DistanceFormula::Haversine, DistanceFormula::Equirectangular] {
+            let sort_field = GeoDistanceSortField::new(location, paris).with_formula(formula);
+            let top_docs =
+                searcher.search(&AllQuery, &TopDocs::with_limit(5).custom_score(sort_field))?;
+            let names: Vec<String> = top_docs
+                .iter()
+                .map(|(_, doc_address)| {
+                    let doc = searcher.doc(*doc_address).unwrap();
+                    doc.get_first(name).unwrap().text().unwrap().to_string()
+                })
+                .collect();
+            assert_eq!(
+                names,
+                vec!["paris", "brussels", "london", "new_york", "nowhere"]
+            );
+            assert!(top_docs[0].0.meters().unwrap() < 1.0);
+            assert_eq!(top_docs[4].0.meters(), None);
+        }
+        Ok(())
+    }
+}
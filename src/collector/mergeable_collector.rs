@@ -0,0 +1,154 @@
+use crate::core::Searcher;
+use crate::SegmentLocalId;
+use crate::SegmentReader;
+
+/// Collectors computing a partial fruit for each segment, and merging these
+/// partial fruits two by two.
+///
+/// A [`Collector`](./trait.Collector.html) only merges its segment fruits once
+/// all of the segments have been collected, in the calling thread.
+/// With [`Searcher::search_parallel`](../struct.Searcher.html#method.search_parallel),
+/// the partial fruits of a `MergeableCollector` are instead merged in the threads
+/// of the search executor, as soon as two of them are available.
+///
+/// The collector is in charge of the collection of a segment as a whole:
+/// it may for instance run its own query, or scan a fast field.
+///
+/// Segments are not guaranteed to be visited, nor their partial fruits to be merged,
+/// in any specific order: `merge` is expected to be associative and commutative.
+///
+/// ```rust
+/// use tantivy::collector::MergeableCollector;
+/// use tantivy::schema::{Field, Schema, FAST};
+/// use tantivy::{doc, Index, Searcher, SegmentLocalId, SegmentReader};
+///
+/// /// Computes the sum of the values of a fast field.
+/// struct FastFieldSum(Field);
+///
+/// impl MergeableCollector for FastFieldSum {
+///     type PartialFruit = u64;
+///
+///     fn collect_segment(
+///         &self,
+///         _searcher: &Searcher,
+///         _segment_ord: SegmentLocalId,
+///         segment_reader: &SegmentReader,
+///     ) -> tantivy::Result<u64> {
+///         let fast_field_reader = segment_reader.fast_fields().u64(self.0)?;
+///         Ok(segment_reader
+///             .doc_ids_alive()
+///             .map(|doc| fast_field_reader.get(doc))
+///             .sum())
+///     }
+///
+///     fn merge(&self, left: u64, right: u64) -> u64 {
+///         left + right
+///     }
+/// }
+///
+/// # fn main() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let price = schema_builder.add_u64_field("price", FAST);
+/// let index = Index::create_in_ram(schema_builder.build());
+/// let mut index_writer = index.writer(3_000_000)?;
+/// index_writer.add_document(doc!(price => 3u64));
+/// index_writer.commit()?;
+/// index_writer.add_document(doc!(price => 4u64));
+/// index_writer.commit()?;
+///
+/// let searcher = index.reader()?.searcher();
+/// assert_eq!(searcher.search_parallel(&FastFieldSum(price))?, Some(7));
+/// # Ok(())
+/// # }
+/// ```
+pub trait MergeableCollector: Sync {
+    /// Result of the collection of one or several segments.
+    type PartialFruit: Send;
+
+    /// Collects the segment `segment_reader`, of ordinal `segment_ord` in the `searcher`.
+    fn collect_segment(
+        &self,
+        searcher: &Searcher,
+        segment_ord: SegmentLocalId,
+        segment_reader: &SegmentReader,
+    ) -> crate::Result<Self::PartialFruit>;
+
+    /// Merges two partial fruits into one.
+    fn merge(&self, left: Self::PartialFruit, right: Self::PartialFruit) -> Self::PartialFruit;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MergeableCollector;
+    use crate::collector::DocSetCollector;
+    use crate::query::{Query, TermQuery};
+    use crate::schema::{IndexRecordOption, Schema, TEXT};
+    use crate::{DocAddress, Index, Searcher, SegmentLocalId, SegmentReader, Term};
+
+    /// Collects the addresses of the documents matching a term.
+    struct MatchingDocAddresses(TermQuery);
+
+    impl MergeableCollector for MatchingDocAddresses {
+        type PartialFruit = Vec<DocAddress>;
+
+        fn collect_segment(
+            &self,
+            searcher: &Searcher,
+            segment_ord: SegmentLocalId,
+            segment_reader: &SegmentReader,
+        ) -> crate::Result<Vec<DocAddress>> {
+            let weight = self.0.weight(searcher, false)?;
+            let mut doc_addresses = Vec::new();
+            weight.for_each(segment_reader, &mut |doc, _score| {
+                if segment_reader.is_deleted(doc) {
+                    return;
+                }
+                doc_addresses.push(DocAddress(segment_ord, doc));
+            })?;
+            Ok(doc_addresses)
+        }
+
+        fn merge(&self, mut left: Vec<DocAddress>, right: Vec<DocAddress>) -> Vec<DocAddress> {
+            left.extend(right);
+            left
+        }
+    }
+
+    #[test]
+    fn test_search_parallel() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let mut index = Index::create_in_ram(schema_builder.build());
+        index.set_multithread_executor(3)?;
+        let collector = MatchingDocAddresses(TermQuery::new(
+            Term::from_field_text(text, "a"),
+            IndexRecordOption::Basic,
+        ));
+        assert_eq!(
+            index.reader()?.searcher().search_parallel(&collector)?,
+            None
+        );
+
+        let mut index_writer = index.writer_for_tests()?;
+        for i in 0..5 {
+            index_writer.add_document(doc!(text => "a"));
+            index_writer.add_document(doc!(text => "b"));
+            if i % 2 == 0 {
+                index_writer.add_document(doc!(text => "a b"));
+            }
+            index_writer.commit()?;
+        }
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 5);
+        let mut doc_addresses = searcher.search_parallel(&collector)?.unwrap();
+        doc_addresses.sort();
+        let mut expected: Vec<DocAddress> = searcher
+            .search(&collector.0, &DocSetCollector)?
+            .into_iter()
+            .collect();
+        expected.sort();
+        assert_eq!(doc_addresses.len(), 8);
+        assert_eq!(doc_addresses, expected);
+        Ok(())
+    }
+}
@@ -9,6 +9,7 @@ Your fruit could for instance be :
 - [the count of matching documents](./struct.Count.html)
 - [the top 10 documents, by relevancy or by a fast field](./struct.TopDocs.html)
 - [facet counts](./struct.FacetCollector.html)
+- [statistics over a fast field](./struct.AggregationCollector.html)
 
 At one point in your code, you will trigger the actual search operation by calling
 [the `search(...)` method of your `Searcher` object](../struct.Searcher.html#method.search).
@@ -97,6 +98,7 @@ mod multi_collector;
 pub use self::multi_collector::MultiCollector;
 
 mod top_collector;
+pub(crate) use self::top_collector::ComparableDoc;
 
 mod top_score_collector;
 pub use self::top_score_collector::TopDocs;
@@ -112,12 +114,51 @@ pub use self::facet_collector::FacetCollector;
 pub use self::facet_collector::FacetCounts;
 use crate::query::Weight;
 
+mod numeric_range_facet_collector;
+pub use self::numeric_range_facet_collector::{
+    NumericRangeFacetCollector, NumericRangeFacetSegmentCollector, RangeBucket,
+};
+
 mod docset_collector;
 pub use self::docset_collector::DocSetCollector;
 
 mod filter_collector_wrapper;
 pub use self::filter_collector_wrapper::FilterCollector;
 
+mod mergeable_collector;
+pub use self::mergeable_collector::MergeableCollector;
+
+mod search_meta_collector;
+pub use self::search_meta_collector::{
+    SearchMeta, SearchMetaCollector, SearchMetaSegmentCollector,
+};
+
+mod timeout_collector;
+pub use self::timeout_collector::{TimeoutCollector, TimeoutResult, TimeoutSegmentCollector};
+
+mod collapse_collector;
+pub use self::collapse_collector::{CollapseCollector, CollapseResult, CollapseSegmentCollector};
+
+mod sort_collector;
+pub(crate) use self::sort_collector::{check_sort_field, sort_value_reader};
+pub use self::sort_collector::{Order, SortCollector, SortKey, SortSegmentCollector};
+
+mod geo_distance_sort;
+pub use self::geo_distance_sort::{
+    DistanceFormula, GeoDistance, GeoDistanceSegmentScorer, GeoDistanceSortField,
+};
+
+mod aggregation;
+pub use self::aggregation::{
+    AggregationCollector, AggregationResult, AggregationSegmentCollector, CardinalityAggregation,
+    CardinalitySegmentCollector, DateBucket, DateHistogramCollector, DateHistogramSegmentCollector,
+    DateInterval, HistogramAggregation, HistogramAggregationSegmentCollector, HistogramBucket,
+    HyperLogLog, MatchingDocs, MetricAggregation, MetricStats, PercentilesAggregation,
+    PercentilesResult, PercentilesSegmentCollector, SignificanceHeuristic, SignificantTerm,
+    SignificantTermsAggregation, SignificantTermsSegmentCollector, TDigest, TermsAggregation,
+    TermsAggregationSegmentCollector, TermsBucket,
+};
+
 /// `Fruit` is the type for the result of our collection.
 /// e.g. `usize` for the `Count` collector.
 pub trait Fruit: Send + downcast_rs::Downcast {}
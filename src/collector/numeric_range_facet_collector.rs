@@ -0,0 +1,247 @@
+use crate::collector::{Collector, SegmentCollector};
+use crate::fastfield::FastFieldReader;
+use crate::schema::Field;
+use crate::{DocId, Score, SegmentLocalId, SegmentReader};
+use std::ops::Bound;
+
+/// A labelled range of values, counted by the
+/// [`NumericRangeFacetCollector`](./struct.NumericRangeFacetCollector.html).
+#[derive(Clone, Debug, PartialEq)]
+pub struct RangeBucket<T> {
+    /// Label of the range, returned along with its count.
+    pub label: String,
+    /// Lower bound of the range.
+    pub lower: Bound<T>,
+    /// Upper bound of the range.
+    pub upper: Bound<T>,
+}
+
+impl<T: PartialOrd> RangeBucket<T> {
+    /// Creates a range labelled `label`, going from `lower` to `upper`.
+    pub fn new(label: &str, lower: Bound<T>, upper: Bound<T>) -> RangeBucket<T> {
+        RangeBucket {
+            label: label.to_string(),
+            lower,
+            upper,
+        }
+    }
+
+    /// Returns true if `value` is within the range.
+    pub fn contains(&self, value: &T) -> bool {
+        let above_lower = match &self.lower {
+            Bound::Included(lower) => value >= lower,
+            Bound::Excluded(lower) => value > lower,
+            Bound::Unbounded => true,
+        };
+        let below_upper = match &self.upper {
+            Bound::Included(upper) => value <= upper,
+            Bound::Excluded(upper) => value < upper,
+            Bound::Unbounded => true,
+        };
+        above_lower && below_upper
+    }
+}
+
+/// Collector counting the matching documents whose value for a `u64` fast field
+/// falls in each of a list of ranges.
+///
+/// The field must be a single-valued `u64` fast field. The ranges may overlap:
+/// a document is counted in all of the ranges containing its value.
+/// The counts are returned in the order of the ranges, along with their labels.
+///
+/// ```rust
+/// use tantivy::collector::{NumericRangeFacetCollector, RangeBucket};
+/// use tantivy::query::AllQuery;
+/// use tantivy::schema::{Schema, FAST};
+/// use tantivy::{doc, Index};
+/// use std::ops::Bound;
+///
+/// # fn main() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let price = schema_builder.add_u64_field("price", FAST);
+/// let schema = schema_builder.build();
+/// let index = Index::create_in_ram(schema);
+///
+/// let mut index_writer = index.writer(3_000_000)?;
+/// index_writer.add_document(doc!(price => 5u64));
+/// index_writer.add_document(doc!(price => 12u64));
+/// index_writer.add_document(doc!(price => 70u64));
+/// index_writer.commit()?;
+///
+/// let searcher = index.reader()?.searcher();
+/// let collector = NumericRangeFacetCollector::new(
+///     price,
+///     vec![
+///         RangeBucket::new("under 10", Bound::Unbounded, Bound::Excluded(10)),
+///         RangeBucket::new("10 to 50", Bound::Included(10), Bound::Excluded(50)),
+///         RangeBucket::new("50 and above", Bound::Included(50), Bound::Unbounded),
+///     ],
+/// );
+/// let counts = searcher.search(&AllQuery, &collector)?;
+/// assert_eq!(
+///     counts,
+///     vec![
+///         ("under 10".to_string(), 1),
+///         ("10 to 50".to_string(), 1),
+///         ("50 and above".to_string(), 1),
+///     ]
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct NumericRangeFacetCollector {
+    field: Field,
+    ranges: Vec<RangeBucket<u64>>,
+}
+
+impl NumericRangeFacetCollector {
+    /// Creates a collector counting the documents in each of `ranges`,
+    /// based on their value for `field`.
+    pub fn new(field: Field, ranges: Vec<RangeBucket<u64>>) -> NumericRangeFacetCollector {
+        NumericRangeFacetCollector { field, ranges }
+    }
+}
+
+impl Collector for NumericRangeFacetCollector {
+    type Fruit = Vec<(String, u64)>;
+
+    type Child = NumericRangeFacetSegmentCollector;
+
+    fn for_segment(
+        &self,
+        _: SegmentLocalId,
+        reader: &SegmentReader,
+    ) -> crate::Result<NumericRangeFacetSegmentCollector> {
+        let reader = reader.fast_fields().u64(self.field)?;
+        Ok(NumericRangeFacetSegmentCollector {
+            ranges: self.ranges.clone(),
+            reader,
+            counts: vec![0; self.ranges.len()],
+        })
+    }
+
+    fn requires_scoring(&self) -> bool {
+        false
+    }
+
+    fn merge_fruits(&self, segment_counts: Vec<Vec<u64>>) -> crate::Result<Vec<(String, u64)>> {
+        let mut counts = vec![0u64; self.ranges.len()];
+        for segment_counts in segment_counts {
+            for (count, segment_count) in counts.iter_mut().zip(segment_counts) {
+                *count += segment_count;
+            }
+        }
+        Ok(self
+            .ranges
+            .iter()
+            .map(|range| range.label.clone())
+            .zip(counts)
+            .collect())
+    }
+}
+
+/// Segment collector of the `NumericRangeFacetCollector`.
+pub struct NumericRangeFacetSegmentCollector {
+    ranges: Vec<RangeBucket<u64>>,
+    reader: FastFieldReader<u64>,
+    counts: Vec<u64>,
+}
+
+impl SegmentCollector for NumericRangeFacetSegmentCollector {
+    type Fruit = Vec<u64>;
+
+    fn collect(&mut self, doc: DocId, _: Score) {
+        let value = self.reader.get(doc);
+        for (range, count) in self.ranges.iter().zip(self.counts.iter_mut()) {
+            if range.contains(&value) {
+                *count += 1;
+            }
+        }
+    }
+
+    fn harvest(self) -> Vec<u64> {
+        self.counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NumericRangeFacetCollector, RangeBucket};
+    use crate::collector::Count;
+    use crate::query::{AllQuery, TermQuery};
+    use crate::schema::{IndexRecordOption, Schema, FAST, STRING};
+    use crate::{Index, Term};
+    use std::ops::Bound;
+
+    #[test]
+    fn test_range_bucket_contains() {
+        let range = RangeBucket::new("range", Bound::Excluded(3u64), Bound::Included(5u64));
+        assert!(!range.contains(&3));
+        assert!(range.contains(&4));
+        assert!(range.contains(&5));
+        assert!(!range.contains(&6));
+        let unbounded = RangeBucket::new("all", Bound::Unbounded, Bound::Unbounded);
+        assert!(unbounded.contains(&0u64));
+        assert!(unbounded.contains(&u64::MAX));
+    }
+
+    #[test]
+    fn test_numeric_range_facet_collector() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let color = schema_builder.add_text_field("color", STRING);
+        let price = schema_builder.add_u64_field("price", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(color => "red", price => 5u64));
+        index_writer.add_document(doc!(color => "red", price => 10u64));
+        index_writer.add_document(doc!(color => "blue", price => 20u64));
+        index_writer.commit()?;
+        index_writer.add_document(doc!(color => "red", price => 100u64));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 2);
+
+        let collector = NumericRangeFacetCollector::new(
+            price,
+            vec![
+                RangeBucket::new("expensive", Bound::Included(50), Bound::Unbounded),
+                RangeBucket::new("cheap", Bound::Unbounded, Bound::Excluded(10)),
+                RangeBucket::new("up to 20", Bound::Unbounded, Bound::Included(20)),
+                RangeBucket::new("empty", Bound::Excluded(20), Bound::Excluded(50)),
+            ],
+        );
+        let red = TermQuery::new(
+            Term::from_field_text(color, "red"),
+            IndexRecordOption::Basic,
+        );
+        let (count, red_counts) = searcher.search(&red, &(Count, collector.clone()))?;
+        assert_eq!(count, 3);
+        assert_eq!(
+            red_counts,
+            vec![
+                ("expensive".to_string(), 1),
+                ("cheap".to_string(), 1),
+                ("up to 20".to_string(), 2),
+                ("empty".to_string(), 0),
+            ]
+        );
+        let all_counts = searcher.search(&AllQuery, &collector)?;
+        assert_eq!(all_counts[2], ("up to 20".to_string(), 3));
+        Ok(())
+    }
+
+    #[test]
+    fn test_numeric_range_facet_collector_requires_u64_fast_field() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let color = schema_builder.add_text_field("color", STRING);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(color => "red"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let collector = NumericRangeFacetCollector::new(color, Vec::new());
+        assert!(searcher.search(&AllQuery, &collector).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,208 @@
+use crate::collector::{Collector, SegmentCollector};
+use crate::docset::{DocSet, TERMINATED};
+use crate::query::Weight;
+use crate::{DocId, Score, SegmentLocalId, SegmentReader};
+
+/// Fruit of a collector, with statistics about the execution of the query.
+///
+/// See [`SearchMetaCollector`](./struct.SearchMetaCollector.html).
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchMeta<TFruit> {
+    /// Fruit of the wrapped collector.
+    pub fruit: TFruit,
+    /// Number of documents matching the query that were passed to the wrapped collector.
+    /// Deleted documents are not counted.
+    pub docs_scored: u64,
+    /// Estimated number of documents skipped by the `DocSet`s of the query
+    /// without being visited, summed over all of its clauses.
+    ///
+    /// See [`DocSet::docs_skipped`](../trait.DocSet.html#method.docs_skipped).
+    pub docs_skipped: u64,
+}
+
+/// Collector wrapping another collector, to report how many documents were
+/// scored and skipped by the query.
+///
+/// This is meant to diagnose slow queries: for instance, an intersection
+/// of a rare and a frequent term skips most of the postings of the frequent term.
+///
+/// The query is run by iterating over its `Scorer`, so that its `DocSet`s can be observed.
+/// As a result, the `TopDocs` collector does not prune the documents that cannot make it
+/// into the top K when wrapped into a `SearchMetaCollector`: only use it for profiling.
+///
+/// ```rust
+/// use tantivy::collector::TopDocs;
+/// use tantivy::query::QueryParser;
+/// use tantivy::schema::{Schema, TEXT};
+/// use tantivy::{doc, Index};
+///
+/// # fn main() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let title = schema_builder.add_text_field("title", TEXT);
+/// let index = Index::create_in_ram(schema_builder.build());
+/// let mut index_writer = index.writer(3_000_000)?;
+/// index_writer.add_document(doc!(title => "The Name of the Wind"));
+/// index_writer.add_document(doc!(title => "The Diary of Muadib"));
+/// index_writer.commit()?;
+///
+/// let searcher = index.reader()?.searcher();
+/// let query = QueryParser::for_index(&index, vec![title]).parse_query("diary")?;
+/// let search_meta = searcher.search(&query, &TopDocs::with_limit(10).with_search_meta())?;
+/// assert_eq!(search_meta.fruit.len(), 1);
+/// assert_eq!(search_meta.docs_scored, 1);
+/// # Ok(())
+/// # }
+/// ```
+pub struct SearchMetaCollector<TCollector> {
+    collector: TCollector,
+}
+
+impl<TCollector: Collector> SearchMetaCollector<TCollector> {
+    /// Wraps `collector`.
+    pub fn new(collector: TCollector) -> SearchMetaCollector<TCollector> {
+        SearchMetaCollector { collector }
+    }
+}
+
+impl<TCollector: Collector> Collector for SearchMetaCollector<TCollector> {
+    type Fruit = SearchMeta<TCollector::Fruit>;
+
+    type Child = SearchMetaSegmentCollector<TCollector::Child>;
+
+    fn for_segment(
+        &self,
+        segment_local_id: SegmentLocalId,
+        segment: &SegmentReader,
+    ) -> crate::Result<Self::Child> {
+        let segment_collector = self.collector.for_segment(segment_local_id, segment)?;
+        Ok(SearchMetaSegmentCollector {
+            segment_collector,
+            docs_scored: 0u64,
+            docs_skipped: 0u64,
+        })
+    }
+
+    fn requires_scoring(&self) -> bool {
+        self.collector.requires_scoring()
+    }
+
+    fn merge_fruits(
+        &self,
+        segment_fruits: Vec<SearchMeta<<TCollector::Child as SegmentCollector>::Fruit>>,
+    ) -> crate::Result<Self::Fruit> {
+        let mut docs_scored = 0u64;
+        let mut docs_skipped = 0u64;
+        let mut fruits = Vec::with_capacity(segment_fruits.len());
+        for segment_fruit in segment_fruits {
+            docs_scored += segment_fruit.docs_scored;
+            docs_skipped += segment_fruit.docs_skipped;
+            fruits.push(segment_fruit.fruit);
+        }
+        Ok(SearchMeta {
+            fruit: self.collector.merge_fruits(fruits)?,
+            docs_scored,
+            docs_skipped,
+        })
+    }
+
+    fn collect_segment(
+        &self,
+        weight: &dyn Weight,
+        segment_ord: u32,
+        reader: &SegmentReader,
+    ) -> crate::Result<<Self::Child as SegmentCollector>::Fruit> {
+        let mut segment_collector = self.for_segment(segment_ord, reader)?;
+        let mut scorer = weight.scorer(reader, 1.0)?;
+        let delete_bitset_opt = reader.delete_bitset();
+        let mut doc = scorer.doc();
+        while doc != TERMINATED {
+            let is_alive = delete_bitset_opt
+                .map(|delete_bitset| delete_bitset.is_alive(doc))
+                .unwrap_or(true);
+            if is_alive {
+                segment_collector.collect(doc, scorer.score());
+            }
+            doc = scorer.advance();
+        }
+        segment_collector.docs_skipped = scorer.docs_skipped();
+        Ok(segment_collector.harvest())
+    }
+}
+
+/// Segment collector associated to the `SearchMetaCollector`.
+pub struct SearchMetaSegmentCollector<TSegmentCollector> {
+    segment_collector: TSegmentCollector,
+    docs_scored: u64,
+    docs_skipped: u64,
+}
+
+impl<TSegmentCollector: SegmentCollector> SegmentCollector
+    for SearchMetaSegmentCollector<TSegmentCollector>
+{
+    type Fruit = SearchMeta<TSegmentCollector::Fruit>;
+
+    fn collect(&mut self, doc: DocId, score: Score) {
+        self.docs_scored += 1;
+        self.segment_collector.collect(doc, score);
+    }
+
+    fn harvest(self) -> Self::Fruit {
+        SearchMeta {
+            fruit: self.segment_collector.harvest(),
+            docs_scored: self.docs_scored,
+            docs_skipped: self.docs_skipped,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SearchMetaCollector;
+    use crate::collector::{Count, TopDocs};
+    use crate::query::QueryParser;
+    use crate::schema::{Schema, TEXT};
+    use crate::{Index, Term};
+
+    #[test]
+    fn test_search_meta_collector() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        for doc in 0..1_000 {
+            if doc == 500 || doc == 900 {
+                index_writer.add_document(doc!(text => "frequent rare"));
+            } else {
+                index_writer.add_document(doc!(text => "frequent"));
+            }
+        }
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let query_parser = QueryParser::for_index(&index, vec![text]);
+
+        let query = query_parser.parse_query("+frequent +rare")?;
+        let search_meta = searcher.search(&query, &TopDocs::with_limit(10).with_search_meta())?;
+        assert_eq!(
+            search_meta.fruit,
+            searcher.search(&query, &TopDocs::with_limit(10))?
+        );
+        assert_eq!(search_meta.docs_scored, 2);
+        // The postings of `frequent` are skipped up to the 500th and 900th documents.
+        assert!(search_meta.docs_skipped >= 898);
+        assert!(search_meta.docs_skipped < 1_000);
+
+        let query = query_parser.parse_query("frequent")?;
+        let search_meta = searcher.search(&query, &SearchMetaCollector::new(Count))?;
+        assert_eq!(search_meta.fruit, 1_000);
+        assert_eq!(search_meta.docs_scored, 1_000);
+        assert_eq!(search_meta.docs_skipped, 0);
+
+        index_writer.delete_term(Term::from_field_text(text, "rare"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let search_meta = searcher.search(&query, &SearchMetaCollector::new(Count))?;
+        assert_eq!(search_meta.fruit, 998);
+        assert_eq!(search_meta.docs_scored, 998);
+        Ok(())
+    }
+}
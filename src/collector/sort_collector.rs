@@ -0,0 +1,415 @@
+use crate::collector::{Collector, SegmentCollector};
+use crate::fastfield::FastFieldReader;
+use crate::schema::{Cardinality, Field, FieldType, Schema};
+use crate::{DocAddress, DocId, Score, SegmentLocalId, SegmentReader, TantivyError};
+use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
+use std::collections::BinaryHeap;
+use std::sync::Arc;
+
+/// Order of a sort field.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Order {
+    /// Ascending order.
+    Asc,
+    /// Descending order.
+    Desc,
+}
+
+/// The position of a document in the order of a [`SortCollector`](./struct.SortCollector.html).
+///
+/// A `SortKey` is opaque. It is meant to be passed back to
+/// [`SortCollector::search_after`](./struct.SortCollector.html#method.search_after)
+/// to fetch the next page of results. It can be serialized, e.g. to JSON,
+/// to be sent to a client between two pages.
+///
+/// Documents with equal sort values are ordered by `DocAddress`. As a result,
+/// a `SortKey` is only valid for the `Searcher` that produced it: after a reload,
+/// the segments of the index may have changed.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct SortKey {
+    // The `u64` representations of the sort values.
+    // Values of the descending sort fields are bitwise negated,
+    // so that sort keys are always compared in ascending order.
+    // Values of optional fast fields are preceded by a null flag.
+    values: Vec<u64>,
+    segment_ord: SegmentLocalId,
+    doc: DocId,
+}
+
+impl SortKey {
+    /// Returns the address of the document.
+    pub fn doc_address(&self) -> DocAddress {
+        DocAddress(self.segment_ord, self.doc)
+    }
+}
+
+/// Collector returning the first `limit` documents, sorted by some fast fields.
+///
+/// The sort fields must be single-valued or optional numeric fast fields
+/// (`u64`, `i64`, `f64` or date). The documents without a value for an optional
+/// fast field are sorted last, whatever the order.
+/// Documents with the same values are sorted by `DocAddress`, so that the order is total.
+///
+/// The collector does not require documents to be scored.
+///
+/// # Search after
+///
+/// Paginating with an offset (see [`TopDocs::and_offset`](./struct.TopDocs.html#method.and_offset))
+/// requires to collect the documents of all of the previous pages.
+/// Instead, [`search_after`](#method.search_after) skips the documents sorted before
+/// the `SortKey` of the last document of the previous page. The cost of a page is then
+/// independent of its depth. [`Searcher::scroll`](../struct.Searcher.html#method.scroll)
+/// is a shortcut for this pattern.
+///
+/// ```rust
+/// use tantivy::collector::{Order, SortCollector};
+/// use tantivy::query::AllQuery;
+/// use tantivy::schema::{Schema, FAST};
+/// use tantivy::{doc, Index};
+///
+/// # fn main() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let price = schema_builder.add_u64_field("price", FAST);
+/// let schema = schema_builder.build();
+/// let index = Index::create_in_ram(schema);
+///
+/// let mut index_writer = index.writer(3_000_000)?;
+/// index_writer.add_document(doc!(price => 30u64));
+/// index_writer.add_document(doc!(price => 10u64));
+/// index_writer.add_document(doc!(price => 20u64));
+/// index_writer.commit()?;
+///
+/// let searcher = index.reader()?.searcher();
+/// let first_page = searcher.search(&AllQuery, &SortCollector::new(vec![(price, Order::Desc)], 2))?;
+/// assert_eq!(first_page.len(), 2);
+/// let (last_sort_key, _) = first_page.last().unwrap().clone();
+/// let next_page = SortCollector::new(vec![(price, Order::Desc)], 2).search_after(last_sort_key);
+/// let second_page = searcher.search(&AllQuery, &next_page)?;
+/// assert_eq!(second_page.len(), 1);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct SortCollector {
+    sort_fields: Vec<(Field, Order)>,
+    limit: usize,
+    after: Option<SortKey>,
+}
+
+impl SortCollector {
+    /// Creates a `SortCollector` returning the first `limit` documents,
+    /// sorted by `sort_fields`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `limit` is 0.
+    pub fn new(sort_fields: Vec<(Field, Order)>, limit: usize) -> SortCollector {
+        assert!(limit >= 1, "Limit must be strictly greater than 0.");
+        SortCollector {
+            sort_fields,
+            limit,
+            after: None,
+        }
+    }
+
+    /// Only collects the documents sorted after `sort_key`.
+    ///
+    /// The `sort_key` must have been returned by a collector with the same sort fields,
+    /// on the same searcher.
+    pub fn search_after(mut self, sort_key: SortKey) -> SortCollector {
+        self.after = Some(sort_key);
+        self
+    }
+}
+
+/// Reads the `u64` representation of the values of a sort field.
+pub(crate) struct SortValueReader {
+    values: FastFieldReader<u64>,
+    // Documents having a value, for optional fast fields.
+    null_bitmap: Option<Arc<RoaringBitmap>>,
+    order: Order,
+}
+
+impl SortValueReader {
+    /// Returns the number of values pushed by `push_sort_values`.
+    fn num_sort_values(&self) -> usize {
+        if self.null_bitmap.is_some() {
+            2
+        } else {
+            1
+        }
+    }
+
+    /// Pushes the sort values of `doc` to `values`.
+    ///
+    /// For optional fast fields, the value is preceded by a null flag,
+    /// so that the documents without a value are sorted last.
+    pub(crate) fn push_sort_values(&self, doc: DocId, values: &mut Vec<u64>) {
+        if let Some(null_bitmap) = self.null_bitmap.as_ref() {
+            if !null_bitmap.contains(doc) {
+                values.extend_from_slice(&[1, 0]);
+                return;
+            }
+            values.push(0);
+        }
+        values.push(match self.order {
+            Order::Asc => self.values.get(doc),
+            Order::Desc => !self.values.get(doc),
+        });
+    }
+}
+
+/// Returns an error if `field` is not a single-valued numeric fast field,
+/// and therefore cannot be used to sort documents.
+pub(crate) fn check_sort_field(schema: &Schema, field: Field) -> crate::Result<()> {
+    let field_entry = schema.get_field_entry(field);
+    let cardinality = match field_entry.field_type() {
+        FieldType::U64(options)
+        | FieldType::I64(options)
+        | FieldType::F64(options)
+        | FieldType::Date(options) => options.get_fastfield_cardinality(),
+        _ => None,
+    };
+    if !matches!(
+        cardinality,
+        Some(Cardinality::SingleValue) | Some(Cardinality::Optional)
+    ) {
+        return Err(TantivyError::SchemaError(format!(
+            "Field {:?} is not a single-valued numeric fast field.",
+            field_entry.name()
+        )));
+    }
+    Ok(())
+}
+
+pub(crate) fn sort_value_reader(
+    segment_reader: &SegmentReader,
+    field: Field,
+    order: Order,
+) -> crate::Result<SortValueReader> {
+    check_sort_field(segment_reader.schema(), field)?;
+    // The `u64` representation of fast values preserves their order.
+    let fast_fields = segment_reader.fast_fields();
+    Ok(SortValueReader {
+        values: fast_fields.typed_fast_field_reader::<u64>(field)?,
+        null_bitmap: fast_fields.null_bitmap(field)?,
+        order,
+    })
+}
+
+impl Collector for SortCollector {
+    type Fruit = Vec<(SortKey, DocAddress)>;
+
+    type Child = SortSegmentCollector;
+
+    fn for_segment(
+        &self,
+        segment_ord: SegmentLocalId,
+        reader: &SegmentReader,
+    ) -> crate::Result<SortSegmentCollector> {
+        let mut readers = Vec::with_capacity(self.sort_fields.len());
+        for &(field, order) in &self.sort_fields {
+            readers.push(sort_value_reader(reader, field, order)?);
+        }
+        if let Some(after) = self.after.as_ref() {
+            let num_sort_values: usize = readers.iter().map(SortValueReader::num_sort_values).sum();
+            if after.values.len() != num_sort_values {
+                return Err(TantivyError::InvalidArgument(format!(
+                    "The sort key has {} values, expected {}.",
+                    after.values.len(),
+                    num_sort_values
+                )));
+            }
+        }
+        Ok(SortSegmentCollector {
+            segment_ord,
+            readers,
+            limit: self.limit,
+            after: self.after.clone(),
+            heap: BinaryHeap::with_capacity(self.limit),
+        })
+    }
+
+    fn requires_scoring(&self) -> bool {
+        false
+    }
+
+    fn merge_fruits(
+        &self,
+        segment_fruits: Vec<Vec<(SortKey, DocAddress)>>,
+    ) -> crate::Result<Vec<(SortKey, DocAddress)>> {
+        let mut sort_keys: Vec<(SortKey, DocAddress)> =
+            segment_fruits.into_iter().flatten().collect();
+        sort_keys.sort_unstable_by(|left, right| left.0.cmp(&right.0));
+        sort_keys.truncate(self.limit);
+        Ok(sort_keys)
+    }
+}
+
+/// Segment collector of the `SortCollector`.
+pub struct SortSegmentCollector {
+    segment_ord: SegmentLocalId,
+    readers: Vec<SortValueReader>,
+    limit: usize,
+    after: Option<SortKey>,
+    // Max-heap of the `limit` smallest sort keys.
+    heap: BinaryHeap<SortKey>,
+}
+
+impl SegmentCollector for SortSegmentCollector {
+    type Fruit = Vec<(SortKey, DocAddress)>;
+
+    fn collect(&mut self, doc: DocId, _: Score) {
+        let mut values = Vec::with_capacity(self.readers.len());
+        for reader in &self.readers {
+            reader.push_sort_values(doc, &mut values);
+        }
+        let sort_key = SortKey {
+            values,
+            segment_ord: self.segment_ord,
+            doc,
+        };
+        if let Some(after) = self.after.as_ref() {
+            if sort_key <= *after {
+                return;
+            }
+        }
+        if self.heap.len() < self.limit {
+            self.heap.push(sort_key);
+        } else if let Some(mut last_sort_key) = self.heap.peek_mut() {
+            if sort_key < *last_sort_key {
+                *last_sort_key = sort_key;
+            }
+        }
+    }
+
+    fn harvest(self) -> Vec<(SortKey, DocAddress)> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|sort_key| {
+                let doc_address = sort_key.doc_address();
+                (sort_key, doc_address)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Order, SortCollector, SortKey};
+    use crate::query::{AllQuery, TermQuery};
+    use crate::schema::{Field, IndexRecordOption, Schema, FAST, STRING, TEXT};
+    use crate::{DocAddress, Index, Searcher, Term};
+
+    fn scroll_all(
+        searcher: &Searcher,
+        sort_fields: &[(Field, Order)],
+        page_size: usize,
+    ) -> crate::Result<Vec<DocAddress>> {
+        let mut doc_addresses = Vec::new();
+        let mut after = None;
+        loop {
+            let (page, next) = searcher.scroll(&AllQuery, sort_fields, after, page_size)?;
+            assert!(page.len() <= page_size);
+            doc_addresses.extend(page);
+            if next.is_none() {
+                return Ok(doc_addresses);
+            }
+            after = next;
+        }
+    }
+
+    #[test]
+    fn test_sort_collector_search_after() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let price = schema_builder.add_f64_field("price", FAST);
+        let rating = schema_builder.add_i64_field("rating", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(price => 3.5f64, rating => 2i64));
+        index_writer.add_document(doc!(price => -1.0f64, rating => 5i64));
+        index_writer.add_document(doc!(price => 3.5f64, rating => -4i64));
+        index_writer.commit()?;
+        index_writer.add_document(doc!(price => 10.0f64, rating => 2i64));
+        index_writer.add_document(doc!(price => 3.5f64, rating => 2i64));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 2);
+        let expected = vec![
+            DocAddress(0, 1),
+            DocAddress(0, 0),
+            DocAddress(1, 1),
+            DocAddress(0, 2),
+            DocAddress(1, 0),
+        ];
+        let sort_fields = [(price, Order::Asc), (rating, Order::Desc)];
+        for page_size in 1..=6 {
+            assert_eq!(scroll_all(&searcher, &sort_fields, page_size)?, expected);
+        }
+        let reversed_sort_fields = [(price, Order::Desc), (rating, Order::Asc)];
+        // Documents with equal values are still sorted by address.
+        assert_eq!(
+            scroll_all(&searcher, &reversed_sort_fields, 2)?,
+            vec![
+                DocAddress(1, 0),
+                DocAddress(0, 2),
+                DocAddress(0, 0),
+                DocAddress(1, 1),
+                DocAddress(0, 1),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_sort_collector_filters_and_serializes() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let price = schema_builder.add_u64_field("price", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        for (doc_title, doc_price) in &[("sofa", 3u64), ("chair", 1u64), ("sofa", 2u64)] {
+            index_writer.add_document(doc!(title => *doc_title, price => *doc_price));
+        }
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let sofa = TermQuery::new(
+            Term::from_field_text(title, "sofa"),
+            IndexRecordOption::Basic,
+        );
+        let (page, next) = searcher.scroll(&sofa, &[(price, Order::Asc)], None, 1)?;
+        assert_eq!(page, vec![DocAddress(0, 2)]);
+        let json = serde_json::to_string(&next.unwrap()).unwrap();
+        let sort_key: SortKey = serde_json::from_str(&json).unwrap();
+        let (page, next) = searcher.scroll(&sofa, &[(price, Order::Asc)], Some(sort_key), 1)?;
+        assert_eq!(page, vec![DocAddress(0, 0)]);
+        let (page, next) = searcher.scroll(&sofa, &[(price, Order::Asc)], next, 1)?;
+        assert!(page.is_empty());
+        assert!(next.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_sort_collector_errors() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", STRING);
+        let price = schema_builder.add_u64_field("price", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title => "sofa", price => 3u64));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let by_title = SortCollector::new(vec![(title, Order::Asc)], 10);
+        assert!(searcher.search(&AllQuery, &by_title).is_err());
+        let first_page =
+            searcher.search(&AllQuery, &SortCollector::new(vec![(price, Order::Asc)], 1))?;
+        let sort_key = first_page[0].0.clone();
+        let by_price_and_doc =
+            SortCollector::new(vec![(price, Order::Asc), (price, Order::Desc)], 1)
+                .search_after(sort_key);
+        assert!(searcher.search(&AllQuery, &by_price_and_doc).is_err());
+        Ok(())
+    }
+}
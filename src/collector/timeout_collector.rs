@@ -0,0 +1,246 @@
+use crate::collector::{Collector, SegmentCollector};
+use crate::docset::{DocSet, TERMINATED};
+use crate::query::Weight;
+use crate::{DocId, Score, SegmentLocalId, SegmentReader};
+use std::time::{Duration, Instant};
+
+/// Default number of documents visited between two checks of the deadline.
+const DEFAULT_CHECK_INTERVAL: u64 = 1_000;
+
+/// Fruit of a [`TimeoutCollector`](./struct.TimeoutCollector.html).
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimeoutResult<TFruit> {
+    /// Fruit of the wrapped collector, over the documents collected before the deadline.
+    pub result: TFruit,
+    /// True if the deadline was reached before all of the matching documents were collected.
+    pub timed_out: bool,
+    /// Number of documents matching the query that were passed to the wrapped collector.
+    /// Deleted documents are not counted.
+    pub docs_considered: u64,
+}
+
+/// Collector wrapping another collector, to stop the collection once a deadline is reached.
+///
+/// The deadline is checked before collecting each segment, and then every
+/// `check_interval` documents (1,000 by default). Once it is reached, the documents
+/// that remain to be visited are ignored, and the fruit of the wrapped collector
+/// only accounts for the documents collected so far.
+///
+/// The query is run by iterating over its `Scorer`, so that the collection can be
+/// interrupted. As a result, the `TopDocs` collector does not prune the documents that
+/// cannot make it into the top K when wrapped into a `TimeoutCollector`.
+///
+/// ```rust
+/// use std::time::Duration;
+/// use tantivy::collector::{Count, TimeoutCollector};
+/// use tantivy::query::QueryParser;
+/// use tantivy::schema::{Schema, TEXT};
+/// use tantivy::{doc, Index};
+///
+/// # fn main() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let title = schema_builder.add_text_field("title", TEXT);
+/// let index = Index::create_in_ram(schema_builder.build());
+/// let mut index_writer = index.writer(3_000_000)?;
+/// index_writer.add_document(doc!(title => "The Name of the Wind"));
+/// index_writer.add_document(doc!(title => "The Diary of Muadib"));
+/// index_writer.commit()?;
+///
+/// let searcher = index.reader()?.searcher();
+/// let query = QueryParser::for_index(&index, vec![title]).parse_query("diary")?;
+/// let collector = TimeoutCollector::with_timeout(Count, Duration::from_secs(1));
+/// let timeout_result = searcher.search(&query, &collector)?;
+/// assert!(!timeout_result.timed_out);
+/// assert_eq!(timeout_result.result, 1);
+/// # Ok(())
+/// # }
+/// ```
+pub struct TimeoutCollector<TCollector> {
+    collector: TCollector,
+    deadline: Instant,
+    check_interval: u64,
+}
+
+impl<TCollector: Collector> TimeoutCollector<TCollector> {
+    /// Wraps `collector`, to stop collecting documents once `deadline` is reached.
+    pub fn new(collector: TCollector, deadline: Instant) -> TimeoutCollector<TCollector> {
+        TimeoutCollector {
+            collector,
+            deadline,
+            check_interval: DEFAULT_CHECK_INTERVAL,
+        }
+    }
+
+    /// Wraps `collector`, to stop collecting documents once `timeout` has elapsed from now.
+    pub fn with_timeout(collector: TCollector, timeout: Duration) -> TimeoutCollector<TCollector> {
+        TimeoutCollector::new(collector, Instant::now() + timeout)
+    }
+
+    /// Sets the number of documents visited between two checks of the deadline.
+    ///
+    /// Reading the clock is not free: the smaller the interval, the closer to the
+    /// deadline the collection stops, but the slower it is.
+    ///
+    /// # Panics
+    /// Panics if `check_interval` is 0.
+    pub fn with_check_interval(mut self, check_interval: u64) -> TimeoutCollector<TCollector> {
+        assert!(check_interval > 0, "The check interval must be positive.");
+        self.check_interval = check_interval;
+        self
+    }
+}
+
+impl<TCollector: Collector> Collector for TimeoutCollector<TCollector> {
+    type Fruit = TimeoutResult<TCollector::Fruit>;
+
+    type Child = TimeoutSegmentCollector<TCollector::Child>;
+
+    fn for_segment(
+        &self,
+        segment_local_id: SegmentLocalId,
+        segment: &SegmentReader,
+    ) -> crate::Result<Self::Child> {
+        let segment_collector = self.collector.for_segment(segment_local_id, segment)?;
+        Ok(TimeoutSegmentCollector {
+            segment_collector,
+            timed_out: false,
+            docs_considered: 0u64,
+        })
+    }
+
+    fn requires_scoring(&self) -> bool {
+        self.collector.requires_scoring()
+    }
+
+    fn merge_fruits(
+        &self,
+        segment_fruits: Vec<TimeoutResult<<TCollector::Child as SegmentCollector>::Fruit>>,
+    ) -> crate::Result<Self::Fruit> {
+        let mut timed_out = false;
+        let mut docs_considered = 0u64;
+        let mut fruits = Vec::with_capacity(segment_fruits.len());
+        for segment_fruit in segment_fruits {
+            timed_out |= segment_fruit.timed_out;
+            docs_considered += segment_fruit.docs_considered;
+            fruits.push(segment_fruit.result);
+        }
+        Ok(TimeoutResult {
+            result: self.collector.merge_fruits(fruits)?,
+            timed_out,
+            docs_considered,
+        })
+    }
+
+    fn collect_segment(
+        &self,
+        weight: &dyn Weight,
+        segment_ord: u32,
+        reader: &SegmentReader,
+    ) -> crate::Result<<Self::Child as SegmentCollector>::Fruit> {
+        let mut segment_collector = self.for_segment(segment_ord, reader)?;
+        if Instant::now() >= self.deadline {
+            segment_collector.timed_out = true;
+            return Ok(segment_collector.harvest());
+        }
+        let mut scorer = weight.scorer(reader, 1.0)?;
+        let delete_bitset_opt = reader.delete_bitset();
+        let mut docs_before_check = self.check_interval;
+        let mut doc = scorer.doc();
+        while doc != TERMINATED {
+            docs_before_check -= 1;
+            if docs_before_check == 0 {
+                if Instant::now() >= self.deadline {
+                    segment_collector.timed_out = true;
+                    break;
+                }
+                docs_before_check = self.check_interval;
+            }
+            let is_alive = delete_bitset_opt
+                .map(|delete_bitset| delete_bitset.is_alive(doc))
+                .unwrap_or(true);
+            if is_alive {
+                segment_collector.collect(doc, scorer.score());
+            }
+            doc = scorer.advance();
+        }
+        Ok(segment_collector.harvest())
+    }
+}
+
+/// Segment collector associated to the `TimeoutCollector`.
+pub struct TimeoutSegmentCollector<TSegmentCollector> {
+    segment_collector: TSegmentCollector,
+    timed_out: bool,
+    docs_considered: u64,
+}
+
+impl<TSegmentCollector: SegmentCollector> SegmentCollector
+    for TimeoutSegmentCollector<TSegmentCollector>
+{
+    type Fruit = TimeoutResult<TSegmentCollector::Fruit>;
+
+    fn collect(&mut self, doc: DocId, score: Score) {
+        self.docs_considered += 1;
+        self.segment_collector.collect(doc, score);
+    }
+
+    fn harvest(self) -> Self::Fruit {
+        TimeoutResult {
+            result: self.segment_collector.harvest(),
+            timed_out: self.timed_out,
+            docs_considered: self.docs_considered,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TimeoutCollector;
+    use crate::collector::{Count, TopDocs};
+    use crate::query::{AllQuery, TermQuery};
+    use crate::schema::{IndexRecordOption, Schema, TEXT};
+    use crate::{Index, Term};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_timeout_collector() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        for doc in 0..1_000 {
+            if doc % 10 == 0 {
+                index_writer.add_document(doc!(text => "deleted"));
+            } else {
+                index_writer.add_document(doc!(text => "kept"));
+            }
+        }
+        index_writer.commit()?;
+        index_writer.delete_term(Term::from_field_text(text, "deleted"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+
+        let collector = TimeoutCollector::with_timeout(Count, Duration::from_secs(3_600));
+        let timeout_result = searcher.search(&AllQuery, &collector)?;
+        assert!(!timeout_result.timed_out);
+        assert_eq!(timeout_result.result, 900);
+        assert_eq!(timeout_result.docs_considered, 900);
+
+        let collector = TimeoutCollector::new(TopDocs::with_limit(3), Instant::now());
+        let timeout_result = searcher.search(&AllQuery, &collector)?;
+        assert!(timeout_result.timed_out);
+        assert!(timeout_result.result.is_empty());
+        assert_eq!(timeout_result.docs_considered, 0);
+
+        let query = TermQuery::new(
+            Term::from_field_text(text, "kept"),
+            IndexRecordOption::Basic,
+        );
+        let collector = TimeoutCollector::with_timeout(Count, Duration::from_secs(3_600))
+            .with_check_interval(1);
+        let timeout_result = searcher.search(&query, &collector)?;
+        assert!(!timeout_result.timed_out);
+        assert_eq!(timeout_result.result, 900);
+        Ok(())
+    }
+}
@@ -2,7 +2,8 @@ use super::Collector;
 use crate::collector::top_collector::{ComparableDoc, TopCollector};
 use crate::collector::tweak_score_top_collector::TweakedScoreTopCollector;
 use crate::collector::{
-    CustomScorer, CustomSegmentScorer, ScoreSegmentTweaker, ScoreTweaker, SegmentCollector,
+    CustomScorer, CustomSegmentScorer, ScoreSegmentTweaker, ScoreTweaker, SearchMetaCollector,
+    SegmentCollector,
 };
 use crate::fastfield::FastFieldReader;
 use crate::query::Weight;
@@ -88,6 +89,18 @@ where
 /// This collector guarantees a stable sorting in case of a tie on the
 /// document score. As such, it is suitable to implement pagination.
 ///
+/// Unless the scores are tweaked or customized, the documents are collected with
+/// [`Weight::for_each_pruning`](../query/trait.Weight.html#method.for_each_pruning),
+/// using the lowest score of the heap, once full, as a threshold. Term queries,
+/// and unions of term queries, then skip the blocks of postings whose maximum score,
+/// computed from the maximum term frequency and field norm stored in the skip list
+/// of each block, cannot beat this threshold (Block-Max WAND).
+///
+/// To rank the documents by something else than their score, without scoring them,
+/// see [`SortCollector`](./struct.SortCollector.html), which sorts them by some fast
+/// fields, and [`custom_score`](#method.custom_score), which ranks them by any key
+/// computed from the `SegmentReader`.
+///
 /// ```rust
 /// use tantivy::collector::TopDocs;
 /// use tantivy::query::QueryParser;
@@ -116,7 +129,7 @@ where
 /// assert_eq!(top_docs[0].1, DocAddress(0, 1));
 /// assert_eq!(top_docs[1].1, DocAddress(0, 3));
 /// ```
-pub struct TopDocs(TopCollector<Score>);
+pub struct TopDocs(pub(crate) TopCollector<Score>);
 
 impl fmt::Debug for TopDocs {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -208,6 +221,15 @@ impl TopDocs {
         TopDocs(self.0.and_offset(offset))
     }
 
+    /// Wraps the collector into a [`SearchMetaCollector`](./struct.SearchMetaCollector.html),
+    /// reporting the number of documents scored and skipped by the query along with the top docs.
+    ///
+    /// This disables the pruning of the documents that cannot make it into the top K,
+    /// and should only be used to profile queries.
+    pub fn with_search_meta(self) -> SearchMetaCollector<TopDocs> {
+        SearchMetaCollector::new(self)
+    }
+
     /// Set top-K to rank documents by a given fast field.
     ///
     /// If the field is not a fast or does not exist, this method returns successfully (it is not aware of any schema).
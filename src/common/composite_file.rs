@@ -96,6 +96,37 @@ impl<W: TerminatingWrite + Write> CompositeWrite<W> {
     }
 }
 
+/// Rewrites each of the fields of the composite file `data` with `convert`,
+/// which is passed the field, the idx and the bytes of the field.
+///
+/// The fields are written in the same order as in `data`.
+pub(crate) fn convert_composite_file<F>(data: &[u8], mut convert: F) -> io::Result<Vec<u8>>
+where
+    F: FnMut(Field, usize, &[u8]) -> io::Result<Vec<u8>>,
+{
+    let composite_file = CompositeFile::open(&FileSlice::from(data.to_vec()))?;
+    let mut fields: Vec<(usize, usize, FileAddr)> = composite_file
+        .offsets_index
+        .iter()
+        .map(|(file_addr, &(start_offset, end_offset))| (start_offset, end_offset, *file_addr))
+        .collect();
+    fields.sort();
+    let mut converted = Vec::new();
+    let mut composite_write = CompositeWrite::wrap(&mut converted);
+    for (start_offset, end_offset, file_addr) in fields {
+        let converted_field = convert(
+            file_addr.field,
+            file_addr.idx,
+            &data[start_offset..end_offset],
+        )?;
+        composite_write
+            .for_field_with_idx(file_addr.field, file_addr.idx)
+            .write_all(&converted_field)?;
+    }
+    composite_write.close()?;
+    Ok(converted)
+}
+
 /// A composite file is an abstraction to store a
 /// file partitioned by field.
 ///
@@ -170,6 +201,22 @@ impl CompositeFile {
             .map(|&(from, to)| self.data.slice(from, to))
     }
 
+    /// Returns all of the `FileSlice`s associated to a given `Field`,
+    /// ordered by index.
+    pub(crate) fn open_read_all(&self, field: Field) -> Vec<FileSlice> {
+        let mut file_addrs: Vec<FileAddr> = self
+            .offsets_index
+            .keys()
+            .filter(|file_addr| file_addr.field == field)
+            .cloned()
+            .collect();
+        file_addrs.sort();
+        file_addrs
+            .into_iter()
+            .filter_map(|file_addr| self.open_read_with_idx(field, file_addr.idx))
+            .collect()
+    }
+
     pub fn space_usage(&self) -> PerFieldSpaceUsage {
         let mut fields = HashMap::new();
         for (&field_addr, &(start, end)) in self.offsets_index.iter() {
@@ -0,0 +1,166 @@
+use crate::collector::Collector;
+use crate::query::Query;
+use crate::schema::{Document, Field};
+use crate::{DocAddress, LeasedItem, Searcher, TantivyError};
+use std::sync::Arc;
+
+/// Runs the blocking function `f` on the blocking thread pool of tokio.
+///
+/// If `f` panics, the panic is propagated to the caller.
+pub(crate) async fn spawn_blocking<F, R>(f: F) -> crate::Result<R>
+where
+    F: FnOnce() -> crate::Result<R> + Send + 'static,
+    R: Send + 'static,
+{
+    match tokio::task::spawn_blocking(f).await {
+        Ok(result) => result,
+        Err(join_error) if join_error.is_panic() => {
+            std::panic::resume_unwind(join_error.into_panic())
+        }
+        Err(join_error) => Err(TantivyError::ErrorInThread(join_error.to_string())),
+    }
+}
+
+/// Asynchronous wrapper around a `Searcher`.
+///
+/// Searching is CPU bound and blocking. In an async runtime, the methods
+/// of the `AsyncSearcher` run it on the blocking thread pool of tokio,
+/// so that the threads of the runtime are not blocked.
+/// They must be called from within a tokio runtime.
+///
+/// Cloning an `AsyncSearcher` is cheap: the searcher is shared.
+///
+/// This API requires the `async` feature.
+///
+/// ```rust
+/// use tantivy::collector::Count;
+/// use tantivy::query::AllQuery;
+/// use tantivy::schema::{Schema, TEXT};
+/// use tantivy::{doc, AsyncSearcher, Index};
+///
+/// # fn main() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let title = schema_builder.add_text_field("title", TEXT);
+/// let index = Index::create_in_ram(schema_builder.build());
+/// let mut index_writer = index.writer(3_000_000)?;
+/// index_writer.add_document(doc!(title => "The Name of the Wind"));
+/// index_writer.commit()?;
+///
+/// let searcher = AsyncSearcher::from(index.reader()?.searcher());
+/// let runtime = tokio::runtime::Builder::new_current_thread().build()?;
+/// let count = runtime.block_on(searcher.search(Box::new(AllQuery), Count))?;
+/// assert_eq!(count, 1);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct AsyncSearcher {
+    searcher: Arc<LeasedItem<Searcher>>,
+}
+
+impl From<LeasedItem<Searcher>> for AsyncSearcher {
+    fn from(searcher: LeasedItem<Searcher>) -> AsyncSearcher {
+        AsyncSearcher {
+            searcher: Arc::new(searcher),
+        }
+    }
+}
+
+impl AsyncSearcher {
+    /// Returns the wrapped `Searcher`.
+    pub fn searcher(&self) -> &Searcher {
+        &self.searcher
+    }
+
+    /// Asynchronous version of [`Searcher::search`](./struct.Searcher.html#method.search).
+    pub async fn search<C>(&self, query: Box<dyn Query>, collector: C) -> crate::Result<C::Fruit>
+    where
+        C: Collector + 'static,
+    {
+        let searcher = self.searcher.clone();
+        spawn_blocking(move || searcher.search(query.as_ref(), &collector)).await
+    }
+
+    /// Asynchronous version of [`Searcher::doc`](./struct.Searcher.html#method.doc).
+    pub async fn doc(&self, doc_address: DocAddress) -> crate::Result<Document> {
+        let searcher = self.searcher.clone();
+        spawn_blocking(move || searcher.doc(doc_address)).await
+    }
+
+    /// Asynchronous version of [`Searcher::positions`](./struct.Searcher.html#method.positions).
+    pub async fn positions(
+        &self,
+        query: Box<dyn Query>,
+        field: Field,
+        doc_address: DocAddress,
+    ) -> crate::Result<Vec<u32>> {
+        let searcher = self.searcher.clone();
+        spawn_blocking(move || searcher.positions(query.as_ref(), field, doc_address)).await
+    }
+
+    /// Asynchronous version of [`Searcher::positions_batch`](./struct.Searcher.html#method.positions_batch).
+    pub async fn positions_batch(
+        &self,
+        query: Box<dyn Query>,
+        field: Field,
+        doc_addresses: Vec<DocAddress>,
+    ) -> crate::Result<Vec<Vec<u32>>> {
+        let searcher = self.searcher.clone();
+        spawn_blocking(move || searcher.positions_batch(query.as_ref(), field, &doc_addresses))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AsyncSearcher;
+    use crate::collector::TopDocs;
+    use crate::query::TermQuery;
+    use crate::schema::{IndexRecordOption, Schema, STORED, TEXT};
+    use crate::{DocAddress, Index, Term};
+
+    #[test]
+    fn test_async_searcher() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT | STORED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text => "a b c"));
+        index_writer.add_document(doc!(text => "b a a"));
+        index_writer.commit()?;
+        let searcher = AsyncSearcher::from(index.reader()?.searcher());
+        let query = TermQuery::new(
+            Term::from_field_text(text, "a"),
+            IndexRecordOption::WithFreqsAndPositions,
+        );
+        let runtime = tokio::runtime::Builder::new_current_thread().build()?;
+        runtime.block_on(async {
+            let top_docs = searcher
+                .clone()
+                .search(Box::new(query.clone()), TopDocs::with_limit(1))
+                .await?;
+            assert_eq!(top_docs.len(), 1);
+            let doc_address = top_docs[0].1;
+            assert_eq!(
+                searcher
+                    .searcher()
+                    .search(&query, &TopDocs::with_limit(1))?,
+                top_docs
+            );
+            let doc = searcher.doc(doc_address).await?;
+            assert_eq!(
+                doc.get_first(text).and_then(|value| value.text()),
+                Some("b a a")
+            );
+            let positions = searcher
+                .positions(Box::new(query.clone()), text, doc_address)
+                .await?;
+            assert_eq!(positions, vec![1, 2]);
+            let positions = searcher
+                .positions_batch(Box::new(query), text, vec![doc_address, DocAddress(0, 0)])
+                .await?;
+            assert_eq!(positions, vec![vec![1, 2], vec![0]]);
+            Ok(())
+        })
+    }
+}
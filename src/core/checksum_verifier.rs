@@ -0,0 +1,45 @@
+use crate::directory::{FileSlice, Footer};
+use crate::error::DataCorruption;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Verifies the checksums of the files of the segments.
+///
+/// The files are immutable, so each file is only verified once.
+#[derive(Default)]
+pub(crate) struct ChecksumVerifier {
+    verified_files: Mutex<HashSet<PathBuf>>,
+}
+
+impl ChecksumVerifier {
+    /// Verifies the checksum of the file at `path`. `file` must include the footer.
+    ///
+    /// Returns a `DataCorruption` error if the checksum does not match.
+    pub fn verify(&self, path: &Path, file: &FileSlice) -> crate::Result<()> {
+        if self.is_verified(path) {
+            return Ok(());
+        }
+        let is_checksum_valid = Footer::is_checksum_valid(file.clone())
+            .map_err(|io_error| DataCorruption::new(path.to_path_buf(), io_error.to_string()))?;
+        if !is_checksum_valid {
+            return Err(DataCorruption::new(
+                path.to_path_buf(),
+                "The checksum of the file does not match its content".to_string(),
+            )
+            .into());
+        }
+        self.verified_files
+            .lock()
+            .expect("Checksum verifier lock poisoned")
+            .insert(path.to_path_buf());
+        Ok(())
+    }
+
+    fn is_verified(&self, path: &Path) -> bool {
+        self.verified_files
+            .lock()
+            .expect("Checksum verifier lock poisoned")
+            .contains(path)
+    }
+}
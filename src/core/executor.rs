@@ -1,4 +1,5 @@
 use crossbeam::channel;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use rayon::{ThreadPool, ThreadPoolBuilder};
 
 /// Search executor whether search request are single thread or multithread.
@@ -82,6 +83,35 @@ impl Executor {
             }
         }
     }
+
+    /// Perform a map in the thread pool, and reduce its results two by two
+    /// with `reduce`.
+    ///
+    /// In the `ThreadPool` variant, results are reduced in the thread pool
+    /// as soon as they are available, in no specific order.
+    ///
+    /// Returns `None` if there is no argument.
+    pub fn map_reduce<
+        A: Send,
+        R: Send,
+        F: Sized + Sync + Fn(A) -> crate::Result<R>,
+        G: Sized + Sync + Fn(R, R) -> R,
+    >(
+        &self,
+        f: F,
+        reduce: G,
+        args: Vec<A>,
+    ) -> crate::Result<Option<R>> {
+        let reduce_results =
+            |left: crate::Result<R>, right: crate::Result<R>| Ok(reduce(left?, right?));
+        let result_opt = match self {
+            Executor::SingleThread => args.into_iter().map(f).reduce(reduce_results),
+            Executor::ThreadPool(pool) => {
+                pool.install(|| args.into_par_iter().map(&f).reduce_with(reduce_results))
+            }
+        };
+        result_opt.transpose()
+    }
 }
 
 #[cfg(test)]
@@ -116,6 +146,40 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn test_map_reduce() {
+        let executors = vec![
+            Executor::single_thread(),
+            Executor::multi_thread(3, "search-test").unwrap(),
+        ];
+        for executor in &executors {
+            let sum = executor
+                .map_reduce(
+                    |i| Ok(i * 2),
+                    |left, right| left + right,
+                    (0..1_000).collect(),
+                )
+                .unwrap();
+            assert_eq!(sum, Some(999 * 1_000));
+            let empty: Option<usize> = executor
+                .map_reduce(Ok, |left, right| left + right, Vec::new())
+                .unwrap();
+            assert_eq!(empty, None);
+            let err = executor.map_reduce(
+                |i: usize| {
+                    if i == 7 {
+                        Err(crate::TantivyError::InvalidArgument(i.to_string()))
+                    } else {
+                        Ok(i)
+                    }
+                },
+                |left, right| left + right,
+                (0..10).collect(),
+            );
+            assert!(err.is_err());
+        }
+    }
+
     #[test]
     fn test_map_singlethread() {
         let result: Vec<usize> = Executor::single_thread()
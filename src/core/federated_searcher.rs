@@ -0,0 +1,208 @@
+use crate::collector::Collector;
+use crate::query::Query;
+use crate::schema::Document;
+use crate::{DocAddress, Score, Searcher, TantivyError};
+use rayon::prelude::*;
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+/// Address of a document in one of the searchers of a
+/// [`FederatedSearcher`](./struct.FederatedSearcher.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MultiDocAddress {
+    /// Ordinal of the searcher in the list given to `FederatedSearcher::new`.
+    pub searcher_index: usize,
+    /// Address of the document in this searcher.
+    pub doc_address: DocAddress,
+}
+
+/// Defines how the scores of the different searchers are made
+/// comparable before their results are merged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScoreNormalization {
+    /// The raw scores are merged as is.
+    ///
+    /// The BM25 scores depend on the statistics of each index,
+    /// so they are only comparable across indexes with similar contents.
+    Raw,
+    /// The scores of each searcher are linearly rescaled to `[0, 1]`,
+    /// the best result of each searcher getting `1` and the worst `0`.
+    ///
+    /// If all of the results of a searcher have the same score, they all get `1`.
+    MinMax,
+}
+
+/// Searches several indexes at once and merges their results.
+///
+/// The query runs on all of the searchers in parallel, in the thread pool of `rayon`.
+/// The collector must return scored documents, like `TopDocs`. The results of all of
+/// the searchers are merged by decreasing score, after applying the
+/// [`ScoreNormalization`](./enum.ScoreNormalization.html) of the federated searcher.
+pub struct FederatedSearcher {
+    searchers: Vec<Arc<Searcher>>,
+    score_normalization: ScoreNormalization,
+}
+
+impl FederatedSearcher {
+    /// Creates a `FederatedSearcher` merging the raw scores of `searchers`.
+    pub fn new(searchers: Vec<Arc<Searcher>>) -> FederatedSearcher {
+        FederatedSearcher {
+            searchers,
+            score_normalization: ScoreNormalization::Raw,
+        }
+    }
+
+    /// Sets the normalization applied to the scores before merging.
+    pub fn with_score_normalization(
+        mut self,
+        score_normalization: ScoreNormalization,
+    ) -> FederatedSearcher {
+        self.score_normalization = score_normalization;
+        self
+    }
+
+    /// Returns the searchers, in the order of `MultiDocAddress::searcher_index`.
+    pub fn searchers(&self) -> &[Arc<Searcher>] {
+        &self.searchers
+    }
+
+    /// Fetches a document from the searcher it belongs to.
+    pub fn doc(&self, doc_address: MultiDocAddress) -> crate::Result<Document> {
+        let searcher = self
+            .searchers
+            .get(doc_address.searcher_index)
+            .ok_or_else(|| {
+                TantivyError::InvalidArgument(format!(
+                    "Invalid searcher index {}",
+                    doc_address.searcher_index
+                ))
+            })?;
+        searcher.doc(doc_address.doc_address)
+    }
+
+    /// Runs `query` on all of the searchers and merges the results of `collector`.
+    ///
+    /// With `TopDocs::with_limit(k)`, each searcher returns its `k` best documents:
+    /// the merged results contain up to `k` documents per searcher, and their first `k`
+    /// elements are the overall top `k`.
+    pub fn search<C>(
+        &self,
+        query: &dyn Query,
+        collector: &C,
+    ) -> crate::Result<Vec<(Score, MultiDocAddress)>>
+    where
+        C: Collector<Fruit = Vec<(Score, DocAddress)>>,
+    {
+        let searcher_results: Vec<Vec<(Score, DocAddress)>> = self
+            .searchers
+            .par_iter()
+            .map(|searcher| searcher.search(query, collector))
+            .collect::<crate::Result<_>>()?;
+        let mut merged_results = Vec::new();
+        for (searcher_index, mut results) in searcher_results.into_iter().enumerate() {
+            if self.score_normalization == ScoreNormalization::MinMax {
+                normalize_min_max(&mut results);
+            }
+            merged_results.extend(results.into_iter().map(|(score, doc_address)| {
+                (
+                    score,
+                    MultiDocAddress {
+                        searcher_index,
+                        doc_address,
+                    },
+                )
+            }));
+        }
+        merged_results.sort_by(|(left_score, left_address), (right_score, right_address)| {
+            right_score
+                .partial_cmp(left_score)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| left_address.cmp(right_address))
+        });
+        Ok(merged_results)
+    }
+}
+
+fn normalize_min_max(results: &mut [(Score, DocAddress)]) {
+    let (min, max) = results
+        .iter()
+        .fold((Score::MAX, Score::MIN), |(min, max), &(score, _)| {
+            (min.min(score), max.max(score))
+        });
+    for (score, _) in results.iter_mut() {
+        *score = if max > min {
+            (*score - min) / (max - min)
+        } else {
+            1.0
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FederatedSearcher, MultiDocAddress, ScoreNormalization};
+    use crate::collector::TopDocs;
+    use crate::query::QueryParser;
+    use crate::schema::{Field, Schema, STORED, TEXT};
+    use crate::{assert_nearly_equals, DocAddress, Index, Searcher};
+    use std::sync::Arc;
+
+    fn build_searcher(texts: &[&str]) -> crate::Result<(Arc<Searcher>, Field)> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT | STORED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        for &doc_text in texts {
+            index_writer.add_document(doc!(text => doc_text));
+        }
+        index_writer.commit()?;
+        let searcher: Searcher = index.reader()?.searcher().clone();
+        Ok((Arc::new(searcher), text))
+    }
+
+    #[test]
+    fn test_federated_searcher() -> crate::Result<()> {
+        let (english_searcher, text) = build_searcher(&["hello", "hello hello world", "world"])?;
+        let (other_searcher, _) = build_searcher(&["hello buongiorno", "ciao"])?;
+        let query =
+            QueryParser::for_index(english_searcher.index(), vec![text]).parse_query("hello")?;
+        let federated_searcher = FederatedSearcher::new(vec![english_searcher, other_searcher]);
+
+        let results = federated_searcher.search(&query, &TopDocs::with_limit(2))?;
+        assert_eq!(results.len(), 3);
+        assert!(results[0].0 >= results[1].0);
+        assert!(results[1].0 >= results[2].0);
+
+        let federated_searcher =
+            federated_searcher.with_score_normalization(ScoreNormalization::MinMax);
+        let results = federated_searcher.search(&query, &TopDocs::with_limit(2))?;
+        let scores: Vec<f32> = results.iter().map(|(score, _)| *score).collect();
+        assert_nearly_equals!(scores[0], 1.0);
+        assert_nearly_equals!(scores[1], 1.0);
+        assert_nearly_equals!(scores[2], 0.0);
+        let addresses: Vec<MultiDocAddress> = results.iter().map(|(_, address)| *address).collect();
+        assert_eq!(
+            addresses,
+            vec![
+                MultiDocAddress {
+                    searcher_index: 0,
+                    doc_address: DocAddress(0, 0),
+                },
+                MultiDocAddress {
+                    searcher_index: 1,
+                    doc_address: DocAddress(0, 0),
+                },
+                MultiDocAddress {
+                    searcher_index: 0,
+                    doc_address: DocAddress(0, 1),
+                },
+            ]
+        );
+        let doc = federated_searcher.doc(addresses[1])?;
+        assert_eq!(
+            doc.get_first(text).unwrap().text(),
+            Some("hello buongiorno")
+        );
+        Ok(())
+    }
+}
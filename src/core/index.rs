@@ -1,10 +1,22 @@
 use super::segment::Segment;
+use crate::collector::Order;
+#[cfg(feature = "mmap")]
+use crate::core::index_clone;
+use crate::core::index_delta;
+use crate::core::index_health;
+#[cfg(feature = "mmap")]
+use crate::core::index_migration::{self, MigrateOptions};
 use crate::core::Executor;
+use crate::core::IndexHealthReport;
 use crate::core::IndexMeta;
+use crate::core::IndexSettings;
 use crate::core::SegmentId;
 use crate::core::SegmentMeta;
 use crate::core::SegmentMetaInventory;
 use crate::core::META_FILEPATH;
+use crate::core::{index_stats, IndexStats};
+use crate::core::{postings_verifier, VerificationReport};
+use crate::core::{MetricsCollector, NoopMetricsCollector};
 use crate::directory::error::OpenReadError;
 use crate::directory::ManagedDirectory;
 #[cfg(feature = "mmap")]
@@ -14,22 +26,28 @@ use crate::directory::{Directory, RAMDirectory};
 use crate::error::DataCorruption;
 use crate::error::TantivyError;
 use crate::indexer::index_writer::HEAP_SIZE_MIN;
-use crate::indexer::segment_updater::save_new_metas;
+use crate::indexer::segment_updater::{save_metas, save_new_metas};
+use crate::indexer::NoMergePolicy;
 use crate::reader::IndexReader;
 use crate::reader::IndexReaderBuilder;
 use crate::schema::Field;
 use crate::schema::FieldType;
 use crate::schema::Schema;
-use crate::tokenizer::{TextAnalyzer, TokenizerManager};
+use crate::tokenizer::{AnalyzerDescription, TextAnalyzer, TokenizerManager};
 use crate::IndexWriter;
-use std::collections::HashSet;
+use crate::Opstamp;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::io::Read;
 
 #[cfg(feature = "mmap")]
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 
+/// Prefix of the keys of the metadata of the index recording the field aliases.
+const FIELD_ALIAS_METADATA_PREFIX: &str = "tantivy.field_alias.";
+
 fn load_metas(
     directory: &dyn Directory,
     inventory: &SegmentMetaInventory,
@@ -63,6 +81,8 @@ pub struct Index {
     executor: Arc<Executor>,
     tokenizers: TokenizerManager,
     inventory: SegmentMetaInventory,
+    metrics_collector: Arc<dyn MetricsCollector>,
+    settings: IndexSettings,
 }
 
 impl Index {
@@ -97,6 +117,19 @@ impl Index {
         self.set_multithread_executor(default_num_threads)
     }
 
+    /// Accessor to the `MetricsCollector` of the index.
+    pub fn metrics_collector(&self) -> &dyn MetricsCollector {
+        self.metrics_collector.as_ref()
+    }
+
+    /// Replaces the default `NoopMetricsCollector`, which ignores all of the metrics.
+    ///
+    /// The `IndexWriter` and `IndexReader` get their own copy of the `Index`
+    /// when they are created: the `MetricsCollector` has to be set before.
+    pub fn set_metrics_collector(&mut self, metrics_collector: Arc<dyn MetricsCollector>) {
+        self.metrics_collector = metrics_collector;
+    }
+
     /// Creates a new index using the `RAMDirectory`.
     ///
     /// The index will be allocated in anonymous memory.
@@ -181,6 +214,8 @@ impl Index {
             tokenizers: TokenizerManager::default(),
             executor: Arc::new(Executor::single_thread()),
             inventory,
+            metrics_collector: Arc::new(NoopMetricsCollector),
+            settings: metas.index_settings.clone(),
         })
     }
 
@@ -210,6 +245,23 @@ impl Index {
         }
     }
 
+    /// Returns the description of the analyzer registered for `field`.
+    ///
+    /// Returns `None` if `field` is not an indexed text field, or if its
+    /// analyzer is not registered in the tokenizer manager of the index.
+    pub fn field_analyzer(&self, field: Field) -> Option<AnalyzerDescription> {
+        match self.schema.get_field_entry(field).field_type() {
+            FieldType::Str(text_options) => {
+                text_options
+                    .get_indexing_options()
+                    .and_then(|indexing_options| {
+                        self.tokenizers.describe(indexing_options.tokenizer())
+                    })
+            }
+            _ => None,
+        }
+    }
+
     /// Create a default `IndexReader` for the given index.
     ///
     /// See [`Index.reader_builder()`](#method.reader_builder).
@@ -248,7 +300,10 @@ impl Index {
     /// `SegmentMeta` are guaranteed to not be garbage collected, regardless of
     /// whether the segment is recorded as part of the index or not.
     pub fn new_segment_meta(&self, segment_id: SegmentId, max_doc: u32) -> SegmentMeta {
-        self.inventory.new_segment_meta(segment_id, max_doc)
+        self.inventory
+            .new_segment_meta(segment_id, max_doc)
+            .with_schema_version(self.schema.version())
+            .with_store_compression(self.settings.stored_field_compression())
     }
 
     /// Open the index using the provided directory
@@ -259,11 +314,29 @@ impl Index {
         Index::create_from_metas(directory, &metas, inventory)
     }
 
+    /// Opens an index from the bytes of a tar archive of its directory.
+    ///
+    /// The index is entirely loaded in a `RAMDirectory`. This is the way to open
+    /// an index on platforms without a file system or `mmap`, like WebAssembly,
+    /// where the archive can be downloaded with `fetch`.
+    ///
+    /// See [`RAMDirectory::from_tar`](./directory/struct.RAMDirectory.html#method.from_tar)
+    /// for the supported archives.
+    pub fn open_from_bytes(data: &[u8]) -> crate::Result<Index> {
+        let directory = RAMDirectory::from_tar(data)?;
+        Index::open(directory)
+    }
+
     /// Reads the index meta file from the directory.
     pub fn load_metas(&self) -> crate::Result<IndexMeta> {
         load_metas(self.directory(), &self.inventory)
     }
 
+    /// Deserializes the content of a `meta.json` file, tracking its segments.
+    pub(crate) fn deserialize_metas(&self, meta_json: &str) -> serde_json::Result<IndexMeta> {
+        IndexMeta::deserialize(meta_json, &self.inventory)
+    }
+
     /// Open a new index writer. Attempts to acquire a lockfile.
     ///
     /// The lockfile should be deleted on drop, but it is possible
@@ -341,6 +414,23 @@ impl Index {
         self.writer_with_num_threads(num_threads, overall_heap_size_in_bytes)
     }
 
+    /// Rewrites the segments of the index, with their documents sorted by
+    /// their value for the fast field `field`.
+    ///
+    /// The segments are not merged together afterwards, so that they stay sorted.
+    /// See [`IndexWriter::optimize_for_read`](./struct.IndexWriter.html#method.optimize_for_read).
+    ///
+    /// # Errors
+    /// As this opens an `IndexWriter`, this fails with `LockFailure` if
+    /// another `IndexWriter` is working on the index.
+    pub fn optimize_for_read(&self, field: Field, order: Order) -> crate::Result<()> {
+        let mut index_writer = self.writer_with_num_threads(1, HEAP_SIZE_MIN)?;
+        // The sorted segments must not be merged back together once rewritten.
+        index_writer.set_merge_policy(Box::new(NoMergePolicy));
+        index_writer.optimize_for_read(field, order)?;
+        index_writer.wait_merging_threads()
+    }
+
     /// Accessor to the index schema
     ///
     /// The schema is actually cloned.
@@ -348,6 +438,229 @@ impl Index {
         self.schema.clone()
     }
 
+    /// Replaces the schema of the index by `new_schema`, without reindexing.
+    ///
+    /// The new schema has to contain all of the fields of the current schema,
+    /// in the same order and with the same name and options: it can only append
+    /// new fields. The segments written before the evolution behave as if none of
+    /// their documents had a value for the new fields: the text fields are empty,
+    /// and the numeric fast fields return `0`.
+    ///
+    /// The `IndexWriter` and `IndexReader` get their own copy of the `Index`
+    /// when they are created: they have to be created after the evolution.
+    ///
+    /// # Errors
+    /// If an `IndexWriter` is working on the index, returns `Error::LockFailure`.
+    /// If the new schema is not compatible with the current one, returns `Error::SchemaError`.
+    pub fn evolve_schema(&mut self, new_schema: Schema) -> crate::Result<()> {
+        let _directory_lock = self
+            .directory
+            .acquire_lock(&INDEX_WRITER_LOCK)
+            .map_err(|err| {
+                TantivyError::LockFailure(
+                    err,
+                    Some(
+                        "Failed to acquire index lock. The schema cannot evolve while \
+                         an `IndexWriter` is working on this `Directory`."
+                            .to_string(),
+                    ),
+                )
+            })?;
+        for (field, field_entry) in self.schema.fields() {
+            let new_field_entry = new_schema
+                .fields()
+                .nth(field.field_id() as usize)
+                .map(|(_, new_field_entry)| new_field_entry);
+            if new_field_entry != Some(field_entry) {
+                return Err(TantivyError::SchemaError(format!(
+                    "The new schema is not compatible with the field {:?}. \
+                     Fields can only be added to the schema.",
+                    field_entry.name()
+                )));
+            }
+        }
+        let schema_version = self.schema.version();
+        let metas = self.load_metas()?;
+        let segments = metas
+            .segments
+            .into_iter()
+            .map(|segment_meta| {
+                if segment_meta.schema_version().is_some() {
+                    segment_meta
+                } else {
+                    segment_meta.with_schema_version(schema_version)
+                }
+            })
+            .collect();
+        let new_metas = IndexMeta {
+            segments,
+            schema: new_schema.clone(),
+            opstamp: metas.opstamp,
+            payload: metas.payload,
+            metadata: metas.metadata,
+            index_settings: metas.index_settings,
+        };
+        save_metas(&new_metas, self.directory())?;
+        self.schema = new_schema;
+        Ok(())
+    }
+
+    /// Returns the settings of the index.
+    pub fn settings(&self) -> &IndexSettings {
+        &self.settings
+    }
+
+    /// Replaces the settings of the index, and persists them in the `meta.json` file.
+    ///
+    /// The new settings apply to the segments written afterwards, including
+    /// the segments resulting from a merge.
+    ///
+    /// # Errors
+    /// If an `IndexWriter` is working on the index, returns `Error::LockFailure`.
+    /// If the compression of the doc store is not available in this build of tantivy,
+    /// returns `Error::InvalidArgument`.
+    pub fn set_settings(&mut self, settings: IndexSettings) -> crate::Result<()> {
+        let stored_field_compression = settings.stored_field_compression();
+        if !stored_field_compression.is_supported() {
+            return Err(TantivyError::InvalidArgument(format!(
+                "The {:?} compression of the doc store is not available. \
+                 Check the compression features of tantivy.",
+                stored_field_compression
+            )));
+        }
+        let _directory_lock = self
+            .directory
+            .acquire_lock(&INDEX_WRITER_LOCK)
+            .map_err(|err| {
+                TantivyError::LockFailure(
+                    err,
+                    Some(
+                        "Failed to acquire index lock. The settings cannot be changed while \
+                         an `IndexWriter` is working on this `Directory`."
+                            .to_string(),
+                    ),
+                )
+            })?;
+        let mut metas = self.load_metas()?;
+        metas.index_settings = settings.clone();
+        save_metas(&metas, self.directory())?;
+        self.settings = settings;
+        Ok(())
+    }
+
+    /// Sets the value of `key` in the metadata of the index.
+    ///
+    /// The metadata is a map of application defined key-value pairs, persisted
+    /// in the `meta.json` file and kept by the following commits. For instance,
+    /// an indexing pipeline can record the last event it indexed, to resume from it
+    /// after a restart.
+    ///
+    /// The `meta.json` file is rewritten right away. To record a value together with
+    /// the documents of a commit, call `set_metadata` on the `PreparedCommit` returned by
+    /// [`IndexWriter::prepare_commit`](./struct.IndexWriter.html#method.prepare_commit)
+    /// instead.
+    ///
+    /// # Errors
+    /// If an `IndexWriter` is working on the index, returns `Error::LockFailure`.
+    pub fn set_metadata(&self, key: &str, value: &str) -> crate::Result<()> {
+        let _directory_lock = self
+            .directory
+            .acquire_lock(&INDEX_WRITER_LOCK)
+            .map_err(|err| {
+                TantivyError::LockFailure(
+                    err,
+                    Some(
+                        "Failed to acquire index lock. The metadata cannot be set while \
+                         an `IndexWriter` is working on this `Directory`."
+                            .to_string(),
+                    ),
+                )
+            })?;
+        let mut metas = self.load_metas()?;
+        metas.metadata.insert(key.to_string(), value.to_string());
+        save_metas(&metas, self.directory())
+    }
+
+    /// Returns the value of `key` in the metadata of the index, as of the last commit.
+    ///
+    /// See [`set_metadata`](#method.set_metadata).
+    pub fn get_metadata(&self, key: &str) -> crate::Result<Option<String>> {
+        Ok(self.load_metas()?.metadata.remove(key))
+    }
+
+    /// Returns all of the metadata of the index, as of the last commit.
+    pub fn metadata(&self) -> crate::Result<HashMap<String, String>> {
+        Ok(self.load_metas()?.metadata)
+    }
+
+    /// Registers `alias` as a search-time alias for `fields`.
+    ///
+    /// The `QueryParser`s created with
+    /// [`QueryParser::for_index`](./query/struct.QueryParser.html#method.for_index)
+    /// expand `alias:coffee` into `title:coffee OR body:coffee`, if `alias`
+    /// stands for `title` and `body`. Registering an alias again replaces its fields.
+    ///
+    /// The alias is recorded in the metadata of the index, under the key
+    /// `tantivy.field_alias.<alias>`. It is not a field of the schema.
+    ///
+    /// # Errors
+    /// Returns `InvalidArgument` if `alias` is the name of a field of the schema,
+    /// or if `fields` is empty, and `LockFailure` if an `IndexWriter` is working on the index.
+    pub fn set_field_alias(&self, alias: &str, fields: &[Field]) -> crate::Result<()> {
+        if self.schema.get_field(alias).is_some() {
+            return Err(TantivyError::InvalidArgument(format!(
+                "Field alias {:?} is the name of a field of the schema",
+                alias
+            )));
+        }
+        if fields.is_empty() {
+            return Err(TantivyError::InvalidArgument(format!(
+                "Field alias {:?} has no field",
+                alias
+            )));
+        }
+        let field_names: Vec<&str> = fields
+            .iter()
+            .map(|&field| self.schema.get_field_name(field))
+            .collect();
+        let value =
+            serde_json::to_string(&field_names).expect("Serializing a list of strings cannot fail");
+        self.set_metadata(&format!("{}{}", FIELD_ALIAS_METADATA_PREFIX, alias), &value)
+    }
+
+    /// Returns the field aliases registered with
+    /// [`set_field_alias`](#method.set_field_alias), as of the last commit.
+    pub fn field_aliases(&self) -> crate::Result<HashMap<String, Vec<Field>>> {
+        self.load_metas()?
+            .metadata
+            .iter()
+            .filter_map(|(key, value)| {
+                let alias = key.strip_prefix(FIELD_ALIAS_METADATA_PREFIX)?;
+                Some((alias, value))
+            })
+            .map(|(alias, value)| {
+                let field_names: Vec<String> = serde_json::from_str(value).map_err(|_| {
+                    DataCorruption::comment_only(format!(
+                        "Field alias {:?} is invalid: {:?}",
+                        alias, value
+                    ))
+                })?;
+                let fields = field_names
+                    .iter()
+                    .map(|field_name| {
+                        self.schema.get_field(field_name).ok_or_else(|| {
+                            TantivyError::SchemaError(format!(
+                                "Field alias {:?} refers to unknown field {:?}",
+                                alias, field_name
+                            ))
+                        })
+                    })
+                    .collect::<crate::Result<Vec<Field>>>()?;
+                Ok((alias.to_string(), fields))
+            })
+            .collect()
+    }
+
     /// Returns the list of segments that are searchable
     pub fn searchable_segments(&self) -> crate::Result<Vec<Segment>> {
         Ok(self
@@ -366,7 +679,8 @@ impl Index {
     pub fn new_segment(&self) -> Segment {
         let segment_meta = self
             .inventory
-            .new_segment_meta(SegmentId::generate_random(), 0);
+            .new_segment_meta(SegmentId::generate_random(), 0)
+            .with_store_compression(self.settings.stored_field_compression());
         self.segment(segment_meta)
     }
 
@@ -399,6 +713,131 @@ impl Index {
     pub fn validate_checksum(&self) -> crate::Result<HashSet<PathBuf>> {
         self.directory.list_damaged().map_err(Into::into)
     }
+
+    /// Returns aggregate metrics of the last commit of the index:
+    /// its number of segments and documents, and the size of its files.
+    ///
+    /// It reads `meta.json` and opens the files of each segment, without reading them.
+    /// It can be called while the index is being written or searched.
+    pub fn stats(&self) -> crate::Result<IndexStats> {
+        index_stats::stats(self)
+    }
+
+    /// Checks the consistency of the files of the index, e.g. after an unclean shutdown.
+    ///
+    /// The following issues are reported:
+    /// - errors, if `meta.json` cannot be loaded, or if a file of a segment
+    ///   of the last commit is missing or has a wrong checksum.
+    /// - warnings, for each file created by tantivy that is not used by the index
+    ///   anymore. Such files are normally garbage collected by the `IndexWriter`.
+    pub fn check_health(&self) -> crate::Result<IndexHealthReport> {
+        index_health::check_health(self)
+    }
+
+    /// Deletes the orphaned files of a report returned by
+    /// [`check_health`](#method.check_health).
+    ///
+    /// tantivy only keeps the `meta.json` of the last commit: an index reporting
+    /// errors cannot be rolled back to a previous commit, and a `DataCorruption`
+    /// error is returned.
+    ///
+    /// The index cannot be repaired while an `IndexWriter` is working on it.
+    pub fn repair(&self, report: &IndexHealthReport) -> crate::Result<()> {
+        index_health::repair(self, report)
+    }
+
+    /// Checks that the posting lists of the stored text fields are consistent
+    /// with the stored text, e.g. to test a custom tokenizer or after a merge.
+    ///
+    /// A fraction `sampling_rate` (between 0 and 1) of the alive documents of each segment
+    /// is picked evenly. Their stored values are tokenized again, with the tokenizer
+    /// currently registered for the field. An inconsistency is reported for each term
+    /// that is missing from the posting list of the document, and for each
+    /// posting of the document whose term is not in its stored text.
+    ///
+    /// Only the fields that are both stored and indexed as text are checked.
+    /// All of the posting lists of these fields are read.
+    ///
+    /// Returns `InvalidArgument` if `sampling_rate` is not between 0 and 1.
+    pub fn verify_postings(&self, sampling_rate: f32) -> crate::Result<VerificationReport> {
+        postings_verifier::verify_postings(self, sampling_rate)
+    }
+
+    /// Rewrites the index at `src_path` in a new index at `dst_path`, in the format
+    /// of this version of tantivy, without reindexing the documents.
+    ///
+    /// The source index can be in the current index format, or in one of the formats
+    /// written by the previous versions of tantivy, which this version does not open
+    /// otherwise. The migration fails with `OpenReadError::IncompatibleIndex` before
+    /// anything is written if the source index cannot be read, e.g. if its doc store was
+    /// written with a compression this build does not support: the index then has to be
+    /// migrated with a build that reads it, or to be reindexed.
+    /// The source index must not be written during the migration.
+    ///
+    /// Each segment of the last commit is rewritten as a merge would, and its
+    /// deleted documents are purged. `dst_path` must be an existing directory
+    /// that does not contain an index yet.
+    ///
+    /// With `MigrateOptions::dry_run`, the files of the source index are only
+    /// checked, and nothing is written.
+    #[cfg(feature = "mmap")]
+    pub fn migrate<P: AsRef<Path>, Q: AsRef<Path>>(
+        src_path: P,
+        dst_path: Q,
+        options: MigrateOptions,
+    ) -> crate::Result<()> {
+        let src_index = index_migration::open_src_index(MmapDirectory::open(src_path)?)?;
+        let dst_directory = MmapDirectory::open(dst_path)?;
+        if Index::exists(&dst_directory)? {
+            return Err(TantivyError::IndexAlreadyExists);
+        }
+        index_migration::check_compatibility(&src_index)?;
+        if options.dry_run {
+            return Ok(());
+        }
+        let dst_index = Index::create(dst_directory, src_index.schema())?;
+        index_migration::migrate(&src_index, &dst_index)
+    }
+
+    /// Copies the last commit of the index in a new index at `dst_path`.
+    ///
+    /// The files of the segments are hard linked when the index is stored on the
+    /// same filesystem as `dst_path`, and copied otherwise. As the files of a segment
+    /// are never modified once written, the copy does not depend on the source index
+    /// afterwards: the commits, merges and garbage collections of either index do not
+    /// affect the other one. The source index can be written during the copy.
+    ///
+    /// `dst_path` must be an existing directory that does not contain an index yet.
+    /// The copy can then be opened with [`Index::open_in_dir`](#method.open_in_dir).
+    #[cfg(feature = "mmap")]
+    pub fn clone_to<P: AsRef<Path>>(&self, dst_path: P) -> crate::Result<()> {
+        let dst_path = dst_path.as_ref();
+        if Index::exists(&MmapDirectory::open(dst_path)?)? {
+            return Err(TantivyError::IndexAlreadyExists);
+        }
+        index_clone::clone_to(self, dst_path)
+    }
+
+    /// Applies a delta exported by
+    /// [`IndexWriter::export_delta`](./struct.IndexWriter.html#method.export_delta)
+    /// on a primary index, and returns the opstamp of the commit of the primary it
+    /// brings the index to.
+    ///
+    /// The opstamp should be passed as `since_opstamp` to the next call to `export_delta`.
+    /// The first delta applied to an empty index must be exported since opstamp `0`.
+    ///
+    /// The delta is applied atomically: the new `meta.json` is written once all of the
+    /// files of the delta are, and searchers see the deletes and the new segments
+    /// together on their next reload. Applying the same delta twice is harmless.
+    ///
+    /// # Errors
+    /// If an `IndexWriter` is working on the index, returns `Error::LockFailure`.
+    /// If the schema of the primary differs, returns `Error::SchemaError`.
+    /// If the delta was not exported since the opstamp of the last delta applied to
+    /// the index, returns `Error::DataCorruption`, and the index is left unchanged.
+    pub fn apply_delta(&self, src: &mut dyn Read) -> crate::Result<Opstamp> {
+        index_delta::apply_delta(self, src)
+    }
 }
 
 impl fmt::Debug for Index {
@@ -409,12 +848,21 @@ impl fmt::Debug for Index {
 
 #[cfg(test)]
 mod tests {
+    use crate::collector::Count;
+    use crate::directory::error::{Incompatibility, OpenReadError};
     use crate::directory::{RAMDirectory, WatchCallback};
+    use crate::query::TermQuery;
     use crate::schema::Field;
-    use crate::schema::{Schema, INDEXED, TEXT};
+    use crate::schema::{IndexRecordOption, Schema, FAST, INDEXED, STORED, STRING, TEXT};
+    use crate::store::StoredFieldCompression;
+    use crate::tokenizer::AnalyzerDescription;
     use crate::IndexReader;
     use crate::ReloadPolicy;
-    use crate::{Directory, Index};
+    use crate::SegmentReader;
+    use crate::{Directory, DocAddress, Index, IndexSettings, SegmentMeta, TantivyError, Term};
+    use futures::executor::block_on;
+    use std::thread;
+    use std::time::Duration;
 
     #[test]
     fn test_indexer_for_field() {
@@ -430,6 +878,293 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_field_analyzer() -> serde_json::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let num_likes_field = schema_builder.add_u64_field("num_likes", INDEXED);
+        let body_field = schema_builder.add_text_field("body", TEXT);
+        let id_field = schema_builder.add_text_field("id", STRING);
+        let stored_field = schema_builder.add_text_field("stored", STORED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let description = index.field_analyzer(body_field).unwrap();
+        assert_eq!(description.name, "default");
+        assert_eq!(description.tokenizer, "SimpleTokenizer");
+        assert_eq!(description.filters, vec!["RemoveLongFilter", "LowerCaser"]);
+        assert_eq!(
+            description.configuration,
+            "SimpleTokenizer | RemoveLongFilter | LowerCaser"
+        );
+        let json = serde_json::to_string(&description)?;
+        assert_eq!(
+            serde_json::from_str::<AnalyzerDescription>(&json)?,
+            description
+        );
+        let id_description = index.field_analyzer(id_field).unwrap();
+        assert_eq!(id_description.configuration, "RawTokenizer");
+        assert!(id_description.filters.is_empty());
+        assert!(index.field_analyzer(num_likes_field).is_none());
+        assert!(index.field_analyzer(stored_field).is_none());
+        let names: Vec<String> = index
+            .tokenizers()
+            .descriptions()
+            .into_iter()
+            .map(|description| description.name)
+            .collect();
+        assert_eq!(names, vec!["default", "en_stem", "raw"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_open_from_bytes() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let directory = RAMDirectory::create();
+        let index = Index::create(directory.clone(), schema_builder.build())?;
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title => "hello"));
+        index_writer.add_document(doc!(title => "happy tax payer"));
+        index_writer.commit()?;
+        drop(index_writer);
+        let archive = directory.to_tar()?;
+        let index_from_bytes = Index::open_from_bytes(&archive)?;
+        let searcher = index_from_bytes.reader()?.searcher();
+        let query = TermQuery::new(
+            Term::from_field_text(title, "hello"),
+            IndexRecordOption::Basic,
+        );
+        assert_eq!(searcher.search(&query, &Count)?, 1);
+        assert!(Index::open_from_bytes(&archive[..100]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_open_index_in_old_format() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let directory = RAMDirectory::create();
+        let index = Index::create(directory.clone(), schema_builder.build())?;
+        let mut index_writer = index.writer_for_tests()?;
+        for _ in 0..10 {
+            index_writer.add_document(doc!(title => "hello"));
+        }
+        index_writer.commit()?;
+        drop(index_writer);
+        crate::directory::rewrite_in_index_format_v3(&directory, &index)?;
+        let index = Index::open(directory)?;
+        assert!(matches!(
+            index.reader(),
+            Err(TantivyError::OpenReadError(
+                OpenReadError::IncompatibleIndex(_)
+            ))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_evolve_schema() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let old_schema = schema_builder.build();
+        let directory = RAMDirectory::create();
+        let mut index = Index::create(directory.clone(), old_schema.clone())?;
+        {
+            let mut index_writer = index.writer_for_tests()?;
+            index_writer.add_document(doc!(title => "old"));
+            index_writer.commit()?;
+        }
+
+        let mut schema_builder = Schema::builder();
+        schema_builder.add_text_field("title", TEXT);
+        let popularity = schema_builder.add_u64_field("popularity", FAST);
+        let body = schema_builder.add_text_field("body", TEXT);
+        index.evolve_schema(schema_builder.build())?;
+        {
+            let mut index_writer = index.writer_for_tests()?;
+            index_writer.add_document(doc!(title => "new", popularity => 3u64, body => "body"));
+            index_writer.commit()?;
+        }
+        let body_query = TermQuery::new(
+            Term::from_field_text(body, "body"),
+            IndexRecordOption::WithFreqs,
+        );
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.num_docs(), 2);
+        assert_eq!(searcher.search(&body_query, &Count)?, 1);
+        for segment_reader in searcher.segment_readers() {
+            let popularity_reader = segment_reader.fast_fields().u64(popularity)?;
+            let fieldnorm_reader = segment_reader.get_fieldnorms_reader(body)?;
+            if segment_reader.inverted_index(body)?.terms().num_terms() == 0 {
+                assert_eq!(popularity_reader.get(0), 0);
+                assert_eq!(fieldnorm_reader.fieldnorm(0), 0);
+            } else {
+                assert_eq!(popularity_reader.get(0), 3);
+            }
+        }
+
+        let segment_ids = index.searchable_segment_ids()?;
+        let mut index_writer = index.writer_for_tests()?;
+        block_on(index_writer.merge(&segment_ids))?;
+        index_writer.wait_merging_threads()?;
+        let reopened_index = Index::open(directory)?;
+        assert!(reopened_index.schema() == index.schema());
+        let searcher = reopened_index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 1);
+        assert_eq!(searcher.search(&body_query, &Count)?, 1);
+        let mut popularities = vec![0u64; 2];
+        let popularity_reader = searcher.segment_reader(0).fast_fields().u64(popularity)?;
+        popularity_reader.get_range(0, &mut popularities[..]);
+        popularities.sort_unstable();
+        assert_eq!(popularities, vec![0, 3]);
+
+        assert!(matches!(
+            index.evolve_schema(old_schema),
+            Err(TantivyError::SchemaError(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_metadata() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        assert_eq!(index.get_metadata("last_event_id")?, None);
+        index.set_metadata("last_event_id", "abc123")?;
+        index.set_metadata("source", "events")?;
+        assert_eq!(
+            index.get_metadata("last_event_id")?.as_deref(),
+            Some("abc123")
+        );
+        {
+            let mut index_writer = index.writer_for_tests()?;
+            assert!(matches!(
+                index.set_metadata("last_event_id", "abc124"),
+                Err(TantivyError::LockFailure(..))
+            ));
+            index_writer.add_document(doc!(title => "abc124"));
+            let mut prepared_commit = index_writer.prepare_commit()?;
+            prepared_commit.set_metadata("last_event_id", "abc124");
+            prepared_commit.commit()?;
+            index_writer.add_document(doc!(title => "abc125"));
+            index_writer.commit()?;
+        }
+        let metadata = index.metadata()?;
+        assert_eq!(metadata.len(), 2);
+        assert_eq!(metadata["last_event_id"], "abc124");
+        assert_eq!(metadata["source"], "events");
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_settings_stored_field_compression() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT | STORED);
+        let directory = RAMDirectory::create();
+        let mut index = Index::create(directory.clone(), schema_builder.build())?;
+        assert_eq!(index.settings(), &IndexSettings::default());
+        {
+            let mut index_writer = index.writer_for_tests()?;
+            index_writer.add_document(doc!(title => "default"));
+            index_writer.commit()?;
+            assert!(matches!(
+                index.set_settings(IndexSettings::default()),
+                Err(TantivyError::LockFailure(..))
+            ));
+        }
+        let settings =
+            IndexSettings::default().with_stored_field_compression(StoredFieldCompression::None);
+        index.set_settings(settings.clone())?;
+        {
+            let mut index_writer = index.writer_for_tests()?;
+            index_writer.add_document(doc!(title => "none"));
+            index_writer.commit()?;
+        }
+        let reopened_index = Index::open(directory)?;
+        assert_eq!(reopened_index.settings(), &settings);
+        let mut compressions: Vec<StoredFieldCompression> = reopened_index
+            .searchable_segment_metas()?
+            .iter()
+            .map(SegmentMeta::store_compression)
+            .collect();
+        compressions.sort_by_key(|compression| *compression == StoredFieldCompression::None);
+        assert_eq!(
+            compressions,
+            vec![
+                StoredFieldCompression::default(),
+                StoredFieldCompression::None
+            ]
+        );
+
+        let segment_ids = index.searchable_segment_ids()?;
+        let mut index_writer = index.writer_for_tests()?;
+        block_on(index_writer.merge(&segment_ids))?;
+        index_writer.wait_merging_threads()?;
+        let segment_metas = index.searchable_segment_metas()?;
+        assert_eq!(segment_metas.len(), 1);
+        assert_eq!(
+            segment_metas[0].store_compression(),
+            StoredFieldCompression::None
+        );
+        let searcher = index.reader()?.searcher();
+        let mut titles: Vec<String> = (0..2)
+            .map(|doc| -> crate::Result<String> {
+                let doc = searcher.doc(DocAddress(0, doc))?;
+                Ok(doc.get_first(title).unwrap().text().unwrap().to_string())
+            })
+            .collect::<crate::Result<_>>()?;
+        titles.sort();
+        assert_eq!(titles, vec!["default".to_string(), "none".to_string()]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_open_segment_with_unsupported_compression() -> crate::Result<()> {
+        let schema = throw_away_schema();
+        let field = schema.get_field("num_likes").unwrap();
+        let index = Index::create_in_ram(schema);
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(field => 1u64));
+        index_writer.commit()?;
+        let segment_meta = index.searchable_segment_metas()?.remove(0);
+        let compressions = [
+            StoredFieldCompression::Lz4,
+            StoredFieldCompression::Brotli,
+            StoredFieldCompression::Zstd { level: 3 },
+        ];
+        for &compression in &compressions {
+            let segment = index.segment(segment_meta.clone().with_store_compression(compression));
+            let segment_reader = SegmentReader::open(&segment);
+            if compression.is_supported() {
+                assert!(segment_reader.is_ok());
+            } else {
+                assert!(matches!(
+                    segment_reader,
+                    Err(TantivyError::IncompatibleIndex(
+                        Incompatibility::CompressionMismatch { .. }
+                    ))
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_settings_unsupported_compression() {
+        let mut index = Index::create_in_ram(throw_away_schema());
+        let compressions = [
+            StoredFieldCompression::Lz4,
+            StoredFieldCompression::Brotli,
+            StoredFieldCompression::Zstd { level: 3 },
+        ];
+        for &compression in &compressions {
+            let settings = IndexSettings::default().with_stored_field_compression(compression);
+            assert_eq!(
+                index.set_settings(settings).is_ok(),
+                compression.is_supported()
+            );
+        }
+    }
+
     #[test]
     fn test_index_exists() {
         let directory = RAMDirectory::create();
@@ -495,10 +1230,35 @@ mod tests {
         test_index_on_commit_reload_policy_aux(field, &index, &reader);
     }
 
+    #[test]
+    fn test_index_scheduled_reload_policy() -> crate::Result<()> {
+        let schema = throw_away_schema();
+        let field = schema.get_field("num_likes").unwrap();
+        let index = Index::create_in_ram(schema);
+        let reader = index
+            .reader_builder()
+            .reload_policy(ReloadPolicy::Scheduled {
+                interval: Duration::from_millis(10),
+            })
+            .try_into()?;
+        assert_eq!(reader.searcher().num_docs(), 0);
+        let mut writer = index.writer_for_tests()?;
+        writer.add_document(doc!(field=>1u64));
+        writer.commit()?;
+        for _ in 0..500 {
+            if reader.searcher().num_docs() == 1 {
+                return Ok(());
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        panic!("The scheduled reload did not pick up the commit.");
+    }
+
     #[cfg(feature = "mmap")]
     mod mmap_specific {
 
         use super::*;
+        use crate::collector::TopDocs;
         use crate::Directory;
         use std::path::PathBuf;
         use tempfile::TempDir;
@@ -560,6 +1320,151 @@ mod tests {
             assert_eq!(reader.searcher().num_docs(), 0);
             test_index_on_commit_reload_policy_aux(field, &write_index, &reader);
         }
+
+        #[test]
+        fn test_migrate() -> crate::Result<()> {
+            let schema = throw_away_schema();
+            let field = schema.get_field("num_likes").unwrap();
+            let src_dir = TempDir::new()?;
+            let dst_dir = TempDir::new()?;
+            {
+                let src_index = Index::create_in_dir(src_dir.path(), schema)?;
+                let mut index_writer = src_index.writer_for_tests()?;
+                index_writer.add_document(doc!(field => 1u64));
+                index_writer.add_document(doc!(field => 2u64));
+                index_writer.commit()?;
+            }
+            let dry_run = crate::MigrateOptions { dry_run: true };
+            Index::migrate(src_dir.path(), dst_dir.path(), dry_run)?;
+            assert!(!Index::exists(&crate::directory::MmapDirectory::open(
+                dst_dir.path()
+            )?)?);
+
+            Index::migrate(src_dir.path(), dst_dir.path(), Default::default())?;
+            let dst_index = Index::open_in_dir(dst_dir.path())?;
+            assert_eq!(dst_index.reader()?.searcher().num_docs(), 2);
+            assert!(matches!(
+                Index::migrate(src_dir.path(), dst_dir.path(), Default::default()),
+                Err(TantivyError::IndexAlreadyExists)
+            ));
+            Ok(())
+        }
+
+        #[test]
+        fn test_migrate_index_in_old_format() -> crate::Result<()> {
+            let mut schema_builder = Schema::builder();
+            let id = schema_builder.add_text_field("id", STRING | STORED);
+            let body = schema_builder.add_text_field("body", TEXT);
+            let count = schema_builder.add_u64_field("count", FAST | INDEXED);
+            let schema = schema_builder.build();
+            let src_dir = TempDir::new()?;
+            let dst_dir = TempDir::new()?;
+            {
+                let src_index = Index::create_in_dir(src_dir.path(), schema)?;
+                let mut index_writer = src_index.writer_for_tests()?;
+                // Enough documents for the posting lists of the ids to be compressed
+                // by blocks, as in the index format 3.
+                for i in 0..200u64 {
+                    index_writer.add_document(doc!(
+                        id => format!("doc-{}", i),
+                        body => if i % 2 == 0 { "hello happy tax payer" } else { "hello" },
+                        count => (i * 37) % 200,
+                    ));
+                }
+                index_writer.commit()?;
+                index_writer.delete_term(Term::from_field_text(id, "doc-3"));
+                index_writer.commit()?;
+                let directory = crate::directory::MmapDirectory::open(src_dir.path())?;
+                crate::directory::rewrite_in_index_format_v3(&directory, &src_index)?;
+            }
+            assert!(matches!(
+                Index::open_in_dir(src_dir.path())?.reader(),
+                Err(TantivyError::OpenReadError(
+                    OpenReadError::IncompatibleIndex(_)
+                ))
+            ));
+
+            Index::migrate(
+                src_dir.path(),
+                dst_dir.path(),
+                crate::MigrateOptions { dry_run: true },
+            )?;
+            assert!(!Index::exists(&crate::directory::MmapDirectory::open(
+                dst_dir.path()
+            )?)?);
+            Index::migrate(src_dir.path(), dst_dir.path(), Default::default())?;
+
+            let dst_index = Index::open_in_dir(dst_dir.path())?;
+            let searcher = dst_index.reader()?.searcher();
+            assert_eq!(searcher.num_docs(), 199);
+            let happy_query = TermQuery::new(
+                Term::from_field_text(body, "happy"),
+                IndexRecordOption::Basic,
+            );
+            assert_eq!(searcher.search(&happy_query, &Count)?, 100);
+            let hello_query = TermQuery::new(
+                Term::from_field_text(body, "hello"),
+                IndexRecordOption::Basic,
+            );
+            assert_eq!(searcher.search(&hello_query, &Count)?, 199);
+            let doc_query =
+                TermQuery::new(Term::from_field_text(id, "doc-5"), IndexRecordOption::Basic);
+            let doc_addresses = searcher.search(&doc_query, &TopDocs::with_limit(1))?;
+            assert_eq!(doc_addresses.len(), 1);
+            let DocAddress(segment_ord, doc_id) = doc_addresses[0].1;
+            let count_reader = searcher
+                .segment_reader(segment_ord)
+                .fast_fields()
+                .u64(count)?;
+            assert_eq!(count_reader.get(doc_id), 185);
+            Ok(())
+        }
+
+        #[test]
+        fn test_clone_to() -> crate::Result<()> {
+            let schema = throw_away_schema();
+            let field = schema.get_field("num_likes").unwrap();
+            let src_dir = TempDir::new()?;
+            let dst_dir = TempDir::new()?;
+            let src_index = Index::create_in_dir(src_dir.path(), schema.clone())?;
+            let mut index_writer = src_index.writer_for_tests()?;
+            index_writer.add_document(doc!(field => 1u64));
+            index_writer.commit()?;
+            index_writer.add_document(doc!(field => 2u64));
+            index_writer.commit()?;
+            src_index.clone_to(dst_dir.path())?;
+            assert!(matches!(
+                src_index.clone_to(dst_dir.path()),
+                Err(TantivyError::IndexAlreadyExists)
+            ));
+
+            // The segments of the source are merged and garbage collected.
+            let segment_ids = src_index.searchable_segment_ids()?;
+            block_on(index_writer.merge(&segment_ids))?;
+            block_on(index_writer.garbage_collect_files())?;
+            index_writer.add_document(doc!(field => 3u64));
+            index_writer.commit()?;
+            let dst_index = Index::open_in_dir(dst_dir.path())?;
+            assert_eq!(dst_index.searchable_segment_ids()?.len(), 2);
+            assert_eq!(dst_index.reader()?.searcher().num_docs(), 2);
+            let mut dst_index_writer = dst_index.writer_for_tests()?;
+            dst_index_writer.add_document(doc!(field => 4u64));
+            dst_index_writer.commit()?;
+            assert_eq!(dst_index.reader()?.searcher().num_docs(), 3);
+            assert_eq!(src_index.reader()?.searcher().num_docs(), 3);
+
+            // The files of an index in RAM are copied.
+            let ram_index = Index::create_in_ram(schema);
+            let mut ram_index_writer = ram_index.writer_for_tests()?;
+            ram_index_writer.add_document(doc!(field => 1u64));
+            ram_index_writer.commit()?;
+            let ram_dst_dir = TempDir::new()?;
+            ram_index.clone_to(ram_dst_dir.path())?;
+            let ram_dst_index = Index::open_in_dir(ram_dst_dir.path())?;
+            assert!(ram_dst_index.validate_checksum()?.is_empty());
+            assert_eq!(ram_dst_index.reader()?.searcher().num_docs(), 1);
+            Ok(())
+        }
     }
     fn test_index_on_commit_reload_policy_aux(field: Field, index: &Index, reader: &IndexReader) {
         let mut reader_index = reader.index();
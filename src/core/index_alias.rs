@@ -0,0 +1,170 @@
+use crate::reader::{IndexReader, LeasedItem};
+use crate::{Index, Searcher};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+
+/// Serializable description of an [`IndexAlias`](./struct.IndexAlias.html).
+///
+/// It is meant to be persisted by the application, so that the alias
+/// can be reopened on the same index after a restart.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexAliasMeta {
+    /// Name of the alias.
+    pub name: String,
+    /// Identifier of the index the alias points to.
+    ///
+    /// Tantivy does not interpret it, except in
+    /// [`IndexAlias::open_in_dir`](./struct.IndexAlias.html#method.open_in_dir),
+    /// for which it is the path of the index directory.
+    pub target: String,
+}
+
+struct AliasedIndex {
+    target: String,
+    index: Index,
+    reader: IndexReader,
+}
+
+/// Stable name for an index that can be replaced without downtime.
+///
+/// A typical reindexing pipeline builds a new index in the background,
+/// and then [`swap`](#method.swap)s it in. The searches go through
+/// [`searcher`](#method.searcher) and always use the current index.
+/// The `Searcher`s obtained before the swap remain valid, and keep on
+/// searching the previous index until they are dropped.
+///
+/// `IndexAlias` is cheap to clone: the clones share the same alias.
+#[derive(Clone)]
+pub struct IndexAlias {
+    name: String,
+    aliased_index: Arc<RwLock<AliasedIndex>>,
+}
+
+impl IndexAlias {
+    /// Creates an alias named `name`, for the index `index` identified by `target`.
+    ///
+    /// A reader is created for the index, with the default reload policy.
+    pub fn new(name: &str, target: &str, index: Index) -> crate::Result<IndexAlias> {
+        let reader = index.reader()?;
+        Ok(IndexAlias {
+            name: name.to_string(),
+            aliased_index: Arc::new(RwLock::new(AliasedIndex {
+                target: target.to_string(),
+                index,
+                reader,
+            })),
+        })
+    }
+
+    /// Reopens the alias described by `meta`, interpreting its target
+    /// as the path of the index directory.
+    #[cfg(feature = "mmap")]
+    pub fn open_in_dir(meta: &IndexAliasMeta) -> crate::Result<IndexAlias> {
+        let index = Index::open_in_dir(&meta.target)?;
+        IndexAlias::new(&meta.name, &meta.target, index)
+    }
+
+    /// Returns the name of the alias.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the identifier of the index the alias currently points to.
+    pub fn target(&self) -> String {
+        self.aliased_index.read().unwrap().target.clone()
+    }
+
+    /// Returns the serializable description of the alias.
+    pub fn meta(&self) -> IndexAliasMeta {
+        IndexAliasMeta {
+            name: self.name.clone(),
+            target: self.target(),
+        }
+    }
+
+    /// Returns the index the alias currently points to.
+    pub fn index(&self) -> Index {
+        self.aliased_index.read().unwrap().index.clone()
+    }
+
+    /// Returns the reader of the index the alias currently points to.
+    ///
+    /// The reader is not affected by later swaps.
+    pub fn reader(&self) -> IndexReader {
+        self.aliased_index.read().unwrap().reader.clone()
+    }
+
+    /// Returns a searcher on the index the alias currently points to.
+    pub fn searcher(&self) -> LeasedItem<Searcher> {
+        self.aliased_index.read().unwrap().reader.searcher()
+    }
+
+    /// Atomically points the alias to `new_index`, identified by `target`.
+    ///
+    /// The reader of the new index is created before the swap, so that searches
+    /// are never blocked on it. Returns the index the alias pointed to before the swap.
+    pub fn swap(&self, target: &str, new_index: Index) -> crate::Result<Index> {
+        let reader = new_index.reader()?;
+        let mut aliased_index = self.aliased_index.write().unwrap();
+        aliased_index.target = target.to_string();
+        aliased_index.reader = reader;
+        Ok(std::mem::replace(&mut aliased_index.index, new_index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IndexAlias, IndexAliasMeta};
+    use crate::collector::Count;
+    use crate::query::AllQuery;
+    use crate::schema::{Schema, TEXT};
+    use crate::Index;
+
+    fn build_index(num_docs: usize) -> crate::Result<Index> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        for _ in 0..num_docs {
+            index_writer.add_document(doc!(text => "hello"));
+        }
+        index_writer.commit()?;
+        Ok(index)
+    }
+
+    #[test]
+    fn test_index_alias_swap() -> crate::Result<()> {
+        let alias = IndexAlias::new("products", "products-v1", build_index(1)?)?;
+        let searcher_before_swap = alias.searcher();
+        assert_eq!(searcher_before_swap.search(&AllQuery, &Count)?, 1);
+
+        let previous_index = alias.clone().swap("products-v2", build_index(2)?)?;
+        assert_eq!(previous_index.searchable_segment_ids()?.len(), 1);
+        assert_eq!(searcher_before_swap.search(&AllQuery, &Count)?, 1);
+        assert_eq!(alias.searcher().search(&AllQuery, &Count)?, 2);
+        assert_eq!(alias.reader().searcher().search(&AllQuery, &Count)?, 2);
+        assert_eq!(
+            alias.meta(),
+            IndexAliasMeta {
+                name: "products".to_string(),
+                target: "products-v2".to_string(),
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_alias_meta_serialization() {
+        let meta = IndexAliasMeta {
+            name: "products".to_string(),
+            target: "/indexes/products-v2".to_string(),
+        };
+        let json = serde_json::to_string(&meta).unwrap();
+        assert_eq!(
+            json,
+            r#"{"name":"products","target":"/indexes/products-v2"}"#
+        );
+        let deserialized: IndexAliasMeta = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, meta);
+    }
+}
@@ -0,0 +1,44 @@
+//! Copy of the last commit of an index in another directory.
+use crate::core::Index;
+use crate::directory::{Directory, ManagedDirectory, MmapDirectory};
+use crate::indexer::segment_updater::save_metas;
+use std::fs;
+use std::path::Path;
+
+/// Hard links the file `path` of `index` to `dst_file_path`, or copies it
+/// if it cannot be linked.
+fn link_or_copy(index: &Index, path: &Path, dst_file_path: &Path) -> crate::Result<()> {
+    if let Some(src_file_path) = index.directory().local_path(path) {
+        if fs::hard_link(&src_file_path, dst_file_path).is_ok() {
+            return Ok(());
+        }
+        fs::copy(&src_file_path, dst_file_path)?;
+        return Ok(());
+    }
+    // The footer is part of the file, and is copied as is.
+    let bytes = index
+        .directory()
+        .open_read_with_footer(path)?
+        .read_bytes()?;
+    fs::write(dst_file_path, bytes.as_slice())?;
+    Ok(())
+}
+
+/// Copies the files of the segments of the last commit of `index`,
+/// and then writes the `meta.json` file of the copy.
+pub(crate) fn clone_to(index: &Index, dst_path: &Path) -> crate::Result<()> {
+    // The files of the segments of `metas` are not garbage collected
+    // as long as `metas` is alive.
+    let metas = index.load_metas()?;
+    let dst_directory = ManagedDirectory::wrap(MmapDirectory::open(dst_path)?)?;
+    for segment_meta in &metas.segments {
+        for path in segment_meta.list_files() {
+            if !index.directory().exists(&path)? {
+                continue;
+            }
+            dst_directory.register_file_as_managed(&path)?;
+            link_or_copy(index, &path, &dst_path.join(&path))?;
+        }
+    }
+    save_metas(&metas, &dst_directory)
+}
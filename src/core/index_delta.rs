@@ -0,0 +1,297 @@
+//! Export and import of the changes of an index since a given commit,
+//! to synchronize a replica with its primary.
+//!
+//! A delta contains the `meta.json` of the last commit of the primary,
+//! followed by the files that the replica is missing:
+//! - the files of the segments added to the index after `since_opstamp`,
+//! - the delete files of the other segments, if their deletes were updated
+//!   after `since_opstamp`.
+use crate::common::BinarySerializable;
+use crate::core::{Index, IndexMeta, SegmentComponent, SegmentMeta, META_FILEPATH};
+use crate::directory::{Directory, TerminatingWrite, INDEX_WRITER_LOCK};
+use crate::error::{DataCorruption, TantivyError};
+use crate::indexer::segment_updater::save_metas;
+use crate::Opstamp;
+use std::io::{self, Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+/// Version of the format of the deltas.
+const DELTA_FORMAT_VERSION: u32 = 1;
+
+/// Returns the files of the segment, in the order they should be applied.
+///
+/// The delete file comes first: the deletes are applied before the other files
+/// of the segment are written.
+fn segment_files(segment_meta: &SegmentMeta, include_delete_file_only: bool) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    if segment_meta.has_deletes() {
+        files.push(segment_meta.relative_path(SegmentComponent::DELETE));
+    }
+    if !include_delete_file_only {
+        for &component in SegmentComponent::iterator() {
+            if !matches!(component, SegmentComponent::DELETE) {
+                files.push(segment_meta.relative_path(component));
+            }
+        }
+    }
+    files
+}
+
+fn is_added_after(segment_meta: &SegmentMeta, since_opstamp: Opstamp) -> bool {
+    match segment_meta.opstamp() {
+        Some(opstamp) => opstamp > since_opstamp,
+        // Segments added by older versions of tantivy are only
+        // part of a full export.
+        None => since_opstamp == 0,
+    }
+}
+
+fn has_deletes_after(segment_meta: &SegmentMeta, since_opstamp: Opstamp) -> bool {
+    segment_meta.has_deletes()
+        && matches!(segment_meta.delete_opstamp(), Some(opstamp) if opstamp > since_opstamp)
+}
+
+pub(crate) fn export_delta(
+    index: &Index,
+    since_opstamp: Opstamp,
+    mut dest: &mut dyn Write,
+) -> crate::Result<()> {
+    // The segment metas are tracked: their files cannot be garbage collected
+    // until the export is over.
+    let metas = index.load_metas()?;
+    let directory = index.directory();
+    let mut files: Vec<PathBuf> = Vec::new();
+    // Deletes of the existing segments go first.
+    for segment_meta in &metas.segments {
+        if !is_added_after(segment_meta, since_opstamp)
+            && has_deletes_after(segment_meta, since_opstamp)
+        {
+            files.extend(segment_files(segment_meta, true));
+        }
+    }
+    for segment_meta in &metas.segments {
+        if is_added_after(segment_meta, since_opstamp) {
+            for path in segment_files(segment_meta, false) {
+                // Segments written by older versions of tantivy do not have term vectors.
+                if directory.exists(&path)? {
+                    files.push(path);
+                }
+            }
+        }
+    }
+    DELTA_FORMAT_VERSION.serialize(&mut dest)?;
+    serde_json::to_string(&metas)?.serialize(&mut dest)?;
+    (files.len() as u64).serialize(&mut dest)?;
+    for path in &files {
+        let bytes = directory.open_read(path)?.read_bytes()?;
+        path.to_string_lossy().to_string().serialize(&mut dest)?;
+        (bytes.len() as u64).serialize(&mut dest)?;
+        dest.write_all(bytes.as_slice())?;
+    }
+    dest.flush()?;
+    Ok(())
+}
+
+/// Returns an error if `path` is not the name of a file of a segment.
+fn check_file_name(path: &Path) -> crate::Result<()> {
+    let mut components = path.components();
+    let is_file_name = matches!(
+        (components.next(), components.next()),
+        (Some(Component::Normal(_)), None)
+    );
+    if !is_file_name || path == *META_FILEPATH {
+        return Err(DataCorruption::comment_only(format!(
+            "The delta contains an invalid file name {:?}.",
+            path
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+pub(crate) fn apply_delta(index: &Index, mut src: &mut dyn Read) -> crate::Result<Opstamp> {
+    let _directory_lock = index
+        .directory()
+        .acquire_lock(&INDEX_WRITER_LOCK)
+        .map_err(|err| {
+            TantivyError::LockFailure(
+                err,
+                Some(
+                    "Failed to acquire index lock. A delta cannot be applied while \
+                     an `IndexWriter` is working on this `Directory`."
+                        .to_string(),
+                ),
+            )
+        })?;
+    let version = u32::deserialize(&mut src)?;
+    if version != DELTA_FORMAT_VERSION {
+        return Err(DataCorruption::comment_only(format!(
+            "Unsupported delta format version {} (expected version {}).",
+            version, DELTA_FORMAT_VERSION
+        ))
+        .into());
+    }
+    let meta_json = String::deserialize(&mut src)?;
+    let metas: IndexMeta = index.deserialize_metas(&meta_json).map_err(|err| {
+        DataCorruption::comment_only(format!("The meta.json of the delta is invalid: {}", err))
+    })?;
+    if metas.schema != index.schema() {
+        return Err(TantivyError::SchemaError(
+            "The schema of the delta differs from the schema of the index. The index must be \
+             synchronized from scratch."
+                .to_string(),
+        ));
+    }
+    let directory = index.directory();
+    let num_files = u64::deserialize(&mut src)?;
+    for _ in 0..num_files {
+        let path = PathBuf::from(String::deserialize(&mut src)?);
+        check_file_name(&path)?;
+        let num_bytes = u64::deserialize(&mut src)?;
+        let mut file_bytes = (&mut src).take(num_bytes);
+        if directory.exists(&path)? {
+            // The file was part of a previous delta.
+            io::copy(&mut file_bytes, &mut io::sink())?;
+            continue;
+        }
+        let mut write = directory.open_write(&path)?;
+        let num_bytes_copied = io::copy(&mut file_bytes, &mut write)?;
+        if num_bytes_copied != num_bytes {
+            return Err(DataCorruption::comment_only(format!(
+                "The delta is truncated: {:?} is missing {} bytes.",
+                path,
+                num_bytes - num_bytes_copied
+            ))
+            .into());
+        }
+        write.terminate()?;
+    }
+    for segment_meta in &metas.segments {
+        for path in segment_files(segment_meta, false) {
+            let is_optional = path == segment_meta.relative_path(SegmentComponent::TERMVECTORS);
+            if !is_optional && !directory.exists(&path)? {
+                return Err(DataCorruption::comment_only(format!(
+                    "The delta cannot be applied, the file {:?} is missing. The delta must be \
+                     exported since the opstamp returned by the last delta applied to the index.",
+                    path
+                ))
+                .into());
+            }
+        }
+    }
+    // Writing `meta.json` atomically makes the deletes and the new segments
+    // visible all at once.
+    save_metas(&metas, directory)?;
+    Ok(metas.opstamp)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collector::Count;
+    use crate::query::TermQuery;
+    use crate::schema::{IndexRecordOption, Schema, STRING};
+    use crate::{Index, TantivyError, Term};
+
+    fn count_docs(index: &Index, id_field: crate::schema::Field, id: &str) -> crate::Result<usize> {
+        let query = TermQuery::new(
+            Term::from_field_text(id_field, id),
+            IndexRecordOption::Basic,
+        );
+        let reader = index.reader()?;
+        reader.reload()?;
+        reader.searcher().search(&query, &Count)
+    }
+
+    #[test]
+    fn test_export_and_apply_delta() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let id = schema_builder.add_text_field("id", STRING);
+        let schema = schema_builder.build();
+        let primary = Index::create_in_ram(schema.clone());
+        let replica = Index::create_in_ram(schema);
+        let mut index_writer = primary.writer_for_tests()?;
+
+        index_writer.add_document(doc!(id => "a"));
+        index_writer.add_document(doc!(id => "b"));
+        let first_opstamp = index_writer.commit()?;
+        let mut delta = Vec::new();
+        index_writer.export_delta(0, &mut delta)?;
+        assert_eq!(replica.apply_delta(&mut &delta[..])?, first_opstamp);
+        assert_eq!(count_docs(&replica, id, "a")?, 1);
+
+        index_writer.delete_term(Term::from_field_text(id, "a"));
+        index_writer.add_document(doc!(id => "c"));
+        let second_opstamp = index_writer.commit()?;
+        let mut delta = Vec::new();
+        index_writer.export_delta(first_opstamp, &mut delta)?;
+        let mut full_export = Vec::new();
+        index_writer.export_delta(0, &mut full_export)?;
+        assert!(delta.len() < full_export.len());
+        assert_eq!(replica.apply_delta(&mut &delta[..])?, second_opstamp);
+        assert_eq!(count_docs(&replica, id, "a")?, 0);
+        assert_eq!(count_docs(&replica, id, "b")?, 1);
+        assert_eq!(count_docs(&replica, id, "c")?, 1);
+        assert_eq!(
+            replica.searchable_segment_ids()?,
+            primary.searchable_segment_ids()?
+        );
+        // Applying a delta twice is harmless.
+        assert_eq!(replica.apply_delta(&mut &delta[..])?, second_opstamp);
+        Ok(())
+    }
+
+    #[test]
+    fn test_apply_delta_after_merge() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let id = schema_builder.add_text_field("id", STRING);
+        let schema = schema_builder.build();
+        let primary = Index::create_in_ram(schema.clone());
+        let replica = Index::create_in_ram(schema);
+        let mut index_writer = primary.writer_for_tests()?;
+        index_writer.add_document(doc!(id => "a"));
+        index_writer.commit()?;
+        index_writer.add_document(doc!(id => "b"));
+        let opstamp = index_writer.commit()?;
+        let mut delta = Vec::new();
+        index_writer.export_delta(0, &mut delta)?;
+        replica.apply_delta(&mut &delta[..])?;
+
+        let segment_ids = primary.searchable_segment_ids()?;
+        futures::executor::block_on(index_writer.merge(&segment_ids))?;
+        let mut delta = Vec::new();
+        index_writer.export_delta(opstamp, &mut delta)?;
+        replica.apply_delta(&mut &delta[..])?;
+        assert_eq!(replica.searchable_segment_ids()?.len(), 1);
+        assert_eq!(count_docs(&replica, id, "a")?, 1);
+        assert_eq!(count_docs(&replica, id, "b")?, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_apply_delta_requires_previous_deltas() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let id = schema_builder.add_text_field("id", STRING);
+        let schema = schema_builder.build();
+        let primary = Index::create_in_ram(schema.clone());
+        let replica = Index::create_in_ram(schema);
+        let mut index_writer = primary.writer_for_tests()?;
+        index_writer.add_document(doc!(id => "a"));
+        let opstamp = index_writer.commit()?;
+        index_writer.add_document(doc!(id => "b"));
+        index_writer.commit()?;
+        let mut delta = Vec::new();
+        index_writer.export_delta(opstamp, &mut delta)?;
+        assert!(matches!(
+            replica.apply_delta(&mut &delta[..]),
+            Err(TantivyError::DataCorruption(_))
+        ));
+        assert!(replica.searchable_segment_ids()?.is_empty());
+
+        let _replica_writer = replica.writer_for_tests()?;
+        assert!(matches!(
+            replica.apply_delta(&mut &delta[..]),
+            Err(TantivyError::LockFailure(_, _))
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,273 @@
+use crate::core::{Index, SegmentComponent, META_FILEPATH};
+use crate::directory::{Directory, INDEX_WRITER_LOCK};
+use crate::error::{DataCorruption, TantivyError};
+use std::collections::HashSet;
+use std::fmt;
+use std::path::PathBuf;
+
+/// Severity of a [`HealthIssue`](./struct.HealthIssue.html).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HealthSeverity {
+    /// The index is consistent, but some disk space is wasted.
+    Warning,
+    /// Some data of the index is missing or corrupted.
+    Error,
+}
+
+/// An issue found by [`Index::check_health`](./struct.Index.html#method.check_health).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HealthIssue {
+    /// Severity of the issue.
+    pub severity: HealthSeverity,
+    /// Human readable description of the issue.
+    pub description: String,
+}
+
+impl fmt::Display for HealthIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}: {}", self.severity, self.description)
+    }
+}
+
+/// Report of [`Index::check_health`](./struct.Index.html#method.check_health).
+#[derive(Clone, Debug, Default)]
+pub struct IndexHealthReport {
+    issues: Vec<HealthIssue>,
+    orphaned_files: Vec<PathBuf>,
+}
+
+impl IndexHealthReport {
+    /// Returns the issues found in the index.
+    pub fn issues(&self) -> &[HealthIssue] {
+        &self.issues
+    }
+
+    /// Returns the files created by tantivy that are not used by the index anymore.
+    pub fn orphaned_files(&self) -> &[PathBuf] {
+        &self.orphaned_files
+    }
+
+    /// Returns true iff no issue was found.
+    pub fn is_healthy(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Returns true iff some of the issues are errors.
+    ///
+    /// Such an index cannot be repaired.
+    pub fn has_errors(&self) -> bool {
+        self.issues
+            .iter()
+            .any(|issue| issue.severity == HealthSeverity::Error)
+    }
+
+    fn error(&mut self, description: String) {
+        self.issues.push(HealthIssue {
+            severity: HealthSeverity::Error,
+            description,
+        });
+    }
+}
+
+/// Returns the files used by the last commit and by the segments being written.
+fn living_files(index: &Index) -> crate::Result<HashSet<PathBuf>> {
+    let mut files: HashSet<PathBuf> = index
+        .searchable_segment_metas()?
+        .into_iter()
+        .chain(index.list_all_segment_metas())
+        .flat_map(|segment_meta| segment_meta.list_files())
+        .collect();
+    files.insert(META_FILEPATH.to_path_buf());
+    Ok(files)
+}
+
+pub(crate) fn check_health(index: &Index) -> crate::Result<IndexHealthReport> {
+    let mut report = IndexHealthReport::default();
+    let directory = index.directory();
+    let metas = match index.load_metas() {
+        Ok(metas) => metas,
+        Err(err) => {
+            report.error(format!("meta.json cannot be loaded: {}", err));
+            return Ok(report);
+        }
+    };
+    for segment_meta in &metas.segments {
+        for &component in SegmentComponent::iterator() {
+            if matches!(component, SegmentComponent::DELETE) && !segment_meta.has_deletes() {
+                continue;
+            }
+            let path = segment_meta.relative_path(component);
+            match directory.exists(&path) {
+                Ok(true) => {}
+                // Segments written by older versions of tantivy do not have term vectors.
+                Ok(false) if matches!(component, SegmentComponent::TERMVECTORS) => continue,
+                Ok(false) => {
+                    report.error(format!("File {:?} of the index is missing.", path));
+                    continue;
+                }
+                Err(err) => {
+                    report.error(format!("File {:?} cannot be accessed: {}", path, err));
+                    continue;
+                }
+            }
+            match directory.validate_checksum(&path) {
+                Ok(true) => {}
+                Ok(false) => report.error(format!("File {:?} has a wrong checksum.", path)),
+                Err(err) => report.error(format!("File {:?} cannot be read: {}", path, err)),
+            }
+        }
+    }
+    let living_files = living_files(index)?;
+    let mut orphaned_files: Vec<PathBuf> = directory
+        .list_managed_files()
+        .into_iter()
+        .filter(|path| !living_files.contains(path))
+        .collect();
+    orphaned_files.sort();
+    for path in &orphaned_files {
+        report.issues.push(HealthIssue {
+            severity: HealthSeverity::Warning,
+            description: format!("File {:?} is not used by the index.", path),
+        });
+    }
+    report.orphaned_files = orphaned_files;
+    Ok(report)
+}
+
+pub(crate) fn repair(index: &Index, report: &IndexHealthReport) -> crate::Result<()> {
+    if report.has_errors() {
+        let errors: Vec<String> = report
+            .issues()
+            .iter()
+            .filter(|issue| issue.severity == HealthSeverity::Error)
+            .map(|issue| issue.description.clone())
+            .collect();
+        return Err(DataCorruption::comment_only(format!(
+            "The index cannot be repaired: {}",
+            errors.join(" ")
+        ))
+        .into());
+    }
+    let _directory_lock = index
+        .directory()
+        .acquire_lock(&INDEX_WRITER_LOCK)
+        .map_err(|err| {
+            TantivyError::LockFailure(
+                err,
+                Some(
+                    "Failed to acquire index lock. The index cannot be repaired while \
+                     an `IndexWriter` is working on this `Directory`."
+                        .to_string(),
+                ),
+            )
+        })?;
+    // Files that became used since the report was computed are kept.
+    let living_files = living_files(index)?;
+    let files_to_delete: HashSet<&PathBuf> = report
+        .orphaned_files()
+        .iter()
+        .filter(|path| !living_files.contains(*path))
+        .collect();
+    let mut index = index.clone();
+    let directory = index.directory_mut();
+    let files_to_keep: HashSet<PathBuf> = directory
+        .list_managed_files()
+        .into_iter()
+        .filter(|path| !files_to_delete.contains(path))
+        .collect();
+    let gc_result = directory.garbage_collect(move || files_to_keep)?;
+    if !gc_result.failed_to_delete_files.is_empty() {
+        return Err(TantivyError::SystemError(format!(
+            "Failed to delete the orphaned files {:?}",
+            gc_result.failed_to_delete_files
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HealthSeverity;
+    use crate::directory::{Directory, RAMDirectory, TerminatingWrite};
+    use crate::schema::{Schema, TEXT};
+    use crate::{Index, SegmentComponent, TantivyError};
+    use std::io::Write;
+    use std::path::Path;
+
+    #[test]
+    fn test_check_health_and_repair() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create(RAMDirectory::create(), schema_builder.build())?;
+        {
+            let mut index_writer = index.writer_for_tests()?;
+            index_writer.add_document(doc!(text => "hello"));
+            index_writer.commit()?;
+            index_writer.add_document(doc!(text => "happy tax payer"));
+            index_writer.commit()?;
+        }
+        assert!(index.check_health()?.is_healthy());
+
+        let orphan = Path::new("orphan.idx");
+        let mut wrt = index.directory().open_write(orphan)?;
+        wrt.write_all(b"orphan")?;
+        wrt.terminate()?;
+        let report = index.check_health()?;
+        assert!(!report.is_healthy());
+        assert!(!report.has_errors());
+        assert_eq!(report.orphaned_files(), &[orphan.to_path_buf()]);
+        assert_eq!(report.issues()[0].severity, HealthSeverity::Warning);
+
+        index.repair(&report)?;
+        assert!(!index.directory().exists(orphan)?);
+        assert!(index.check_health()?.is_healthy());
+        assert_eq!(index.reader()?.searcher().num_docs(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_health_damaged_index() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create(RAMDirectory::create(), schema_builder.build())?;
+        {
+            let mut index_writer = index.writer_for_tests()?;
+            index_writer.add_document(doc!(text => "hello"));
+            index_writer.commit()?;
+        }
+        let segment_meta = index.searchable_segment_metas()?[0].clone();
+        let store_path = segment_meta.relative_path(SegmentComponent::STORE);
+        index.directory().atomic_write(&store_path, b"garbage")?;
+        let postings_path = segment_meta.relative_path(SegmentComponent::POSTINGS);
+        index.directory().delete(&postings_path).unwrap();
+
+        let report = index.check_health()?;
+        assert!(report.has_errors());
+        let errors: Vec<&str> = report
+            .issues()
+            .iter()
+            .filter(|issue| issue.severity == HealthSeverity::Error)
+            .map(|issue| issue.description.as_str())
+            .collect();
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().any(|error| error.contains(".idx")));
+        assert!(errors.iter().any(|error| error.contains(".store")));
+        assert!(matches!(
+            index.repair(&report),
+            Err(TantivyError::DataCorruption(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_repair_requires_writer_lock() -> crate::Result<()> {
+        let index = Index::create_in_ram(Schema::builder().build());
+        let report = index.check_health()?;
+        let _index_writer = index.writer_for_tests()?;
+        assert!(matches!(
+            index.repair(&report),
+            Err(TantivyError::LockFailure(_, _))
+        ));
+        Ok(())
+    }
+}
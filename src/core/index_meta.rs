@@ -1,10 +1,11 @@
 use super::SegmentComponent;
 use crate::core::SegmentId;
-use crate::schema::Schema;
+use crate::schema::{Schema, SchemaVersion};
+use crate::store::StoredFieldCompression;
 use crate::Opstamp;
 use census::{Inventory, TrackedObject};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::path::PathBuf;
 
@@ -34,6 +35,11 @@ impl SegmentMetaInventory {
             segment_id,
             max_doc,
             deletes: None,
+            schema_version: None,
+            num_bytes: None,
+            user_data: HashMap::new(),
+            store_compression: None,
+            opstamp: None,
         };
         SegmentMeta::from(self.inventory.track(inner))
     }
@@ -115,6 +121,7 @@ impl SegmentMeta {
             SegmentComponent::STORE => ".store".to_string(),
             SegmentComponent::FASTFIELDS => ".fast".to_string(),
             SegmentComponent::FIELDNORMS => ".fieldnorm".to_string(),
+            SegmentComponent::TERMVECTORS => ".termvec".to_string(),
             SegmentComponent::DELETE => format!(".{}.del", self.delete_opstamp().unwrap_or(0)),
         });
         PathBuf::from(path)
@@ -143,6 +150,48 @@ impl SegmentMeta {
             .map(|delete_meta| delete_meta.opstamp)
     }
 
+    /// Returns the version of the schema the segment was written with.
+    ///
+    /// It is `None` for the segments written before the schema versions
+    /// were recorded, and whose schema has not evolved since.
+    pub fn schema_version(&self) -> Option<SchemaVersion> {
+        self.tracked.schema_version
+    }
+
+    /// Returns the size in bytes of the files of the segment,
+    /// not counting its delete file.
+    ///
+    /// It is `None` for the segments written before the sizes were recorded.
+    pub fn num_bytes(&self) -> Option<u64> {
+        self.tracked.num_bytes
+    }
+
+    /// Returns the user data attached to the segment.
+    ///
+    /// See [`with_user_data`](#method.with_user_data).
+    pub fn user_data(&self) -> &HashMap<String, String> {
+        &self.tracked.user_data
+    }
+
+    /// Returns the compression of the doc store of the segment.
+    ///
+    /// The segments written before the compression was recorded use
+    /// the default compression.
+    pub fn store_compression(&self) -> StoredFieldCompression {
+        self.tracked.store_compression.unwrap_or_default()
+    }
+
+    /// Returns an opstamp greater than the opstamps of all of the commits
+    /// that preceded the addition of the segment to the index.
+    ///
+    /// It is the opstamp of the commit that added the segment, or for merged
+    /// segments, the opstamp following the commit preceding the end of the merge.
+    /// It is `None` for the segments added by older versions of tantivy,
+    /// until the next commit.
+    pub fn opstamp(&self) -> Option<Opstamp> {
+        self.tracked.opstamp
+    }
+
     /// Returns true iff the segment meta contains
     /// delete information.
     pub fn has_deletes(&self) -> bool {
@@ -160,6 +209,11 @@ impl SegmentMeta {
             segment_id: inner_meta.segment_id,
             max_doc,
             deletes: None,
+            schema_version: inner_meta.schema_version,
+            num_bytes: inner_meta.num_bytes,
+            user_data: inner_meta.user_data.clone(),
+            store_compression: inner_meta.store_compression,
+            opstamp: inner_meta.opstamp,
         });
         SegmentMeta { tracked }
     }
@@ -174,6 +228,94 @@ impl SegmentMeta {
             segment_id: inner_meta.segment_id,
             max_doc: inner_meta.max_doc,
             deletes: Some(delete_meta),
+            schema_version: inner_meta.schema_version,
+            num_bytes: inner_meta.num_bytes,
+            user_data: inner_meta.user_data.clone(),
+            store_compression: inner_meta.store_compression,
+            opstamp: inner_meta.opstamp,
+        });
+        SegmentMeta { tracked }
+    }
+
+    /// Sets the version of the schema the segment was written with.
+    pub(crate) fn with_schema_version(self, schema_version: SchemaVersion) -> SegmentMeta {
+        let tracked = self.tracked.map(move |inner_meta| InnerSegmentMeta {
+            segment_id: inner_meta.segment_id,
+            max_doc: inner_meta.max_doc,
+            deletes: inner_meta.deletes.clone(),
+            schema_version: Some(schema_version),
+            num_bytes: inner_meta.num_bytes,
+            user_data: inner_meta.user_data.clone(),
+            store_compression: inner_meta.store_compression,
+            opstamp: inner_meta.opstamp,
+        });
+        SegmentMeta { tracked }
+    }
+
+    /// Sets the size of the files of the segment.
+    pub(crate) fn with_num_bytes(self, num_bytes: u64) -> SegmentMeta {
+        let tracked = self.tracked.map(move |inner_meta| InnerSegmentMeta {
+            segment_id: inner_meta.segment_id,
+            max_doc: inner_meta.max_doc,
+            deletes: inner_meta.deletes.clone(),
+            schema_version: inner_meta.schema_version,
+            num_bytes: Some(num_bytes),
+            user_data: inner_meta.user_data.clone(),
+            store_compression: inner_meta.store_compression,
+            opstamp: inner_meta.opstamp,
+        });
+        SegmentMeta { tracked }
+    }
+
+    /// Sets the opstamp at which the segment was added to the index.
+    pub(crate) fn with_opstamp(self, opstamp: Opstamp) -> SegmentMeta {
+        let tracked = self.tracked.map(move |inner_meta| InnerSegmentMeta {
+            segment_id: inner_meta.segment_id,
+            max_doc: inner_meta.max_doc,
+            deletes: inner_meta.deletes.clone(),
+            schema_version: inner_meta.schema_version,
+            num_bytes: inner_meta.num_bytes,
+            user_data: inner_meta.user_data.clone(),
+            store_compression: inner_meta.store_compression,
+            opstamp: Some(opstamp),
+        });
+        SegmentMeta { tracked }
+    }
+
+    /// Attaches application defined key-value pairs to the segment.
+    ///
+    /// They are persisted in the `meta.json` file, and are never
+    /// interpreted by tantivy. When segments are merged, the merged segment
+    /// receives the union of their user data. The values of the segments that
+    /// come last in the merge take precedence.
+    pub fn with_user_data(self, user_data: HashMap<String, String>) -> SegmentMeta {
+        let tracked = self.tracked.map(move |inner_meta| InnerSegmentMeta {
+            segment_id: inner_meta.segment_id,
+            max_doc: inner_meta.max_doc,
+            deletes: inner_meta.deletes.clone(),
+            schema_version: inner_meta.schema_version,
+            num_bytes: inner_meta.num_bytes,
+            user_data,
+            store_compression: inner_meta.store_compression,
+            opstamp: inner_meta.opstamp,
+        });
+        SegmentMeta { tracked }
+    }
+
+    /// Sets the compression of the doc store of the segment.
+    pub(crate) fn with_store_compression(
+        self,
+        store_compression: StoredFieldCompression,
+    ) -> SegmentMeta {
+        let tracked = self.tracked.map(move |inner_meta| InnerSegmentMeta {
+            segment_id: inner_meta.segment_id,
+            max_doc: inner_meta.max_doc,
+            deletes: inner_meta.deletes.clone(),
+            schema_version: inner_meta.schema_version,
+            num_bytes: inner_meta.num_bytes,
+            user_data: inner_meta.user_data.clone(),
+            store_compression: Some(store_compression),
+            opstamp: inner_meta.opstamp,
         });
         SegmentMeta { tracked }
     }
@@ -184,6 +326,16 @@ struct InnerSegmentMeta {
     segment_id: SegmentId,
     max_doc: u32,
     deletes: Option<DeleteMeta>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    schema_version: Option<SchemaVersion>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    num_bytes: Option<u64>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    user_data: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    store_compression: Option<StoredFieldCompression>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    opstamp: Option<Opstamp>,
 }
 
 impl InnerSegmentMeta {
@@ -194,6 +346,38 @@ impl InnerSegmentMeta {
     }
 }
 
+/// Settings of an `Index`, persisted in its `meta.json` file.
+///
+/// See [`Index::set_settings`](./struct.Index.html#method.set_settings).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexSettings {
+    #[serde(default)]
+    stored_field_compression: StoredFieldCompression,
+}
+
+impl IndexSettings {
+    /// Sets the compression of the doc store of the new segments.
+    ///
+    /// The existing segments keep their compression until they are merged:
+    /// the merged segment is compressed with the compression of the settings.
+    pub fn with_stored_field_compression(
+        mut self,
+        stored_field_compression: StoredFieldCompression,
+    ) -> IndexSettings {
+        self.stored_field_compression = stored_field_compression;
+        self
+    }
+
+    /// Returns the compression of the doc store of the new segments.
+    pub fn stored_field_compression(&self) -> StoredFieldCompression {
+        self.stored_field_compression
+    }
+
+    fn is_default(&self) -> bool {
+        *self == IndexSettings::default()
+    }
+}
+
 /// Meta information about the `Index`.
 ///
 /// This object is serialized on disk in the `meta.json` file.
@@ -217,6 +401,15 @@ pub struct IndexMeta {
     /// to help identify this commit.
     /// This payload is entirely unused by tantivy.
     pub payload: Option<String>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    /// Application defined key-value pairs.
+    ///
+    /// Unlike the payload, they are kept from one commit to the next.
+    /// See [`Index::set_metadata`](./struct.Index.html#method.set_metadata).
+    pub metadata: HashMap<String, String>,
+    #[serde(skip_serializing_if = "IndexSettings::is_default")]
+    /// Settings of the index.
+    pub index_settings: IndexSettings,
 }
 
 #[derive(Deserialize)]
@@ -226,6 +419,10 @@ struct UntrackedIndexMeta {
     pub opstamp: Opstamp,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub payload: Option<String>,
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+    #[serde(default)]
+    pub index_settings: IndexSettings,
 }
 
 impl UntrackedIndexMeta {
@@ -239,6 +436,8 @@ impl UntrackedIndexMeta {
             schema: self.schema,
             opstamp: self.opstamp,
             payload: self.payload,
+            metadata: self.metadata,
+            index_settings: self.index_settings,
         }
     }
 }
@@ -255,6 +454,8 @@ impl IndexMeta {
             schema,
             opstamp: 0u64,
             payload: None,
+            metadata: HashMap::new(),
+            index_settings: IndexSettings::default(),
         }
     }
 
@@ -281,9 +482,10 @@ impl fmt::Debug for IndexMeta {
 #[cfg(test)]
 mod tests {
 
-    use super::IndexMeta;
+    use super::{IndexMeta, IndexSettings};
     use crate::schema::{Schema, TEXT};
     use serde_json;
+    use std::collections::HashMap;
 
     #[test]
     fn test_serialize_metas() {
@@ -297,6 +499,8 @@ mod tests {
             schema,
             opstamp: 0u64,
             payload: None,
+            metadata: HashMap::new(),
+            index_settings: IndexSettings::default(),
         };
         let json = serde_json::ser::to_string(&index_metas).expect("serialization failed");
         assert_eq!(
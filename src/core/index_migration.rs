@@ -0,0 +1,344 @@
+//! Rewriting of an index in the format of the current version of tantivy.
+//!
+//! The segments of the source index are read with the readers of this version,
+//! and written again with the current serializers, as a merge of a single
+//! segment would. The files written before the index format 4 are converted
+//! to the current format as they are read, by a `LegacyFormatDirectory`.
+use crate::common::convert_composite_file;
+use crate::core::{Index, IndexMeta, SegmentMeta, SegmentReader};
+use crate::directory::error::{
+    DeleteError, Incompatibility, LockError, OpenReadError, OpenWriteError,
+};
+use crate::directory::{
+    Directory, DirectoryLock, FileHandle, FileSlice, Footer, Lock, VersionedFooter, WatchCallback,
+    WatchHandle, WritePtr, INDEX_WRITER_LOCK,
+};
+use crate::error::{DataCorruption, TantivyError};
+use crate::indexer::merger::IndexMerger;
+use crate::indexer::segment_updater::save_metas;
+use crate::indexer::SegmentSerializer;
+use crate::schema::Schema;
+use std::ffi::OsStr;
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+/// Options of [`Index::migrate`](./struct.Index.html#method.migrate).
+#[derive(Clone, Debug, Default)]
+pub struct MigrateOptions {
+    /// If true, the source index is only checked: its files are opened and their
+    /// checksums are verified, but the destination index is not written.
+    pub dry_run: bool,
+}
+
+/// Directory presenting the files of an index written before the index format 4
+/// as if they were written in the current format, so that the index can be migrated.
+///
+/// The term dictionaries and the fast fields of these files are converted when they are
+/// opened. Their footer is replaced by a footer in the current format, whose checksum is
+/// valid if and only if the checksum of the original file was. The other files, and the
+/// files already written in the current format, are read as they are.
+///
+/// The directory is only used to read the source index of a migration.
+pub(crate) struct LegacyFormatDirectory {
+    directory: Box<dyn Directory>,
+    schema: Schema,
+}
+
+impl LegacyFormatDirectory {
+    /// Wraps the directory of an index whose schema is `schema`.
+    pub fn new<D: Directory>(directory: D, schema: Schema) -> LegacyFormatDirectory {
+        LegacyFormatDirectory {
+            directory: Box::new(directory),
+            schema,
+        }
+    }
+
+    fn convert_body(&self, path: &Path, body: &[u8]) -> io::Result<Vec<u8>> {
+        match path.extension().and_then(OsStr::to_str) {
+            Some("term") => convert_composite_file(body, |_field, _idx, term_dictionary| {
+                crate::termdict::upgrade_from_index_format_v3(term_dictionary)
+            }),
+            Some("fast") => crate::fastfield::upgrade_from_index_format_v3(body, &self.schema),
+            _ => Ok(body.to_vec()),
+        }
+    }
+
+    fn convert_file(&self, path: &Path, file: FileSlice) -> Result<FileSlice, OpenReadError> {
+        let wrap_io_error = |io_error| OpenReadError::wrap_io_error(io_error, path.to_path_buf());
+        let (footer, body) = Footer::extract_footer(file.clone()).map_err(wrap_io_error)?;
+        let (crc32, store_compression) = match &footer.versioned_footer {
+            VersionedFooter::V1 {
+                crc32,
+                store_compression,
+            }
+            | VersionedFooter::V2 {
+                crc32,
+                store_compression,
+            }
+            | VersionedFooter::V3 {
+                crc32,
+                store_compression,
+            } => (*crc32, store_compression.clone()),
+            VersionedFooter::V4 { .. } | VersionedFooter::UnknownVersion => return Ok(file),
+        };
+        // Before the index format 4, the doc stores of all of the segments were
+        // compressed with the default compression of the build.
+        if store_compression != crate::store::COMPRESSION {
+            return Err(OpenReadError::IncompatibleIndex(
+                Incompatibility::CompressionMismatch {
+                    library_compression_format: crate::store::COMPRESSION.to_string(),
+                    index_compression_format: store_compression,
+                },
+            ));
+        }
+        let body_bytes = body.read_bytes().map_err(wrap_io_error)?;
+        let is_checksum_valid = crc32fast::hash(body_bytes.as_slice()) == crc32;
+        let mut converted = self
+            .convert_body(path, body_bytes.as_slice())
+            .map_err(wrap_io_error)?;
+        let mut converted_crc32 = crc32fast::hash(&converted);
+        if !is_checksum_valid {
+            // The converted file has to be reported as damaged too.
+            converted_crc32 = !converted_crc32;
+        }
+        let converted_footer = Footer {
+            version: footer.version,
+            meta: footer.meta,
+            versioned_footer: VersionedFooter::V4 {
+                crc32: converted_crc32,
+                store_compression,
+            },
+        };
+        converted_footer
+            .append_footer(&mut converted)
+            .map_err(wrap_io_error)?;
+        Ok(FileSlice::from(converted))
+    }
+}
+
+impl Clone for LegacyFormatDirectory {
+    fn clone(&self) -> Self {
+        LegacyFormatDirectory {
+            directory: self.directory.box_clone(),
+            schema: self.schema.clone(),
+        }
+    }
+}
+
+impl fmt::Debug for LegacyFormatDirectory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "LegacyFormatDirectory({:?})", self.directory)
+    }
+}
+
+impl Directory for LegacyFormatDirectory {
+    fn get_file_handle(&self, path: &Path) -> Result<Box<dyn FileHandle>, OpenReadError> {
+        Ok(Box::new(self.open_read(path)?))
+    }
+
+    fn open_read(&self, path: &Path) -> Result<FileSlice, OpenReadError> {
+        let file = self.directory.open_read(path)?;
+        self.convert_file(path, file)
+    }
+
+    fn delete(&self, path: &Path) -> Result<(), DeleteError> {
+        self.directory.delete(path)
+    }
+
+    fn exists(&self, path: &Path) -> Result<bool, OpenReadError> {
+        self.directory.exists(path)
+    }
+
+    fn open_write(&self, path: &Path) -> Result<WritePtr, OpenWriteError> {
+        self.directory.open_write(path)
+    }
+
+    fn atomic_read(&self, path: &Path) -> Result<Vec<u8>, OpenReadError> {
+        self.directory.atomic_read(path)
+    }
+
+    fn atomic_write(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        self.directory.atomic_write(path, data)
+    }
+
+    fn acquire_lock(&self, lock: &Lock) -> Result<DirectoryLock, LockError> {
+        self.directory.acquire_lock(lock)
+    }
+
+    fn watch(&self, watch_callback: WatchCallback) -> crate::Result<WatchHandle> {
+        self.directory.watch(watch_callback)
+    }
+}
+
+/// Opens the source index of a migration, stored in `directory`.
+///
+/// The files written before the index format 4 are read as if they were written
+/// in the current format.
+pub(crate) fn open_src_index<D: Directory + Clone>(directory: D) -> crate::Result<Index> {
+    let schema = Index::open(directory.clone())?.schema();
+    Index::open(LegacyFormatDirectory::new(directory, schema))
+}
+
+/// Checks that all of the segments of `index` can be read by this version of tantivy.
+pub(crate) fn check_compatibility(index: &Index) -> crate::Result<()> {
+    let damaged_files = index.validate_checksum()?;
+    if !damaged_files.is_empty() {
+        return Err(DataCorruption::comment_only(format!(
+            "The index cannot be migrated, the following files are damaged: {:?}",
+            damaged_files
+        ))
+        .into());
+    }
+    for segment in index.searchable_segments()? {
+        SegmentReader::open(&segment)?;
+    }
+    Ok(())
+}
+
+/// Rewrites the last commit of `src_index` in `dst_index`, segment by segment.
+///
+/// The deleted documents are purged, and the opstamp, the payload, the metadata
+/// and the settings of the commit are kept.
+pub(crate) fn migrate(src_index: &Index, dst_index: &Index) -> crate::Result<()> {
+    let _directory_lock = dst_index
+        .directory()
+        .acquire_lock(&INDEX_WRITER_LOCK)
+        .map_err(|err| {
+            TantivyError::LockFailure(
+                err,
+                Some(
+                    "Failed to acquire index lock. An index cannot be migrated while \
+                     an `IndexWriter` is working on its destination."
+                        .to_string(),
+                ),
+            )
+        })?;
+    check_compatibility(src_index)?;
+    let src_metas = src_index.load_metas()?;
+    let mut segment_metas: Vec<SegmentMeta> = Vec::new();
+    for src_segment in src_index.searchable_segments()? {
+        if src_segment.meta().num_docs() == 0 {
+            continue;
+        }
+        let dst_segment = dst_index.new_segment();
+        let merger =
+            IndexMerger::open(src_index.schema(), std::slice::from_ref(&src_segment), None)?;
+        let segment_serializer = SegmentSerializer::for_segment(dst_segment.clone())?;
+        let num_docs = merger.write(segment_serializer, |_| Ok(()))?;
+        let mut segment_meta = dst_index
+            .new_segment_meta(dst_segment.id(), num_docs)
+            .with_num_bytes(dst_segment.compute_num_bytes())
+            .with_user_data(src_segment.meta().user_data().clone());
+        if let Some(opstamp) = src_segment.meta().opstamp() {
+            segment_meta = segment_meta.with_opstamp(opstamp);
+        }
+        segment_metas.push(segment_meta);
+    }
+    let dst_metas = IndexMeta {
+        segments: segment_metas,
+        schema: src_metas.schema,
+        opstamp: src_metas.opstamp,
+        payload: src_metas.payload,
+        metadata: src_metas.metadata,
+        index_settings: src_metas.index_settings,
+    };
+    save_metas(&dst_metas, dst_index.directory())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_compatibility, migrate, open_src_index};
+    use crate::collector::Count;
+    use crate::directory::{Directory, RAMDirectory};
+    use crate::query::TermQuery;
+    use crate::schema::{IndexRecordOption, Schema, FAST, STORED, STRING, TEXT};
+    use crate::{Index, TantivyError, Term};
+
+    #[test]
+    fn test_migrate() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let id = schema_builder.add_text_field("id", STRING | STORED);
+        let body = schema_builder.add_text_field("body", TEXT);
+        let count = schema_builder.add_u64_field("count", FAST);
+        let src_index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = src_index.writer_for_tests()?;
+            for i in 0..3u64 {
+                index_writer.add_document(doc!(
+                    id => format!("doc-{}", i),
+                    body => "hello world",
+                    count => i,
+                ));
+                index_writer.commit()?;
+            }
+            index_writer.delete_term(Term::from_field_text(id, "doc-1"));
+            let mut prepared_commit = index_writer.prepare_commit()?;
+            prepared_commit.set_payload("payload");
+            prepared_commit.commit()?;
+        }
+        check_compatibility(&src_index)?;
+
+        let dst_index = Index::create_in_ram(src_index.schema());
+        migrate(&src_index, &dst_index)?;
+        let src_metas = src_index.load_metas()?;
+        let dst_metas = dst_index.load_metas()?;
+        assert_eq!(dst_metas.opstamp, src_metas.opstamp);
+        assert_eq!(dst_metas.payload, Some("payload".to_string()));
+        assert_eq!(dst_metas.segments.len(), 2);
+        assert!(dst_metas
+            .segments
+            .iter()
+            .all(|segment_meta| !segment_meta.has_deletes()));
+
+        let searcher = dst_index.reader()?.searcher();
+        assert_eq!(searcher.num_docs(), 2);
+        let query = TermQuery::new(
+            Term::from_field_text(body, "hello"),
+            IndexRecordOption::Basic,
+        );
+        assert_eq!(searcher.search(&query, &Count)?, 2);
+        let doc_1 = TermQuery::new(Term::from_field_text(id, "doc-1"), IndexRecordOption::Basic);
+        assert_eq!(searcher.search(&doc_1, &Count)?, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_migrate_damaged_index_in_format_v3() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let body = schema_builder.add_text_field("body", TEXT);
+        let count = schema_builder.add_u64_field("count", FAST);
+        let directory = RAMDirectory::create();
+        let index = Index::create(directory.clone(), schema_builder.build())?;
+        {
+            let mut index_writer = index.writer_for_tests()?;
+            for i in 0..10u64 {
+                index_writer.add_document(doc!(body => "hello", count => i));
+            }
+            index_writer.commit()?;
+        }
+        crate::directory::rewrite_in_index_format_v3(&directory, &index)?;
+        let src_index = open_src_index(directory.clone())?;
+        check_compatibility(&src_index)?;
+        let dst_index = Index::create_in_ram(src_index.schema());
+        migrate(&src_index, &dst_index)?;
+        assert_eq!(dst_index.reader()?.searcher().num_docs(), 10);
+
+        // The converted term dictionary is damaged, like the original one.
+        let segment_meta = &index.searchable_segment_metas()?[0];
+        let path = segment_meta.relative_path(crate::SegmentComponent::TERMS);
+        let mut data = directory
+            .open_read(&path)?
+            .read_bytes()?
+            .as_slice()
+            .to_vec();
+        data[0] ^= 1;
+        directory.atomic_write(&path, &data)?;
+        let src_index = open_src_index(directory)?;
+        assert!(matches!(
+            check_compatibility(&src_index),
+            Err(TantivyError::DataCorruption(_))
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,142 @@
+use crate::core::{Index, SegmentComponent};
+use crate::directory::error::OpenReadError;
+use crate::indexer::MergePolicy;
+use crate::HasLen;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Aggregate metrics of an index, returned by
+/// [`Index::stats`](./struct.Index.html#method.stats).
+///
+/// It serializes to a JSON object, for monitoring dashboards.
+#[derive(Clone, Debug, Serialize)]
+pub struct IndexStats {
+    /// Number of segments of the last commit.
+    pub num_segments: usize,
+    /// Number of alive documents.
+    pub num_docs: u64,
+    /// Number of deleted documents that have not been purged by a merge yet.
+    pub num_deleted_docs: u64,
+    /// Size on disk of the files of the segments, in bytes, by type of file.
+    ///
+    /// The keys are `"postings"`, `"positions"`, `"term_dict"`, `"fast_fields"`,
+    /// `"field_norms"`, `"stored_fields"`, `"term_vectors"` and `"delete_bitset"`.
+    pub disk_size_bytes_by_file_type: HashMap<&'static str, u64>,
+    /// Name of the merge policy of the `IndexWriter`, like `"LogMergePolicy"`.
+    ///
+    /// `Index::stats` does not know about the `IndexWriter` and leaves it to `None`.
+    /// It is set by [`IndexWriter::stats`](./struct.IndexWriter.html#method.stats).
+    pub merge_policy_name: Option<String>,
+}
+
+fn file_type(component: SegmentComponent) -> &'static str {
+    match component {
+        SegmentComponent::POSTINGS => "postings",
+        SegmentComponent::POSITIONS | SegmentComponent::POSITIONSSKIP => "positions",
+        SegmentComponent::FASTFIELDS => "fast_fields",
+        SegmentComponent::FIELDNORMS => "field_norms",
+        SegmentComponent::TERMS => "term_dict",
+        SegmentComponent::STORE => "stored_fields",
+        SegmentComponent::DELETE => "delete_bitset",
+        SegmentComponent::TERMVECTORS => "term_vectors",
+    }
+}
+
+/// Returns the name of the type of a merge policy, out of its `Debug` representation.
+pub(crate) fn merge_policy_name(merge_policy: &dyn MergePolicy) -> String {
+    format!("{:?}", merge_policy)
+        .chars()
+        .take_while(|&c| c.is_alphanumeric() || c == '_')
+        .collect()
+}
+
+pub(crate) fn stats(index: &Index) -> crate::Result<IndexStats> {
+    let directory = index.directory();
+    let segment_metas = index.searchable_segment_metas()?;
+    let mut disk_size_bytes_by_file_type: HashMap<&'static str, u64> = SegmentComponent::iterator()
+        .map(|&component| (file_type(component), 0u64))
+        .collect();
+    let mut num_docs = 0u64;
+    let mut num_deleted_docs = 0u64;
+    for segment_meta in &segment_metas {
+        num_docs += u64::from(segment_meta.num_docs());
+        num_deleted_docs += u64::from(segment_meta.num_deleted_docs());
+        for &component in SegmentComponent::iterator() {
+            if matches!(component, SegmentComponent::DELETE) && !segment_meta.has_deletes() {
+                continue;
+            }
+            let path = segment_meta.relative_path(component);
+            let num_bytes = match directory.open_read_with_footer(&path) {
+                Ok(file) => file.len() as u64,
+                // Segments written by older versions of tantivy do not have term vectors,
+                // and the files of the segments may be garbage collected after a commit
+                // concurrent to this call.
+                Err(OpenReadError::FileDoesNotExist(_)) => 0,
+                Err(err) => return Err(err.into()),
+            };
+            *disk_size_bytes_by_file_type
+                .entry(file_type(component))
+                .or_insert(0) += num_bytes;
+        }
+    }
+    Ok(IndexStats {
+        num_segments: segment_metas.len(),
+        num_docs,
+        num_deleted_docs,
+        disk_size_bytes_by_file_type,
+        merge_policy_name: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::indexer::LogMergePolicy;
+    use crate::schema::{Schema, STORED, TEXT};
+    use crate::{Index, Term};
+
+    #[test]
+    fn test_index_stats() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT | STORED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let stats = index.stats()?;
+        assert_eq!(stats.num_segments, 0);
+        assert_eq!(stats.disk_size_bytes_by_file_type["postings"], 0);
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.set_merge_policy(Box::new(LogMergePolicy::default()));
+        index_writer.add_document(doc!(text => "hello happy tax payer"));
+        index_writer.add_document(doc!(text => "goodbye"));
+        index_writer.commit()?;
+        index_writer.add_document(doc!(text => "hello again"));
+        index_writer.delete_term(Term::from_field_text(text, "goodbye"));
+        index_writer.commit()?;
+        let stats = index_writer.stats()?;
+        assert_eq!(stats.num_segments, 2);
+        assert_eq!(stats.num_docs, 2);
+        assert_eq!(stats.num_deleted_docs, 1);
+        assert_eq!(stats.merge_policy_name.as_deref(), Some("LogMergePolicy"));
+        assert_eq!(stats.disk_size_bytes_by_file_type.len(), 8);
+        for file_type in &[
+            "postings",
+            "positions",
+            "term_dict",
+            "fast_fields",
+            "field_norms",
+            "stored_fields",
+            "delete_bitset",
+        ] {
+            assert!(stats.disk_size_bytes_by_file_type[file_type] > 0);
+        }
+        assert_eq!(index.stats()?.merge_policy_name, None);
+        let json: serde_json::Value = serde_json::to_value(&stats).unwrap();
+        assert_eq!(json["num_docs"], 2);
+        assert_eq!(json["merge_policy_name"], "LogMergePolicy");
+        assert!(
+            json["disk_size_bytes_by_file_type"]["stored_fields"]
+                .as_u64()
+                .unwrap()
+                > 0
+        );
+        Ok(())
+    }
+}
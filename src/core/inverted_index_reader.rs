@@ -93,8 +93,11 @@ impl InvertedIndexReader {
         let start_offset = term_info.postings_start_offset as usize;
         let stop_offset = term_info.postings_stop_offset as usize;
         let postings_slice = self.postings_file_slice.slice(start_offset, stop_offset);
-        block_postings.reset(term_info.doc_freq, postings_slice.read_bytes()?);
-        Ok(())
+        block_postings.reset(
+            term_info.doc_freq,
+            term_info.posting_format,
+            postings_slice.read_bytes()?,
+        )
     }
 
     /// Returns a block postings given a `Term`.
@@ -126,6 +129,7 @@ impl InvertedIndexReader {
         );
         BlockSegmentPostings::open(
             term_info.doc_freq,
+            term_info.posting_format,
             postings_data,
             self.record_option,
             requested_option,
@@ -165,6 +169,34 @@ impl InvertedIndexReader {
         self.total_num_tokens
     }
 
+    /// Returns the number of occurrences of the term over all documents
+    /// (including deleted documents).
+    ///
+    /// If the field does not record the term frequencies, each document
+    /// containing the term counts as one occurrence.
+    pub fn total_term_freq(&self, term: &Term) -> io::Result<u64> {
+        let term_info = if let Some(term_info) = self.get_term_info(term)? {
+            term_info
+        } else {
+            return Ok(0);
+        };
+        if !self.record_option.has_freq() {
+            return Ok(u64::from(term_info.doc_freq));
+        }
+        let mut block_postings =
+            self.read_block_postings_from_terminfo(&term_info, IndexRecordOption::WithFreqs)?;
+        let mut total_term_freq = 0u64;
+        while block_postings.block_len() > 0 {
+            total_term_freq += block_postings
+                .freqs()
+                .iter()
+                .map(|&freq| u64::from(freq))
+                .sum::<u64>();
+            block_postings.advance();
+        }
+        Ok(total_term_freq)
+    }
+
     /// Returns the segment postings associated with the term, and with the given option,
     /// or `None` if the term has never been encountered and indexed.
     ///
@@ -0,0 +1,103 @@
+/// Hook through which tantivy reports the activity of an `Index`.
+///
+/// Tantivy does not depend on any metrics library. Instead, an implementation
+/// of this trait can be registered on the index with
+/// [`Index::set_metrics_collector`](./struct.Index.html#method.set_metrics_collector),
+/// to feed the metrics library of your choice, e.g. the counters, gauges
+/// and histograms of a Prometheus client.
+///
+/// All of the methods default to doing nothing.
+/// They are called from the searching, indexing and merging threads,
+/// and should therefore be cheap.
+pub trait MetricsCollector: Send + Sync + 'static {
+    /// Called after each call to `Searcher::search`, with the duration of the search.
+    ///
+    /// It is not called on `wasm32-unknown-unknown`, which has no clock.
+    fn record_query(&self, _duration_ms: u64) {}
+
+    /// Called each time an indexing thread flushes a new segment,
+    /// with the number of documents of the segment.
+    fn record_indexing(&self, _num_docs: u64) {}
+
+    /// Called after each successful merge, with the duration of the merge.
+    fn record_merge(&self, _duration_ms: u64) {}
+
+    /// Called each time the list of the segments of the index is saved,
+    /// i.e. on commit and at the end of a merge, with the number of segments.
+    fn record_segment_count(&self, _segment_count: usize) {}
+}
+
+/// `MetricsCollector` ignoring all of the metrics.
+///
+/// This is the default `MetricsCollector` of an `Index`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoopMetricsCollector;
+
+impl MetricsCollector for NoopMetricsCollector {}
+
+#[cfg(test)]
+mod tests {
+    use super::MetricsCollector;
+    use crate::collector::Count;
+    use crate::query::AllQuery;
+    use crate::schema::{Schema, TEXT};
+    use crate::{Index, SegmentId};
+    use futures::executor::block_on;
+    use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[derive(Default)]
+    struct CountingMetricsCollector {
+        queries_total: AtomicU64,
+        index_docs_total: AtomicU64,
+        merge_count: AtomicU64,
+        segment_count: AtomicUsize,
+    }
+
+    impl MetricsCollector for CountingMetricsCollector {
+        fn record_query(&self, _duration_ms: u64) {
+            self.queries_total.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn record_indexing(&self, num_docs: u64) {
+            self.index_docs_total.fetch_add(num_docs, Ordering::SeqCst);
+        }
+
+        fn record_merge(&self, _duration_ms: u64) {
+            self.merge_count.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn record_segment_count(&self, segment_count: usize) {
+            self.segment_count.store(segment_count, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_metrics_collector() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let mut index = Index::create_in_ram(schema_builder.build());
+        let metrics_collector = Arc::new(CountingMetricsCollector::default());
+        index.set_metrics_collector(metrics_collector.clone());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text => "a"));
+        index_writer.add_document(doc!(text => "b"));
+        index_writer.commit()?;
+        index_writer.add_document(doc!(text => "c"));
+        index_writer.commit()?;
+        assert_eq!(metrics_collector.index_docs_total.load(Ordering::SeqCst), 3);
+        assert_eq!(metrics_collector.segment_count.load(Ordering::SeqCst), 2);
+
+        let segment_ids: Vec<SegmentId> = index.searchable_segment_ids()?;
+        block_on(index_writer.merge(&segment_ids))?;
+        index_writer.wait_merging_threads()?;
+        assert_eq!(metrics_collector.merge_count.load(Ordering::SeqCst), 1);
+        assert_eq!(metrics_collector.segment_count.load(Ordering::SeqCst), 1);
+
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.search(&AllQuery, &Count)?, 3);
+        assert_eq!(searcher.search(&AllQuery, &Count)?, 3);
+        assert_eq!(metrics_collector.queries_total.load(Ordering::SeqCst), 2);
+        Ok(())
+    }
+}
@@ -1,22 +1,49 @@
+#[cfg(feature = "async")]
+mod async_searcher;
+mod checksum_verifier;
 mod executor;
+mod federated_searcher;
 pub mod index;
+mod index_alias;
+#[cfg(feature = "mmap")]
+mod index_clone;
+pub(crate) mod index_delta;
+mod index_health;
 mod index_meta;
+mod index_migration;
+pub(crate) mod index_stats;
 mod inverted_index_reader;
+mod metrics_collector;
+mod postings_verifier;
+mod reranker;
 pub mod searcher;
 mod segment;
 mod segment_component;
 mod segment_id;
 mod segment_reader;
 
+#[cfg(feature = "async")]
+pub(crate) use self::async_searcher::spawn_blocking;
+#[cfg(feature = "async")]
+pub use self::async_searcher::AsyncSearcher;
+pub(crate) use self::checksum_verifier::ChecksumVerifier;
 pub use self::executor::Executor;
+pub use self::federated_searcher::{FederatedSearcher, MultiDocAddress, ScoreNormalization};
 pub use self::index::Index;
-pub use self::index_meta::{IndexMeta, SegmentMeta, SegmentMetaInventory};
+pub use self::index_alias::{IndexAlias, IndexAliasMeta};
+pub use self::index_health::{HealthIssue, HealthSeverity, IndexHealthReport};
+pub use self::index_meta::{IndexMeta, IndexSettings, SegmentMeta, SegmentMetaInventory};
+pub use self::index_migration::MigrateOptions;
+pub use self::index_stats::IndexStats;
 pub use self::inverted_index_reader::InvertedIndexReader;
-pub use self::searcher::Searcher;
+pub use self::metrics_collector::{MetricsCollector, NoopMetricsCollector};
+pub use self::postings_verifier::{Inconsistency, VerificationReport};
+pub use self::reranker::ParallelReranker;
+pub use self::searcher::{Searcher, SuggestEntry, TermStatistics};
 pub use self::segment::Segment;
-pub use self::segment::SerializableSegment;
 pub use self::segment_component::SegmentComponent;
 pub use self::segment_id::SegmentId;
+pub(crate) use self::segment_reader::SegmentFiles;
 pub use self::segment_reader::SegmentReader;
 
 use once_cell::sync::Lazy;
@@ -0,0 +1,268 @@
+use crate::core::{Index, SegmentReader};
+use crate::docset::{DocSet, TERMINATED};
+use crate::schema::{Field, FieldType, IndexRecordOption, Term, Value};
+use crate::tokenizer::{Token, MAX_TOKEN_LEN};
+use crate::{DocAddress, DocId, SegmentLocalId, TantivyError};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// An inconsistency between the stored fields and the posting lists of a document,
+/// found by [`Index::verify_postings`](./struct.Index.html#method.verify_postings).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Inconsistency {
+    /// A term of the stored text of the document is missing from the posting list of the term.
+    MissingPosting {
+        /// Address of the document.
+        doc_address: DocAddress,
+        /// Term obtained by tokenizing the stored text.
+        term: Term,
+    },
+    /// The posting list of a term contains the document, but the tokenized stored text
+    /// of the document does not contain the term.
+    UnexpectedPosting {
+        /// Address of the document.
+        doc_address: DocAddress,
+        /// Term of the term dictionary.
+        term: Term,
+    },
+}
+
+impl fmt::Display for Inconsistency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Inconsistency::MissingPosting { doc_address, term } => write!(
+                f,
+                "Document {:?} is missing from the posting list of {:?}.",
+                doc_address, term
+            ),
+            Inconsistency::UnexpectedPosting { doc_address, term } => write!(
+                f,
+                "Document {:?} is in the posting list of {:?}, but its stored text \
+                 does not contain the term.",
+                doc_address, term
+            ),
+        }
+    }
+}
+
+/// Report of [`Index::verify_postings`](./struct.Index.html#method.verify_postings).
+#[derive(Clone, Debug, Default)]
+pub struct VerificationReport {
+    /// Number of documents whose postings were verified.
+    pub num_docs_checked: u64,
+    /// Inconsistencies found, sorted by document.
+    pub inconsistencies: Vec<Inconsistency>,
+}
+
+impl VerificationReport {
+    /// Returns true iff no inconsistency was found.
+    pub fn is_consistent(&self) -> bool {
+        self.inconsistencies.is_empty()
+    }
+}
+
+/// Returns the alive documents of the segment picked with a rate of `sampling_rate`.
+///
+/// The documents are picked evenly, so that a verification can be reproduced.
+fn sample_docs(segment_reader: &SegmentReader, sampling_rate: f32) -> Vec<DocId> {
+    let sampling_rate = f64::from(sampling_rate);
+    segment_reader
+        .doc_ids_alive()
+        .enumerate()
+        .filter(|&(ord, _)| {
+            (ord as f64 * sampling_rate).floor() < ((ord + 1) as f64 * sampling_rate).floor()
+        })
+        .map(|(_, doc)| doc)
+        .collect()
+}
+
+/// Returns the terms of `field` in the stored fields of each of the documents.
+fn expected_terms(
+    index: &Index,
+    segment_reader: &SegmentReader,
+    field: Field,
+    docs: &[DocId],
+) -> crate::Result<BTreeMap<DocId, BTreeSet<Vec<u8>>>> {
+    let text_analyzer = index.tokenizer_for_field(field)?;
+    let store_reader = segment_reader.get_store_reader()?;
+    let mut expected_terms = BTreeMap::new();
+    for &doc in docs {
+        let mut terms = BTreeSet::new();
+        let mut add_token = |token: &Token| {
+            if token.text.len() <= MAX_TOKEN_LEN {
+                terms.insert(token.text.as_bytes().to_vec());
+            }
+        };
+        for value in store_reader.get(doc)?.get_all(field) {
+            match value {
+                Value::Str(text) => {
+                    text_analyzer.token_stream(text).process(&mut add_token);
+                }
+                Value::PreTokStr(pre_tokenized) => {
+                    pre_tokenized.tokens.iter().for_each(&mut add_token);
+                }
+                _ => {}
+            }
+        }
+        expected_terms.insert(doc, terms);
+    }
+    Ok(expected_terms)
+}
+
+fn verify_field(
+    index: &Index,
+    segment_ord: SegmentLocalId,
+    segment_reader: &SegmentReader,
+    field: Field,
+    docs: &[DocId],
+    inconsistencies: &mut Vec<(DocAddress, Inconsistency)>,
+) -> crate::Result<()> {
+    let mut expected_terms = expected_terms(index, segment_reader, field, docs)?;
+    let inverted_index = segment_reader.inverted_index(field)?;
+    let mut term_stream = inverted_index.terms().stream()?;
+    while term_stream.advance() {
+        let term_bytes = term_stream.key();
+        let mut postings = inverted_index
+            .read_postings_from_terminfo(term_stream.value(), IndexRecordOption::Basic)?;
+        for &doc in docs {
+            if postings.doc() < doc && postings.seek(doc) == TERMINATED {
+                break;
+            }
+            if postings.doc() != doc {
+                continue;
+            }
+            let is_expected = expected_terms
+                .get_mut(&doc)
+                .map(|terms| terms.remove(term_bytes))
+                .unwrap_or(false);
+            if !is_expected {
+                let doc_address = DocAddress(segment_ord, doc);
+                inconsistencies.push((
+                    doc_address,
+                    Inconsistency::UnexpectedPosting {
+                        doc_address,
+                        term: Term::from_field_bytes(field, term_bytes),
+                    },
+                ));
+            }
+        }
+    }
+    for (doc, terms) in expected_terms {
+        let doc_address = DocAddress(segment_ord, doc);
+        for term_bytes in terms {
+            inconsistencies.push((
+                doc_address,
+                Inconsistency::MissingPosting {
+                    doc_address,
+                    term: Term::from_field_bytes(field, &term_bytes),
+                },
+            ));
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn verify_postings(
+    index: &Index,
+    sampling_rate: f32,
+) -> crate::Result<VerificationReport> {
+    if !(0.0..=1.0).contains(&sampling_rate) {
+        return Err(TantivyError::InvalidArgument(format!(
+            "The sampling rate must be between 0 and 1, got {}.",
+            sampling_rate
+        )));
+    }
+    let schema = index.schema();
+    let fields: Vec<Field> = schema
+        .fields()
+        .filter(|(_, field_entry)| match field_entry.field_type() {
+            FieldType::Str(text_options) => {
+                text_options.is_stored() && text_options.get_indexing_options().is_some()
+            }
+            _ => false,
+        })
+        .map(|(field, _)| field)
+        .collect();
+    let mut report = VerificationReport::default();
+    let mut inconsistencies = Vec::new();
+    for (segment_ord, segment) in index.searchable_segments()?.iter().enumerate() {
+        let segment_reader = SegmentReader::open(segment)?;
+        let docs = sample_docs(&segment_reader, sampling_rate);
+        report.num_docs_checked += docs.len() as u64;
+        for &field in &fields {
+            verify_field(
+                index,
+                segment_ord as SegmentLocalId,
+                &segment_reader,
+                field,
+                &docs,
+                &mut inconsistencies,
+            )?;
+        }
+    }
+    // The sort is stable: the inconsistencies of a document stay grouped by field.
+    inconsistencies.sort_by_key(|(doc_address, _)| *doc_address);
+    report.inconsistencies = inconsistencies
+        .into_iter()
+        .map(|(_, inconsistency)| inconsistency)
+        .collect();
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Inconsistency;
+    use crate::schema::{Schema, STORED, STRING, TEXT};
+    use crate::tokenizer::SimpleTokenizer;
+    use crate::{DocAddress, Index, TantivyError, Term};
+
+    #[test]
+    fn test_verify_postings() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT | STORED);
+        let body = schema_builder.add_text_field("body", TEXT);
+        let id = schema_builder.add_text_field("id", STRING | STORED);
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_for_tests()?;
+            for i in 0..10 {
+                index_writer.add_document(doc!(
+                    title => format!("The Title {}", i),
+                    body => "not stored",
+                    id => format!("ID-{}", i),
+                ));
+            }
+            index_writer.commit()?;
+            index_writer.delete_term(Term::from_field_text(id, "ID-3"));
+            index_writer.commit()?;
+        }
+        let report = index.verify_postings(1.0)?;
+        assert!(report.is_consistent());
+        assert_eq!(report.num_docs_checked, 9);
+        assert_eq!(index.verify_postings(0.5)?.num_docs_checked, 4);
+        assert_eq!(index.verify_postings(0.0)?.num_docs_checked, 0);
+        assert!(matches!(
+            index.verify_postings(1.5),
+            Err(TantivyError::InvalidArgument(_))
+        ));
+
+        // The tokenizer of the title is changed after the documents were indexed.
+        index.tokenizers().register("default", SimpleTokenizer);
+        let report = index.verify_postings(1.0)?;
+        assert_eq!(report.inconsistencies.len(), 9 * 2 * 2);
+        assert_eq!(
+            report.inconsistencies[0],
+            Inconsistency::UnexpectedPosting {
+                doc_address: DocAddress(0, 0),
+                term: Term::from_field_text(title, "the"),
+            }
+        );
+        assert!(report
+            .inconsistencies
+            .contains(&Inconsistency::MissingPosting {
+                doc_address: DocAddress(0, 9),
+                term: Term::from_field_text(title, "Title"),
+            }));
+        Ok(())
+    }
+}
@@ -0,0 +1,235 @@
+use crate::schema::{Document, Field};
+use crate::{DocAddress, Score, Searcher};
+use rayon::prelude::*;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+/// Loads the document to rerank, restricted to `fields` if any.
+fn load_doc(
+    searcher: &Searcher,
+    doc_address: DocAddress,
+    fields: Option<&[Field]>,
+) -> crate::Result<Document> {
+    match fields {
+        Some(fields) => searcher.doc_projected(doc_address, fields),
+        None => searcher.doc(doc_address),
+    }
+}
+
+/// Removes the documents appearing more than once in `initial`,
+/// keeping their first occurrence.
+fn dedup(initial: Vec<(Score, DocAddress)>) -> Vec<(Score, DocAddress)> {
+    let mut seen = HashSet::with_capacity(initial.len());
+    initial
+        .into_iter()
+        .filter(|&(_, doc_address)| seen.insert(doc_address))
+        .collect()
+}
+
+/// Sorts the results by decreasing score, breaking ties by `DocAddress`.
+fn sort_results(results: &mut [(Score, DocAddress)]) {
+    results.sort_by(|(left_score, left_address), (right_score, right_address)| {
+        right_score
+            .partial_cmp(left_score)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| left_address.cmp(right_address))
+    });
+}
+
+pub(crate) fn rerank<F>(
+    searcher: &Searcher,
+    initial: Vec<(Score, DocAddress)>,
+    fields: Option<&[Field]>,
+    reranker: F,
+) -> crate::Result<Vec<(Score, DocAddress)>>
+where
+    F: Fn(&Document, Score) -> crate::Result<Score>,
+{
+    let mut results = dedup(initial)
+        .into_iter()
+        .map(|(score, doc_address)| {
+            let doc = load_doc(searcher, doc_address, fields)?;
+            Ok((reranker(&doc, score)?, doc_address))
+        })
+        .collect::<crate::Result<Vec<_>>>()?;
+    sort_results(&mut results);
+    Ok(results)
+}
+
+/// Reranks the results of a search in parallel, in the thread pool of `rayon`.
+///
+/// This is the parallel version of
+/// [`Searcher::rerank`](./struct.Searcher.html#method.rerank), for rerankers
+/// that are expensive to run, e.g. a call to a neural model.
+/// The reranker is called once for each document of the initial results,
+/// and only these documents are returned, sorted by decreasing new score.
+///
+/// # Example
+///
+/// ```rust
+/// use tantivy::collector::TopDocs;
+/// use tantivy::query::QueryParser;
+/// use tantivy::schema::{Schema, STORED, TEXT};
+/// use tantivy::{doc, Index, ParallelReranker};
+/// # fn test() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let title = schema_builder.add_text_field("title", TEXT | STORED);
+/// let index = Index::create_in_ram(schema_builder.build());
+/// let mut index_writer = index.writer_with_num_threads(1, 10_000_000)?;
+/// index_writer.add_document(doc!(title => "the old man and the sea"));
+/// index_writer.add_document(doc!(title => "the sea"));
+/// index_writer.commit()?;
+///
+/// let searcher = index.reader()?.searcher();
+/// let query = QueryParser::for_index(&index, vec![title]).parse_query("sea")?;
+/// let initial = searcher.search(&query, &TopDocs::with_limit(10))?;
+/// // Favors the longest titles.
+/// let reranker = ParallelReranker::new(|doc: &tantivy::Document, _score: f32| {
+///     let text = doc.get_first(title).and_then(|value| value.text()).unwrap_or("");
+///     Ok(text.len() as f32)
+/// })
+/// .with_fields(vec![title]);
+/// let reranked = reranker.rerank(&searcher, initial)?;
+/// assert_eq!(reranked[0].0, 23.0);
+/// # Ok(())
+/// # }
+/// # assert!(test().is_ok());
+/// ```
+pub struct ParallelReranker<F> {
+    reranker: F,
+    fields: Option<Vec<Field>>,
+}
+
+impl<F> ParallelReranker<F>
+where
+    F: Fn(&Document, Score) -> crate::Result<Score> + Sync,
+{
+    /// Creates a `ParallelReranker`.
+    ///
+    /// The reranker is given the stored document and its initial score,
+    /// and returns its new score.
+    pub fn new(reranker: F) -> ParallelReranker<F> {
+        ParallelReranker {
+            reranker,
+            fields: None,
+        }
+    }
+
+    /// Restricts the documents given to the reranker to the stored values of `fields`.
+    ///
+    /// By default, all of the stored fields are loaded.
+    pub fn with_fields(mut self, fields: Vec<Field>) -> ParallelReranker<F> {
+        self.fields = Some(fields);
+        self
+    }
+
+    /// Reranks the `initial` results of a search run on `searcher`.
+    ///
+    /// If the reranker returns an error for any of the documents,
+    /// this error is returned.
+    pub fn rerank(
+        &self,
+        searcher: &Searcher,
+        initial: Vec<(Score, DocAddress)>,
+    ) -> crate::Result<Vec<(Score, DocAddress)>> {
+        let fields = self.fields.as_deref();
+        let mut results = dedup(initial)
+            .into_par_iter()
+            .map(|(score, doc_address)| {
+                let doc = load_doc(searcher, doc_address, fields)?;
+                Ok(((self.reranker)(&doc, score)?, doc_address))
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+        sort_results(&mut results);
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ParallelReranker;
+    use crate::collector::TopDocs;
+    use crate::query::TermQuery;
+    use crate::schema::{Document, Field, IndexRecordOption, Schema, STORED, STRING, TEXT};
+    use crate::{DocAddress, Index, Score, Searcher, TantivyError, Term};
+
+    fn build_searcher() -> crate::Result<(Searcher, Field, Field)> {
+        let mut schema_builder = Schema::builder();
+        let tag = schema_builder.add_text_field("tag", STRING);
+        let body = schema_builder.add_text_field("body", TEXT | STORED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        for &text in &["a", "bbb", "cc", "dddd"] {
+            index_writer.add_document(doc!(tag => "x", body => text));
+        }
+        index_writer.commit()?;
+        let searcher: Searcher = index.reader()?.searcher().clone();
+        Ok((searcher, tag, body))
+    }
+
+    fn body_len(body: Field) -> impl Fn(&Document, Score) -> crate::Result<Score> + Sync {
+        move |doc: &Document, _score: Score| {
+            let text = doc.get_first(body).and_then(|value| value.text());
+            Ok(text.map(str::len).unwrap_or(0) as Score)
+        }
+    }
+
+    #[test]
+    fn test_rerank() -> crate::Result<()> {
+        let (searcher, tag, body) = build_searcher()?;
+        let query = TermQuery::new(Term::from_field_text(tag, "x"), IndexRecordOption::Basic);
+        let initial = searcher.search(&query, &TopDocs::with_limit(3))?;
+        assert_eq!(initial.len(), 3);
+        let reranked = searcher.rerank(initial.clone(), body_len(body))?;
+        assert_eq!(reranked.len(), 3);
+        assert!(reranked.windows(2).all(|pair| pair[0].0 >= pair[1].0));
+        for (_, doc_address) in &reranked {
+            assert!(initial.iter().any(|(_, address)| address == doc_address));
+        }
+        let parallel_reranked = ParallelReranker::new(body_len(body)).rerank(&searcher, initial)?;
+        assert_eq!(parallel_reranked, reranked);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rerank_projected() -> crate::Result<()> {
+        let (searcher, _, body) = build_searcher()?;
+        let initial = vec![
+            (1.0, DocAddress(0, 0)),
+            (1.0, DocAddress(0, 3)),
+            (0.5, DocAddress(0, 0)),
+        ];
+        let boost_by_len = |doc: &Document, score: Score| {
+            assert_eq!(doc.field_values().len(), 1);
+            Ok(score * body_len(body)(doc, score)?)
+        };
+        let expected = vec![(4.0, DocAddress(0, 3)), (1.0, DocAddress(0, 0))];
+        assert_eq!(
+            searcher.rerank_projected(initial.clone(), &[body], boost_by_len)?,
+            expected
+        );
+        let parallel_reranker = ParallelReranker::new(boost_by_len).with_fields(vec![body]);
+        assert_eq!(parallel_reranker.rerank(&searcher, initial)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rerank_error() -> crate::Result<()> {
+        let (searcher, _, _) = build_searcher()?;
+        let initial = vec![(1.0, DocAddress(0, 0)), (1.0, DocAddress(0, 1))];
+        let failing_reranker = |_: &Document, _: Score| -> crate::Result<Score> {
+            Err(TantivyError::InvalidArgument(
+                "model unavailable".to_string(),
+            ))
+        };
+        assert!(matches!(
+            searcher.rerank(initial.clone(), failing_reranker),
+            Err(TantivyError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            ParallelReranker::new(failing_reranker).rerank(&searcher, initial),
+            Err(TantivyError::InvalidArgument(_))
+        ));
+        Ok(())
+    }
+}
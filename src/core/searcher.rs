@@ -1,28 +1,147 @@
 use crate::collector::Collector;
+use crate::collector::MergeableCollector;
+use crate::collector::{Order, SortCollector, SortKey};
+use crate::core::reranker;
+use crate::core::ChecksumVerifier;
 use crate::core::Executor;
 
-use crate::core::SegmentReader;
-use crate::query::Query;
+use crate::core::{Segment, SegmentFiles, SegmentReader};
+use crate::directory::FileSlice;
+use crate::postings::Postings;
+use crate::query::{
+    build_dfa, more_like_this_query, BM25Params, DFAWrapper, DocumentBoostScoreFunction,
+    Explanation, MoreLikeThisParams, Query, Weight,
+};
+use crate::reader::SearcherGeneration;
 use crate::schema::Document;
+use crate::schema::Field;
+use crate::schema::IndexRecordOption;
 use crate::schema::Schema;
-use crate::schema::Term;
+use crate::schema::{FieldType, Term};
 use crate::space_usage::SearcherSpaceUsage;
 use crate::store::StoreReader;
+use crate::termdict::TermMerger;
+use crate::termvector::TermVectors;
 use crate::DocAddress;
+use crate::DocId;
+use crate::DocSet;
 use crate::Index;
+use crate::QueryCache;
+use crate::Score;
+use crate::SegmentLocalId;
+use crate::SegmentOpening;
+use crate::TantivyError;
+use crate::VerifyMode;
+use crate::TERMINATED;
 
+use levenshtein_automata::Distance;
+use once_cell::sync::OnceCell;
+use std::cmp::{self, Reverse};
+use std::collections::{BTreeSet, BinaryHeap, HashMap};
+use std::sync::Arc;
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use std::time::Instant;
 use std::{fmt, io};
 
+/// A term completing a prefix, returned by
+/// [`Searcher::suggest`](./struct.Searcher.html#method.suggest).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SuggestEntry {
+    /// Text of the term.
+    pub term: String,
+    /// Number of documents containing the term, including the deleted documents.
+    pub doc_freq: u64,
+}
+
+/// Statistics of a term over all of the segments of a `Searcher`, as returned by
+/// [`Searcher::term_statistics`](./struct.Searcher.html#method.term_statistics).
+///
+/// Just like the statistics used by BM25, they include the deleted documents.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TermStatistics {
+    /// Number of documents containing the term.
+    pub doc_freq: u64,
+    /// Number of occurrences of the term, summed over all documents.
+    pub total_term_freq: u64,
+}
+
+/// The segments of the `Searcher`s of a given generation.
+///
+/// Their files are opened right away, so that they remain readable even if
+/// the segments get garbage collected. With `SegmentOpening::Lazy`, the
+/// `SegmentReader`s are only opened once they are first needed.
+pub(crate) struct SearcherSegments {
+    segment_files: Vec<SegmentFiles>,
+    segment_readers: OnceCell<Vec<SegmentReader>>,
+}
+
+impl SearcherSegments {
+    pub(crate) fn open(
+        segments: &[Segment],
+        segment_opening: SegmentOpening,
+        verify_mode: VerifyMode,
+        checksum_verifier: &Arc<ChecksumVerifier>,
+    ) -> crate::Result<SearcherSegments> {
+        let segment_files = segments
+            .iter()
+            .map(|segment| match verify_mode {
+                VerifyMode::Disabled => SegmentFiles::open(segment),
+                VerifyMode::OnOpen | VerifyMode::OnAccess => {
+                    SegmentFiles::open_with_checksums(segment, checksum_verifier.clone())
+                }
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+        if verify_mode == VerifyMode::OnOpen {
+            for segment_files in &segment_files {
+                segment_files.verify_checksums()?;
+            }
+        }
+        let searcher_segments = SearcherSegments {
+            segment_files,
+            segment_readers: OnceCell::new(),
+        };
+        if segment_opening == SegmentOpening::Eager {
+            searcher_segments.segment_readers(&Executor::single_thread())?;
+        }
+        Ok(searcher_segments)
+    }
+
+    fn segment_readers(&self, executor: &Executor) -> crate::Result<&[SegmentReader]> {
+        self.segment_readers
+            .get_or_try_init(|| {
+                executor.map(
+                    |segment_files| {
+                        segment_files.verify_checksums()?;
+                        SegmentReader::from_files(segment_files)
+                    },
+                    self.segment_files.iter(),
+                )
+            })
+            .map(Vec::as_slice)
+    }
+}
+
 /// Holds a list of `SegmentReader`s ready for search.
 ///
 /// It guarantees that the `Segment` will not be removed before
 /// the destruction of the `Searcher`.
 ///
+/// Cloning a `Searcher` is cheap, and does not perform any I/O: the clone shares
+/// the `SegmentReader`s and the document store caches. Each concurrent task can
+/// therefore hold its own clone, searching the same generation of the index.
+///
+/// With `SegmentOpening::Lazy`, the `SegmentReader`s are opened by the first
+/// search. (See [`IndexReaderBuilder::segment_opening`](./struct.IndexReaderBuilder.html#method.segment_opening).)
+#[derive(Clone)]
 pub struct Searcher {
     schema: Schema,
     index: Index,
-    segment_readers: Vec<SegmentReader>,
-    store_readers: Vec<StoreReader>,
+    segments: Arc<SearcherSegments>,
+    store_readers: Arc<OnceCell<Vec<StoreReader>>>,
+    bm25_params: BM25Params,
+    query_cache: Option<QueryCache>,
+    generation: SearcherGeneration,
+    commit_payload: Option<Arc<str>>,
 }
 
 impl Searcher {
@@ -30,20 +149,121 @@ impl Searcher {
     pub(crate) fn new(
         schema: Schema,
         index: Index,
-        segment_readers: Vec<SegmentReader>,
+        segments: Arc<SearcherSegments>,
+        bm25_params: BM25Params,
     ) -> io::Result<Searcher> {
-        let store_readers: Vec<StoreReader> = segment_readers
-            .iter()
-            .map(SegmentReader::get_store_reader)
-            .collect::<io::Result<Vec<_>>>()?;
+        let store_readers = OnceCell::new();
+        if let Some(segment_readers) = segments.segment_readers.get() {
+            let _ = store_readers.set(
+                segment_readers
+                    .iter()
+                    .map(SegmentReader::get_store_reader)
+                    .collect::<io::Result<Vec<_>>>()?,
+            );
+        }
         Ok(Searcher {
             schema,
             index,
-            segment_readers,
-            store_readers,
+            segments,
+            store_readers: Arc::new(store_readers),
+            bm25_params,
+            query_cache: None,
+            generation: SearcherGeneration::default(),
+            commit_payload: None,
         })
     }
 
+    /// Attaches the `QueryCache` of the `IndexReader`, for the searchers
+    /// of the given generation.
+    pub(crate) fn with_query_cache(
+        mut self,
+        query_cache: Option<QueryCache>,
+        generation: SearcherGeneration,
+    ) -> Searcher {
+        self.query_cache = query_cache;
+        self.generation = generation;
+        self
+    }
+
+    /// Attaches the payload of the commit the searcher's segments were loaded from.
+    pub(crate) fn with_commit_payload(mut self, commit_payload: Option<Arc<str>>) -> Searcher {
+        self.commit_payload = commit_payload;
+        self
+    }
+
+    /// Returns the payload of the commit this searcher was opened on, if any.
+    ///
+    /// The payload is set with
+    /// [`IndexWriter::commit_with_payload`](./struct.IndexWriter.html#method.commit_with_payload)
+    /// or on the `PreparedCommit` returned by
+    /// [`IndexWriter::prepare_commit`](./struct.IndexWriter.html#method.prepare_commit),
+    /// and is kept by the merges happening after the commit.
+    pub fn commit_payload(&self) -> Option<&str> {
+        self.commit_payload.as_deref()
+    }
+
+    /// Opens the `SegmentReader`s of the searcher in parallel, and its document store readers,
+    /// if they are not opened yet.
+    pub(crate) fn warm(&self) -> crate::Result<()> {
+        if self.segments.segment_readers.get().is_none() {
+            let num_threads = num_cpus::get().min(self.segments.segment_files.len());
+            let executor = Executor::multi_thread(num_threads.max(1), "warm-segment-")?;
+            self.segments.segment_readers(&executor)?;
+        }
+        self.store_readers()?;
+        Ok(())
+    }
+
+    /// Returns the `SegmentReader`s of the searcher, opening them if they are not opened yet.
+    pub(crate) fn open_segment_readers(&self) -> crate::Result<&[SegmentReader]> {
+        self.segments.segment_readers(self.index.search_executor())
+    }
+
+    /// Returns the files of the segments of the searcher, or, if `fields` is
+    /// given, the parts of these files that are read to search `fields`.
+    pub(crate) fn warm_up_file_slices(
+        &self,
+        fields: Option<&[Field]>,
+    ) -> crate::Result<Vec<FileSlice>> {
+        let file_slices = match fields {
+            Some(fields) => self
+                .open_segment_readers()?
+                .iter()
+                .flat_map(|segment_reader| {
+                    fields
+                        .iter()
+                        .flat_map(move |&field| segment_reader.field_file_slices(field))
+                })
+                .collect(),
+            None => self
+                .segments
+                .segment_files
+                .iter()
+                .flat_map(SegmentFiles::file_slices)
+                .collect(),
+        };
+        Ok(file_slices)
+    }
+
+    fn store_readers(&self) -> crate::Result<&[StoreReader]> {
+        self.store_readers
+            .get_or_try_init(|| {
+                self.open_segment_readers()?
+                    .iter()
+                    .map(SegmentReader::get_store_reader)
+                    .collect::<io::Result<Vec<_>>>()
+                    .map_err(TantivyError::from)
+            })
+            .map(Vec::as_slice)
+    }
+
+    /// Returns the `QueryCache` used by the searcher, if any.
+    ///
+    /// See [`IndexReaderBuilder::query_cache`](./struct.IndexReaderBuilder.html#method.query_cache).
+    pub fn query_cache(&self) -> Option<&QueryCache> {
+        self.query_cache.as_ref()
+    }
+
     /// Returns the `Index` associated to the `Searcher`
     pub fn index(&self) -> &Index {
         &self.index
@@ -55,10 +275,45 @@ impl Searcher {
     /// the request to the right `Segment`.
     pub fn doc(&self, doc_address: DocAddress) -> crate::Result<Document> {
         let DocAddress(segment_local_id, doc_id) = doc_address;
-        let store_reader = &self.store_readers[segment_local_id as usize];
+        let store_reader = &self.store_readers()?[segment_local_id as usize];
         store_reader.get(doc_id)
     }
 
+    /// Fetches the values of the given `fields` of a document from tantivy's store.
+    ///
+    /// The values of the other fields are skipped without being decoded, which is
+    /// cheaper than [`doc(...)`](#method.doc) for documents with large stored fields.
+    pub fn doc_projected(
+        &self,
+        doc_address: DocAddress,
+        fields: &[Field],
+    ) -> crate::Result<Document> {
+        let DocAddress(segment_local_id, doc_id) = doc_address;
+        let store_reader = &self.store_readers()?[segment_local_id as usize];
+        store_reader.get_projected(doc_id, fields)
+    }
+
+    /// Fetches the term vectors of the `field` of a document given its `DocAddress`.
+    ///
+    /// The field must store term vectors. (See
+    /// [`TextFieldIndexing::set_term_vectors`](../schema/struct.TextFieldIndexing.html#method.set_term_vectors).)
+    pub fn term_vectors(
+        &self,
+        field: Field,
+        doc_address: DocAddress,
+    ) -> crate::Result<TermVectors> {
+        let DocAddress(segment_local_id, doc_id) = doc_address;
+        let segment_reader = &self.open_segment_readers()?[segment_local_id as usize];
+        segment_reader.term_vectors(field, doc_id)
+    }
+
+    /// Returns the parameters of the BM25 similarity used by this searcher.
+    ///
+    /// See [`IndexReaderBuilder::bm25_params`](../struct.IndexReaderBuilder.html#method.bm25_params).
+    pub fn bm25_params(&self) -> BM25Params {
+        self.bm25_params
+    }
+
     /// Access the schema associated to the index of this searcher.
     pub fn schema(&self) -> &Schema {
         &self.schema
@@ -66,9 +321,10 @@ impl Searcher {
 
     /// Returns the overall number of documents in the index.
     pub fn num_docs(&self) -> u64 {
-        self.segment_readers
+        self.segments
+            .segment_files
             .iter()
-            .map(|segment_reader| u64::from(segment_reader.num_docs()))
+            .map(|segment_files| u64::from(segment_files.num_docs()))
             .sum::<u64>()
     }
 
@@ -76,7 +332,7 @@ impl Searcher {
     /// the given term.
     pub fn doc_freq(&self, term: &Term) -> crate::Result<u64> {
         let mut total_doc_freq = 0;
-        for segment_reader in &self.segment_readers {
+        for segment_reader in self.open_segment_readers()? {
             let inverted_index = segment_reader.inverted_index(term.field())?;
             let doc_freq = inverted_index.doc_freq(term)?;
             total_doc_freq += u64::from(doc_freq);
@@ -84,14 +340,335 @@ impl Searcher {
         Ok(total_doc_freq)
     }
 
+    /// Returns the document frequency and the total term frequency of the given term,
+    /// summed over all segments.
+    ///
+    /// Computing the total term frequency requires to read the whole posting list
+    /// of the term in each segment.
+    pub fn term_statistics(&self, term: &Term) -> crate::Result<TermStatistics> {
+        let mut term_statistics = TermStatistics {
+            doc_freq: 0,
+            total_term_freq: 0,
+        };
+        for segment_reader in self.open_segment_readers()? {
+            let inverted_index = segment_reader.inverted_index(term.field())?;
+            term_statistics.doc_freq += u64::from(inverted_index.doc_freq(term)?);
+            term_statistics.total_term_freq += inverted_index.total_term_freq(term)?;
+        }
+        Ok(term_statistics)
+    }
+
+    /// Returns the `limit` most frequent terms of the text field `field`
+    /// starting with `prefix`, by decreasing document frequency and then by term.
+    ///
+    /// The term dictionary of each segment is only traversed from the node of
+    /// the prefix, and no posting list is read: the document frequencies
+    /// include the deleted documents.
+    pub fn suggest(
+        &self,
+        field: Field,
+        prefix: &str,
+        limit: usize,
+    ) -> crate::Result<Vec<SuggestEntry>> {
+        self.check_indexed_text_field(field)?;
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+        let inverted_indexes = self
+            .open_segment_readers()?
+            .iter()
+            .map(|segment_reader| segment_reader.inverted_index(field))
+            .collect::<crate::Result<Vec<_>>>()?;
+        let streams = inverted_indexes
+            .iter()
+            .map(|inverted_index| inverted_index.terms().range().ge(prefix).into_stream())
+            .collect::<io::Result<Vec<_>>>()?;
+        let mut term_merger = TermMerger::new(streams);
+        // The top of the heap is the worst entry kept so far:
+        // the one with the lowest document frequency and, among those, the highest term.
+        let mut heap: BinaryHeap<(Reverse<u64>, Vec<u8>)> = BinaryHeap::with_capacity(limit);
+        while term_merger.advance() {
+            if !term_merger.key().starts_with(prefix.as_bytes()) {
+                break;
+            }
+            let doc_freq: u64 = term_merger
+                .current_kvs()
+                .iter()
+                .map(|heap_item| u64::from(heap_item.streamer.value().doc_freq))
+                .sum();
+            let entry = (Reverse(doc_freq), term_merger.key().to_vec());
+            if heap.len() < limit {
+                heap.push(entry);
+            } else if let Some(mut worst_entry) = heap.peek_mut() {
+                if entry < *worst_entry {
+                    *worst_entry = entry;
+                }
+            }
+        }
+        Ok(heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|(doc_freq, term)| SuggestEntry {
+                term: String::from_utf8_lossy(&term).into_owned(),
+                doc_freq: doc_freq.0,
+            })
+            .collect())
+    }
+
+    /// Returns up to `limit` terms of the text field `field` within `max_edits`
+    /// edits of `term`, as spelling corrections for `term`.
+    ///
+    /// The term dictionary of each segment is intersected with a Levenshtein
+    /// automaton of `term`, where a transposition counts as one edit. The terms
+    /// are sorted by increasing edit distance, then by decreasing document
+    /// frequency, and then by term. `term` itself is returned first if it is
+    /// in the index.
+    ///
+    /// Returns an error if `max_edits` is above 2.
+    pub fn suggest_corrections(
+        &self,
+        field: Field,
+        term: &str,
+        max_edits: u8,
+        limit: usize,
+    ) -> crate::Result<Vec<String>> {
+        self.check_indexed_text_field(field)?;
+        let dfa = DFAWrapper(build_dfa(term, max_edits, true, false)?);
+        let mut doc_freqs: HashMap<Vec<u8>, u64> = HashMap::new();
+        for segment_reader in self.open_segment_readers()? {
+            let inverted_index = segment_reader.inverted_index(field)?;
+            let mut stream = inverted_index.terms().search(&dfa).into_stream()?;
+            while stream.advance() {
+                let doc_freq = u64::from(stream.value().doc_freq);
+                *doc_freqs.entry(stream.key().to_vec()).or_insert(0) += doc_freq;
+            }
+        }
+        let mut corrections: Vec<(u8, Reverse<u64>, Vec<u8>)> = doc_freqs
+            .into_iter()
+            .map(|(term, doc_freq)| {
+                let distance = match dfa.0.eval(&term) {
+                    Distance::Exact(distance) => distance,
+                    Distance::AtLeast(distance) => distance,
+                };
+                (distance, Reverse(doc_freq), term)
+            })
+            .collect();
+        corrections.sort();
+        Ok(corrections
+            .into_iter()
+            .take(limit)
+            .map(|(_, _, term)| String::from_utf8_lossy(&term).into_owned())
+            .collect())
+    }
+
+    fn check_indexed_text_field(&self, field: Field) -> crate::Result<()> {
+        let field_entry = self.schema.get_field_entry(field);
+        match field_entry.field_type() {
+            FieldType::Str(_) if field_entry.is_indexed() => Ok(()),
+            _ => Err(TantivyError::SchemaError(format!(
+                "Field {:?} is not an indexed text field.",
+                field_entry.name()
+            ))),
+        }
+    }
+
+    /// Returns an `Explanation` of the score of the document `doc_address`
+    /// for the given query.
+    ///
+    /// Returns an error if the document does not match the query.
+    pub fn explain(
+        &self,
+        query: &dyn Query,
+        doc_address: DocAddress,
+    ) -> crate::Result<Explanation> {
+        self.open_segment_readers()?;
+        if self.schema.document_boost_field().is_none() {
+            return query.explain(self, doc_address);
+        }
+        let reader = self.segment_reader(doc_address.segment_ord());
+        let weight = self.weight(query, true)?;
+        weight.explain(reader, doc_address.doc())
+    }
+
+    /// Returns the number of documents matching `query`.
+    ///
+    /// The result is the same as searching with the `Count` collector, but some
+    /// queries are counted without iterating through their matching documents:
+    /// - `AllQuery` returns the number of alive documents of the segments.
+    /// - `TermQuery` returns the document frequency of its term, as read in the term
+    ///   dictionary, on the segments without deleted documents.
+    /// - `RangeQuery` counts the documents of the bitset of documents it matches.
+    pub fn count(&self, query: &dyn Query) -> crate::Result<u64> {
+        let weight = self.weight(query, false)?;
+        let mut count = 0u64;
+        for segment_reader in self.segment_readers() {
+            count += u64::from(weight.count(segment_reader)?);
+        }
+        Ok(count)
+    }
+
+    /// Returns an estimate of the number of documents matching `query`.
+    ///
+    /// On the segments larger than `0.25 / tolerance²` documents, the matching
+    /// documents are only counted within this many documents, in evenly spaced
+    /// windows of the segment, and the count is extrapolated to the whole segment.
+    /// The standard error of the estimate of a segment is then at most
+    /// `tolerance * max_doc`. The smaller segments are counted exactly.
+    ///
+    /// This is meant for queries that are expensive to count, like phrase queries or
+    /// conjunctions: the ones benefiting from a shortcut in [`count`](#method.count)
+    /// are cheaper to count exactly.
+    ///
+    /// A `tolerance` of `0` returns the exact count. Returns an `InvalidArgument`
+    /// error if `tolerance` is not within `[0, 1)`.
+    pub fn count_approximate(&self, query: &dyn Query, tolerance: f64) -> crate::Result<u64> {
+        if !(0.0..1.0).contains(&tolerance) {
+            return Err(TantivyError::InvalidArgument(format!(
+                "The tolerance must be within [0, 1), got {}.",
+                tolerance
+            )));
+        }
+        if tolerance == 0.0 {
+            return self.count(query);
+        }
+        let sample_size = ((0.25 / (tolerance * tolerance)).ceil() as u32).max(NUM_SAMPLE_WINDOWS);
+        let weight = self.weight(query, false)?;
+        let mut count = 0f64;
+        for segment_reader in self.segment_readers() {
+            if segment_reader.max_doc() <= sample_size {
+                count += f64::from(weight.count(segment_reader)?);
+            } else {
+                count += estimate_count(weight.as_ref(), segment_reader, sample_size)?;
+            }
+        }
+        Ok(count.round() as u64)
+    }
+
+    /// Returns the weight of `query`.
+    ///
+    /// If the schema has a document boost field, the scores of the
+    /// weight get multiplied by the document boosts.
+    ///
+    /// If the searcher has a `QueryCache`, the weight is taken from the cache.
+    fn weight(&self, query: &dyn Query, scoring_enabled: bool) -> crate::Result<Box<dyn Weight>> {
+        match &self.query_cache {
+            Some(query_cache) => {
+                query_cache.get_or_build(self.generation, query, scoring_enabled, || {
+                    self.build_weight(query, scoring_enabled)
+                })
+            }
+            None => self.build_weight(query, scoring_enabled),
+        }
+    }
+
+    fn build_weight(
+        &self,
+        query: &dyn Query,
+        scoring_enabled: bool,
+    ) -> crate::Result<Box<dyn Weight>> {
+        let weight = query.weight(self, scoring_enabled)?;
+        match self.schema.document_boost_field() {
+            Some(document_boost_field) if scoring_enabled => {
+                Ok(DocumentBoostScoreFunction::new(document_boost_field).boost_weight(weight))
+            }
+            _ => Ok(weight),
+        }
+    }
+
+    /// Returns the positions, within `field`, of the terms of `query`
+    /// in the document `doc_address`.
+    ///
+    /// The positions are sorted and deduplicated. They are empty if the document
+    /// does not contain any of the terms, or if positions are not indexed for `field`.
+    pub fn positions(
+        &self,
+        query: &dyn Query,
+        field: Field,
+        doc_address: DocAddress,
+    ) -> crate::Result<Vec<u32>> {
+        let mut positions = self.positions_batch(query, field, &[doc_address])?;
+        Ok(positions.pop().unwrap_or_default())
+    }
+
+    /// Same as [`positions(...)`](#method.positions), for several documents at once.
+    ///
+    /// The returned positions are in the order of `doc_addresses`. The postings of
+    /// each term are read once per segment, and scanned for all of the requested
+    /// documents of the segment, which is cheaper than calling `positions` for each
+    /// document, e.g. to highlight a page of results.
+    pub fn positions_batch(
+        &self,
+        query: &dyn Query,
+        field: Field,
+        doc_addresses: &[DocAddress],
+    ) -> crate::Result<Vec<Vec<u32>>> {
+        let mut terms = BTreeSet::new();
+        query.query_terms(&mut terms);
+        let mut positions: Vec<Vec<u32>> = vec![Vec::new(); doc_addresses.len()];
+        // The ordinals of `doc_addresses`, sorted by segment and by doc,
+        // so that the postings are only scanned forward.
+        let mut ords: Vec<usize> = (0..doc_addresses.len()).collect();
+        ords.sort_by_key(|&ord| doc_addresses[ord]);
+        let mut term_positions = Vec::new();
+        for segment_ords in ords.chunk_by(|&left, &right| {
+            doc_addresses[left].segment_ord() == doc_addresses[right].segment_ord()
+        }) {
+            let segment_ord = doc_addresses[segment_ords[0]].segment_ord();
+            let inverted_index = self.segment_reader(segment_ord).inverted_index(field)?;
+            for term in terms.iter().filter(|term| term.field() == field) {
+                if let Some(mut postings) =
+                    inverted_index.read_postings(term, IndexRecordOption::WithFreqsAndPositions)?
+                {
+                    for &ord in segment_ords {
+                        let DocAddress(_, doc) = doc_addresses[ord];
+                        if postings.seek(doc) == doc {
+                            postings.positions(&mut term_positions);
+                            positions[ord].extend_from_slice(&term_positions);
+                        }
+                    }
+                }
+            }
+        }
+        for doc_positions in &mut positions {
+            doc_positions.sort_unstable();
+            doc_positions.dedup();
+        }
+        Ok(positions)
+    }
+
+    /// Returns a query matching the documents similar to the document `doc_address`.
+    ///
+    /// The stored values of the given text `fields` are tokenized, and their terms are
+    /// scored by `tf * idf`. The resulting query is a disjunction of the best terms.
+    /// The original document is not excluded from the results.
+    ///
+    /// The `fields` are required to be stored text fields. Otherwise, they will
+    /// simply not contribute any term.
+    pub fn more_like_this(
+        &self,
+        doc_address: DocAddress,
+        fields: &[Field],
+        params: &MoreLikeThisParams,
+    ) -> crate::Result<Box<dyn Query>> {
+        more_like_this_query(self, doc_address, fields, params)
+    }
+
     /// Return the list of segment readers
+    ///
+    /// # Panics
+    ///
+    /// With `SegmentOpening::Lazy`, panics if the `SegmentReader`s are not opened yet
+    /// and opening them fails. Searching, or calling
+    /// [`IndexReader::warm_all_segments`](./struct.IndexReader.html#method.warm_all_segments),
+    /// opens them and returns the error instead.
     pub fn segment_readers(&self) -> &[SegmentReader] {
-        &self.segment_readers
+        self.open_segment_readers()
+            .expect("Failed to open the segments of the searcher")
     }
 
     /// Returns the segment_reader associated with the given segment_ordinal
     pub fn segment_reader(&self, segment_ord: u32) -> &SegmentReader {
-        &self.segment_readers[segment_ord as usize]
+        &self.segment_readers()[segment_ord as usize]
     }
 
     /// Runs a query on the segment readers wrapped by the searcher.
@@ -135,35 +712,652 @@ impl Searcher {
         collector: &C,
         executor: &Executor,
     ) -> crate::Result<C::Fruit> {
+        // `Instant::now()` panics on `wasm32-unknown-unknown`, which has no clock.
+        #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+        let start = Instant::now();
+        let segment_readers = self.segments.segment_readers(executor)?;
         let scoring_enabled = collector.requires_scoring();
-        let weight = query.weight(self, scoring_enabled)?;
-        let segment_readers = self.segment_readers();
+        #[cfg(feature = "otel")]
+        let search_span = crate::otel::SearchSpan::start(self, query);
+        let weight = self.weight(query, scoring_enabled)?;
+        #[cfg(feature = "otel")]
+        let weight = search_span.instrument_weight(weight);
         let fruits = executor.map(
             |(segment_ord, segment_reader)| {
                 collector.collect_segment(weight.as_ref(), segment_ord as u32, segment_reader)
             },
             segment_readers.iter().enumerate(),
         )?;
-        collector.merge_fruits(fruits)
+        let fruit = collector.merge_fruits(fruits);
+        #[cfg(feature = "otel")]
+        search_span.end();
+        #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+        self.index
+            .metrics_collector()
+            .record_query(start.elapsed().as_millis() as u64);
+        fruit
+    }
+
+    /// Collects all of the segments with a
+    /// [`MergeableCollector`](../collector/trait.MergeableCollector.html).
+    ///
+    /// Segments are collected in the search executor of the index, and their partial
+    /// fruits are merged in its threads as soon as they are available.
+    ///
+    /// Returns `None` if the searcher does not have any segment.
+    pub fn search_parallel<C: MergeableCollector>(
+        &self,
+        collector: &C,
+    ) -> crate::Result<Option<C::PartialFruit>> {
+        let executor = self.index.search_executor();
+        let segment_readers = self.segments.segment_readers(executor)?;
+        executor.map_reduce(
+            |(segment_ord, segment_reader)| {
+                collector.collect_segment(self, segment_ord as SegmentLocalId, segment_reader)
+            },
+            |left, right| collector.merge(left, right),
+            segment_readers.iter().enumerate().collect(),
+        )
+    }
+
+    /// Returns a page of `page_size` documents matching `query`, sorted by `sort_fields`,
+    /// starting after the sort key `after`.
+    ///
+    /// The returned sort key is the one of the last document of the page. It should be
+    /// passed as `after` to fetch the next page. It is `None` if this page is the last one.
+    ///
+    /// Unlike an offset, the cost of a page does not depend on its depth.
+    /// The sort key is only valid for this `Searcher`.
+    /// See [`SortCollector`](./collector/struct.SortCollector.html) for more details.
+    pub fn scroll(
+        &self,
+        query: &dyn Query,
+        sort_fields: &[(Field, Order)],
+        after: Option<SortKey>,
+        page_size: usize,
+    ) -> crate::Result<(Vec<DocAddress>, Option<SortKey>)> {
+        let mut collector = SortCollector::new(sort_fields.to_vec(), page_size);
+        if let Some(after) = after {
+            collector = collector.search_after(after);
+        }
+        let page = self.search(query, &collector)?;
+        let next = if page.len() == page_size {
+            page.last().map(|(sort_key, _)| sort_key.clone())
+        } else {
+            None
+        };
+        let doc_addresses = page
+            .into_iter()
+            .map(|(_, doc_address)| doc_address)
+            .collect();
+        Ok((doc_addresses, next))
+    }
+
+    /// Returns `n` documents drawn uniformly at random among the documents matching
+    /// `query`, or all of them if less than `n` documents match.
+    ///
+    /// The matching documents are streamed into a reservoir of `n` documents, using
+    /// Li's Algorithm L: only the reservoir is kept in memory, and the random numbers
+    /// are drawn once per document entering the reservoir rather than once per matching
+    /// document. Given the same `seed`, the same searcher returns the same sample.
+    ///
+    /// The documents are returned in the order of their `DocAddress`.
+    pub fn random_sample(
+        &self,
+        query: &dyn Query,
+        n: usize,
+        seed: u64,
+    ) -> crate::Result<Vec<DocAddress>> {
+        let mut reservoir: Vec<DocAddress> = Vec::with_capacity(n);
+        if n == 0 {
+            return Ok(reservoir);
+        }
+        let weight = self.weight(query, false)?;
+        let mut rng = SplitMix64::new(seed);
+        let mut w = (rng.next_f64().ln() / n as f64).exp();
+        let mut num_matches = 0u64;
+        let mut next_selected = n as u64;
+        for (segment_ord, segment_reader) in self.segment_readers().iter().enumerate() {
+            let mut scorer = weight.scorer(segment_reader, 1.0)?;
+            let mut doc = scorer.doc();
+            while doc != TERMINATED {
+                if !segment_reader.is_deleted(doc) {
+                    let doc_address = DocAddress(segment_ord as SegmentLocalId, doc);
+                    if reservoir.len() < n {
+                        reservoir.push(doc_address);
+                        if reservoir.len() == n {
+                            next_selected = rng.next_selected(w, n as u64);
+                        }
+                    } else if num_matches == next_selected {
+                        reservoir[rng.next_below(n as u64) as usize] = doc_address;
+                        w *= (rng.next_f64().ln() / n as f64).exp();
+                        next_selected = rng.next_selected(w, num_matches + 1);
+                    }
+                    num_matches += 1;
+                }
+                doc = scorer.advance();
+            }
+        }
+        reservoir.sort();
+        Ok(reservoir)
+    }
+
+    /// Reranks the `initial` results of a search, e.g. the top documents returned by
+    /// `TopDocs`, with an expensive scoring function like a neural model.
+    ///
+    /// `reranker` is given the stored document and its initial score, and returns its
+    /// new score. It is called once for each document of `initial`: a document appearing
+    /// twice only keeps its first occurrence. The documents are returned by decreasing new
+    /// score, and no document outside of `initial` is ever returned.
+    ///
+    /// If the reranker returns an error, this error is returned.
+    /// To call the reranker in parallel, use a
+    /// [`ParallelReranker`](./struct.ParallelReranker.html).
+    pub fn rerank<F>(
+        &self,
+        initial: Vec<(Score, DocAddress)>,
+        reranker: F,
+    ) -> crate::Result<Vec<(Score, DocAddress)>>
+    where
+        F: Fn(&Document, Score) -> crate::Result<Score>,
+    {
+        reranker::rerank(self, initial, None, reranker)
+    }
+
+    /// Same as [`rerank(...)`](#method.rerank), but the documents given to the reranker
+    /// only contain the stored values of `fields`.
+    ///
+    /// See [`doc_projected(...)`](#method.doc_projected).
+    pub fn rerank_projected<F>(
+        &self,
+        initial: Vec<(Score, DocAddress)>,
+        fields: &[Field],
+        reranker: F,
+    ) -> crate::Result<Vec<(Score, DocAddress)>>
+    where
+        F: Fn(&Document, Score) -> crate::Result<Score>,
+    {
+        reranker::rerank(self, initial, Some(fields), reranker)
     }
 
     /// Summarize total space usage of this searcher.
     pub fn space_usage(&self) -> io::Result<SearcherSpaceUsage> {
         let mut space_usage = SearcherSpaceUsage::new();
-        for segment_reader in &self.segment_readers {
+        let segment_readers = self.open_segment_readers().map_err(io::Error::other)?;
+        for segment_reader in segment_readers {
             space_usage.add_segment(segment_reader.space_usage()?);
         }
         Ok(space_usage)
     }
 }
 
+/// Pseudo random number generator of `Searcher::random_sample`.
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    fn new(seed: u64) -> SplitMix64 {
+        SplitMix64 { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number within the open interval `(0, 1)`.
+    fn next_f64(&mut self) -> f64 {
+        ((self.next_u64() >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+    }
+
+    /// Returns the ordinal of the next match entering the reservoir of Algorithm L,
+    /// `num_matches` being the ordinal of the next match.
+    fn next_selected(&mut self, w: f64, num_matches: u64) -> u64 {
+        let skip = (self.next_f64().ln() / (1.0 - w).ln()).floor() as u64;
+        num_matches.saturating_add(skip)
+    }
+
+    /// Returns a number within `[0, bound)`.
+    fn next_below(&mut self, bound: u64) -> u64 {
+        ((u128::from(self.next_u64()) * u128::from(bound)) >> 64) as u64
+    }
+}
+
+/// Number of windows of documents sampled by `Searcher::count_approximate` in a segment.
+const NUM_SAMPLE_WINDOWS: u32 = 64;
+
+/// Counts the documents matching `weight` in `sample_size` documents of the segment,
+/// and extrapolates the count to the whole segment.
+fn estimate_count(
+    weight: &dyn Weight,
+    segment_reader: &SegmentReader,
+    sample_size: u32,
+) -> crate::Result<f64> {
+    let max_doc = segment_reader.max_doc();
+    let window_len = sample_size / NUM_SAMPLE_WINDOWS;
+    let mut scorer = weight.scorer(segment_reader, 1.0)?;
+    let mut num_matches = 0u32;
+    let mut num_sampled_docs = 0u32;
+    for window_ord in 0..NUM_SAMPLE_WINDOWS {
+        let window_start =
+            (u64::from(max_doc) * u64::from(window_ord) / u64::from(NUM_SAMPLE_WINDOWS)) as DocId;
+        let window_end = cmp::min(window_start + window_len, max_doc);
+        let mut doc = if scorer.doc() < window_start {
+            scorer.seek(window_start)
+        } else {
+            scorer.doc()
+        };
+        while doc < window_end {
+            if !segment_reader.is_deleted(doc) {
+                num_matches += 1;
+            }
+            doc = scorer.advance();
+        }
+        num_sampled_docs += window_end - window_start;
+    }
+    Ok(f64::from(num_matches) * f64::from(max_doc) / f64::from(num_sampled_docs))
+}
+
 impl fmt::Debug for Searcher {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let segment_ids = self
-            .segment_readers
+            .segments
+            .segment_files
             .iter()
-            .map(SegmentReader::segment_id)
+            .map(SegmentFiles::segment_id)
             .collect::<Vec<_>>();
         write!(f, "Searcher({:?})", segment_ids)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{SuggestEntry, TermStatistics};
+    use crate::collector::Count;
+    use crate::query::{AllQuery, BooleanQuery, PhraseQuery, Query, RangeQuery, TermQuery};
+    use crate::schema::{IndexRecordOption, Schema, FAST, INDEXED, STORED, STRING, TEXT};
+    use crate::{Directory, TantivyError, VerifyMode};
+    use crate::{DocAddress, Index, ReloadPolicy, SegmentComponent, SegmentOpening, Term};
+    use futures::executor::block_on;
+    use std::collections::HashMap;
+
+    fn entry(term: &str, doc_freq: u64) -> SuggestEntry {
+        SuggestEntry {
+            term: term.to_string(),
+            doc_freq,
+        }
+    }
+
+    #[test]
+    fn test_suggest() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let price = schema_builder.add_u64_field("price", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title => "tantivy search"));
+        index_writer.add_document(doc!(title => "tantivy table"));
+        index_writer.commit()?;
+        index_writer.add_document(doc!(title => "tank tantrum"));
+        index_writer.add_document(doc!(title => "tantivy tanker"));
+        index_writer.add_document(doc!(title => "tan"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 2);
+
+        assert_eq!(
+            searcher.suggest(title, "tan", 3)?,
+            vec![entry("tantivy", 3), entry("tan", 1), entry("tank", 1)]
+        );
+        assert_eq!(
+            searcher.suggest(title, "tank", 10)?,
+            vec![entry("tank", 1), entry("tanker", 1)]
+        );
+        assert_eq!(searcher.suggest(title, "", 1)?, vec![entry("tantivy", 3)]);
+        assert!(searcher.suggest(title, "zebra", 10)?.is_empty());
+        assert!(searcher.suggest(title, "tan", 0)?.is_empty());
+        assert!(searcher.suggest(price, "1", 10).is_err());
+        assert_eq!(
+            searcher.suggest(title, "tantivy", 1)?[0].doc_freq,
+            searcher.doc_freq(&Term::from_field_text(title, "tantivy"))?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_count() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let price = schema_builder.add_u64_field("price", INDEXED | FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        for i in 0u64..10_000 {
+            let text = if i % 4 == 0 { "a b" } else { "a" };
+            index_writer.add_document(doc!(title => text, price => i));
+        }
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let term_a = Term::from_field_text(title, "a");
+        let term_b = Term::from_field_text(title, "b");
+        let term_query = TermQuery::new(term_b.clone(), IndexRecordOption::Basic);
+        let range_query = RangeQuery::new_u64(price, 1_000..3_000);
+        let boolean_query = BooleanQuery::new_multiterms_query(vec![term_a, term_b.clone()]);
+        assert_eq!(searcher.count(&AllQuery)?, 10_000);
+        assert_eq!(searcher.count(&term_query)?, 2_500);
+        assert_eq!(searcher.count(&range_query)?, 2_000);
+        assert_eq!(searcher.count(&boolean_query)?, 10_000);
+        index_writer.delete_term(Term::from_field_u64(price, 4));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        for query in &[&AllQuery as &dyn Query, &term_query, &range_query] {
+            assert_eq!(
+                searcher.count(*query)?,
+                searcher.search(*query, &Count)? as u64
+            );
+        }
+        assert_eq!(searcher.count(&term_query)?, 2_499);
+        assert_eq!(searcher.count_approximate(&term_query, 0.0)?, 2_499);
+        let phrase_query = PhraseQuery::new(vec![Term::from_field_text(title, "a"), term_b]);
+        let estimate = searcher.count_approximate(&phrase_query, 0.02)?;
+        assert!((2_400..=2_600).contains(&estimate), "{}", estimate);
+        for &tolerance in &[1.0, -0.1, f64::NAN] {
+            assert!(matches!(
+                searcher.count_approximate(&term_query, tolerance),
+                Err(TantivyError::InvalidArgument(_))
+            ));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_random_sample() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", STRING);
+        let id = schema_builder.add_u64_field("id", INDEXED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        for i in 0u64..100 {
+            let text = if i % 2 == 0 { "even" } else { "odd" };
+            index_writer.add_document(doc!(title => text, id => i));
+            if i == 50 {
+                index_writer.commit()?;
+            }
+        }
+        index_writer.delete_term(Term::from_field_u64(id, 0));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 2);
+        let even_query = TermQuery::new(
+            Term::from_field_text(title, "even"),
+            IndexRecordOption::Basic,
+        );
+        let sample = searcher.random_sample(&even_query, 5, 42)?;
+        assert_eq!(sample.len(), 5);
+        assert_eq!(sample, searcher.random_sample(&even_query, 5, 42)?);
+        assert!(sample.windows(2).all(|docs| docs[0] < docs[1]));
+        assert_ne!(sample, searcher.random_sample(&even_query, 5, 43)?);
+        let all_even_docs = searcher.random_sample(&even_query, 100, 42)?;
+        assert_eq!(all_even_docs.len(), 49);
+        assert!(sample.iter().all(|doc| all_even_docs.contains(doc)));
+        assert!(searcher.random_sample(&even_query, 0, 42)?.is_empty());
+        // Each of the 49 documents is expected in `5 / 49` of the samples.
+        let mut counts: HashMap<DocAddress, usize> = HashMap::new();
+        for seed in 0..2_000 {
+            for doc_address in searcher.random_sample(&even_query, 5, seed)? {
+                *counts.entry(doc_address).or_insert(0) += 1;
+            }
+        }
+        assert_eq!(counts.len(), 49);
+        assert!(counts.values().all(|&count| (120..=300).contains(&count)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_term_statistics() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let tag = schema_builder.add_text_field("tag", STRING);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title => "to be or not to be", tag => "a"));
+        index_writer.add_document(doc!(title => "be quick", tag => "a"));
+        index_writer.commit()?;
+        index_writer.add_document(doc!(title => "let it be be be", tag => "a"));
+        index_writer.add_document(doc!(title => "hello"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 2);
+        assert_eq!(
+            searcher.term_statistics(&Term::from_field_text(title, "be"))?,
+            TermStatistics {
+                doc_freq: 3,
+                total_term_freq: 6,
+            }
+        );
+        // The posting list spans several blocks.
+        for _ in 0..300 {
+            index_writer.add_document(doc!(title => "be be"));
+        }
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(
+            searcher.term_statistics(&Term::from_field_text(title, "be"))?,
+            TermStatistics {
+                doc_freq: 303,
+                total_term_freq: 606,
+            }
+        );
+        assert_eq!(
+            searcher.term_statistics(&Term::from_field_text(title, "absent"))?,
+            TermStatistics {
+                doc_freq: 0,
+                total_term_freq: 0,
+            }
+        );
+        // `tag` does not record term frequencies.
+        assert_eq!(
+            searcher.term_statistics(&Term::from_field_text(tag, "a"))?,
+            TermStatistics {
+                doc_freq: 3,
+                total_term_freq: 3,
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_suggest_corrections() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let price = schema_builder.add_u64_field("price", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title => "search engine"));
+        index_writer.add_document(doc!(title => "research paper"));
+        index_writer.commit()?;
+        index_writer.add_document(doc!(title => "search party"));
+        index_writer.add_document(doc!(title => "perch lake"));
+        index_writer.add_document(doc!(title => "march"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 2);
+
+        // One transposition away from "search", found in both segments.
+        assert_eq!(
+            searcher.suggest_corrections(title, "saerch", 1, 10)?,
+            vec!["search".to_string()]
+        );
+        // "search" is more frequent than "perch".
+        assert_eq!(
+            searcher.suggest_corrections(title, "serch", 1, 10)?,
+            vec!["search", "perch"]
+        );
+        assert_eq!(
+            searcher.suggest_corrections(title, "serch", 2, 10)?,
+            vec!["search", "perch", "march"]
+        );
+        assert_eq!(
+            searcher.suggest_corrections(title, "search", 2, 4)?,
+            vec!["search", "march", "perch", "research"]
+        );
+        assert!(searcher
+            .suggest_corrections(title, "zebra", 1, 10)?
+            .is_empty());
+        assert!(searcher
+            .suggest_corrections(title, "search", 3, 10)
+            .is_err());
+        assert!(searcher.suggest_corrections(price, "1", 1, 10).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_segment_opening() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT | STORED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title => "a b"));
+        index_writer.commit()?;
+        index_writer.add_document(doc!(title => "a"));
+        index_writer.commit()?;
+        let reader = index
+            .reader_builder()
+            .reload_policy(ReloadPolicy::Manual)
+            .segment_opening(SegmentOpening::Lazy)
+            .try_into()?;
+        let searcher = reader.searcher();
+        assert!(searcher.segments.segment_readers.get().is_none());
+        assert_eq!(searcher.num_docs(), 2);
+        assert!(searcher.segments.segment_readers.get().is_none());
+
+        // Merging the segments garbage collects their files,
+        // which must not prevent the searcher from opening them.
+        let segment_ids = index.searchable_segment_ids()?;
+        block_on(index_writer.merge(&segment_ids))?;
+        index_writer.wait_merging_threads()?;
+        assert_eq!(index.searchable_segment_ids()?.len(), 1);
+
+        let query = TermQuery::new(Term::from_field_text(title, "a"), IndexRecordOption::Basic);
+        assert_eq!(searcher.search(&query, &Count)?, 2);
+        assert_eq!(searcher.segment_readers().len(), 2);
+        assert_eq!(
+            searcher
+                .doc(DocAddress(1, 0))?
+                .get_first(title)
+                .unwrap()
+                .text(),
+            Some("a")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_searcher_clone() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title => "a b"));
+        index_writer.add_document(doc!(title => "a"));
+        index_writer.commit_with_payload("first")?;
+        let reader = index
+            .reader_builder()
+            .reload_policy(ReloadPolicy::Manual)
+            .segment_opening(SegmentOpening::Lazy)
+            .try_into()?;
+        let searcher = reader.searcher().clone();
+        let searcher_clone = searcher.clone();
+        assert!(searcher_clone.segments.segment_readers.get().is_none());
+
+        index_writer.add_document(doc!(title => "a"));
+        index_writer.commit()?;
+        reader.reload()?;
+        let query = TermQuery::new(Term::from_field_text(title, "a"), IndexRecordOption::Basic);
+        let handle = std::thread::spawn(move || searcher_clone.search(&query, &Count));
+        assert_eq!(handle.join().unwrap()?, 2);
+        // The segments opened by the clone are shared with the original searcher.
+        assert!(searcher.segments.segment_readers.get().is_some());
+        assert_eq!(searcher.commit_payload(), Some("first"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_warm_all_segments() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        for _ in 0..3 {
+            index_writer.add_document(doc!(title => "a"));
+            index_writer.commit()?;
+        }
+        let eager_reader = index.reader()?;
+        assert!(eager_reader
+            .searcher()
+            .segments
+            .segment_readers
+            .get()
+            .is_some());
+        let lazy_reader = index
+            .reader_builder()
+            .segment_opening(SegmentOpening::Lazy)
+            .try_into()?;
+        assert!(lazy_reader
+            .searcher()
+            .segments
+            .segment_readers
+            .get()
+            .is_none());
+        lazy_reader.warm_all_segments()?;
+        let searcher = lazy_reader.searcher();
+        assert_eq!(searcher.segments.segment_readers.get().unwrap().len(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_mode() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT | STORED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title => "a"));
+        index_writer.commit()?;
+        let reader_builder = |verify_mode, segment_opening| {
+            index
+                .reader_builder()
+                .reload_policy(ReloadPolicy::Manual)
+                .verify_mode(verify_mode)
+                .segment_opening(segment_opening)
+        };
+        reader_builder(VerifyMode::OnOpen, SegmentOpening::Eager).try_into()?;
+
+        // Flips a byte of the store, leaving its footer untouched.
+        let segment = index.searchable_segments()?.into_iter().next().unwrap();
+        let store_path = segment.relative_path(SegmentComponent::STORE);
+        let mut store_bytes = index
+            .directory()
+            .open_read_with_footer(&store_path)?
+            .read_bytes()?
+            .as_slice()
+            .to_vec();
+        store_bytes[0] ^= 1;
+        index.directory().atomic_write(&store_path, &store_bytes)?;
+
+        assert!(matches!(
+            reader_builder(VerifyMode::OnOpen, SegmentOpening::Lazy).try_into(),
+            Err(TantivyError::DataCorruption(_))
+        ));
+        let lazy_reader = reader_builder(VerifyMode::OnAccess, SegmentOpening::Lazy).try_into()?;
+        assert!(matches!(
+            lazy_reader.warm_all_segments(),
+            Err(TantivyError::DataCorruption(_))
+        ));
+        let reader = reader_builder(VerifyMode::Disabled, SegmentOpening::Eager).try_into()?;
+        assert_eq!(reader.searcher().num_docs(), 1);
+        Ok(())
+    }
+}
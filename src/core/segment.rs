@@ -5,9 +5,8 @@ use crate::core::SegmentMeta;
 use crate::directory::error::{OpenReadError, OpenWriteError};
 use crate::directory::Directory;
 use crate::directory::{FileSlice, WritePtr};
-use crate::indexer::segment_serializer::SegmentSerializer;
 use crate::schema::Schema;
-use crate::Opstamp;
+use crate::{HasLen, Opstamp};
 use std::fmt;
 use std::path::PathBuf;
 
@@ -56,6 +55,38 @@ impl Segment {
         }
     }
 
+    /// Records the size of the files of a segment that has been written.
+    pub(crate) fn with_num_bytes(self) -> Segment {
+        let num_bytes = self.compute_num_bytes();
+        Segment {
+            index: self.index,
+            meta: self.meta.with_num_bytes(num_bytes),
+        }
+    }
+
+    /// Returns the total size of the files of the segment, except for its delete file.
+    pub(crate) fn compute_num_bytes(&self) -> u64 {
+        let components: Vec<SegmentComponent> = SegmentComponent::iterator()
+            .cloned()
+            .filter(|component| !matches!(component, SegmentComponent::DELETE))
+            .collect();
+        self.components_num_bytes(&components)
+    }
+
+    /// Returns the total size of the files of the `components`.
+    ///
+    /// The missing files are ignored.
+    pub(crate) fn components_num_bytes(&self, components: &[SegmentComponent]) -> u64 {
+        components
+            .iter()
+            .map(|&component| {
+                self.open_read(component)
+                    .map(|file| file.len() as u64)
+                    .unwrap_or(0u64)
+            })
+            .sum()
+    }
+
     #[doc(hidden)]
     pub fn with_delete_meta(self, num_deleted_docs: u32, opstamp: Opstamp) -> Segment {
         Segment {
@@ -90,12 +121,3 @@ impl Segment {
         Ok(write)
     }
 }
-
-pub trait SerializableSegment {
-    /// Writes a view of a segment by pushing information
-    /// to the `SegmentSerializer`.
-    ///
-    /// # Returns
-    /// The number of documents in the segment.
-    fn write(&self, serializer: SegmentSerializer) -> crate::Result<u32>;
-}
@@ -26,12 +26,16 @@ pub enum SegmentComponent {
     STORE,
     /// Bitset describing which document of the segment is deleted.
     DELETE,
+    /// Row-oriented, compressed storage of the term vectors of the documents.
+    ///
+    /// Segments written before term vectors were introduced do not have this file.
+    TERMVECTORS,
 }
 
 impl SegmentComponent {
     /// Iterates through the components.
     pub fn iterator() -> slice::Iter<'static, SegmentComponent> {
-        static SEGMENT_COMPONENTS: [SegmentComponent; 8] = [
+        static SEGMENT_COMPONENTS: [SegmentComponent; 9] = [
             SegmentComponent::POSTINGS,
             SegmentComponent::POSITIONS,
             SegmentComponent::POSITIONSSKIP,
@@ -40,6 +44,7 @@ impl SegmentComponent {
             SegmentComponent::TERMS,
             SegmentComponent::STORE,
             SegmentComponent::DELETE,
+            SegmentComponent::TERMVECTORS,
         ];
         SEGMENT_COMPONENTS.iter()
     }
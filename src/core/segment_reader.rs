@@ -1,27 +1,192 @@
 use crate::common::HasLen;
+use crate::core::ChecksumVerifier;
 use crate::core::InvertedIndexReader;
 use crate::core::Segment;
 use crate::core::SegmentComponent;
 use crate::core::SegmentId;
+use crate::directory::error::{Incompatibility, OpenReadError};
 use crate::directory::FileSlice;
 use crate::fastfield::DeleteBitSet;
 use crate::fastfield::FacetReader;
 use crate::fastfield::FastFieldReaders;
 use crate::fieldnorm::{FieldNormReader, FieldNormReaders};
 use crate::schema::FieldType;
-use crate::schema::Schema;
 use crate::schema::{Field, IndexRecordOption};
+use crate::schema::{Schema, SchemaVersion, TextFieldIndexing};
 use crate::space_usage::SegmentSpaceUsage;
-use crate::store::StoreReader;
+use crate::store::{StoreReader, StoredFieldCompression};
 use crate::termdict::TermDictionary;
+use crate::termvector::{TermVectors, TermVectorsReader};
 use crate::DocId;
 use crate::{common::CompositeFile, error::DataCorruption};
 use fail::fail_point;
 use std::fmt;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::RwLock;
 use std::{collections::HashMap, io};
 
+/// The files of a `Segment`, opened but not read yet.
+///
+/// Once opened, the files remain readable even if the segment
+/// gets garbage collected, so that the `SegmentReader` can be
+/// opened later on. (See `SegmentOpening::Lazy`.)
+///
+/// The `Segment` itself is not kept, as living segments are
+/// protected from garbage collection.
+#[derive(Clone)]
+pub(crate) struct SegmentFiles {
+    segment_id: SegmentId,
+    max_doc: DocId,
+    num_docs: DocId,
+    schema: Schema,
+    schema_version: SchemaVersion,
+    termdict: FileSlice,
+    store: FileSlice,
+    store_compression: StoredFieldCompression,
+    term_vectors: Option<FileSlice>,
+    postings: FileSlice,
+    positions: Option<FileSlice>,
+    positions_skip: Option<FileSlice>,
+    fast_fields: FileSlice,
+    fieldnorms: FileSlice,
+    delete: Option<FileSlice>,
+    checksums: Option<SegmentChecksums>,
+}
+
+/// The files of a segment to verify, including their footers.
+#[derive(Clone)]
+struct SegmentChecksums {
+    checksum_verifier: Arc<ChecksumVerifier>,
+    files: Vec<(PathBuf, FileSlice)>,
+}
+
+impl SegmentFiles {
+    /// Opens the files of a `Segment`.
+    pub fn open(segment: &Segment) -> crate::Result<SegmentFiles> {
+        let termdict = segment.open_read(SegmentComponent::TERMS)?;
+        let store = segment.open_read(SegmentComponent::STORE)?;
+
+        fail_point!("SegmentReader::open#middle");
+
+        let postings = segment.open_read(SegmentComponent::POSTINGS)?;
+        let positions = segment.open_read(SegmentComponent::POSITIONS).ok();
+        let positions_skip = segment.open_read(SegmentComponent::POSITIONSSKIP).ok();
+        let term_vectors = segment.open_read(SegmentComponent::TERMVECTORS).ok();
+        let fast_fields = segment.open_read(SegmentComponent::FASTFIELDS)?;
+        let fieldnorms = segment.open_read(SegmentComponent::FIELDNORMS)?;
+        let delete = if segment.meta().has_deletes() {
+            Some(segment.open_read(SegmentComponent::DELETE)?)
+        } else {
+            None
+        };
+        let store_compression = segment.meta().store_compression();
+        if !store_compression.is_supported() {
+            return Err(crate::TantivyError::IncompatibleIndex(
+                Incompatibility::CompressionMismatch {
+                    library_compression_format: crate::store::COMPRESSION.to_string(),
+                    index_compression_format: format!("{:?}", store_compression),
+                },
+            ));
+        }
+        let schema = segment.schema();
+        let schema_version = segment
+            .meta()
+            .schema_version()
+            .unwrap_or_else(|| schema.version());
+        Ok(SegmentFiles {
+            segment_id: segment.id(),
+            max_doc: segment.meta().max_doc(),
+            num_docs: segment.meta().num_docs(),
+            schema,
+            schema_version,
+            termdict,
+            store,
+            store_compression,
+            term_vectors,
+            postings,
+            positions,
+            positions_skip,
+            fast_fields,
+            fieldnorms,
+            delete,
+            checksums: None,
+        })
+    }
+
+    /// Opens the files of a `Segment`, so that their checksums can then be verified
+    /// with [`verify_checksums`](#method.verify_checksums).
+    pub fn open_with_checksums(
+        segment: &Segment,
+        checksum_verifier: Arc<ChecksumVerifier>,
+    ) -> crate::Result<SegmentFiles> {
+        let mut segment_files = SegmentFiles::open(segment)?;
+        let directory = segment.index().directory();
+        let mut files = Vec::new();
+        for &component in SegmentComponent::iterator() {
+            if matches!(component, SegmentComponent::DELETE) && !segment.meta().has_deletes() {
+                continue;
+            }
+            let path = segment.relative_path(component);
+            match directory.open_read_with_footer(&path) {
+                Ok(file) => files.push((path, file)),
+                // The positions and term vectors are optional.
+                Err(OpenReadError::FileDoesNotExist(_)) => continue,
+                Err(err) => return Err(err.into()),
+            }
+        }
+        segment_files.checksums = Some(SegmentChecksums {
+            checksum_verifier,
+            files,
+        });
+        Ok(segment_files)
+    }
+
+    /// Verifies the checksums of the files, if they were opened with
+    /// [`open_with_checksums`](#method.open_with_checksums).
+    ///
+    /// Returns a `DataCorruption` error if a file is corrupted.
+    pub fn verify_checksums(&self) -> crate::Result<()> {
+        if let Some(checksums) = &self.checksums {
+            for (path, file) in &checksums.files {
+                checksums.checksum_verifier.verify(path, file)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the id of the segment.
+    pub fn segment_id(&self) -> SegmentId {
+        self.segment_id
+    }
+
+    /// Returns the number of documents of the segment.
+    /// Deleted documents are not counted.
+    pub fn num_docs(&self) -> DocId {
+        self.num_docs
+    }
+
+    /// Returns all of the files of the segment.
+    pub fn file_slices(&self) -> Vec<FileSlice> {
+        let mut file_slices = vec![
+            self.termdict.clone(),
+            self.store.clone(),
+            self.postings.clone(),
+            self.fast_fields.clone(),
+            self.fieldnorms.clone(),
+        ];
+        file_slices.extend(
+            self.term_vectors
+                .iter()
+                .chain(&self.positions)
+                .chain(&self.positions_skip)
+                .chain(&self.delete)
+                .cloned(),
+        );
+        file_slices
+    }
+}
+
 /// Entry point to access all of the datastructures of the `Segment`
 ///
 /// - term dictionary
@@ -51,8 +216,11 @@ pub struct SegmentReader {
     fieldnorm_readers: FieldNormReaders,
 
     store_file: FileSlice,
+    store_compression: StoredFieldCompression,
+    term_vectors_file: Option<FileSlice>,
     delete_bitset_opt: Option<DeleteBitSet>,
     schema: Schema,
+    schema_version: SchemaVersion,
 }
 
 impl SegmentReader {
@@ -130,84 +298,109 @@ impl SegmentReader {
     ///
     /// They are simply stored as a fast field, serialized in
     /// the `.fieldnorm` file of the segment.
+    ///
+    /// If the segment does not have fieldnorms for the field, e.g. because
+    /// they were disabled, all of the fieldnorms are 1.
     pub fn get_fieldnorms_reader(&self, field: Field) -> crate::Result<FieldNormReader> {
-        self.fieldnorm_readers.get_field(field)?.ok_or_else(|| {
-            let field_name = self.schema.get_field_name(field);
-            let err_msg = format!(
-                "Field norm not found for field {:?}. Was it marked as indexed during indexing?",
-                field_name
-            );
-            crate::TantivyError::SchemaError(err_msg)
-        })
+        if !self.schema_version.has_field(field) && self.schema.version().has_field(field) {
+            // The field was added to the schema after this segment was written.
+            return Ok(FieldNormReader::constant(self.max_doc, 0));
+        }
+        Ok(self
+            .fieldnorm_readers
+            .get_field(field)?
+            .unwrap_or_else(|| FieldNormReader::constant(self.max_doc, 1)))
     }
 
     /// Accessor to the segment's `StoreReader`.
     pub fn get_store_reader(&self) -> io::Result<StoreReader> {
-        StoreReader::open(self.store_file.clone())
+        StoreReader::open_with_compression(self.store_file.clone(), self.store_compression)
     }
 
-    /// Open a new segment for reading.
-    pub fn open(segment: &Segment) -> crate::Result<SegmentReader> {
-        let termdict_file = segment.open_read(SegmentComponent::TERMS)?;
-        let termdict_composite = CompositeFile::open(&termdict_file)?;
-
-        let store_file = segment.open_read(SegmentComponent::STORE)?;
+    /// Returns the term vectors of the `field` of a document.
+    ///
+    /// Returns a `SchemaError` if the schema does not enable term vectors for the field.
+    /// Segments written before term vectors were introduced return empty term vectors.
+    pub fn term_vectors(&self, field: Field, doc_id: DocId) -> crate::Result<TermVectors> {
+        let field_entry = self.schema.get_field_entry(field);
+        let term_vectors = match field_entry.field_type() {
+            FieldType::Str(text_options) => text_options
+                .get_indexing_options()
+                .map(TextFieldIndexing::term_vectors)
+                .unwrap_or(false),
+            _ => false,
+        };
+        if !term_vectors {
+            return Err(crate::TantivyError::SchemaError(format!(
+                "Field {:?} does not store term vectors.",
+                field_entry.name()
+            )));
+        }
+        match self.get_term_vectors_reader()? {
+            Some(term_vectors_reader) => term_vectors_reader.get(doc_id, field),
+            None => Ok(TermVectors::default()),
+        }
+    }
 
-        fail_point!("SegmentReader::open#middle");
+    pub(crate) fn get_term_vectors_reader(&self) -> io::Result<Option<TermVectorsReader>> {
+        self.term_vectors_file
+            .clone()
+            .map(|file| TermVectorsReader::open(file, self.store_compression))
+            .transpose()
+    }
 
-        let postings_file = segment.open_read(SegmentComponent::POSTINGS)?;
-        let postings_composite = CompositeFile::open(&postings_file)?;
+    /// Open a new segment for reading.
+    pub fn open(segment: &Segment) -> crate::Result<SegmentReader> {
+        SegmentReader::from_files(&SegmentFiles::open(segment)?)
+    }
 
-        let positions_composite = {
-            if let Ok(positions_file) = segment.open_read(SegmentComponent::POSITIONS) {
-                CompositeFile::open(&positions_file)?
-            } else {
-                CompositeFile::empty()
-            }
+    /// Opens the readers of the files of a segment.
+    pub(crate) fn from_files(segment_files: &SegmentFiles) -> crate::Result<SegmentReader> {
+        let termdict_composite = CompositeFile::open(&segment_files.termdict)?;
+        let postings_composite = CompositeFile::open(&segment_files.postings)?;
+        let positions_composite = match &segment_files.positions {
+            Some(positions_file) => CompositeFile::open(positions_file)?,
+            None => CompositeFile::empty(),
         };
-
-        let positions_idx_composite = {
-            if let Ok(positions_skip_file) = segment.open_read(SegmentComponent::POSITIONSSKIP) {
-                CompositeFile::open(&positions_skip_file)?
-            } else {
-                CompositeFile::empty()
-            }
+        let positions_idx_composite = match &segment_files.positions_skip {
+            Some(positions_skip_file) => CompositeFile::open(positions_skip_file)?,
+            None => CompositeFile::empty(),
         };
 
-        let schema = segment.schema();
+        let schema = segment_files.schema.clone();
+        let schema_version = segment_files.schema_version;
 
-        let fast_fields_data = segment.open_read(SegmentComponent::FASTFIELDS)?;
-        let fast_fields_composite = CompositeFile::open(&fast_fields_data)?;
+        let fast_fields_composite = CompositeFile::open(&segment_files.fast_fields)?;
         let fast_field_readers = Arc::new(FastFieldReaders::new(
             schema.clone(),
+            schema_version,
             fast_fields_composite,
         )?);
 
-        let fieldnorm_data = segment.open_read(SegmentComponent::FIELDNORMS)?;
-        let fieldnorm_readers = FieldNormReaders::open(fieldnorm_data)?;
+        let fieldnorm_readers = FieldNormReaders::open(segment_files.fieldnorms.clone())?;
 
-        let delete_bitset_opt = if segment.meta().has_deletes() {
-            let delete_data = segment.open_read(SegmentComponent::DELETE)?;
-            let delete_bitset = DeleteBitSet::open(delete_data)?;
-            Some(delete_bitset)
-        } else {
-            None
+        let delete_bitset_opt = match &segment_files.delete {
+            Some(delete_data) => Some(DeleteBitSet::open(delete_data.clone())?),
+            None => None,
         };
 
         Ok(SegmentReader {
             inv_idx_reader_cache: Default::default(),
-            max_doc: segment.meta().max_doc(),
-            num_docs: segment.meta().num_docs(),
+            max_doc: segment_files.max_doc,
+            num_docs: segment_files.num_docs,
             termdict_composite,
             postings_composite,
             fast_fields_readers: fast_field_readers,
             fieldnorm_readers,
-            segment_id: segment.id(),
-            store_file,
+            segment_id: segment_files.segment_id,
+            store_file: segment_files.store.clone(),
+            store_compression: segment_files.store_compression,
+            term_vectors_file: segment_files.term_vectors.clone(),
             delete_bitset_opt,
             positions_composite,
             positions_idx_composite,
             schema,
+            schema_version,
         })
     }
 
@@ -311,6 +504,23 @@ impl SegmentReader {
         (0u32..self.max_doc).filter(move |doc| !self.is_deleted(*doc))
     }
 
+    /// Returns the parts of the files of the segment that are read to search `field`:
+    /// its term dictionary, postings, positions, fast fields and field norms.
+    pub(crate) fn field_file_slices(&self, field: Field) -> Vec<FileSlice> {
+        let mut file_slices = Vec::new();
+        for composite_file in &[
+            &self.termdict_composite,
+            &self.postings_composite,
+            &self.positions_composite,
+            &self.positions_idx_composite,
+        ] {
+            file_slices.extend(composite_file.open_read_all(field));
+        }
+        file_slices.extend(self.fast_fields_readers.file_slices(field));
+        file_slices.extend(self.fieldnorm_readers.file_slices(field));
+        file_slices
+    }
+
     /// Summarize total space usage of this segment.
     pub fn space_usage(&self) -> io::Result<SegmentSpaceUsage> {
         Ok(SegmentSpaceUsage::new(
@@ -322,6 +532,10 @@ impl SegmentReader {
             self.fast_fields_readers.space_usage(),
             self.fieldnorm_readers.space_usage(),
             self.get_store_reader()?.space_usage(),
+            self.term_vectors_file
+                .as_ref()
+                .map(FileSlice::len)
+                .unwrap_or(0),
             self.delete_bitset_opt
                 .as_ref()
                 .map(DeleteBitSet::space_usage)
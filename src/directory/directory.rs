@@ -139,6 +139,16 @@ pub trait Directory: DirectoryClone + fmt::Debug + Send + Sync + 'static {
     /// Returns true iff the file exists
     fn exists(&self, path: &Path) -> Result<bool, OpenReadError>;
 
+    /// Returns the path of the file on the local filesystem, if the directory
+    /// stores its files there.
+    ///
+    /// This makes it possible to hard link the files of an index, as
+    /// [`Index::clone_to`](../struct.Index.html#method.clone_to) does.
+    /// By default, returns `None`.
+    fn local_path(&self, _path: &Path) -> Option<PathBuf> {
+        None
+    }
+
     /// Opens a writer for the *virtual file* associated with
     /// a Path.
     ///
@@ -77,56 +77,53 @@ impl Footer {
                 ),
             ));
         }
+        let file_len = file.len();
         let (body_footer, footer_len_file) = file.split_from_end(u32::SIZE_IN_BYTES);
         let mut footer_len_bytes = footer_len_file.read_bytes()?;
         let footer_len = u32::deserialize(&mut footer_len_bytes)? as usize;
+        if footer_len > body_footer.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "File corrupted. The footer len ({}) is larger than the file (len={}).",
+                    footer_len, file_len
+                ),
+            ));
+        }
         let (body, footer) = body_footer.split_from_end(footer_len);
         let mut footer_bytes = footer.read_bytes()?;
         let footer = Footer::deserialize(&mut footer_bytes)?;
         Ok((footer, body))
     }
 
+    /// Returns true iff the checksum stored in the footer of `file`
+    /// matches the content of the file.
+    pub fn is_checksum_valid(file: FileSlice) -> io::Result<bool> {
+        let (footer, body) = Footer::extract_footer(file)?;
+        let bytes = body.read_bytes()?;
+        let mut hasher = Hasher::new();
+        hasher.update(bytes.as_slice());
+        let crc = hasher.finalize();
+        Ok(footer.versioned_footer.crc() == Some(crc))
+    }
+
     /// Confirms that the index will be read correctly by this version of tantivy
     /// Has to be called after `extract_footer` to make sure it's not accessing uninitialised memory
     pub fn is_compatible(&self) -> Result<(), Incompatibility> {
         let library_version = crate::version();
         match &self.versioned_footer {
-            VersionedFooter::V1 {
-                crc32: _crc,
-                store_compression,
-            } => {
-                if &library_version.store_compression != store_compression {
-                    return Err(Incompatibility::CompressionMismatch {
-                        library_compression_format: library_version.store_compression.to_string(),
-                        index_compression_format: store_compression.to_string(),
-                    });
-                }
-                Ok(())
-            }
-            VersionedFooter::V2 {
-                crc32: _crc,
-                store_compression,
-            } => {
-                if &library_version.store_compression != store_compression {
-                    return Err(Incompatibility::CompressionMismatch {
-                        library_compression_format: library_version.store_compression.to_string(),
-                        index_compression_format: store_compression.to_string(),
-                    });
-                }
-                Ok(())
-            }
-            VersionedFooter::V3 {
-                crc32: _crc,
-                store_compression,
-            } => {
-                if &library_version.store_compression != store_compression {
-                    return Err(Incompatibility::CompressionMismatch {
-                        library_compression_format: library_version.store_compression.to_string(),
-                        index_compression_format: store_compression.to_string(),
-                    });
-                }
-                Ok(())
-            }
+            // The term infos of the older formats do not store the format
+            // of their posting list. These files are only read by `Index::migrate`,
+            // which converts them to the current format.
+            VersionedFooter::V1 { .. }
+            | VersionedFooter::V2 { .. }
+            | VersionedFooter::V3 { .. } => Err(Incompatibility::IndexMismatch {
+                library_version: library_version.clone(),
+                index_version: self.version.clone(),
+            }),
+            // The compression of the doc store is recorded in the meta of each
+            // segment, and checked when the segment is opened.
+            VersionedFooter::V4 { .. } => Ok(()),
             VersionedFooter::UnknownVersion => Err(Incompatibility::IndexMismatch {
                 library_version: library_version.clone(),
                 index_version: self.version.clone(),
@@ -153,24 +150,32 @@ pub enum VersionedFooter {
         crc32: CrcHashU32,
         store_compression: String,
     },
+    // Posting format of the terms in the term info store.
+    // The store compression is only the default compression of the build
+    // that wrote the file: the doc store of each segment records its own.
+    V4 {
+        crc32: CrcHashU32,
+        store_compression: String,
+    },
 }
 
 impl BinarySerializable for VersionedFooter {
     fn serialize<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
         let mut buf = Vec::new();
         match self {
-            VersionedFooter::V3 {
+            VersionedFooter::V4 {
                 crc32,
                 store_compression: compression,
             } => {
                 // Serializes a valid `VersionedFooter` or panics if the version is unknown
                 // [   version    |   crc_hash  | compression_mode ]
                 // [    0..4      |     4..8    |     variable     ]
-                BinarySerializable::serialize(&3u32, &mut buf)?;
+                BinarySerializable::serialize(&4u32, &mut buf)?;
                 BinarySerializable::serialize(crc32, &mut buf)?;
                 BinarySerializable::serialize(compression, &mut buf)?;
             }
-            VersionedFooter::V2 { .. }
+            VersionedFooter::V3 { .. }
+            | VersionedFooter::V2 { .. }
             | VersionedFooter::V1 { .. }
             | VersionedFooter::UnknownVersion => {
                 return Err(io::Error::new(
@@ -201,7 +206,7 @@ impl BinarySerializable for VersionedFooter {
         reader.read_exact(&mut buf[..])?;
         let mut cursor = &buf[..];
         let version = u32::deserialize(&mut cursor)?;
-        if version > 3 {
+        if version > 4 {
             return Ok(VersionedFooter::UnknownVersion);
         }
         let crc32 = u32::deserialize(&mut cursor)?;
@@ -216,12 +221,17 @@ impl BinarySerializable for VersionedFooter {
                 crc32,
                 store_compression,
             }
-        } else {
-            assert_eq!(version, 3);
+        } else if version == 3 {
             VersionedFooter::V3 {
                 crc32,
                 store_compression,
             }
+        } else {
+            assert_eq!(version, 4);
+            VersionedFooter::V4 {
+                crc32,
+                store_compression,
+            }
         })
     }
 }
@@ -229,6 +239,7 @@ impl BinarySerializable for VersionedFooter {
 impl VersionedFooter {
     pub fn crc(&self) -> Option<CrcHashU32> {
         match self {
+            VersionedFooter::V4 { crc32, .. } => Some(*crc32),
             VersionedFooter::V3 { crc32, .. } => Some(*crc32),
             VersionedFooter::V2 { crc32, .. } => Some(*crc32),
             VersionedFooter::V1 { crc32, .. } => Some(*crc32),
@@ -268,7 +279,7 @@ impl<W: TerminatingWrite> Write for FooterProxy<W> {
 impl<W: TerminatingWrite> TerminatingWrite for FooterProxy<W> {
     fn terminate_ref(&mut self, _: AntiCallToken) -> io::Result<()> {
         let crc32 = self.hasher.take().unwrap().finalize();
-        let footer = Footer::new(VersionedFooter::V3 {
+        let footer = Footer::new(VersionedFooter::V4 {
             crc32,
             store_compression: crate::store::COMPRESSION.to_string(),
         });
@@ -278,12 +289,69 @@ impl<W: TerminatingWrite> TerminatingWrite for FooterProxy<W> {
     }
 }
 
+/// Rewrites the files of the searchable segments of `index` as if they were
+/// written with the index format 3, to test the handling of the indexes written
+/// by older versions of tantivy.
+///
+/// The term dictionaries and the fast fields are converted to their previous layout,
+/// which requires all of the posting lists to be compressed by blocks, and all of
+/// the fast fields to be bitpacked and not optional.
+///
+/// `directory` is the directory of the index, without the `ManagedDirectory`.
+#[cfg(test)]
+pub(crate) fn rewrite_in_index_format_v3(
+    directory: &dyn crate::Directory,
+    index: &crate::Index,
+) -> crate::Result<()> {
+    for segment_meta in index.searchable_segment_metas()? {
+        for path in segment_meta.list_files() {
+            if !directory.exists(&path)? {
+                continue;
+            }
+            let (footer, body) = Footer::extract_footer(directory.open_read(&path)?)?;
+            let body = body.read_bytes()?;
+            let mut data = match path.extension().and_then(std::ffi::OsStr::to_str) {
+                Some("term") => crate::common::convert_composite_file(
+                    body.as_slice(),
+                    |_field, _idx, term_dictionary| {
+                        crate::termdict::downgrade_to_index_format_v3(term_dictionary)
+                    },
+                )?,
+                Some("fast") => crate::fastfield::downgrade_to_index_format_v3(
+                    body.as_slice(),
+                    &index.schema(),
+                )?,
+                _ => body.as_slice().to_vec(),
+            };
+            let mut version = footer.version.clone();
+            version.index_format_version = 3;
+            // `V3` footers cannot be serialized anymore.
+            let mut versioned_footer = Vec::new();
+            3u32.serialize(&mut versioned_footer)?;
+            crc32fast::hash(&data).serialize(&mut versioned_footer)?;
+            crate::store::COMPRESSION
+                .to_string()
+                .serialize(&mut versioned_footer)?;
+            let mut old_footer = Vec::new();
+            VInt(versioned_footer.len() as u64).serialize(&mut old_footer)?;
+            old_footer.extend_from_slice(&versioned_footer);
+            version.to_string().serialize(&mut old_footer)?;
+            serde_json::to_string(&version)?.serialize(&mut old_footer)?;
+            data.extend_from_slice(&old_footer);
+            (old_footer.len() as u32).serialize(&mut data)?;
+            directory.atomic_write(&path, &data)?;
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
 
     use super::CrcHashU32;
     use super::FooterProxy;
     use crate::common::{BinarySerializable, VInt};
+    use crate::directory::error::Incompatibility;
     use crate::directory::footer::{Footer, VersionedFooter};
     use crate::directory::TerminatingWrite;
     use byteorder::{ByteOrder, LittleEndian};
@@ -303,17 +371,24 @@ mod tests {
         let footer = Footer::deserialize(&mut &vec[..]).unwrap();
         assert!(matches!(
            footer.versioned_footer,
-           VersionedFooter::V3 { store_compression, .. }
+           VersionedFooter::V4 { store_compression, .. }
            if store_compression == crate::store::COMPRESSION
         ));
         assert_eq!(&footer.version, crate::version());
     }
 
+    #[test]
+    fn test_extract_footer_from_garbage() {
+        let file = crate::directory::FileSlice::from(b"garbage".to_vec());
+        let err = Footer::extract_footer(file).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
     #[test]
     fn test_serialize_deserialize_footer() {
         let mut buffer = Vec::new();
         let crc32 = 123456u32;
-        let footer: Footer = Footer::new(VersionedFooter::V3 {
+        let footer: Footer = Footer::new(VersionedFooter::V4 {
             crc32,
             store_compression: "lz4".to_string(),
         });
@@ -325,7 +400,7 @@ mod tests {
     #[test]
     fn footer_length() {
         let crc32 = 1111111u32;
-        let versioned_footer = VersionedFooter::V3 {
+        let versioned_footer = VersionedFooter::V4 {
             crc32,
             store_compression: "lz4".to_string(),
         };
@@ -346,7 +421,7 @@ mod tests {
             // versionned footer length
             12 | 128,
             // index format version
-            3,
+            4,
             0,
             0,
             0,
@@ -365,7 +440,7 @@ mod tests {
         let versioned_footer = VersionedFooter::deserialize(&mut cursor).unwrap();
         assert!(cursor.is_empty());
         let expected_crc: u32 = LittleEndian::read_u32(&v_footer_bytes[5..9]) as CrcHashU32;
-        let expected_versioned_footer: VersionedFooter = VersionedFooter::V3 {
+        let expected_versioned_footer: VersionedFooter = VersionedFooter::V4 {
             crc32: expected_crc,
             store_compression: "lz4".to_string(),
         };
@@ -375,6 +450,30 @@ mod tests {
         assert_eq!(&v_footer_bytes[..], &buffer[..]);
     }
 
+    #[test]
+    fn test_old_versioned_footers_are_incompatible() {
+        for version in 1u32..=3u32 {
+            let mut buf = Vec::new();
+            version.serialize(&mut buf).unwrap();
+            1111111u32.serialize(&mut buf).unwrap();
+            crate::store::COMPRESSION
+                .to_string()
+                .serialize(&mut buf)
+                .unwrap();
+            let mut v_footer_bytes = Vec::new();
+            VInt(buf.len() as u64)
+                .serialize(&mut v_footer_bytes)
+                .unwrap();
+            v_footer_bytes.extend_from_slice(&buf);
+            let versioned_footer = VersionedFooter::deserialize(&mut &v_footer_bytes[..]).unwrap();
+            assert_eq!(versioned_footer.crc(), Some(1111111));
+            assert!(matches!(
+                Footer::new(versioned_footer).is_compatible(),
+                Err(Incompatibility::IndexMismatch { .. })
+            ));
+        }
+    }
+
     #[test]
     fn versioned_footer_panic() {
         let v_footer_bytes = vec![6u8 | 128u8, 3u8, 0u8, 0u8, 1u8, 0u8, 0u8];
@@ -389,15 +488,14 @@ mod tests {
 
     #[test]
     #[cfg(not(feature = "lz4"))]
-    fn compression_mismatch() {
+    fn test_v4_footer_ignores_store_compression() {
         let crc32 = 1111111u32;
-        let versioned_footer = VersionedFooter::V1 {
+        let versioned_footer = VersionedFooter::V4 {
             crc32,
             store_compression: "lz4".to_string(),
         };
         let footer = Footer::new(versioned_footer);
-        let res = footer.is_compatible();
-        assert!(res.is_err());
+        assert!(footer.is_compatible().is_ok());
     }
 
     #[test]
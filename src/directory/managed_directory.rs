@@ -10,7 +10,6 @@ use crate::directory::{WatchCallback, WatchHandle};
 use crate::error::DataCorruption;
 use crate::Directory;
 
-use crc32fast::Hasher;
 use std::collections::HashSet;
 use std::io;
 use std::io::Write;
@@ -212,7 +211,7 @@ impl ManagedDirectory {
     /// File starting by "." are reserved to locks.
     /// They are not managed and cannot be subjected
     /// to garbage collection.
-    fn register_file_as_managed(&self, filepath: &Path) -> io::Result<()> {
+    pub(crate) fn register_file_as_managed(&self, filepath: &Path) -> io::Result<()> {
         // Files starting by "." (e.g. lock files) are not managed.
         if !is_managed(filepath) {
             return Ok(());
@@ -231,25 +230,27 @@ impl ManagedDirectory {
     /// Verify checksum of a managed file
     pub fn validate_checksum(&self, path: &Path) -> result::Result<bool, OpenReadError> {
         let reader = self.directory.open_read(path)?;
-        let (footer, data) =
-            Footer::extract_footer(reader).map_err(|io_error| OpenReadError::IOError {
-                io_error,
-                filepath: path.to_path_buf(),
-            })?;
-        let bytes = data
-            .read_bytes()
-            .map_err(|io_error| OpenReadError::IOError {
-                filepath: path.to_path_buf(),
-                io_error,
-            })?;
-        let mut hasher = Hasher::new();
-        hasher.update(bytes.as_slice());
-        let crc = hasher.finalize();
-        Ok(footer
-            .versioned_footer
-            .crc()
-            .map(|v| v == crc)
-            .unwrap_or(false))
+        Footer::is_checksum_valid(reader).map_err(|io_error| OpenReadError::IOError {
+            io_error,
+            filepath: path.to_path_buf(),
+        })
+    }
+
+    /// Opens a managed file for read, including its footer.
+    pub(crate) fn open_read_with_footer(
+        &self,
+        path: &Path,
+    ) -> result::Result<FileSlice, OpenReadError> {
+        self.directory.open_read(path)
+    }
+
+    /// Returns the files that were created by tantivy and not deleted yet.
+    pub(crate) fn list_managed_files(&self) -> HashSet<PathBuf> {
+        self.meta_informations
+            .read()
+            .expect("Managed directory rlock poisoned in list managed files.")
+            .managed_paths
+            .clone()
     }
 
     /// List files for which checksum does not match content
@@ -316,6 +317,10 @@ impl Directory for ManagedDirectory {
         self.directory.exists(path)
     }
 
+    fn local_path(&self, path: &Path) -> Option<PathBuf> {
+        self.directory.local_path(path)
+    }
+
     fn acquire_lock(&self, lock: &Lock) -> result::Result<DirectoryLock, LockError> {
         self.directory.acquire_lock(lock)
     }
@@ -396,6 +396,10 @@ impl Directory for MmapDirectory {
         Ok(full_path.exists())
     }
 
+    fn local_path(&self, path: &Path) -> Option<PathBuf> {
+        Some(self.resolve_path(path))
+    }
+
     fn open_write(&self, path: &Path) -> Result<WritePtr, OpenWriteError> {
         debug!("Open Write {:?}", path);
         let full_path = self.resolve_path(path);
@@ -615,7 +619,7 @@ mod tests {
             let num_segments = reader.searcher().segment_readers().len();
             assert!(num_segments <= 4);
             assert_eq!(
-                num_segments * 7,
+                num_segments * 8,
                 mmap_directory.get_cache_info().mmapped.len()
             );
         }
@@ -15,6 +15,8 @@ mod footer;
 mod managed_directory;
 mod owned_bytes;
 mod ram_directory;
+mod range_reader_directory;
+mod tar;
 mod watch_event_router;
 
 /// Errors specific to the directory module.
@@ -25,8 +27,12 @@ pub use self::directory::{Directory, DirectoryClone};
 pub use self::directory_lock::{Lock, INDEX_WRITER_LOCK, META_LOCK};
 pub(crate) use self::file_slice::{ArcBytes, WeakArcBytes};
 pub use self::file_slice::{FileHandle, FileSlice};
+#[cfg(test)]
+pub(crate) use self::footer::rewrite_in_index_format_v3;
+pub(crate) use self::footer::{Footer, VersionedFooter};
 pub use self::owned_bytes::OwnedBytes;
 pub use self::ram_directory::RAMDirectory;
+pub use self::range_reader_directory::{AsyncRangeReader, RangeReaderDirectory};
 pub use self::watch_event_router::{WatchCallback, WatchCallbackList, WatchHandle};
 use std::io::{self, BufWriter, Write};
 use std::path::PathBuf;
@@ -84,7 +90,6 @@ impl<W: TerminatingWrite> TerminatingWrite for BufWriter<W> {
     }
 }
 
-#[cfg(test)]
 impl<'a> TerminatingWrite for &'a mut Vec<u8> {
     fn terminate_ref(&mut self, _a: AntiCallToken) -> io::Result<()> {
         self.flush()
@@ -12,6 +12,7 @@ use std::path::{Path, PathBuf};
 use std::result;
 use std::sync::{Arc, RwLock};
 
+use super::tar;
 use super::FileHandle;
 
 /// Writer associated with the `RAMDirectory`
@@ -130,6 +131,9 @@ impl fmt::Debug for RAMDirectory {
 /// It is mainly meant for unit testing.
 /// Writes are only made visible upon flushing.
 ///
+/// It is also the directory to use on platforms without a file system,
+/// like WebAssembly: the files of an index can be loaded from a tar archive
+/// with [`RAMDirectory::from_tar`](#method.from_tar).
 #[derive(Clone, Default)]
 pub struct RAMDirectory {
     fs: Arc<RwLock<InnerDirectory>>,
@@ -162,6 +166,36 @@ impl RAMDirectory {
         }
         Ok(())
     }
+
+    /// Creates a `RAMDirectory` containing the files of the tar archive `data`.
+    ///
+    /// Only the regular files of the archive are loaded, and their path is reduced
+    /// to their file name. The archive of an index directory, as built by
+    /// `tar -cf index.tar -C my_index .` or by [`to_tar`](#method.to_tar), can be loaded.
+    pub fn from_tar(data: &[u8]) -> io::Result<RAMDirectory> {
+        let directory = RAMDirectory::create();
+        {
+            let mut wlock = directory.fs.write().unwrap();
+            for (path, content) in tar::read_tar(data)? {
+                wlock.write(path, content);
+            }
+        }
+        Ok(directory)
+    }
+
+    /// Serializes all of the files saved in the `RAMDirectory` as a tar archive.
+    ///
+    /// The files are sorted by path, so that the archive is deterministic.
+    pub fn to_tar(&self) -> crate::Result<Vec<u8>> {
+        let rlock = self.fs.read().unwrap();
+        let mut files = Vec::with_capacity(rlock.fs.len());
+        for (path, file) in rlock.fs.iter() {
+            files.push((path.as_path(), file.read_bytes()?));
+        }
+        files.sort_by_key(|(path, _)| *path);
+        let archive = tar::write_tar(files.iter().map(|(path, bytes)| (*path, bytes.as_slice())))?;
+        Ok(archive)
+    }
 }
 
 impl Directory for RAMDirectory {
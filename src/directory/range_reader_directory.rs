@@ -0,0 +1,395 @@
+use crate::common::HasLen;
+use crate::directory::error::{DeleteError, LockError, OpenReadError, OpenWriteError};
+use crate::directory::{
+    Directory, DirectoryLock, FileHandle, Lock, OwnedBytes, WatchCallback, WatchHandle, WritePtr,
+    INDEX_WRITER_LOCK,
+};
+use futures::executor::block_on;
+use futures::future::BoxFuture;
+use lru::LruCache;
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+
+const DEFAULT_BLOCK_SIZE: usize = 64 * 1024;
+const DEFAULT_CACHE_NUM_BLOCKS: usize = 1_024;
+
+/// Asynchronous access to byte ranges of remote files.
+///
+/// This is the abstraction over the storage used by the
+/// [`RangeReaderDirectory`](./struct.RangeReaderDirectory.html).
+/// It is typically implemented with HTTP range requests,
+/// e.g. against an object storage like S3 or GCS.
+pub trait AsyncRangeReader: Send + Sync + 'static {
+    /// Returns the length of the file `path`.
+    ///
+    /// If the file does not exist, the error should be of kind `io::ErrorKind::NotFound`.
+    fn file_len<'a>(&'a self, path: &'a Path) -> BoxFuture<'a, io::Result<usize>>;
+
+    /// Reads the bytes of the file `path` within `range`.
+    fn read_range<'a>(
+        &'a self,
+        path: &'a Path,
+        range: Range<usize>,
+    ) -> BoxFuture<'a, io::Result<Vec<u8>>>;
+}
+
+fn read_only_error() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::PermissionDenied,
+        "The RangeReaderDirectory is read-only.",
+    )
+}
+
+struct InnerRangeReaderDirectory {
+    range_reader: Box<dyn AsyncRangeReader>,
+    block_size: usize,
+    block_cache: Mutex<LruCache<(PathBuf, usize), OwnedBytes>>,
+    file_lens: RwLock<HashMap<PathBuf, usize>>,
+}
+
+impl InnerRangeReaderDirectory {
+    fn file_len(&self, path: &Path) -> io::Result<usize> {
+        if let Some(&len) = self.file_lens.read().unwrap().get(path) {
+            return Ok(len);
+        }
+        let len = block_on(self.range_reader.file_len(path))?;
+        self.file_lens
+            .write()
+            .unwrap()
+            .insert(path.to_path_buf(), len);
+        Ok(len)
+    }
+
+    /// Fetches the blocks `block_ids` of the file in a single range request,
+    /// and adds them to the cache.
+    fn fetch_blocks(
+        &self,
+        path: &Path,
+        file_len: usize,
+        block_ids: Range<usize>,
+    ) -> io::Result<Vec<OwnedBytes>> {
+        let start = block_ids.start * self.block_size;
+        let end = (block_ids.end * self.block_size).min(file_len);
+        let data = block_on(self.range_reader.read_range(path, start..end))?;
+        if data.len() != end - start {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!(
+                    "Expected {} bytes for the range {:?} of {:?}, got {}.",
+                    end - start,
+                    start..end,
+                    path,
+                    data.len()
+                ),
+            ));
+        }
+        let data = OwnedBytes::new(data);
+        let mut block_cache = self.block_cache.lock().unwrap();
+        let blocks: Vec<OwnedBytes> = block_ids
+            .map(|block_id| {
+                let block_start = block_id * self.block_size - start;
+                let block_end = ((block_id + 1) * self.block_size).min(file_len) - start;
+                let block = data.slice(block_start, block_end);
+                block_cache.put((path.to_path_buf(), block_id), block.clone());
+                block
+            })
+            .collect();
+        Ok(blocks)
+    }
+
+    fn read_bytes(
+        &self,
+        path: &Path,
+        file_len: usize,
+        range: Range<usize>,
+    ) -> io::Result<OwnedBytes> {
+        if range.start == range.end {
+            return Ok(OwnedBytes::empty());
+        }
+        let first_block = range.start / self.block_size;
+        let last_block = (range.end - 1) / self.block_size;
+        let mut blocks: Vec<Option<OwnedBytes>> = {
+            let mut block_cache = self.block_cache.lock().unwrap();
+            (first_block..=last_block)
+                .map(|block_id| block_cache.get(&(path.to_path_buf(), block_id)).cloned())
+                .collect()
+        };
+        // Contiguous missing blocks are fetched with a single request.
+        let mut i = 0;
+        while i < blocks.len() {
+            if blocks[i].is_some() {
+                i += 1;
+                continue;
+            }
+            let mut j = i;
+            while j < blocks.len() && blocks[j].is_none() {
+                j += 1;
+            }
+            let fetched_blocks =
+                self.fetch_blocks(path, file_len, first_block + i..first_block + j)?;
+            for (block, fetched_block) in blocks[i..j].iter_mut().zip(fetched_blocks) {
+                *block = Some(fetched_block);
+            }
+            i = j;
+        }
+        let offset = range.start - first_block * self.block_size;
+        let len = range.end - range.start;
+        if let [Some(block)] = &blocks[..] {
+            return Ok(block.slice(offset, offset + len));
+        }
+        let mut data = Vec::with_capacity(blocks.len() * self.block_size);
+        for block in blocks.into_iter().flatten() {
+            data.extend_from_slice(block.as_slice());
+        }
+        Ok(OwnedBytes::new(data).slice(offset, offset + len))
+    }
+}
+
+struct RangeReaderFileHandle {
+    directory: Arc<InnerRangeReaderDirectory>,
+    path: PathBuf,
+    len: usize,
+}
+
+impl HasLen for RangeReaderFileHandle {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl FileHandle for RangeReaderFileHandle {
+    fn read_bytes(&self, from: usize, to: usize) -> io::Result<OwnedBytes> {
+        self.directory.read_bytes(&self.path, self.len, from..to)
+    }
+}
+
+/// Read-only `Directory` fetching the byte ranges of the files it needs
+/// through an [`AsyncRangeReader`](./trait.AsyncRangeReader.html).
+///
+/// It makes it possible to search an index stored remotely, for instance
+/// in an object storage, without downloading it first.
+///
+/// The files are split in blocks of 64KB by default. The blocks that are read
+/// are kept in an LRU cache, so that the frequently accessed parts of the index,
+/// like the headers of the term dictionaries or the skip lists of the postings,
+/// are only fetched once. Contiguous missing blocks are fetched with a single request.
+///
+/// `tantivy`'s reads are synchronous: the futures of the `AsyncRangeReader` are
+/// driven with `futures::executor::block_on`. The searches should therefore not run
+/// on the threads of an asynchronous runtime.
+///
+/// The `IndexReader` should be created with `ReloadPolicy::Manual`: the files are
+/// never watched.
+#[derive(Clone)]
+pub struct RangeReaderDirectory {
+    inner: Arc<InnerRangeReaderDirectory>,
+}
+
+impl RangeReaderDirectory {
+    /// Creates a `RangeReaderDirectory` with blocks of 64KB and a cache of 1024 blocks.
+    pub fn new<R: AsyncRangeReader>(range_reader: R) -> RangeReaderDirectory {
+        RangeReaderDirectory::with_cache(range_reader, DEFAULT_BLOCK_SIZE, DEFAULT_CACHE_NUM_BLOCKS)
+    }
+
+    /// Creates a `RangeReaderDirectory`, fetching blocks of `block_size` bytes,
+    /// and keeping at most `cache_num_blocks` of them in its cache.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `block_size` is 0.
+    pub fn with_cache<R: AsyncRangeReader>(
+        range_reader: R,
+        block_size: usize,
+        cache_num_blocks: usize,
+    ) -> RangeReaderDirectory {
+        assert!(block_size > 0, "The block size must be positive.");
+        RangeReaderDirectory {
+            inner: Arc::new(InnerRangeReaderDirectory {
+                range_reader: Box::new(range_reader),
+                block_size,
+                block_cache: Mutex::new(LruCache::new(cache_num_blocks)),
+                file_lens: Default::default(),
+            }),
+        }
+    }
+
+    fn file_len(&self, path: &Path) -> Result<usize, OpenReadError> {
+        self.inner.file_len(path).map_err(|io_error| {
+            if io_error.kind() == io::ErrorKind::NotFound {
+                OpenReadError::FileDoesNotExist(path.to_path_buf())
+            } else {
+                OpenReadError::wrap_io_error(io_error, path.to_path_buf())
+            }
+        })
+    }
+}
+
+impl fmt::Debug for RangeReaderDirectory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RangeReaderDirectory")
+    }
+}
+
+impl Directory for RangeReaderDirectory {
+    fn get_file_handle(&self, path: &Path) -> Result<Box<dyn FileHandle>, OpenReadError> {
+        let len = self.file_len(path)?;
+        Ok(Box::new(RangeReaderFileHandle {
+            directory: self.inner.clone(),
+            path: path.to_path_buf(),
+            len,
+        }))
+    }
+
+    fn delete(&self, path: &Path) -> Result<(), DeleteError> {
+        Err(DeleteError::IOError {
+            io_error: read_only_error(),
+            filepath: path.to_path_buf(),
+        })
+    }
+
+    fn exists(&self, path: &Path) -> Result<bool, OpenReadError> {
+        match self.file_len(path) {
+            Ok(_) => Ok(true),
+            Err(OpenReadError::FileDoesNotExist(_)) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn open_write(&self, path: &Path) -> Result<WritePtr, OpenWriteError> {
+        Err(OpenWriteError::wrap_io_error(
+            read_only_error(),
+            path.to_path_buf(),
+        ))
+    }
+
+    fn atomic_read(&self, path: &Path) -> Result<Vec<u8>, OpenReadError> {
+        // `meta.json` is read directly and not cached,
+        // so that reloading the reader sees new commits.
+        let len = match block_on(self.inner.range_reader.file_len(path)) {
+            Ok(len) => len,
+            Err(io_error) if io_error.kind() == io::ErrorKind::NotFound => {
+                return Err(OpenReadError::FileDoesNotExist(path.to_path_buf()));
+            }
+            Err(io_error) => {
+                return Err(OpenReadError::wrap_io_error(io_error, path.to_path_buf()))
+            }
+        };
+        block_on(self.inner.range_reader.read_range(path, 0..len))
+            .map_err(|io_error| OpenReadError::wrap_io_error(io_error, path.to_path_buf()))
+    }
+
+    fn atomic_write(&self, _path: &Path, _data: &[u8]) -> io::Result<()> {
+        Err(read_only_error())
+    }
+
+    fn acquire_lock(&self, lock: &Lock) -> Result<DirectoryLock, LockError> {
+        if lock.filepath == INDEX_WRITER_LOCK.filepath {
+            return Err(LockError::IOError(read_only_error()));
+        }
+        // Nothing can be deleted from a read-only directory,
+        // so the other locks are not needed.
+        Ok(DirectoryLock::from(Box::new(())))
+    }
+
+    fn watch(&self, _watch_callback: WatchCallback) -> crate::Result<WatchHandle> {
+        Ok(WatchHandle::empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AsyncRangeReader, RangeReaderDirectory};
+    use crate::collector::{Count, TopDocs};
+    use crate::common::HasLen;
+    use crate::directory::error::OpenReadError;
+    use crate::directory::{Directory, RAMDirectory};
+    use crate::query::QueryParser;
+    use crate::schema::{Schema, STORED, TEXT};
+    use crate::{Index, ReloadPolicy};
+    use futures::future::{self, BoxFuture};
+    use std::io;
+    use std::ops::Range;
+    use std::path::Path;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[derive(Clone)]
+    struct CountingRangeReader {
+        directory: RAMDirectory,
+        num_requests: Arc<AtomicUsize>,
+    }
+
+    impl AsyncRangeReader for CountingRangeReader {
+        fn file_len<'a>(&'a self, path: &'a Path) -> BoxFuture<'a, io::Result<usize>> {
+            let len = self.directory.open_read(path).map(|file| file.len());
+            Box::pin(future::ready(len.map_err(|err| match err {
+                OpenReadError::FileDoesNotExist(_) => io::ErrorKind::NotFound.into(),
+                _ => io::ErrorKind::Other.into(),
+            })))
+        }
+
+        fn read_range<'a>(
+            &'a self,
+            path: &'a Path,
+            range: Range<usize>,
+        ) -> BoxFuture<'a, io::Result<Vec<u8>>> {
+            self.num_requests.fetch_add(1, Ordering::SeqCst);
+            let data = self
+                .directory
+                .open_read(path)
+                .map_err(|_| io::Error::from(io::ErrorKind::NotFound))
+                .and_then(|file| file.read_bytes_slice(range.start, range.end))
+                .map(|bytes| bytes.as_slice().to_vec());
+            Box::pin(future::ready(data))
+        }
+    }
+
+    #[test]
+    fn test_range_reader_directory() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT | STORED);
+        let ram_directory = RAMDirectory::create();
+        let index = Index::create(ram_directory.clone(), schema_builder.build())?;
+        let mut index_writer = index.writer_for_tests()?;
+        for i in 0..1_000 {
+            index_writer.add_document(doc!(title => format!("document {}", i)));
+        }
+        index_writer.add_document(doc!(title => "needle"));
+        index_writer.commit()?;
+        drop(index_writer);
+
+        let range_reader = CountingRangeReader {
+            directory: ram_directory,
+            num_requests: Arc::new(AtomicUsize::new(0)),
+        };
+        let directory = RangeReaderDirectory::with_cache(range_reader.clone(), 1_024, 1_024);
+        let remote_index = Index::open(directory.clone())?;
+        assert!(remote_index.writer_for_tests().is_err());
+        assert!(!directory.exists(Path::new("missing"))?);
+        let reader = remote_index
+            .reader_builder()
+            .reload_policy(ReloadPolicy::Manual)
+            .try_into()?;
+        let searcher = reader.searcher();
+        let query = QueryParser::for_index(&remote_index, vec![title]).parse_query("needle")?;
+        let top_docs = searcher.search(&query, &TopDocs::with_limit(1))?;
+        assert_eq!(top_docs.len(), 1);
+        let doc = searcher.doc(top_docs[0].1)?;
+        assert_eq!(doc.get_first(title).unwrap().text(), Some("needle"));
+        assert_eq!(searcher.search(&query, &Count)?, 1);
+
+        // The blocks read by the first query are served from the cache.
+        let num_requests = range_reader.num_requests.load(Ordering::SeqCst);
+        assert!(num_requests > 0);
+        assert_eq!(searcher.search(&query, &Count)?, 1);
+        assert_eq!(
+            range_reader.num_requests.load(Ordering::SeqCst),
+            num_requests
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,147 @@
+//! Minimal reader and writer of `ustar` archives.
+//!
+//! Index directories are flat, so only regular files are supported.
+//! Directory entries are skipped, and the path of the files is reduced
+//! to their file name: an archive of `my_index/` or of `./` can both
+//! be read.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+const BLOCK_LEN: usize = 512;
+const NAME_LEN: usize = 100;
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
+
+fn parse_octal(field: &[u8]) -> io::Result<u64> {
+    let digits = std::str::from_utf8(field)
+        .map_err(|_| invalid_data("Invalid number in tar header."))?
+        .trim_matches(|c: char| c == '\0' || c == ' ');
+    if digits.is_empty() {
+        return Ok(0);
+    }
+    u64::from_str_radix(digits, 8).map_err(|_| invalid_data("Invalid number in tar header."))
+}
+
+fn write_octal(field: &mut [u8], val: u64) {
+    // The last byte is left as a NUL terminator.
+    let num_digits = field.len() - 1;
+    let digits = format!("{:0width$o}", val, width = num_digits);
+    field[..num_digits].copy_from_slice(digits.as_bytes());
+}
+
+fn checksum(header: &[u8]) -> u64 {
+    header
+        .iter()
+        .enumerate()
+        .map(|(i, &b)| if (148..156).contains(&i) { b' ' } else { b })
+        .map(u64::from)
+        .sum()
+}
+
+/// Returns the files of the tar archive `data`, as `(file name, content)` pairs.
+pub(crate) fn read_tar(data: &[u8]) -> io::Result<Vec<(PathBuf, &[u8])>> {
+    let mut files = Vec::new();
+    let mut offset = 0;
+    while offset + BLOCK_LEN <= data.len() {
+        let header = &data[offset..offset + BLOCK_LEN];
+        if header.iter().all(|&b| b == 0) {
+            // End of archive.
+            break;
+        }
+        if parse_octal(&header[148..156])? != checksum(header) {
+            return Err(invalid_data("Invalid tar header checksum."));
+        }
+        let size = parse_octal(&header[124..136])? as usize;
+        let start = offset + BLOCK_LEN;
+        let end = start + size;
+        if end > data.len() {
+            return Err(invalid_data("Truncated tar archive."));
+        }
+        // Regular files. Directories, links and pax headers are skipped.
+        if header[156] == b'0' || header[156] == 0 {
+            let name_end = header[..NAME_LEN]
+                .iter()
+                .position(|&b| b == 0)
+                .unwrap_or(NAME_LEN);
+            let name = std::str::from_utf8(&header[..name_end])
+                .map_err(|_| invalid_data("Invalid file name in tar header."))?;
+            let file_name = Path::new(name)
+                .file_name()
+                .ok_or_else(|| invalid_data("Invalid file name in tar header."))?;
+            files.push((PathBuf::from(file_name), &data[start..end]));
+        }
+        let padding = (BLOCK_LEN - size % BLOCK_LEN) % BLOCK_LEN;
+        offset = end + padding;
+    }
+    Ok(files)
+}
+
+/// Serializes the files `(file name, content)` as a tar archive.
+pub(crate) fn write_tar<'a>(
+    files: impl Iterator<Item = (&'a Path, &'a [u8])>,
+) -> io::Result<Vec<u8>> {
+    let mut archive = Vec::new();
+    for (path, content) in files {
+        let name = path.to_str().map(str::as_bytes).unwrap_or_default();
+        if name.is_empty() || name.len() > NAME_LEN {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Cannot archive the file {:?}.", path),
+            ));
+        }
+        let mut header = [0u8; BLOCK_LEN];
+        header[..name.len()].copy_from_slice(name);
+        write_octal(&mut header[100..108], 0o644);
+        write_octal(&mut header[108..116], 0);
+        write_octal(&mut header[116..124], 0);
+        write_octal(&mut header[124..136], content.len() as u64);
+        write_octal(&mut header[136..148], 0);
+        header[156] = b'0';
+        header[257..263].copy_from_slice(b"ustar\0");
+        header[263..265].copy_from_slice(b"00");
+        let header_checksum = checksum(&header);
+        write_octal(&mut header[148..155], header_checksum);
+        header[155] = b' ';
+        archive.extend_from_slice(&header);
+        archive.extend_from_slice(content);
+        let padding = (BLOCK_LEN - content.len() % BLOCK_LEN) % BLOCK_LEN;
+        archive.resize(archive.len() + padding, 0u8);
+    }
+    archive.resize(archive.len() + 2 * BLOCK_LEN, 0u8);
+    Ok(archive)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_tar, write_tar, BLOCK_LEN};
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn test_tar_round_trip() {
+        let files: Vec<(&Path, &[u8])> = vec![
+            (Path::new("meta.json"), b"{}"),
+            (Path::new("empty"), b""),
+            (Path::new("block"), &[1u8; BLOCK_LEN]),
+        ];
+        let archive = write_tar(files.iter().cloned()).unwrap();
+        assert_eq!(archive.len(), 7 * BLOCK_LEN);
+        let read_files = read_tar(&archive).unwrap();
+        let expected: Vec<(PathBuf, &[u8])> = files
+            .into_iter()
+            .map(|(path, content)| (path.to_path_buf(), content))
+            .collect();
+        assert_eq!(read_files, expected);
+    }
+
+    #[test]
+    fn test_tar_strips_directories() {
+        let mut archive =
+            write_tar(vec![(Path::new("index/meta.json"), &b"{}"[..])].into_iter()).unwrap();
+        assert_eq!(read_tar(&archive).unwrap()[0].0, PathBuf::from("meta.json"));
+        archive[0] = b'j';
+        assert!(read_tar(&archive).is_err());
+    }
+}
@@ -111,6 +111,15 @@ pub trait DocSet: Send {
         }
         count
     }
+
+    /// Returns an estimate of the number of documents of the `DocSet` that
+    /// were skipped by `.seek(...)` without being visited.
+    ///
+    /// This is only meant for profiling. `DocSet`s that do not track it return 0,
+    /// and composite `DocSet`s return the sum over their underlying `DocSet`s.
+    fn docs_skipped(&self) -> u64 {
+        0u64
+    }
 }
 
 impl<'a> DocSet for &'a mut dyn DocSet {
@@ -137,6 +146,10 @@ impl<'a> DocSet for &'a mut dyn DocSet {
     fn count_including_deleted(&mut self) -> u32 {
         (**self).count_including_deleted()
     }
+
+    fn docs_skipped(&self) -> u64 {
+        (**self).docs_skipped()
+    }
 }
 
 impl<TDocSet: DocSet + ?Sized> DocSet for Box<TDocSet> {
@@ -169,4 +182,9 @@ impl<TDocSet: DocSet + ?Sized> DocSet for Box<TDocSet> {
         let unboxed: &mut TDocSet = self.borrow_mut();
         unboxed.count_including_deleted()
     }
+
+    fn docs_skipped(&self) -> u64 {
+        let unboxed: &TDocSet = self.borrow();
+        unboxed.docs_skipped()
+    }
 }
@@ -92,6 +92,9 @@ pub enum TantivyError {
     /// Index incompatible with current version of tantivy
     #[error("{0:?}")]
     IncompatibleIndex(Incompatibility),
+    /// The operation was cancelled, e.g. by a `CancellationToken`.
+    #[error("The operation was cancelled")]
+    Cancelled,
 }
 
 impl From<DataCorruption> for TantivyError {
@@ -40,6 +40,11 @@ impl BytesFastFieldWriter {
         self.field
     }
 
+    /// Returns the number of bytes used to buffer the values.
+    pub fn mem_usage(&self) -> usize {
+        self.vals.capacity() + self.doc_index.capacity() * std::mem::size_of::<u64>()
+    }
+
     /// Finalize the current document.
     pub(crate) fn next_doc(&mut self) {
         self.doc_index.push(self.vals.len() as u64);
@@ -0,0 +1,196 @@
+use crate::common::{convert_composite_file, BinarySerializable};
+use crate::directory::OwnedBytes;
+use crate::error::DataCorruption;
+use crate::schema::{Field, FieldType, Schema};
+use std::io::{self, Write};
+
+/// Version of the format of the fast field columns.
+///
+/// It is stored in the footer of each column, so that readers
+/// can reject the columns written in a format they do not know about.
+///
+/// Version 2 introduced optional fast fields: their column of values is followed
+/// by a null bitmap, and the values of the documents without a value are meaningless.
+/// Only these columns are written with version 2, so that the other columns remain
+/// readable by the previous versions of tantivy.
+const FAST_FIELD_FORMAT_VERSION: u8 = NULLABLE_FORMAT_VERSION;
+
+/// Version of the columns of the fast fields that are not optional.
+pub(crate) const BASE_FORMAT_VERSION: u8 = 1;
+
+/// Version of the columns of the optional fast fields.
+pub(crate) const NULLABLE_FORMAT_VERSION: u8 = 2;
+
+/// Number of bytes of the footer of a fast field column.
+const FOOTER_NUM_BYTES: usize = 2;
+
+/// Codec used to encode the values of a fast field column.
+///
+/// The codec is selected automatically when the column is serialized,
+/// depending on the distribution of its values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FastFieldCodec {
+    /// `val - min_value` is bitpacked, using the number of bits
+    /// required to encode `max_value - min_value`.
+    Bitpacked,
+    /// Values are delta-encoded, and the deltas are zigzag-encoded,
+    /// before getting bitpacked by blocks of 128 values.
+    ///
+    /// This codec is selected for the columns in which consecutive values are
+    /// close to each other, but spread over a large range, and only if it
+    /// makes the column at least twice as small: accessing a single value
+    /// requires to sum up to 7 deltas, which is slower than with `Bitpacked`.
+    DeltaZigzag,
+}
+
+impl FastFieldCodec {
+    fn code(self) -> u8 {
+        match self {
+            FastFieldCodec::Bitpacked => 0,
+            FastFieldCodec::DeltaZigzag => 1,
+        }
+    }
+
+    fn from_code(code: u8) -> Option<FastFieldCodec> {
+        match code {
+            0 => Some(FastFieldCodec::Bitpacked),
+            1 => Some(FastFieldCodec::DeltaZigzag),
+            _ => None,
+        }
+    }
+}
+
+/// Writes the footer of a fast field column.
+pub(crate) fn write_footer<W: Write>(
+    codec: FastFieldCodec,
+    version: u8,
+    write: &mut W,
+) -> io::Result<()> {
+    debug_assert!(version <= FAST_FIELD_FORMAT_VERSION);
+    codec.code().serialize(write)?;
+    version.serialize(write)
+}
+
+/// Splits a fast field column into its body, and the codec and the format version
+/// stored in its footer.
+pub(crate) fn read_footer(bytes: OwnedBytes) -> crate::Result<(OwnedBytes, FastFieldCodec, u8)> {
+    if bytes.len() < FOOTER_NUM_BYTES {
+        return Err(DataCorruption::comment_only("Fast field column is too short.").into());
+    }
+    let body_len = bytes.len() - FOOTER_NUM_BYTES;
+    let (body, footer) = bytes.split(body_len);
+    let (codec_code, version) = (footer.as_slice()[0], footer.as_slice()[1]);
+    if version > FAST_FIELD_FORMAT_VERSION {
+        return Err(DataCorruption::comment_only(format!(
+            "Unsupported fast field format version {} (this version of tantivy supports up to \
+             version {}).",
+            version, FAST_FIELD_FORMAT_VERSION
+        ))
+        .into());
+    }
+    let codec = FastFieldCodec::from_code(codec_code).ok_or_else(|| {
+        DataCorruption::comment_only(format!("Unknown fast field codec {}.", codec_code))
+    })?;
+    Ok((body, codec, version))
+}
+
+/// Returns true if the field `idx` of the fast field `field` is a column of `u64`
+/// values, which has a footer, in an index written before the index format 4.
+///
+/// The values of the bytes fast fields are stored as they are, after the column
+/// of their offsets.
+fn is_u64_column_in_index_format_v3(schema: &Schema, field: Field, idx: usize) -> bool {
+    !(idx == 1
+        && matches!(
+            schema.get_field_entry(field).field_type(),
+            FieldType::Bytes(_)
+        ))
+}
+
+/// Converts a fast field file written before the index format 4, whose
+/// columns were all bitpacked and did not have a footer, to the current format.
+pub(crate) fn upgrade_from_index_format_v3(file: &[u8], schema: &Schema) -> io::Result<Vec<u8>> {
+    convert_composite_file(file, |field, idx, column| {
+        let mut upgraded = column.to_vec();
+        if is_u64_column_in_index_format_v3(schema, field, idx) {
+            write_footer(
+                FastFieldCodec::Bitpacked,
+                BASE_FORMAT_VERSION,
+                &mut upgraded,
+            )?;
+        }
+        Ok(upgraded)
+    })
+}
+
+/// Converts a fast field file to the format used before the index format 4.
+///
+/// Fails if one of the columns is not bitpacked, or is optional, as the older
+/// formats cannot express it.
+#[cfg(test)]
+pub(crate) fn downgrade_to_index_format_v3(file: &[u8], schema: &Schema) -> io::Result<Vec<u8>> {
+    convert_composite_file(file, |field, idx, column| {
+        if !is_u64_column_in_index_format_v3(schema, field, idx) {
+            return Ok(column.to_vec());
+        }
+        let body_len = column.len().saturating_sub(FOOTER_NUM_BYTES);
+        let mut footer = [0u8; FOOTER_NUM_BYTES];
+        write_footer(
+            FastFieldCodec::Bitpacked,
+            BASE_FORMAT_VERSION,
+            &mut &mut footer[..],
+        )?;
+        if column[body_len..] != footer {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Only the bitpacked columns can be written in the index format 3.",
+            ));
+        }
+        Ok(column[..body_len].to_vec())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_footer, write_footer, FastFieldCodec};
+    use super::{BASE_FORMAT_VERSION, NULLABLE_FORMAT_VERSION};
+    use crate::directory::OwnedBytes;
+    use crate::TantivyError;
+
+    #[test]
+    fn test_footer() -> crate::Result<()> {
+        let mut buffer = vec![1u8, 2u8, 3u8];
+        write_footer(
+            FastFieldCodec::DeltaZigzag,
+            BASE_FORMAT_VERSION,
+            &mut buffer,
+        )?;
+        let (body, codec, version) = read_footer(OwnedBytes::new(buffer))?;
+        assert_eq!(body.as_slice(), &[1u8, 2u8, 3u8]);
+        assert_eq!(codec, FastFieldCodec::DeltaZigzag);
+        assert_eq!(version, BASE_FORMAT_VERSION);
+        let mut buffer = vec![];
+        write_footer(
+            FastFieldCodec::Bitpacked,
+            NULLABLE_FORMAT_VERSION,
+            &mut buffer,
+        )?;
+        let (_, _, version) = read_footer(OwnedBytes::new(buffer))?;
+        assert_eq!(version, NULLABLE_FORMAT_VERSION);
+        Ok(())
+    }
+
+    #[test]
+    fn test_footer_rejects_unknown_version_and_codec() {
+        let unknown_version = OwnedBytes::new(vec![1u8, 0u8, 3u8]);
+        assert!(matches!(
+            read_footer(unknown_version),
+            Err(TantivyError::DataCorruption(_))
+        ));
+        let unknown_codec = OwnedBytes::new(vec![1u8, 7u8, 1u8]);
+        assert!(matches!(
+            read_footer(unknown_codec),
+            Err(TantivyError::DataCorruption(_))
+        ));
+    }
+}
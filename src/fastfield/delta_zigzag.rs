@@ -0,0 +1,310 @@
+//! The `DeltaZigzag` fast field codec.
+//!
+//! Values are split into blocks of 128 values, and blocks into sub-blocks of 8 values.
+//! For each block, the first value is stored as is, and the following values
+//! are stored as the zigzag-encoded difference with the value that precedes them.
+//! The first value of each sub-block is also stored as a checkpoint: the
+//! zigzag-encoded difference with the first value of the block.
+//! The checkpoints and the deltas of a block are then bitpacked.
+//!
+//! The column layout is the following:
+//! - the number of values, as a `u64`
+//! - the meta of each block: its first value and the offset of its bitpacked data
+//!   as `u64`s, and the numbers of bits used to bitpack its deltas and its checkpoints.
+//! - the bitpacked data of all of the blocks: for each block, the checkpoints of its
+//!   sub-blocks but the first one, followed by its deltas but the first one. The
+//!   checkpoints of the missing sub-blocks of the last block are 0.
+//!
+//! Accessing a value requires to sum the deltas of the values that precede it
+//! in its sub-block, that is at most 7 deltas.
+
+use crate::common::bitpacker::BitPacker;
+use crate::common::{compute_num_bits, BinarySerializable, FixedSize};
+use crate::directory::OwnedBytes;
+use crate::fastfield::FastValue;
+use byteorder::{ByteOrder, LittleEndian};
+use std::io::{self, Write};
+
+const BLOCK_LEN: usize = 128;
+const SUB_BLOCK_LEN: usize = 8;
+// The first sub-block of a block has no checkpoint.
+const NUM_CHECKPOINTS: usize = BLOCK_LEN / SUB_BLOCK_LEN - 1;
+// The first value and the offset, followed by the numbers of bits of the deltas
+// and of the checkpoints, one byte each.
+const BLOCK_META_NUM_BYTES: usize = 2 * u64::SIZE_IN_BYTES + 2;
+
+fn zigzag_encode(delta: i64) -> u64 {
+    ((delta << 1) ^ (delta >> 63)) as u64
+}
+
+fn zigzag_decode(zigzag: u64) -> i64 {
+    ((zigzag >> 1) as i64) ^ -((zigzag & 1) as i64)
+}
+
+/// Zigzag-encoded deltas and checkpoints of a block.
+struct EncodedBlock {
+    // `deltas[i]` is the delta of the value `i + 1` of the block.
+    deltas: Vec<u64>,
+    // `checkpoints[i]` is the checkpoint of the sub-block `i + 1` of the block.
+    // The last block gets 0 checkpoints for its missing sub-blocks, so that all
+    // of the blocks have the same number of checkpoints.
+    checkpoints: Vec<u64>,
+    delta_num_bits: u8,
+    checkpoint_num_bits: u8,
+}
+
+impl EncodedBlock {
+    // Returns `None` if one of the deltas does not fit in a `u32`.
+    fn encode(block_vals: &[u64]) -> Option<EncodedBlock> {
+        let mut deltas = Vec::with_capacity(block_vals.len());
+        for window in block_vals.windows(2) {
+            let zigzag = zigzag_encode(window[1].wrapping_sub(window[0]) as i64);
+            if zigzag > u64::from(u32::MAX) {
+                return None;
+            }
+            deltas.push(zigzag);
+        }
+        let mut checkpoints: Vec<u64> = block_vals
+            .iter()
+            .step_by(SUB_BLOCK_LEN)
+            .skip(1)
+            .map(|&val| zigzag_encode(val.wrapping_sub(block_vals[0]) as i64))
+            .collect();
+        checkpoints.resize(NUM_CHECKPOINTS, 0u64);
+        let delta_num_bits = compute_num_bits(deltas.iter().cloned().max().unwrap_or(0));
+        let checkpoint_num_bits = compute_num_bits(checkpoints.iter().cloned().max().unwrap_or(0));
+        Some(EncodedBlock {
+            deltas,
+            checkpoints,
+            delta_num_bits,
+            checkpoint_num_bits,
+        })
+    }
+
+    fn num_bytes(&self) -> u64 {
+        let num_bits = self.checkpoints.len() as u64 * u64::from(self.checkpoint_num_bits)
+            + self.deltas.len() as u64 * u64::from(self.delta_num_bits);
+        num_bits.div_ceil(8)
+    }
+}
+
+/// Returns the number of bytes required to encode `vals` with the `DeltaZigzag` codec,
+/// or `None` if some of the deltas are too large for this codec.
+pub(crate) fn delta_zigzag_num_bytes(vals: &[u64]) -> Option<u64> {
+    let mut num_bytes = u64::SIZE_IN_BYTES as u64;
+    for block_vals in vals.chunks(BLOCK_LEN) {
+        let block = EncodedBlock::encode(block_vals)?;
+        num_bytes += BLOCK_META_NUM_BYTES as u64 + block.num_bytes();
+    }
+    // The bitpacked data is padded with 7 bytes.
+    Some(num_bytes + 7)
+}
+
+/// Serializes `vals` with the `DeltaZigzag` codec.
+///
+/// # Panics
+///
+/// Panics if some of the deltas are too large for this codec, that is if
+/// `delta_zigzag_num_bytes(vals)` is `None`.
+pub(crate) fn serialize_delta_zigzag<W: Write>(vals: &[u64], write: &mut W) -> io::Result<()> {
+    let mut blocks: Vec<u8> = Vec::new();
+    (vals.len() as u64).serialize(write)?;
+    for block_vals in vals.chunks(BLOCK_LEN) {
+        let block = EncodedBlock::encode(block_vals)
+            .expect("Deltas are too large for the DeltaZigzag codec.");
+        block_vals[0].serialize(write)?;
+        (blocks.len() as u64).serialize(write)?;
+        block.delta_num_bits.serialize(write)?;
+        block.checkpoint_num_bits.serialize(write)?;
+        let mut bit_packer = BitPacker::new();
+        for &checkpoint in &block.checkpoints {
+            bit_packer.write(checkpoint, block.checkpoint_num_bits, &mut blocks)?;
+        }
+        for &delta in &block.deltas {
+            bit_packer.write(delta, block.delta_num_bits, &mut blocks)?;
+        }
+        // Blocks need to start at the beginning of a byte.
+        bit_packer.flush(&mut blocks)?;
+    }
+    // Padding the blocks with 7 bytes, so that 8 bytes can always be read.
+    blocks.extend_from_slice(&[0u8; 7]);
+    write.write_all(&blocks[..])?;
+    Ok(())
+}
+
+/// Meta of a block of a `DeltaZigzag` column.
+struct BlockMeta {
+    first_val: u64,
+    // Offsets of the bitpacked checkpoints and deltas of the block, in bits.
+    checkpoints_addr: usize,
+    deltas_addr: usize,
+    delta_num_bits: u8,
+    checkpoint_num_bits: u8,
+}
+
+/// Reader of a fast field column serialized with the `DeltaZigzag` codec.
+#[derive(Clone)]
+pub(crate) struct DeltaZigzagReader {
+    block_metas: OwnedBytes,
+    blocks: OwnedBytes,
+}
+
+impl DeltaZigzagReader {
+    pub fn open(mut bytes: OwnedBytes) -> io::Result<DeltaZigzagReader> {
+        let num_vals = u64::deserialize(&mut bytes)? as usize;
+        let num_blocks = num_vals.div_ceil(BLOCK_LEN);
+        let block_metas_num_bytes = num_blocks * BLOCK_META_NUM_BYTES;
+        if bytes.len() < block_metas_num_bytes + 7 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "DeltaZigzag fast field column is truncated.",
+            ));
+        }
+        let (block_metas, blocks) = bytes.split(block_metas_num_bytes);
+        Ok(DeltaZigzagReader {
+            block_metas,
+            blocks,
+        })
+    }
+
+    fn block_meta(&self, block_id: usize) -> BlockMeta {
+        let block_meta = &self.block_metas.as_slice()[block_id * BLOCK_META_NUM_BYTES..];
+        let checkpoints_addr = LittleEndian::read_u64(&block_meta[8..16]) as usize * 8;
+        let delta_num_bits = block_meta[16];
+        let checkpoint_num_bits = block_meta[17];
+        BlockMeta {
+            first_val: LittleEndian::read_u64(&block_meta[..8]),
+            checkpoints_addr,
+            deltas_addr: checkpoints_addr + NUM_CHECKPOINTS * checkpoint_num_bits as usize,
+            delta_num_bits,
+            checkpoint_num_bits,
+        }
+    }
+
+    // The bitpacked data is padded with 7 bytes, so that 8 bytes can always be read.
+    fn read_bits(&self, addr: usize, num_bits: u8) -> u64 {
+        if num_bits == 0 {
+            return 0u64;
+        }
+        let val_unshifted_unmasked = LittleEndian::read_u64(&self.blocks.as_slice()[addr / 8..]);
+        (val_unshifted_unmasked >> (addr % 8)) & ((1u64 << num_bits) - 1)
+    }
+
+    // Returns the difference between the value `delta_idx + 1` of the block and the
+    // value that precedes it.
+    fn delta(&self, block_meta: &BlockMeta, delta_idx: usize) -> u64 {
+        let delta_addr = block_meta.deltas_addr + delta_idx * block_meta.delta_num_bits as usize;
+        zigzag_decode(self.read_bits(delta_addr, block_meta.delta_num_bits)) as u64
+    }
+
+    /// Returns the value at index `idx`.
+    pub fn get(&self, idx: u64) -> u64 {
+        let idx = idx as usize;
+        let block_meta = self.block_meta(idx / BLOCK_LEN);
+        let inner_idx = idx % BLOCK_LEN;
+        let sub_block_id = inner_idx / SUB_BLOCK_LEN;
+        let mut val = block_meta.first_val;
+        if sub_block_id > 0 {
+            let checkpoint_addr = block_meta.checkpoints_addr
+                + (sub_block_id - 1) * block_meta.checkpoint_num_bits as usize;
+            let checkpoint = self.read_bits(checkpoint_addr, block_meta.checkpoint_num_bits);
+            val = val.wrapping_add(zigzag_decode(checkpoint) as u64);
+        }
+        for delta_idx in sub_block_id * SUB_BLOCK_LEN..inner_idx {
+            val = val.wrapping_add(self.delta(&block_meta, delta_idx));
+        }
+        val
+    }
+
+    /// Fills `output` with the values from index `start` to `start + output.len()`.
+    ///
+    /// Only the first value of the range, and the first values of the
+    /// blocks it covers, are looked up: the following values are computed
+    /// by adding their delta to the value that precedes them.
+    pub fn get_range<Item: FastValue>(&self, start: u64, output: &mut [Item]) {
+        if output.is_empty() {
+            return;
+        }
+        let start = start as usize;
+        let mut block_meta = self.block_meta(start / BLOCK_LEN);
+        let mut val = self.get(start as u64);
+        for (idx, out) in (start..).zip(output.iter_mut()) {
+            if idx > start {
+                let inner_idx = idx % BLOCK_LEN;
+                if inner_idx == 0 {
+                    block_meta = self.block_meta(idx / BLOCK_LEN);
+                    val = block_meta.first_val;
+                } else {
+                    val = val.wrapping_add(self.delta(&block_meta, inner_idx - 1));
+                }
+            }
+            *out = Item::from_u64(val);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        delta_zigzag_num_bytes, serialize_delta_zigzag, zigzag_decode, zigzag_encode,
+        DeltaZigzagReader,
+    };
+    use crate::directory::OwnedBytes;
+
+    fn reader(vals: &[u64]) -> DeltaZigzagReader {
+        let mut buffer = Vec::new();
+        serialize_delta_zigzag(vals, &mut buffer).unwrap();
+        assert_eq!(buffer.len() as u64, delta_zigzag_num_bytes(vals).unwrap());
+        DeltaZigzagReader::open(OwnedBytes::new(buffer)).unwrap()
+    }
+
+    #[test]
+    fn test_zigzag() {
+        for &delta in &[0i64, 1, -1, 2, -2, 1_000_000, -1_000_000, 2_147_483_647] {
+            let zigzag = zigzag_encode(delta);
+            assert!(zigzag <= u64::from(u32::MAX));
+            assert_eq!(zigzag_decode(zigzag), delta);
+        }
+        assert_eq!(zigzag_encode(-1), 1);
+        assert_eq!(zigzag_encode(1), 2);
+    }
+
+    #[test]
+    fn test_delta_zigzag_get() {
+        let vals: Vec<u64> = (0..1_000u64)
+            .map(|i| 10_000_000_000u64 + (i * 7_919) % 1_013 - i)
+            .collect();
+        let reader = reader(&vals);
+        for (idx, &val) in vals.iter().enumerate() {
+            assert_eq!(reader.get(idx as u64), val);
+        }
+    }
+
+    #[test]
+    fn test_delta_zigzag_get_large_deltas() {
+        // The checkpoints are much larger than the deltas.
+        let vals: Vec<u64> = (0..300u64).map(|i| (1u64 << 40) + i * (1u64 << 30)).collect();
+        let reader = reader(&vals);
+        for (idx, &val) in vals.iter().enumerate() {
+            assert_eq!(reader.get(idx as u64), val);
+        }
+    }
+
+    #[test]
+    fn test_delta_zigzag_get_range() {
+        let vals: Vec<u64> = (0..1_000u64).map(|i| (i % 17) * 1_000 + i).collect();
+        let reader = reader(&vals);
+        for &(start, len) in &[(0, 1_000), (0, 0), (3, 200), (127, 2), (128, 128), (999, 1)] {
+            let mut output = vec![0u64; len];
+            reader.get_range(start as u64, &mut output[..]);
+            assert_eq!(&output[..], &vals[start..start + len]);
+        }
+    }
+
+    #[test]
+    fn test_delta_zigzag_too_large_deltas() {
+        assert!(delta_zigzag_num_bytes(&[0u64, 1u64 << 40]).is_none());
+        assert!(delta_zigzag_num_bytes(&[1u64 << 40, 0u64]).is_none());
+        assert!(delta_zigzag_num_bytes(&[0u64, 1u64 << 30, 0u64]).is_some());
+    }
+}
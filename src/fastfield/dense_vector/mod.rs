@@ -0,0 +1,60 @@
+mod reader;
+mod writer;
+
+pub use self::reader::DenseVectorFastFieldReader;
+pub use self::writer::DenseVectorFastFieldWriter;
+
+#[cfg(test)]
+mod tests {
+    use crate::schema::{DenseVectorOptions, Schema, Value, VectorSimilarity};
+    use crate::Index;
+    use futures::executor::block_on;
+
+    #[test]
+    fn test_dense_vector_fast_field() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let vector_options = DenseVectorOptions::new(3, VectorSimilarity::Cosine);
+        let embedding = schema_builder.add_dense_vector_field("embedding", vector_options);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(embedding => Value::DenseVector(vec![1.0, 2.0, 3.0])));
+        index_writer.add_document(doc!());
+        index_writer.commit()?;
+        index_writer.add_document(doc!(embedding => Value::DenseVector(vec![-1.0, 0.5, 0.0])));
+        index_writer.commit()?;
+        let segment_ids = index.searchable_segment_ids()?;
+        block_on(index_writer.merge(&segment_ids))?;
+        let reader = index.reader()?;
+        let searcher = reader.searcher();
+        assert_eq!(searcher.segment_readers().len(), 1);
+        let vector_reader = searcher
+            .segment_reader(0)
+            .fast_fields()
+            .dense_vector(embedding)?;
+        assert_eq!(vector_reader.dims(), 3);
+        let mut vectors = Vec::new();
+        let mut vector = Vec::new();
+        for doc in 0..3 {
+            vector_reader.get_vector(doc, &mut vector);
+            assert_eq!(vector_reader.has_vector(doc), !vector.is_empty());
+            vectors.push(vector.clone());
+        }
+        vectors.sort_by(|left, right| left.partial_cmp(right).unwrap());
+        assert_eq!(
+            vectors,
+            vec![vec![], vec![-1.0, 0.5, 0.0], vec![1.0, 2.0, 3.0]]
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected a dense vector of 3 dimensions")]
+    fn test_dense_vector_wrong_dims() {
+        let mut schema_builder = Schema::builder();
+        let vector_options = DenseVectorOptions::new(3, VectorSimilarity::Cosine);
+        let embedding = schema_builder.add_dense_vector_field("embedding", vector_options);
+        let schema = schema_builder.build();
+        let mut fast_field_writers = crate::fastfield::FastFieldsWriter::from_schema(&schema);
+        fast_field_writers.add_document(&doc!(embedding => Value::DenseVector(vec![1.0, 2.0])));
+    }
+}
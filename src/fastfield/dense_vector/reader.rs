@@ -0,0 +1,46 @@
+use crate::fastfield::BytesFastFieldReader;
+use crate::DocId;
+use std::convert::TryInto;
+
+/// Reader for dense vector fast fields.
+///
+/// The reader is implemented as a bytes fast field reader,
+/// each vector being encoded as the little endian representation
+/// of its `f32` components.
+#[derive(Clone)]
+pub struct DenseVectorFastFieldReader {
+    dims: usize,
+    bytes_reader: BytesFastFieldReader,
+}
+
+impl DenseVectorFastFieldReader {
+    pub(crate) fn open(
+        dims: usize,
+        bytes_reader: BytesFastFieldReader,
+    ) -> DenseVectorFastFieldReader {
+        DenseVectorFastFieldReader { dims, bytes_reader }
+    }
+
+    /// Returns the number of dimensions of the vectors.
+    pub fn dims(&self) -> usize {
+        self.dims
+    }
+
+    /// Returns true iff a vector is associated to the given `doc`.
+    pub fn has_vector(&self, doc: DocId) -> bool {
+        !self.bytes_reader.get_bytes(doc).is_empty()
+    }
+
+    /// Fills `vector` with the vector associated to the given `doc`.
+    ///
+    /// `vector` is left empty if the document has no vector.
+    pub fn get_vector(&self, doc: DocId, vector: &mut Vec<f32>) {
+        vector.clear();
+        vector.extend(
+            self.bytes_reader
+                .get_bytes(doc)
+                .chunks_exact(4)
+                .map(|bytes| f32::from_le_bytes(bytes.try_into().unwrap())),
+        );
+    }
+}
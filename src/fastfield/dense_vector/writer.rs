@@ -0,0 +1,72 @@
+use std::io;
+
+use crate::fastfield::serializer::FastFieldSerializer;
+use crate::fastfield::BytesFastFieldWriter;
+use crate::schema::{Document, Field, Value};
+
+/// Writer for dense vector fast fields.
+///
+/// Vectors are serialized as a bytes fast field, each vector being
+/// encoded as the little endian representation of its `f32` components.
+/// Documents without any vector are associated to an empty array of bytes.
+pub struct DenseVectorFastFieldWriter {
+    dims: usize,
+    bytes_writer: BytesFastFieldWriter,
+    buffer: Vec<u8>,
+}
+
+impl DenseVectorFastFieldWriter {
+    /// Creates a new `DenseVectorFastFieldWriter`, for vectors
+    /// of `dims` dimensions.
+    pub fn new(field: Field, dims: usize) -> DenseVectorFastFieldWriter {
+        DenseVectorFastFieldWriter {
+            dims,
+            bytes_writer: BytesFastFieldWriter::new(field),
+            buffer: Vec::with_capacity(dims * 4),
+        }
+    }
+
+    /// Access the field associated to the `DenseVectorFastFieldWriter`
+    pub fn field(&self) -> Field {
+        self.bytes_writer.field()
+    }
+
+    /// Returns the number of bytes used to buffer the vectors.
+    pub fn mem_usage(&self) -> usize {
+        self.bytes_writer.mem_usage() + self.buffer.capacity()
+    }
+
+    /// Records the vector associated to the field for a new document.
+    ///
+    /// If the document has more than one vector for the given field,
+    /// only the first one is taken in account.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vector does not have the number of dimensions
+    /// declared in the schema.
+    pub fn add_document(&mut self, doc: &Document) {
+        self.buffer.clear();
+        match doc.get_first(self.field()) {
+            Some(Value::DenseVector(vector)) => {
+                assert_eq!(
+                    vector.len(),
+                    self.dims,
+                    "Expected a dense vector of {} dimensions",
+                    self.dims
+                );
+                for val in vector {
+                    self.buffer.extend_from_slice(&val.to_le_bytes());
+                }
+            }
+            Some(value) => panic!("Expected a dense vector field, got {:?} ", value),
+            None => {}
+        }
+        self.bytes_writer.add_document_val(&self.buffer);
+    }
+
+    /// Serializes the fast field values by pushing them to the `FastFieldSerializer`.
+    pub fn serialize(&self, serializer: &mut FastFieldSerializer) -> io::Result<()> {
+        self.bytes_writer.serialize(serializer)
+    }
+}
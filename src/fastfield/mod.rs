@@ -18,20 +18,35 @@ supported.
 
 They are stored in a bit-packed fashion so that their
 memory usage is directly linear with the amplitude of the
-values stored.
+values stored. Narrower integer types are therefore not
+needed to save space: a column of ratings between 1 and 5
+takes 3 bits per document. Columns in which consecutive
+values are close to each other are delta-encoded instead
+(see `FastFieldCodec`).
 
 Read access performance is comparable to that of an array lookup.
+
+Documents without a value get a default value (`0`). Single-valued fast fields
+declared with `Cardinality::Optional` additionally store a null bitmap, so that
+these documents can be told apart (see `NullableFastFieldReader`).
 */
 
 pub use self::bytes::{BytesFastFieldReader, BytesFastFieldWriter};
+#[cfg(test)]
+pub(crate) use self::codec::downgrade_to_index_format_v3;
+pub(crate) use self::codec::upgrade_from_index_format_v3;
+pub use self::codec::FastFieldCodec;
 pub use self::delete::write_delete_bitset;
 pub use self::delete::DeleteBitSet;
+pub use self::dense_vector::{DenseVectorFastFieldReader, DenseVectorFastFieldWriter};
 pub use self::error::{FastFieldNotAvailableError, Result};
 pub use self::facet_reader::FacetReader;
 pub use self::multivalued::{MultiValuedFastFieldReader, MultiValuedFastFieldWriter};
+pub use self::nullable::NullableFastFieldReader;
 pub use self::reader::FastFieldReader;
 pub use self::readers::FastFieldReaders;
 pub use self::serializer::FastFieldSerializer;
+pub use self::u128::{U128FastFieldReader, U128FastFieldWriter};
 pub use self::writer::{FastFieldsWriter, IntFastFieldWriter};
 use crate::common;
 use crate::schema::Cardinality;
@@ -43,13 +58,18 @@ use crate::{
 };
 
 mod bytes;
+mod codec;
 mod delete;
+mod delta_zigzag;
+mod dense_vector;
 mod error;
 mod facet_reader;
 mod multivalued;
+mod nullable;
 mod reader;
 mod readers;
 mod serializer;
+mod u128;
 mod writer;
 
 /// Trait for types that are allowed for fast fields: (u64, i64 and f64).
@@ -192,6 +212,7 @@ fn value_to_u64(value: &Value) -> u64 {
         Value::I64(ref val) => common::i64_to_u64(*val),
         Value::F64(ref val) => common::f64_to_u64(*val),
         Value::Date(ref datetime) => common::i64_to_u64(datetime.timestamp()),
+        Value::GeoPoint(ref geo_point) => geo_point.to_u64(),
         _ => panic!("Expected a u64/i64/f64 field, got {:?} ", value),
     }
 }
@@ -256,7 +277,7 @@ mod tests {
             serializer.close().unwrap();
         }
         let file = directory.open_read(&path).unwrap();
-        assert_eq!(file.len(), 36 as usize);
+        assert_eq!(file.len(), 38 as usize);
         let composite_file = CompositeFile::open(&file)?;
         let file = composite_file.open_read(*FIELD).unwrap();
         let fast_field_reader = FastFieldReader::<u64>::open(file)?;
@@ -266,6 +287,32 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_intfastfield_small_amplitude() -> crate::Result<()> {
+        let path = Path::new("test");
+        let directory: RAMDirectory = RAMDirectory::create();
+        {
+            let write: WritePtr = directory.open_write(path)?;
+            let mut serializer = FastFieldSerializer::from_write(write)?;
+            let mut fast_field_writers = FastFieldsWriter::from_schema(&SCHEMA);
+            for doc in 0..10_000u64 {
+                fast_field_writers.add_document(&doc!(*FIELD=>1 + doc * 7 % 5));
+            }
+            fast_field_writers.serialize(&mut serializer, &HashMap::new())?;
+            serializer.close()?;
+        }
+        let file = directory.open_read(path)?;
+        // 3 bits per document, plus the headers.
+        assert!(file.len() < 10_000 * 3 / 8 + 64);
+        let composite_file = CompositeFile::open(&file)?;
+        let file = composite_file.open_read(*FIELD).unwrap();
+        let fast_field_reader = FastFieldReader::<u64>::open(file)?;
+        for doc in 0..10_000u64 {
+            assert_eq!(fast_field_reader.get(doc as u32), 1 + doc * 7 % 5);
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_intfastfield_large() -> crate::Result<()> {
         let path = Path::new("test");
@@ -287,7 +334,7 @@ mod tests {
             serializer.close()?;
         }
         let file = directory.open_read(&path)?;
-        assert_eq!(file.len(), 61 as usize);
+        assert_eq!(file.len(), 63 as usize);
         {
             let fast_fields_composite = CompositeFile::open(&file)?;
             let data = fast_fields_composite.open_read(*FIELD).unwrap();
@@ -323,7 +370,7 @@ mod tests {
             serializer.close().unwrap();
         }
         let file = directory.open_read(&path).unwrap();
-        assert_eq!(file.len(), 34 as usize);
+        assert_eq!(file.len(), 36 as usize);
         {
             let fast_fields_composite = CompositeFile::open(&file).unwrap();
             let data = fast_fields_composite.open_read(*FIELD).unwrap();
@@ -355,11 +402,12 @@ mod tests {
             serializer.close().unwrap();
         }
         let file = directory.open_read(&path).unwrap();
-        assert_eq!(file.len(), 80042 as usize);
+        assert_eq!(file.len(), 80044 as usize);
         {
             let fast_fields_composite = CompositeFile::open(&file)?;
             let data = fast_fields_composite.open_read(*FIELD).unwrap();
             let fast_field_reader = FastFieldReader::<u64>::open(data)?;
+            assert_eq!(fast_field_reader.codec(), FastFieldCodec::Bitpacked);
             assert_eq!(fast_field_reader.get(0), 0u64);
             for doc in 1..10_001 {
                 assert_eq!(
@@ -394,11 +442,12 @@ mod tests {
             serializer.close().unwrap();
         }
         let file = directory.open_read(&path).unwrap();
-        assert_eq!(file.len(), 17709 as usize);
+        assert_eq!(file.len(), 5176 as usize);
         {
             let fast_fields_composite = CompositeFile::open(&file)?;
             let data = fast_fields_composite.open_read(i64_field).unwrap();
             let fast_field_reader = FastFieldReader::<i64>::open(data)?;
+            assert_eq!(fast_field_reader.codec(), FastFieldCodec::DeltaZigzag);
 
             assert_eq!(fast_field_reader.min_value(), -100i64);
             assert_eq!(fast_field_reader.max_value(), 9_999i64);
@@ -414,6 +463,49 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_intfastfield_codec_selection() -> crate::Result<()> {
+        let directory: RAMDirectory = RAMDirectory::create();
+        let mut schema_builder = Schema::builder();
+        let timestamp_field = schema_builder.add_u64_field("timestamp", FAST);
+        let random_field = schema_builder.add_u64_field("random", FAST);
+        let schema = schema_builder.build();
+        let timestamps: Vec<u64> = (0..10_000u64)
+            .map(|i| 1_600_000_000u64 + i * 3 + i % 5)
+            .collect();
+        let random_vals = generate_permutation();
+        {
+            let write: WritePtr = directory.open_write(Path::new("test"))?;
+            let mut serializer = FastFieldSerializer::from_write(write)?;
+            let mut fast_field_writers = FastFieldsWriter::from_schema(&schema);
+            for (&timestamp, &random_val) in timestamps.iter().zip(random_vals.iter()) {
+                fast_field_writers
+                    .add_document(&doc!(timestamp_field=>timestamp, random_field=>random_val));
+            }
+            fast_field_writers.serialize(&mut serializer, &HashMap::new())?;
+            serializer.close()?;
+        }
+        let file = directory.open_read(Path::new("test"))?;
+        let fast_fields_composite = CompositeFile::open(&file)?;
+        let timestamp_reader = FastFieldReader::<u64>::open(
+            fast_fields_composite.open_read(timestamp_field).unwrap(),
+        )?;
+        assert_eq!(timestamp_reader.codec(), FastFieldCodec::DeltaZigzag);
+        for (doc, &timestamp) in timestamps.iter().enumerate() {
+            assert_eq!(timestamp_reader.get(doc as u32), timestamp);
+        }
+        let mut buffer = vec![0u64; 1_000];
+        timestamp_reader.get_range(4_321, &mut buffer[..]);
+        assert_eq!(&buffer[..], &timestamps[4_321..5_321]);
+        let random_reader =
+            FastFieldReader::<u64>::open(fast_fields_composite.open_read(random_field).unwrap())?;
+        assert_eq!(random_reader.codec(), FastFieldCodec::Bitpacked);
+        for (doc, &random_val) in random_vals.iter().take(10_000).enumerate() {
+            assert_eq!(random_reader.get(doc as u32), random_val);
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_signed_intfastfield_default_val() -> crate::Result<()> {
         let path = Path::new("test");
@@ -0,0 +1,182 @@
+use super::{FastFieldReader, FastValue};
+use crate::DocId;
+use roaring::RoaringBitmap;
+use std::sync::Arc;
+
+/// Reader of an optional fast field.
+///
+/// An optional fast field is made of a column of values, and of a null bitmap
+/// in which a set bit means that the document has a value.
+/// In the column, the documents without a value get the default value of the
+/// field (`0`), which must not be mistaken for an actual value.
+///
+/// The fast field of a field is optional if it is declared with
+/// `Cardinality::Optional`.
+#[derive(Clone)]
+pub struct NullableFastFieldReader<Item: FastValue> {
+    vals_reader: FastFieldReader<Item>,
+    null_bitmap: Arc<RoaringBitmap>,
+}
+
+impl<Item: FastValue> NullableFastFieldReader<Item> {
+    pub(crate) fn open(
+        vals_reader: FastFieldReader<Item>,
+        null_bitmap: Arc<RoaringBitmap>,
+    ) -> NullableFastFieldReader<Item> {
+        NullableFastFieldReader {
+            vals_reader,
+            null_bitmap,
+        }
+    }
+
+    /// Returns true iff the document has a value.
+    pub fn has_value(&self, doc: DocId) -> bool {
+        self.null_bitmap.contains(doc)
+    }
+
+    /// Returns the value associated to the given document, or `None`
+    /// if the document does not have any value.
+    ///
+    /// # Panics
+    ///
+    /// May panic if `doc` is greater than the segment
+    /// `maxdoc`.
+    pub fn get(&self, doc: DocId) -> Option<Item> {
+        if self.has_value(doc) {
+            Some(self.vals_reader.get(doc))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the number of documents having a value,
+    /// including the deleted documents.
+    pub fn num_non_null(&self) -> u64 {
+        self.null_bitmap.len()
+    }
+
+    /// Returns the column of the values.
+    ///
+    /// The column has a value for all of the documents, including
+    /// the documents that do not have one (see `has_value`).
+    pub fn values(&self) -> &FastFieldReader<Item> {
+        &self.vals_reader
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collector::{Order, SortCollector};
+    use crate::indexer::NoMergePolicy;
+    use crate::query::AllQuery;
+    use crate::schema::{Cardinality, IntOptions, Schema, STRING};
+    use crate::{DocAddress, Index, TantivyError, Term};
+
+    #[test]
+    fn test_nullable_fast_field() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let int_options = IntOptions::default().set_fast(Cardinality::Optional);
+        let count = schema_builder.add_u64_field("count", int_options.clone());
+        let delta = schema_builder.add_i64_field("delta", int_options);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(count => 0u64, delta => -3i64));
+        index_writer.add_document(doc!());
+        index_writer.add_document(doc!(count => 7u64));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let fast_fields = searcher.segment_reader(0).fast_fields();
+
+        let count_reader = fast_fields.nullable_u64(count)?;
+        assert_eq!(count_reader.get(0), Some(0u64));
+        assert_eq!(count_reader.get(1), None);
+        assert_eq!(count_reader.get(2), Some(7u64));
+        assert_eq!(count_reader.num_non_null(), 2);
+        assert_eq!(count_reader.values().get(1), 0u64);
+
+        let delta_reader = fast_fields.nullable_i64(delta)?;
+        assert_eq!(delta_reader.get(0), Some(-3i64));
+        assert!(!delta_reader.has_value(1));
+        assert!(!delta_reader.has_value(2));
+
+        assert!(matches!(
+            fast_fields.u64(count),
+            Err(TantivyError::SchemaError(_))
+        ));
+        assert!(matches!(
+            fast_fields.nullable_u64(delta),
+            Err(TantivyError::SchemaError(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_nullable_fast_field_merge() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let id = schema_builder.add_text_field("id", STRING);
+        let score = schema_builder.add_f64_field(
+            "score",
+            IntOptions::default().set_fast(Cardinality::Optional),
+        );
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.set_merge_policy(Box::new(NoMergePolicy));
+        index_writer.add_document(doc!(id => "a", score => 1.5f64));
+        index_writer.add_document(doc!(id => "b"));
+        index_writer.commit()?;
+        index_writer.add_document(doc!(id => "c", score => -2.0f64));
+        index_writer.add_document(doc!(id => "d"));
+        index_writer.commit()?;
+        index_writer.delete_term(Term::from_field_text(id, "a"));
+        index_writer.commit()?;
+        let segment_ids = index.searchable_segment_ids()?;
+        futures::executor::block_on(index_writer.merge(&segment_ids))?;
+        index_writer.wait_merging_threads()?;
+
+        let reader = index.reader()?;
+        reader.reload()?;
+        let searcher = reader.searcher();
+        assert_eq!(searcher.segment_readers().len(), 1);
+        let score_reader = searcher
+            .segment_reader(0)
+            .fast_fields()
+            .nullable_f64(score)?;
+        let scores: Vec<Option<f64>> = (0..3).map(|doc| score_reader.get(doc)).collect();
+        assert_eq!(scores, vec![None, Some(-2.0f64), None]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_nullable_fast_field_sort() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let price = schema_builder.add_u64_field(
+            "price",
+            IntOptions::default().set_fast(Cardinality::Optional),
+        );
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!());
+        index_writer.add_document(doc!(price => 0u64));
+        index_writer.add_document(doc!(price => 5u64));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let sorted_docs = |order: Order| -> crate::Result<Vec<DocAddress>> {
+            let sort_collector = SortCollector::new(vec![(price, order)], 10);
+            Ok(searcher
+                .search(&AllQuery, &sort_collector)?
+                .into_iter()
+                .map(|(_, doc_address)| doc_address)
+                .collect())
+        };
+        // Documents without a value are sorted last.
+        assert_eq!(
+            sorted_docs(Order::Asc)?,
+            vec![DocAddress(0, 1), DocAddress(0, 2), DocAddress(0, 0)]
+        );
+        assert_eq!(
+            sorted_docs(Order::Desc)?,
+            vec![DocAddress(0, 2), DocAddress(0, 1), DocAddress(0, 0)]
+        );
+        Ok(())
+    }
+}
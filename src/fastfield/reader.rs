@@ -5,6 +5,8 @@ use crate::common::BinarySerializable;
 use crate::common::CompositeFile;
 use crate::directory::FileSlice;
 use crate::directory::{Directory, RAMDirectory, WritePtr};
+use crate::fastfield::codec::{read_footer, FastFieldCodec};
+use crate::fastfield::delta_zigzag::DeltaZigzagReader;
 use crate::fastfield::{FastFieldSerializer, FastFieldsWriter};
 use crate::schema::Schema;
 use crate::schema::FAST;
@@ -19,29 +21,57 @@ use std::path::Path;
 /// fast field is required.
 #[derive(Clone)]
 pub struct FastFieldReader<Item: FastValue> {
-    bit_unpacker: BitUnpacker,
+    codec_reader: CodecReader,
     min_value_u64: u64,
     max_value_u64: u64,
+    format_version: u8,
     _phantom: PhantomData<Item>,
 }
 
+#[derive(Clone)]
+enum CodecReader {
+    Bitpacked(BitUnpacker),
+    DeltaZigzag(DeltaZigzagReader),
+}
+
 impl<Item: FastValue> FastFieldReader<Item> {
     /// Opens a fast field given a file.
     pub fn open(file: FileSlice) -> crate::Result<Self> {
-        let mut bytes = file.read_bytes()?;
+        let (mut bytes, codec, format_version) = read_footer(file.read_bytes()?)?;
         let min_value = u64::deserialize(&mut bytes)?;
         let amplitude = u64::deserialize(&mut bytes)?;
         let max_value = min_value + amplitude;
-        let num_bits = compute_num_bits(amplitude);
-        let bit_unpacker = BitUnpacker::new(bytes, num_bits);
+        let codec_reader = match codec {
+            FastFieldCodec::Bitpacked => {
+                let num_bits = compute_num_bits(amplitude);
+                CodecReader::Bitpacked(BitUnpacker::new(bytes, num_bits))
+            }
+            FastFieldCodec::DeltaZigzag => {
+                CodecReader::DeltaZigzag(DeltaZigzagReader::open(bytes)?)
+            }
+        };
         Ok(FastFieldReader {
             min_value_u64: min_value,
             max_value_u64: max_value,
-            bit_unpacker,
+            codec_reader,
+            format_version,
             _phantom: PhantomData,
         })
     }
 
+    /// Returns the codec that was selected to encode this fast field.
+    pub fn codec(&self) -> FastFieldCodec {
+        match self.codec_reader {
+            CodecReader::Bitpacked(_) => FastFieldCodec::Bitpacked,
+            CodecReader::DeltaZigzag(_) => FastFieldCodec::DeltaZigzag,
+        }
+    }
+
+    /// Returns the version of the format of the column.
+    pub(crate) fn format_version(&self) -> u8 {
+        self.format_version
+    }
+
     /// Return the value associated to the given document.
     ///
     /// This accessor should return as fast as possible.
@@ -55,7 +85,14 @@ impl<Item: FastValue> FastFieldReader<Item> {
     }
 
     pub(crate) fn get_u64(&self, doc: u64) -> Item {
-        Item::from_u64(self.min_value_u64 + self.bit_unpacker.get(doc))
+        match self.codec_reader {
+            CodecReader::Bitpacked(ref bit_unpacker) => {
+                Item::from_u64(self.min_value_u64 + bit_unpacker.get(doc))
+            }
+            CodecReader::DeltaZigzag(ref delta_zigzag_reader) => {
+                Item::from_u64(delta_zigzag_reader.get(doc))
+            }
+        }
     }
 
     /// Internally `multivalued` also use SingleValue Fast fields.
@@ -71,6 +108,10 @@ impl<Item: FastValue> FastFieldReader<Item> {
     ///
     /// See `get_range` for an actual documentation about this method.
     pub(crate) fn get_range_u64(&self, start: u64, output: &mut [Item]) {
+        if let CodecReader::DeltaZigzag(ref delta_zigzag_reader) = self.codec_reader {
+            delta_zigzag_reader.get_range(start, output);
+            return;
+        }
         for (i, out) in output.iter_mut().enumerate() {
             *out = self.get_u64(start + (i as u64));
         }
@@ -1,11 +1,17 @@
-use crate::common::CompositeFile;
+use crate::common::{BinarySerializable, CompositeFile};
 use crate::directory::FileSlice;
+use crate::error::DataCorruption;
+use crate::fastfield::codec::{write_footer, FastFieldCodec};
+use crate::fastfield::codec::{BASE_FORMAT_VERSION, NULLABLE_FORMAT_VERSION};
 use crate::fastfield::MultiValuedFastFieldReader;
-use crate::fastfield::{BytesFastFieldReader, FastValue};
-use crate::fastfield::{FastFieldNotAvailableError, FastFieldReader};
-use crate::schema::{Cardinality, Field, FieldType, Schema};
+use crate::fastfield::NullableFastFieldReader;
+use crate::fastfield::{BytesFastFieldReader, DenseVectorFastFieldReader, FastValue};
+use crate::fastfield::{FastFieldNotAvailableError, FastFieldReader, U128FastFieldReader};
+use crate::schema::{Cardinality, Field, FieldType, Schema, SchemaVersion};
 use crate::space_usage::PerFieldSpaceUsage;
 use crate::TantivyError;
+use roaring::RoaringBitmap;
+use std::sync::Arc;
 
 /// Provides access to all of the FastFieldReader.
 ///
@@ -14,6 +20,7 @@ use crate::TantivyError;
 #[derive(Clone)]
 pub struct FastFieldReaders {
     schema: Schema,
+    schema_version: SchemaVersion,
     fast_fields_composite: CompositeFile,
 }
 #[derive(Eq, PartialEq, Debug)]
@@ -22,6 +29,7 @@ enum FastType {
     U64,
     F64,
     Date,
+    GeoPoint,
 }
 
 fn type_and_cardinality(field_type: &FieldType) -> Option<(FastType, Cardinality)> {
@@ -38,7 +46,11 @@ fn type_and_cardinality(field_type: &FieldType) -> Option<(FastType, Cardinality
         FieldType::Date(options) => options
             .get_fastfield_cardinality()
             .map(|cardinality| (FastType::Date, cardinality)),
+        FieldType::GeoPoint(options) if options.is_fast() => {
+            Some((FastType::GeoPoint, Cardinality::SingleValue))
+        }
         FieldType::HierarchicalFacet => Some((FastType::U64, Cardinality::MultiValues)),
+        FieldType::DocumentBoost(_) => Some((FastType::F64, Cardinality::SingleValue)),
         _ => None,
     }
 }
@@ -46,19 +58,44 @@ fn type_and_cardinality(field_type: &FieldType) -> Option<(FastType, Cardinality
 impl FastFieldReaders {
     pub(crate) fn new(
         schema: Schema,
+        schema_version: SchemaVersion,
         fast_fields_composite: CompositeFile,
     ) -> crate::Result<FastFieldReaders> {
         Ok(FastFieldReaders {
             fast_fields_composite,
             schema,
+            schema_version,
         })
     }
 
+    /// Returns the parts of the fast field file storing `field`.
+    pub(crate) fn file_slices(&self, field: Field) -> Vec<FileSlice> {
+        self.fast_fields_composite.open_read_all(field)
+    }
+
     pub(crate) fn space_usage(&self) -> PerFieldSpaceUsage {
         self.fast_fields_composite.space_usage()
     }
 
     fn fast_field_data(&self, field: Field, idx: usize) -> crate::Result<FileSlice> {
+        if !self.schema_version.has_field(field) && self.schema.version().has_field(field) {
+            // The field was added to the schema after the segment was written.
+            // All of its documents get the value `0`, or a boost of `1`.
+            let field_type = self.schema.get_field_entry(field).field_type();
+            let default_value = match (field_type, type_and_cardinality(field_type)) {
+                (FieldType::DocumentBoost(_), _) => 1f64.to_u64(),
+                (_, Some((FastType::I64, Cardinality::SingleValue)))
+                | (_, Some((FastType::Date, Cardinality::SingleValue))) => 0i64.to_u64(),
+                (_, Some((FastType::F64, Cardinality::SingleValue))) => 0f64.to_u64(),
+                _ => 0u64,
+            };
+            // min value and amplitude of the values, followed by the footer.
+            let mut data = Vec::new();
+            default_value.serialize(&mut data)?;
+            0u64.serialize(&mut data)?;
+            write_footer(FastFieldCodec::Bitpacked, BASE_FORMAT_VERSION, &mut data)?;
+            return Ok(FileSlice::from(data));
+        }
         self.fast_fields_composite
             .open_read_with_idx(field, idx)
             .ok_or_else(|| {
@@ -104,7 +141,15 @@ impl FastFieldReaders {
         &self,
         field: Field,
     ) -> crate::Result<FastFieldReader<TFastValue>> {
-        let fast_field_slice = self.fast_field_data(field, 0)?;
+        self.typed_fast_field_reader_with_idx(field, 0)
+    }
+
+    pub(crate) fn typed_fast_field_reader_with_idx<TFastValue: FastValue>(
+        &self,
+        field: Field,
+        idx: usize,
+    ) -> crate::Result<FastFieldReader<TFastValue>> {
+        let fast_field_slice = self.fast_field_data(field, idx)?;
         FastFieldReader::open(fast_field_slice)
     }
 
@@ -152,6 +197,95 @@ impl FastFieldReaders {
         self.typed_fast_field_reader(field)
     }
 
+    /// Returns the geo point fast field reader associated to `field`.
+    ///
+    /// The points are encoded as `u64` values and can be
+    /// decoded using `GeoPoint::from_u64`.
+    ///
+    /// If `field` is not a geo point fast field, this method returns an error.
+    pub fn geo_point(&self, field: Field) -> crate::Result<FastFieldReader<u64>> {
+        self.check_type(field, FastType::GeoPoint, Cardinality::SingleValue)?;
+        self.typed_fast_field_reader(field)
+    }
+
+    /// Returns the null bitmap of `field`, i.e. the documents having a value,
+    /// or `None` if `field` is not an optional fast field.
+    pub(crate) fn null_bitmap(&self, field: Field) -> crate::Result<Option<Arc<RoaringBitmap>>> {
+        let field_type = self.schema.get_field_entry(field).field_type();
+        match type_and_cardinality(field_type) {
+            Some((_, Cardinality::Optional)) => {}
+            _ => return Ok(None),
+        }
+        if !self.schema_version.has_field(field) && self.schema.version().has_field(field) {
+            // The field was added to the schema after the segment was written.
+            return Ok(Some(Arc::new(RoaringBitmap::new())));
+        }
+        let field_name = self.schema.get_field_entry(field).name();
+        // The columns written before optional fast fields were introduced do not
+        // have a null bitmap.
+        let vals_reader: FastFieldReader<u64> = self.typed_fast_field_reader(field)?;
+        if vals_reader.format_version() < NULLABLE_FORMAT_VERSION {
+            return Err(DataCorruption::comment_only(format!(
+                "The fast field {:?} was not written as an optional fast field.",
+                field_name
+            ))
+            .into());
+        }
+        let null_bitmap_bytes = self.fast_field_data(field, 1)?.read_bytes()?;
+        let null_bitmap = RoaringBitmap::deserialize_from(null_bitmap_bytes.as_slice())?;
+        Ok(Some(Arc::new(null_bitmap)))
+    }
+
+    fn typed_fast_field_nullable_reader<TFastValue: FastValue>(
+        &self,
+        field: Field,
+    ) -> crate::Result<NullableFastFieldReader<TFastValue>> {
+        let null_bitmap = self.null_bitmap(field)?.ok_or_else(|| {
+            let field_name = self.schema.get_field_entry(field).name();
+            TantivyError::SchemaError(format!(
+                "Field {:?} is not an optional fast field.",
+                field_name
+            ))
+        })?;
+        let vals_reader = self.typed_fast_field_reader(field)?;
+        Ok(NullableFastFieldReader::open(vals_reader, null_bitmap))
+    }
+
+    /// Returns the `u64` optional fast field reader associated to `field`.
+    ///
+    /// If `field` is not a u64 optional fast field, this method returns an error.
+    pub fn nullable_u64(&self, field: Field) -> crate::Result<NullableFastFieldReader<u64>> {
+        self.check_type(field, FastType::U64, Cardinality::Optional)?;
+        self.typed_fast_field_nullable_reader(field)
+    }
+
+    /// Returns the `i64` optional fast field reader associated to `field`.
+    ///
+    /// If `field` is not a i64 optional fast field, this method returns an error.
+    pub fn nullable_i64(&self, field: Field) -> crate::Result<NullableFastFieldReader<i64>> {
+        self.check_type(field, FastType::I64, Cardinality::Optional)?;
+        self.typed_fast_field_nullable_reader(field)
+    }
+
+    /// Returns the `f64` optional fast field reader associated to `field`.
+    ///
+    /// If `field` is not a f64 optional fast field, this method returns an error.
+    pub fn nullable_f64(&self, field: Field) -> crate::Result<NullableFastFieldReader<f64>> {
+        self.check_type(field, FastType::F64, Cardinality::Optional)?;
+        self.typed_fast_field_nullable_reader(field)
+    }
+
+    /// Returns the `crate::DateTime` optional fast field reader associated to `field`.
+    ///
+    /// If `field` is not a date optional fast field, this method returns an error.
+    pub fn nullable_date(
+        &self,
+        field: Field,
+    ) -> crate::Result<NullableFastFieldReader<crate::DateTime>> {
+        self.check_type(field, FastType::Date, Cardinality::Optional)?;
+        self.typed_fast_field_nullable_reader(field)
+    }
+
     /// Returns a `u64s` multi-valued fast field reader reader associated to `field`.
     ///
     /// If `field` is not a u64 multi-valued fast field, this method returns `None`.
@@ -199,10 +333,58 @@ impl FastFieldReaders {
                     field_entry.name()
                 )));
             }
-            let fast_field_idx_file = self.fast_field_data(field, 0)?;
-            let idx_reader = FastFieldReader::open(fast_field_idx_file)?;
-            let data = self.fast_field_data(field, 1)?;
-            BytesFastFieldReader::open(idx_reader, data)
+            self.raw_bytes(field)
+        } else {
+            Err(FastFieldNotAvailableError::new(field_entry).into())
+        }
+    }
+
+    /// Returns a bytes fast field reader over the data of `field`,
+    /// without checking its type.
+    ///
+    /// Dense vector fields are stored as bytes fast fields.
+    pub(crate) fn raw_bytes(&self, field: Field) -> crate::Result<BytesFastFieldReader> {
+        let fast_field_idx_file = self.fast_field_data(field, 0)?;
+        let idx_reader = FastFieldReader::open(fast_field_idx_file)?;
+        let data = self.fast_field_data(field, 1)?;
+        BytesFastFieldReader::open(idx_reader, data)
+    }
+
+    /// Returns the dense vector fast field reader associated to `field`.
+    ///
+    /// If `field` is not a dense vector field, returns an error.
+    pub fn dense_vector(&self, field: Field) -> crate::Result<DenseVectorFastFieldReader> {
+        let field_entry = self.schema.get_field_entry(field);
+        if let FieldType::DenseVector(dense_vector_options) = field_entry.field_type() {
+            let bytes_reader = self.raw_bytes(field)?;
+            Ok(DenseVectorFastFieldReader::open(
+                dense_vector_options.dims(),
+                bytes_reader,
+            ))
+        } else {
+            Err(FastFieldNotAvailableError::new(field_entry).into())
+        }
+    }
+
+    /// Returns the ip address fast field reader associated to `field`.
+    ///
+    /// Addresses are returned as `u128` values, and can be converted back
+    /// using `std::net::Ipv6Addr::from`. IPv4 addresses are stored as
+    /// IPv4-mapped IPv6 addresses.
+    ///
+    /// If `field` is not an ip address fast field, returns an error.
+    pub fn ip_addr(&self, field: Field) -> crate::Result<U128FastFieldReader> {
+        let field_entry = self.schema.get_field_entry(field);
+        if let FieldType::IpAddr(ip_addr_options) = field_entry.field_type() {
+            if !ip_addr_options.is_fast() {
+                return Err(crate::TantivyError::SchemaError(format!(
+                    "Field {:?} is not a fast field.",
+                    field_entry.name()
+                )));
+            }
+            let high_bits_reader = self.typed_fast_field_reader_with_idx(field, 0)?;
+            let low_bits_reader = self.typed_fast_field_reader_with_idx(field, 1)?;
+            Ok(U128FastFieldReader::open(high_bits_reader, low_bits_reader))
         } else {
             Err(FastFieldNotAvailableError::new(field_entry).into())
         }
@@ -4,13 +4,19 @@ use crate::common::BinarySerializable;
 use crate::common::CompositeWrite;
 use crate::common::CountingWriter;
 use crate::directory::WritePtr;
+use crate::fastfield::codec::{write_footer, FastFieldCodec};
+use crate::fastfield::codec::{BASE_FORMAT_VERSION, NULLABLE_FORMAT_VERSION};
+use crate::fastfield::delta_zigzag::{delta_zigzag_num_bytes, serialize_delta_zigzag};
 use crate::schema::Field;
+use roaring::RoaringBitmap;
 use std::io::{self, Write};
 
 /// `FastFieldSerializer` is in charge of serializing
 /// fastfields on disk.
 ///
-/// Fast fields are encoded using bit-packing.
+/// Fast fields are encoded using bit-packing, or using the `DeltaZigzag` codec
+/// when their consecutive values are close to each other.
+/// (See `FastFieldCodec`.)
 ///
 /// `FastFieldWriter`s are in charge of pushing the data to
 /// the serializer.
@@ -58,7 +64,24 @@ impl FastFieldSerializer {
         idx: usize,
     ) -> io::Result<FastSingleFieldSerializer<'_, CountingWriter<WritePtr>>> {
         let field_write = self.composite_write.for_field_with_idx(field, idx);
-        FastSingleFieldSerializer::open(field_write, min_value, max_value)
+        FastSingleFieldSerializer::open(field_write, min_value, max_value, BASE_FORMAT_VERSION)
+    }
+
+    /// Start serializing a new optional u64 fast field.
+    ///
+    /// `null_bitmap` contains the documents having a value.
+    /// A value must still be pushed for each of the documents.
+    pub fn new_nullable_u64_fast_field(
+        &mut self,
+        field: Field,
+        min_value: u64,
+        max_value: u64,
+        null_bitmap: &RoaringBitmap,
+    ) -> io::Result<FastSingleFieldSerializer<'_, CountingWriter<WritePtr>>> {
+        let null_bitmap_write = self.composite_write.for_field_with_idx(field, 1);
+        null_bitmap.serialize_into(null_bitmap_write)?;
+        let field_write = self.composite_write.for_field_with_idx(field, 0);
+        FastSingleFieldSerializer::open(field_write, min_value, max_value, NULLABLE_FORMAT_VERSION)
     }
 
     /// Start serializing a new [u8] fast field
@@ -80,17 +103,19 @@ impl FastFieldSerializer {
 }
 
 pub struct FastSingleFieldSerializer<'a, W: Write> {
-    bit_packer: BitPacker,
     write: &'a mut W,
     min_value: u64,
     num_bits: u8,
+    format_version: u8,
+    vals: Vec<u64>,
 }
 
 impl<'a, W: Write> FastSingleFieldSerializer<'a, W> {
     /// Creates a new fast field serializer.
     ///
     /// The serializer in fact encode the values by bitpacking
-    /// `(val - min_value)`.
+    /// `(val - min_value)`, unless the `DeltaZigzag` codec
+    /// requires less than half of the space.
     ///
     /// It requires a `min_value` and a `max_value` to compute
     /// compute the minimum number of bits required to encode
@@ -99,31 +124,58 @@ impl<'a, W: Write> FastSingleFieldSerializer<'a, W> {
         write: &'a mut W,
         min_value: u64,
         max_value: u64,
+        format_version: u8,
     ) -> io::Result<FastSingleFieldSerializer<'a, W>> {
         assert!(min_value <= max_value);
         min_value.serialize(write)?;
         let amplitude = max_value - min_value;
         amplitude.serialize(write)?;
         let num_bits = compute_num_bits(amplitude);
-        let bit_packer = BitPacker::new();
         Ok(FastSingleFieldSerializer {
             write,
-            bit_packer,
             min_value,
             num_bits,
+            format_version,
+            vals: Vec::new(),
         })
     }
 
     /// Pushes a new value to the currently open u64 fast field.
     pub fn add_val(&mut self, val: u64) -> io::Result<()> {
-        let val_to_write: u64 = val - self.min_value;
-        self.bit_packer
-            .write(val_to_write, self.num_bits, &mut self.write)?;
+        debug_assert!(val >= self.min_value);
+        self.vals.push(val);
         Ok(())
     }
 
-    pub fn close_field(mut self) -> io::Result<()> {
-        self.bit_packer.close(&mut self.write)
+    fn select_codec(&self) -> FastFieldCodec {
+        // bitpacked values are padded with 7 bytes.
+        let bitpacked_num_bytes =
+            (self.vals.len() as u64 * u64::from(self.num_bits)).div_ceil(8) + 7;
+        match delta_zigzag_num_bytes(&self.vals[..]) {
+            // Accessing a value encoded with the `DeltaZigzag` codec is slower,
+            // so it has to be worth it.
+            Some(delta_zigzag_num_bytes) if delta_zigzag_num_bytes * 2 <= bitpacked_num_bytes => {
+                FastFieldCodec::DeltaZigzag
+            }
+            _ => FastFieldCodec::Bitpacked,
+        }
+    }
+
+    pub fn close_field(self) -> io::Result<()> {
+        let codec = self.select_codec();
+        match codec {
+            FastFieldCodec::Bitpacked => {
+                let mut bit_packer = BitPacker::new();
+                for &val in &self.vals {
+                    bit_packer.write(val - self.min_value, self.num_bits, self.write)?;
+                }
+                bit_packer.close(self.write)?;
+            }
+            FastFieldCodec::DeltaZigzag => {
+                serialize_delta_zigzag(&self.vals[..], self.write)?;
+            }
+        }
+        write_footer(codec, self.format_version, self.write)
     }
 }
 
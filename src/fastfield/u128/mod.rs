@@ -0,0 +1,71 @@
+mod reader;
+mod writer;
+
+pub use self::reader::U128FastFieldReader;
+pub use self::writer::U128FastFieldWriter;
+
+#[cfg(test)]
+mod tests {
+    use crate::schema::{Schema, FAST};
+    use crate::Index;
+    use futures::executor::block_on;
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_ip_addr_fast_field() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let ip_field = schema_builder.add_ip_addr_field("ip", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(ip_field => IpAddr::from([10, 0, 0, 1])));
+        index_writer.add_document(doc!(
+            ip_field => IpAddr::from_str("2001:db8::ff00:42:8329").unwrap()
+        ));
+        index_writer.add_document(doc!());
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let segment_reader = searcher.segment_reader(0);
+        let ip_reader = segment_reader.fast_fields().ip_addr(ip_field)?;
+        assert_eq!(
+            Ipv6Addr::from(ip_reader.get(0)),
+            Ipv4Addr::new(10, 0, 0, 1).to_ipv6_mapped()
+        );
+        assert_eq!(
+            Ipv6Addr::from(ip_reader.get(1)),
+            Ipv6Addr::from_str("2001:db8::ff00:42:8329").unwrap()
+        );
+        assert_eq!(ip_reader.get(2), 0u128);
+        Ok(())
+    }
+
+    #[test]
+    fn test_ip_addr_fast_field_after_merge() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let ip_field = schema_builder.add_ip_addr_field("ip", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(ip_field => IpAddr::from([10, 0, 0, 1])));
+        index_writer.commit()?;
+        index_writer.add_document(doc!(ip_field => IpAddr::from_str("::1").unwrap()));
+        index_writer.commit()?;
+        let segment_ids = index.searchable_segment_ids()?;
+        block_on(index_writer.merge(&segment_ids))?;
+        let reader = index.reader()?;
+        let searcher = reader.searcher();
+        assert_eq!(searcher.segment_readers().len(), 1);
+        let ip_reader = searcher.segment_reader(0).fast_fields().ip_addr(ip_field)?;
+        let mut ip_addrs: Vec<Ipv6Addr> = (0..2)
+            .map(|doc| Ipv6Addr::from(ip_reader.get(doc)))
+            .collect();
+        ip_addrs.sort();
+        assert_eq!(
+            ip_addrs,
+            vec![
+                Ipv6Addr::from(1u128),
+                Ipv4Addr::new(10, 0, 0, 1).to_ipv6_mapped()
+            ]
+        );
+        Ok(())
+    }
+}
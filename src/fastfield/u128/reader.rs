@@ -0,0 +1,34 @@
+use crate::fastfield::FastFieldReader;
+use crate::DocId;
+
+/// Reader for `u128` fast fields.
+///
+/// The reader is implemented as two `u64` fast fields,
+/// respectively holding the 64 high bits and the 64 low bits
+/// of the value of each document.
+#[derive(Clone)]
+pub struct U128FastFieldReader {
+    high_bits_reader: FastFieldReader<u64>,
+    low_bits_reader: FastFieldReader<u64>,
+}
+
+impl U128FastFieldReader {
+    pub(crate) fn open(
+        high_bits_reader: FastFieldReader<u64>,
+        low_bits_reader: FastFieldReader<u64>,
+    ) -> U128FastFieldReader {
+        U128FastFieldReader {
+            high_bits_reader,
+            low_bits_reader,
+        }
+    }
+
+    /// Returns the value associated to the given `doc`.
+    ///
+    /// Documents without any value are associated to `0`.
+    pub fn get(&self, doc: DocId) -> u128 {
+        let high_bits = u128::from(self.high_bits_reader.get(doc));
+        let low_bits = u128::from(self.low_bits_reader.get(doc));
+        (high_bits << 64) | low_bits
+    }
+}
@@ -0,0 +1,71 @@
+use std::io;
+
+use crate::fastfield::serializer::FastFieldSerializer;
+use crate::schema::{Document, Field, Value};
+
+/// Writer for `u128` fast fields.
+///
+/// It is used for ip address fields, which values are
+/// written as IPv6 addresses.
+///
+/// Values are serialized as two `u64` fast fields, respectively
+/// holding the 64 high bits (`idx=0`) and the 64 low bits (`idx=1`)
+/// of the value.
+pub struct U128FastFieldWriter {
+    field: Field,
+    vals: Vec<u128>,
+}
+
+impl U128FastFieldWriter {
+    /// Creates a new `U128FastFieldWriter`
+    pub fn new(field: Field) -> U128FastFieldWriter {
+        U128FastFieldWriter {
+            field,
+            vals: Vec::new(),
+        }
+    }
+
+    /// Access the field associated to the `U128FastFieldWriter`
+    pub fn field(&self) -> Field {
+        self.field
+    }
+
+    /// Returns the number of bytes used to buffer the values.
+    pub fn mem_usage(&self) -> usize {
+        self.vals.capacity() * std::mem::size_of::<u128>()
+    }
+
+    /// Records the value of the next document.
+    pub fn add_val(&mut self, val: u128) {
+        self.vals.push(val);
+    }
+
+    /// Records the value associated to the field for a new document.
+    ///
+    /// If the document has more than one value for the given field,
+    /// only the first one is taken in account. If it has none, `0` is recorded.
+    pub fn add_document(&mut self, doc: &Document) {
+        let val = match doc.get_first(self.field) {
+            Some(Value::IpAddr(ip_addr)) => u128::from(*ip_addr),
+            Some(value) => panic!("Expected an ip address field, got {:?} ", value),
+            None => 0u128,
+        };
+        self.add_val(val);
+    }
+
+    /// Serializes the fast field values by pushing them to the `FastFieldSerializer`.
+    pub fn serialize(&self, serializer: &mut FastFieldSerializer) -> io::Result<()> {
+        for (idx, shift) in [64u32, 0u32].iter().enumerate() {
+            let half_vals = self.vals.iter().map(|&val| (val >> shift) as u64);
+            let min_value = half_vals.clone().min().unwrap_or(0u64);
+            let max_value = half_vals.clone().max().unwrap_or(0u64);
+            let mut single_field_serializer =
+                serializer.new_u64_fast_field_with_idx(self.field, min_value, max_value, idx)?;
+            for half_val in half_vals {
+                single_field_serializer.add_val(half_val)?;
+            }
+            single_field_serializer.close_field()?;
+        }
+        Ok(())
+    }
+}
@@ -2,11 +2,14 @@ use super::multivalued::MultiValuedFastFieldWriter;
 use crate::common;
 use crate::common::BinarySerializable;
 use crate::common::VInt;
-use crate::fastfield::{BytesFastFieldWriter, FastFieldSerializer};
+use crate::fastfield::{
+    BytesFastFieldWriter, DenseVectorFastFieldWriter, FastFieldSerializer, U128FastFieldWriter,
+};
 use crate::postings::UnorderedTermId;
 use crate::schema::{Cardinality, Document, Field, FieldEntry, FieldType, Schema};
 use crate::termdict::TermOrdinal;
 use fnv::FnvHashMap;
+use roaring::RoaringBitmap;
 use std::collections::HashMap;
 use std::io;
 
@@ -15,12 +18,15 @@ pub struct FastFieldsWriter {
     single_value_writers: Vec<IntFastFieldWriter>,
     multi_values_writers: Vec<MultiValuedFastFieldWriter>,
     bytes_value_writers: Vec<BytesFastFieldWriter>,
+    u128_value_writers: Vec<U128FastFieldWriter>,
+    dense_vector_writers: Vec<DenseVectorFastFieldWriter>,
 }
 
 fn fast_field_default_value(field_entry: &FieldEntry) -> u64 {
     match *field_entry.field_type() {
         FieldType::I64(_) | FieldType::Date(_) => common::i64_to_u64(0i64),
         FieldType::F64(_) => common::f64_to_u64(0.0f64),
+        FieldType::DocumentBoost(_) => common::f64_to_u64(1.0f64),
         _ => 0u64,
     }
 }
@@ -31,6 +37,8 @@ impl FastFieldsWriter {
         let mut single_value_writers = Vec::new();
         let mut multi_values_writers = Vec::new();
         let mut bytes_value_writers = Vec::new();
+        let mut u128_value_writers = Vec::new();
+        let mut dense_vector_writers = Vec::new();
 
         for (field, field_entry) in schema.fields() {
             match field_entry.field_type() {
@@ -49,9 +57,33 @@ impl FastFieldsWriter {
                             let fast_field_writer = MultiValuedFastFieldWriter::new(field, false);
                             multi_values_writers.push(fast_field_writer);
                         }
+                        Some(Cardinality::Optional) => {
+                            let mut fast_field_writer = IntFastFieldWriter::new(field);
+                            fast_field_writer
+                                .set_val_if_missing(fast_field_default_value(field_entry));
+                            fast_field_writer.record_null_bitmap();
+                            single_value_writers.push(fast_field_writer);
+                        }
                         None => {}
                     }
                 }
+                FieldType::IpAddr(ref ip_addr_options) if ip_addr_options.is_fast() => {
+                    u128_value_writers.push(U128FastFieldWriter::new(field));
+                }
+                FieldType::GeoPoint(ref geo_point_options) if geo_point_options.is_fast() => {
+                    single_value_writers.push(IntFastFieldWriter::new(field));
+                }
+                FieldType::DocumentBoost(_) => {
+                    let mut fast_field_writer = IntFastFieldWriter::new(field);
+                    fast_field_writer.set_val_if_missing(fast_field_default_value(field_entry));
+                    single_value_writers.push(fast_field_writer);
+                }
+                FieldType::DenseVector(ref dense_vector_options) => {
+                    dense_vector_writers.push(DenseVectorFastFieldWriter::new(
+                        field,
+                        dense_vector_options.dims(),
+                    ));
+                }
                 FieldType::HierarchicalFacet => {
                     let fast_field_writer = MultiValuedFastFieldWriter::new(field, true);
                     multi_values_writers.push(fast_field_writer);
@@ -69,6 +101,8 @@ impl FastFieldsWriter {
             single_value_writers,
             multi_values_writers,
             bytes_value_writers,
+            u128_value_writers,
+            dense_vector_writers,
         }
     }
 
@@ -116,6 +150,40 @@ impl FastFieldsWriter {
         for field_writer in &mut self.bytes_value_writers {
             field_writer.add_document(doc);
         }
+        for field_writer in &mut self.u128_value_writers {
+            field_writer.add_document(doc);
+        }
+        for field_writer in &mut self.dense_vector_writers {
+            field_writer.add_document(doc);
+        }
+    }
+
+    /// Returns the number of bytes used to buffer the values of the fast fields.
+    pub fn mem_usage(&self) -> usize {
+        self.single_value_writers
+            .iter()
+            .map(IntFastFieldWriter::mem_usage)
+            .sum::<usize>()
+            + self
+                .multi_values_writers
+                .iter()
+                .map(MultiValuedFastFieldWriter::mem_usage)
+                .sum::<usize>()
+            + self
+                .bytes_value_writers
+                .iter()
+                .map(BytesFastFieldWriter::mem_usage)
+                .sum::<usize>()
+            + self
+                .u128_value_writers
+                .iter()
+                .map(U128FastFieldWriter::mem_usage)
+                .sum::<usize>()
+            + self
+                .dense_vector_writers
+                .iter()
+                .map(DenseVectorFastFieldWriter::mem_usage)
+                .sum::<usize>()
     }
 
     /// Serializes all of the `FastFieldWriter`s by pushing them in
@@ -136,6 +204,12 @@ impl FastFieldsWriter {
         for field_writer in &self.bytes_value_writers {
             field_writer.serialize(serializer)?;
         }
+        for field_writer in &self.u128_value_writers {
+            field_writer.serialize(serializer)?;
+        }
+        for field_writer in &self.dense_vector_writers {
+            field_writer.serialize(serializer)?;
+        }
         Ok(())
     }
 }
@@ -155,6 +229,9 @@ impl FastFieldsWriter {
 /// Both u64, i64 and f64 use the same writer.
 /// i64 and f64 are just remapped to the `0..2^64 - 1`
 /// using `common::i64_to_u64` and `common::f64_to_u64`.
+///
+/// For optional fast fields, the writer also records
+/// the null bitmap of the documents having a value.
 pub struct IntFastFieldWriter {
     field: Field,
     vals: Vec<u8>,
@@ -162,6 +239,7 @@ pub struct IntFastFieldWriter {
     val_if_missing: u64,
     val_min: u64,
     val_max: u64,
+    null_bitmap: Option<RoaringBitmap>,
 }
 
 impl IntFastFieldWriter {
@@ -174,6 +252,7 @@ impl IntFastFieldWriter {
             val_if_missing: 0u64,
             val_min: u64::max_value(),
             val_max: 0,
+            null_bitmap: None,
         }
     }
 
@@ -182,6 +261,16 @@ impl IntFastFieldWriter {
         self.field
     }
 
+    /// Returns the number of bytes used to buffer the values.
+    pub fn mem_usage(&self) -> usize {
+        self.vals.capacity()
+            + self
+                .null_bitmap
+                .as_ref()
+                .map(RoaringBitmap::serialized_size)
+                .unwrap_or(0)
+    }
+
     /// Sets the default value.
     ///
     /// This default value is recorded for documents if
@@ -190,6 +279,12 @@ impl IntFastFieldWriter {
         self.val_if_missing = val_if_missing;
     }
 
+    /// Records the documents having a value in a null bitmap,
+    /// serialized along with the values.
+    fn record_null_bitmap(&mut self) {
+        self.null_bitmap = Some(RoaringBitmap::new());
+    }
+
     /// Records a new value.
     ///
     /// The n-th value being recorded is implicitely
@@ -230,6 +325,11 @@ impl IntFastFieldWriter {
     /// Extract the fast field value from the document
     /// (or use the default value) and records it.
     pub fn add_document(&mut self, doc: &Document) {
+        if let Some(null_bitmap) = self.null_bitmap.as_mut() {
+            if doc.get_first(self.field).is_some() {
+                null_bitmap.insert(self.val_count as u32);
+            }
+        }
         let val = self.extract_val(doc);
         self.add_val(val);
     }
@@ -242,7 +342,11 @@ impl IntFastFieldWriter {
             (self.val_min, self.val_max)
         };
 
-        let mut single_field_serializer = serializer.new_u64_fast_field(self.field, min, max)?;
+        let mut single_field_serializer = if let Some(null_bitmap) = self.null_bitmap.as_ref() {
+            serializer.new_nullable_u64_fast_field(self.field, min, max, null_bitmap)?
+        } else {
+            serializer.new_u64_fast_field(self.field, min, max)?
+        };
 
         let mut cursor = self.vals.as_slice();
         while let Ok(VInt(val)) = VInt::deserialize(&mut cursor) {
@@ -26,3 +26,76 @@ pub use self::serializer::FieldNormsSerializer;
 pub use self::writer::FieldNormsWriter;
 
 use self::code::{fieldnorm_to_id, id_to_fieldnorm};
+
+#[cfg(test)]
+mod tests {
+    use crate::collector::TopDocs;
+    use crate::query::TermQuery;
+    use crate::schema::{IndexRecordOption, Schema, TextFieldIndexing, TextOptions, TEXT};
+    use crate::{Index, SegmentReader, Term};
+    use futures::executor::block_on;
+
+    #[test]
+    fn test_fieldnorms_disabled() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let tags = schema_builder.add_text_field(
+            "tags",
+            TextOptions::default().set_indexing_options(
+                TextFieldIndexing::default()
+                    .set_index_option(IndexRecordOption::WithFreqs)
+                    .set_fieldnorms(false),
+            ),
+        );
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text => "a", tags => "a"));
+        index_writer.add_document(doc!(text => "a b c d", tags => "a b c d"));
+        index_writer.commit()?;
+        index_writer.add_document(doc!(text => "b", tags => "b"));
+        index_writer.commit()?;
+
+        let reader = index.reader()?;
+        let searcher = reader.searcher();
+        let segment_reader = searcher.segment_reader(0);
+        let has_fieldnorms = |segment_reader: &SegmentReader, field| -> crate::Result<bool> {
+            Ok(segment_reader
+                .space_usage()?
+                .fieldnorms()
+                .fields()
+                .any(|(&fieldnorm_field, _)| fieldnorm_field == field))
+        };
+        assert!(has_fieldnorms(segment_reader, text)?);
+        assert!(!has_fieldnorms(segment_reader, tags)?);
+        let fieldnorm_reader = segment_reader.get_fieldnorms_reader(tags)?;
+        assert_eq!(fieldnorm_reader.fieldnorm(0), 1);
+        assert_eq!(fieldnorm_reader.fieldnorm(1), 1);
+        assert_eq!(segment_reader.get_fieldnorms_reader(text)?.fieldnorm(1), 4);
+
+        let top_docs = |field| -> crate::Result<Vec<f32>> {
+            let query = TermQuery::new(
+                Term::from_field_text(field, "a"),
+                IndexRecordOption::WithFreqs,
+            );
+            let top_docs = searcher.search(&query, &TopDocs::with_limit(2))?;
+            Ok(top_docs.into_iter().map(|(score, _)| score).collect())
+        };
+        let text_scores = top_docs(text)?;
+        assert!(text_scores[0] > text_scores[1]);
+        let tags_scores = top_docs(tags)?;
+        assert!((tags_scores[0] - tags_scores[1]).abs() < 1e-5);
+
+        let segment_ids = index.searchable_segment_ids()?;
+        block_on(index_writer.merge(&segment_ids))?;
+        index_writer.wait_merging_threads()?;
+        reader.reload()?;
+        let searcher = reader.searcher();
+        assert_eq!(searcher.segment_readers().len(), 1);
+        let segment_reader = searcher.segment_reader(0);
+        assert!(!has_fieldnorms(segment_reader, tags)?);
+        assert_eq!(segment_reader.get_fieldnorms_reader(tags)?.fieldnorm(2), 1);
+        assert_eq!(segment_reader.get_fieldnorms_reader(text)?.fieldnorm(2), 1);
+        assert_eq!(segment_reader.inverted_index(tags)?.total_num_tokens(), 6);
+        Ok(())
+    }
+}
@@ -27,6 +27,11 @@ impl FieldNormReaders {
         })
     }
 
+    /// Returns the part of the field norm file storing `field`.
+    pub(crate) fn file_slices(&self, field: Field) -> Vec<FileSlice> {
+        self.data.open_read_all(field)
+    }
+
     /// Returns the FieldNormReader for a specific field.
     pub fn get_field(&self, field: Field) -> crate::Result<Option<FieldNormReader>> {
         if let Some(file) = self.data.open_read(field) {
@@ -13,7 +13,7 @@ use std::{io, iter};
 /// byte per document per field.
 pub struct FieldNormsWriter {
     fields: Vec<Field>,
-    fieldnorms_buffer: Vec<Vec<u8>>,
+    fieldnorms_buffer: Vec<Option<Vec<u8>>>,
 }
 
 impl FieldNormsWriter {
@@ -23,7 +23,7 @@ impl FieldNormsWriter {
         schema
             .fields()
             .filter_map(|(field, field_entry)| {
-                if field_entry.is_indexed() {
+                if field_entry.field_type().has_fieldnorms() {
                     Some(field)
                 } else {
                     None
@@ -42,21 +42,34 @@ impl FieldNormsWriter {
             .max()
             .map(|max_field_id| max_field_id as usize + 1)
             .unwrap_or(0);
+        let mut fieldnorms_buffer = iter::repeat_with(|| None)
+            .take(max_field)
+            .collect::<Vec<_>>();
+        for field in &fields {
+            fieldnorms_buffer[field.field_id() as usize] = Some(Vec::new());
+        }
         FieldNormsWriter {
             fields,
-            fieldnorms_buffer: iter::repeat_with(Vec::new)
-                .take(max_field)
-                .collect::<Vec<_>>(),
+            fieldnorms_buffer,
         }
     }
 
+    /// Returns the number of bytes used to buffer the fieldnorms.
+    pub fn mem_usage(&self) -> usize {
+        self.fieldnorms_buffer
+            .iter()
+            .flatten()
+            .map(Vec::capacity)
+            .sum()
+    }
+
     /// Ensure that all documents in 0..max_doc have a byte associated with them
     /// in each of the fieldnorm vectors.
     ///
     /// Will extend with 0-bytes for documents that have not been seen.
     pub fn fill_up_to_max_doc(&mut self, max_doc: DocId) {
-        for field in self.fields.iter() {
-            self.fieldnorms_buffer[field.field_id() as usize].resize(max_doc as usize, 0u8);
+        for fieldnorm_buffer in self.fieldnorms_buffer.iter_mut().flatten() {
+            fieldnorm_buffer.resize(max_doc as usize, 0u8);
         }
     }
 
@@ -65,11 +78,17 @@ impl FieldNormsWriter {
     /// Will internally convert the u32 `fieldnorm` value to the appropriate byte
     /// to approximate the field norm in less space.
     ///
+    /// The fieldnorms of the fields that do not have fieldnorms are ignored.
+    ///
     /// * doc       - the document id
     /// * field     - the field being set
     /// * fieldnorm - the number of terms present in document `doc` in field `field`
     pub fn record(&mut self, doc: DocId, field: Field, fieldnorm: u32) {
-        let fieldnorm_buffer: &mut Vec<u8> = &mut self.fieldnorms_buffer[field.field_id() as usize];
+        let fieldnorm_buffer: &mut Vec<u8> =
+            match self.fieldnorms_buffer.get_mut(field.field_id() as usize) {
+                Some(Some(fieldnorm_buffer)) => fieldnorm_buffer,
+                _ => return,
+            };
         assert!(
             fieldnorm_buffer.len() <= doc as usize,
             "Cannot register a given fieldnorm twice"
@@ -82,7 +101,9 @@ impl FieldNormsWriter {
     /// Serialize the seen fieldnorm values to the serializer for all fields.
     pub fn serialize(&self, mut fieldnorms_serializer: FieldNormsSerializer) -> io::Result<()> {
         for &field in self.fields.iter() {
-            let fieldnorm_values: &[u8] = &self.fieldnorms_buffer[field.field_id() as usize][..];
+            let fieldnorm_values: &[u8] = self.fieldnorms_buffer[field.field_id() as usize]
+                .as_deref()
+                .unwrap_or(&[]);
             fieldnorms_serializer.serialize_field(field, fieldnorm_values)?;
         }
         fieldnorms_serializer.close()?;
@@ -1,8 +1,12 @@
-use super::operation::{AddOperation, UserOperation};
+use super::operation::{AddOperation, DeletePolicy, UserOperation};
 use super::segment_updater::SegmentUpdater;
 use super::PreparedCommit;
+use crate::collector::{check_sort_field, DocSetCollector, Order};
 use crate::common::BitSet;
+use crate::core::index_delta;
+use crate::core::index_stats::merge_policy_name;
 use crate::core::Index;
+use crate::core::IndexStats;
 use crate::core::Segment;
 use crate::core::SegmentComponent;
 use crate::core::SegmentId;
@@ -17,21 +21,30 @@ use crate::indexer::delete_queue::{DeleteCursor, DeleteQueue};
 use crate::indexer::doc_opstamp_mapping::DocToOpstampMapping;
 use crate::indexer::operation::DeleteOperation;
 use crate::indexer::stamper::Stamper;
-use crate::indexer::MergePolicy;
+use crate::indexer::wal::{Wal, WalOperation, WalRecord, WalSync};
 use crate::indexer::SegmentEntry;
 use crate::indexer::SegmentWriter;
+use crate::indexer::{CancellationToken, MergeCallback, MergePolicy, NoMergePolicy};
+use crate::query::TermQuery;
+use crate::reader::{LiveSearcher, LiveSearcherRegistry};
 use crate::schema::Document;
+use crate::schema::Field;
 use crate::schema::IndexRecordOption;
+use crate::schema::PartialDocument;
 use crate::schema::Term;
 use crate::Opstamp;
+use crate::Searcher;
 use crossbeam::channel;
 use futures::executor::block_on;
 use futures::future::Future;
 use smallvec::smallvec;
 use smallvec::SmallVec;
+use std::io::Write;
 use std::mem;
 use std::ops::Range;
-use std::sync::Arc;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::thread;
 use std::thread::JoinHandle;
 
@@ -47,6 +60,9 @@ pub const HEAP_SIZE_MAX: usize = u32::max_value() as usize - MARGIN_IN_BYTES;
 // reaches `PIPELINE_MAX_SIZE_IN_DOCS`
 const PIPELINE_MAX_SIZE_IN_DOCS: usize = 10_000;
 
+// Ratio of deleted documents above which `force_merge_deletes` merges a segment.
+const FORCE_MERGE_DELETES_RATIO: f32 = 0.1;
+
 // Group of operations.
 // Most of the time, users will send operation one-by-one, but it can be useful to
 // send them as a small block to ensure that
@@ -63,6 +79,12 @@ type OperationReceiver = channel::Receiver<OperationGroup>;
 /// indexing queue.
 /// Each indexing thread builds its own independent `Segment`, via
 /// a `SegmentWriter` object.
+///
+/// Adding and deleting documents only require a shared reference, so that
+/// several producer threads can feed the same `IndexWriter` concurrently,
+/// typically through an `Arc<RwLock<IndexWriter>>`: the documents are spread
+/// over the in-memory buffers of the indexing threads, and a commit publishes
+/// all of them atomically. See the `multiple_producer` example.
 pub struct IndexWriter {
     // the lock is just used to bind the
     // lifetime of the lock with that of the IndexWriter.
@@ -72,6 +94,9 @@ pub struct IndexWriter {
 
     heap_size_in_bytes_per_thread: usize,
 
+    // Sum of the memory usage reported by the indexing threads.
+    memory_usage: Arc<AtomicUsize>,
+
     workers_join_handle: Vec<JoinHandle<crate::Result<()>>>,
 
     operation_receiver: OperationReceiver,
@@ -87,6 +112,14 @@ pub struct IndexWriter {
 
     stamper: Stamper,
     committed_opstamp: Opstamp,
+
+    wal: Option<Arc<Wal>>,
+
+    live_searchers: LiveSearcherRegistry,
+
+    delete_policy: DeletePolicy,
+    // Delete operations since the last commit, only recorded with `DeletePolicy::Error`.
+    uncommitted_delete_operations: Mutex<Vec<DeleteOperation>>,
 }
 
 fn compute_deleted_bitset(
@@ -115,6 +148,11 @@ fn compute_deleted_bitset(
             let mut deleted_doc = docset.doc();
             while deleted_doc != TERMINATED {
                 if deleted_doc < limit_doc {
+                    if !segment_reader.is_deleted(deleted_doc)
+                        && !delete_bitset.contains(deleted_doc)
+                    {
+                        delete_op.set_matched();
+                    }
                     delete_bitset.insert(deleted_doc);
                     might_have_changed = true;
                 }
@@ -189,8 +227,40 @@ pub(crate) fn advance_deletes(
     Ok(())
 }
 
+/// Share of the `IndexWriter` memory usage of an indexing thread.
+///
+/// It is withdrawn from the total when the thread is done with its segment.
+struct MemoryUsageReport<'a> {
+    total: &'a AtomicUsize,
+    reported: usize,
+}
+
+impl<'a> MemoryUsageReport<'a> {
+    fn new(total: &'a AtomicUsize) -> MemoryUsageReport<'a> {
+        MemoryUsageReport { total, reported: 0 }
+    }
+
+    fn report(&mut self, mem_usage: usize) {
+        if mem_usage >= self.reported {
+            self.total
+                .fetch_add(mem_usage - self.reported, Ordering::Relaxed);
+        } else {
+            self.total
+                .fetch_sub(self.reported - mem_usage, Ordering::Relaxed);
+        }
+        self.reported = mem_usage;
+    }
+}
+
+impl<'a> Drop for MemoryUsageReport<'a> {
+    fn drop(&mut self) {
+        self.report(0);
+    }
+}
+
 fn index_documents(
     memory_budget: usize,
+    memory_usage: &AtomicUsize,
     segment: Segment,
     grouped_document_iterator: &mut dyn Iterator<Item = OperationGroup>,
     segment_updater: &mut SegmentUpdater,
@@ -199,11 +269,13 @@ fn index_documents(
     let schema = segment.schema();
 
     let mut segment_writer = SegmentWriter::for_segment(memory_budget, segment.clone(), &schema)?;
+    let mut memory_usage_report = MemoryUsageReport::new(memory_usage);
     for document_group in grouped_document_iterator {
         for doc in document_group {
             segment_writer.add_document(doc, &schema)?;
         }
         let mem_usage = segment_writer.mem_usage();
+        memory_usage_report.report(mem_usage);
         if mem_usage >= memory_budget - MARGIN_IN_BYTES {
             info!(
                 "Buffer limit reached, flushing segment with maxdoc={}.",
@@ -225,7 +297,7 @@ fn index_documents(
 
     let doc_opstamps: Vec<Opstamp> = segment_writer.finalize()?;
 
-    let segment_with_max_doc = segment.with_max_doc(max_doc);
+    let segment_with_max_doc = segment.with_max_doc(max_doc).with_num_bytes();
 
     let last_docstamp: Opstamp = *(doc_opstamps.last().unwrap());
 
@@ -242,6 +314,10 @@ fn index_documents(
         delete_bitset_opt,
     );
     block_on(segment_updater.schedule_add_segment(segment_entry))?;
+    segment_with_max_doc
+        .index()
+        .metrics_collector()
+        .record_indexing(u64::from(max_doc));
     Ok(true)
 }
 
@@ -275,6 +351,19 @@ fn apply_deletes(
     })
 }
 
+/// Waits for the indexing workers to terminate.
+fn join_indexing_workers(
+    workers_join_handle: Vec<JoinHandle<crate::Result<()>>>,
+) -> crate::Result<()> {
+    for worker_handle in workers_join_handle {
+        let indexing_worker_result = worker_handle
+            .join()
+            .map_err(|e| TantivyError::ErrorInThread(format!("{:?}", e)))?;
+        indexing_worker_result?;
+    }
+    Ok(())
+}
+
 impl IndexWriter {
     /// Create a new index writer. Attempts to acquire a lockfile.
     ///
@@ -323,6 +412,7 @@ impl IndexWriter {
             _directory_lock: Some(directory_lock),
 
             heap_size_in_bytes_per_thread,
+            memory_usage: Arc::default(),
             index: index.clone(),
 
             operation_receiver: document_receiver,
@@ -339,6 +429,12 @@ impl IndexWriter {
             stamper,
 
             worker_id: 0,
+
+            wal: None,
+
+            live_searchers: LiveSearcherRegistry::default(),
+            delete_policy: DeletePolicy::default(),
+            uncommitted_delete_operations: Mutex::default(),
         };
         index_writer.start_workers()?;
         Ok(index_writer)
@@ -385,6 +481,18 @@ impl IndexWriter {
         block_on(self.segment_updater.schedule_add_segment(segment_entry))
     }
 
+    /// Returns an estimate of the number of bytes used by the indexing threads
+    /// to buffer the documents that have not been flushed to a segment yet.
+    ///
+    /// Each indexing thread flushes its segment once its own share of memory,
+    /// `overall_heap_size_in_bytes / num_threads`, is almost full: the memory usage
+    /// stays close to `overall_heap_size_in_bytes` at most, without requiring any commit.
+    /// Once the indexing threads are busy, `add_document` blocks until they
+    /// catch up with the pending documents.
+    pub fn memory_usage(&self) -> usize {
+        self.memory_usage.load(Ordering::Relaxed)
+    }
+
     /// Creates a new segment.
     ///
     /// This method is useful only for users trying to do complex
@@ -406,6 +514,7 @@ impl IndexWriter {
         let mut delete_cursor = self.delete_queue.cursor();
 
         let mem_budget = self.heap_size_in_bytes_per_thread;
+        let memory_usage = self.memory_usage.clone();
         let index = self.index.clone();
         let join_handle: JoinHandle<crate::Result<()>> = thread::Builder::new()
             .name(format!("thrd-tantivy-index{}", self.worker_id))
@@ -436,6 +545,7 @@ impl IndexWriter {
                     let segment = index.new_segment();
                     index_documents(
                         mem_budget,
+                        &memory_usage,
                         segment,
                         &mut document_iterator,
                         &mut segment_updater,
@@ -448,6 +558,14 @@ impl IndexWriter {
         Ok(())
     }
 
+    /// Returns the [`Index::stats`](../struct.Index.html#method.stats) of the index,
+    /// along with the name of the merge policy of the writer.
+    pub fn stats(&self) -> crate::Result<IndexStats> {
+        let mut stats = self.index.stats()?;
+        stats.merge_policy_name = Some(merge_policy_name(&*self.get_merge_policy()));
+        Ok(stats)
+    }
+
     /// Accessor to the merge policy.
     pub fn get_merge_policy(&self) -> Arc<dyn MergePolicy> {
         self.segment_updater.get_merge_policy()
@@ -458,6 +576,142 @@ impl IndexWriter {
         self.segment_updater.set_merge_policy(merge_policy);
     }
 
+    /// Registers a callback notified of the progress of the merges.
+    ///
+    /// It applies to the merges started after the call.
+    pub fn set_merge_callback(&self, merge_callback: Box<dyn MergeCallback>) {
+        self.segment_updater.set_merge_callback(merge_callback);
+    }
+
+    /// Sets the token that cancels the merges.
+    ///
+    /// It applies to the merges started after the call. A cancelled merge
+    /// returns `TantivyError::Cancelled`, and its segments are left as they were.
+    /// A new token must be set to merge again after a cancellation.
+    pub fn set_merge_cancellation_token(&self, cancellation_token: CancellationToken) {
+        self.segment_updater
+            .set_merge_cancellation_token(cancellation_token);
+    }
+
+    /// Sets how the delete operations that do not match any document are handled.
+    ///
+    /// With `DeletePolicy::Error`, the commit fails with `TantivyError::InvalidArgument` if
+    /// one of its delete operations did not delete any document, i.e. if no document
+    /// added before the operation, and not deleted yet, contains its term. Nothing
+    /// is committed, and the `IndexWriter` should then be rolled back.
+    ///
+    /// The default policy is `DeletePolicy::Ignore`. The policy applies to the
+    /// delete operations received after the call.
+    pub fn set_delete_policy(&mut self, delete_policy: DeletePolicy) {
+        self.delete_policy = delete_policy;
+    }
+
+    /// Enables the write-ahead log of the `IndexWriter`, stored in the file at `path`.
+    ///
+    /// Once it is enabled, the operations are written to the log before being
+    /// sent to the indexing threads, and the log is emptied by each successful
+    /// `commit()` and by `rollback()`. It should be enabled right after creating
+    /// the `IndexWriter`.
+    ///
+    /// If the file already exists, for instance because the process crashed before
+    /// committing, the operations it contains that are more recent than the last commit
+    /// are first replayed, in the order of their opstamps. They receive new opstamps,
+    /// and are not committed. Returns the number of replayed operations.
+    ///
+    /// The log is a regular file, outside of the `Directory` of the index, and
+    /// should not be shared by several indexes. The commits made through
+    /// `PreparedCommit::commit_future` do not empty the log, but the operations
+    /// they committed are not replayed.
+    ///
+    /// # Panics
+    /// Once the log is enabled, `add_document`, `delete_term` and `run` panic if the
+    /// log cannot be written. The other methods return an error instead.
+    pub fn enable_wal<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        wal_sync: WalSync,
+    ) -> crate::Result<usize> {
+        if self.wal.is_some() {
+            return Err(TantivyError::InvalidArgument(
+                "The write-ahead log is already enabled".to_string(),
+            ));
+        }
+        let path = path.as_ref();
+        let last_commit_opstamp = self.index.load_metas()?.opstamp;
+        let mut records = Wal::read(path)?;
+        // `delete_all_documents` empties the log before logging the opstamp of the
+        // last commit. As it reverts the opstamps, all of the operations logged after
+        // it are replayed, unless a commit happened since then.
+        let replay_all = matches!(
+            records.first(),
+            Some((opstamp, WalRecord::DeleteAll)) if *opstamp == last_commit_opstamp
+        );
+        if replay_all {
+            records[1..].sort_by_key(|(opstamp, _)| *opstamp);
+        } else {
+            records.retain(|(opstamp, record)| {
+                !matches!(record, WalRecord::DeleteAll) && *opstamp > last_commit_opstamp
+            });
+            records.sort_by_key(|(opstamp, _)| *opstamp);
+        }
+        let num_records = records.len();
+
+        // The operations are logged again, with their new opstamps, into a new log
+        // replacing the former one once the replay is complete.
+        let replay_path = path.with_extension("replay");
+        self.wal = Some(Arc::new(Wal::create(&replay_path, wal_sync)?));
+        for (_, record) in records {
+            match record {
+                WalRecord::Add(document) => {
+                    self.add_document(document);
+                }
+                WalRecord::Delete(term) => {
+                    self.delete_term(term);
+                }
+                WalRecord::DeleteAll => {
+                    self.delete_all_documents()?;
+                }
+            }
+        }
+        let wal = self
+            .wal
+            .take()
+            .and_then(|wal| Arc::try_unwrap(wal).ok())
+            .expect("The write-ahead log was just created. This is a bug, please report.");
+        self.wal = Some(Arc::new(wal.persist(path)?));
+        Ok(num_records)
+    }
+
+    fn log_operations<'a, I>(&self, operations: I) -> std::io::Result<()>
+    where
+        I: IntoIterator<Item = (Opstamp, WalOperation<'a>)>,
+    {
+        if let Some(wal) = &self.wal {
+            let operations: Vec<(Opstamp, WalOperation<'a>)> = operations.into_iter().collect();
+            wal.append(&operations)?;
+        }
+        Ok(())
+    }
+
+    /// Registers a `LiveSearcher`, to notify it synchronously after each successful commit.
+    ///
+    /// The first search of the `LiveSearcher` following a commit then sees its changes.
+    /// The `IndexWriter` does not keep the `LiveSearcher` alive.
+    pub fn register_live_searcher(&self, live_searcher: &LiveSearcher) {
+        self.live_searchers.register(live_searcher);
+    }
+
+    pub(crate) fn live_searchers(&self) -> &LiveSearcherRegistry {
+        &self.live_searchers
+    }
+
+    pub(crate) fn truncate_wal(&self) -> crate::Result<()> {
+        if let Some(wal) = &self.wal {
+            wal.truncate()?;
+        }
+        Ok(())
+    }
+
     fn start_workers(&mut self) -> crate::Result<()> {
         for _ in 0..self.num_threads {
             self.add_indexing_worker()?;
@@ -478,6 +732,10 @@ impl IndexWriter {
     /// Enables users to rebuild the index,
     /// by clearing and resubmitting necessary documents
     ///
+    /// This does not depend on the size of the index: the segments are removed
+    /// from the index rather than having their documents deleted, and their files
+    /// are garbage collected once the next commit is done. There is nothing left to merge.
+    ///
     /// ```rust
     /// use tantivy::collector::TopDocs;
     /// use tantivy::query::QueryParser;
@@ -511,8 +769,15 @@ impl IndexWriter {
     pub fn delete_all_documents(&self) -> crate::Result<Opstamp> {
         // Delete segments
         self.segment_updater.remove_all_segments();
+        // The previous delete operations are discarded together with the segments.
+        self.uncommitted_delete_operations.lock().unwrap().clear();
         // Return new stamp - reverted stamp
         self.stamper.revert(self.committed_opstamp);
+        if let Some(wal) = &self.wal {
+            wal.truncate()?;
+            let last_commit_opstamp = self.index.load_metas()?.opstamp;
+            wal.append(&[(last_commit_opstamp, WalOperation::DeleteAll)])?;
+        }
         Ok(self.committed_opstamp)
     }
 
@@ -523,11 +788,147 @@ impl IndexWriter {
         &mut self,
         segment_ids: &[SegmentId],
     ) -> impl Future<Output = crate::Result<SegmentMeta>> {
-        let merge_operation = self.segment_updater.make_merge_operation(segment_ids);
+        self.merge_with_sort(segment_ids, None)
+    }
+
+    fn merge_with_sort(
+        &mut self,
+        segment_ids: &[SegmentId],
+        sort_by: Option<(Field, Order)>,
+    ) -> impl Future<Output = crate::Result<SegmentMeta>> {
+        let merge_operation = self
+            .segment_updater
+            .make_sorted_merge_operation(segment_ids, sort_by);
         let segment_updater = self.segment_updater.clone();
         async move { segment_updater.start_merge(merge_operation)?.await }
     }
 
+    /// Rewrites a single segment without its deleted documents.
+    ///
+    /// The compacted segment replaces the original one exactly like a merged
+    /// segment replaces the segments it was merged from: the new `meta.json` is
+    /// written atomically when the compaction is over, and the files of the
+    /// original segment are garbage collected once they are not used anymore.
+    ///
+    /// Compacting does not block the `IndexWriter`: the returned future
+    /// can be awaited from another thread while documents keep being indexed.
+    ///
+    /// Returns an error if `segment_id` is not a segment of the `IndexWriter`.
+    pub fn compact_segment(
+        &self,
+        segment_id: SegmentId,
+    ) -> impl Future<Output = crate::Result<SegmentMeta>> {
+        let merge_operation = self.segment_updater.make_merge_operation(&[segment_id]);
+        let segment_updater = self.segment_updater.clone();
+        async move { segment_updater.start_merge(merge_operation)?.await }
+    }
+
+    /// Merges the committed segments of the index until there are at most
+    /// `max_num_segments` of them, and waits for the merges to complete.
+    ///
+    /// This is typically used to compact an index that will not be updated anymore,
+    /// as searching over fewer segments is faster. The merges also remove the
+    /// deleted documents: with `max_num_segments = 1`, the result is a single
+    /// segment without any deletes.
+    ///
+    /// The merge policy is suspended during the operation.
+    pub fn force_merge(&mut self, max_num_segments: u32) -> crate::Result<()> {
+        if max_num_segments == 0 {
+            return Err(TantivyError::InvalidArgument(
+                "The number of segments after a force merge must be at least 1".to_string(),
+            ));
+        }
+        self.run_forced_merges(None, |mut segment_metas| {
+            // Segments are spread in `max_num_segments` groups of even number of docs.
+            segment_metas.sort_by_key(|segment_meta| std::cmp::Reverse(segment_meta.num_docs()));
+            let num_groups = (max_num_segments as usize).min(segment_metas.len());
+            let mut groups: Vec<(u64, Vec<SegmentMeta>)> = vec![(0, Vec::new()); num_groups];
+            for segment_meta in segment_metas {
+                let group = groups
+                    .iter_mut()
+                    .min_by_key(|(num_docs, _)| *num_docs)
+                    .expect("There is at least one group");
+                group.0 += u64::from(segment_meta.num_docs());
+                group.1.push(segment_meta);
+            }
+            groups
+                .into_iter()
+                .map(|(_, segment_metas)| segment_metas)
+                .filter(|segment_metas| segment_metas.len() > 1 || segment_metas[0].has_deletes())
+                .map(|segment_metas| segment_metas.iter().map(SegmentMeta::id).collect())
+                .collect()
+        })
+    }
+
+    /// Merges on their own the committed segments with more than 10% of deleted
+    /// documents, in order to reclaim their space, and waits for the merges to complete.
+    ///
+    /// Contrary to [`force_merge`](#method.force_merge), the number of segments
+    /// of the index does not change.
+    pub fn force_merge_deletes(&mut self) -> crate::Result<()> {
+        self.run_forced_merges(None, |segment_metas| {
+            segment_metas
+                .into_iter()
+                .filter(|segment_meta| {
+                    segment_meta.max_doc() > 0
+                        && segment_meta.num_deleted_docs() as f32 / segment_meta.max_doc() as f32
+                            > FORCE_MERGE_DELETES_RATIO
+                })
+                .map(|segment_meta| vec![segment_meta.id()])
+                .collect()
+        })
+    }
+
+    /// Rewrites each of the committed segments on its own, with its documents sorted by
+    /// their value for the fast field `field`, and waits for the rewrites to complete.
+    ///
+    /// This is meant for indexes that are rarely updated: the documents with
+    /// close values get close doc ids, so that collecting the top documents for `field`
+    /// visits them first. The rewritten segments do not have any deletes anymore.
+    ///
+    /// Documents with the same value keep their relative order. For an optional
+    /// fast field, the documents without a value are put last.
+    /// Only the committed segments are sorted: the documents added afterwards
+    /// are not, until `optimize_for_read` is called again.
+    ///
+    /// Returns `SchemaError` if `field` is not a single-valued numeric fast field.
+    pub fn optimize_for_read(&mut self, field: Field, order: Order) -> crate::Result<()> {
+        check_sort_field(&self.index.schema(), field)?;
+        self.run_forced_merges(Some((field, order)), |segment_metas| {
+            segment_metas
+                .iter()
+                .map(|segment_meta| vec![segment_meta.id()])
+                .collect()
+        })
+    }
+
+    /// Runs the merges computed by `merge_candidates` from the committed segments,
+    /// after the ongoing merges are over, and waits for their completion.
+    ///
+    /// The documents of the merged segments are sorted if `sort_by` is given.
+    fn run_forced_merges<F>(
+        &mut self,
+        sort_by: Option<(Field, Order)>,
+        merge_candidates: F,
+    ) -> crate::Result<()>
+    where
+        F: FnOnce(Vec<SegmentMeta>) -> Vec<Vec<SegmentId>>,
+    {
+        let merge_policy = self.get_merge_policy();
+        self.segment_updater
+            .set_merge_policy(Box::new(NoMergePolicy));
+        let result = self.segment_updater.wait_merging_thread().and_then(|()| {
+            let segment_metas = self.index.searchable_segment_metas()?;
+            let merge_futures: Vec<_> = merge_candidates(segment_metas)
+                .iter()
+                .map(|segment_ids| self.merge_with_sort(segment_ids, sort_by))
+                .collect();
+            block_on(futures::future::try_join_all(merge_futures))
+        });
+        self.segment_updater.restore_merge_policy(merge_policy);
+        result.map(|_| ())
+    }
+
     /// Closes the current document channel send.
     /// and replace all the channels by new ones.
     ///
@@ -558,6 +959,9 @@ impl IndexWriter {
         // segment updates will be ignored.
         self.segment_updater.kill();
         let document_receiver = self.operation_receiver.clone();
+        let wal = self.wal.take();
+        let live_searchers = self.live_searchers.clone();
+        let delete_policy = self.delete_policy;
 
         // take the directory lock to create a new index_writer.
         let directory_lock = self
@@ -585,6 +989,10 @@ impl IndexWriter {
         // was dropped with the index_writer.
         for _ in document_receiver {}
 
+        self.wal = wal;
+        self.live_searchers = live_searchers;
+        self.delete_policy = delete_policy;
+        self.truncate_wal()?;
         Ok(self.committed_opstamp)
     }
 
@@ -620,24 +1028,43 @@ impl IndexWriter {
         //
         // This will move uncommitted segments to the state of
         // committed segments.
+        let former_workers_join_handle = self.cut_indexing_queue();
+        let num_workers = former_workers_join_handle.len();
+        join_indexing_workers(former_workers_join_handle)?;
+        self.prepare_commit_after_join(num_workers)
+    }
+
+    /// Cuts the indexing queue, and returns the join handles of the current
+    /// indexing workers. They index the pending documents, and then terminate.
+    fn cut_indexing_queue(&mut self) -> Vec<JoinHandle<crate::Result<()>>> {
         info!("Preparing commit");
 
         // this will drop the current document channel
         // and recreate a new one.
         self.recreate_document_channel();
 
-        let former_workers_join_handle = mem::replace(&mut self.workers_join_handle, Vec::new());
+        mem::take(&mut self.workers_join_handle)
+    }
 
-        for worker_handle in former_workers_join_handle {
-            let indexing_worker_result = worker_handle
-                .join()
-                .map_err(|e| TantivyError::ErrorInThread(format!("{:?}", e)))?;
-            indexing_worker_result?;
+    /// Prepares the commit, once the `num_workers` indexing workers returned by
+    /// `cut_indexing_queue` have been joined.
+    fn prepare_commit_after_join(
+        &mut self,
+        num_workers: usize,
+    ) -> crate::Result<PreparedCommit<'_>> {
+        for _ in 0..num_workers {
             self.add_indexing_worker()?;
         }
 
         let commit_opstamp = self.stamper.stamp();
-        let prepared_commit = PreparedCommit::new(self, commit_opstamp);
+        let delete_operations = self.take_uncommitted_delete_operations();
+        // All of the operations of the commit were logged before it was prepared.
+        let wal_checkpoint = match &self.wal {
+            Some(wal) => Some((wal.clone(), wal.checkpoint()?)),
+            None => None,
+        };
+        let prepared_commit =
+            PreparedCommit::new(self, commit_opstamp, delete_operations, wal_checkpoint);
         info!("Prepared commit {}", commit_opstamp);
         Ok(prepared_commit)
     }
@@ -657,7 +1084,95 @@ impl IndexWriter {
     /// that made it in the commit.
     ///
     pub fn commit(&mut self) -> crate::Result<Opstamp> {
-        self.prepare_commit()?.commit()
+        #[cfg(feature = "otel")]
+        let mut span = crate::otel::start_span("tantivy.commit", &[]);
+        let opstamp = self.prepare_commit()?.commit()?;
+        #[cfg(feature = "otel")]
+        crate::otel::record(&mut span, &[("tantivy.opstamp", opstamp as i64)]);
+        Ok(opstamp)
+    }
+
+    /// Commits all of the pending changes, recording `payload` in the meta of the index.
+    ///
+    /// The payload is a free-form string, e.g. a description of the batch of
+    /// documents or a serialized JSON object, that tantivy does not interpret.
+    /// It is returned by [`Searcher::commit_payload`](../struct.Searcher.html#method.commit_payload)
+    /// for the searchers opened on this commit, and is replaced by the next commit.
+    pub fn commit_with_payload(&mut self, payload: &str) -> crate::Result<Opstamp> {
+        let mut prepared_commit = self.prepare_commit()?;
+        prepared_commit.set_payload(payload);
+        prepared_commit.commit()
+    }
+
+    /// Returns an estimate of the size in bytes of the changes since the last commit.
+    ///
+    /// It is the size of the segments flushed since the last commit, plus the
+    /// [`memory_usage`](#method.memory_usage) of the documents that have not been
+    /// flushed yet. The documents still in the channel to the indexing threads are not counted.
+    pub fn uncommitted_num_bytes(&self) -> u64 {
+        self.segment_updater.uncommitted_num_bytes() + self.memory_usage() as u64
+    }
+
+    /// Commits if the [estimated size](#method.uncommitted_num_bytes) of the changes since
+    /// the last commit exceeds `max_num_bytes`.
+    ///
+    /// The heap size of the `IndexWriter` bounds the memory used to buffer documents,
+    /// but not the amount of uncommitted data: a bulk load that commits only once it is
+    /// over may lose all of its progress on a crash.
+    ///
+    /// The `IndexWriter` never commits on its own, as `add_document` only takes `&self`
+    /// and may be called from several threads while a commit requires `&mut self`.
+    /// This is a manual check instead: calling it after adding each document, or each
+    /// batch of documents, produces incremental commits.
+    ///
+    /// Returns the opstamp of the commit, or `None` if there was no need to commit.
+    pub fn commit_if_uncommitted_num_bytes_exceeds(
+        &mut self,
+        max_num_bytes: u64,
+    ) -> crate::Result<Option<Opstamp>> {
+        if self.uncommitted_num_bytes() <= max_num_bytes {
+            return Ok(None);
+        }
+        self.commit().map(Some)
+    }
+
+    /// Asynchronous version of [`commit()`](#method.commit).
+    ///
+    /// The indexing workers are joined on the blocking thread pool of tokio,
+    /// and the commit itself runs on the thread pool of the segment updater,
+    /// so that the threads of the async runtime are not blocked.
+    /// This method must be called from within a tokio runtime.
+    ///
+    /// The returned future must be driven to completion. If it is dropped
+    /// before, the `IndexWriter` should be rolled back.
+    ///
+    /// This API requires the `async` feature.
+    #[cfg(feature = "async")]
+    pub async fn commit_async(&mut self) -> crate::Result<Opstamp> {
+        #[cfg(feature = "otel")]
+        let mut span = crate::otel::start_span("tantivy.commit", &[]);
+        let former_workers_join_handle = self.cut_indexing_queue();
+        let num_workers = former_workers_join_handle.len();
+        crate::core::spawn_blocking(move || join_indexing_workers(former_workers_join_handle))
+            .await?;
+        let opstamp = self
+            .prepare_commit_after_join(num_workers)?
+            .commit_future()
+            .await?;
+        #[cfg(feature = "otel")]
+        crate::otel::record(&mut span, &[("tantivy.opstamp", opstamp as i64)]);
+        Ok(opstamp)
+    }
+
+    /// Asynchronous version of [`wait_merging_threads()`](#method.wait_merging_threads).
+    ///
+    /// The merging threads are waited for on the blocking thread pool of tokio.
+    /// This method must be called from within a tokio runtime.
+    ///
+    /// This API requires the `async` feature.
+    #[cfg(feature = "async")]
+    pub async fn wait_merging_threads_async(self) -> crate::Result<()> {
+        crate::core::spawn_blocking(move || self.wait_merging_threads()).await
     }
 
     pub(crate) fn segment_updater(&self) -> &SegmentUpdater {
@@ -672,13 +1187,34 @@ impl IndexWriter {
     ///
     /// Like adds, the deletion itself will be visible
     /// only after calling `commit()`.
+    ///
+    /// The postings of the deleted documents are left untouched:
+    /// the documents are marked in the delete bitset of their segment,
+    /// which every query skips, and are only removed when their
+    /// segment gets merged.
     pub fn delete_term(&self, term: Term) -> Opstamp {
         let opstamp = self.stamper.stamp();
-        let delete_operation = DeleteOperation { opstamp, term };
-        self.delete_queue.push(delete_operation);
+        if let Err(e) = self.log_operations(vec![(opstamp, WalOperation::Delete(&term))]) {
+            panic!("Failed to write to the write-ahead log. {:?}", e);
+        }
+        self.push_delete_operation(DeleteOperation::new(opstamp, term));
         opstamp
     }
 
+    fn take_uncommitted_delete_operations(&self) -> Vec<DeleteOperation> {
+        mem::take(&mut *self.uncommitted_delete_operations.lock().unwrap())
+    }
+
+    fn push_delete_operation(&self, delete_operation: DeleteOperation) {
+        if self.delete_policy == DeletePolicy::Error {
+            self.uncommitted_delete_operations
+                .lock()
+                .unwrap()
+                .push(delete_operation.clone());
+        }
+        self.delete_queue.push(delete_operation);
+    }
+
     /// Returns the opstamp of the last successful commit.
     ///
     /// This is, for instance, the opstamp the index will
@@ -690,8 +1226,29 @@ impl IndexWriter {
         self.committed_opstamp
     }
 
+    /// Writes the changes committed after `since_opstamp` to `dest`, to be applied
+    /// to a replica of the index with
+    /// [`Index::apply_delta`](../struct.Index.html#method.apply_delta).
+    ///
+    /// The delta contains the files of the segments added after `since_opstamp`,
+    /// either by a commit or by a merge, and the delete files updated after it.
+    /// `since_opstamp` is normally the opstamp returned by the last `apply_delta`
+    /// on the replica, or `0` to export the entire index.
+    ///
+    /// Segments written by a version of tantivy that did not record their opstamp
+    /// are only exported since opstamp `0`, until the next commit.
+    pub fn export_delta(&self, since_opstamp: Opstamp, dest: &mut dyn Write) -> crate::Result<()> {
+        index_delta::export_delta(&self.index, since_opstamp, dest)
+    }
+
     /// Adds a document.
     ///
+    /// The document is not tokenized on the calling thread: it is sent
+    /// to the indexing threads of the `IndexWriter`, each of them tokenizing documents
+    /// into its own in-memory segment. Indexing therefore uses as many cores as there
+    /// are indexing threads, as set by
+    /// [`Index::writer_with_num_threads`](../struct.Index.html#method.writer_with_num_threads).
+    ///
     /// If the indexing pipeline is full, this call may block.
     ///
     /// The opstamp is an increasing `u64` that can
@@ -699,6 +1256,14 @@ impl IndexWriter {
     /// document queue.
     pub fn add_document(&self, document: Document) -> Opstamp {
         let opstamp = self.stamper.stamp();
+        #[cfg(feature = "otel")]
+        let _span = crate::otel::start_span(
+            "tantivy.add_document",
+            &[("tantivy.opstamp", opstamp as i64)],
+        );
+        if let Err(e) = self.log_operations(vec![(opstamp, WalOperation::Add(&document))]) {
+            panic!("Failed to write to the write-ahead log. {:?}", e);
+        }
         let add_operation = AddOperation { opstamp, document };
         let send_result = self.operation_sender.send(smallvec![add_operation]);
         if let Err(e) = send_result {
@@ -707,6 +1272,94 @@ impl IndexWriter {
         opstamp
     }
 
+    /// Adds a document given as a JSON object, and returns its opstamp.
+    ///
+    /// The keys of the object are matched to the names of the fields of the schema,
+    /// and the keys without a matching field are skipped. The values are converted
+    /// to the type of their field: strings to text (or to dates, facets, bytes...),
+    /// numbers to `u64`, `i64` or `f64`, and booleans to the `u64` 0 or 1.
+    /// See [`Schema::json_value_to_doc`](./schema/struct.Schema.html#method.json_value_to_doc).
+    ///
+    /// The [`JsonDocumentBuilder`](./schema/struct.JsonDocumentBuilder.html) helps
+    /// build such objects.
+    ///
+    /// # Errors
+    /// Returns `TantivyError::InvalidArgument` if `json` is not an object, or if one of
+    /// its values does not match the type of its field. The document is then not added.
+    pub fn add_json_document(&self, json: serde_json::Value) -> crate::Result<Opstamp> {
+        let document = self.index.schema().json_value_to_doc(&json)?;
+        Ok(self.add_document(document))
+    }
+
+    /// Adds a batch of documents, and returns the opstamp of the batch.
+    ///
+    /// All of the documents receive the same opstamp. Compared to calling
+    /// `add_document` for each document, this only stamps the batch once, and sends
+    /// the documents to the indexing threads in groups of `batch_size`. The documents
+    /// of a group are indexed in the same segment.
+    ///
+    /// If the indexing pipeline is full, this call may block.
+    ///
+    /// # Errors
+    /// Returns `TantivyError::InvalidArgument` if `batch_size` is 0, and
+    /// `TantivyError::ErrorInThread` if the indexing threads have panicked.
+    /// In the latter case, some of the documents may have been sent.
+    pub fn add_documents<I>(&self, documents: I, batch_size: usize) -> crate::Result<Opstamp>
+    where
+        I: IntoIterator<Item = Document>,
+    {
+        if batch_size == 0 {
+            return Err(TantivyError::InvalidArgument(
+                "The batch size must be at least 1".to_string(),
+            ));
+        }
+        let opstamp = self.stamper.stamp();
+        let mut documents = documents.into_iter();
+        loop {
+            let adds: OperationGroup = documents
+                .by_ref()
+                .take(batch_size)
+                .map(|document| AddOperation { opstamp, document })
+                .collect();
+            if adds.is_empty() {
+                return Ok(opstamp);
+            }
+            self.log_operations(
+                adds.iter()
+                    .map(|add| (opstamp, WalOperation::Add(&add.document))),
+            )?;
+            self.operation_sender.send(adds).map_err(|_| {
+                TantivyError::ErrorInThread(
+                    "Failed to index documents. Sending to indexing channel failed. \
+                     This probably means all of the indexing threads have panicked."
+                        .to_string(),
+                )
+            })?;
+        }
+    }
+
+    /// Adds a document, with a boost multiplying its score at search time.
+    ///
+    /// The boost is recorded in the document boost field of the schema,
+    /// replacing any value the document may have had for it.
+    ///
+    /// # Errors
+    /// If the schema does not have a document boost field, returns `Error::SchemaError`.
+    pub fn add_document_with_boost(
+        &self,
+        mut document: Document,
+        boost: f64,
+    ) -> crate::Result<Opstamp> {
+        let document_boost_field = self.index.schema().document_boost_field().ok_or_else(|| {
+            TantivyError::SchemaError(
+                "The schema does not have a document boost field.".to_string(),
+            )
+        })?;
+        document.filter_fields(|field| field != document_boost_field);
+        document.add_f64(document_boost_field, boost);
+        Ok(self.add_document(document))
+    }
+
     /// Gets a range of stamps from the stamper and "pops" the last stamp
     /// from the range returning a tuple of the last optstamp and the popped
     /// range.
@@ -747,13 +1400,27 @@ impl IndexWriter {
         }
         let (batch_opstamp, stamps) = self.get_batch_opstamps(count);
 
+        let logged_operations =
+            user_operations
+                .iter()
+                .zip(stamps.clone())
+                .map(|(user_op, opstamp)| {
+                    let operation = match user_op {
+                        UserOperation::Delete(term) => WalOperation::Delete(term),
+                        UserOperation::Add(document) => WalOperation::Add(document),
+                    };
+                    (opstamp, operation)
+                });
+        if let Err(e) = self.log_operations(logged_operations) {
+            panic!("Failed to write to the write-ahead log. {:?}", e);
+        }
+
         let mut adds = OperationGroup::default();
 
         for (user_op, opstamp) in user_operations.into_iter().zip(stamps) {
             match user_op {
                 UserOperation::Delete(term) => {
-                    let delete_operation = DeleteOperation { opstamp, term };
-                    self.delete_queue.push(delete_operation);
+                    self.push_delete_operation(DeleteOperation::new(opstamp, term));
                 }
                 UserOperation::Add(document) => {
                     let add_operation = AddOperation { opstamp, document };
@@ -768,6 +1435,54 @@ impl IndexWriter {
 
         batch_opstamp
     }
+
+    /// Updates some of the fields of the document identified by `term`.
+    ///
+    /// The document is fetched from the doc store of `searcher`, the values
+    /// of the fields of `updates` are replaced, and the resulting document
+    /// is indexed again. Just like calling `run` with a delete of `term` followed
+    /// by an add, the old and the new version of the document are never
+    /// visible at the same time.
+    ///
+    /// Only the stored fields of the document can be fetched: its fields that are
+    /// not stored are lost, unless they are part of `updates`.
+    ///
+    /// `searcher` is expected to contain exactly one document matching `term`,
+    /// otherwise an error is returned. Updates that have not been
+    /// committed yet are not visible to `searcher`: updating the same document
+    /// twice between two commits discards the first update.
+    ///
+    /// Like adds and deletes, the update will be visible
+    /// only after calling `commit()`.
+    pub fn update_document(
+        &self,
+        searcher: &Searcher,
+        term: Term,
+        updates: PartialDocument,
+    ) -> crate::Result<Opstamp> {
+        let term_query = TermQuery::new(term.clone(), IndexRecordOption::Basic);
+        let mut doc_addresses = searcher.search(&term_query, &DocSetCollector)?.into_iter();
+        let doc_address = match (doc_addresses.next(), doc_addresses.next()) {
+            (Some(doc_address), None) => doc_address,
+            (None, _) => {
+                return Err(TantivyError::InvalidArgument(format!(
+                    "No document matches {:?}",
+                    term
+                )));
+            }
+            (Some(_), Some(_)) => {
+                return Err(TantivyError::InvalidArgument(format!(
+                    "More than one document matches {:?}",
+                    term
+                )));
+            }
+        };
+        let document = updates.apply(searcher.doc(doc_address)?);
+        Ok(self.run(vec![
+            UserOperation::Delete(term),
+            UserOperation::Add(document),
+        ]))
+    }
 }
 
 impl Drop for IndexWriter {
@@ -784,15 +1499,25 @@ impl Drop for IndexWriter {
 mod tests {
 
     use super::super::operation::UserOperation;
-    use crate::collector::TopDocs;
+    use crate::collector::{Count, TopDocs};
     use crate::directory::error::LockError;
     use crate::error::*;
     use crate::indexer::NoMergePolicy;
     use crate::query::TermQuery;
-    use crate::schema::{self, IndexRecordOption, STRING};
+    use crate::schema::{
+        self, IndexRecordOption, JsonDocumentBuilder, PartialDocument, INDEXED, STORED, STRING,
+        TEXT,
+    };
+    use crate::DeletePolicy;
     use crate::Index;
     use crate::ReloadPolicy;
+    use crate::SegmentId;
     use crate::Term;
+    #[cfg(feature = "mmap")]
+    use crate::WalSync;
+    use futures::executor::block_on;
+    use std::collections::HashSet;
+    use std::thread;
 
     #[test]
     fn test_operations_group() {
@@ -954,6 +1679,266 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_add_documents() -> crate::Result<()> {
+        let mut schema_builder = schema::Schema::builder();
+        let text_field = schema_builder.add_text_field("text", STRING);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        let first_opstamp = index_writer.add_document(doc!(text_field => "first"));
+        let documents = (0..10).map(|i| doc!(text_field => format!("doc{}", i % 2)));
+        let batch_opstamp = index_writer.add_documents(documents, 3)?;
+        assert_eq!(batch_opstamp, first_opstamp + 1);
+        assert!(index_writer.add_documents(Vec::new(), 0).is_err());
+        index_writer.delete_term(Term::from_field_text(text_field, "doc0"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.num_docs(), 6);
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_json_document() -> crate::Result<()> {
+        let mut schema_builder = schema::Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT | STORED);
+        let count = schema_builder.add_u64_field("count", STORED);
+        let delta = schema_builder.add_i64_field("delta", STORED);
+        let score = schema_builder.add_f64_field("score", STORED);
+        let published = schema_builder.add_u64_field("published", STORED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        let json = JsonDocumentBuilder::new()
+            .add("title", "hello")
+            .add("count", 3)
+            .add("delta", -2)
+            .add("score", 0.5)
+            .add("published", true)
+            .add("unknown", "skipped")
+            .build();
+        index_writer.add_json_document(json)?;
+        assert!(matches!(
+            index_writer.add_json_document(serde_json::json!({"count": "three"})),
+            Err(TantivyError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            index_writer.add_json_document(serde_json::json!(["hello"])),
+            Err(TantivyError::InvalidArgument(_))
+        ));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.num_docs(), 1);
+        let doc = searcher.doc(crate::DocAddress(0, 0))?;
+        assert_eq!(doc.get_first(title).unwrap().text(), Some("hello"));
+        assert_eq!(doc.get_first(count).unwrap().u64_value(), Some(3));
+        assert_eq!(doc.get_first(delta).unwrap().i64_value(), Some(-2));
+        assert_eq!(doc.get_first(score).unwrap().f64_value(), Some(0.5));
+        assert_eq!(doc.get_first(published).unwrap().u64_value(), Some(1));
+        assert_eq!(doc.field_values().len(), 5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_force_merge() -> crate::Result<()> {
+        let mut schema_builder = schema::Schema::builder();
+        let text_field = schema_builder.add_text_field("text", STRING);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.set_merge_policy(Box::new(NoMergePolicy));
+        for i in 0..4 {
+            index_writer.add_document(doc!(text_field => format!("doc{}", i)));
+            index_writer.add_document(doc!(text_field => "other"));
+            index_writer.commit()?;
+        }
+        index_writer.delete_term(Term::from_field_text(text_field, "doc0"));
+        index_writer.commit()?;
+        assert_eq!(index.searchable_segment_ids()?.len(), 4);
+        assert!(index_writer.force_merge(0).is_err());
+
+        index_writer.force_merge(2)?;
+        assert_eq!(index.searchable_segment_ids()?.len(), 2);
+
+        index_writer.force_merge(1)?;
+        let segment_metas = index.searchable_segment_metas()?;
+        assert_eq!(segment_metas.len(), 1);
+        assert_eq!(segment_metas[0].max_doc(), 7);
+        assert!(!segment_metas[0].has_deletes());
+        assert_eq!(
+            format!("{:?}", index_writer.get_merge_policy()),
+            "NoMergePolicy"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_force_merge_deletes() -> crate::Result<()> {
+        let mut schema_builder = schema::Schema::builder();
+        let text_field = schema_builder.add_text_field("text", STRING);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        for i in 0..2 {
+            for _ in 0..10 {
+                index_writer.add_document(doc!(text_field => format!("segment{}", i)));
+            }
+            index_writer.add_document(doc!(text_field => format!("deleted{}", i)));
+            index_writer.commit()?;
+        }
+        // 1 doc out of 11 is deleted in the first segment, 10 out of 11 in the second one.
+        index_writer.delete_term(Term::from_field_text(text_field, "deleted0"));
+        index_writer.delete_term(Term::from_field_text(text_field, "segment1"));
+        index_writer.commit()?;
+
+        index_writer.force_merge_deletes()?;
+        let mut segment_metas = index.searchable_segment_metas()?;
+        segment_metas.sort_by_key(|segment_meta| segment_meta.max_doc());
+        assert_eq!(segment_metas.len(), 2);
+        assert_eq!(segment_metas[0].max_doc(), 1);
+        assert!(!segment_metas[0].has_deletes());
+        assert_eq!(segment_metas[1].max_doc(), 11);
+        assert_eq!(segment_metas[1].num_deleted_docs(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_memory_usage_includes_fast_fields() -> crate::Result<()> {
+        let mut schema_builder = schema::Schema::builder();
+        let vals_field = schema_builder.add_u64_field(
+            "vals",
+            schema::IntOptions::default().set_fast(schema::Cardinality::MultiValues),
+        );
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_with_num_threads(1, 3_000_000)?;
+        index_writer.set_merge_policy(Box::new(NoMergePolicy));
+        assert_eq!(index_writer.memory_usage(), 0);
+        for _ in 0..10_000 {
+            let mut doc = schema::Document::default();
+            for val in 0..100u64 {
+                doc.add_u64(vals_field, val);
+            }
+            index_writer.add_document(doc);
+        }
+        index_writer.commit()?;
+        assert_eq!(index_writer.memory_usage(), 0);
+        // The values of the fast field do not fit in the memory budget of a single segment.
+        assert!(index.searchable_segment_ids()?.len() > 1);
+        assert_eq!(index.reader()?.searcher().num_docs(), 10_000);
+        Ok(())
+    }
+
+    #[test]
+    fn test_compact_segment() -> crate::Result<()> {
+        let mut schema_builder = schema::Schema::builder();
+        let text_field = schema_builder.add_text_field("text", STRING);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.set_merge_policy(Box::new(NoMergePolicy));
+        for i in 0..2 {
+            for _ in 0..10 {
+                index_writer.add_document(doc!(text_field => format!("segment{}", i)));
+            }
+            index_writer.add_document(doc!(text_field => format!("deleted{}", i)));
+            index_writer.commit()?;
+        }
+        index_writer.delete_term(Term::from_field_text(text_field, "deleted0"));
+        index_writer.delete_term(Term::from_field_text(text_field, "deleted1"));
+        index_writer.commit()?;
+        let segment_metas = index.searchable_segment_metas()?;
+        let segment_id = segment_metas[0].id();
+        let other_segment_id = segment_metas[1].id();
+
+        let compaction = index_writer.compact_segment(segment_id);
+        let compacted_segment_meta = thread::spawn(move || block_on(compaction))
+            .join()
+            .unwrap()?;
+        assert_eq!(compacted_segment_meta.max_doc(), 10);
+        assert!(!compacted_segment_meta.has_deletes());
+        let segment_ids: HashSet<SegmentId> = index.searchable_segment_ids()?.into_iter().collect();
+        assert_eq!(
+            segment_ids,
+            vec![compacted_segment_meta.id(), other_segment_id]
+                .into_iter()
+                .collect()
+        );
+        let reader = index.reader()?;
+        assert_eq!(reader.searcher().num_docs(), 20);
+
+        assert!(block_on(index_writer.compact_segment(segment_id)).is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_wal_replay() -> crate::Result<()> {
+        let mut schema_builder = schema::Schema::builder();
+        let text_field = schema_builder.add_text_field("text", schema::TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let wal_dir = tempfile::TempDir::new()?;
+        let wal_path = wal_dir.path().join("index.wal");
+        let num_docs =
+            |index: &Index| -> crate::Result<u64> { Ok(index.reader()?.searcher().num_docs()) };
+        {
+            let mut index_writer = index.writer_for_tests()?;
+            assert_eq!(index_writer.enable_wal(&wal_path, WalSync::PerBatch)?, 0);
+            index_writer.add_document(doc!(text_field => "a"));
+            index_writer.commit()?;
+            index_writer.add_document(doc!(text_field => "b"));
+            index_writer
+                .add_documents(vec![doc!(text_field => "c"), doc!(text_field => "c")], 1)?;
+            index_writer.delete_term(Term::from_field_text(text_field, "a"));
+            // The writer is dropped without committing, as if the process had crashed.
+        }
+        {
+            let mut index_writer = index.writer_for_tests()?;
+            assert_eq!(index_writer.enable_wal(&wal_path, WalSync::PerBatch)?, 4);
+            index_writer.commit()?;
+            assert_eq!(num_docs(&index)?, 3);
+            index_writer.delete_all_documents()?;
+            index_writer.add_document(doc!(text_field => "d"));
+        }
+        {
+            let mut index_writer = index.writer_for_tests()?;
+            assert_eq!(index_writer.enable_wal(&wal_path, WalSync::PerCommit)?, 2);
+            index_writer.commit()?;
+            assert_eq!(num_docs(&index)?, 1);
+        }
+        let mut index_writer = index.writer_for_tests()?;
+        assert_eq!(index_writer.enable_wal(&wal_path, WalSync::PerCommit)?, 0);
+        assert!(index_writer
+            .enable_wal(&wal_path, WalSync::PerCommit)
+            .is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_wal_truncated_by_commit_future() -> crate::Result<()> {
+        let mut schema_builder = schema::Schema::builder();
+        let text_field = schema_builder.add_text_field("text", schema::TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let wal_dir = tempfile::TempDir::new()?;
+        let wal_path = wal_dir.path().join("index.wal");
+        let wal_len = || std::fs::metadata(&wal_path).map(|metadata| metadata.len());
+        {
+            let mut index_writer = index.writer_for_tests()?;
+            index_writer.enable_wal(&wal_path, WalSync::PerCommit)?;
+            index_writer.add_document(doc!(text_field => "a"));
+            block_on(index_writer.prepare_commit()?.commit_future())?;
+            assert_eq!(wal_len()?, 0);
+
+            // The document added while the commit is running belongs to the next commit.
+            index_writer.add_document(doc!(text_field => "b"));
+            let commit_future = index_writer.prepare_commit()?.commit_future();
+            index_writer.add_document(doc!(text_field => "c"));
+            block_on(commit_future)?;
+            assert!(wal_len()? > 0);
+        }
+        let mut index_writer = index.writer_for_tests()?;
+        assert_eq!(index_writer.enable_wal(&wal_path, WalSync::PerCommit)?, 1);
+        index_writer.commit()?;
+        assert_eq!(index.reader()?.searcher().num_docs(), 3);
+        assert_eq!(wal_len()?, 0);
+        Ok(())
+    }
+
     #[test]
     fn test_lockfile_released_on_drop() {
         let schema_builder = schema::Schema::builder();
@@ -1003,6 +1988,33 @@ mod tests {
         reader.searcher();
     }
 
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_commit_async() -> crate::Result<()> {
+        let mut schema_builder = schema::Schema::builder();
+        let text_field = schema_builder.add_text_field("text", schema::TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let reader = index
+            .reader_builder()
+            .reload_policy(ReloadPolicy::Manual)
+            .try_into()?;
+        let mut index_writer = index.writer_for_tests()?;
+        let runtime = tokio::runtime::Builder::new_current_thread().build()?;
+        let add_opstamp = index_writer.add_document(doc!(text_field => "a"));
+        let commit_opstamp = runtime.block_on(index_writer.commit_async())?;
+        assert!(commit_opstamp > add_opstamp);
+        reader.reload()?;
+        assert_eq!(reader.searcher().num_docs(), 1);
+        // The writer keeps indexing after an async commit.
+        index_writer.add_document(doc!(text_field => "b"));
+        let add_opstamp = index_writer.add_document(doc!(text_field => "c"));
+        assert!(index_writer.commit()? > add_opstamp);
+        reader.reload()?;
+        assert_eq!(reader.searcher().num_docs(), 3);
+        runtime.block_on(index_writer.wait_merging_threads_async())?;
+        Ok(())
+    }
+
     #[test]
     fn test_with_merges() {
         let mut schema_builder = schema::Schema::builder();
@@ -1074,6 +2086,99 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_commit_with_payload() -> crate::Result<()> {
+        let mut schema_builder = schema::Schema::builder();
+        let text_field = schema_builder.add_text_field("text", schema::TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let reader = index
+            .reader_builder()
+            .reload_policy(ReloadPolicy::Manual)
+            .try_into()?;
+        assert_eq!(reader.searcher().commit_payload(), None);
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text_field => "a"));
+        index_writer.commit_with_payload("batch import 2021-03-01")?;
+        assert_eq!(
+            index.load_metas()?.payload.as_deref(),
+            Some("batch import 2021-03-01")
+        );
+        let searcher = reader.searcher();
+        assert_eq!(searcher.commit_payload(), None);
+        reader.reload()?;
+        assert_eq!(
+            reader.searcher().commit_payload(),
+            Some("batch import 2021-03-01")
+        );
+        index_writer.add_document(doc!(text_field => "b"));
+        index_writer.commit()?;
+        reader.reload()?;
+        assert_eq!(reader.searcher().commit_payload(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_commit_if_uncommitted_num_bytes_exceeds() -> crate::Result<()> {
+        let mut schema_builder = schema::Schema::builder();
+        let text_field = schema_builder.add_text_field("text", schema::TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        assert_eq!(index_writer.uncommitted_num_bytes(), 0);
+        assert_eq!(
+            index_writer.commit_if_uncommitted_num_bytes_exceeds(0)?,
+            None
+        );
+        for _ in 0..100 {
+            index_writer.add_document(doc!(text_field => "happy tax payer"));
+        }
+        // The memory usage is reported by the indexing thread.
+        let start = std::time::Instant::now();
+        while index_writer.uncommitted_num_bytes() == 0 {
+            assert!(start.elapsed() < std::time::Duration::from_secs(10));
+            thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert_eq!(
+            index_writer.commit_if_uncommitted_num_bytes_exceeds(u64::MAX)?,
+            None
+        );
+        assert!(index_writer
+            .commit_if_uncommitted_num_bytes_exceeds(0)?
+            .is_some());
+        assert_eq!(index_writer.uncommitted_num_bytes(), 0);
+        assert_eq!(index.reader()?.searcher().num_docs(), 100);
+        Ok(())
+    }
+
+    #[test]
+    fn test_bulk_load_commits_incrementally() -> crate::Result<()> {
+        let mut schema_builder = schema::Schema::builder();
+        let text_field = schema_builder.add_text_field("text", schema::TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        let mut commit_opstamps = Vec::new();
+        for _ in 0..3 {
+            for _ in 0..100 {
+                index_writer.add_document(doc!(text_field => "happy tax payer"));
+            }
+            // The memory usage is reported by the indexing thread.
+            let start = std::time::Instant::now();
+            while index_writer.uncommitted_num_bytes() == 0 {
+                assert!(start.elapsed() < std::time::Duration::from_secs(10));
+                thread::sleep(std::time::Duration::from_millis(10));
+            }
+            if let Some(opstamp) = index_writer.commit_if_uncommitted_num_bytes_exceeds(1_000)? {
+                commit_opstamps.push(opstamp);
+            }
+            let reader = index.reader()?;
+            assert_eq!(
+                reader.searcher().num_docs() as usize,
+                commit_opstamps.len() * 100
+            );
+        }
+        assert_eq!(commit_opstamps.len(), 3);
+        Ok(())
+    }
+
     #[test]
     fn test_prepare_but_rollback() {
         let mut schema_builder = schema::Schema::builder();
@@ -1280,6 +2385,85 @@ mod tests {
         assert!(commit_again.is_ok());
     }
 
+    #[test]
+    fn test_delete_policy() -> crate::Result<()> {
+        let mut schema_builder = schema::Schema::builder();
+        let id_field = schema_builder.add_text_field("id", STRING);
+        let index = Index::create_in_ram(schema_builder.build());
+        let id_term = |id: &str| Term::from_field_text(id_field, id);
+        let mut index_writer = index.writer_for_tests()?;
+        // By default, deleting a term that no document contains is a no-op.
+        index_writer.delete_term(id_term("a"));
+        assert!(index_writer.commit().is_ok());
+
+        index_writer.set_delete_policy(DeletePolicy::Error);
+        index_writer.add_document(doc!(id_field => "a"));
+        index_writer.add_document(doc!(id_field => "b"));
+        index_writer.commit()?;
+        // Deletes of documents of a previous commit, and of the same commit.
+        index_writer.delete_term(id_term("a"));
+        index_writer.add_document(doc!(id_field => "c"));
+        index_writer.delete_term(id_term("c"));
+        let commit_opstamp = index_writer.commit()?;
+
+        let check_commit_fails = |index_writer: &mut super::IndexWriter| -> crate::Result<()> {
+            assert!(matches!(
+                index_writer.commit(),
+                Err(TantivyError::InvalidArgument(_))
+            ));
+            assert_eq!(index.load_metas()?.opstamp, commit_opstamp);
+            index_writer.rollback()?;
+            Ok(())
+        };
+        // Already deleted.
+        index_writer.delete_term(id_term("a"));
+        check_commit_fails(&mut index_writer)?;
+        // Added after the delete.
+        index_writer.delete_term(id_term("d"));
+        index_writer.add_document(doc!(id_field => "d"));
+        check_commit_fails(&mut index_writer)?;
+        // Deleted twice. The policy survives the rollbacks.
+        index_writer.delete_term(id_term("b"));
+        index_writer.delete_term(id_term("b"));
+        check_commit_fails(&mut index_writer)?;
+
+        index_writer.delete_term(id_term("b"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.num_docs(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_delete_all_documents_removes_segment_files() -> crate::Result<()> {
+        let mut schema_builder = schema::Schema::builder();
+        let text_field = schema_builder.add_text_field("text", schema::TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text_field => "a"));
+        index_writer.commit()?;
+        index_writer.add_document(doc!(text_field => "b"));
+        index_writer.commit()?;
+        let segment_metas = index.searchable_segment_metas()?;
+        assert_eq!(segment_metas.len(), 2);
+        // The segment metas would keep their files alive.
+        let segment_files: Vec<std::path::PathBuf> = segment_metas
+            .into_iter()
+            .flat_map(|segment_meta| segment_meta.list_files())
+            .collect();
+
+        index_writer.delete_all_documents()?;
+        index_writer.commit()?;
+        block_on(index_writer.garbage_collect_files())?;
+        assert!(index.searchable_segment_metas()?.is_empty());
+        let managed_files = index.directory().list_managed_files();
+        for file in &segment_files {
+            assert!(!managed_files.contains(file));
+        }
+        assert_eq!(index.reader()?.searcher().num_docs(), 0);
+        Ok(())
+    }
+
     #[test]
     fn test_index_doc_missing_field() {
         let mut schema_builder = schema::Schema::builder();
@@ -1291,4 +2475,68 @@ mod tests {
         let commit = index_writer.commit();
         assert!(commit.is_ok());
     }
+
+    #[test]
+    fn test_update_document() -> crate::Result<()> {
+        let mut schema_builder = schema::Schema::builder();
+        let id_field = schema_builder.add_text_field("id", STRING | STORED);
+        let title_field = schema_builder.add_text_field("title", TEXT | STORED);
+        let count_field = schema_builder.add_u64_field("count", INDEXED | STORED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(
+            id_field => "a",
+            title_field => "hello happy tax payer",
+            count_field => 1u64,
+        ));
+        index_writer.add_document(doc!(id_field => "b", title_field => "bye", count_field => 1u64));
+        index_writer.add_document(doc!(id_field => "b", title_field => "bye", count_field => 2u64));
+        index_writer.commit()?;
+        let reader = index
+            .reader_builder()
+            .reload_policy(ReloadPolicy::Manual)
+            .try_into()?;
+        let mut updates = PartialDocument::new();
+        updates.set(count_field, 3u64);
+        let searcher = reader.searcher();
+        index_writer.update_document(
+            &searcher,
+            Term::from_field_text(id_field, "a"),
+            updates.clone(),
+        )?;
+        assert!(index_writer
+            .update_document(
+                &searcher,
+                Term::from_field_text(id_field, "b"),
+                updates.clone()
+            )
+            .is_err());
+        assert!(index_writer
+            .update_document(&searcher, Term::from_field_text(id_field, "c"), updates)
+            .is_err());
+        index_writer.commit()?;
+        reader.reload()?;
+        let searcher = reader.searcher();
+        assert_eq!(searcher.num_docs(), 3);
+        let count_docs = |term: Term| -> crate::Result<usize> {
+            let query = TermQuery::new(term, IndexRecordOption::Basic);
+            searcher.search(&query, &Count)
+        };
+        assert_eq!(count_docs(Term::from_field_u64(count_field, 3u64))?, 1);
+        assert_eq!(count_docs(Term::from_field_u64(count_field, 1u64))?, 1);
+        assert_eq!(count_docs(Term::from_field_text(title_field, "happy"))?, 1);
+        let query = TermQuery::new(
+            Term::from_field_text(id_field, "a"),
+            IndexRecordOption::Basic,
+        );
+        let top_docs = searcher.search(&query, &TopDocs::with_limit(2))?;
+        assert_eq!(top_docs.len(), 1);
+        let doc = searcher.doc(top_docs[0].1)?;
+        assert_eq!(
+            doc.get_first(title_field).unwrap().text(),
+            Some("hello happy tax payer")
+        );
+        assert_eq!(doc.get_first(count_field).unwrap().u64_value(), Some(3u64));
+        Ok(())
+    }
 }
@@ -0,0 +1,186 @@
+use crate::postings::MultiFieldPostingsWriter;
+use crate::schema::{Field, Term};
+use crate::tokenizer::{TextAnalyzer, Token, MAX_TOKEN_LEN};
+use crate::DocId;
+use serde_json::{Map as JsonObject, Value as JsonValue};
+
+/// Indexes the leaves of the json objects of a field, under their dotted path.
+///
+/// All of the leaves of the objects of a document share the same positions:
+/// the positions of two consecutive leaves are `position_gap` apart,
+/// so that a phrase query cannot match across leaves.
+pub(crate) struct JsonIndexer<'a> {
+    doc: DocId,
+    field: Field,
+    text_analyzer: Option<&'a TextAnalyzer>,
+    position_gap: u32,
+    path: String,
+    position: u32,
+    num_tokens: u32,
+    term_buffer: &'a mut Term,
+    postings_writer: &'a mut MultiFieldPostingsWriter,
+}
+
+impl<'a> JsonIndexer<'a> {
+    /// Creates a `JsonIndexer`.
+    ///
+    /// The string values are only indexed if a `text_analyzer` is given.
+    pub fn new(
+        doc: DocId,
+        field: Field,
+        text_analyzer: Option<&'a TextAnalyzer>,
+        position_gap: u32,
+        term_buffer: &'a mut Term,
+        postings_writer: &'a mut MultiFieldPostingsWriter,
+    ) -> JsonIndexer<'a> {
+        term_buffer.set_field(field);
+        JsonIndexer {
+            doc,
+            field,
+            text_analyzer,
+            position_gap,
+            path: String::new(),
+            position: 0,
+            num_tokens: 0,
+            term_buffer,
+            postings_writer,
+        }
+    }
+
+    /// Returns the number of terms indexed so far, to be recorded as the fieldnorm.
+    pub fn num_tokens(&self) -> u32 {
+        self.num_tokens
+    }
+
+    /// Indexes the leaves of `json_object`.
+    pub fn index_json_object(&mut self, json_object: &JsonObject<String, JsonValue>) {
+        let path_len = self.path.len();
+        for (key, json_value) in json_object {
+            if path_len > 0 {
+                self.path.push('.');
+            }
+            self.path.push_str(key);
+            self.index_json_value(json_value);
+            self.path.truncate(path_len);
+        }
+    }
+
+    fn index_json_value(&mut self, json_value: &JsonValue) {
+        match json_value {
+            JsonValue::Null => {}
+            JsonValue::Bool(val) => self.index_text_token(if *val { "true" } else { "false" }),
+            JsonValue::Number(number) => {
+                let term = Term::from_json_path_number(self.field, &self.path, number);
+                self.postings_writer
+                    .subscribe_at_position(self.doc, self.position, &term);
+                self.num_tokens += 1;
+                self.end_leaf(self.position);
+            }
+            JsonValue::String(text) => self.index_text(text),
+            JsonValue::Array(json_values) => {
+                for json_value in json_values {
+                    self.index_json_value(json_value);
+                }
+            }
+            JsonValue::Object(json_object) => self.index_json_object(json_object),
+        }
+    }
+
+    fn index_text(&mut self, text: &str) {
+        let text_analyzer = if let Some(text_analyzer) = self.text_analyzer {
+            text_analyzer
+        } else {
+            return;
+        };
+        let start_position = self.position;
+        let mut last_position = None;
+        let mut token_stream = text_analyzer.token_stream(text);
+        token_stream.process(&mut |token: &Token| {
+            if token.text.len() > MAX_TOKEN_LEN {
+                return;
+            }
+            let position = start_position + token.position as u32;
+            self.term_buffer.set_json_path_text(&self.path, &token.text);
+            self.postings_writer
+                .subscribe_at_position(self.doc, position, self.term_buffer);
+            self.num_tokens += 1;
+            last_position = Some(position);
+        });
+        if let Some(last_position) = last_position {
+            self.end_leaf(last_position);
+        }
+    }
+
+    fn index_text_token(&mut self, text: &str) {
+        self.term_buffer.set_json_path_text(&self.path, text);
+        self.postings_writer
+            .subscribe_at_position(self.doc, self.position, self.term_buffer);
+        self.num_tokens += 1;
+        self.end_leaf(self.position);
+    }
+
+    fn end_leaf(&mut self, last_position: u32) {
+        self.position = last_position + 1 + self.position_gap;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collector::Count;
+    use crate::query::QueryParser;
+    use crate::schema::{Schema, Term, STORED, TEXT};
+    use crate::Index;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_json_object_field() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let attributes = schema_builder.add_json_field("attributes", STORED | TEXT);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema.clone());
+        let mut index_writer = index.writer_for_tests()?;
+        let json_doc = r#"{
+            "title": "the art of computer programming",
+            "attributes": {"author": {"name": "Donald Knuth"}, "volume": 1, "tags": ["algorithms", "math"]}
+        }"#;
+        index_writer.add_document(schema.parse_document(json_doc).unwrap());
+        let mut doc = doc!(title => "the c programming language");
+        let json_object = serde_json::json!({"author": {"name": "Brian Kernighan"}, "volume": 1.5});
+        doc.add_json_object(attributes, json_object.as_object().unwrap().clone());
+        index_writer.add_document(doc);
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let query_parser = QueryParser::for_index(&index, vec![title]);
+        let count = |query: &str| -> crate::Result<usize> {
+            let query = query_parser.parse_query(query).unwrap();
+            searcher.search(&query, &Count)
+        };
+        assert_eq!(count("attributes.author.name:donald")?, 1);
+        assert_eq!(count("attributes.author.name:\"donald knuth\"")?, 1);
+        assert_eq!(count("attributes.author.name:\"knuth brian\"")?, 0);
+        assert_eq!(count("attributes.author:donald")?, 0);
+        assert_eq!(count("attributes.volume:1")?, 1);
+        assert_eq!(count("attributes.volume:1.5")?, 1);
+        assert_eq!(count("attributes.tags:math")?, 1);
+        assert_eq!(count("title:programming")?, 2);
+        let term = Term::from_json_path_text(attributes, "author.name", "brian");
+        let query = query_parser
+            .parse_query("attributes.author.name:brian")
+            .unwrap();
+        let mut terms = BTreeSet::new();
+        query.query_terms(&mut terms);
+        assert_eq!(terms.into_iter().collect::<Vec<Term>>(), vec![term]);
+        let top_docs = searcher.search(
+            &query_parser.parse_query("attributes.volume:1").unwrap(),
+            &crate::collector::TopDocs::with_limit(1),
+        )?;
+        let stored_doc = searcher.doc(top_docs[0].1)?;
+        let stored_json = stored_doc
+            .get_first(attributes)
+            .unwrap()
+            .json_object_value();
+        assert_eq!(stored_json.unwrap()["author"]["name"], "Donald Knuth");
+        Ok(())
+    }
+}
@@ -0,0 +1,61 @@
+use crate::SegmentId;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Receives the progress of the merges of an `IndexWriter`.
+///
+/// It is registered with
+/// [`IndexWriter::set_merge_callback`](../struct.IndexWriter.html#method.set_merge_callback).
+/// The methods are called from the merging threads, and default to doing nothing.
+pub trait MergeCallback: Send + Sync + 'static {
+    /// Called when the merge of the segments `segment_ids` starts.
+    fn on_merge_start(&self, _segment_ids: &[SegmentId]) {}
+
+    /// Called after each step of a merge.
+    ///
+    /// `bytes_total` is the size of the merged segments, and `bytes_written`
+    /// the size of the parts of them that have been merged so far.
+    fn on_merge_progress(&self, _bytes_written: u64, _bytes_total: u64) {}
+
+    /// Called when a merge has been written, with the id of the new segment.
+    fn on_merge_complete(&self, _new_segment_id: SegmentId) {}
+}
+
+/// Token to cancel the merges of an `IndexWriter`.
+///
+/// It is registered with
+/// [`IndexWriter::set_merge_cancellation_token`](../struct.IndexWriter.html#method.set_merge_cancellation_token).
+/// Once it is cancelled, the running merges stop at their next step, and
+/// the new merges fail immediately, with a `TantivyError::Cancelled` error.
+/// The segments that were being merged are left untouched.
+///
+/// Clones share the same state: a clone can be kept to cancel the merges
+/// from another thread.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Creates a new token, that is not cancelled.
+    pub fn new() -> CancellationToken {
+        CancellationToken::default()
+    }
+
+    /// Cancels the token.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns true if the token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn check(&self) -> crate::Result<()> {
+        if self.is_cancelled() {
+            return Err(crate::TantivyError::Cancelled);
+        }
+        Ok(())
+    }
+}
@@ -1,3 +1,5 @@
+use crate::collector::Order;
+use crate::schema::Field;
 use crate::Opstamp;
 use crate::SegmentId;
 use census::{Inventory, TrackedObject};
@@ -32,6 +34,8 @@ impl MergeOperationInventory {
 /// - `target_opstamp` is the opstamp up to which we want to consume the
 /// delete queue and reflect their deletes.
 /// - `segment_ids` is the list of segment to be merged.
+/// - `sort_by` is the optional fast field by which the documents of the merged
+///   segment are sorted.
 ///
 /// The second role is to ensure keep track of the fact that these
 /// segments are in merge and avoid starting a merge operation that
@@ -47,6 +51,7 @@ pub struct MergeOperation {
 pub(crate) struct InnerMergeOperation {
     target_opstamp: Opstamp,
     segment_ids: Vec<SegmentId>,
+    sort_by: Option<(Field, Order)>,
 }
 
 impl MergeOperation {
@@ -54,10 +59,20 @@ impl MergeOperation {
         inventory: &MergeOperationInventory,
         target_opstamp: Opstamp,
         segment_ids: Vec<SegmentId>,
+    ) -> MergeOperation {
+        MergeOperation::new_with_sort(inventory, target_opstamp, segment_ids, None)
+    }
+
+    pub(crate) fn new_with_sort(
+        inventory: &MergeOperationInventory,
+        target_opstamp: Opstamp,
+        segment_ids: Vec<SegmentId>,
+        sort_by: Option<(Field, Order)>,
     ) -> MergeOperation {
         let inner_merge_operation = InnerMergeOperation {
             target_opstamp,
             segment_ids,
+            sort_by,
         };
         MergeOperation {
             inner: inventory.track(inner_merge_operation),
@@ -71,4 +86,8 @@ impl MergeOperation {
     pub fn segment_ids(&self) -> &[SegmentId] {
         &self.inner.segment_ids[..]
     }
+
+    pub fn sort_by(&self) -> Option<(Field, Order)> {
+        self.inner.sort_by
+    }
 }
@@ -11,11 +11,20 @@ pub struct MergeCandidate(pub Vec<SegmentId>);
 ///
 /// Every time a the list of segments changes, the segment updater
 /// asks the merge policy if some segments should be merged.
+///
+/// Tantivy comes with the `LogMergePolicy`, which is the default, the
+/// `TieredMergePolicy` and the `NoMergePolicy`. Other policies can be
+/// implemented outside of tantivy, and set with `IndexWriter::set_merge_policy`.
+/// They can rely on the number of documents of the segments, the number of
+/// deleted documents, and their size in bytes (`SegmentMeta::num_bytes`).
 pub trait MergePolicy: marker::Send + marker::Sync + Debug {
     /// Given the list of segment metas, returns the list of merge candidates.
     ///
     /// This call happens on the segment updater thread, and will block
     /// other segment updates, so all implementations should happen rapidly.
+    ///
+    /// The segments that are already being merged are not part of `segments`.
+    /// A segment should appear in at most one of the candidates.
     fn compute_merge_candidates(&self, segments: &[SegmentMeta]) -> Vec<MergeCandidate>;
 }
 
@@ -1,7 +1,7 @@
+use crate::collector::{sort_value_reader, Order};
 use crate::common::MAX_DOC_LIMIT;
 use crate::core::Segment;
 use crate::core::SegmentReader;
-use crate::core::SerializableSegment;
 use crate::docset::{DocSet, TERMINATED};
 use crate::fastfield::BytesFastFieldReader;
 use crate::fastfield::DeleteBitSet;
@@ -20,16 +20,31 @@ use crate::schema::{Field, Schema};
 use crate::store::StoreWriter;
 use crate::termdict::TermMerger;
 use crate::termdict::TermOrdinal;
+use crate::termvector::TermVectorsWriter;
 use crate::{DocId, InvertedIndexReader, SegmentComponent};
+use roaring::RoaringBitmap;
 use std::cmp;
 use std::collections::HashMap;
+use std::io;
+use std::ops::Range;
 use std::sync::Arc;
 
 fn compute_total_num_tokens(readers: &[SegmentReader], field: Field) -> crate::Result<u64> {
     let mut total_tokens = 0u64;
     let mut count: [usize; 256] = [0; 256];
     for reader in readers {
-        if reader.has_deletes() {
+        let has_fieldnorms = reader
+            .schema()
+            .get_field_entry(field)
+            .field_type()
+            .has_fieldnorms();
+        if reader.has_deletes() && !has_fieldnorms {
+            // without fieldnorms, the tokens of the deleted documents are
+            // removed proportionally.
+            let total_num_tokens = reader.inverted_index(field)?.total_num_tokens();
+            total_tokens +=
+                total_num_tokens * u64::from(reader.num_docs()) / u64::from(reader.max_doc());
+        } else if reader.has_deletes() {
             // if there are deletes, then we use an approximation
             // using the fieldnorm
             let fieldnorms_reader = reader.get_fieldnorms_reader(field)?;
@@ -52,10 +67,45 @@ fn compute_total_num_tokens(readers: &[SegmentReader], field: Field) -> crate::R
             .sum::<u64>())
 }
 
+/// Lists the alive documents of `readers`, in the order of their value for the fast field `field`.
+///
+/// Documents with the same value keep their relative order.
+fn sort_doc_ids(
+    readers: &[SegmentReader],
+    field: Field,
+    order: Order,
+) -> crate::Result<Vec<(usize, DocId)>> {
+    let mut sort_values = Vec::with_capacity(2);
+    let mut docs_with_sort_key = Vec::new();
+    for (segment_ord, reader) in readers.iter().enumerate() {
+        let sort_value_reader = sort_value_reader(reader, field, order)?;
+        for doc_id in reader.doc_ids_alive() {
+            sort_values.clear();
+            sort_value_reader.push_sort_values(doc_id, &mut sort_values);
+            // For optional fast fields, the value is preceded by a null flag.
+            let sort_key = if sort_values.len() == 2 {
+                (sort_values[0], sort_values[1])
+            } else {
+                (0, sort_values[0])
+            };
+            docs_with_sort_key.push((sort_key, segment_ord, doc_id));
+        }
+    }
+    docs_with_sort_key.sort_by_key(|(sort_key, _, _)| *sort_key);
+    Ok(docs_with_sort_key
+        .into_iter()
+        .map(|(_, segment_ord, doc_id)| (segment_ord, doc_id))
+        .collect())
+}
+
 pub struct IndexMerger {
     schema: Schema,
     readers: Vec<SegmentReader>,
     max_doc: u32,
+    // `(segment_ord, doc_id)` of the documents of the merged segment, in their new order.
+    doc_id_mapping: Vec<(usize, DocId)>,
+    // True if the documents are not simply stacked segment after segment.
+    is_sorted: bool,
 }
 
 fn compute_min_max_val(
@@ -141,7 +191,15 @@ impl DeltaComputer {
 }
 
 impl IndexMerger {
-    pub fn open(schema: Schema, segments: &[Segment]) -> crate::Result<IndexMerger> {
+    /// Opens an `IndexMerger` over `segments`.
+    ///
+    /// If `sort_by` is given, the documents are written in the order of their value
+    /// for its fast field, rather than segment after segment.
+    pub fn open(
+        schema: Schema,
+        segments: &[Segment],
+        sort_by: Option<(Field, Order)>,
+    ) -> crate::Result<IndexMerger> {
         let mut readers = vec![];
         let mut max_doc: u32 = 0u32;
         for segment in segments {
@@ -159,10 +217,25 @@ impl IndexMerger {
             );
             return Err(crate::TantivyError::InvalidArgument(err_msg));
         }
+        let doc_id_mapping = if let Some((field, order)) = sort_by {
+            sort_doc_ids(&readers, field, order)?
+        } else {
+            readers
+                .iter()
+                .enumerate()
+                .flat_map(|(segment_ord, reader)| {
+                    reader
+                        .doc_ids_alive()
+                        .map(move |doc_id| (segment_ord, doc_id))
+                })
+                .collect()
+        };
         Ok(IndexMerger {
             schema,
             readers,
             max_doc,
+            doc_id_mapping,
+            is_sorted: sort_by.is_some(),
         })
     }
 
@@ -174,12 +247,14 @@ impl IndexMerger {
         let mut fieldnorms_data = Vec::with_capacity(self.max_doc as usize);
         for field in fields {
             fieldnorms_data.clear();
-            for reader in &self.readers {
-                let fieldnorms_reader = reader.get_fieldnorms_reader(field)?;
-                for doc_id in reader.doc_ids_alive() {
-                    let fieldnorm_id = fieldnorms_reader.fieldnorm_id(doc_id);
-                    fieldnorms_data.push(fieldnorm_id);
-                }
+            let fieldnorms_readers = self
+                .readers
+                .iter()
+                .map(|reader| reader.get_fieldnorms_reader(field))
+                .collect::<crate::Result<Vec<_>>>()?;
+            for &(segment_ord, doc_id) in &self.doc_id_mapping {
+                let fieldnorm_id = fieldnorms_readers[segment_ord].fieldnorm_id(doc_id);
+                fieldnorms_data.push(fieldnorm_id);
             }
             fieldnorms_serializer.serialize_field(field, &fieldnorms_data[..])?;
         }
@@ -216,6 +291,9 @@ impl IndexMerger {
                     Some(Cardinality::MultiValues) => {
                         self.write_multi_fast_field(field, fast_field_serializer)?;
                     }
+                    Some(Cardinality::Optional) => {
+                        self.write_nullable_fast_field(field, fast_field_serializer)?;
+                    }
                     None => {}
                 },
                 FieldType::Str(_) => {
@@ -228,6 +306,27 @@ impl IndexMerger {
                         self.write_bytes_fast_field(field, fast_field_serializer)?;
                     }
                 }
+                FieldType::IpAddr(ref ip_addr_options) => {
+                    if ip_addr_options.is_fast() {
+                        self.write_u128_fast_field(field, fast_field_serializer)?;
+                    }
+                }
+                FieldType::GeoPoint(ref geo_point_options) => {
+                    if geo_point_options.is_fast() {
+                        self.write_single_fast_field(field, fast_field_serializer)?;
+                    }
+                }
+                FieldType::DenseVector(_) => {
+                    // Dense vectors are stored as a bytes fast field.
+                    self.write_bytes_fast_field(field, fast_field_serializer)?;
+                }
+                FieldType::DocumentBoost(_) => {
+                    self.write_single_fast_field(field, fast_field_serializer)?;
+                }
+
+                FieldType::JsonObject(_) => {
+                    // Json object fields do not have fast fields.
+                }
             }
         }
         Ok(())
@@ -238,6 +337,49 @@ impl IndexMerger {
         &self,
         field: Field,
         fast_field_serializer: &mut FastFieldSerializer,
+    ) -> crate::Result<()> {
+        self.write_single_fast_field_with_idx(field, 0, None, fast_field_serializer)
+    }
+
+    // used to merge optional `u64/i64/f64` fast fields, along with their null bitmap.
+    fn write_nullable_fast_field(
+        &self,
+        field: Field,
+        fast_field_serializer: &mut FastFieldSerializer,
+    ) -> crate::Result<()> {
+        let mut null_bitmap = RoaringBitmap::new();
+        let mut segment_null_bitmaps = Vec::with_capacity(self.readers.len());
+        for reader in &self.readers {
+            let segment_null_bitmap = reader
+                .fast_fields()
+                .null_bitmap(field)?
+                .expect("Optional fast field without a null bitmap. This is a tantivy bug.");
+            segment_null_bitmaps.push(segment_null_bitmap);
+        }
+        for (merged_doc_id, &(segment_ord, doc_id)) in self.doc_id_mapping.iter().enumerate() {
+            if segment_null_bitmaps[segment_ord].contains(doc_id) {
+                null_bitmap.insert(merged_doc_id as DocId);
+            }
+        }
+        self.write_single_fast_field_with_idx(field, 0, Some(&null_bitmap), fast_field_serializer)
+    }
+
+    // used to merge `u128` fast fields, stored as two `u64` single fast fields.
+    fn write_u128_fast_field(
+        &self,
+        field: Field,
+        fast_field_serializer: &mut FastFieldSerializer,
+    ) -> crate::Result<()> {
+        self.write_single_fast_field_with_idx(field, 0, None, fast_field_serializer)?;
+        self.write_single_fast_field_with_idx(field, 1, None, fast_field_serializer)
+    }
+
+    fn write_single_fast_field_with_idx(
+        &self,
+        field: Field,
+        idx: usize,
+        null_bitmap: Option<&RoaringBitmap>,
+        fast_field_serializer: &mut FastFieldSerializer,
     ) -> crate::Result<()> {
         let mut u64_readers = vec![];
         let mut min_value = u64::max_value();
@@ -246,7 +388,7 @@ impl IndexMerger {
         for reader in &self.readers {
             let u64_reader: FastFieldReader<u64> = reader
                 .fast_fields()
-                .typed_fast_field_reader(field)
+                .typed_fast_field_reader_with_idx(field, idx)
                 .expect("Failed to find a reader for single fast field. This is a tantivy bug and it should never happen.");
             if let Some((seg_min_val, seg_max_val)) =
                 compute_min_max_val(&u64_reader, reader.max_doc(), reader.delete_bitset())
@@ -254,10 +396,10 @@ impl IndexMerger {
                 // the segment has some non-deleted documents
                 min_value = cmp::min(min_value, seg_min_val);
                 max_value = cmp::max(max_value, seg_max_val);
-                u64_readers.push((reader.max_doc(), u64_reader, reader.delete_bitset()));
             } else {
                 // all documents have been deleted.
             }
+            u64_readers.push(u64_reader);
         }
 
         if min_value > max_value {
@@ -266,18 +408,19 @@ impl IndexMerger {
             max_value = 0;
         }
 
-        let mut fast_single_field_serializer =
-            fast_field_serializer.new_u64_fast_field(field, min_value, max_value)?;
-        for (max_doc, u64_reader, delete_bitset_opt) in u64_readers {
-            for doc_id in 0u32..max_doc {
-                let is_deleted = delete_bitset_opt
-                    .map(|delete_bitset| delete_bitset.is_deleted(doc_id))
-                    .unwrap_or(false);
-                if !is_deleted {
-                    let val = u64_reader.get(doc_id);
-                    fast_single_field_serializer.add_val(val)?;
-                }
-            }
+        let mut fast_single_field_serializer = if let Some(null_bitmap) = null_bitmap {
+            fast_field_serializer.new_nullable_u64_fast_field(
+                field,
+                min_value,
+                max_value,
+                null_bitmap,
+            )?
+        } else {
+            fast_field_serializer.new_u64_fast_field_with_idx(field, min_value, max_value, idx)?
+        };
+        for &(segment_ord, doc_id) in &self.doc_id_mapping {
+            let val = u64_readers[segment_ord].get(doc_id);
+            fast_single_field_serializer.add_val(val)?;
         }
 
         fast_single_field_serializer.close_field()?;
@@ -318,11 +461,9 @@ impl IndexMerger {
         let mut serialize_idx =
             fast_field_serializer.new_u64_fast_field_with_idx(field, 0, total_num_vals, 0)?;
         let mut idx = 0;
-        for (segment_reader, u64s_reader) in self.readers.iter().zip(&u64s_readers) {
-            for doc in segment_reader.doc_ids_alive() {
-                serialize_idx.add_val(idx)?;
-                idx += u64s_reader.num_vals(doc) as u64;
-            }
+        for &(segment_ord, doc) in &self.doc_id_mapping {
+            serialize_idx.add_val(idx)?;
+            idx += u64s_readers[segment_ord].num_vals(doc) as u64;
         }
         serialize_idx.add_val(idx)?;
         serialize_idx.close_field()?;
@@ -349,20 +490,23 @@ impl IndexMerger {
             let mut serialize_vals =
                 fast_field_serializer.new_u64_fast_field_with_idx(field, 0u64, max_term_ord, 1)?;
             let mut vals = Vec::with_capacity(100);
-            for (segment_ord, segment_reader) in self.readers.iter().enumerate() {
+            let ff_readers: Vec<MultiValuedFastFieldReader<u64>> = self
+                .readers
+                .iter()
+                .map(|segment_reader| {
+                    segment_reader
+                        .fast_fields()
+                        .u64s(field)
+                        .expect("Could not find multivalued u64 fast value reader.")
+                })
+                .collect();
+            for &(segment_ord, doc) in &self.doc_id_mapping {
                 let term_ordinal_mapping: &[TermOrdinal] =
                     term_ordinal_mappings.get_segment(segment_ord);
-                let ff_reader: MultiValuedFastFieldReader<u64> = segment_reader
-                    .fast_fields()
-                    .u64s(field)
-                    .expect("Could not find multivalued u64 fast value reader.");
-                // TODO optimize if no deletes
-                for doc in segment_reader.doc_ids_alive() {
-                    ff_reader.get_vals(doc, &mut vals);
-                    for &prev_term_ord in &vals {
-                        let new_term_ord = term_ordinal_mapping[prev_term_ord as usize];
-                        serialize_vals.add_val(new_term_ord)?;
-                    }
+                ff_readers[segment_ord].get_vals(doc, &mut vals);
+                for &prev_term_ord in &vals {
+                    let new_term_ord = term_ordinal_mapping[prev_term_ord as usize];
+                    serialize_vals.add_val(new_term_ord)?;
                 }
             }
             serialize_vals.close_field()?;
@@ -423,13 +567,10 @@ impl IndexMerger {
         {
             let mut serialize_vals = fast_field_serializer
                 .new_u64_fast_field_with_idx(field, min_value, max_value, 1)?;
-            for (reader, ff_reader) in self.readers.iter().zip(ff_readers) {
-                // TODO optimize if no deletes
-                for doc in reader.doc_ids_alive() {
-                    ff_reader.get_vals(doc, &mut vals);
-                    for &val in &vals {
-                        serialize_vals.add_val(val)?;
-                    }
+            for &(segment_ord, doc) in &self.doc_id_mapping {
+                ff_readers[segment_ord].get_vals(doc, &mut vals);
+                for &val in &vals {
+                    serialize_vals.add_val(val)?;
                 }
             }
             serialize_vals.close_field()?;
@@ -446,7 +587,7 @@ impl IndexMerger {
         let mut bytes_readers: Vec<BytesFastFieldReader> = Vec::new();
 
         for reader in &self.readers {
-            let bytes_reader = reader.fast_fields().bytes(field)?;
+            let bytes_reader = reader.fast_fields().raw_bytes(field)?;
             if let Some(delete_bitset) = reader.delete_bitset() {
                 for doc in 0u32..reader.max_doc() {
                     if delete_bitset.is_alive(doc) {
@@ -466,25 +607,18 @@ impl IndexMerger {
             let mut serialize_idx =
                 fast_field_serializer.new_u64_fast_field_with_idx(field, 0, total_num_vals, 0)?;
             let mut idx = 0;
-            for (segment_reader, bytes_reader) in self.readers.iter().zip(&bytes_readers) {
-                for doc in segment_reader.doc_ids_alive() {
-                    serialize_idx.add_val(idx)?;
-                    idx += bytes_reader.get_bytes(doc).len() as u64;
-                }
+            for &(segment_ord, doc) in &self.doc_id_mapping {
+                serialize_idx.add_val(idx)?;
+                idx += bytes_readers[segment_ord].get_bytes(doc).len() as u64;
             }
             serialize_idx.add_val(idx)?;
             serialize_idx.close_field()?;
         }
 
         let mut serialize_vals = fast_field_serializer.new_bytes_fast_field_with_idx(field, 1)?;
-        for segment_reader in &self.readers {
-            let bytes_reader = segment_reader.fast_fields().bytes(field)
-                .expect("Failed to find bytes field in fast field reader. This is a bug in tantivy. Please report.");
-            // TODO: optimize if no deletes
-            for doc in segment_reader.doc_ids_alive() {
-                let val = bytes_reader.get_bytes(doc);
-                serialize_vals.write_all(val)?;
-            }
+        for &(segment_ord, doc) in &self.doc_id_mapping {
+            let val = bytes_readers[segment_ord].get_bytes(doc);
+            serialize_vals.write_all(val)?;
         }
         serialize_vals.flush()?;
         Ok(())
@@ -522,22 +656,15 @@ impl IndexMerger {
         };
 
         let mut merged_terms = TermMerger::new(field_term_streams);
-        let mut max_doc = 0;
 
         // map from segment doc ids to the resulting merged segment doc id.
-        let mut merged_doc_id_map: Vec<Vec<Option<DocId>>> = Vec::with_capacity(self.readers.len());
-
-        for reader in &self.readers {
-            let mut segment_local_map = Vec::with_capacity(reader.max_doc() as usize);
-            for doc_id in 0..reader.max_doc() {
-                if reader.is_deleted(doc_id) {
-                    segment_local_map.push(None);
-                } else {
-                    segment_local_map.push(Some(max_doc));
-                    max_doc += 1u32;
-                }
-            }
-            merged_doc_id_map.push(segment_local_map);
+        let mut merged_doc_id_map: Vec<Vec<Option<DocId>>> = self
+            .readers
+            .iter()
+            .map(|reader| vec![None; reader.max_doc() as usize])
+            .collect();
+        for (merged_doc_id, &(segment_ord, doc_id)) in self.doc_id_mapping.iter().enumerate() {
+            merged_doc_id_map[segment_ord][doc_id as usize] = Some(merged_doc_id as DocId);
         }
 
         // The total number of tokens will only be exact when there has been no deletes.
@@ -555,6 +682,9 @@ impl IndexMerger {
         // - Segment 2's doc ids become  [seg0.max_doc + seg1.max_doc,
         //                                seg0.max_doc + seg1.max_doc + seg2.max_doc]
         // ...
+        //
+        // If the merged segment is sorted, the doc ids are remapped following
+        // the sort order instead.
         let mut field_serializer =
             serializer.new_field(indexed_field, total_num_tokens, fieldnorm_reader)?;
 
@@ -568,6 +698,12 @@ impl IndexMerger {
 
         let mut segment_postings_containing_the_term: Vec<(usize, SegmentPostings)> = vec![];
 
+        // If the merged segment is sorted, the documents of a term need to be
+        // sorted before being written. Their delta positions are then buffered
+        // in `sorted_positions`.
+        let mut sorted_docs: Vec<(DocId, u32, Range<usize>)> = Vec::new();
+        let mut sorted_positions: Vec<u32> = Vec::new();
+
         while merged_terms.advance() {
             segment_postings_containing_the_term.clear();
             let term_bytes: &[u8] = merged_terms.key();
@@ -629,13 +765,39 @@ impl IndexMerger {
                         segment_postings.positions(&mut positions_buffer);
 
                         let delta_positions = delta_computer.compute_delta(&positions_buffer);
-                        field_serializer.write_doc(remapped_doc_id, term_freq, delta_positions)?;
+                        if self.is_sorted {
+                            let start = sorted_positions.len();
+                            sorted_positions.extend_from_slice(delta_positions);
+                            sorted_docs.push((
+                                remapped_doc_id,
+                                term_freq,
+                                start..sorted_positions.len(),
+                            ));
+                        } else {
+                            field_serializer.write_doc(
+                                remapped_doc_id,
+                                term_freq,
+                                delta_positions,
+                            )?;
+                        }
                     }
 
                     doc = segment_postings.advance();
                 }
             }
 
+            if self.is_sorted {
+                sorted_docs.sort_unstable_by_key(|(doc, _, _)| *doc);
+                for (doc, term_freq, positions_range) in sorted_docs.drain(..) {
+                    field_serializer.write_doc(
+                        doc,
+                        term_freq,
+                        &sorted_positions[positions_range],
+                    )?;
+                }
+                sorted_positions.clear();
+            }
+
             // closing the term.
             field_serializer.close_term()?;
         }
@@ -666,9 +828,25 @@ impl IndexMerger {
     }
 
     fn write_storable_fields(&self, store_writer: &mut StoreWriter) -> crate::Result<()> {
+        if self.is_sorted {
+            let store_readers = self
+                .readers
+                .iter()
+                .map(SegmentReader::get_store_reader)
+                .collect::<io::Result<Vec<_>>>()?;
+            for &(segment_ord, doc_id) in &self.doc_id_mapping {
+                let doc = store_readers[segment_ord].get(doc_id)?;
+                store_writer.store(&doc)?;
+            }
+            return Ok(());
+        }
         for reader in &self.readers {
             let store_reader = reader.get_store_reader()?;
-            if reader.num_deleted_docs() > 0 {
+            // Blocks can only be stacked if they do not contain deleted documents,
+            // and are compressed with the compression of the merged segment.
+            if reader.num_deleted_docs() > 0
+                || store_reader.compression() != store_writer.compression()
+            {
                 for doc_id in reader.doc_ids_alive() {
                     let doc = store_reader.get(doc_id)?;
                     store_writer.store(&doc)?;
@@ -679,22 +857,90 @@ impl IndexMerger {
         }
         Ok(())
     }
+
+    fn write_term_vectors(&self, term_vectors_writer: &mut TermVectorsWriter) -> crate::Result<()> {
+        if self.is_sorted {
+            let term_vectors_readers = self
+                .readers
+                .iter()
+                .map(SegmentReader::get_term_vectors_reader)
+                .collect::<io::Result<Vec<_>>>()?;
+            for &(segment_ord, doc_id) in &self.doc_id_mapping {
+                if let Some(term_vectors_reader) = term_vectors_readers[segment_ord].as_ref() {
+                    let doc_bytes = term_vectors_reader.get_document_bytes(doc_id)?;
+                    term_vectors_writer.store_bytes(&doc_bytes)?;
+                } else {
+                    // The segment was written before term vectors were introduced.
+                    term_vectors_writer.end_doc()?;
+                }
+            }
+            return Ok(());
+        }
+        for reader in &self.readers {
+            match reader.get_term_vectors_reader()? {
+                Some(term_vectors_reader)
+                    if reader.num_deleted_docs() == 0
+                        && term_vectors_reader.compression()
+                            == term_vectors_writer.compression() =>
+                {
+                    term_vectors_writer.stack(&term_vectors_reader)?;
+                }
+                Some(term_vectors_reader) => {
+                    for doc_id in reader.doc_ids_alive() {
+                        let doc_bytes = term_vectors_reader.get_document_bytes(doc_id)?;
+                        term_vectors_writer.store_bytes(&doc_bytes)?;
+                    }
+                }
+                None => {
+                    // The segment was written before term vectors were introduced.
+                    for _ in reader.doc_ids_alive() {
+                        term_vectors_writer.end_doc()?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
-impl SerializableSegment for IndexMerger {
-    fn write(&self, mut serializer: SegmentSerializer) -> crate::Result<u32> {
+impl IndexMerger {
+    /// Writes the merged segment, calling `after_step` with the
+    /// components written after each step of the merge.
+    ///
+    /// If `after_step` returns an error, the merge is interrupted.
+    ///
+    /// # Returns
+    /// The number of documents in the segment.
+    pub(crate) fn write<F>(
+        &self,
+        mut serializer: SegmentSerializer,
+        mut after_step: F,
+    ) -> crate::Result<u32>
+    where
+        F: FnMut(&[SegmentComponent]) -> crate::Result<()>,
+    {
         if let Some(fieldnorms_serializer) = serializer.extract_fieldnorms_serializer() {
             self.write_fieldnorms(fieldnorms_serializer)?;
         }
+        after_step(&[SegmentComponent::FIELDNORMS])?;
         let fieldnorm_data = serializer
             .segment()
             .open_read(SegmentComponent::FIELDNORMS)?;
         let fieldnorm_readers = FieldNormReaders::open(fieldnorm_data)?;
         let term_ord_mappings =
             self.write_postings(serializer.get_postings_serializer(), fieldnorm_readers)?;
+        after_step(&[
+            SegmentComponent::POSTINGS,
+            SegmentComponent::POSITIONS,
+            SegmentComponent::POSITIONSSKIP,
+            SegmentComponent::TERMS,
+        ])?;
         self.write_fast_fields(serializer.get_fast_field_serializer(), term_ord_mappings)?;
+        after_step(&[SegmentComponent::FASTFIELDS])?;
         self.write_storable_fields(serializer.get_store_writer())?;
+        self.write_term_vectors(serializer.get_term_vectors_writer())?;
         serializer.close()?;
+        after_step(&[SegmentComponent::STORE, SegmentComponent::TERMVECTORS])?;
         Ok(self.max_doc)
     }
 }
@@ -706,6 +952,7 @@ mod tests {
     use crate::collector::tests::{BytesFastFieldTestCollector, FastFieldTestCollector};
     use crate::collector::{Count, FacetCollector};
     use crate::core::Index;
+    use crate::indexer::NoMergePolicy;
     use crate::query::AllQuery;
     use crate::query::BooleanQuery;
     use crate::query::Scorer;
@@ -1581,4 +1828,141 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_optimize_for_read() -> crate::Result<()> {
+        let mut schema_builder = schema::Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT | schema::STORED);
+        let popularity = schema_builder.add_u64_field("popularity", FAST);
+        let tags = schema_builder.add_u64_field(
+            "tags",
+            IntOptions::default().set_fast(Cardinality::MultiValues),
+        );
+        let payload = schema_builder.add_bytes_field("payload", FAST);
+        let facet = schema_builder.add_facet_field("facet");
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_for_tests()?;
+            for (body, pop, facet_path) in &[
+                ("a happy tax payer", 2u64, "/cat/a"),
+                ("deleted happy doc", 9u64, "/cat/b"),
+                ("happy tax", 5u64, "/cat/b"),
+                ("tax payer", 7u64, "/cat/c"),
+            ] {
+                index_writer.add_document(doc!(
+                    text => *body,
+                    popularity => *pop,
+                    tags => *pop,
+                    tags => *pop * 10,
+                    payload => pop.to_be_bytes().as_ref(),
+                    facet => Facet::from(*facet_path),
+                ));
+            }
+            index_writer.commit()?;
+            index_writer.delete_term(Term::from_field_text(text, "deleted"));
+            index_writer.commit()?;
+        }
+        index.optimize_for_read(popularity, crate::collector::Order::Desc)?;
+
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 1);
+        let segment_reader = searcher.segment_reader(0u32);
+        assert!(!segment_reader.has_deletes());
+        let popularity_reader = segment_reader
+            .fast_fields()
+            .u64(popularity)
+            .expect("popularity is a fast field");
+        let popularities: Vec<u64> = (0..3).map(|doc| popularity_reader.get(doc)).collect();
+        assert_eq!(popularities, vec![7, 5, 2]);
+        let tags_reader = segment_reader.fast_fields().u64s(tags).unwrap();
+        let mut vals = Vec::new();
+        tags_reader.get_vals(1, &mut vals);
+        assert_eq!(&vals, &[5, 50]);
+        let payload_reader = segment_reader.fast_fields().bytes(payload).unwrap();
+        assert_eq!(payload_reader.get_bytes(2), &2u64.to_be_bytes()[..]);
+        let doc = searcher.doc(DocAddress(0, 0))?;
+        assert_eq!(doc.get_first(text).unwrap().text(), Some("tax payer"));
+
+        let get_doc_ids = |query: &dyn crate::query::Query| {
+            searcher
+                .search(query, &TEST_COLLECTOR_WITH_SCORE)
+                .map(|top_docs| top_docs.docs().to_vec())
+        };
+        let happy_query = TermQuery::new(
+            Term::from_field_text(text, "happy"),
+            IndexRecordOption::Basic,
+        );
+        assert_eq!(
+            get_doc_ids(&happy_query)?,
+            vec![DocAddress(0, 1), DocAddress(0, 2)]
+        );
+        let phrase_query = crate::query::PhraseQuery::new(vec![
+            Term::from_field_text(text, "tax"),
+            Term::from_field_text(text, "payer"),
+        ]);
+        assert_eq!(
+            get_doc_ids(&phrase_query)?,
+            vec![DocAddress(0, 0), DocAddress(0, 2)]
+        );
+        let mut facet_collector = FacetCollector::for_field(facet);
+        facet_collector.add_facet(Facet::from("/cat"));
+        let facet_counts = searcher.search(&AllQuery, &facet_collector)?;
+        let facets: Vec<(String, u64)> = facet_counts
+            .get("/cat")
+            .map(|(facet, count)| (facet.to_string(), count))
+            .collect();
+        assert_eq!(
+            facets,
+            vec![
+                ("/cat/a".to_string(), 1),
+                ("/cat/b".to_string(), 1),
+                ("/cat/c".to_string(), 1)
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_optimize_for_read_many_segments() -> crate::Result<()> {
+        let mut schema_builder = schema::Schema::builder();
+        let popularity = schema_builder.add_u64_field("popularity", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_for_tests()?;
+            index_writer.set_merge_policy(Box::new(NoMergePolicy));
+            for segment in 0..10u64 {
+                for doc in 0..10u64 {
+                    index_writer.add_document(doc!(popularity => (segment * 7 + doc * 3) % 10));
+                }
+                index_writer.commit()?;
+            }
+        }
+        index.optimize_for_read(popularity, crate::collector::Order::Asc)?;
+
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 10);
+        for segment_reader in searcher.segment_readers() {
+            let popularity_reader = segment_reader.fast_fields().u64(popularity).unwrap();
+            let popularities: Vec<u64> = (0..segment_reader.max_doc())
+                .map(|doc| popularity_reader.get(doc))
+                .collect();
+            assert_eq!(popularities, (0..10u64).collect::<Vec<_>>());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_optimize_for_read_requires_fast_field() -> crate::Result<()> {
+        let mut schema_builder = schema::Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text => "hello"));
+        index_writer.commit()?;
+        assert!(matches!(
+            index_writer.optimize_for_read(text, crate::collector::Order::Asc),
+            Err(crate::TantivyError::SchemaError(_))
+        ));
+        Ok(())
+    }
 }
@@ -2,7 +2,9 @@ pub mod delete_queue;
 
 mod doc_opstamp_mapping;
 pub mod index_writer;
+mod json_indexer;
 mod log_merge_policy;
+mod merge_callback;
 mod merge_operation;
 pub mod merge_policy;
 pub mod merger;
@@ -15,9 +17,12 @@ pub mod segment_serializer;
 pub mod segment_updater;
 mod segment_writer;
 mod stamper;
+mod tiered_merge_policy;
+mod wal;
 
 pub use self::index_writer::IndexWriter;
 pub use self::log_merge_policy::LogMergePolicy;
+pub use self::merge_callback::{CancellationToken, MergeCallback};
 pub use self::merge_operation::MergeOperation;
 pub use self::merge_policy::{MergeCandidate, MergePolicy, NoMergePolicy};
 pub use self::prepared_commit::PreparedCommit;
@@ -25,6 +30,8 @@ pub use self::segment_entry::SegmentEntry;
 pub use self::segment_manager::SegmentManager;
 pub use self::segment_serializer::SegmentSerializer;
 pub use self::segment_writer::SegmentWriter;
+pub use self::tiered_merge_policy::TieredMergePolicy;
+pub use self::wal::WalSync;
 
 /// Alias for the default merge policy, which is the `LogMergePolicy`.
 pub type DefaultMergePolicy = LogMergePolicy;
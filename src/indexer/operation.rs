@@ -1,21 +1,69 @@
 use crate::schema::Document;
 use crate::schema::Term;
 use crate::Opstamp;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Defines how an `IndexWriter` handles the delete operations
+/// that do not match any document.
+///
+/// See [`IndexWriter::set_delete_policy`](./struct.IndexWriter.html#method.set_delete_policy).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeletePolicy {
+    /// Deleting a term that no document contains is a no-op.
+    Ignore,
+    /// The commit fails if one of its delete operations did not delete any document.
+    Error,
+}
+
+impl Default for DeletePolicy {
+    /// Delete operations matching no document are ignored by default.
+    fn default() -> Self {
+        DeletePolicy::Ignore
+    }
+}
 
 /// Timestamped Delete operation.
-#[derive(Clone, Eq, PartialEq, Debug)]
+#[derive(Clone, Debug)]
 pub struct DeleteOperation {
     pub opstamp: Opstamp,
     pub term: Term,
+    // Set once the operation has deleted a document, shared by the clones.
+    matched: Arc<AtomicBool>,
 }
 
-impl Default for DeleteOperation {
-    fn default() -> Self {
+impl DeleteOperation {
+    pub fn new(opstamp: Opstamp, term: Term) -> DeleteOperation {
         DeleteOperation {
-            opstamp: 0u64,
-            term: Term::new(),
+            opstamp,
+            term,
+            matched: Arc::default(),
         }
     }
+
+    /// Records that the operation deleted a document.
+    pub fn set_matched(&self) {
+        self.matched.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns true iff the operation deleted at least one document.
+    pub fn has_matched(&self) -> bool {
+        self.matched.load(Ordering::Relaxed)
+    }
+}
+
+impl PartialEq for DeleteOperation {
+    fn eq(&self, other: &DeleteOperation) -> bool {
+        self.opstamp == other.opstamp && self.term == other.term
+    }
+}
+
+impl Eq for DeleteOperation {}
+
+impl Default for DeleteOperation {
+    fn default() -> Self {
+        DeleteOperation::new(0u64, Term::new())
+    }
 }
 
 /// Timestamped Add operation.
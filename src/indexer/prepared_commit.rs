@@ -1,20 +1,38 @@
+use super::operation::DeleteOperation;
+use super::wal::Wal;
 use super::IndexWriter;
 use crate::Opstamp;
 use futures::executor::block_on;
+use futures::future::Future;
+use std::collections::HashMap;
+use std::sync::Arc;
 
 /// A prepared commit
 pub struct PreparedCommit<'a> {
     index_writer: &'a mut IndexWriter,
     payload: Option<String>,
+    metadata: HashMap<String, String>,
     opstamp: Opstamp,
+    // Delete operations that must have matched a document, as per the `DeletePolicy`.
+    delete_operations: Vec<DeleteOperation>,
+    // Write-ahead log of the `IndexWriter`, and its length when the commit was prepared.
+    wal_checkpoint: Option<(Arc<Wal>, u64)>,
 }
 
 impl<'a> PreparedCommit<'a> {
-    pub(crate) fn new(index_writer: &'a mut IndexWriter, opstamp: Opstamp) -> PreparedCommit<'_> {
+    pub(crate) fn new(
+        index_writer: &'a mut IndexWriter,
+        opstamp: Opstamp,
+        delete_operations: Vec<DeleteOperation>,
+        wal_checkpoint: Option<(Arc<Wal>, u64)>,
+    ) -> PreparedCommit<'a> {
         PreparedCommit {
             index_writer,
             payload: None,
+            metadata: HashMap::new(),
             opstamp,
+            delete_operations,
+            wal_checkpoint,
         }
     }
 
@@ -22,21 +40,53 @@ impl<'a> PreparedCommit<'a> {
         self.opstamp
     }
 
+    /// Sets the payload of the commit, a free-form string stored in the meta of the index.
     pub fn set_payload(&mut self, payload: &str) {
         self.payload = Some(payload.to_string())
     }
 
+    /// Sets the value of `key` in the metadata of the index, as part of the commit.
+    ///
+    /// Unlike [`Index::set_metadata`](../struct.Index.html#method.set_metadata),
+    /// the value is only persisted if the commit succeeds, together with
+    /// the documents of the commit.
+    pub fn set_metadata(&mut self, key: &str, value: &str) {
+        self.metadata.insert(key.to_string(), value.to_string());
+    }
+
     pub fn abort(self) -> crate::Result<Opstamp> {
         self.index_writer.rollback()
     }
 
-    pub fn commit(self) -> crate::Result<Opstamp> {
+    /// Returns a future completing once the commit has been persisted.
+    ///
+    /// Unlike [`commit()`](#method.commit), the calling thread is not blocked:
+    /// the commit runs on the thread pool of the segment updater.
+    ///
+    /// Once the commit is persisted, the write-ahead log of the `IndexWriter`, if any,
+    /// is emptied, unless operations were logged since the commit was prepared.
+    pub fn commit_future(self) -> impl Future<Output = crate::Result<Opstamp>> {
         info!("committing {}", self.opstamp);
-        let _ = block_on(
-            self.index_writer
-                .segment_updater()
-                .schedule_commit(self.opstamp, self.payload),
+        let opstamp = self.opstamp;
+        let live_searchers = self.index_writer.live_searchers().clone();
+        let wal_checkpoint = self.wal_checkpoint;
+        let commit = self.index_writer.segment_updater().schedule_commit(
+            opstamp,
+            self.payload,
+            self.metadata,
+            self.delete_operations,
         );
-        Ok(self.opstamp)
+        async move {
+            commit.await?;
+            if let Some((wal, checkpoint)) = wal_checkpoint {
+                wal.truncate_committed(checkpoint)?;
+            }
+            live_searchers.notify_commit(opstamp);
+            Ok(opstamp)
+        }
+    }
+
+    pub fn commit(self) -> crate::Result<Opstamp> {
+        block_on(self.commit_future())
     }
 }
@@ -210,4 +210,14 @@ impl SegmentManager {
         let registers_lock = self.read();
         registers_lock.committed.segment_metas()
     }
+
+    /// Returns the sum of the sizes in bytes of the uncommitted segments.
+    pub fn uncommitted_num_bytes(&self) -> u64 {
+        self.read()
+            .uncommitted
+            .segment_metas()
+            .iter()
+            .filter_map(SegmentMeta::num_bytes)
+            .sum()
+    }
 }
@@ -4,12 +4,14 @@ use crate::fastfield::FastFieldSerializer;
 use crate::fieldnorm::FieldNormsSerializer;
 use crate::postings::InvertedIndexSerializer;
 use crate::store::StoreWriter;
+use crate::termvector::TermVectorsWriter;
 
 /// Segment serializer is in charge of laying out on disk
 /// the data accumulated and sorted by the `SegmentWriter`.
 pub struct SegmentSerializer {
     segment: Segment,
     store_writer: StoreWriter,
+    term_vectors_writer: TermVectorsWriter,
     fast_field_serializer: FastFieldSerializer,
     fieldnorms_serializer: Option<FieldNormsSerializer>,
     postings_serializer: InvertedIndexSerializer,
@@ -19,6 +21,14 @@ impl SegmentSerializer {
     /// Creates a new `SegmentSerializer`.
     pub fn for_segment(mut segment: Segment) -> crate::Result<SegmentSerializer> {
         let store_write = segment.open_write(SegmentComponent::STORE)?;
+        let store_writer =
+            StoreWriter::with_compression(store_write, segment.meta().store_compression());
+
+        let term_vectors_write = segment.open_write(SegmentComponent::TERMVECTORS)?;
+        let term_vectors_writer = TermVectorsWriter::new(StoreWriter::with_compression(
+            term_vectors_write,
+            segment.meta().store_compression(),
+        ));
 
         let fast_field_write = segment.open_write(SegmentComponent::FASTFIELDS)?;
         let fast_field_serializer = FastFieldSerializer::from_write(fast_field_write)?;
@@ -29,7 +39,8 @@ impl SegmentSerializer {
         let postings_serializer = InvertedIndexSerializer::open(&mut segment)?;
         Ok(SegmentSerializer {
             segment,
-            store_writer: StoreWriter::new(store_write),
+            store_writer,
+            term_vectors_writer,
             fast_field_serializer,
             fieldnorms_serializer: Some(fieldnorms_serializer),
             postings_serializer,
@@ -62,6 +73,11 @@ impl SegmentSerializer {
         &mut self.store_writer
     }
 
+    /// Accessor to the `TermVectorsWriter`.
+    pub(crate) fn get_term_vectors_writer(&mut self) -> &mut TermVectorsWriter {
+        &mut self.term_vectors_writer
+    }
+
     /// Finalize the segment serialization.
     pub fn close(mut self) -> crate::Result<()> {
         if let Some(fieldnorms_serializer) = self.extract_fieldnorms_serializer() {
@@ -70,6 +86,7 @@ impl SegmentSerializer {
         self.fast_field_serializer.close()?;
         self.postings_serializer.close()?;
         self.store_writer.close()?;
+        self.term_vectors_writer.close()?;
         Ok(())
     }
 }
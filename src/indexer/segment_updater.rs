@@ -1,36 +1,40 @@
 use super::segment_manager::{get_mergeable_segments, SegmentManager};
+use crate::collector::Order;
 use crate::core::Index;
 use crate::core::IndexMeta;
+use crate::core::IndexSettings;
 use crate::core::Segment;
 use crate::core::SegmentId;
 use crate::core::SegmentMeta;
-use crate::core::SerializableSegment;
 use crate::core::META_FILEPATH;
 use crate::directory::{Directory, DirectoryClone, GarbageCollectionResult};
 use crate::indexer::delete_queue::DeleteCursor;
 use crate::indexer::index_writer::advance_deletes;
+use crate::indexer::merge_callback::{CancellationToken, MergeCallback};
 use crate::indexer::merge_operation::MergeOperationInventory;
 use crate::indexer::merger::IndexMerger;
+use crate::indexer::operation::DeleteOperation;
 use crate::indexer::segment_manager::SegmentsStatus;
 use crate::indexer::stamper::Stamper;
 use crate::indexer::SegmentEntry;
 use crate::indexer::SegmentSerializer;
 use crate::indexer::{DefaultMergePolicy, MergePolicy};
 use crate::indexer::{MergeCandidate, MergeOperation};
-use crate::schema::Schema;
+use crate::schema::{Field, Schema};
 use crate::Opstamp;
 use futures::channel::oneshot;
 use futures::executor::{ThreadPool, ThreadPoolBuilder};
 use futures::future::Future;
 use futures::future::TryFutureExt;
 use std::borrow::BorrowMut;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::io::Write;
 use std::ops::Deref;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::sync::RwLock;
+use std::time::Instant;
 
 const NUM_MERGE_THREADS: usize = 4;
 
@@ -50,6 +54,8 @@ pub fn save_new_metas(schema: Schema, directory: &dyn Directory) -> crate::Resul
             schema,
             opstamp: 0u64,
             payload: None,
+            metadata: HashMap::new(),
+            index_settings: IndexSettings::default(),
         },
         directory,
     )
@@ -64,7 +70,7 @@ pub fn save_new_metas(schema: Schema, directory: &dyn Directory) -> crate::Resul
 /// and flushed.
 ///
 /// This method is not part of tantivy's public API
-fn save_metas(metas: &IndexMeta, directory: &dyn Directory) -> crate::Result<()> {
+pub(crate) fn save_metas(metas: &IndexMeta, directory: &dyn Directory) -> crate::Result<()> {
     info!("save metas");
     let mut buffer = serde_json::to_vec_pretty(metas)?;
     // Just adding a new line at the end of the buffer.
@@ -110,7 +116,17 @@ fn merge(
     index: &Index,
     mut segment_entries: Vec<SegmentEntry>,
     target_opstamp: Opstamp,
+    sort_by: Option<(Field, Order)>,
+    merge_callback: Option<Arc<dyn MergeCallback>>,
+    cancellation_token: CancellationToken,
 ) -> crate::Result<SegmentEntry> {
+    cancellation_token.check()?;
+    #[cfg(feature = "otel")]
+    let mut span = crate::otel::start_span(
+        "tantivy.merge",
+        &[("tantivy.num_segments", segment_entries.len() as i64)],
+    );
+    let start = Instant::now();
     // first we need to apply deletes to our segment.
     let merged_segment = index.new_segment();
 
@@ -127,17 +143,54 @@ fn merge(
         .map(|segment_entry| index.segment(segment_entry.meta().clone()))
         .collect();
 
+    if let Some(merge_callback) = merge_callback.as_ref() {
+        let segment_ids: Vec<SegmentId> = segments.iter().map(Segment::id).collect();
+        merge_callback.on_merge_start(&segment_ids);
+    }
+    let bytes_total: u64 = segments.iter().map(Segment::compute_num_bytes).sum();
+    let mut bytes_written = 0u64;
+
     // An IndexMerger is like a "view" of our merged segments.
-    let merger: IndexMerger = IndexMerger::open(index.schema(), &segments[..])?;
+    let merger: IndexMerger = IndexMerger::open(index.schema(), &segments[..], sort_by)?;
 
     // ... we just serialize this index merger in our new segment to merge the two segments.
     let segment_serializer = SegmentSerializer::for_segment(merged_segment.clone())?;
 
-    let num_docs = merger.write(segment_serializer)?;
+    let num_docs = merger.write(segment_serializer, |components| {
+        bytes_written += segments
+            .iter()
+            .map(|segment| segment.components_num_bytes(components))
+            .sum::<u64>();
+        if let Some(merge_callback) = merge_callback.as_ref() {
+            merge_callback.on_merge_progress(bytes_written, bytes_total);
+        }
+        cancellation_token.check()
+    })?;
+    #[cfg(feature = "otel")]
+    crate::otel::record(&mut span, &[("tantivy.num_docs", num_docs as i64)]);
 
     let merged_segment_id = merged_segment.id();
+    if let Some(merge_callback) = merge_callback.as_ref() {
+        merge_callback.on_merge_complete(merged_segment_id);
+    }
 
-    let segment_meta = index.new_segment_meta(merged_segment_id, num_docs);
+    let mut user_data = HashMap::new();
+    for segment_entry in &segment_entries {
+        user_data.extend(
+            segment_entry
+                .meta()
+                .user_data()
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone())),
+        );
+    }
+    let segment_meta = index
+        .new_segment_meta(merged_segment_id, num_docs)
+        .with_num_bytes(merged_segment.compute_num_bytes())
+        .with_user_data(user_data);
+    index
+        .metrics_collector()
+        .record_merge(start.elapsed().as_millis() as u64);
     Ok(SegmentEntry::new(segment_meta, delete_cursor, None))
 }
 
@@ -155,6 +208,8 @@ pub(crate) struct InnerSegmentUpdater {
     index: Index,
     segment_manager: SegmentManager,
     merge_policy: RwLock<Arc<dyn MergePolicy>>,
+    merge_callback: RwLock<Option<Arc<dyn MergeCallback>>>,
+    merge_cancellation_token: RwLock<CancellationToken>,
     killed: AtomicBool,
     stamper: Stamper,
     merge_operations: MergeOperationInventory,
@@ -194,6 +249,8 @@ impl SegmentUpdater {
             index,
             segment_manager,
             merge_policy: RwLock::new(Arc::new(DefaultMergePolicy::default())),
+            merge_callback: RwLock::new(None),
+            merge_cancellation_token: RwLock::new(CancellationToken::default()),
             killed: AtomicBool::new(false),
             stamper,
             merge_operations: Default::default(),
@@ -209,6 +266,18 @@ impl SegmentUpdater {
         *self.merge_policy.write().unwrap() = arc_merge_policy;
     }
 
+    pub(crate) fn restore_merge_policy(&self, merge_policy: Arc<dyn MergePolicy>) {
+        *self.merge_policy.write().unwrap() = merge_policy;
+    }
+
+    pub fn set_merge_callback(&self, merge_callback: Box<dyn MergeCallback>) {
+        *self.merge_callback.write().unwrap() = Some(Arc::from(merge_callback));
+    }
+
+    pub fn set_merge_cancellation_token(&self, cancellation_token: CancellationToken) {
+        *self.merge_cancellation_token.write().unwrap() = cancellation_token;
+    }
+
     fn schedule_future<T: 'static + Send, F: Future<Output = crate::Result<T>> + 'static + Send>(
         &self,
         f: F,
@@ -247,6 +316,10 @@ impl SegmentUpdater {
         self.segment_manager.remove_all_segments();
     }
 
+    pub(crate) fn uncommitted_num_bytes(&self) -> u64 {
+        self.segment_manager.uncommitted_num_bytes()
+    }
+
     pub fn kill(&mut self) {
         self.killed.store(true, Ordering::Release);
     }
@@ -272,6 +345,7 @@ impl SegmentUpdater {
         &self,
         opstamp: Opstamp,
         commit_message: Option<String>,
+        metadata: HashMap<String, String>,
     ) -> crate::Result<()> {
         if self.is_alive() {
             let index = &self.index;
@@ -297,10 +371,15 @@ impl SegmentUpdater {
                 schema: index.schema(),
                 opstamp,
                 payload: commit_message,
+                metadata,
+                index_settings: index.settings().clone(),
             };
             // TODO add context to the error.
             save_metas(&index_meta, directory.box_clone().borrow_mut())?;
             self.store_meta(&index_meta);
+            index
+                .metrics_collector()
+                .record_segment_count(index_meta.segments.len());
         }
         Ok(())
     }
@@ -331,12 +410,32 @@ impl SegmentUpdater {
         &self,
         opstamp: Opstamp,
         payload: Option<String>,
+        metadata_updates: HashMap<String, String>,
+        delete_operations: Vec<DeleteOperation>,
     ) -> impl Future<Output = crate::Result<()>> {
         let segment_updater: SegmentUpdater = self.clone();
         self.schedule_future(async move {
-            let segment_entries = segment_updater.purge_deletes(opstamp)?;
+            let mut segment_entries = segment_updater.purge_deletes(opstamp)?;
+            // All of the deletes of the commit have been applied by now.
+            if let Some(delete_operation) = delete_operations
+                .iter()
+                .find(|delete_operation| !delete_operation.has_matched())
+            {
+                return Err(crate::TantivyError::InvalidArgument(format!(
+                    "The delete operation {} on {:?} did not match any document",
+                    delete_operation.opstamp, delete_operation.term
+                )));
+            }
+            for segment_entry in &mut segment_entries {
+                if segment_entry.meta().opstamp().is_none() {
+                    let segment_meta = segment_entry.meta().clone().with_opstamp(opstamp);
+                    segment_entry.set_meta(segment_meta);
+                }
+            }
             segment_updater.segment_manager.commit(segment_entries);
-            segment_updater.save_metas(opstamp, payload)?;
+            let mut metadata = segment_updater.load_metas().metadata.clone();
+            metadata.extend(metadata_updates);
+            segment_updater.save_metas(opstamp, payload, metadata)?;
             let _ = garbage_collect_files(segment_updater.clone()).await;
             segment_updater.consider_merge_options().await;
             Ok(())
@@ -352,8 +451,21 @@ impl SegmentUpdater {
     }
 
     pub(crate) fn make_merge_operation(&self, segment_ids: &[SegmentId]) -> MergeOperation {
+        self.make_sorted_merge_operation(segment_ids, None)
+    }
+
+    pub(crate) fn make_sorted_merge_operation(
+        &self,
+        segment_ids: &[SegmentId],
+        sort_by: Option<(Field, Order)>,
+    ) -> MergeOperation {
         let commit_opstamp = self.load_metas().opstamp;
-        MergeOperation::new(&self.merge_operations, commit_opstamp, segment_ids.to_vec())
+        MergeOperation::new_with_sort(
+            &self.merge_operations,
+            commit_opstamp,
+            segment_ids.to_vec(),
+            sort_by,
+        )
     }
 
     // Starts a merge operation. This function will block until the merge operation is effectively
@@ -391,6 +503,8 @@ impl SegmentUpdater {
 
         let (merging_future_send, merging_future_recv) =
             oneshot::channel::<crate::Result<SegmentMeta>>();
+        let merge_callback = self.merge_callback.read().unwrap().clone();
+        let cancellation_token = self.merge_cancellation_token.read().unwrap().clone();
 
         self.merge_thread_pool.spawn_ok(async move {
             // The fact that `merge_operation` is moved here is important.
@@ -401,6 +515,9 @@ impl SegmentUpdater {
                 &segment_updater.index,
                 segment_entries,
                 merge_operation.target_opstamp(),
+                merge_operation.sort_by(),
+                merge_callback,
+                cancellation_token,
             ) {
                 Ok(after_merge_segment_entry) => {
                     let segment_meta = segment_updater
@@ -408,6 +525,14 @@ impl SegmentUpdater {
                         .await;
                     let _send_result = merging_future_send.send(segment_meta);
                 }
+                Err(crate::TantivyError::Cancelled) => {
+                    info!(
+                        "Merge of {:?} was cancelled",
+                        merge_operation.segment_ids().to_vec()
+                    );
+                    let _send_result =
+                        merging_future_send.send(Err(crate::TantivyError::Cancelled));
+                }
                 Err(e) => {
                     warn!(
                         "Merge of {:?} was cancelled: {:?}",
@@ -500,13 +625,22 @@ impl SegmentUpdater {
                     }
                 }
                 let previous_metas = segment_updater.load_metas();
+                // The merged segment did not exist as of the last commit.
+                let segment_meta = after_merge_segment_entry
+                    .meta()
+                    .clone()
+                    .with_opstamp(previous_metas.opstamp + 1);
+                after_merge_segment_entry.set_meta(segment_meta);
                 let segments_status = segment_updater
                     .segment_manager
                     .end_merge(merge_operation.segment_ids(), after_merge_segment_entry)?;
 
                 if segments_status == SegmentsStatus::Committed {
-                    segment_updater
-                        .save_metas(previous_metas.opstamp, previous_metas.payload.clone())?;
+                    segment_updater.save_metas(
+                        previous_metas.opstamp,
+                        previous_metas.payload.clone(),
+                        previous_metas.metadata.clone(),
+                    )?;
                 }
 
                 segment_updater.consider_merge_options().await;
@@ -542,8 +676,12 @@ impl SegmentUpdater {
 mod tests {
 
     use crate::indexer::merge_policy::tests::MergeWheneverPossible;
+    use crate::indexer::{CancellationToken, MergeCallback, NoMergePolicy};
     use crate::schema::*;
-    use crate::Index;
+    use crate::{Index, SegmentId, TantivyError};
+    use futures::executor::block_on;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
 
     #[test]
     fn test_delete_during_merge() {
@@ -694,4 +832,110 @@ mod tests {
             .segment_entries();
         assert!(seg_vec.is_empty());
     }
+
+    #[derive(Default)]
+    struct RecordingMergeCallback {
+        started: Mutex<Vec<SegmentId>>,
+        progress: Mutex<Vec<(u64, u64)>>,
+        completed: Mutex<Option<SegmentId>>,
+    }
+
+    impl MergeCallback for Arc<RecordingMergeCallback> {
+        fn on_merge_start(&self, segment_ids: &[SegmentId]) {
+            self.started.lock().unwrap().extend_from_slice(segment_ids);
+        }
+
+        fn on_merge_progress(&self, bytes_written: u64, bytes_total: u64) {
+            self.progress
+                .lock()
+                .unwrap()
+                .push((bytes_written, bytes_total));
+        }
+
+        fn on_merge_complete(&self, new_segment_id: SegmentId) {
+            *self.completed.lock().unwrap() = Some(new_segment_id);
+        }
+    }
+
+    #[test]
+    fn test_merge_user_data() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_for_tests()?;
+            index_writer.set_merge_policy(Box::new(NoMergePolicy));
+            for _ in 0..2 {
+                index_writer.add_document(doc!(text_field => "a"));
+                index_writer.commit()?;
+            }
+        }
+        let mut metas = index.load_metas()?;
+        metas.segments = metas
+            .segments
+            .into_iter()
+            .enumerate()
+            .map(|(ordinal, segment_meta)| {
+                let mut user_data = HashMap::new();
+                user_data.insert("source".to_string(), format!("batch-{}", ordinal));
+                user_data.insert(format!("key-{}", ordinal), "value".to_string());
+                segment_meta.with_user_data(user_data)
+            })
+            .collect();
+        super::save_metas(&metas, index.directory())?;
+
+        let mut index_writer = index.writer_for_tests()?;
+        let segment_ids = index.searchable_segment_ids()?;
+        let merged_segment_meta = block_on(index_writer.merge(&segment_ids))?;
+        let user_data = merged_segment_meta.user_data();
+        assert_eq!(user_data.len(), 3);
+        assert_eq!(user_data["key-0"], "value");
+        assert_eq!(user_data["key-1"], "value");
+        index_writer.wait_merging_threads()?;
+        let segment_metas = index.searchable_segment_metas()?;
+        assert_eq!(segment_metas.len(), 1);
+        assert_eq!(segment_metas[0].user_data(), user_data);
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_callback_and_cancellation() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text_field = schema_builder.add_text_field("text", TEXT | STORED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.set_merge_policy(Box::new(NoMergePolicy));
+        let merge_callback = Arc::new(RecordingMergeCallback::default());
+        index_writer.set_merge_callback(Box::new(merge_callback.clone()));
+        for _ in 0..2 {
+            index_writer.add_document(doc!(text_field => "a b c"));
+            index_writer.commit()?;
+        }
+        let segment_ids = index.searchable_segment_ids()?;
+        let merged_segment_meta = block_on(index_writer.merge(&segment_ids))?;
+        assert_eq!(merge_callback.started.lock().unwrap().len(), 2);
+        assert_eq!(
+            *merge_callback.completed.lock().unwrap(),
+            Some(merged_segment_meta.id())
+        );
+        let progress = merge_callback.progress.lock().unwrap().clone();
+        assert_eq!(progress.len(), 4);
+        let (bytes_written, bytes_total) = progress[3];
+        assert!(bytes_total > 0);
+        assert_eq!(bytes_written, bytes_total);
+
+        index_writer.add_document(doc!(text_field => "d"));
+        index_writer.commit()?;
+        let cancellation_token = CancellationToken::new();
+        index_writer.set_merge_cancellation_token(cancellation_token.clone());
+        cancellation_token.cancel();
+        let segment_ids = index.searchable_segment_ids()?;
+        assert!(matches!(
+            block_on(index_writer.merge(&segment_ids)),
+            Err(TantivyError::Cancelled)
+        ));
+        index_writer.wait_merging_threads()?;
+        assert_eq!(index.searchable_segment_ids()?.len(), 2);
+        Ok(())
+    }
 }
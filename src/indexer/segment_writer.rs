@@ -1,8 +1,8 @@
 use super::operation::AddOperation;
 use crate::core::Segment;
-use crate::core::SerializableSegment;
 use crate::fastfield::FastFieldsWriter;
 use crate::fieldnorm::{FieldNormReaders, FieldNormsWriter};
+use crate::indexer::json_indexer::JsonIndexer;
 use crate::indexer::segment_serializer::SegmentSerializer;
 use crate::postings::compute_table_size;
 use crate::postings::MultiFieldPostingsWriter;
@@ -11,6 +11,8 @@ use crate::schema::Schema;
 use crate::schema::Term;
 use crate::schema::Value;
 use crate::schema::{Field, FieldEntry};
+use crate::schema::{TextFieldIndexing, DEFAULT_POSITION_GAP};
+use crate::termvector::TermVectorsTokenStream;
 use crate::tokenizer::{BoxTokenStream, PreTokenizedStream};
 use crate::tokenizer::{FacetTokenizer, TextAnalyzer};
 use crate::tokenizer::{TokenStreamChain, Tokenizer};
@@ -78,6 +80,11 @@ impl SegmentWriter {
                             let tokenizer_name = &text_index_option.tokenizer();
                             tokenizer_manager.get(tokenizer_name)
                         }),
+                    FieldType::JsonObject(ref json_object_options) => json_object_options
+                        .get_indexing_options()
+                        .and_then(|text_index_option| {
+                            tokenizer_manager.get(text_index_option.tokenizer())
+                        }),
                     _ => None,
                 },
             )
@@ -109,8 +116,16 @@ impl SegmentWriter {
         Ok(self.doc_opstamps)
     }
 
+    /// Returns the number of bytes used to buffer the documents
+    /// that have been added to the segment so far.
+    ///
+    /// The stored fields are not accounted for, as they are
+    /// compressed and written block by block as documents are added.
     pub fn mem_usage(&self) -> usize {
         self.multifield_postings.mem_usage()
+            + self.fast_field_writers.mem_usage()
+            + self.fieldnorms_writer.mem_usage()
+            + self.doc_opstamps.capacity() * std::mem::size_of::<Opstamp>()
     }
 
     /// Indexes a new document
@@ -171,7 +186,7 @@ impl SegmentWriter {
                         }
                     }
                 }
-                FieldType::Str(_) => {
+                FieldType::Str(ref text_options) => {
                     let mut token_streams: Vec<BoxTokenStream> = vec![];
                     let mut offsets = vec![];
                     let mut total_offset = 0;
@@ -202,13 +217,36 @@ impl SegmentWriter {
                     let num_tokens = if token_streams.is_empty() {
                         0
                     } else {
-                        let mut token_stream = TokenStreamChain::new(offsets, token_streams);
-                        multifield_postings.index_text(
-                            doc_id,
-                            field,
-                            &mut token_stream,
-                            term_buffer,
-                        )
+                        let position_gap = text_options
+                            .get_indexing_options()
+                            .map(TextFieldIndexing::position_gap)
+                            .unwrap_or(DEFAULT_POSITION_GAP);
+                        let mut token_stream = TokenStreamChain::new(offsets, token_streams)
+                            .with_position_gap(position_gap as usize);
+                        let term_vectors = text_options
+                            .get_indexing_options()
+                            .map(TextFieldIndexing::term_vectors)
+                            .unwrap_or(false);
+                        if term_vectors {
+                            let mut token_stream = TermVectorsTokenStream::new(
+                                &mut token_stream,
+                                field,
+                                self.segment_serializer.get_term_vectors_writer(),
+                            );
+                            multifield_postings.index_text(
+                                doc_id,
+                                field,
+                                &mut token_stream,
+                                term_buffer,
+                            )
+                        } else {
+                            multifield_postings.index_text(
+                                doc_id,
+                                field,
+                                &mut token_stream,
+                                term_buffer,
+                            )
+                        }
                     };
 
                     self.fieldnorms_writer.record(doc_id, field, num_tokens);
@@ -278,12 +316,63 @@ impl SegmentWriter {
                         }
                     }
                 }
+                FieldType::IpAddr(ref option) => {
+                    if option.is_indexed() {
+                        for field_value in field_values {
+                            term_buffer.set_field(field_value.field());
+                            let ip_addr = field_value
+                                .value()
+                                .ip_addr_value()
+                                .ok_or_else(make_schema_error)?;
+                            term_buffer.set_ip_addr(ip_addr);
+                            multifield_postings.subscribe(doc_id, term_buffer);
+                        }
+                    }
+                }
+                FieldType::GeoPoint(_) => {
+                    // Geo points are not indexed, they are only
+                    // written as fast fields.
+                }
+                FieldType::DenseVector(_) => {
+                    // Dense vectors are not indexed, they are only
+                    // written as fast fields.
+                }
+                FieldType::DocumentBoost(_) => {
+                    // Document boosts are not indexed, they are only
+                    // written as fast fields.
+                }
+                FieldType::JsonObject(ref json_object_options) => {
+                    let position_gap = json_object_options
+                        .get_indexing_options()
+                        .map(TextFieldIndexing::position_gap)
+                        .unwrap_or(DEFAULT_POSITION_GAP);
+                    let mut json_indexer = JsonIndexer::new(
+                        doc_id,
+                        field,
+                        self.tokenizers[field.field_id() as usize].as_ref(),
+                        position_gap,
+                        term_buffer,
+                        multifield_postings,
+                    );
+                    for field_value in field_values {
+                        let json_object = field_value
+                            .value()
+                            .json_object_value()
+                            .ok_or_else(make_schema_error)?;
+                        json_indexer.index_json_object(json_object);
+                    }
+                    let num_tokens = json_indexer.num_tokens();
+                    self.fieldnorms_writer.record(doc_id, field, num_tokens);
+                }
             }
         }
         doc.filter_fields(|field| schema.get_field_entry(field).is_stored());
         doc.prepare_for_store();
         let doc_writer = self.segment_serializer.get_store_writer();
         doc_writer.store(&doc)?;
+        self.segment_serializer
+            .get_term_vectors_writer()
+            .end_doc()?;
         self.max_doc += 1;
         Ok(())
     }
@@ -330,19 +419,6 @@ fn write(
     Ok(())
 }
 
-impl SerializableSegment for SegmentWriter {
-    fn write(&self, serializer: SegmentSerializer) -> crate::Result<u32> {
-        let max_doc = self.max_doc;
-        write(
-            &self.multifield_postings,
-            &self.fast_field_writers,
-            &self.fieldnorms_writer,
-            serializer,
-        )?;
-        Ok(max_doc)
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::initial_table_size;
@@ -0,0 +1,248 @@
+use super::merge_policy::{MergeCandidate, MergePolicy};
+use crate::core::SegmentMeta;
+
+const DEFAULT_FLOOR_SEGMENT_SIZE: u64 = 2 * 1024 * 1024;
+const DEFAULT_SEGMENTS_PER_TIER: f64 = 10.0;
+const DEFAULT_MAX_MERGED_SEGMENT_MB: u64 = 5 * 1024;
+
+/// `TieredMergePolicy` merges segments of roughly equal size, measured in bytes,
+/// and tries to keep a bounded number of segments per tier of size.
+///
+/// It is inspired by the `TieredMergePolicy` of Lucene, and behaves better than the
+/// `LogMergePolicy` under write-heavy workloads, where a lot of segments of the same
+/// size accumulate.
+///
+/// The tiers grow exponentially in size, starting from the floor segment size.
+/// When the index holds more segments than its tiers allow, the policy picks the
+/// merge that gives segments of the most even sizes, without exceeding the maximum
+/// merged segment size.
+///
+/// The sizes of the segments do not count their deleted documents. The segments
+/// written by versions of tantivy that did not record their size are considered
+/// to be as small as the floor segment size.
+#[derive(Debug, Clone)]
+pub struct TieredMergePolicy {
+    floor_segment_size: u64,
+    segments_per_tier: f64,
+    max_merged_segment_mb: u64,
+}
+
+impl TieredMergePolicy {
+    /// Set the size in bytes under which all of the segments are considered
+    /// to be of the same size. This avoids having a long tail of tiny segments.
+    pub fn set_floor_segment_size(&mut self, floor_segment_size: u64) {
+        self.floor_segment_size = floor_segment_size;
+    }
+
+    /// Set the number of segments allowed in each tier, which is also
+    /// the maximum number of segments merged at once.
+    ///
+    /// Smaller values mean more merging, but fewer segments to search.
+    /// It is expected to be at least `2`.
+    pub fn set_segments_per_tier(&mut self, segments_per_tier: f64) {
+        assert!(segments_per_tier >= 2.0);
+        self.segments_per_tier = segments_per_tier;
+    }
+
+    /// Set the maximum size, in megabytes, of the segments produced by a merge.
+    ///
+    /// The segments larger than half of this size are never merged.
+    pub fn set_max_merged_segment_mb(&mut self, max_merged_segment_mb: u64) {
+        self.max_merged_segment_mb = max_merged_segment_mb;
+    }
+
+    fn max_merged_segment_size(&self) -> u64 {
+        self.max_merged_segment_mb * 1024 * 1024
+    }
+
+    fn max_merge_at_once(&self) -> usize {
+        self.segments_per_tier.floor() as usize
+    }
+
+    /// Size of the live documents of the segment.
+    fn segment_size(&self, segment: &SegmentMeta) -> u64 {
+        let num_bytes = segment.num_bytes().unwrap_or(self.floor_segment_size);
+        let max_doc = segment.max_doc();
+        if max_doc == 0 {
+            return num_bytes;
+        }
+        (num_bytes as f64 * f64::from(segment.num_docs()) / f64::from(max_doc)) as u64
+    }
+
+    fn floored(&self, size: u64) -> u64 {
+        size.max(self.floor_segment_size).max(1)
+    }
+
+    /// Number of segments the tiers allow for an index of `total_size` bytes.
+    fn allowed_segment_count(&self, mut total_size: u64) -> usize {
+        let mut tier_segment_size = self.floored(0);
+        let mut allowed_segment_count = 0f64;
+        loop {
+            let tier_segment_count = total_size as f64 / tier_segment_size as f64;
+            if tier_segment_count < self.segments_per_tier {
+                allowed_segment_count += tier_segment_count.ceil();
+                break;
+            }
+            allowed_segment_count += self.segments_per_tier;
+            total_size = total_size
+                .saturating_sub((self.segments_per_tier * tier_segment_size as f64) as u64);
+            tier_segment_size *= self.max_merge_at_once() as u64;
+        }
+        allowed_segment_count as usize
+    }
+
+    /// Scores a merge. Lower is better: it favors merging segments of even sizes.
+    fn merge_score(&self, sizes: &[u64]) -> f64 {
+        let total_size: u64 = sizes.iter().sum();
+        let total_floored_size: u64 = sizes.iter().map(|&size| self.floored(size)).sum();
+        let skew = self.floored(sizes[0]) as f64 / total_floored_size as f64;
+        // Gently favor smaller merges.
+        skew * (total_size.max(1) as f64).powf(0.05)
+    }
+}
+
+impl MergePolicy for TieredMergePolicy {
+    fn compute_merge_candidates(&self, segments: &[SegmentMeta]) -> Vec<MergeCandidate> {
+        let max_merged_segment_size = self.max_merged_segment_size();
+        let mut eligible_segments: Vec<(u64, &SegmentMeta)> = segments
+            .iter()
+            .map(|segment| (self.segment_size(segment), segment))
+            .filter(|(size, _)| *size <= max_merged_segment_size / 2)
+            .collect();
+        eligible_segments.sort_by_key(|(size, segment)| (std::cmp::Reverse(*size), segment.id()));
+
+        let total_size: u64 = eligible_segments
+            .iter()
+            .map(|(size, _)| self.floored(*size))
+            .sum();
+        let allowed_segment_count = self.allowed_segment_count(total_size);
+        let mut segment_count = eligible_segments.len();
+
+        let mut merge_candidates = Vec::new();
+        while segment_count > allowed_segment_count && eligible_segments.len() >= 2 {
+            // Finds the best run of consecutive segments, by decreasing size.
+            let mut best_merge: Option<(f64, usize, usize)> = None;
+            for start in 0..eligible_segments.len() - 1 {
+                let mut merge_size = 0u64;
+                let mut end = start;
+                while end < eligible_segments.len() && end - start < self.max_merge_at_once() {
+                    let segment_size = eligible_segments[end].0;
+                    if merge_size + segment_size > max_merged_segment_size {
+                        break;
+                    }
+                    merge_size += segment_size;
+                    end += 1;
+                }
+                if end - start < 2 {
+                    continue;
+                }
+                let sizes: Vec<u64> = eligible_segments[start..end]
+                    .iter()
+                    .map(|(size, _)| *size)
+                    .collect();
+                let score = self.merge_score(&sizes);
+                let is_best_merge = match best_merge {
+                    Some((best_score, _, _)) => score < best_score,
+                    None => true,
+                };
+                if is_best_merge {
+                    best_merge = Some((score, start, end));
+                }
+            }
+            let (_, start, end) = if let Some(best_merge) = best_merge {
+                best_merge
+            } else {
+                break;
+            };
+            let merged_segments: Vec<(u64, &SegmentMeta)> =
+                eligible_segments.drain(start..end).collect();
+            segment_count -= merged_segments.len() - 1;
+            merge_candidates.push(MergeCandidate(
+                merged_segments
+                    .iter()
+                    .map(|(_, segment)| segment.id())
+                    .collect(),
+            ));
+        }
+        merge_candidates
+    }
+}
+
+impl Default for TieredMergePolicy {
+    fn default() -> TieredMergePolicy {
+        TieredMergePolicy {
+            floor_segment_size: DEFAULT_FLOOR_SEGMENT_SIZE,
+            segments_per_tier: DEFAULT_SEGMENTS_PER_TIER,
+            max_merged_segment_mb: DEFAULT_MAX_MERGED_SEGMENT_MB,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TieredMergePolicy;
+    use crate::core::{SegmentId, SegmentMeta, SegmentMetaInventory};
+    use crate::indexer::merge_policy::MergePolicy;
+    use once_cell::sync::Lazy;
+
+    static INVENTORY: Lazy<SegmentMetaInventory> = Lazy::new(SegmentMetaInventory::default);
+
+    const MB: u64 = 1024 * 1024;
+
+    fn create_segment_meta(num_bytes: u64) -> SegmentMeta {
+        INVENTORY
+            .new_segment_meta(SegmentId::generate_random(), 1_000)
+            .with_num_bytes(num_bytes)
+    }
+
+    #[test]
+    fn test_tiered_merge_policy_empty() {
+        let merge_candidates = TieredMergePolicy::default().compute_merge_candidates(&[]);
+        assert!(merge_candidates.is_empty());
+    }
+
+    #[test]
+    fn test_tiered_merge_policy_within_tiers() {
+        let segments: Vec<SegmentMeta> = (0..10).map(|_| create_segment_meta(MB)).collect();
+        let merge_candidates = TieredMergePolicy::default().compute_merge_candidates(&segments);
+        assert!(merge_candidates.is_empty());
+    }
+
+    #[test]
+    fn test_tiered_merge_policy_too_many_segments() {
+        let segments: Vec<SegmentMeta> = (0..30).map(|_| create_segment_meta(MB)).collect();
+        let merge_candidates = TieredMergePolicy::default().compute_merge_candidates(&segments);
+        assert_eq!(merge_candidates.len(), 2);
+        assert_eq!(merge_candidates[0].0.len(), 10);
+        assert_eq!(merge_candidates[1].0.len(), 10);
+    }
+
+    #[test]
+    fn test_tiered_merge_policy_even_sizes() {
+        let large_segment = create_segment_meta(1_000 * MB);
+        let small_segments: Vec<SegmentMeta> = (0..25).map(|_| create_segment_meta(MB)).collect();
+        let mut segments = small_segments.clone();
+        segments.push(large_segment.clone());
+        let merge_candidates = TieredMergePolicy::default().compute_merge_candidates(&segments);
+        assert_eq!(merge_candidates.len(), 1);
+        assert_eq!(merge_candidates[0].0.len(), 10);
+        assert!(!merge_candidates[0].0.contains(&large_segment.id()));
+    }
+
+    #[test]
+    fn test_tiered_merge_policy_max_merged_segment_size() {
+        let mut merge_policy = TieredMergePolicy::default();
+        merge_policy.set_max_merged_segment_mb(1);
+        let segments: Vec<SegmentMeta> = (0..30).map(|_| create_segment_meta(MB)).collect();
+        assert!(merge_policy.compute_merge_candidates(&segments).is_empty());
+    }
+
+    #[test]
+    fn test_tiered_merge_policy_unknown_sizes() {
+        let segments: Vec<SegmentMeta> = (0..30)
+            .map(|_| INVENTORY.new_segment_meta(SegmentId::generate_random(), 1_000))
+            .collect();
+        let merge_candidates = TieredMergePolicy::default().compute_merge_candidates(&segments);
+        assert_eq!(merge_candidates.len(), 2);
+    }
+}
@@ -0,0 +1,253 @@
+use crate::common::BinarySerializable;
+use crate::schema::{Document, Term};
+use crate::Opstamp;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const ADD_RECORD: u8 = 0;
+const DELETE_RECORD: u8 = 1;
+const DELETE_ALL_RECORD: u8 = 2;
+
+/// Defines when the write-ahead log of an `IndexWriter` is synced to disk.
+///
+/// See [`IndexWriter::enable_wal`](./struct.IndexWriter.html#method.enable_wal).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WalSync {
+    /// The log is fsynced after each call to the `IndexWriter`.
+    ///
+    /// The acknowledged operations survive a power loss, at the cost of
+    /// a sync per document added with `add_document`.
+    PerBatch,
+    /// The log is written, but only synced to disk by the commits.
+    ///
+    /// The operations survive a crash of the process, but some of the
+    /// operations received since the last commit may be lost on a power loss.
+    PerCommit,
+}
+
+/// Operation logged in the write-ahead log.
+pub(crate) enum WalOperation<'a> {
+    Add(&'a Document),
+    Delete(&'a Term),
+    DeleteAll,
+}
+
+/// Operation read back from the write-ahead log.
+pub(crate) enum WalRecord {
+    Add(Document),
+    Delete(Term),
+    DeleteAll,
+}
+
+/// Append-only log of the operations of an `IndexWriter` that
+/// have not been committed yet.
+///
+/// Each record is the length of its payload, as a `u32`, followed by the
+/// payload: the kind of operation, its opstamp and the operation itself.
+pub(crate) struct Wal {
+    path: PathBuf,
+    file: Mutex<File>,
+    wal_sync: WalSync,
+}
+
+impl Wal {
+    /// Creates an empty log at `path`, replacing any existing file.
+    pub fn create(path: &Path, wal_sync: WalSync) -> io::Result<Wal> {
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(path)?;
+        Wal::with_file(path, file, wal_sync)
+    }
+
+    fn with_file(path: &Path, file: File, wal_sync: WalSync) -> io::Result<Wal> {
+        Ok(Wal {
+            path: path.to_path_buf(),
+            file: Mutex::new(file),
+            wal_sync,
+        })
+    }
+
+    /// Reads the records of the log at `path`, in the order they were written.
+    ///
+    /// A missing file is an empty log. The reading stops at the first record
+    /// that is incomplete or cannot be decoded, as it was being written when
+    /// the process stopped.
+    pub fn read(path: &Path) -> io::Result<Vec<(Opstamp, WalRecord)>> {
+        let data = match fs::read(path) {
+            Ok(data) => data,
+            Err(io_error) if io_error.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(io_error) => return Err(io_error),
+        };
+        let mut cursor: &[u8] = &data[..];
+        let mut records = Vec::new();
+        while !cursor.is_empty() {
+            match decode_record(&mut cursor) {
+                Ok(record) => records.push(record),
+                Err(io_error) => {
+                    warn!(
+                        "Ignoring the end of the write-ahead log {:?}: {:?}",
+                        path, io_error
+                    );
+                    break;
+                }
+            }
+        }
+        Ok(records)
+    }
+
+    /// Appends `operations` to the log, with a single write.
+    pub fn append(&self, operations: &[(Opstamp, WalOperation<'_>)]) -> io::Result<()> {
+        let mut buffer = Vec::new();
+        for (opstamp, operation) in operations {
+            encode_record(*opstamp, operation, &mut buffer)?;
+        }
+        let mut file = self.file.lock().unwrap();
+        file.write_all(&buffer)?;
+        if self.wal_sync == WalSync::PerBatch {
+            file.sync_data()?;
+        }
+        Ok(())
+    }
+
+    /// Empties the log, once its operations have been committed.
+    pub fn truncate(&self) -> io::Result<()> {
+        let file = self.file.lock().unwrap();
+        file.set_len(0)?;
+        file.sync_all()
+    }
+
+    /// Empties the log once the commit prepared at `checkpoint` succeeded, unless
+    /// operations were logged since then.
+    ///
+    /// These operations belong to the next commit, and must be kept. The log is then
+    /// emptied by the next commit instead: its operations that were committed are
+    /// skipped on replay anyway, as their opstamp is not greater than the opstamp
+    /// of the last commit.
+    pub fn truncate_committed(&self, checkpoint: u64) -> io::Result<()> {
+        let file = self.file.lock().unwrap();
+        if file.metadata()?.len() != checkpoint {
+            return Ok(());
+        }
+        file.set_len(0)?;
+        file.sync_all()
+    }
+
+    /// Returns the length of the log, to be passed to
+    /// [`truncate_committed`](#method.truncate_committed) once the commit succeeded.
+    pub fn checkpoint(&self) -> io::Result<u64> {
+        Ok(self.file.lock().unwrap().metadata()?.len())
+    }
+
+    /// Syncs the log and atomically moves it to `path`.
+    pub fn persist(self, path: &Path) -> io::Result<Wal> {
+        let file = self.file.into_inner().unwrap();
+        file.sync_all()?;
+        drop(file);
+        fs::rename(&self.path, path)?;
+        let file = OpenOptions::new().append(true).open(path)?;
+        Wal::with_file(path, file, self.wal_sync)
+    }
+}
+
+fn encode_record(
+    opstamp: Opstamp,
+    operation: &WalOperation<'_>,
+    buffer: &mut Vec<u8>,
+) -> io::Result<()> {
+    let mut payload = Vec::new();
+    match operation {
+        WalOperation::Add(document) => {
+            ADD_RECORD.serialize(&mut payload)?;
+            opstamp.serialize(&mut payload)?;
+            document.serialize(&mut payload)?;
+        }
+        WalOperation::Delete(term) => {
+            DELETE_RECORD.serialize(&mut payload)?;
+            opstamp.serialize(&mut payload)?;
+            payload.extend_from_slice(term.as_slice());
+        }
+        WalOperation::DeleteAll => {
+            DELETE_ALL_RECORD.serialize(&mut payload)?;
+            opstamp.serialize(&mut payload)?;
+        }
+    }
+    (payload.len() as u32).serialize(buffer)?;
+    buffer.extend_from_slice(&payload);
+    Ok(())
+}
+
+fn decode_record(cursor: &mut &[u8]) -> io::Result<(Opstamp, WalRecord)> {
+    let len = u32::deserialize(cursor)? as usize;
+    if cursor.len() < len {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "Incomplete record",
+        ));
+    }
+    let (mut payload, rest) = cursor.split_at(len);
+    *cursor = rest;
+    let kind = u8::deserialize(&mut payload)?;
+    let opstamp = u64::deserialize(&mut payload)?;
+    let record = match kind {
+        ADD_RECORD => WalRecord::Add(Document::deserialize(&mut payload)?),
+        DELETE_RECORD => {
+            let mut term_bytes = Vec::new();
+            payload.read_to_end(&mut term_bytes)?;
+            WalRecord::Delete(Term::wrap(term_bytes))
+        }
+        DELETE_ALL_RECORD => WalRecord::DeleteAll,
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Unknown record kind {}", kind),
+            ));
+        }
+    };
+    Ok((opstamp, record))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_record, encode_record, WalOperation, WalRecord};
+    use crate::schema::{Field, Term};
+
+    #[test]
+    fn test_wal_record_serialization() -> crate::Result<()> {
+        let field = Field::from_field_id(1);
+        let document = doc!(field => "hello");
+        let term = Term::from_field_text(field, "hello");
+        let mut buffer = Vec::new();
+        encode_record(3, &WalOperation::Add(&document), &mut buffer)?;
+        encode_record(4, &WalOperation::Delete(&term), &mut buffer)?;
+        encode_record(5, &WalOperation::DeleteAll, &mut buffer)?;
+        let mut cursor: &[u8] = &buffer[..];
+        match decode_record(&mut cursor)? {
+            (3, WalRecord::Add(decoded_document)) => {
+                assert_eq!(
+                    decoded_document.get_first(field).unwrap().text(),
+                    Some("hello")
+                );
+            }
+            _ => panic!("Expected an add record"),
+        }
+        match decode_record(&mut cursor)? {
+            (4, WalRecord::Delete(decoded_term)) => assert_eq!(decoded_term, term),
+            _ => panic!("Expected a delete record"),
+        }
+        assert!(matches!(
+            decode_record(&mut cursor)?,
+            (5, WalRecord::DeleteAll)
+        ));
+        assert!(cursor.is_empty());
+
+        let mut truncated: &[u8] = &buffer[..buffer.len() - 1];
+        decode_record(&mut truncated)?;
+        decode_record(&mut truncated)?;
+        assert!(decode_record(&mut truncated).is_err());
+        Ok(())
+    }
+}
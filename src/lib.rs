@@ -119,6 +119,7 @@ mod macros;
 
 pub use crate::error::TantivyError;
 pub use chrono;
+pub use tantivy_fst;
 
 /// Tantivy result.
 ///
@@ -148,23 +149,42 @@ pub mod schema;
 pub mod space_usage;
 pub mod store;
 pub mod termdict;
+pub mod termvector;
 
 mod reader;
 
-pub use self::reader::{IndexReader, IndexReaderBuilder, ReloadPolicy};
-mod snippet;
-pub use self::snippet::{Snippet, SnippetGenerator};
+#[cfg(feature = "otel")]
+mod otel;
+
+pub use self::reader::{
+    FieldStats, IndexReader, IndexReaderBuilder, LiveSearcher, QueryCache, ReloadPolicy,
+    SegmentInfo, SegmentOpening, VerifyMode,
+};
+pub mod snippet;
+pub use self::snippet::{
+    HighlightSection, HighlightSections, OffsetMode, Snippet, SnippetGenerator, TextSection,
+};
 
 mod docset;
 pub use self::docset::{DocSet, TERMINATED};
 pub use crate::common::HasLen;
 pub use crate::common::{f64_to_u64, i64_to_u64, u64_to_f64, u64_to_i64};
+#[cfg(feature = "async")]
+pub use crate::core::AsyncSearcher;
 pub use crate::core::{Executor, SegmentComponent};
-pub use crate::core::{Index, IndexMeta, Searcher, Segment, SegmentId, SegmentMeta};
+pub use crate::core::{
+    FederatedSearcher, HealthIssue, HealthSeverity, Index, IndexAlias, IndexAliasMeta,
+    IndexHealthReport, IndexMeta, IndexSettings, IndexStats, MigrateOptions, MultiDocAddress,
+    ParallelReranker, ScoreNormalization, Searcher, Segment, SegmentId, SegmentMeta, SuggestEntry,
+    TermStatistics,
+};
+pub use crate::core::{Inconsistency, VerificationReport};
 pub use crate::core::{InvertedIndexReader, SegmentReader};
+pub use crate::core::{MetricsCollector, NoopMetricsCollector};
 pub use crate::directory::Directory;
-pub use crate::indexer::operation::UserOperation;
+pub use crate::indexer::operation::{DeletePolicy, UserOperation};
 pub use crate::indexer::IndexWriter;
+pub use crate::indexer::WalSync;
 pub use crate::postings::Postings;
 pub use crate::reader::LeasedItem;
 pub use crate::schema::{Document, Term};
@@ -174,7 +194,7 @@ use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 
 /// Index format version.
-const INDEX_FORMAT_VERSION: u32 = 3;
+const INDEX_FORMAT_VERSION: u32 = 4;
 
 /// Structure version for the index.
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -225,10 +245,13 @@ pub fn version_string() -> &'static str {
 
 /// Defines tantivy's merging strategy
 pub mod merge_policy {
+    pub use crate::indexer::CancellationToken;
     pub use crate::indexer::DefaultMergePolicy;
     pub use crate::indexer::LogMergePolicy;
+    pub use crate::indexer::MergeCallback;
     pub use crate::indexer::MergePolicy;
     pub use crate::indexer::NoMergePolicy;
+    pub use crate::indexer::TieredMergePolicy;
 }
 
 /// A `u32` identifying a document within a segment.
@@ -828,6 +851,78 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_searcher_positions() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let title_field = schema_builder.add_text_field("title", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text_field => "a b c a d", title_field => "a"));
+        index_writer.add_document(doc!(text_field => "b"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let query = crate::query::QueryParser::for_index(&index, vec![text_field, title_field])
+            .parse_query("a d")?;
+        assert_eq!(
+            searcher.positions(&query, text_field, DocAddress(0, 0))?,
+            vec![0, 3, 4]
+        );
+        assert_eq!(
+            searcher.positions(&query, title_field, DocAddress(0, 0))?,
+            vec![0]
+        );
+        assert!(searcher
+            .positions(&query, text_field, DocAddress(0, 1))?
+            .is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_searcher_positions_batch() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text_field => "a b c a d"));
+        index_writer.add_document(doc!(text_field => "b"));
+        index_writer.add_document(doc!(text_field => "d d a"));
+        index_writer.commit()?;
+        index_writer.add_document(doc!(text_field => "c a"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let query =
+            crate::query::QueryParser::for_index(&index, vec![text_field]).parse_query("a d")?;
+        let other_segment_ord = if searcher.segment_reader(0).max_doc() == 1 {
+            0
+        } else {
+            1
+        };
+        let first_segment_ord = 1 - other_segment_ord;
+        let doc_addresses = vec![
+            DocAddress(first_segment_ord, 2),
+            DocAddress(other_segment_ord, 0),
+            DocAddress(first_segment_ord, 0),
+            DocAddress(first_segment_ord, 1),
+            DocAddress(first_segment_ord, 2),
+        ];
+        let positions = searcher.positions_batch(&query, text_field, &doc_addresses)?;
+        assert_eq!(
+            positions,
+            vec![vec![0, 1, 2], vec![1], vec![0, 3, 4], vec![], vec![0, 1, 2]]
+        );
+        for (&doc_address, doc_positions) in doc_addresses.iter().zip(&positions) {
+            assert_eq!(
+                &searcher.positions(&query, text_field, doc_address)?,
+                doc_positions
+            );
+        }
+        assert!(searcher
+            .positions_batch(&query, text_field, &[])?
+            .is_empty());
+        Ok(())
+    }
+
     #[test]
     fn test_doc_macro() {
         let mut schema_builder = Schema::builder();
@@ -0,0 +1,305 @@
+//! OpenTelemetry instrumentation of the search and indexing operations.
+//!
+//! The spans are started with the tracer `tantivy` of the global tracer provider
+//! of `opentelemetry`: they are exported by whatever exporter the application
+//! registered, and are no-ops if none was registered.
+//!
+//! The following spans are created:
+//! - `tantivy.parse_query` for `QueryParser::parse_query`.
+//! - `tantivy.search` for `Searcher::search`, with the debug representation of the query,
+//!   the number of segments, the number of documents scored and the elapsed time.
+//! - `tantivy.search_segment` for the search of each segment, as a child of `tantivy.search`.
+//! - `tantivy.add_document`, `tantivy.commit` and `tantivy.merge` for the `IndexWriter`.
+
+use crate::query::{Explanation, Query, Scorer, Weight};
+use crate::{DocId, Score, Searcher, SegmentReader};
+use opentelemetry::global::{self, BoxedSpan};
+use opentelemetry::trace::{Span, TraceContextExt, Tracer};
+use opentelemetry::{Context, KeyValue};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+const TRACER_NAME: &str = "tantivy";
+
+/// Starts a span, child of the current context.
+pub(crate) fn start_span(name: &'static str, attributes: &[(&'static str, i64)]) -> BoxedSpan {
+    let mut span = global::tracer(TRACER_NAME).start(name);
+    record(&mut span, attributes);
+    span
+}
+
+/// Sets some integer attributes on `span`.
+pub(crate) fn record(span: &mut BoxedSpan, attributes: &[(&'static str, i64)]) {
+    for &(key, value) in attributes {
+        span.set_attribute(KeyValue::new(key, value));
+    }
+}
+
+/// Starts the span of the parsing of `query`.
+pub(crate) fn start_parse_query_span(query: &str) -> BoxedSpan {
+    let mut span = global::tracer(TRACER_NAME).start("tantivy.parse_query");
+    span.set_attribute(KeyValue::new("tantivy.query", query.to_string()));
+    span
+}
+
+/// Span of a search.
+///
+/// The search of the segments is traced by wrapping the weight
+/// of the query with [`instrument_weight`](#method.instrument_weight).
+pub(crate) struct SearchSpan {
+    context: Context,
+    num_docs_scored: Arc<AtomicU64>,
+    start: Instant,
+}
+
+impl SearchSpan {
+    pub(crate) fn start(searcher: &Searcher, query: &dyn Query) -> SearchSpan {
+        let mut span = start_span(
+            "tantivy.search",
+            &[(
+                "tantivy.num_segments",
+                searcher.segment_readers().len() as i64,
+            )],
+        );
+        span.set_attribute(KeyValue::new("tantivy.query", format!("{:?}", query)));
+        SearchSpan {
+            context: Context::current_with_span(span),
+            num_docs_scored: Arc::new(AtomicU64::new(0)),
+            start: Instant::now(),
+        }
+    }
+
+    /// Wraps `weight`, so that the search of each segment gets its own span,
+    /// and the scored documents are counted.
+    pub(crate) fn instrument_weight(&self, weight: Box<dyn Weight>) -> Box<dyn Weight> {
+        Box::new(TracedWeight {
+            weight,
+            context: self.context.clone(),
+            num_docs_scored: self.num_docs_scored.clone(),
+        })
+    }
+
+    pub(crate) fn end(self) {
+        let span = self.context.span();
+        span.set_attribute(KeyValue::new(
+            "tantivy.num_docs_scored",
+            self.num_docs_scored.load(Ordering::Relaxed) as i64,
+        ));
+        span.set_attribute(KeyValue::new(
+            "tantivy.elapsed_micros",
+            self.start.elapsed().as_micros() as i64,
+        ));
+        span.end();
+    }
+}
+
+struct TracedWeight {
+    weight: Box<dyn Weight>,
+    context: Context,
+    num_docs_scored: Arc<AtomicU64>,
+}
+
+impl TracedWeight {
+    fn start_segment_span(&self, reader: &SegmentReader) -> BoxedSpan {
+        let mut span =
+            global::tracer(TRACER_NAME).start_with_context("tantivy.search_segment", &self.context);
+        span.set_attribute(KeyValue::new(
+            "tantivy.segment_id",
+            reader.segment_id().uuid_string(),
+        ));
+        record(&mut span, &[("tantivy.num_docs", reader.num_docs() as i64)]);
+        span
+    }
+
+    fn end_segment_span(&self, mut span: BoxedSpan, num_docs_scored: u64) {
+        self.num_docs_scored
+            .fetch_add(num_docs_scored, Ordering::Relaxed);
+        record(
+            &mut span,
+            &[("tantivy.num_docs_scored", num_docs_scored as i64)],
+        );
+        span.end();
+    }
+}
+
+impl Weight for TracedWeight {
+    fn scorer(&self, reader: &SegmentReader, boost: Score) -> crate::Result<Box<dyn Scorer>> {
+        self.weight.scorer(reader, boost)
+    }
+
+    fn explain(&self, reader: &SegmentReader, doc: DocId) -> crate::Result<Explanation> {
+        self.weight.explain(reader, doc)
+    }
+
+    fn count(&self, reader: &SegmentReader) -> crate::Result<u32> {
+        self.weight.count(reader)
+    }
+
+    fn for_each(
+        &self,
+        reader: &SegmentReader,
+        callback: &mut dyn FnMut(DocId, Score),
+    ) -> crate::Result<()> {
+        let span = self.start_segment_span(reader);
+        let mut num_docs_scored = 0u64;
+        let result = self.weight.for_each(reader, &mut |doc, score| {
+            num_docs_scored += 1;
+            callback(doc, score);
+        });
+        self.end_segment_span(span, num_docs_scored);
+        result
+    }
+
+    fn for_each_pruning(
+        &self,
+        threshold: Score,
+        reader: &SegmentReader,
+        callback: &mut dyn FnMut(DocId, Score) -> Score,
+    ) -> crate::Result<()> {
+        let span = self.start_segment_span(reader);
+        let mut num_docs_scored = 0u64;
+        let result = self
+            .weight
+            .for_each_pruning(threshold, reader, &mut |doc, score| {
+                num_docs_scored += 1;
+                callback(doc, score)
+            });
+        self.end_segment_span(span, num_docs_scored);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collector::{Count, TopDocs};
+    use crate::query::QueryParser;
+    use crate::schema::{Schema, TEXT};
+    use crate::Index;
+    use once_cell::sync::Lazy;
+    use opentelemetry::trace::{Span, SpanBuilder, SpanContext, Status, Tracer, TracerProvider};
+    use opentelemetry::{global, Context, InstrumentationScope, KeyValue, Value};
+    use std::borrow::Cow;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use std::time::SystemTime;
+
+    type RecordedSpan = (Cow<'static, str>, HashMap<String, Value>);
+
+    static RECORDED_SPANS: Lazy<Mutex<Vec<RecordedSpan>>> = Lazy::new(Default::default);
+
+    struct RecordingTracerProvider;
+
+    struct RecordingTracer;
+
+    struct RecordingSpan {
+        name: Cow<'static, str>,
+        attributes: HashMap<String, Value>,
+    }
+
+    impl TracerProvider for RecordingTracerProvider {
+        type Tracer = RecordingTracer;
+
+        fn tracer_with_scope(&self, _: InstrumentationScope) -> RecordingTracer {
+            RecordingTracer
+        }
+    }
+
+    impl Tracer for RecordingTracer {
+        type Span = RecordingSpan;
+
+        fn build_with_context(&self, builder: SpanBuilder, _: &Context) -> RecordingSpan {
+            RecordingSpan {
+                name: builder.name,
+                attributes: HashMap::new(),
+            }
+        }
+    }
+
+    impl Span for RecordingSpan {
+        fn add_event_with_timestamp<T>(&mut self, _: T, _: SystemTime, _: Vec<KeyValue>)
+        where
+            T: Into<Cow<'static, str>>,
+        {
+        }
+
+        fn span_context(&self) -> &SpanContext {
+            &SpanContext::NONE
+        }
+
+        fn is_recording(&self) -> bool {
+            true
+        }
+
+        fn set_attribute(&mut self, attribute: KeyValue) {
+            self.attributes
+                .insert(attribute.key.to_string(), attribute.value);
+        }
+
+        fn set_status(&mut self, _: Status) {}
+
+        fn update_name<T>(&mut self, _: T)
+        where
+            T: Into<Cow<'static, str>>,
+        {
+        }
+
+        fn add_link(&mut self, _: SpanContext, _: Vec<KeyValue>) {}
+
+        fn end_with_timestamp(&mut self, _: SystemTime) {}
+    }
+
+    impl Drop for RecordingSpan {
+        fn drop(&mut self) {
+            let span = (self.name.clone(), std::mem::take(&mut self.attributes));
+            RECORDED_SPANS.lock().unwrap().push(span);
+        }
+    }
+
+    #[test]
+    fn test_search_spans() -> crate::Result<()> {
+        global::set_tracer_provider(RecordingTracerProvider);
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title => "otelsofa"));
+        index_writer.add_document(doc!(title => "otelsofa otelchair"));
+        index_writer.commit()?;
+        index_writer.add_document(doc!(title => "otelchair"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let query = QueryParser::for_index(&index, vec![title]).parse_query("otelsofa")?;
+        let query_debug = Value::from(format!("{:?}", query));
+        let (count, top_docs) = searcher.search(&query, &(Count, TopDocs::with_limit(1)))?;
+        assert_eq!(count, 2);
+        assert_eq!(top_docs.len(), 1);
+
+        let recorded_spans = RECORDED_SPANS.lock().unwrap();
+        let search_spans: Vec<&HashMap<String, Value>> = recorded_spans
+            .iter()
+            .filter(|(name, attributes)| {
+                name == "tantivy.search" && attributes.get("tantivy.query") == Some(&query_debug)
+            })
+            .map(|(_, attributes)| attributes)
+            .collect();
+        assert_eq!(search_spans.len(), 1);
+        let search_span = search_spans[0];
+        assert_eq!(search_span["tantivy.num_segments"], Value::I64(2));
+        assert_eq!(search_span["tantivy.num_docs_scored"], Value::I64(2));
+        assert!(search_span.contains_key("tantivy.elapsed_micros"));
+        for segment_reader in searcher.segment_readers() {
+            let segment_id = Value::from(segment_reader.segment_id().uuid_string());
+            assert!(recorded_spans.iter().any(|(name, attributes)| {
+                name == "tantivy.search_segment"
+                    && attributes.get("tantivy.segment_id") == Some(&segment_id)
+            }));
+        }
+        let num_commit_spans = recorded_spans
+            .iter()
+            .filter(|(name, _)| name == "tantivy.commit")
+            .count();
+        assert!(num_commit_spans >= 2);
+        Ok(())
+    }
+}
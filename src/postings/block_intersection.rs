@@ -0,0 +1,237 @@
+//! This modules defines the logic used to intersect two sorted arrays of docs,
+//! typically the decoded blocks of two posting lists.
+//!
+//! Counting the documents of a conjunction of terms does not require to score them,
+//! so instead of seeking the posting lists one document at a time, whole blocks
+//! can be intersected at once.
+
+use crate::fastfield::DeleteBitSet;
+use crate::postings::SegmentPostings;
+use crate::{DocId, DocSet, TERMINATED};
+
+#[cfg(target_arch = "x86_64")]
+mod avx2 {
+    use std::arch::x86_64::__m256i as DataType;
+    use std::arch::x86_64::_mm256_castsi256_ps as cast_ps;
+    use std::arch::x86_64::_mm256_cmpeq_epi32 as op_eq;
+    use std::arch::x86_64::_mm256_loadu_si256 as op_load;
+    use std::arch::x86_64::_mm256_movemask_ps as op_movemask;
+    use std::arch::x86_64::_mm256_or_si256 as op_or;
+    use std::arch::x86_64::_mm256_permutevar8x32_epi32 as op_permute;
+    use std::arch::x86_64::_mm256_setr_epi32 as setr;
+
+    const LANES: usize = 8;
+
+    /// Intersects `left` and `right` 8 docs at a time.
+    ///
+    /// Each 8 docs of `left` are compared to all of the 8 docs of `right`
+    /// by rotating the latter 7 times. The resulting bitmap tells which docs of
+    /// `left` are in `right`. The array whose last doc is the smallest is then
+    /// moved by 8 docs (both of them if they are equal).
+    /// The remaining docs are intersected by the scalar implementation.
+    ///
+    /// # Safety
+    ///
+    /// The running CPU must support AVX2.
+    #[target_feature(enable = "avx2")]
+    pub(crate) unsafe fn intersect_avx2(left: &[u32], right: &[u32], output: &mut Vec<u32>) {
+        let rotate = setr(1, 2, 3, 4, 5, 6, 7, 0);
+        let (mut left_start, mut right_start) = (0, 0);
+        while left_start + LANES <= left.len() && right_start + LANES <= right.len() {
+            let left_docs = op_load(left.as_ptr().add(left_start) as *const DataType);
+            let mut right_docs = op_load(right.as_ptr().add(right_start) as *const DataType);
+            let mut matches = op_eq(left_docs, right_docs);
+            for _ in 1..LANES {
+                right_docs = op_permute(right_docs, rotate);
+                matches = op_or(matches, op_eq(left_docs, right_docs));
+            }
+            let mut bitmap = op_movemask(cast_ps(matches)) as u32;
+            while bitmap != 0 {
+                let lane = bitmap.trailing_zeros() as usize;
+                output.push(left[left_start + lane]);
+                bitmap &= bitmap - 1;
+            }
+            let left_last = left[left_start + LANES - 1];
+            let right_last = right[right_start + LANES - 1];
+            if left_last <= right_last {
+                left_start += LANES;
+            }
+            if right_last <= left_last {
+                right_start += LANES;
+            }
+        }
+        super::intersect_scalar(&left[left_start..], &right[right_start..], output);
+    }
+}
+
+/// Merges the two arrays, one doc at a time.
+fn intersect_scalar(left: &[u32], right: &[u32], output: &mut Vec<u32>) {
+    let (mut left_it, mut right_it) = (left.iter().peekable(), right.iter().peekable());
+    while let (Some(&&left_doc), Some(&&right_doc)) = (left_it.peek(), right_it.peek()) {
+        if left_doc < right_doc {
+            left_it.next();
+        } else if right_doc < left_doc {
+            right_it.next();
+        } else {
+            output.push(left_doc);
+            left_it.next();
+            right_it.next();
+        }
+    }
+}
+
+/// Tantivy may rely on SIMD instructions to intersect arrays of docs.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum BlockIntersector {
+    #[cfg(target_arch = "x86_64")]
+    AVX2,
+    Scalar,
+}
+
+impl BlockIntersector {
+    /// Appends the docs that are both in `left` and `right` to `output`.
+    ///
+    /// # Assumption
+    ///
+    /// Both arrays are strictly increasing.
+    pub(crate) fn intersect(self, left: &[u32], right: &[u32], output: &mut Vec<u32>) {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if self == BlockIntersector::AVX2 {
+                // Safe, as `BlockIntersector::AVX2` is only built if the CPU supports AVX2.
+                unsafe { avx2::intersect_avx2(left, right, output) };
+                return;
+            }
+        }
+        intersect_scalar(left, right, output);
+    }
+
+    /// Counts the documents that are both in `left` and `right`
+    /// from their current positions on, ignoring the deleted documents.
+    ///
+    /// The posting lists are intersected one pair of blocks at a time:
+    /// once the intersection of the remaining docs of their current blocks is computed,
+    /// both of the posting lists are moved past the smallest of their last docs.
+    pub(crate) fn count_intersection(
+        self,
+        left: &mut SegmentPostings,
+        right: &mut SegmentPostings,
+        delete_bitset: Option<&DeleteBitSet>,
+    ) -> u32 {
+        let mut count = 0u32;
+        let mut intersection: Vec<DocId> = Vec::new();
+        loop {
+            let (left_doc, right_doc) = (left.doc(), right.doc());
+            if left_doc == TERMINATED || right_doc == TERMINATED {
+                return count;
+            }
+            if left_doc < right_doc {
+                left.seek(right_doc);
+                continue;
+            }
+            if right_doc < left_doc {
+                right.seek(left_doc);
+                continue;
+            }
+            let (left_docs, right_docs) =
+                (left.block_remaining_docs(), right.block_remaining_docs());
+            intersection.clear();
+            self.intersect(left_docs, right_docs, &mut intersection);
+            count += match delete_bitset {
+                Some(delete_bitset) => intersection
+                    .iter()
+                    .filter(|&&doc| delete_bitset.is_alive(doc))
+                    .count() as u32,
+                None => intersection.len() as u32,
+            };
+            let last_doc = left_docs[left_docs.len() - 1].min(right_docs[right_docs.len() - 1]);
+            left.seek(last_doc + 1);
+            right.seek(last_doc + 1);
+        }
+    }
+}
+
+impl Default for BlockIntersector {
+    fn default() -> BlockIntersector {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx2") {
+                return BlockIntersector::AVX2;
+            }
+        }
+        BlockIntersector::Scalar
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BlockIntersector;
+    use crate::docset::DocSet;
+    use crate::postings::SegmentPostings;
+    use std::collections::BTreeSet;
+
+    fn block_intersectors() -> Vec<BlockIntersector> {
+        let mut block_intersectors = vec![BlockIntersector::Scalar];
+        if BlockIntersector::default() != BlockIntersector::Scalar {
+            block_intersectors.push(BlockIntersector::default());
+        }
+        block_intersectors
+    }
+
+    fn docs(len: u32, step: u32, offset: u32) -> Vec<u32> {
+        (0..len).map(|i| i * step + offset).collect()
+    }
+
+    fn intersect_trivial_but_slow(left: &[u32], right: &[u32]) -> Vec<u32> {
+        let right: BTreeSet<u32> = right.iter().cloned().collect();
+        left.iter()
+            .cloned()
+            .filter(|doc| right.contains(doc))
+            .collect()
+    }
+
+    #[test]
+    fn test_intersect() {
+        let arrays = vec![
+            vec![],
+            vec![3],
+            docs(7, 1, 0),
+            docs(8, 1, 0),
+            docs(100, 2, 0),
+            docs(100, 3, 1),
+            docs(130, 5, 2),
+            docs(33, 7, 0),
+        ];
+        for block_intersector in block_intersectors() {
+            for left in &arrays {
+                for right in &arrays {
+                    let mut output = Vec::new();
+                    block_intersector.intersect(left, right, &mut output);
+                    assert_eq!(output, intersect_trivial_but_slow(left, right));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_count_intersection() {
+        let left_docs = docs(1_000, 3, 0);
+        let right_docs = docs(700, 4, 8);
+        let expected = intersect_trivial_but_slow(&left_docs, &right_docs).len() as u32;
+        for block_intersector in block_intersectors() {
+            let mut left = SegmentPostings::create_from_docs(&left_docs);
+            let mut right = SegmentPostings::create_from_docs(&right_docs);
+            assert_eq!(
+                block_intersector.count_intersection(&mut left, &mut right, None),
+                expected
+            );
+            let mut left = SegmentPostings::create_from_docs(&left_docs);
+            let mut right = SegmentPostings::create_from_docs(&right_docs);
+            left.seek(1_000);
+            assert_eq!(
+                block_intersector.count_intersection(&mut left, &mut right, None),
+                intersect_trivial_but_slow(&left_docs[334..], &right_docs).len() as u32
+            );
+        }
+    }
+}
@@ -7,7 +7,9 @@ use crate::fieldnorm::FieldNormReader;
 use crate::postings::compression::{
     AlignedBuffer, BlockDecoder, VIntDecoder, COMPRESSION_BLOCK_SIZE,
 };
-use crate::postings::{BlockInfo, FreqReadingOption, SkipReader};
+use crate::postings::{
+    BlockInfo, FreqReadingOption, PostingFormat, RoaringBlockCursor, SkipReader,
+};
 use crate::query::BM25Weight;
 use crate::schema::IndexRecordOption;
 use crate::{DocId, Score, TERMINATED};
@@ -38,7 +40,10 @@ pub struct BlockSegmentPostings {
     doc_freq: u32,
 
     data: OwnedBytes,
-    pub(crate) skip_reader: SkipReader,
+    skip_reader: SkipReader,
+    // Only defined if the posting list is serialized as a `RoaringBitmap`,
+    // in which case the `skip_reader` is not used.
+    roaring_cursor: Option<RoaringBlockCursor>,
 }
 
 fn decode_bitpacked_block(
@@ -88,6 +93,7 @@ fn split_into_skips_and_postings(
 impl BlockSegmentPostings {
     pub(crate) fn open(
         doc_freq: u32,
+        posting_format: PostingFormat,
         data: FileSlice,
         record_option: IndexRecordOption,
         requested_option: IndexRecordOption,
@@ -98,11 +104,24 @@ impl BlockSegmentPostings {
             (_, _) => FreqReadingOption::ReadFreq,
         };
 
-        let (skip_data_opt, postings_data) =
-            split_into_skips_and_postings(doc_freq, data.read_bytes()?);
-        let skip_reader = match skip_data_opt {
-            Some(skip_data) => SkipReader::new(skip_data, doc_freq, record_option),
-            None => SkipReader::new(OwnedBytes::empty(), doc_freq, record_option),
+        let mut roaring_cursor = None;
+        let (skip_reader, postings_data) = match posting_format {
+            PostingFormat::Compressed => {
+                let (skip_data_opt, postings_data) =
+                    split_into_skips_and_postings(doc_freq, data.read_bytes()?);
+                let skip_reader = match skip_data_opt {
+                    Some(skip_data) => SkipReader::new(skip_data, doc_freq, record_option),
+                    None => SkipReader::new(OwnedBytes::empty(), doc_freq, record_option),
+                };
+                (skip_reader, postings_data)
+            }
+            PostingFormat::Roaring => {
+                roaring_cursor = Some(RoaringBlockCursor::open(data.read_bytes()?.as_slice())?);
+                (
+                    SkipReader::new(OwnedBytes::empty(), 0, record_option),
+                    OwnedBytes::empty(),
+                )
+            }
         };
 
         let mut block_segment_postings = BlockSegmentPostings {
@@ -114,6 +133,7 @@ impl BlockSegmentPostings {
             doc_freq,
             data: postings_data,
             skip_reader,
+            roaring_cursor,
         };
         block_segment_postings.load_block();
         Ok(block_segment_postings)
@@ -172,18 +192,35 @@ impl BlockSegmentPostings {
     // # Warning
     //
     // This does not reset the positions list.
-    pub(crate) fn reset(&mut self, doc_freq: u32, postings_data: OwnedBytes) {
-        let (skip_data_opt, postings_data) = split_into_skips_and_postings(doc_freq, postings_data);
-        self.data = postings_data;
+    pub(crate) fn reset(
+        &mut self,
+        doc_freq: u32,
+        posting_format: PostingFormat,
+        postings_data: OwnedBytes,
+    ) -> io::Result<()> {
         self.block_max_score_cache = None;
         self.loaded_offset = std::usize::MAX;
-        if let Some(skip_data) = skip_data_opt {
-            self.skip_reader.reset(skip_data, doc_freq);
-        } else {
-            self.skip_reader.reset(OwnedBytes::empty(), doc_freq);
+        match posting_format {
+            PostingFormat::Compressed => {
+                let (skip_data_opt, postings_data) =
+                    split_into_skips_and_postings(doc_freq, postings_data);
+                self.data = postings_data;
+                if let Some(skip_data) = skip_data_opt {
+                    self.skip_reader.reset(skip_data, doc_freq);
+                } else {
+                    self.skip_reader.reset(OwnedBytes::empty(), doc_freq);
+                }
+                self.roaring_cursor = None;
+            }
+            PostingFormat::Roaring => {
+                self.roaring_cursor = Some(RoaringBlockCursor::open(postings_data.as_slice())?);
+                self.data = OwnedBytes::empty();
+                self.skip_reader.reset(OwnedBytes::empty(), 0);
+            }
         }
         self.doc_freq = doc_freq;
         self.load_block();
+        Ok(())
     }
 
     /// Returns the overall number of documents in the block postings.
@@ -262,6 +299,17 @@ impl BlockSegmentPostings {
         self.skip_reader.position_offset()
     }
 
+    /// Returns the last document of the current block, or `TERMINATED`
+    /// if the current block is the last block of the posting list.
+    ///
+    /// It does not require the block to be loaded.
+    pub(crate) fn last_doc_in_block(&self) -> DocId {
+        if let Some(roaring_cursor) = self.roaring_cursor.as_ref() {
+            return roaring_cursor.last_doc_in_block();
+        }
+        self.skip_reader.last_doc_in_block()
+    }
+
     /// Dangerous API! This calls seek on the skip list,
     /// but does not `.load_block()` afterwards.
     ///
@@ -269,21 +317,47 @@ impl BlockSegmentPostings {
     /// If all docs are smaller than target, the block loaded may be empty,
     /// or be the last an incomplete VInt block.
     pub(crate) fn shallow_seek(&mut self, target_doc: DocId) {
-        if self.skip_reader.seek(target_doc) {
+        let moved = if let Some(roaring_cursor) = self.roaring_cursor.as_mut() {
+            roaring_cursor.seek(target_doc)
+        } else {
+            self.skip_reader.seek(target_doc)
+        };
+        if moved {
             self.block_max_score_cache = None;
         }
     }
 
+    /// Returns the ordinal, within the posting list, of the first document of the current block.
+    pub(crate) fn block_first_doc_ord(&self) -> u32 {
+        if let Some(roaring_cursor) = self.roaring_cursor.as_ref() {
+            return roaring_cursor.block_ord() as u32 * COMPRESSION_BLOCK_SIZE as u32;
+        }
+        self.doc_freq - self.skip_reader.remaining_docs()
+    }
+
+    // Identifies the current block: this is its byte offset for compressed posting lists,
+    // and its ordinal for roaring posting lists.
+    fn block_offset(&self) -> usize {
+        if let Some(roaring_cursor) = self.roaring_cursor.as_ref() {
+            return roaring_cursor.block_ord();
+        }
+        self.skip_reader.byte_offset()
+    }
+
     pub(crate) fn block_is_loaded(&self) -> bool {
-        self.loaded_offset == self.skip_reader.byte_offset()
+        self.loaded_offset == self.block_offset()
     }
 
     pub(crate) fn load_block(&mut self) {
-        let offset = self.skip_reader.byte_offset();
+        let offset = self.block_offset();
         if self.loaded_offset == offset {
             return;
         }
         self.loaded_offset = offset;
+        if let Some(roaring_cursor) = self.roaring_cursor.as_ref() {
+            roaring_cursor.load_block(&mut self.doc_decoder);
+            return;
+        }
         match self.skip_reader.block_info() {
             BlockInfo::BitPacked {
                 doc_num_bits,
@@ -330,7 +404,11 @@ impl BlockSegmentPostings {
     ///
     /// Returns false iff there was no remaining blocks.
     pub fn advance(&mut self) {
-        self.skip_reader.advance();
+        if let Some(roaring_cursor) = self.roaring_cursor.as_mut() {
+            roaring_cursor.advance();
+        } else {
+            self.skip_reader.advance();
+        }
         self.block_max_score_cache = None;
         self.load_block();
     }
@@ -346,6 +424,7 @@ impl BlockSegmentPostings {
             doc_freq: 0,
             data: OwnedBytes::empty(),
             skip_reader: SkipReader::new(OwnedBytes::empty(), 0, IndexRecordOption::Basic),
+            roaring_cursor: None,
         }
     }
 }
@@ -358,12 +437,13 @@ mod tests {
     use crate::docset::{DocSet, TERMINATED};
     use crate::postings::compression::COMPRESSION_BLOCK_SIZE;
     use crate::postings::postings::Postings;
-    use crate::postings::SegmentPostings;
+    use crate::postings::{PostingFormat, SegmentPostings};
     use crate::schema::IndexRecordOption;
     use crate::schema::Schema;
     use crate::schema::Term;
     use crate::schema::INDEXED;
-    use crate::DocId;
+    use crate::{DocId, Searcher};
+    use futures::executor::block_on;
 
     #[test]
     fn test_empty_segment_postings() {
@@ -527,4 +607,113 @@ mod tests {
         assert_eq!(block_segments.docs(), &[1, 3, 5]);
         Ok(())
     }
+
+    fn term_docs(searcher: &Searcher, term: &Term) -> crate::Result<(PostingFormat, Vec<DocId>)> {
+        let inverted_index = searcher.segment_reader(0).inverted_index(term.field())?;
+        let term_info = inverted_index.get_term_info(term)?.unwrap();
+        let mut postings =
+            inverted_index.read_postings_from_terminfo(&term_info, IndexRecordOption::Basic)?;
+        let mut docs = Vec::new();
+        while postings.doc() != TERMINATED {
+            docs.push(postings.doc());
+            postings.advance();
+        }
+        assert_eq!(docs.len() as u32, term_info.doc_freq);
+        Ok((term_info.posting_format, docs))
+    }
+
+    #[test]
+    fn test_roaring_segment_postings() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let int_field = schema_builder.add_u64_field("id", INDEXED);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        let mut index_writer = index.writer_for_tests()?;
+        for i in 0..2_000u64 {
+            let val = if i % 3 == 0 {
+                0u64
+            } else if i == 5 || i == 1_501 {
+                1u64
+            } else {
+                2u64
+            };
+            index_writer.add_document(doc!(int_field=>val));
+        }
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let dense_term = Term::from_field_u64(int_field, 0u64);
+        let sparse_term = Term::from_field_u64(int_field, 1u64);
+        let (posting_format, docs) = term_docs(&searcher, &dense_term)?;
+        assert_eq!(posting_format, PostingFormat::Roaring);
+        assert_eq!(docs, (0..2_000).step_by(3).collect::<Vec<DocId>>());
+        let (posting_format, docs) = term_docs(&searcher, &sparse_term)?;
+        assert_eq!(posting_format, PostingFormat::Compressed);
+        assert_eq!(docs, vec![5, 1_501]);
+
+        let inverted_index = searcher.segment_reader(0).inverted_index(int_field)?;
+        let mut postings = inverted_index
+            .read_postings(&dense_term, IndexRecordOption::Basic)?
+            .unwrap();
+        assert_eq!(postings.seek(1_000), 1_002);
+        assert_eq!(postings.seek(1_002), 1_002);
+        assert_eq!(postings.advance(), 1_005);
+        assert_eq!(postings.seek(1_998), 1_998);
+        assert_eq!(postings.seek(1_999), TERMINATED);
+
+        let term_info = inverted_index.get_term_info(&dense_term)?.unwrap();
+        let mut block_segments = inverted_index
+            .read_block_postings_from_terminfo(&term_info, IndexRecordOption::Basic)?;
+        assert_eq!(block_segments.docs().len(), COMPRESSION_BLOCK_SIZE);
+        let term_info = inverted_index.get_term_info(&sparse_term)?.unwrap();
+        inverted_index.reset_block_postings_from_terminfo(&term_info, &mut block_segments)?;
+        assert_eq!(block_segments.docs(), &[5, 1_501]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_segments_with_different_posting_formats() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let int_field = schema_builder.add_u64_field("id", INDEXED);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        let mut index_writer = index.writer_for_tests()?;
+        for i in 0..1_000u64 {
+            index_writer.add_document(doc!(int_field=>i % 2));
+        }
+        index_writer.commit()?;
+        for i in 0..1_000u64 {
+            index_writer.add_document(doc!(int_field=>if i == 10 { 0u64 } else { 1u64 }));
+        }
+        index_writer.commit()?;
+        let term = Term::from_field_u64(int_field, 0u64);
+        let reader = index.reader()?;
+        let searcher = reader.searcher();
+        let posting_formats: Vec<PostingFormat> = searcher
+            .segment_readers()
+            .iter()
+            .map(|segment_reader| {
+                let inverted_index = segment_reader.inverted_index(int_field).unwrap();
+                let term_info = inverted_index.get_term_info(&term).unwrap().unwrap();
+                term_info.posting_format
+            })
+            .collect();
+        assert!(posting_formats.contains(&PostingFormat::Roaring));
+        assert!(posting_formats.contains(&PostingFormat::Compressed));
+
+        let segment_ids = index.searchable_segment_ids()?;
+        block_on(index_writer.merge(&segment_ids))?;
+        reader.reload()?;
+        let searcher = reader.searcher();
+        assert_eq!(searcher.segment_readers().len(), 1);
+        let (posting_format, docs) = term_docs(&searcher, &term)?;
+        assert_eq!(posting_format, PostingFormat::Roaring);
+        // The segments may be stacked in any order.
+        let dense_first: Vec<DocId> = (0..1_000).step_by(2).chain(Some(1_010)).collect();
+        let sparse_first: Vec<DocId> = Some(10)
+            .into_iter()
+            .chain((1_000..2_000).step_by(2))
+            .collect();
+        assert!(docs == dense_first || docs == sparse_first);
+        Ok(())
+    }
 }
@@ -95,6 +95,19 @@ impl BlockDecoder {
     }
 
     #[inline]
+    /// Copies the (at most `COMPRESSION_BLOCK_SIZE`) given values to the output.
+    ///
+    /// The value given in `padding` will be used to fill the remaining values.
+    pub(crate) fn fill<I: Iterator<Item = u32>>(&mut self, vals: I, padding: u32) {
+        self.output.0.iter_mut().for_each(|el| *el = padding);
+        let mut output_len = 0;
+        for (el, val) in self.output.0.iter_mut().zip(vals) {
+            *el = val;
+            output_len += 1;
+        }
+        self.output_len = output_len;
+    }
+
     pub fn output_array(&self) -> &[u32] {
         &self.output.0[..self.output_len]
     }
@@ -2,27 +2,31 @@
 Postings module (also called inverted index)
 */
 
+mod block_intersection;
 mod block_search;
 mod block_segment_postings;
 pub(crate) mod compression;
 mod postings;
 mod postings_writer;
 mod recorder;
+mod roaring_postings;
 mod segment_postings;
 mod serializer;
 mod skip;
 mod stacker;
 mod term_info;
 
+pub(crate) use self::block_intersection::BlockIntersector;
 pub(crate) use self::block_search::BlockSearcher;
 pub use self::block_segment_postings::BlockSegmentPostings;
 pub use self::postings::Postings;
 pub(crate) use self::postings_writer::MultiFieldPostingsWriter;
+pub(crate) use self::roaring_postings::RoaringBlockCursor;
 pub use self::segment_postings::SegmentPostings;
 pub use self::serializer::{FieldSerializer, InvertedIndexSerializer};
 pub(crate) use self::skip::{BlockInfo, SkipReader};
 pub(crate) use self::stacker::compute_table_size;
-pub use self::term_info::TermInfo;
+pub use self::term_info::{PostingFormat, TermInfo};
 
 pub(crate) type UnorderedTermId = u64;
 
@@ -2,12 +2,13 @@ use super::stacker::{Addr, MemoryArena, TermHashMap};
 
 use crate::fieldnorm::FieldNormReaders;
 use crate::postings::recorder::{
-    BufferLender, NothingRecorder, Recorder, TFAndPositionRecorder, TermFrequencyRecorder,
+    BinaryTermFrequencyRecorder, BufferLender, NothingRecorder, Recorder, TFAndPositionRecorder,
+    TermFrequencyRecorder,
 };
 use crate::postings::UnorderedTermId;
 use crate::postings::{FieldSerializer, InvertedIndexSerializer};
-use crate::schema::IndexRecordOption;
 use crate::schema::{Field, FieldEntry, FieldType, Schema, Term};
+use crate::schema::{IndexRecordOption, TermFrequencyMode, TextFieldIndexing};
 use crate::termdict::TermOrdinal;
 use crate::tokenizer::TokenStream;
 use crate::tokenizer::{Token, MAX_TOKEN_LEN};
@@ -18,27 +19,50 @@ use std::io;
 use std::marker::PhantomData;
 use std::ops::DerefMut;
 
-fn posting_from_field_entry(field_entry: &FieldEntry) -> Box<dyn PostingsWriter> {
-    match *field_entry.field_type() {
-        FieldType::Str(ref text_options) => text_options
-            .get_indexing_options()
-            .map(|indexing_options| match indexing_options.index_option() {
-                IndexRecordOption::Basic => {
+fn posting_from_indexing_options(
+    indexing_options: Option<&TextFieldIndexing>,
+) -> Box<dyn PostingsWriter> {
+    indexing_options
+        .map(|indexing_options| {
+            match (
+                indexing_options.index_option(),
+                indexing_options.term_frequency_mode(),
+            ) {
+                (IndexRecordOption::Basic, _) => {
                     SpecializedPostingsWriter::<NothingRecorder>::new_boxed()
                 }
-                IndexRecordOption::WithFreqs => {
+                (IndexRecordOption::WithFreqs, TermFrequencyMode::Count) => {
                     SpecializedPostingsWriter::<TermFrequencyRecorder>::new_boxed()
                 }
-                IndexRecordOption::WithFreqsAndPositions => {
+                (IndexRecordOption::WithFreqs, TermFrequencyMode::Binary) => {
+                    SpecializedPostingsWriter::<BinaryTermFrequencyRecorder>::new_boxed()
+                }
+                // The positions require the actual term frequency.
+                (IndexRecordOption::WithFreqsAndPositions, _) => {
                     SpecializedPostingsWriter::<TFAndPositionRecorder>::new_boxed()
                 }
-            })
-            .unwrap_or_else(|| SpecializedPostingsWriter::<NothingRecorder>::new_boxed()),
+            }
+        })
+        .unwrap_or_else(|| SpecializedPostingsWriter::<NothingRecorder>::new_boxed())
+}
+
+fn posting_from_field_entry(field_entry: &FieldEntry) -> Box<dyn PostingsWriter> {
+    match *field_entry.field_type() {
+        FieldType::Str(ref text_options) => {
+            posting_from_indexing_options(text_options.get_indexing_options())
+        }
+        FieldType::JsonObject(ref json_object_options) => {
+            posting_from_indexing_options(json_object_options.get_indexing_options())
+        }
         FieldType::U64(_)
         | FieldType::I64(_)
         | FieldType::F64(_)
         | FieldType::Date(_)
         | FieldType::Bytes(_)
+        | FieldType::GeoPoint(_)
+        | FieldType::IpAddr(_)
+        | FieldType::DenseVector(_)
+        | FieldType::DocumentBoost(_)
         | FieldType::HierarchicalFacet => SpecializedPostingsWriter::<NothingRecorder>::new_boxed(),
     }
 }
@@ -116,9 +140,21 @@ impl MultiFieldPostingsWriter {
     }
 
     pub fn subscribe(&mut self, doc: DocId, term: &Term) -> UnorderedTermId {
+        self.subscribe_at_position(doc, 0u32, term)
+    }
+
+    /// Records that a document contains a term at a given position.
+    ///
+    /// The positions of the terms of a document must be given in increasing order.
+    pub fn subscribe_at_position(
+        &mut self,
+        doc: DocId,
+        position: u32,
+        term: &Term,
+    ) -> UnorderedTermId {
         let postings_writer =
             self.per_field_postings_writers[term.field().field_id() as usize].deref_mut();
-        postings_writer.subscribe(&mut self.term_index, doc, 0u32, term, &mut self.heap)
+        postings_writer.subscribe(&mut self.term_index, doc, position, term, &mut self.heap)
     }
 
     /// Serialize the inverted index.
@@ -158,6 +194,11 @@ impl MultiFieldPostingsWriter {
                 }
                 FieldType::U64(_) | FieldType::I64(_) | FieldType::F64(_) | FieldType::Date(_) => {}
                 FieldType::Bytes(_) => {}
+                FieldType::GeoPoint(_) => {}
+                FieldType::IpAddr(_) => {}
+                FieldType::DenseVector(_) => {}
+                FieldType::DocumentBoost(_) => {}
+                FieldType::JsonObject(_) => {}
             }
 
             let postings_writer =
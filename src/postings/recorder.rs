@@ -190,6 +190,58 @@ impl Recorder for TermFrequencyRecorder {
     }
 }
 
+/// Recorder encoding document ids, with a term frequency of 1.
+///
+/// Used for the fields with `TermFrequencyMode::Binary`.
+#[derive(Clone, Copy)]
+pub struct BinaryTermFrequencyRecorder {
+    stack: ExpUnrolledLinkedList,
+    current_doc: DocId,
+    term_doc_freq: u32,
+}
+
+impl Recorder for BinaryTermFrequencyRecorder {
+    fn new() -> Self {
+        BinaryTermFrequencyRecorder {
+            stack: ExpUnrolledLinkedList::new(),
+            current_doc: u32::MAX,
+            term_doc_freq: 0u32,
+        }
+    }
+
+    fn current_doc(&self) -> DocId {
+        self.current_doc
+    }
+
+    fn new_doc(&mut self, doc: DocId, heap: &mut MemoryArena) {
+        self.term_doc_freq += 1;
+        self.current_doc = doc;
+        let _ = write_u32_vint(doc, &mut self.stack.writer(heap));
+    }
+
+    fn record_position(&mut self, _position: u32, _heap: &mut MemoryArena) {}
+
+    fn close_doc(&mut self, _heap: &mut MemoryArena) {}
+
+    fn serialize(
+        &self,
+        buffer_lender: &mut BufferLender,
+        serializer: &mut FieldSerializer<'_>,
+        heap: &MemoryArena,
+    ) -> io::Result<()> {
+        let buffer = buffer_lender.lend_u8();
+        self.stack.read_to_end(heap, buffer);
+        for doc in VInt32Reader::new(&buffer[..]) {
+            serializer.write_doc(doc, 1u32, &[][..])?;
+        }
+        Ok(())
+    }
+
+    fn term_doc_freq(&self) -> Option<u32> {
+        Some(self.term_doc_freq)
+    }
+}
+
 /// Recorder encoding term frequencies as well as positions.
 #[derive(Clone, Copy)]
 pub struct TFAndPositionRecorder {
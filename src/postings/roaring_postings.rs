@@ -0,0 +1,158 @@
+//! The posting lists of the terms appearing in a large proportion
+//! of the documents of a segment are stored as a `RoaringBitmap`.
+//! (See `PostingFormat::Roaring`.)
+//!
+//! `RoaringBlockCursor` exposes such a bitmap as a sequence of
+//! blocks of `COMPRESSION_BLOCK_SIZE` docs, so that `BlockSegmentPostings`,
+//! and therefore `SegmentPostings`, can iterate over it exactly like they iterate
+//! over a compressed posting list.
+
+use crate::postings::compression::{BlockDecoder, COMPRESSION_BLOCK_SIZE};
+use crate::{DocId, TERMINATED};
+use roaring::RoaringBitmap;
+use std::io;
+use std::sync::Arc;
+
+const BLOCK_LEN: u32 = COMPRESSION_BLOCK_SIZE as u32;
+
+/// Cursor over the blocks of a posting list serialized as a `RoaringBitmap`.
+///
+/// Just like for compressed posting lists, all of the blocks contain
+/// `COMPRESSION_BLOCK_SIZE` docs, except for the last one that contains
+/// the remaining docs (possibly none).
+#[derive(Clone)]
+pub(crate) struct RoaringBlockCursor {
+    docs: Arc<RoaringBitmap>,
+    num_docs: u32,
+    block_ord: u32,
+    last_doc_in_block: DocId,
+}
+
+impl RoaringBlockCursor {
+    pub fn open(data: &[u8]) -> io::Result<RoaringBlockCursor> {
+        let docs = RoaringBitmap::deserialize_from(data)?;
+        let mut cursor = RoaringBlockCursor {
+            num_docs: docs.len() as u32,
+            docs: Arc::new(docs),
+            block_ord: 0,
+            last_doc_in_block: TERMINATED,
+        };
+        cursor.set_block(0);
+        Ok(cursor)
+    }
+
+    fn num_full_blocks(&self) -> u32 {
+        self.num_docs / BLOCK_LEN
+    }
+
+    fn set_block(&mut self, block_ord: u32) {
+        self.block_ord = block_ord;
+        self.last_doc_in_block = if block_ord < self.num_full_blocks() {
+            self.docs
+                .select(block_ord * BLOCK_LEN + BLOCK_LEN - 1)
+                .unwrap_or(TERMINATED)
+        } else {
+            TERMINATED
+        };
+    }
+
+    /// Returns the ordinal of the current block.
+    pub fn block_ord(&self) -> usize {
+        self.block_ord as usize
+    }
+
+    /// Returns the last doc of the current block, or `TERMINATED`
+    /// if the current block is the last one.
+    pub fn last_doc_in_block(&self) -> DocId {
+        self.last_doc_in_block
+    }
+
+    pub fn advance(&mut self) {
+        if self.block_ord <= self.num_full_blocks() {
+            self.set_block(self.block_ord + 1);
+        }
+    }
+
+    /// Advances the cursor to the block that may contain the target.
+    ///
+    /// If the target is larger than all documents, the cursor
+    /// then advances to the last block.
+    ///
+    /// Returns true iff the cursor moved.
+    pub fn seek(&mut self, target: DocId) -> bool {
+        if self.last_doc_in_block >= target {
+            return false;
+        }
+        // `target > last_doc_in_block >= 0`
+        let num_docs_before_target = self.docs.rank(target - 1) as u32;
+        let block_ord = (num_docs_before_target / BLOCK_LEN).min(self.num_full_blocks());
+        self.set_block(block_ord);
+        true
+    }
+
+    /// Decodes the docs of the current block into the `doc_decoder`.
+    pub fn load_block(&self, doc_decoder: &mut BlockDecoder) {
+        let first_doc_ord = self.block_ord * BLOCK_LEN;
+        match self.docs.select(first_doc_ord) {
+            Some(first_doc) => doc_decoder.fill(
+                self.docs.range(first_doc..).take(COMPRESSION_BLOCK_SIZE),
+                TERMINATED,
+            ),
+            None => doc_decoder.fill(std::iter::empty(), TERMINATED),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RoaringBlockCursor;
+    use crate::postings::compression::BlockDecoder;
+    use crate::TERMINATED;
+    use roaring::RoaringBitmap;
+
+    fn cursor(docs: &[u32]) -> RoaringBlockCursor {
+        let docs: RoaringBitmap = docs.iter().cloned().collect();
+        let mut buffer = Vec::new();
+        docs.serialize_into(&mut buffer).unwrap();
+        RoaringBlockCursor::open(&buffer[..]).unwrap()
+    }
+
+    #[test]
+    fn test_roaring_block_cursor() {
+        let docs: Vec<u32> = (0..300).map(|i| i * 3).collect();
+        let mut cursor = cursor(&docs);
+        let mut doc_decoder = BlockDecoder::default();
+        assert_eq!(cursor.last_doc_in_block(), docs[127]);
+        cursor.load_block(&mut doc_decoder);
+        assert_eq!(doc_decoder.output_array(), &docs[..128]);
+        cursor.advance();
+        assert_eq!(cursor.last_doc_in_block(), docs[255]);
+        cursor.load_block(&mut doc_decoder);
+        assert_eq!(doc_decoder.output_array(), &docs[128..256]);
+        cursor.advance();
+        assert_eq!(cursor.last_doc_in_block(), TERMINATED);
+        cursor.load_block(&mut doc_decoder);
+        assert_eq!(doc_decoder.output_array(), &docs[256..]);
+        assert_eq!(doc_decoder.output(docs.len() - 256), TERMINATED);
+        cursor.advance();
+        cursor.load_block(&mut doc_decoder);
+        assert!(doc_decoder.output_array().is_empty());
+    }
+
+    #[test]
+    fn test_roaring_block_cursor_seek() {
+        let docs: Vec<u32> = (0..256).map(|i| i * 2).collect();
+        let mut cursor = cursor(&docs);
+        assert!(!cursor.seek(docs[127]));
+        assert_eq!(cursor.block_ord(), 0);
+        assert!(cursor.seek(docs[127] + 1));
+        assert_eq!(cursor.block_ord(), 1);
+        assert_eq!(cursor.last_doc_in_block(), docs[255]);
+        assert!(cursor.seek(10_000));
+        assert_eq!(cursor.block_ord(), 2);
+        assert_eq!(cursor.last_doc_in_block(), TERMINATED);
+        let mut doc_decoder = BlockDecoder::default();
+        cursor.load_block(&mut doc_decoder);
+        assert!(doc_decoder.output_array().is_empty());
+    }
+}
@@ -19,6 +19,7 @@ pub struct SegmentPostings {
     cur: usize,
     position_reader: Option<PositionReader>,
     block_searcher: BlockSearcher,
+    docs_skipped: u64,
 }
 
 impl SegmentPostings {
@@ -29,6 +30,7 @@ impl SegmentPostings {
             cur: 0,
             position_reader: None,
             block_searcher: BlockSearcher::default(),
+            docs_skipped: 0u64,
         }
     }
 
@@ -51,6 +53,11 @@ impl SegmentPostings {
         }
     }
 
+    /// Returns the docs of the current block, starting from the current doc.
+    pub(crate) fn block_remaining_docs(&self) -> &[DocId] {
+        self.block_cursor.docs().get(self.cur..).unwrap_or(&[])
+    }
+
     /// Returns the overall number of documents in the block postings.
     /// It does not take in account whether documents are deleted or not.
     pub fn doc_freq(&self) -> u32 {
@@ -69,6 +76,7 @@ impl SegmentPostings {
     pub fn create_from_docs(docs: &[u32]) -> SegmentPostings {
         use crate::directory::FileSlice;
         use crate::postings::serializer::PostingsSerializer;
+        use crate::postings::PostingFormat;
         use crate::schema::IndexRecordOption;
         let mut buffer = Vec::new();
         {
@@ -84,6 +92,7 @@ impl SegmentPostings {
         }
         let block_segment_postings = BlockSegmentPostings::open(
             docs.len() as u32,
+            PostingFormat::Compressed,
             FileSlice::from(buffer),
             IndexRecordOption::Basic,
             IndexRecordOption::Basic,
@@ -101,6 +110,7 @@ impl SegmentPostings {
         use crate::directory::FileSlice;
         use crate::fieldnorm::FieldNormReader;
         use crate::postings::serializer::PostingsSerializer;
+        use crate::postings::PostingFormat;
         use crate::schema::IndexRecordOption;
         use crate::Score;
         let mut buffer: Vec<u8> = Vec::new();
@@ -132,6 +142,7 @@ impl SegmentPostings {
             .unwrap();
         let block_segment_postings = BlockSegmentPostings::open(
             doc_and_tfs.len() as u32,
+            PostingFormat::Compressed,
             FileSlice::from(buffer),
             IndexRecordOption::WithFreqs,
             IndexRecordOption::WithFreqs,
@@ -140,6 +151,12 @@ impl SegmentPostings {
         SegmentPostings::from_block_postings(block_segment_postings, None)
     }
 
+    // Returns the ordinal of the current document in the posting list,
+    // or the length of the posting list if it is entirely consumed.
+    fn doc_ord(&self) -> u32 {
+        (self.block_cursor.block_first_doc_ord() + self.cur as u32).min(self.len() as u32)
+    }
+
     /// Reads a Segment postings from an &[u8]
     ///
     /// * `len` - number of document in the posting lists.
@@ -155,6 +172,7 @@ impl SegmentPostings {
             cur: 0, // cursor within the block
             position_reader,
             block_searcher: BlockSearcher::default(),
+            docs_skipped: 0u64,
         }
     }
 }
@@ -179,6 +197,7 @@ impl DocSet for SegmentPostings {
         if self.doc() >= target {
             return self.doc();
         }
+        let doc_ord_before_seek = self.doc_ord();
 
         self.block_cursor.seek(target);
 
@@ -200,6 +219,11 @@ impl DocSet for SegmentPostings {
         let doc = output.0[self.cur];
         debug_assert!(doc >= target);
         debug_assert_eq!(doc, self.doc());
+        self.docs_skipped += u64::from(
+            self.doc_ord()
+                .saturating_sub(doc_ord_before_seek)
+                .saturating_sub(1),
+        );
         doc
     }
 
@@ -212,6 +236,10 @@ impl DocSet for SegmentPostings {
     fn size_hint(&self) -> u32 {
         self.len() as u32
     }
+
+    fn docs_skipped(&self) -> u64 {
+        self.docs_skipped
+    }
 }
 
 impl HasLen for SegmentPostings {
@@ -295,6 +323,22 @@ mod tests {
         assert_eq!(postings.term_freq(), 1);
     }
 
+    #[test]
+    fn test_docs_skipped() {
+        let docs: Vec<u32> = (0..1_000u32).map(|doc| doc * 2).collect();
+        let mut postings = SegmentPostings::create_from_docs(&docs[..]);
+        assert_eq!(postings.docs_skipped(), 0);
+        assert_eq!(postings.advance(), 2);
+        assert_eq!(postings.docs_skipped(), 0);
+        // The docs 4 to 998 are skipped.
+        assert_eq!(postings.seek(1_000), 1_000);
+        assert_eq!(postings.docs_skipped(), 498);
+        assert_eq!(postings.seek(1_001), 1_002);
+        assert_eq!(postings.docs_skipped(), 498);
+        assert_eq!(postings.seek(TERMINATED), TERMINATED);
+        assert_eq!(postings.docs_skipped(), 498 + 498);
+    }
+
     #[test]
     fn test_doc_freq() {
         let docs = SegmentPostings::create_from_docs(&[0, 2, 10]);
@@ -1,4 +1,4 @@
-use super::TermInfo;
+use super::{PostingFormat, TermInfo};
 use crate::common::{BinarySerializable, VInt};
 use crate::common::{CompositeWrite, CountingWriter};
 use crate::core::Segment;
@@ -9,9 +9,10 @@ use crate::postings::compression::{BlockEncoder, VIntEncoder, COMPRESSION_BLOCK_
 use crate::postings::skip::SkipSerializer;
 use crate::query::BM25Weight;
 use crate::schema::{Field, FieldEntry, FieldType};
-use crate::schema::{IndexRecordOption, Schema};
+use crate::schema::{IndexRecordOption, Schema, TextFieldIndexing};
 use crate::termdict::{TermDictionaryBuilder, TermOrdinal};
 use crate::{DocId, Score};
+use roaring::RoaringBitmap;
 use std::cmp::Ordering;
 use std::io::{self, Write};
 
@@ -151,6 +152,10 @@ impl<'a> FieldSerializer<'a> {
                     IndexRecordOption::Basic
                 }
             }
+            FieldType::JsonObject(ref json_object_options) => json_object_options
+                .get_indexing_options()
+                .map(TextFieldIndexing::index_option)
+                .unwrap_or(IndexRecordOption::Basic),
             _ => IndexRecordOption::Basic,
         };
         let term_dictionary_builder = TermDictionaryBuilder::create(term_dictionary_write)?;
@@ -188,6 +193,7 @@ impl<'a> FieldSerializer<'a> {
             postings_start_offset: self.postings_serializer.addr(),
             postings_stop_offset: 0u64,
             positions_idx,
+            posting_format: PostingFormat::Compressed,
         }
     }
 
@@ -240,7 +246,8 @@ impl<'a> FieldSerializer<'a> {
     /// using `VInt` encoding.
     pub fn close_term(&mut self) -> io::Result<()> {
         if self.term_open {
-            self.postings_serializer
+            self.current_term_info.posting_format = self
+                .postings_serializer
                 .close_term(self.current_term_info.doc_freq)?;
             self.current_term_info.postings_stop_offset = self.postings_serializer.addr();
             self.term_dictionary_builder
@@ -325,6 +332,10 @@ pub struct PostingsSerializer<W: Write> {
 
     bm25_weight: Option<BM25Weight>,
 
+    // Docs of the current term, in case its posting list ends up being
+    // serialized as a `RoaringBitmap`.
+    roaring_docs: RoaringBitmap,
+
     num_docs: u32, // Number of docs in the segment
     avg_fieldnorm: Score, // Average number of term in the field for that segment.
                    // this value is used to compute the block wand information.
@@ -355,12 +366,19 @@ impl<W: Write> PostingsSerializer<W> {
 
             fieldnorm_reader,
             bm25_weight: None,
+            roaring_docs: RoaringBitmap::new(),
 
             num_docs,
             avg_fieldnorm,
         }
     }
 
+    // Posting lists can only be serialized as a `RoaringBitmap` if they do not
+    // have any term frequencies, and if we know the number of docs in the segment.
+    fn may_use_roaring(&self) -> bool {
+        self.mode == IndexRecordOption::Basic && self.num_docs > 0
+    }
+
     pub fn new_term(&mut self, term_doc_freq: u32) {
         if self.mode.has_freq() && self.num_docs > 0 {
             let bm25_weight = BM25Weight::for_one_term(
@@ -426,6 +444,9 @@ impl<W: Write> PostingsSerializer<W> {
     }
 
     pub fn write_doc(&mut self, doc_id: DocId, term_freq: u32) {
+        if self.may_use_roaring() {
+            self.roaring_docs.push(doc_id);
+        }
         self.block.append_doc(doc_id, term_freq);
         if self.block.is_full() {
             self.write_block();
@@ -436,41 +457,51 @@ impl<W: Write> PostingsSerializer<W> {
         self.postings_write.flush()
     }
 
-    pub fn close_term(&mut self, doc_freq: u32) -> io::Result<()> {
-        if !self.block.is_empty() {
-            // we have doc ids waiting to be written
-            // this happens when the number of doc ids is
-            // not a perfect multiple of our block size.
-            //
-            // In that case, the remaining part is encoded
-            // using variable int encoding.
-            {
-                let block_encoded = self
-                    .block_encoder
-                    .compress_vint_sorted(&self.block.doc_ids(), self.last_doc_id_encoded);
-                self.postings_write.write_all(block_encoded)?;
-            }
-            // ... Idem for term frequencies
-            if self.mode.has_freq() {
-                let block_encoded = self
-                    .block_encoder
-                    .compress_vint_unsorted(self.block.term_freqs());
-                self.postings_write.write_all(block_encoded)?;
-            }
+    /// Serializes the posting list of the current term, and returns
+    /// the format in which it was serialized.
+    pub fn close_term(&mut self, doc_freq: u32) -> io::Result<PostingFormat> {
+        let posting_format = PostingFormat::for_term(self.mode, doc_freq, self.num_docs);
+        if posting_format == PostingFormat::Roaring {
+            // The blocks encoded so far are simply discarded.
             self.block.clear();
-        }
-        if doc_freq >= COMPRESSION_BLOCK_SIZE as u32 {
-            let skip_data = self.skip_write.data();
-            VInt(skip_data.len() as u64).serialize(&mut self.output_write)?;
-            self.output_write.write_all(skip_data)?;
-            self.output_write.write_all(&self.postings_write[..])?;
+            self.roaring_docs.serialize_into(&mut self.output_write)?;
         } else {
-            self.output_write.write_all(&self.postings_write[..])?;
+            if !self.block.is_empty() {
+                // we have doc ids waiting to be written
+                // this happens when the number of doc ids is
+                // not a perfect multiple of our block size.
+                //
+                // In that case, the remaining part is encoded
+                // using variable int encoding.
+                {
+                    let block_encoded = self
+                        .block_encoder
+                        .compress_vint_sorted(self.block.doc_ids(), self.last_doc_id_encoded);
+                    self.postings_write.write_all(block_encoded)?;
+                }
+                // ... Idem for term frequencies
+                if self.mode.has_freq() {
+                    let block_encoded = self
+                        .block_encoder
+                        .compress_vint_unsorted(self.block.term_freqs());
+                    self.postings_write.write_all(block_encoded)?;
+                }
+                self.block.clear();
+            }
+            if doc_freq >= COMPRESSION_BLOCK_SIZE as u32 {
+                let skip_data = self.skip_write.data();
+                VInt(skip_data.len() as u64).serialize(&mut self.output_write)?;
+                self.output_write.write_all(skip_data)?;
+                self.output_write.write_all(&self.postings_write[..])?;
+            } else {
+                self.output_write.write_all(&self.postings_write[..])?;
+            }
         }
         self.skip_write.clear();
         self.postings_write.clear();
+        self.roaring_docs.clear();
         self.bm25_weight = None;
-        Ok(())
+        Ok(posting_format)
     }
 
     fn addr(&self) -> u64 {
@@ -479,6 +510,7 @@ impl<W: Write> PostingsSerializer<W> {
 
     fn clear(&mut self) {
         self.block.clear();
+        self.roaring_docs.clear();
         self.last_doc_id_encoded = 0;
     }
 }
@@ -235,6 +235,11 @@ impl SkipReader {
         }
     }
 
+    /// Returns the number of documents in the current block and the following ones.
+    pub fn remaining_docs(&self) -> u32 {
+        self.remaining_docs
+    }
+
     pub fn advance(&mut self) {
         match self.block_info {
             BlockInfo::BitPacked {
@@ -1,6 +1,65 @@
 use crate::common::{BinarySerializable, FixedSize};
+use crate::schema::IndexRecordOption;
 use std::io;
 
+/// Format in which the posting list of a term is serialized.
+#[derive(Debug, Ord, PartialOrd, Eq, PartialEq, Clone, Copy)]
+pub enum PostingFormat {
+    /// Doc ids (and term frequencies) are delta-encoded, and bitpacked
+    /// by blocks of 128 documents.
+    Compressed,
+    /// Doc ids are stored in a `RoaringBitmap`.
+    ///
+    /// This format is only used for terms of fields indexed with
+    /// `IndexRecordOption::Basic`, that appear in more than 1% of
+    /// the documents of the segment.
+    Roaring,
+}
+
+impl Default for PostingFormat {
+    /// Posting lists are compressed by blocks by default.
+    fn default() -> PostingFormat {
+        PostingFormat::Compressed
+    }
+}
+
+impl PostingFormat {
+    /// Picks the format used to serialize the posting list of a term,
+    /// depending on the proportion of the documents of the segment containing it.
+    pub(crate) fn for_term(
+        record_option: IndexRecordOption,
+        doc_freq: u32,
+        num_docs: u32,
+    ) -> PostingFormat {
+        if record_option == IndexRecordOption::Basic
+            && num_docs > 0
+            && u64::from(doc_freq) * 100 > u64::from(num_docs)
+        {
+            PostingFormat::Roaring
+        } else {
+            PostingFormat::Compressed
+        }
+    }
+
+    pub(crate) fn code(self) -> u8 {
+        match self {
+            PostingFormat::Compressed => 0,
+            PostingFormat::Roaring => 1,
+        }
+    }
+
+    pub(crate) fn from_code(code: u8) -> io::Result<PostingFormat> {
+        match code {
+            0 => Ok(PostingFormat::Compressed),
+            1 => Ok(PostingFormat::Roaring),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Invalid posting format code {}", code),
+            )),
+        }
+    }
+}
+
 /// `TermInfo` wraps the metadata associated to a Term.
 /// It is segment-local.
 #[derive(Debug, Default, Ord, PartialOrd, Eq, PartialEq, Clone)]
@@ -14,6 +73,8 @@ pub struct TermInfo {
     pub postings_stop_offset: u64,
     /// Start offset of the first block within the position (`.pos`) file.
     pub positions_idx: u64,
+    /// Format of the posting list.
+    pub posting_format: PostingFormat,
 }
 
 impl TermInfo {
@@ -29,7 +90,8 @@ impl FixedSize for TermInfo {
     /// This is large, but in practise, `TermInfo` are encoded in blocks and
     /// only the first `TermInfo` of a block is serialized uncompressed.
     /// The subsequent `TermInfo` are delta encoded and bitpacked.
-    const SIZE_IN_BYTES: usize = 2 * u32::SIZE_IN_BYTES + 2 * u64::SIZE_IN_BYTES;
+    const SIZE_IN_BYTES: usize =
+        2 * u32::SIZE_IN_BYTES + 2 * u64::SIZE_IN_BYTES + u8::SIZE_IN_BYTES;
 }
 
 impl BinarySerializable for TermInfo {
@@ -38,6 +100,7 @@ impl BinarySerializable for TermInfo {
         self.postings_start_offset.serialize(writer)?;
         self.posting_num_bytes().serialize(writer)?;
         self.positions_idx.serialize(writer)?;
+        self.posting_format.code().serialize(writer)?;
         Ok(())
     }
 
@@ -47,11 +110,13 @@ impl BinarySerializable for TermInfo {
         let postings_num_bytes = u32::deserialize(reader)?;
         let postings_stop_offset = postings_start_offset + u64::from(postings_num_bytes);
         let positions_idx = u64::deserialize(reader)?;
+        let posting_format = PostingFormat::from_code(u8::deserialize(reader)?)?;
         Ok(TermInfo {
             doc_freq,
             postings_start_offset,
             postings_stop_offset,
             positions_idx,
+            posting_format,
         })
     }
 }
@@ -59,11 +124,32 @@ impl BinarySerializable for TermInfo {
 #[cfg(test)]
 mod tests {
 
-    use super::TermInfo;
+    use super::{PostingFormat, TermInfo};
     use crate::common::test::fixed_size_test;
+    use crate::schema::IndexRecordOption;
 
     #[test]
     fn test_fixed_size() {
         fixed_size_test::<TermInfo>();
     }
+
+    #[test]
+    fn test_posting_format_for_term() {
+        assert_eq!(
+            PostingFormat::for_term(IndexRecordOption::Basic, 11, 1_000),
+            PostingFormat::Roaring
+        );
+        assert_eq!(
+            PostingFormat::for_term(IndexRecordOption::Basic, 10, 1_000),
+            PostingFormat::Compressed
+        );
+        assert_eq!(
+            PostingFormat::for_term(IndexRecordOption::WithFreqs, 500, 1_000),
+            PostingFormat::Compressed
+        );
+        assert_eq!(
+            PostingFormat::for_term(IndexRecordOption::Basic, 500, 0),
+            PostingFormat::Compressed
+        );
+    }
 }
@@ -17,6 +17,10 @@ impl Query for AllQuery {
     fn weight(&self, _: &Searcher, _: bool) -> crate::Result<Box<dyn Weight>> {
         Ok(Box::new(AllWeight))
     }
+
+    fn cache_key(&self) -> Option<String> {
+        Some("AllQuery".to_string())
+    }
 }
 
 /// Weight associated to the `AllQuery` query.
@@ -31,6 +35,10 @@ impl Weight for AllWeight {
         Ok(Box::new(BoostScorer::new(all_scorer, boost)))
     }
 
+    fn count(&self, reader: &SegmentReader) -> crate::Result<u32> {
+        Ok(reader.num_docs())
+    }
+
     fn explain(&self, reader: &SegmentReader, doc: DocId) -> crate::Result<Explanation> {
         if doc >= reader.max_doc() {
             return Err(does_not_match(doc));
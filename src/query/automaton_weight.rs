@@ -15,6 +15,7 @@ use tantivy_fst::Automaton;
 pub struct AutomatonWeight<A> {
     field: Field,
     automaton: Arc<A>,
+    max_expansions: Option<usize>,
 }
 
 impl<A> AutomatonWeight<A>
@@ -27,9 +28,18 @@ where
         AutomatonWeight {
             field,
             automaton: automaton.into(),
+            max_expansions: None,
         }
     }
 
+    /// Limits the number of terms the automaton may match within a segment.
+    ///
+    /// If more terms are matched, creating the scorer returns an error.
+    pub fn with_max_expansions(mut self, max_expansions: usize) -> AutomatonWeight<A> {
+        self.max_expansions = Some(max_expansions);
+        self
+    }
+
     fn automaton_stream<'a>(
         &'a self,
         term_dict: &'a TermDictionary,
@@ -51,7 +61,17 @@ where
         let inverted_index = reader.inverted_index(self.field)?;
         let term_dict = inverted_index.terms();
         let mut term_stream = self.automaton_stream(term_dict)?;
+        let mut num_expansions = 0;
         while term_stream.advance() {
+            num_expansions += 1;
+            if let Some(max_expansions) = self.max_expansions {
+                if num_expansions > max_expansions {
+                    return Err(TantivyError::InvalidArgument(format!(
+                        "The query matches more than {} terms",
+                        max_expansions
+                    )));
+                }
+            }
             let term_info = term_stream.value();
             let mut block_segment_postings = inverted_index
                 .read_block_postings_from_terminfo(term_info, IndexRecordOption::Basic)?;
@@ -173,4 +193,20 @@ mod tests {
         assert_eq!(scorer.doc(), 0u32);
         assert_eq!(scorer.score(), 1.32);
     }
+
+    #[test]
+    fn test_automaton_weight_max_expansions() {
+        let index = create_index();
+        let field = index.schema().get_field("title").unwrap();
+        let reader = index.reader().unwrap();
+        let searcher = reader.searcher();
+        let automaton_weight = AutomatonWeight::new(field, PrefixedByA).with_max_expansions(2);
+        assert!(automaton_weight
+            .scorer(searcher.segment_reader(0u32), 1.0)
+            .is_ok());
+        let automaton_weight = AutomatonWeight::new(field, PrefixedByA).with_max_expansions(1);
+        assert!(automaton_weight
+            .scorer(searcher.segment_reader(0u32), 1.0)
+            .is_err());
+    }
 }
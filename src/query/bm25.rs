@@ -1,37 +1,161 @@
 use crate::fieldnorm::FieldNormReader;
 use crate::query::Explanation;
+use crate::schema::{Field, FieldType, TermFrequencyMode, TextFieldIndexing};
 use crate::Score;
 use crate::Searcher;
 use crate::Term;
 use serde::Deserialize;
 use serde::Serialize;
 
-const K1: Score = 1.2;
-const B: Score = 0.75;
+const DEFAULT_K1: Score = 1.2;
+const DEFAULT_B: Score = 0.75;
 
-fn idf(doc_freq: u64, doc_count: u64) -> Score {
+pub(crate) fn idf(doc_freq: u64, doc_count: u64) -> Score {
     assert!(doc_count >= doc_freq, "{} >= {}", doc_count, doc_freq);
     let x = ((doc_count - doc_freq) as Score + 0.5) / (doc_freq as Score + 0.5);
     (1.0 + x).ln()
 }
 
-fn cached_tf_component(fieldnorm: u32, average_fieldnorm: Score) -> Score {
-    K1 * (1.0 - B + B * fieldnorm as Score / average_fieldnorm)
+/// Similarity used to score the matches of the terms of a text field.
+///
+/// The similarity is set in the schema, via
+/// [`TextFieldIndexing::set_similarity`](../schema/struct.TextFieldIndexing.html#method.set_similarity).
+/// It only changes the inverse document frequency (idf) of the terms: the term
+/// frequency and length normalization components of the scores are the same.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Similarity {
+    /// BM25, with Lucene's idf: `ln(1 + (N - n + 0.5) / (n + 0.5))`.
+    #[serde(rename = "bm25")]
+    BM25,
+    /// BM25, with the smoothed idf of Okapi BM25: `ln((N - n + 0.5) / (n + 0.5)) + 1`,
+    /// clamped to 0.
+    ///
+    /// Compared to `BM25`, it gives a larger weight to the rare terms, and
+    /// does not score the terms appearing in almost all documents at all.
+    #[serde(rename = "bm25_smooth")]
+    BM25Smooth,
 }
 
-fn compute_tf_cache(average_fieldnorm: Score) -> [Score; 256] {
+impl Default for Similarity {
+    /// The default similarity is `BM25`.
+    fn default() -> Similarity {
+        Similarity::BM25
+    }
+}
+
+impl Similarity {
+    pub(crate) fn is_default(&self) -> bool {
+        *self == Similarity::default()
+    }
+
+    /// Returns the idf of a term appearing in `doc_freq` documents, out of `doc_count`.
+    ///
+    /// The idf is never negative.
+    pub fn idf(self, doc_freq: u64, doc_count: u64) -> Score {
+        match self {
+            Similarity::BM25 => idf(doc_freq, doc_count),
+            Similarity::BM25Smooth => smooth_idf(doc_freq, doc_count),
+        }
+    }
+
+    fn idf_formula(self) -> &'static str {
+        match self {
+            Similarity::BM25 => "idf, computed as log(1 + (N - n + 0.5) / (n + 0.5))",
+            Similarity::BM25Smooth => "idf, computed as max(0, log((N - n + 0.5) / (n + 0.5)) + 1)",
+        }
+    }
+
+    fn for_field(searcher: &Searcher, field: Field) -> Similarity {
+        text_indexing_options(searcher, field)
+            .map(|indexing_options| indexing_options.similarity())
+            .unwrap_or_default()
+    }
+}
+
+fn text_indexing_options(searcher: &Searcher, field: Field) -> Option<&TextFieldIndexing> {
+    match searcher.schema().get_field_entry(field).field_type() {
+        FieldType::Str(text_options) => text_options.get_indexing_options(),
+        _ => None,
+    }
+}
+
+fn term_frequency_mode_for_field(searcher: &Searcher, field: Field) -> TermFrequencyMode {
+    text_indexing_options(searcher, field)
+        .map(|indexing_options| indexing_options.term_frequency_mode())
+        .unwrap_or_default()
+}
+
+fn smooth_idf(doc_freq: u64, doc_count: u64) -> Score {
+    assert!(doc_count >= doc_freq, "{} >= {}", doc_count, doc_freq);
+    let x = ((doc_count - doc_freq) as Score + 0.5) / (doc_freq as Score + 0.5);
+    (x.ln() + 1.0).max(0.0)
+}
+
+fn cached_tf_component(fieldnorm: u32, average_fieldnorm: Score, params: BM25Params) -> Score {
+    params.k1 * (1.0 - params.b + params.b * fieldnorm as Score / average_fieldnorm)
+}
+
+fn compute_tf_cache(average_fieldnorm: Score, params: BM25Params) -> [Score; 256] {
     let mut cache: [Score; 256] = [0.0; 256];
     for (fieldnorm_id, cache_mut) in cache.iter_mut().enumerate() {
         let fieldnorm = FieldNormReader::id_to_fieldnorm(fieldnorm_id as u8);
-        *cache_mut = cached_tf_component(fieldnorm, average_fieldnorm);
+        *cache_mut = cached_tf_component(fieldnorm, average_fieldnorm, params);
     }
     cache
 }
 
-#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+/// Same as `compute_tf_cache`, but the cache contains the tf factor of a term
+/// frequency of 1, as there is no term frequency to saturate.
+fn compute_binary_tf_cache(average_fieldnorm: Score, params: BM25Params) -> [Score; 256] {
+    let mut cache = compute_tf_cache(average_fieldnorm, params);
+    for cache_mut in cache.iter_mut() {
+        *cache_mut = 1.0 / (1.0 + *cache_mut);
+    }
+    cache
+}
+
+/// Free parameters of the BM25 similarity.
+///
+/// The defaults (`k1=1.2`, `b=0.75`) are the ones used by Lucene, and work
+/// well for most corpora. Short fields (titles, product names...) often
+/// benefit from a lower `b`.
+///
+/// These parameters are only used at search time, and can be set on the searchers
+/// via [`IndexReaderBuilder::bm25_params`](../struct.IndexReaderBuilder.html#method.bm25_params).
+///
+/// Note that the block-max information used to skip blocks
+/// of documents in top-K queries is computed at indexing time
+/// using the default parameters: with other parameters, top-K
+/// queries score all of the matching documents.
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
 pub struct BM25Params {
-    pub idf: Score,
-    pub avg_fieldnorm: Score,
+    /// Term frequency saturation parameter.
+    pub k1: Score,
+    /// Field length normalization parameter,
+    /// between 0 (no normalization) and 1 (full normalization).
+    pub b: Score,
+}
+
+impl Default for BM25Params {
+    fn default() -> BM25Params {
+        BM25Params {
+            k1: DEFAULT_K1,
+            b: DEFAULT_B,
+        }
+    }
+}
+
+/// Returns the total number of documents and the average fieldnorm of `field`.
+pub(crate) fn field_statistics(searcher: &Searcher, field: Field) -> crate::Result<(u64, Score)> {
+    let mut total_num_tokens = 0u64;
+    let mut total_num_docs = 0u64;
+    for segment_reader in searcher.segment_readers() {
+        let inverted_index = segment_reader.inverted_index(field)?;
+        total_num_tokens += inverted_index.total_num_tokens();
+        total_num_docs += u64::from(segment_reader.max_doc());
+    }
+    let average_fieldnorm = total_num_tokens as Score / total_num_docs as Score;
+    Ok((total_num_docs, average_fieldnorm))
 }
 
 #[derive(Clone)]
@@ -40,6 +164,8 @@ pub struct BM25Weight {
     weight: Score,
     cache: [Score; 256],
     average_fieldnorm: Score,
+    params: BM25Params,
+    term_frequency_mode: TermFrequencyMode,
 }
 
 impl BM25Weight {
@@ -49,9 +175,30 @@ impl BM25Weight {
             weight: self.weight * boost,
             cache: self.cache,
             average_fieldnorm: self.average_fieldnorm,
+            params: self.params,
+            term_frequency_mode: self.term_frequency_mode,
         }
     }
 
+    /// Returns the same weight, scoring the term frequencies according to
+    /// `term_frequency_mode`.
+    ///
+    /// With `TermFrequencyMode::Binary`, the term frequency is always 1: the tf
+    /// factor only depends on the fieldnorm, and is read from the cache.
+    pub(crate) fn with_term_frequency_mode(
+        mut self,
+        term_frequency_mode: TermFrequencyMode,
+    ) -> BM25Weight {
+        self.cache = match term_frequency_mode {
+            TermFrequencyMode::Count => compute_tf_cache(self.average_fieldnorm, self.params),
+            TermFrequencyMode::Binary => {
+                compute_binary_tf_cache(self.average_fieldnorm, self.params)
+            }
+        };
+        self.term_frequency_mode = term_frequency_mode;
+        self
+    }
+
     pub fn for_terms(searcher: &Searcher, terms: &[Term]) -> crate::Result<BM25Weight> {
         assert!(!terms.is_empty(), "BM25 requires at least one term");
         let field = terms[0].field();
@@ -63,31 +210,75 @@ impl BM25Weight {
             );
         }
 
-        let mut total_num_tokens = 0u64;
-        let mut total_num_docs = 0u64;
-        for segment_reader in searcher.segment_readers() {
-            let inverted_index = segment_reader.inverted_index(field)?;
-            total_num_tokens += inverted_index.total_num_tokens();
-            total_num_docs += u64::from(segment_reader.max_doc());
-        }
-        let average_fieldnorm = total_num_tokens as Score / total_num_docs as Score;
+        let (total_num_docs, average_fieldnorm) = field_statistics(searcher, field)?;
+        let params = searcher.bm25_params();
+        let similarity = Similarity::for_field(searcher, field);
+        let term_frequency_mode = term_frequency_mode_for_field(searcher, field);
 
         if terms.len() == 1 {
             let term_doc_freq = searcher.doc_freq(&terms[0])?;
-            Ok(BM25Weight::for_one_term(
+            Ok(BM25Weight::for_one_term_with_similarity(
                 term_doc_freq,
                 total_num_docs,
                 average_fieldnorm,
-            ))
+                params,
+                similarity,
+            )
+            .with_term_frequency_mode(term_frequency_mode))
         } else {
             let mut idf_sum: Score = 0.0;
             for term in terms {
                 let term_doc_freq = searcher.doc_freq(term)?;
-                idf_sum += idf(term_doc_freq, total_num_docs);
+                idf_sum += similarity.idf(term_doc_freq, total_num_docs);
             }
             let idf_explain = Explanation::new("idf", idf_sum);
-            Ok(BM25Weight::new(idf_explain, average_fieldnorm))
+            Ok(
+                BM25Weight::new_with_params(idf_explain, average_fieldnorm, params)
+                    .with_term_frequency_mode(term_frequency_mode),
+            )
+        }
+    }
+
+    /// Returns the weight of a phrase whose terms may belong to several fields.
+    ///
+    /// The idf of each term is computed with the similarity of its own field, while
+    /// the occurrences of the phrase are normalized with the fieldnorms of `scoring_field`.
+    pub(crate) fn for_terms_of_fields(
+        searcher: &Searcher,
+        terms: &[Term],
+        scoring_field: Field,
+    ) -> crate::Result<BM25Weight> {
+        let (total_num_docs, average_fieldnorm) = field_statistics(searcher, scoring_field)?;
+        let mut idf_sum: Score = 0.0;
+        for term in terms {
+            let term_doc_freq = searcher.doc_freq(term)?;
+            idf_sum +=
+                Similarity::for_field(searcher, term.field()).idf(term_doc_freq, total_num_docs);
         }
+        let idf_explain = Explanation::new("idf", idf_sum);
+        Ok(
+            BM25Weight::new_with_params(idf_explain, average_fieldnorm, searcher.bm25_params())
+                .with_term_frequency_mode(term_frequency_mode_for_field(searcher, scoring_field)),
+        )
+    }
+
+    /// Returns the weight of `term`, computed as if it appeared in `term_doc_freq` documents.
+    ///
+    /// This makes it possible to blend the statistics of the terms of several fields.
+    pub(crate) fn for_term_with_doc_freq(
+        searcher: &Searcher,
+        term: &Term,
+        term_doc_freq: u64,
+    ) -> crate::Result<BM25Weight> {
+        let (total_num_docs, average_fieldnorm) = field_statistics(searcher, term.field())?;
+        Ok(BM25Weight::for_one_term_with_similarity(
+            term_doc_freq,
+            total_num_docs,
+            average_fieldnorm,
+            searcher.bm25_params(),
+            Similarity::for_field(searcher, term.field()),
+        )
+        .with_term_frequency_mode(term_frequency_mode_for_field(searcher, term.field())))
     }
 
     pub fn for_one_term(
@@ -95,27 +286,72 @@ impl BM25Weight {
         total_num_docs: u64,
         avg_fieldnorm: Score,
     ) -> BM25Weight {
-        let idf = idf(term_doc_freq, total_num_docs);
-        let mut idf_explain =
-            Explanation::new("idf, computed as log(1 + (N - n + 0.5) / (n + 0.5))", idf);
+        BM25Weight::for_one_term_with_params(
+            term_doc_freq,
+            total_num_docs,
+            avg_fieldnorm,
+            BM25Params::default(),
+        )
+    }
+
+    pub fn for_one_term_with_params(
+        term_doc_freq: u64,
+        total_num_docs: u64,
+        avg_fieldnorm: Score,
+        params: BM25Params,
+    ) -> BM25Weight {
+        BM25Weight::for_one_term_with_similarity(
+            term_doc_freq,
+            total_num_docs,
+            avg_fieldnorm,
+            params,
+            Similarity::default(),
+        )
+    }
+
+    fn for_one_term_with_similarity(
+        term_doc_freq: u64,
+        total_num_docs: u64,
+        avg_fieldnorm: Score,
+        params: BM25Params,
+        similarity: Similarity,
+    ) -> BM25Weight {
+        let idf = similarity.idf(term_doc_freq, total_num_docs);
+        let mut idf_explain = Explanation::new(similarity.idf_formula(), idf);
         idf_explain.add_const(
             "n, number of docs containing this term",
             term_doc_freq as Score,
         );
         idf_explain.add_const("N, total number of docs", total_num_docs as Score);
-        BM25Weight::new(idf_explain, avg_fieldnorm)
+        BM25Weight::new_with_params(idf_explain, avg_fieldnorm, params)
     }
 
     pub(crate) fn new(idf_explain: Explanation, average_fieldnorm: Score) -> BM25Weight {
-        let weight = idf_explain.value() * (1.0 + K1);
+        BM25Weight::new_with_params(idf_explain, average_fieldnorm, BM25Params::default())
+    }
+
+    pub(crate) fn new_with_params(
+        idf_explain: Explanation,
+        average_fieldnorm: Score,
+        params: BM25Params,
+    ) -> BM25Weight {
+        let weight = idf_explain.value() * (1.0 + params.k1);
         BM25Weight {
             idf_explain,
             weight,
-            cache: compute_tf_cache(average_fieldnorm),
+            cache: compute_tf_cache(average_fieldnorm, params),
             average_fieldnorm,
+            params,
+            term_frequency_mode: TermFrequencyMode::Count,
         }
     }
 
+    /// Returns true iff the weight uses the BM25 parameters the
+    /// block-max information of the posting lists was computed with.
+    pub(crate) fn has_default_params(&self) -> bool {
+        self.params == BM25Params::default()
+    }
+
     #[inline(always)]
     pub fn score(&self, fieldnorm_id: u8, term_freq: u32) -> Score {
         self.weight * self.tf_factor(fieldnorm_id, term_freq)
@@ -127,6 +363,9 @@ impl BM25Weight {
 
     #[inline(always)]
     pub(crate) fn tf_factor(&self, fieldnorm_id: u8, term_freq: u32) -> Score {
+        if self.term_frequency_mode == TermFrequencyMode::Binary {
+            return self.cache[fieldnorm_id as usize];
+        }
         let term_freq = term_freq as Score;
         let norm = self.cache[fieldnorm_id as usize];
         term_freq / (term_freq + norm)
@@ -136,19 +375,27 @@ impl BM25Weight {
         // The explain format is directly copied from Lucene's.
         // (So, Kudos to Lucene)
         let score = self.score(fieldnorm_id, term_freq);
+        let right_factor = self.tf_factor(fieldnorm_id, term_freq);
 
-        let norm = self.cache[fieldnorm_id as usize];
-        let term_freq = term_freq as Score;
-        let right_factor = term_freq / (term_freq + norm);
-
-        let mut tf_explanation = Explanation::new(
-            "freq / (freq + k1 * (1 - b + b * dl / avgdl))",
-            right_factor,
-        );
-
-        tf_explanation.add_const("freq, occurrences of term within document", term_freq);
-        tf_explanation.add_const("k1, term saturation parameter", K1);
-        tf_explanation.add_const("b, length normalization parameter", B);
+        let mut tf_explanation = match self.term_frequency_mode {
+            TermFrequencyMode::Count => {
+                let mut tf_explanation = Explanation::new(
+                    "freq / (freq + k1 * (1 - b + b * dl / avgdl))",
+                    right_factor,
+                );
+                tf_explanation.add_const(
+                    "freq, occurrences of term within document",
+                    term_freq as Score,
+                );
+                tf_explanation
+            }
+            TermFrequencyMode::Binary => Explanation::new(
+                "1 / (1 + k1 * (1 - b + b * dl / avgdl)), with binary term frequencies",
+                right_factor,
+            ),
+        };
+        tf_explanation.add_const("k1, term saturation parameter", self.params.k1);
+        tf_explanation.add_const("b, length normalization parameter", self.params.b);
         tf_explanation.add_const(
             "dl, length of field",
             FieldNormReader::id_to_fieldnorm(fieldnorm_id) as Score,
@@ -156,7 +403,7 @@ impl BM25Weight {
         tf_explanation.add_const("avgdl, average length of field", self.average_fieldnorm);
 
         let mut explanation = Explanation::new("TermQuery, product of...", score);
-        explanation.add_detail(Explanation::new("(K1+1)", K1 + 1.0));
+        explanation.add_detail(Explanation::new("(K1+1)", self.params.k1 + 1.0));
         explanation.add_detail(self.idf_explain.clone());
         explanation.add_detail(tf_explanation);
         explanation
@@ -166,12 +413,203 @@ impl BM25Weight {
 #[cfg(test)]
 mod tests {
 
-    use super::idf;
-    use crate::{assert_nearly_equals, Score};
+    use super::{idf, smooth_idf, BM25Params, Similarity};
+    use crate::collector::TopDocs;
+    use crate::postings::Postings;
+    use crate::query::TermQuery;
+    use crate::query::{Query, QueryParser};
+    use crate::schema::{
+        IndexRecordOption, Schema, TermFrequencyMode, TextFieldIndexing, TextOptions, TEXT,
+    };
+    use crate::{assert_nearly_equals, DocAddress, DocSet, Index, Score, Term};
 
     #[test]
     fn test_idf() {
         let score: Score = 2.0;
         assert_nearly_equals!(idf(1, 2), score.ln());
     }
+
+    #[test]
+    fn test_smooth_idf() {
+        assert_nearly_equals!(smooth_idf(1, 2), 1.0);
+        assert!(smooth_idf(1, 1_000) > idf(1, 1_000));
+        for doc_count in 0..100 {
+            for doc_freq in 0..=doc_count {
+                assert!(smooth_idf(doc_freq, doc_count) >= 0.0);
+            }
+        }
+        assert_eq!(smooth_idf(100, 100), 0.0);
+    }
+
+    #[test]
+    fn test_similarity() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let smooth_text_options = TextOptions::default().set_indexing_options(
+            TextFieldIndexing::default()
+                .set_index_option(IndexRecordOption::WithFreqs)
+                .set_similarity(Similarity::BM25Smooth),
+        );
+        let smooth_text = schema_builder.add_text_field("smooth_text", smooth_text_options);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        for _ in 0..3 {
+            index_writer.add_document(doc!(text=>"a b", smooth_text=>"a b"));
+        }
+        index_writer.add_document(doc!(text=>"a", smooth_text=>"a"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let score = |field, word: &str| -> crate::Result<Score> {
+            let query = TermQuery::new(
+                Term::from_field_text(field, word),
+                IndexRecordOption::WithFreqs,
+            );
+            let top_docs = searcher.search(&query, &TopDocs::with_limit(1))?;
+            Ok(top_docs[0].0)
+        };
+        assert!(score(text, "a")? > 0.0);
+        assert_eq!(score(smooth_text, "a")?, 0.0);
+        assert!(score(smooth_text, "b")? > 0.0);
+        assert!(score(smooth_text, "b")? < score(text, "b")?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_term_frequency_mode() -> crate::Result<()> {
+        let binary_text_options = |index_option| {
+            TextOptions::default().set_indexing_options(
+                TextFieldIndexing::default()
+                    .set_index_option(index_option)
+                    .set_term_frequency_mode(TermFrequencyMode::Binary),
+            )
+        };
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let binary_text = schema_builder.add_text_field(
+            "binary_text",
+            binary_text_options(IndexRecordOption::WithFreqs),
+        );
+        let binary_text_with_positions = schema_builder.add_text_field(
+            "binary_text_with_positions",
+            binary_text_options(IndexRecordOption::WithFreqsAndPositions),
+        );
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        for &value in &["a a a b", "a b c d"] {
+            index_writer.add_document(doc!(
+                text => value,
+                binary_text => value,
+                binary_text_with_positions => value,
+            ));
+        }
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let segment_reader = searcher.segment_reader(0);
+        let mut postings = segment_reader
+            .inverted_index(binary_text)?
+            .read_postings(
+                &Term::from_field_text(binary_text, "a"),
+                IndexRecordOption::WithFreqs,
+            )?
+            .unwrap();
+        assert_eq!(postings.term_freq(), 1);
+        postings.advance();
+        assert_eq!(postings.term_freq(), 1);
+
+        let scores = |field| -> crate::Result<Vec<Score>> {
+            let query = TermQuery::new(
+                Term::from_field_text(field, "a"),
+                IndexRecordOption::WithFreqs,
+            );
+            let mut top_docs = searcher.search(&query, &TopDocs::with_limit(2))?;
+            top_docs.sort_by_key(|(_, doc_address)| *doc_address);
+            Ok(top_docs.into_iter().map(|(score, _)| score).collect())
+        };
+        let count_scores = scores(text)?;
+        assert!(count_scores[0] > count_scores[1]);
+        let binary_scores = scores(binary_text)?;
+        assert_nearly_equals!(binary_scores[0], binary_scores[1]);
+        assert_nearly_equals!(binary_scores[1], count_scores[1]);
+        let binary_with_positions_scores = scores(binary_text_with_positions)?;
+        assert_eq!(binary_with_positions_scores, binary_scores);
+
+        let query_parser = QueryParser::for_index(&index, vec![binary_text_with_positions]);
+        let phrase_query = query_parser.parse_query("\"a a a\"")?;
+        assert_eq!(
+            searcher
+                .search(&phrase_query, &TopDocs::with_limit(2))?
+                .len(),
+            1
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_bm25_params() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text=>"a b"));
+        index_writer.add_document(doc!(text=>"a b c d e f"));
+        index_writer.commit()?;
+        let query = TermQuery::new(
+            Term::from_field_text(text, "a"),
+            IndexRecordOption::WithFreqs,
+        );
+        let scores = |bm25_params: BM25Params| -> crate::Result<Vec<Score>> {
+            let reader = index.reader_builder().bm25_params(bm25_params).try_into()?;
+            let searcher = reader.searcher();
+            assert_eq!(searcher.bm25_params(), bm25_params);
+            let mut top_docs = searcher.search(&query, &TopDocs::with_limit(2))?;
+            top_docs.sort_by_key(|(_, doc_address)| *doc_address);
+            assert_eq!(
+                top_docs.iter().map(|(_, doc)| *doc).collect::<Vec<_>>(),
+                vec![DocAddress(0, 0), DocAddress(0, 1)]
+            );
+            Ok(top_docs.into_iter().map(|(score, _)| score).collect())
+        };
+        let default_scores = scores(BM25Params::default())?;
+        assert!(default_scores[0] > default_scores[1]);
+        let no_length_norm_scores = scores(BM25Params { k1: 1.2, b: 0.0 })?;
+        assert_nearly_equals!(no_length_norm_scores[0], no_length_norm_scores[1]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_bm25_params_top_docs_over_several_blocks() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        let long_text = vec!["b"; 20].join(" ");
+        index_writer.add_document(doc!(text=>"a"));
+        index_writer.add_document(doc!(text=>format!("a c {}", long_text)));
+        for doc in 2..300 {
+            if doc == 200 {
+                // With the default parameters, the short documents of its block
+                // have higher scores, so the block-max information
+                // of the block is the one of a short document.
+                index_writer.add_document(doc!(text=>format!("a a a c {}", long_text.repeat(10))));
+            } else {
+                index_writer.add_document(doc!(text=>format!("a {}", long_text)));
+            }
+        }
+        index_writer.commit()?;
+        let reader = index
+            .reader_builder()
+            .bm25_params(BM25Params { k1: 1.2, b: 0.0 })
+            .try_into()?;
+        let searcher = reader.searcher();
+        let term_query = TermQuery::new(
+            Term::from_field_text(text, "a"),
+            IndexRecordOption::WithFreqs,
+        );
+        let union_query = QueryParser::for_index(&index, vec![text]).parse_query("a c")?;
+        for query in &[&term_query as &dyn Query, union_query.as_ref()] {
+            let top_docs = searcher.search(*query, &TopDocs::with_limit(1))?;
+            assert_eq!(top_docs[0].1, DocAddress(0, 200));
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,470 @@
+use crate::fieldnorm::FieldNormReader;
+use crate::postings::{Postings, SegmentPostings};
+use crate::query::bm25::{field_statistics, idf};
+use crate::query::explanation::does_not_match;
+use crate::query::{EmptyScorer, Explanation, Query, Scorer, Weight};
+use crate::schema::{Field, IndexRecordOption, Term};
+use crate::{DocId, DocSet, Index, Score, Searcher, SegmentReader, TantivyError, TERMINATED};
+use std::collections::BTreeSet;
+
+/// A field searched by a [`BM25FQuery`](./struct.BM25FQuery.html).
+#[derive(Clone, Debug, PartialEq)]
+pub struct BM25FClause {
+    /// Field to search over. It must be an indexed text field.
+    pub field: Field,
+    /// Text searched in the field, tokenized with the tokenizer of the field.
+    pub query_str: String,
+    /// Weight of the term frequencies of the field, e.g. 2 for a title.
+    pub weight: Score,
+    /// Length normalization parameter of the field,
+    /// between 0 (no normalization) and 1 (full normalization).
+    pub b: Score,
+}
+
+/// `BM25FQuery` scores the documents with BM25F, the extension of BM25 to
+/// documents with several fields.
+///
+/// Instead of summing the BM25 scores of the different fields, BM25F
+/// combines the term frequencies of the fields before applying the
+/// saturation of BM25 once. For each term, the pseudo term frequency of a document is
+///
+/// `sum over the fields of weight * tf / (1 - b + b * dl / avgdl)`
+///
+/// and the score of the document is the sum over the terms of
+/// `idf * (k1 + 1) * pseudo_tf / (k1 + pseudo_tf)`.
+///
+/// The terms of the different clauses are identified by their text: a token `girl`
+/// in the title and in the body counts as the same term. Its idf is computed
+/// with its largest document frequency among these fields.
+/// A document matches the query if it contains any of the terms.
+///
+/// With a single clause of weight 1, the scores are the BM25 scores of the field,
+/// with `k1` and the `b` of the clause.
+///
+/// ```rust
+/// use tantivy::collector::TopDocs;
+/// use tantivy::doc;
+/// use tantivy::query::{BM25FClause, BM25FQuery};
+/// use tantivy::schema::{Schema, TEXT};
+/// use tantivy::Index;
+///
+/// # fn main() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let title = schema_builder.add_text_field("title", TEXT);
+/// let body = schema_builder.add_text_field("body", TEXT);
+/// let index = Index::create_in_ram(schema_builder.build());
+/// let mut index_writer = index.writer(3_000_000)?;
+/// index_writer.add_document(doc!(title => "The Diary of a Young Girl", body => "girl"));
+/// index_writer.add_document(doc!(title => "The Diary of Muadib"));
+/// index_writer.commit()?;
+///
+/// let searcher = index.reader()?.searcher();
+/// let clause = |field, weight, b| BM25FClause {
+///     field,
+///     query_str: "young girl".to_string(),
+///     weight,
+///     b,
+/// };
+/// let clauses = vec![clause(title, 2.0, 0.5), clause(body, 1.0, 0.75)];
+/// let query = BM25FQuery::new(&index, clauses, 1.2)?;
+/// let top_docs = searcher.search(&query, &TopDocs::with_limit(2))?;
+/// assert_eq!(top_docs.len(), 1);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct BM25FQuery {
+    clauses: Vec<BM25FClause>,
+    k1: Score,
+    // For each clause, the ordinals of its distinct terms and the terms.
+    clause_terms: Vec<Vec<(usize, Term)>>,
+    num_terms: usize,
+}
+
+impl BM25FQuery {
+    /// Creates a new `BM25FQuery` from its clauses and the term frequency
+    /// saturation parameter `k1`.
+    ///
+    /// The `query_str` of each clause is tokenized with the tokenizer of its field,
+    /// as registered in the `TokenizerManager` of the `index`.
+    ///
+    /// Returns an error if one of the fields is not an indexed text field,
+    /// if its tokenizer is not registered, or if one of the parameters is out of range.
+    pub fn new(index: &Index, clauses: Vec<BM25FClause>, k1: Score) -> crate::Result<BM25FQuery> {
+        if k1.is_nan() || k1 < 0.0 {
+            return Err(TantivyError::InvalidArgument(format!(
+                "k1 must be positive, got {}.",
+                k1
+            )));
+        }
+        let mut term_texts: Vec<String> = Vec::new();
+        let mut clause_terms = Vec::with_capacity(clauses.len());
+        for clause in &clauses {
+            if !(0.0..=1.0).contains(&clause.b) || clause.weight.is_nan() || clause.weight < 0.0 {
+                return Err(TantivyError::InvalidArgument(format!(
+                    "Invalid BM25F clause {:?}: b must be between 0 and 1, and the weight positive.",
+                    clause
+                )));
+            }
+            let tokenizer = index.tokenizer_for_field(clause.field)?;
+            let mut terms: Vec<(usize, Term)> = Vec::new();
+            tokenizer
+                .token_stream(&clause.query_str)
+                .process(&mut |token| {
+                    let term_ord = term_texts
+                        .iter()
+                        .position(|text| *text == token.text)
+                        .unwrap_or_else(|| {
+                            term_texts.push(token.text.clone());
+                            term_texts.len() - 1
+                        });
+                    if terms.iter().all(|&(ord, _)| ord != term_ord) {
+                        terms.push((term_ord, Term::from_field_text(clause.field, &token.text)));
+                    }
+                });
+            clause_terms.push(terms);
+        }
+        Ok(BM25FQuery {
+            clauses,
+            k1,
+            clause_terms,
+            num_terms: term_texts.len(),
+        })
+    }
+
+    /// Returns the clauses of the query.
+    pub fn clauses(&self) -> &[BM25FClause] {
+        &self.clauses
+    }
+
+    /// Returns the term frequency saturation parameter.
+    pub fn k1(&self) -> Score {
+        self.k1
+    }
+}
+
+impl Query for BM25FQuery {
+    fn weight(
+        &self,
+        searcher: &Searcher,
+        _scoring_enabled: bool,
+    ) -> crate::Result<Box<dyn Weight>> {
+        let mut max_doc_freqs = vec![0u64; self.num_terms];
+        let mut total_num_docs = 0u64;
+        let mut fields = Vec::with_capacity(self.clauses.len());
+        for (clause, terms) in self.clauses.iter().zip(&self.clause_terms) {
+            let (num_docs, average_fieldnorm) = field_statistics(searcher, clause.field)?;
+            total_num_docs = num_docs;
+            for (term_ord, term) in terms {
+                let doc_freq = searcher.doc_freq(term)?;
+                max_doc_freqs[*term_ord] = max_doc_freqs[*term_ord].max(doc_freq);
+            }
+            fields.push(BM25FField {
+                field: clause.field,
+                weight: clause.weight,
+                length_norms: length_norms(clause.b, average_fieldnorm),
+                terms: terms.clone(),
+            });
+        }
+        let idfs = max_doc_freqs
+            .into_iter()
+            .map(|doc_freq| idf(doc_freq, total_num_docs))
+            .collect();
+        Ok(Box::new(BM25FWeight {
+            fields,
+            idfs,
+            k1: self.k1,
+        }))
+    }
+
+    fn query_terms(&self, term_set: &mut BTreeSet<Term>) {
+        for terms in &self.clause_terms {
+            term_set.extend(terms.iter().map(|(_, term)| term.clone()));
+        }
+    }
+}
+
+/// Returns `1 - b + b * dl / avgdl` for each fieldnorm id.
+fn length_norms(b: Score, average_fieldnorm: Score) -> [Score; 256] {
+    let mut length_norms: [Score; 256] = [0.0; 256];
+    for (fieldnorm_id, length_norm) in length_norms.iter_mut().enumerate() {
+        let fieldnorm = FieldNormReader::id_to_fieldnorm(fieldnorm_id as u8);
+        *length_norm = 1.0 - b + b * fieldnorm as Score / average_fieldnorm;
+    }
+    length_norms
+}
+
+struct BM25FField {
+    field: Field,
+    weight: Score,
+    length_norms: [Score; 256],
+    terms: Vec<(usize, Term)>,
+}
+
+struct BM25FWeight {
+    fields: Vec<BM25FField>,
+    idfs: Vec<Score>,
+    k1: Score,
+}
+
+impl Weight for BM25FWeight {
+    fn scorer(&self, reader: &SegmentReader, boost: Score) -> crate::Result<Box<dyn Scorer>> {
+        let mut postings = Vec::new();
+        for field in &self.fields {
+            let inverted_index = reader.inverted_index(field.field)?;
+            let fieldnorm_reader = reader.get_fieldnorms_reader(field.field)?;
+            for (term_ord, term) in &field.terms {
+                if let Some(segment_postings) =
+                    inverted_index.read_postings(term, IndexRecordOption::WithFreqs)?
+                {
+                    postings.push(BM25FPostings {
+                        term_ord: *term_ord,
+                        postings: segment_postings,
+                        fieldnorm_reader: fieldnorm_reader.clone(),
+                        weight: field.weight,
+                        length_norms: field.length_norms,
+                    });
+                }
+            }
+        }
+        if postings.is_empty() {
+            return Ok(Box::new(EmptyScorer));
+        }
+        Ok(Box::new(BM25FScorer::new(
+            postings,
+            self.idfs.clone(),
+            self.k1,
+            boost,
+        )))
+    }
+
+    fn explain(&self, reader: &SegmentReader, doc: DocId) -> crate::Result<Explanation> {
+        let mut scorer = self.scorer(reader, 1.0)?;
+        if scorer.doc() > doc || scorer.seek(doc) != doc {
+            return Err(does_not_match(doc));
+        }
+        let mut explanation = Explanation::new(
+            "BM25F, sum of idf * (k1 + 1) * pseudo_tf / (k1 + pseudo_tf)",
+            scorer.score(),
+        );
+        explanation.add_const("k1, term saturation parameter", self.k1);
+        Ok(explanation)
+    }
+}
+
+struct BM25FPostings {
+    term_ord: usize,
+    postings: SegmentPostings,
+    fieldnorm_reader: FieldNormReader,
+    weight: Score,
+    length_norms: [Score; 256],
+}
+
+/// Scorer of the union of the postings of the terms of a `BM25FQuery`.
+struct BM25FScorer {
+    postings: Vec<BM25FPostings>,
+    idfs: Vec<Score>,
+    k1: Score,
+    boost: Score,
+    doc: DocId,
+    pseudo_tfs: Vec<Score>,
+}
+
+impl BM25FScorer {
+    fn new(postings: Vec<BM25FPostings>, idfs: Vec<Score>, k1: Score, boost: Score) -> Self {
+        let num_terms = idfs.len();
+        let mut scorer = BM25FScorer {
+            postings,
+            idfs,
+            k1,
+            boost,
+            doc: TERMINATED,
+            pseudo_tfs: vec![0.0; num_terms],
+        };
+        scorer.doc = scorer.min_doc();
+        scorer
+    }
+
+    fn min_doc(&self) -> DocId {
+        self.postings
+            .iter()
+            .map(|postings| postings.postings.doc())
+            .min()
+            .unwrap_or(TERMINATED)
+    }
+}
+
+impl DocSet for BM25FScorer {
+    fn advance(&mut self) -> DocId {
+        if self.doc == TERMINATED {
+            return TERMINATED;
+        }
+        for postings in &mut self.postings {
+            if postings.postings.doc() == self.doc {
+                postings.postings.advance();
+            }
+        }
+        self.doc = self.min_doc();
+        self.doc
+    }
+
+    fn seek(&mut self, target: DocId) -> DocId {
+        if self.doc >= target {
+            return self.doc;
+        }
+        for postings in &mut self.postings {
+            if postings.postings.doc() < target {
+                postings.postings.seek(target);
+            }
+        }
+        self.doc = self.min_doc();
+        self.doc
+    }
+
+    fn doc(&self) -> DocId {
+        self.doc
+    }
+
+    fn size_hint(&self) -> u32 {
+        self.postings
+            .iter()
+            .map(|postings| postings.postings.size_hint())
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn docs_skipped(&self) -> u64 {
+        self.postings
+            .iter()
+            .map(|postings| postings.postings.docs_skipped())
+            .sum()
+    }
+}
+
+impl Scorer for BM25FScorer {
+    fn score(&mut self) -> Score {
+        let doc = self.doc;
+        self.pseudo_tfs
+            .iter_mut()
+            .for_each(|pseudo_tf| *pseudo_tf = 0.0);
+        for postings in &self.postings {
+            if postings.postings.doc() != doc {
+                continue;
+            }
+            let fieldnorm_id = postings.fieldnorm_reader.fieldnorm_id(doc);
+            let term_freq = postings.postings.term_freq() as Score;
+            self.pseudo_tfs[postings.term_ord] +=
+                postings.weight * term_freq / postings.length_norms[fieldnorm_id as usize];
+        }
+        let k1 = self.k1;
+        let score: Score = self
+            .pseudo_tfs
+            .iter()
+            .zip(&self.idfs)
+            .filter(|(pseudo_tf, _)| **pseudo_tf > 0.0)
+            .map(|(pseudo_tf, idf)| idf * (k1 + 1.0) * pseudo_tf / (k1 + pseudo_tf))
+            .sum();
+        score * self.boost
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BM25FClause, BM25FQuery};
+    use crate::collector::{Count, TopDocs};
+    use crate::query::{BooleanQuery, Query};
+    use crate::schema::{Field, Schema, INDEXED, TEXT};
+    use crate::{assert_nearly_equals, DocAddress, Index, Score, Term};
+
+    fn create_index() -> crate::Result<Index> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let body = schema_builder.add_text_field("body", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title => "Old Man", body => "the sea"));
+        index_writer.add_document(doc!(title => "The Old Sea", body => "old man and the sea"));
+        index_writer.commit()?;
+        index_writer.add_document(doc!(title => "Moby Dick", body => "the whale"));
+        index_writer.commit()?;
+        Ok(index)
+    }
+
+    fn clause(field: Field, query_str: &str, weight: Score, b: Score) -> BM25FClause {
+        BM25FClause {
+            field,
+            query_str: query_str.to_string(),
+            weight,
+            b,
+        }
+    }
+
+    #[test]
+    fn test_bm25f_single_clause_is_bm25() -> crate::Result<()> {
+        let index = create_index()?;
+        let body = index.schema().get_field("body").unwrap();
+        let searcher = index.reader()?.searcher();
+        let query = BM25FQuery::new(&index, vec![clause(body, "old SEA", 1.0, 0.75)], 1.2)?;
+        let expected = BooleanQuery::new_multiterms_query(vec![
+            Term::from_field_text(body, "old"),
+            Term::from_field_text(body, "sea"),
+        ]);
+        let top_docs = searcher.search(&query, &TopDocs::with_limit(10))?;
+        let expected_top_docs = searcher.search(&expected, &TopDocs::with_limit(10))?;
+        assert_eq!(top_docs.len(), 2);
+        assert_eq!(top_docs.len(), expected_top_docs.len());
+        for ((score, doc), (expected_score, expected_doc)) in
+            top_docs.iter().zip(expected_top_docs.iter())
+        {
+            assert_eq!(doc, expected_doc);
+            assert_nearly_equals!(*score, *expected_score);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_bm25f_combines_fields() -> crate::Result<()> {
+        let index = create_index()?;
+        let title = index.schema().get_field("title").unwrap();
+        let body = index.schema().get_field("body").unwrap();
+        let searcher = index.reader()?.searcher();
+        let query = BM25FQuery::new(
+            &index,
+            vec![clause(title, "old", 2.0, 0.5), clause(body, "old", 1.0, 0.75)],
+            1.2,
+        )?;
+        assert_eq!(searcher.search(&query, &Count)?, 2);
+        let top_docs = searcher.search(&query, &TopDocs::with_limit(10))?;
+        // `old` appears in the titles of 2 documents out of 3, and in 1 body.
+        let idf = (1.0 + 1.5 / 2.5 as Score).ln();
+        let (average_title_len, average_body_len) = (7.0 / 3.0, 3.0);
+        let score = |pseudo_tf: Score| idf * 2.2 * pseudo_tf / (1.2 + pseudo_tf);
+        let title_tf = |len: Score| 2.0 / (0.5 + 0.5 * len / average_title_len);
+        let body_tf = |len: Score| 1.0 / (0.25 + 0.75 * len / average_body_len);
+        assert_eq!(top_docs[0].1, DocAddress(0, 1));
+        assert_nearly_equals!(top_docs[0].0, score(title_tf(3.0) + body_tf(5.0)));
+        assert_eq!(top_docs[1].1, DocAddress(0, 0));
+        assert_nearly_equals!(top_docs[1].0, score(title_tf(2.0)));
+        let explanation = query.explain(&searcher, DocAddress(0, 1))?;
+        assert_nearly_equals!(explanation.value(), top_docs[0].0);
+        assert!(query.explain(&searcher, DocAddress(1, 0)).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_bm25f_query_terms_and_errors() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let id = schema_builder.add_u64_field("id", INDEXED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let query = BM25FQuery::new(&index, vec![clause(title, "Old old man", 1.0, 0.75)], 1.2)?;
+        let mut terms = std::collections::BTreeSet::new();
+        query.query_terms(&mut terms);
+        assert_eq!(terms.len(), 2);
+        assert!(BM25FQuery::new(&index, vec![clause(title, "old", 1.0, 1.5)], 1.2).is_err());
+        assert!(BM25FQuery::new(&index, vec![clause(title, "old", -1.0, 0.5)], 1.2).is_err());
+        assert!(BM25FQuery::new(&index, vec![clause(title, "old", 1.0, 0.5)], -1.0).is_err());
+        assert!(BM25FQuery::new(&index, vec![clause(id, "1", 1.0, 0.5)], 1.2).is_err());
+        Ok(())
+    }
+}
@@ -1,12 +1,15 @@
 use super::boolean_weight::BooleanWeight;
+use crate::query::query::fmt_query;
+use crate::query::BoostQuery;
 use crate::query::Occur;
 use crate::query::Query;
 use crate::query::TermQuery;
 use crate::query::Weight;
 use crate::schema::IndexRecordOption;
 use crate::schema::Term;
-use crate::Searcher;
-use std::collections::BTreeSet;
+use crate::{Score, Searcher};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
 
 /// The boolean query returns a set of documents
 /// that matches the Boolean combination of constituent subqueries.
@@ -20,6 +23,9 @@ use std::collections::BTreeSet;
 /// * match at least one of the subqueries that is not
 /// a `MustNot` occurence.
 ///
+/// It is also possible to require a document to match a minimum number of the
+/// `Should` subqueries. (See [`BooleanQuery::with_minimum_should_match`](#method.with_minimum_should_match))
+///
 ///
 /// You can combine other query types and their `Occur`ances into one `BooleanQuery`
 ///
@@ -129,15 +135,41 @@ use std::collections::BTreeSet;
 #[derive(Debug)]
 pub struct BooleanQuery {
     subqueries: Vec<(Occur, Box<dyn Query>)>,
+    minimum_should_match: usize,
 }
 
 impl Clone for BooleanQuery {
     fn clone(&self) -> Self {
-        self.subqueries
+        let subqueries = self
+            .subqueries
             .iter()
             .map(|(occur, subquery)| (*occur, subquery.box_clone()))
-            .collect::<Vec<_>>()
-            .into()
+            .collect::<Vec<_>>();
+        BooleanQuery::with_minimum_should_match(subqueries, self.minimum_should_match)
+    }
+}
+
+/// Displays the query like Lucene does, like `+0:coffee 1:tea -1:milk`.
+///
+/// The `Must` subqueries are prefixed with `+`, the `MustNot` ones with `-`.
+/// A `minimum_should_match` greater than 0 is appended as `~n`.
+impl fmt::Display for BooleanQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (ord, (occur, subquery)) in self.subqueries.iter().enumerate() {
+            if ord > 0 {
+                write!(f, " ")?;
+            }
+            match occur {
+                Occur::Must => write!(f, "+")?,
+                Occur::MustNot => write!(f, "-")?,
+                Occur::Should => {}
+            }
+            fmt_query(subquery.as_ref(), f)?;
+        }
+        if self.minimum_should_match > 0 {
+            write!(f, "~{}", self.minimum_should_match)?;
+        }
+        Ok(())
     }
 }
 
@@ -156,7 +188,11 @@ impl Query for BooleanQuery {
                 Ok((*occur, subquery.weight(searcher, scoring_enabled)?))
             })
             .collect::<crate::Result<_>>()?;
-        Ok(Box::new(BooleanWeight::new(sub_weights, scoring_enabled)))
+        Ok(Box::new(BooleanWeight::new(
+            sub_weights,
+            self.minimum_should_match,
+            scoring_enabled,
+        )))
     }
 
     fn query_terms(&self, term_set: &mut BTreeSet<Term>) {
@@ -164,12 +200,105 @@ impl Query for BooleanQuery {
             subquery.query_terms(term_set);
         }
     }
+
+    fn normalize(&self) -> Box<dyn Query> {
+        let mut subqueries: Vec<(Occur, Box<dyn Query>)> = Vec::new();
+        for (occur, subquery) in &self.subqueries {
+            let subquery = subquery.normalize();
+            match subquery.downcast_ref::<BooleanQuery>() {
+                Some(nested) if nested.can_be_inlined(*occur, self.minimum_should_match) => {
+                    // `MustNot` of a union is the `MustNot` of each of its clauses.
+                    subqueries.extend(nested.subqueries.iter().map(|(nested_occur, clause)| {
+                        let occur = if *occur == Occur::MustNot {
+                            Occur::MustNot
+                        } else {
+                            *nested_occur
+                        };
+                        (occur, clause.box_clone())
+                    }));
+                }
+                _ => subqueries.push((*occur, subquery)),
+            }
+        }
+        if self.minimum_should_match == 0 {
+            subqueries = merge_duplicate_should_clauses(subqueries);
+        }
+        Box::new(BooleanQuery::with_minimum_should_match(
+            subqueries,
+            self.minimum_should_match,
+        ))
+    }
+
+    /// The query has a key iff all of its clauses have one.
+    fn cache_key(&self) -> Option<String> {
+        let clause_keys = self
+            .subqueries
+            .iter()
+            .map(|(occur, subquery)| Some((*occur, subquery.cache_key()?)))
+            .collect::<Option<Vec<(Occur, String)>>>()?;
+        Some(format!(
+            "BooleanQuery({:?},{:?})",
+            clause_keys, self.minimum_should_match
+        ))
+    }
+}
+
+/// Merges the `Should` clauses matching the same query into the first of them,
+/// boosted by the sum of their boosts.
+///
+/// Clauses are compared by the `Debug` representation of their unboosted query.
+fn merge_duplicate_should_clauses(
+    subqueries: Vec<(Occur, Box<dyn Query>)>,
+) -> Vec<(Occur, Box<dyn Query>)> {
+    let mut merged: Vec<(Occur, Box<dyn Query>)> = Vec::with_capacity(subqueries.len());
+    let mut should_clauses: HashMap<String, (usize, Score)> = HashMap::new();
+    for (occur, subquery) in subqueries {
+        if occur != Occur::Should {
+            merged.push((occur, subquery));
+            continue;
+        }
+        let (key, boost) = match subquery.downcast_ref::<BoostQuery>() {
+            Some(boost_query) => (format!("{:?}", boost_query.query()), boost_query.boost()),
+            None => (format!("{:?}", subquery), 1.0),
+        };
+        if let Some((pos, boost_sum)) = should_clauses.get_mut(&key) {
+            *boost_sum += boost;
+            let unboosted_query = match merged[*pos].1.downcast_ref::<BoostQuery>() {
+                Some(boost_query) => boost_query.query().box_clone(),
+                None => merged[*pos].1.box_clone(),
+            };
+            merged[*pos].1 = Box::new(BoostQuery::new(unboosted_query, *boost_sum));
+        } else {
+            should_clauses.insert(key, (merged.len(), boost));
+            merged.push((occur, subquery));
+        }
+    }
+    merged
 }
 
 impl BooleanQuery {
     /// Creates a new boolean query.
     pub fn new(subqueries: Vec<(Occur, Box<dyn Query>)>) -> BooleanQuery {
-        BooleanQuery { subqueries }
+        BooleanQuery::with_minimum_should_match(subqueries, 0)
+    }
+
+    /// Creates a new boolean query, matching only the documents that match
+    /// at least `minimum_should_match` of the `Should` subqueries.
+    ///
+    /// The `Must` and `MustNot` subqueries keep their usual semantics.
+    /// When `minimum_should_match` is greater than 0, the `Should` subqueries
+    /// are required to match even if the query has some `Must` subqueries.
+    ///
+    /// If `minimum_should_match` is greater than the number of `Should`
+    /// subqueries, the query does not match any document.
+    pub fn with_minimum_should_match(
+        subqueries: Vec<(Occur, Box<dyn Query>)>,
+        minimum_should_match: usize,
+    ) -> BooleanQuery {
+        BooleanQuery {
+            subqueries,
+            minimum_should_match,
+        }
     }
 
     /// Returns the intersection of the queries.
@@ -202,13 +331,43 @@ impl BooleanQuery {
     pub fn clauses(&self) -> &[(Occur, Box<dyn Query>)] {
         &self.subqueries[..]
     }
+
+    /// Returns the minimum number of `Should` subqueries a document is required to match.
+    pub fn minimum_should_match(&self) -> usize {
+        self.minimum_should_match
+    }
+
+    /// Returns true iff this query, as an `occur` clause of a query requiring
+    /// `parent_minimum_should_match` `Should` clauses, can be replaced by its own clauses.
+    fn can_be_inlined(&self, occur: Occur, parent_minimum_should_match: usize) -> bool {
+        if self.minimum_should_match > 0 {
+            return false;
+        }
+        let has_occur = |expected: Occur| self.subqueries.iter().any(|(o, _)| *o == expected);
+        match occur {
+            // A conjunction inside a conjunction.
+            Occur::Must => has_occur(Occur::Must) && !has_occur(Occur::Should),
+            // A disjunction inside a disjunction.
+            Occur::Should => {
+                parent_minimum_should_match == 0
+                    && !self.subqueries.is_empty()
+                    && self.subqueries.iter().all(|(o, _)| *o == Occur::Should)
+            }
+            // The documents matching any of the clauses of a disjunction are excluded.
+            Occur::MustNot => {
+                !self.subqueries.is_empty()
+                    && self.subqueries.iter().all(|(o, _)| *o == Occur::Should)
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::BooleanQuery;
-    use crate::collector::DocSetCollector;
-    use crate::query::{QueryClone, TermQuery};
+    use crate::assert_nearly_equals;
+    use crate::collector::{DocSetCollector, TopDocs};
+    use crate::query::{BoostQuery, Occur, Query, QueryClone, TermQuery};
     use crate::schema::{IndexRecordOption, Schema, TEXT};
     use crate::{DocAddress, Index, Term};
 
@@ -248,6 +407,85 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_minimum_should_match() -> crate::Result<()> {
+        let index = create_test_index()?;
+        let searcher = index.reader()?.searcher();
+        let text = index.schema().get_field("text").unwrap();
+        let term_query = |text_term: &str| -> Box<dyn Query> {
+            Box::new(TermQuery::new(
+                Term::from_field_text(text, text_term),
+                IndexRecordOption::Basic,
+            ))
+        };
+        let docs = |query: &BooleanQuery| -> crate::Result<Vec<u32>> {
+            let mut docs: Vec<u32> = searcher
+                .search(query, &DocSetCollector)?
+                .into_iter()
+                .map(|doc_address| doc_address.doc())
+                .collect();
+            docs.sort_unstable();
+            Ok(docs)
+        };
+        let should = |text_terms: &[&str]| -> Vec<(Occur, Box<dyn Query>)> {
+            text_terms
+                .iter()
+                .map(|text_term| (Occur::Should, term_query(text_term)))
+                .collect()
+        };
+        let query = BooleanQuery::with_minimum_should_match(should(&["a", "b", "c"]), 1);
+        assert_eq!(docs(&query)?, vec![0, 1, 2, 3]);
+        let query = BooleanQuery::with_minimum_should_match(should(&["a", "b", "c"]), 2);
+        assert_eq!(docs(&query)?, vec![0, 1, 2]);
+        assert_eq!(docs(&query.clone())?, vec![0, 1, 2]);
+        let query = BooleanQuery::with_minimum_should_match(should(&["a", "b", "c"]), 3);
+        assert!(docs(&query)?.is_empty());
+        let query = BooleanQuery::with_minimum_should_match(should(&["a"]), 2);
+        assert!(docs(&query)?.is_empty());
+        let must_a_should_bc = || {
+            let mut clauses = should(&["b", "c"]);
+            clauses.push((Occur::Must, term_query("a")));
+            clauses
+        };
+        assert_eq!(docs(&BooleanQuery::new(must_a_should_bc()))?, vec![1, 2, 3]);
+        let query = BooleanQuery::with_minimum_should_match(must_a_should_bc(), 1);
+        assert_eq!(docs(&query)?, vec![1, 2]);
+        let mut clauses = should(&["a", "b", "c"]);
+        clauses.push((Occur::MustNot, term_query("b")));
+        let query = BooleanQuery::with_minimum_should_match(clauses, 2);
+        assert_eq!(docs(&query)?, vec![1]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_minimum_should_match_scores() -> crate::Result<()> {
+        let index = create_test_index()?;
+        let searcher = index.reader()?.searcher();
+        let text = index.schema().get_field("text").unwrap();
+        let term_query = |text_term: &str| -> Box<dyn Query> {
+            Box::new(TermQuery::new(
+                Term::from_field_text(text, text_term),
+                IndexRecordOption::WithFreqs,
+            ))
+        };
+        let clauses = || -> Vec<(Occur, Box<dyn Query>)> {
+            vec![
+                (Occur::Should, term_query("a")),
+                (Occur::Should, term_query("b")),
+            ]
+        };
+        let union_top_docs =
+            searcher.search(&BooleanQuery::new(clauses()), &TopDocs::with_limit(1))?;
+        let query = BooleanQuery::with_minimum_should_match(clauses(), 2);
+        let top_docs = searcher.search(&query, &TopDocs::with_limit(10))?;
+        assert_eq!(top_docs.len(), 1);
+        assert_eq!(top_docs[0].1, DocAddress(0, 2));
+        assert_eq!(top_docs, union_top_docs);
+        let explanation = query.explain(&searcher, DocAddress(0, 2))?;
+        assert_nearly_equals!(explanation.value(), top_docs[0].0);
+        Ok(())
+    }
+
     #[test]
     fn test_intersection() -> crate::Result<()> {
         let index = create_test_index()?;
@@ -276,4 +514,149 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_normalize() -> crate::Result<()> {
+        let index = create_test_index()?;
+        let searcher = index.reader()?.searcher();
+        let text = index.schema().get_field("text").unwrap();
+        let term_query = |text_term: &str| -> Box<dyn Query> {
+            Box::new(TermQuery::new(
+                Term::from_field_text(text, text_term),
+                IndexRecordOption::WithFreqs,
+            ))
+        };
+        let assert_same_scores = |query: &dyn Query, normalized: &dyn Query| {
+            let top_docs = searcher.search(query, &TopDocs::with_limit(10)).unwrap();
+            let normalized_top_docs = searcher
+                .search(normalized, &TopDocs::with_limit(10))
+                .unwrap();
+            assert_eq!(top_docs.len(), normalized_top_docs.len());
+            for ((score, doc), (normalized_score, normalized_doc)) in
+                top_docs.iter().zip(normalized_top_docs.iter())
+            {
+                assert_eq!(doc, normalized_doc);
+                assert_nearly_equals!(*score, *normalized_score);
+            }
+        };
+
+        // (a AND (c AND NOT b)) is a AND c AND NOT b.
+        let query = BooleanQuery::new(vec![
+            (Occur::Must, term_query("a")),
+            (
+                Occur::Must,
+                Box::new(BooleanQuery::new(vec![
+                    (Occur::Must, term_query("c")),
+                    (Occur::MustNot, term_query("b")),
+                ])),
+            ),
+        ]);
+        let normalized = query.normalize();
+        let expected = BooleanQuery::new(vec![
+            (Occur::Must, term_query("a")),
+            (Occur::Must, term_query("c")),
+            (Occur::MustNot, term_query("b")),
+        ]);
+        assert_eq!(format!("{:?}", normalized), format!("{:?}", expected));
+        assert_same_scores(&query, normalized.as_ref());
+
+        // (a OR (c OR a) OR NOT (b OR d)) merges the `a` clauses.
+        let query = BooleanQuery::new(vec![
+            (Occur::Should, term_query("a")),
+            (
+                Occur::Should,
+                Box::new(BooleanQuery::union(vec![term_query("c"), term_query("a")])),
+            ),
+            (
+                Occur::MustNot,
+                Box::new(BooleanQuery::union(vec![term_query("b"), term_query("d")])),
+            ),
+        ]);
+        let normalized = query.normalize();
+        let expected = BooleanQuery::new(vec![
+            (
+                Occur::Should,
+                Box::new(BoostQuery::new(term_query("a"), 2.0)),
+            ),
+            (Occur::Should, term_query("c")),
+            (Occur::MustNot, term_query("b")),
+            (Occur::MustNot, term_query("d")),
+        ]);
+        assert_eq!(format!("{:?}", normalized), format!("{:?}", expected));
+        assert_same_scores(&query, normalized.as_ref());
+
+        // Boosts of duplicate clauses are summed.
+        let query = BooleanQuery::new(vec![
+            (
+                Occur::Should,
+                Box::new(BoostQuery::new(term_query("a"), 0.5)),
+            ),
+            (Occur::Should, term_query("b")),
+            (
+                Occur::Should,
+                Box::new(BoostQuery::new(term_query("a"), 2.0)),
+            ),
+            (Occur::Should, term_query("a")),
+        ]);
+        let normalized = query.normalize();
+        let expected = BooleanQuery::new(vec![
+            (
+                Occur::Should,
+                Box::new(BoostQuery::new(term_query("a"), 3.5)),
+            ),
+            (Occur::Should, term_query("b")),
+        ]);
+        assert_eq!(format!("{:?}", normalized), format!("{:?}", expected));
+        assert_same_scores(&query, normalized.as_ref());
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalize_keeps_non_equivalent_structures() -> crate::Result<()> {
+        let index = create_test_index()?;
+        let text = index.schema().get_field("text").unwrap();
+        let term_query = |text_term: &str| -> Box<dyn Query> {
+            Box::new(TermQuery::new(
+                Term::from_field_text(text, text_term),
+                IndexRecordOption::WithFreqs,
+            ))
+        };
+        let queries = vec![
+            // A disjunction required to match.
+            BooleanQuery::new(vec![
+                (Occur::Must, term_query("a")),
+                (
+                    Occur::Must,
+                    Box::new(BooleanQuery::union(vec![term_query("b"), term_query("c")])),
+                ),
+            ]),
+            // A conjunction as an optional clause.
+            BooleanQuery::new(vec![
+                (Occur::Should, term_query("a")),
+                (
+                    Occur::Should,
+                    Box::new(BooleanQuery::intersection(vec![
+                        term_query("b"),
+                        term_query("c"),
+                    ])),
+                ),
+            ]),
+            // Duplicate clauses count twice towards the minimum number of `Should` clauses.
+            BooleanQuery::with_minimum_should_match(
+                vec![
+                    (Occur::Should, term_query("a")),
+                    (Occur::Should, term_query("a")),
+                    (
+                        Occur::Should,
+                        Box::new(BooleanQuery::union(vec![term_query("b"), term_query("c")])),
+                    ),
+                ],
+                2,
+            ),
+        ];
+        for query in queries {
+            assert_eq!(format!("{:?}", query.normalize()), format!("{:?}", query));
+        }
+        Ok(())
+    }
 }
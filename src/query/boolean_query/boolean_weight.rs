@@ -1,3 +1,4 @@
+use super::min_should_match_scorer::MinShouldMatchScorer;
 use crate::core::SegmentReader;
 use crate::postings::FreqReadingOption;
 use crate::query::explanation::does_not_match;
@@ -6,6 +7,7 @@ use crate::query::term_query::TermScorer;
 use crate::query::weight::{for_each_pruning_scorer, for_each_scorer};
 use crate::query::EmptyScorer;
 use crate::query::Exclude;
+use crate::query::Intersection;
 use crate::query::Occur;
 use crate::query::RequiredOptionalScorer;
 use crate::query::Scorer;
@@ -36,11 +38,12 @@ where
                 .into_iter()
                 .map(|scorer| *(scorer.downcast::<TermScorer>().map_err(|_| ()).unwrap()))
                 .collect();
-            if scorers
-                .iter()
-                .all(|scorer| scorer.freq_reading_option() == FreqReadingOption::ReadFreq)
-            {
-                // Block wand is only available iff we read frequencies.
+            if scorers.iter().all(|scorer| {
+                scorer.freq_reading_option() == FreqReadingOption::ReadFreq
+                    && scorer.has_block_max_scores()
+            }) {
+                // Block wand is only available iff we read frequencies,
+                // and iff the block-max scores are upper bounds of the scores.
                 return SpecializedScorer::TermUnion(scorers);
             } else {
                 return SpecializedScorer::Other(Box::new(Union::<_, TScoreCombiner>::from(
@@ -64,13 +67,19 @@ fn into_box_scorer<TScoreCombiner: ScoreCombiner>(scorer: SpecializedScorer) ->
 
 pub struct BooleanWeight {
     weights: Vec<(Occur, Box<dyn Weight>)>,
+    minimum_should_match: usize,
     scoring_enabled: bool,
 }
 
 impl BooleanWeight {
-    pub fn new(weights: Vec<(Occur, Box<dyn Weight>)>, scoring_enabled: bool) -> BooleanWeight {
+    pub fn new(
+        weights: Vec<(Occur, Box<dyn Weight>)>,
+        minimum_should_match: usize,
+        scoring_enabled: bool,
+    ) -> BooleanWeight {
         BooleanWeight {
             weights,
+            minimum_should_match,
             scoring_enabled,
         }
     }
@@ -98,6 +107,10 @@ impl BooleanWeight {
     ) -> crate::Result<SpecializedScorer> {
         let mut per_occur_scorers = self.per_occur_scorers(reader, boost)?;
 
+        if self.minimum_should_match > 0 {
+            return self.min_should_match_scorer::<TScoreCombiner>(per_occur_scorers);
+        }
+
         let should_scorer_opt: Option<SpecializedScorer> = per_occur_scorers
             .remove(&Occur::Should)
             .map(scorer_union::<TScoreCombiner>);
@@ -144,13 +157,47 @@ impl BooleanWeight {
             Ok(positive_scorer)
         }
     }
+
+    /// Builds the scorer when a minimum number of `Should` clauses is required.
+    ///
+    /// The `Should` clauses then behave as one more required clause.
+    fn min_should_match_scorer<TScoreCombiner: ScoreCombiner>(
+        &self,
+        mut per_occur_scorers: HashMap<Occur, Vec<Box<dyn Scorer>>>,
+    ) -> crate::Result<SpecializedScorer> {
+        let should_scorers = per_occur_scorers.remove(&Occur::Should).unwrap_or_default();
+        if should_scorers.len() < self.minimum_should_match {
+            return Ok(SpecializedScorer::Other(Box::new(EmptyScorer)));
+        }
+        let should_scorer: Box<dyn Scorer> = Box::new(MinShouldMatchScorer::<
+            Box<dyn Scorer>,
+            TScoreCombiner,
+        >::new(
+            should_scorers, self.minimum_should_match
+        ));
+        let mut required_scorers = per_occur_scorers.remove(&Occur::Must).unwrap_or_default();
+        required_scorers.push(should_scorer);
+        let positive_scorer = intersect_scorers(required_scorers);
+        if let Some(exclude_scorers) = per_occur_scorers.remove(&Occur::MustNot) {
+            let exclude_scorer =
+                into_box_scorer::<DoNothingCombiner>(scorer_union::<DoNothingCombiner>(
+                    exclude_scorers,
+                ));
+            Ok(SpecializedScorer::Other(Box::new(Exclude::new(
+                positive_scorer,
+                exclude_scorer,
+            ))))
+        } else {
+            Ok(SpecializedScorer::Other(positive_scorer))
+        }
+    }
 }
 
 impl Weight for BooleanWeight {
     fn scorer(&self, reader: &SegmentReader, boost: Score) -> crate::Result<Box<dyn Scorer>> {
         if self.weights.is_empty() {
             Ok(Box::new(EmptyScorer))
-        } else if self.weights.len() == 1 {
+        } else if self.weights.len() == 1 && self.minimum_should_match <= 1 {
             let &(occur, ref weight) = &self.weights[0];
             if occur == Occur::MustNot {
                 Ok(Box::new(EmptyScorer))
@@ -168,6 +215,23 @@ impl Weight for BooleanWeight {
         }
     }
 
+    fn count(&self, reader: &SegmentReader) -> crate::Result<u32> {
+        let mut scorer = self.scorer(reader, 1.0)?;
+        // The conjunctions of two terms are counted a block of postings at a time.
+        if let Some(intersection) =
+            scorer.downcast_mut::<Intersection<TermScorer, Box<dyn Scorer>>>()
+        {
+            if let Some(count) = intersection.count_term_pair(reader.delete_bitset()) {
+                return Ok(count);
+            }
+        }
+        if let Some(delete_bitset) = reader.delete_bitset() {
+            Ok(scorer.count(delete_bitset))
+        } else {
+            Ok(scorer.count_including_deleted())
+        }
+    }
+
     fn explain(&self, reader: &SegmentReader, doc: DocId) -> crate::Result<Explanation> {
         let mut scorer = self.scorer(reader, 1.0)?;
         if scorer.seek(doc) != doc {
@@ -178,12 +242,9 @@ impl Weight for BooleanWeight {
         }
 
         let mut explanation = Explanation::new("BooleanClause. Sum of ...", scorer.score());
-        for &(ref occur, ref subweight) in &self.weights {
-            if is_positive_occur(*occur) {
-                if let Ok(child_explanation) = subweight.explain(reader, doc) {
-                    explanation.add_detail(child_explanation);
-                }
-            }
+        for &(occur, ref subweight) in &self.weights {
+            let child_explanation_opt = subweight.explain(reader, doc).ok();
+            explanation.add_detail(clause_explanation(occur, child_explanation_opt));
         }
         Ok(explanation)
     }
@@ -242,3 +303,23 @@ fn is_positive_occur(occur: Occur) -> bool {
         Occur::MustNot => false,
     }
 }
+
+/// Explains the contribution of a clause to the score of a document,
+/// given the explanation of the clause if it matches the document.
+///
+/// The clauses that do not match are explained as well, with a contribution of 0.
+fn clause_explanation(occur: Occur, child_explanation_opt: Option<Explanation>) -> Explanation {
+    if let Some(child_explanation) = child_explanation_opt {
+        let contribution = if is_positive_occur(occur) {
+            child_explanation.value()
+        } else {
+            0.0
+        };
+        let mut explanation =
+            Explanation::new(format!("{:?} clause, matching", occur), contribution);
+        explanation.add_detail(child_explanation);
+        explanation
+    } else {
+        Explanation::new(format!("{:?} clause, not matching", occur), 0.0)
+    }
+}
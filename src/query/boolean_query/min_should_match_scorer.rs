@@ -0,0 +1,222 @@
+use crate::docset::{DocSet, TERMINATED};
+use crate::query::score_combiner::ScoreCombiner;
+use crate::query::Scorer;
+use crate::DocId;
+use crate::Score;
+use std::marker::PhantomData;
+
+/// Creates a `DocSet` that iterates through the documents matching
+/// at least `minimum_should_match` of the underlying `DocSet`s.
+///
+/// The score of a document is the combination of the scores
+/// of the scorers matching it.
+///
+/// For each document, all of the underlying scorers are visited:
+/// this is meant for a reasonably small number of clauses.
+pub struct MinShouldMatchScorer<TScorer, TScoreCombiner> {
+    scorers: Vec<TScorer>,
+    minimum_should_match: usize,
+    doc: DocId,
+    score: Score,
+    removed_docs_skipped: u64,
+    _phantom: PhantomData<TScoreCombiner>,
+}
+
+impl<TScorer, TScoreCombiner> MinShouldMatchScorer<TScorer, TScoreCombiner>
+where
+    TScorer: Scorer,
+    TScoreCombiner: ScoreCombiner,
+{
+    /// Creates a new `MinShouldMatchScorer`.
+    ///
+    /// `minimum_should_match` is expected to be greater than 0.
+    pub fn new(
+        scorers: Vec<TScorer>,
+        minimum_should_match: usize,
+    ) -> MinShouldMatchScorer<TScorer, TScoreCombiner> {
+        assert!(minimum_should_match > 0);
+        let scorers: Vec<TScorer> = scorers
+            .into_iter()
+            .filter(|scorer| scorer.doc() != TERMINATED)
+            .collect();
+        let mut min_should_match_scorer = MinShouldMatchScorer {
+            scorers,
+            minimum_should_match,
+            doc: 0,
+            score: 0.0,
+            removed_docs_skipped: 0u64,
+            _phantom: PhantomData,
+        };
+        min_should_match_scorer.find_next_match();
+        min_should_match_scorer
+    }
+
+    // Removes the scorers that have been entirely consumed,
+    // keeping track of the documents they skipped.
+    fn remove_terminated_scorers(&mut self) {
+        let removed_docs_skipped = &mut self.removed_docs_skipped;
+        self.scorers.retain(|scorer| {
+            if scorer.doc() == TERMINATED {
+                *removed_docs_skipped += scorer.docs_skipped();
+                return false;
+            }
+            true
+        });
+    }
+
+    /// Positions the scorer on the first document, among the documents on which
+    /// the underlying scorers are positioned, that is matched by
+    /// at least `minimum_should_match` scorers.
+    ///
+    /// The matching scorers are advanced past that document.
+    fn find_next_match(&mut self) -> DocId {
+        loop {
+            if self.scorers.len() < self.minimum_should_match {
+                self.doc = TERMINATED;
+                return TERMINATED;
+            }
+            let candidate = self
+                .scorers
+                .iter()
+                .map(|scorer| scorer.doc())
+                .min()
+                .unwrap_or(TERMINATED);
+            let mut score_combiner = TScoreCombiner::default();
+            let mut num_matches = 0;
+            for scorer in &mut self.scorers {
+                if scorer.doc() == candidate {
+                    score_combiner.update(scorer);
+                    num_matches += 1;
+                    scorer.advance();
+                }
+            }
+            self.remove_terminated_scorers();
+            if num_matches >= self.minimum_should_match {
+                self.doc = candidate;
+                self.score = score_combiner.score();
+                return candidate;
+            }
+        }
+    }
+}
+
+impl<TScorer, TScoreCombiner> DocSet for MinShouldMatchScorer<TScorer, TScoreCombiner>
+where
+    TScorer: Scorer,
+    TScoreCombiner: ScoreCombiner,
+{
+    fn advance(&mut self) -> DocId {
+        if self.doc == TERMINATED {
+            return TERMINATED;
+        }
+        self.find_next_match()
+    }
+
+    fn seek(&mut self, target: DocId) -> DocId {
+        if self.doc >= target {
+            return self.doc;
+        }
+        for scorer in &mut self.scorers {
+            if scorer.doc() < target {
+                scorer.seek(target);
+            }
+        }
+        self.remove_terminated_scorers();
+        self.find_next_match()
+    }
+
+    fn doc(&self) -> DocId {
+        self.doc
+    }
+
+    fn size_hint(&self) -> u32 {
+        self.scorers
+            .iter()
+            .map(|scorer| scorer.size_hint())
+            .max()
+            .unwrap_or(0u32)
+    }
+
+    fn docs_skipped(&self) -> u64 {
+        self.removed_docs_skipped
+            + self
+                .scorers
+                .iter()
+                .map(|scorer| scorer.docs_skipped())
+                .sum::<u64>()
+    }
+}
+
+impl<TScorer, TScoreCombiner> Scorer for MinShouldMatchScorer<TScorer, TScoreCombiner>
+where
+    TScorer: Scorer,
+    TScoreCombiner: ScoreCombiner,
+{
+    fn score(&mut self) -> Score {
+        self.score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MinShouldMatchScorer;
+    use crate::docset::{DocSet, TERMINATED};
+    use crate::query::score_combiner::{DoNothingCombiner, SumCombiner};
+    use crate::query::{ConstScorer, Scorer, VecDocSet};
+    use crate::DocId;
+
+    fn scorers(docs: &[&[DocId]]) -> Vec<ConstScorer<VecDocSet>> {
+        docs.iter()
+            .map(|docs| ConstScorer::new(VecDocSet::from(docs.to_vec()), 1.0))
+            .collect()
+    }
+
+    fn collect(docset: &mut dyn DocSet) -> Vec<DocId> {
+        let mut docs = vec![];
+        while docset.doc() != TERMINATED {
+            docs.push(docset.doc());
+            docset.advance();
+        }
+        docs
+    }
+
+    #[test]
+    fn test_min_should_match_scorer() {
+        let docs: &[&[DocId]] = &[&[1, 2, 3, 7], &[2, 3, 5, 7], &[3, 5, 7, 9]];
+        let expected_docs: &[(usize, &[DocId])] = &[
+            (1, &[1, 2, 3, 5, 7, 9]),
+            (2, &[2, 3, 5, 7]),
+            (3, &[3, 7]),
+            (4, &[]),
+        ];
+        for &(minimum_should_match, expected) in expected_docs {
+            let mut scorer = MinShouldMatchScorer::<_, DoNothingCombiner>::new(
+                scorers(docs),
+                minimum_should_match,
+            );
+            assert_eq!(&collect(&mut scorer)[..], expected);
+        }
+    }
+
+    #[test]
+    fn test_min_should_match_scorer_score() {
+        let docs: &[&[DocId]] = &[&[1, 2, 3], &[2, 3], &[3]];
+        let mut scorer = MinShouldMatchScorer::<_, SumCombiner>::new(scorers(docs), 2);
+        assert_eq!(scorer.doc(), 2);
+        assert_eq!(scorer.score(), 2.0);
+        assert_eq!(scorer.advance(), 3);
+        assert_eq!(scorer.score(), 3.0);
+        assert_eq!(scorer.advance(), TERMINATED);
+    }
+
+    #[test]
+    fn test_min_should_match_scorer_seek() {
+        let docs: &[&[DocId]] = &[&[1, 2, 3, 7, 10], &[2, 3, 5, 7, 10], &[3, 5, 7, 9]];
+        let mut scorer = MinShouldMatchScorer::<_, DoNothingCombiner>::new(scorers(docs), 2);
+        assert_eq!(scorer.seek(4), 5);
+        assert_eq!(scorer.seek(5), 5);
+        assert_eq!(scorer.seek(8), 10);
+        assert_eq!(scorer.advance(), TERMINATED);
+        assert_eq!(scorer.seek(11), TERMINATED);
+    }
+}
@@ -1,6 +1,7 @@
 mod block_wand;
 mod boolean_query;
 mod boolean_weight;
+mod min_should_match_scorer;
 
 pub(crate) use self::block_wand::block_wand;
 pub use self::boolean_query::BooleanQuery;
@@ -14,6 +15,7 @@ mod tests {
     use crate::collector::TopDocs;
     use crate::query::score_combiner::SumWithCoordsCombiner;
     use crate::query::term_query::TermScorer;
+    use crate::query::Explanation;
     use crate::query::Intersection;
     use crate::query::Occur;
     use crate::query::Query;
@@ -311,6 +313,127 @@ mod tests {
         let query = BooleanQuery::from(vec![(Occur::Should, term_a), (Occur::Should, term_b)]);
         let explanation = query.explain(&searcher, DocAddress(0, 0u32))?;
         assert_nearly_equals!(explanation.value(), 0.6931472);
+        let score_a = explanation.value();
+        let explanation = searcher.explain(&query, DocAddress(0, 1u32))?;
+        assert_nearly_equals!(explanation.value(), score_a);
+        assert_eq!(explanation.details().len(), 2);
+        assert_eq!(
+            explanation.details()[0].description(),
+            "Should clause, not matching"
+        );
+        assert_eq!(explanation.details()[0].value(), 0.0);
+        assert_eq!(
+            explanation.details()[1].description(),
+            "Should clause, matching"
+        );
+        assert_nearly_equals!(explanation.details()[1].value(), score_a);
+        assert!(explanation.details()[1].details()[0]
+            .description()
+            .starts_with("TermQuery"));
+        assert!(searcher.explain(&query, DocAddress(0, 2u32)).is_err());
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_explain_clauses() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text=>"a b"));
+        index_writer.add_document(doc!(text=>"a c d"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let term_query = |word: &str| -> Box<dyn Query> {
+            Box::new(TermQuery::new(
+                Term::from_field_text(text, word),
+                IndexRecordOption::WithFreqs,
+            ))
+        };
+        let sub_query: Box<dyn Query> = Box::new(BooleanQuery::new(vec![
+            (Occur::Should, term_query("b")),
+            (Occur::Should, term_query("c")),
+        ]));
+        let query = BooleanQuery::new(vec![
+            (Occur::Must, term_query("a")),
+            (Occur::Should, sub_query),
+            (Occur::MustNot, term_query("d")),
+        ]);
+        let explanation = Explanation::from_query(&searcher, &query, DocAddress(0, 0))?;
+        let clauses = explanation.details();
+        assert_eq!(clauses.len(), 3);
+        let contributions: Score = clauses.iter().map(Explanation::value).sum();
+        assert_nearly_equals!(contributions, explanation.value());
+
+        assert_eq!(clauses[0].description(), "Must clause, matching");
+        let term_a = &clauses[0].details()[0];
+        assert!(term_a.description().starts_with("TermQuery"));
+        assert!(term_a
+            .details()
+            .iter()
+            .any(|detail| detail.description().starts_with("idf")));
+
+        assert_eq!(clauses[1].description(), "Should clause, matching");
+        let sub_clauses = clauses[1].details()[0].details();
+        assert_eq!(sub_clauses.len(), 2);
+        assert_eq!(sub_clauses[0].description(), "Should clause, matching");
+        assert_eq!(sub_clauses[1].description(), "Should clause, not matching");
+        assert_nearly_equals!(sub_clauses[0].value(), clauses[1].value());
+
+        assert_eq!(clauses[2].description(), "MustNot clause, not matching");
+        assert_eq!(clauses[2].value(), 0.0);
+        assert!(Explanation::from_query(&searcher, &query, DocAddress(0, 1)).is_err());
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_boolean_query_count_term_pair() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", STRING);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_with_num_threads(1, 5_000_000)?;
+        for doc_id in 0u64..2_000 {
+            let mut doc = Document::default();
+            if doc_id % 2 == 0 {
+                doc.add_text(text, "even");
+            }
+            if doc_id % 3 == 0 {
+                doc.add_text(text, "triple");
+            }
+            if doc_id % 5 == 0 {
+                doc.add_text(text, "five");
+            }
+            index_writer.add_document(doc);
+        }
+        index_writer.commit()?;
+        let term_query = |text_value: &str| -> Box<dyn Query> {
+            Box::new(TermQuery::new(
+                Term::from_field_text(text, text_value),
+                IndexRecordOption::Basic,
+            ))
+        };
+        let two_terms = BooleanQuery::new(vec![
+            (Occur::Must, term_query("even")),
+            (Occur::Must, term_query("triple")),
+        ]);
+        let three_terms = BooleanQuery::new(vec![
+            (Occur::Must, term_query("even")),
+            (Occur::Must, term_query("triple")),
+            (Occur::Must, term_query("five")),
+        ]);
+        let searcher = index.reader()?.searcher();
+        assert_eq!(two_terms.count(&searcher)?, 334);
+        assert_eq!(three_terms.count(&searcher)?, 67);
+
+        index_writer.delete_term(Term::from_field_text(text, "five"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(two_terms.count(&searcher)?, 334 - 67);
+        assert_eq!(
+            searcher.search(&two_terms, &crate::collector::Count)?,
+            334 - 67
+        );
+        assert_eq!(three_terms.count(&searcher)?, 0);
         Ok(())
     }
 }
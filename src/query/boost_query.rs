@@ -1,5 +1,6 @@
 use crate::fastfield::DeleteBitSet;
 use crate::query::explanation::does_not_match;
+use crate::query::query::fmt_query;
 use crate::query::{Explanation, Query, Scorer, Weight};
 use crate::{DocId, DocSet, Score, Searcher, SegmentReader, Term};
 use std::collections::BTreeSet;
@@ -20,6 +21,16 @@ impl BoostQuery {
     pub fn new(query: Box<dyn Query>, boost: Score) -> BoostQuery {
         BoostQuery { query, boost }
     }
+
+    /// Returns the underlying query.
+    pub fn query(&self) -> &dyn Query {
+        self.query.as_ref()
+    }
+
+    /// Returns the boost factor.
+    pub fn boost(&self) -> Score {
+        self.boost
+    }
 }
 
 impl Clone for BoostQuery {
@@ -37,6 +48,15 @@ impl fmt::Debug for BoostQuery {
     }
 }
 
+/// Displays the query like `(0:coffee)^2`.
+impl fmt::Display for BoostQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "(")?;
+        fmt_query(self.query.as_ref(), f)?;
+        write!(f, ")^{}", self.boost)
+    }
+}
+
 impl Query for BoostQuery {
     fn weight(&self, searcher: &Searcher, scoring_enabled: bool) -> crate::Result<Box<dyn Weight>> {
         let weight_without_boost = self.query.weight(searcher, scoring_enabled)?;
@@ -51,6 +71,15 @@ impl Query for BoostQuery {
     fn query_terms(&self, term_set: &mut BTreeSet<Term>) {
         self.query.query_terms(term_set)
     }
+
+    fn normalize(&self) -> Box<dyn Query> {
+        Box::new(BoostQuery::new(self.query.normalize(), self.boost))
+    }
+
+    fn cache_key(&self) -> Option<String> {
+        let query_key = self.query.cache_key()?;
+        Some(format!("BoostQuery({:?},{:?})", query_key, self.boost))
+    }
 }
 
 pub(crate) struct BoostWeight {
@@ -118,6 +147,10 @@ impl<S: Scorer> DocSet for BoostScorer<S> {
         self.underlying.size_hint()
     }
 
+    fn docs_skipped(&self) -> u64 {
+        self.underlying.docs_skipped()
+    }
+
     fn count(&mut self, delete_bitset: &DeleteBitSet) -> u32 {
         self.underlying.count(delete_bitset)
     }
@@ -136,9 +169,10 @@ impl<S: Scorer> Scorer for BoostScorer<S> {
 #[cfg(test)]
 mod tests {
     use super::BoostQuery;
-    use crate::query::{AllQuery, Query};
-    use crate::schema::Schema;
-    use crate::{DocAddress, Document, Index};
+    use crate::collector::TopDocs;
+    use crate::query::{AllQuery, BooleanQuery, Occur, Query, TermQuery};
+    use crate::schema::{IndexRecordOption, Schema, TEXT};
+    use crate::{DocAddress, Document, Index, Term};
 
     #[test]
     fn test_boost_query_explain() {
@@ -156,4 +190,46 @@ mod tests {
             "{\n  \"value\": 0.2,\n  \"description\": \"Boost x0.2 of ...\",\n  \"details\": [\n    {\n      \"value\": 1.0,\n      \"description\": \"AllQuery\",\n      \"context\": []\n    }\n  ],\n  \"context\": []\n}"
         )
     }
+
+    #[test]
+    fn test_boost_query_in_boolean_query() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let body = schema_builder.add_text_field("body", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title => "coffee", body => "tea"));
+        index_writer.add_document(doc!(title => "tea", body => "coffee"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let term_query = |field| -> Box<dyn Query> {
+            Box::new(TermQuery::new(
+                Term::from_field_text(field, "coffee"),
+                IndexRecordOption::Basic,
+            ))
+        };
+        let top_docs = |query: &dyn Query| searcher.search(query, &TopDocs::with_limit(2));
+        let unboosted = BooleanQuery::from(vec![
+            (Occur::Should, term_query(title)),
+            (Occur::Should, term_query(body)),
+        ]);
+        let boosted = BooleanQuery::from(vec![
+            (
+                Occur::Should,
+                Box::new(BoostQuery::new(term_query(title), 3.0)) as Box<dyn Query>,
+            ),
+            (Occur::Should, term_query(body)),
+        ]);
+        let unboosted_top_docs = top_docs(&unboosted)?;
+        let boosted_top_docs = top_docs(&boosted)?;
+        assert_eq!(boosted_top_docs[0].1, DocAddress(0, 0));
+        assert_eq!(boosted_top_docs[1].1, DocAddress(0, 1));
+        let unboosted_title_score = unboosted_top_docs
+            .iter()
+            .find(|(_, doc_address)| *doc_address == DocAddress(0, 0))
+            .unwrap()
+            .0;
+        assert!((boosted_top_docs[0].0 - 3.0 * unboosted_title_score).abs() < 0.0001);
+        Ok(())
+    }
 }
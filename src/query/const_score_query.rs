@@ -0,0 +1,97 @@
+use crate::query::explanation::does_not_match;
+use crate::query::{ConstScorer, Explanation, Query, Scorer, Weight};
+use crate::{DocId, Score, Searcher, SegmentReader, Term};
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// `ConstScoreQuery` is a wrapper over a query giving the same score
+/// to all of the documents it matches.
+///
+/// The document set matched by the `ConstScoreQuery` is strictly the same as the
+/// underlying query. The underlying query is run without scoring, which makes
+/// it possible for it to use a cheaper code path, and its documents all get the
+/// score `score`.
+///
+/// It is typically used, with a score of `0`, for the clauses of a
+/// `BooleanQuery` acting as filters.
+pub struct ConstScoreQuery {
+    query: Box<dyn Query>,
+    score: Score,
+}
+
+impl ConstScoreQuery {
+    /// Builds a const score query.
+    pub fn new(query: Box<dyn Query>, score: Score) -> ConstScoreQuery {
+        ConstScoreQuery { query, score }
+    }
+
+    /// Returns the underlying query.
+    pub fn query(&self) -> &dyn Query {
+        self.query.as_ref()
+    }
+
+    /// Returns the score of the matched documents.
+    pub fn score(&self) -> Score {
+        self.score
+    }
+}
+
+impl Clone for ConstScoreQuery {
+    fn clone(&self) -> Self {
+        ConstScoreQuery {
+            query: self.query.box_clone(),
+            score: self.score,
+        }
+    }
+}
+
+impl fmt::Debug for ConstScoreQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Const(query={:?}, score={})", self.query, self.score)
+    }
+}
+
+impl Query for ConstScoreQuery {
+    fn weight(&self, searcher: &Searcher, scoring_enabled: bool) -> crate::Result<Box<dyn Weight>> {
+        let weight_without_scores = self.query.weight(searcher, false)?;
+        if !scoring_enabled {
+            return Ok(weight_without_scores);
+        }
+        Ok(Box::new(ConstScoreWeight {
+            weight: weight_without_scores,
+            score: self.score,
+        }))
+    }
+
+    fn query_terms(&self, term_set: &mut BTreeSet<Term>) {
+        self.query.query_terms(term_set)
+    }
+
+    fn normalize(&self) -> Box<dyn Query> {
+        Box::new(ConstScoreQuery::new(self.query.normalize(), self.score))
+    }
+}
+
+struct ConstScoreWeight {
+    weight: Box<dyn Weight>,
+    score: Score,
+}
+
+impl Weight for ConstScoreWeight {
+    fn scorer(&self, reader: &SegmentReader, boost: Score) -> crate::Result<Box<dyn Scorer>> {
+        let scorer = self.weight.scorer(reader, 1.0)?;
+        Ok(Box::new(ConstScorer::new(scorer, self.score * boost)))
+    }
+
+    fn explain(&self, reader: &SegmentReader, doc: DocId) -> crate::Result<Explanation> {
+        let mut scorer = self.weight.scorer(reader, 1.0)?;
+        if scorer.seek(doc) != doc {
+            return Err(does_not_match(doc));
+        }
+        Ok(Explanation::new("ConstScoreQuery", self.score))
+    }
+
+    fn count(&self, reader: &SegmentReader) -> crate::Result<u32> {
+        self.weight.count(reader)
+    }
+}
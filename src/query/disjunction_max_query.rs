@@ -0,0 +1,386 @@
+use crate::docset::{DocSet, TERMINATED};
+use crate::query::explanation::does_not_match;
+use crate::query::{Explanation, Query, Scorer, Weight};
+use crate::{DocId, Score, Searcher, SegmentReader, Term};
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// `DisjunctionMaxQuery` matches the union of the documents matched by its subqueries.
+///
+/// Contrary to a `BooleanQuery` made of `Should` clauses, the score of a document is not
+/// the sum of the scores of the matching subqueries, but their maximum,
+/// plus `tie_breaker` times the sum of the scores of the other matching subqueries.
+///
+/// This is typically useful when searching the same terms over several fields:
+/// a document matching all of the terms in its title should rank better than a document matching
+/// the same term in its title and its body.
+///
+/// A `tie_breaker` of 0.0 only keeps the best score. A `tie_breaker` of 1.0 sums the scores.
+///
+/// ```rust
+/// use tantivy::collector::TopDocs;
+/// use tantivy::doc;
+/// use tantivy::query::{DisjunctionMaxQuery, Query, TermQuery};
+/// use tantivy::schema::{IndexRecordOption, Schema, TEXT};
+/// use tantivy::{Index, Term};
+///
+/// # fn main() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let title = schema_builder.add_text_field("title", TEXT);
+/// let body = schema_builder.add_text_field("body", TEXT);
+/// let index = Index::create_in_ram(schema_builder.build());
+/// let mut index_writer = index.writer(3_000_000)?;
+/// index_writer.add_document(doc!(title => "The Diary of a Young Girl", body => "girl"));
+/// index_writer.add_document(doc!(title => "The Diary of Muadib"));
+/// index_writer.commit()?;
+///
+/// let searcher = index.reader()?.searcher();
+/// let term_query = |field, text| -> Box<dyn Query> {
+///     Box::new(TermQuery::new(Term::from_field_text(field, text), IndexRecordOption::WithFreqs))
+/// };
+/// let query = DisjunctionMaxQuery::with_tie_breaker(
+///     vec![term_query(title, "girl"), term_query(body, "girl")],
+///     0.1,
+/// );
+/// let top_docs = searcher.search(&query, &TopDocs::with_limit(2))?;
+/// assert_eq!(top_docs.len(), 1);
+/// # Ok(())
+/// # }
+/// ```
+pub struct DisjunctionMaxQuery {
+    disjuncts: Vec<Box<dyn Query>>,
+    tie_breaker: Score,
+}
+
+impl DisjunctionMaxQuery {
+    /// Creates a new `DisjunctionMaxQuery` with a `tie_breaker` of 0.0.
+    pub fn new(disjuncts: Vec<Box<dyn Query>>) -> DisjunctionMaxQuery {
+        DisjunctionMaxQuery::with_tie_breaker(disjuncts, 0.0)
+    }
+
+    /// Creates a new `DisjunctionMaxQuery` with the given `tie_breaker`.
+    pub fn with_tie_breaker(
+        disjuncts: Vec<Box<dyn Query>>,
+        tie_breaker: Score,
+    ) -> DisjunctionMaxQuery {
+        DisjunctionMaxQuery {
+            disjuncts,
+            tie_breaker,
+        }
+    }
+
+    /// Returns the subqueries of the `DisjunctionMaxQuery`.
+    pub fn disjuncts(&self) -> &[Box<dyn Query>] {
+        &self.disjuncts[..]
+    }
+
+    /// Returns the tie breaker of the `DisjunctionMaxQuery`.
+    pub fn tie_breaker(&self) -> Score {
+        self.tie_breaker
+    }
+}
+
+impl Clone for DisjunctionMaxQuery {
+    fn clone(&self) -> Self {
+        DisjunctionMaxQuery {
+            disjuncts: self
+                .disjuncts
+                .iter()
+                .map(|query| query.box_clone())
+                .collect(),
+            tie_breaker: self.tie_breaker,
+        }
+    }
+}
+
+impl fmt::Debug for DisjunctionMaxQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "DisjunctionMax(disjuncts={:?}, tie_breaker={})",
+            self.disjuncts, self.tie_breaker
+        )
+    }
+}
+
+impl Query for DisjunctionMaxQuery {
+    fn weight(&self, searcher: &Searcher, scoring_enabled: bool) -> crate::Result<Box<dyn Weight>> {
+        let weights = self
+            .disjuncts
+            .iter()
+            .map(|query| query.weight(searcher, scoring_enabled))
+            .collect::<crate::Result<Vec<_>>>()?;
+        Ok(Box::new(DisjunctionMaxWeight {
+            weights,
+            tie_breaker: self.tie_breaker,
+        }))
+    }
+
+    fn query_terms(&self, term_set: &mut BTreeSet<Term>) {
+        for query in &self.disjuncts {
+            query.query_terms(term_set);
+        }
+    }
+}
+
+pub(crate) struct DisjunctionMaxWeight {
+    weights: Vec<Box<dyn Weight>>,
+    tie_breaker: Score,
+}
+
+impl DisjunctionMaxWeight {
+    pub(crate) fn new(weights: Vec<Box<dyn Weight>>, tie_breaker: Score) -> DisjunctionMaxWeight {
+        DisjunctionMaxWeight {
+            weights,
+            tie_breaker,
+        }
+    }
+}
+
+impl Weight for DisjunctionMaxWeight {
+    fn scorer(&self, reader: &SegmentReader, boost: Score) -> crate::Result<Box<dyn Scorer>> {
+        let scorers = self
+            .weights
+            .iter()
+            .map(|weight| weight.scorer(reader, boost))
+            .collect::<crate::Result<Vec<_>>>()?;
+        Ok(Box::new(DisjunctionMaxScorer::new(
+            scorers,
+            self.tie_breaker,
+        )))
+    }
+
+    fn explain(&self, reader: &SegmentReader, doc: DocId) -> crate::Result<Explanation> {
+        let mut scorer = self.scorer(reader, 1.0)?;
+        if scorer.seek(doc) != doc {
+            return Err(does_not_match(doc));
+        }
+        let mut explanation = Explanation::new(
+            format!(
+                "DisjunctionMax. Max plus {} times the others of ...",
+                self.tie_breaker
+            ),
+            scorer.score(),
+        );
+        for weight in &self.weights {
+            if let Ok(child_explanation) = weight.explain(reader, doc) {
+                explanation.add_detail(child_explanation);
+            }
+        }
+        Ok(explanation)
+    }
+}
+
+/// Scorer iterating through the union of the documents of its underlying scorers.
+///
+/// The score of a document is the maximum of the scores of the scorers
+/// matching it, plus `tie_breaker` times the sum of the other scores.
+struct DisjunctionMaxScorer<TScorer> {
+    scorers: Vec<TScorer>,
+    tie_breaker: Score,
+    doc: DocId,
+    score: Score,
+    removed_docs_skipped: u64,
+}
+
+impl<TScorer: Scorer> DisjunctionMaxScorer<TScorer> {
+    fn new(scorers: Vec<TScorer>, tie_breaker: Score) -> DisjunctionMaxScorer<TScorer> {
+        let scorers: Vec<TScorer> = scorers
+            .into_iter()
+            .filter(|scorer| scorer.doc() != TERMINATED)
+            .collect();
+        let mut disjunction_max_scorer = DisjunctionMaxScorer {
+            scorers,
+            tie_breaker,
+            doc: 0,
+            score: 0.0,
+            removed_docs_skipped: 0u64,
+        };
+        disjunction_max_scorer.find_next_match();
+        disjunction_max_scorer
+    }
+
+    // Removes the scorers that have been entirely consumed,
+    // keeping track of the documents they skipped.
+    fn remove_terminated_scorers(&mut self) {
+        let removed_docs_skipped = &mut self.removed_docs_skipped;
+        self.scorers.retain(|scorer| {
+            if scorer.doc() == TERMINATED {
+                *removed_docs_skipped += scorer.docs_skipped();
+                return false;
+            }
+            true
+        });
+    }
+
+    /// Positions the scorer on the smallest document on which the underlying scorers
+    /// are positioned, and advances those scorers past that document.
+    fn find_next_match(&mut self) -> DocId {
+        let candidate = self
+            .scorers
+            .iter()
+            .map(|scorer| scorer.doc())
+            .min()
+            .unwrap_or(TERMINATED);
+        self.doc = candidate;
+        if candidate == TERMINATED {
+            return TERMINATED;
+        }
+        let mut max_score: Score = 0.0;
+        let mut sum_score: Score = 0.0;
+        for scorer in &mut self.scorers {
+            if scorer.doc() == candidate {
+                let score = scorer.score();
+                max_score = max_score.max(score);
+                sum_score += score;
+                scorer.advance();
+            }
+        }
+        self.remove_terminated_scorers();
+        self.score = max_score + self.tie_breaker * (sum_score - max_score);
+        candidate
+    }
+}
+
+impl<TScorer: Scorer> DocSet for DisjunctionMaxScorer<TScorer> {
+    fn advance(&mut self) -> DocId {
+        if self.doc == TERMINATED {
+            return TERMINATED;
+        }
+        self.find_next_match()
+    }
+
+    fn seek(&mut self, target: DocId) -> DocId {
+        if self.doc >= target {
+            return self.doc;
+        }
+        for scorer in &mut self.scorers {
+            if scorer.doc() < target {
+                scorer.seek(target);
+            }
+        }
+        self.remove_terminated_scorers();
+        self.find_next_match()
+    }
+
+    fn doc(&self) -> DocId {
+        self.doc
+    }
+
+    fn size_hint(&self) -> u32 {
+        self.scorers
+            .iter()
+            .map(|scorer| scorer.size_hint())
+            .max()
+            .unwrap_or(0u32)
+    }
+
+    fn docs_skipped(&self) -> u64 {
+        self.removed_docs_skipped
+            + self
+                .scorers
+                .iter()
+                .map(|scorer| scorer.docs_skipped())
+                .sum::<u64>()
+    }
+}
+
+impl<TScorer: Scorer> Scorer for DisjunctionMaxScorer<TScorer> {
+    fn score(&mut self) -> Score {
+        self.score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DisjunctionMaxQuery, DisjunctionMaxScorer};
+    use crate::assert_nearly_equals;
+    use crate::collector::TopDocs;
+    use crate::docset::{DocSet, TERMINATED};
+    use crate::query::{BooleanQuery, ConstScorer, Occur, Query, Scorer, TermQuery, VecDocSet};
+    use crate::schema::{IndexRecordOption, Schema, TEXT};
+    use crate::{DocAddress, Index, Term};
+
+    #[test]
+    fn test_disjunction_max_scorer() {
+        let scorers = vec![
+            ConstScorer::new(VecDocSet::from(vec![1, 3, 5]), 1.0),
+            ConstScorer::new(VecDocSet::from(vec![3, 4]), 2.0),
+            ConstScorer::new(VecDocSet::from(vec![3, 5, 8]), 4.0),
+        ];
+        let mut scorer = DisjunctionMaxScorer::new(scorers, 0.5);
+        let mut docs_scores = vec![];
+        while scorer.doc() != TERMINATED {
+            docs_scores.push((scorer.doc(), scorer.score()));
+            scorer.advance();
+        }
+        assert_eq!(
+            docs_scores,
+            vec![(1, 1.0), (3, 5.5), (4, 2.0), (5, 4.5), (8, 4.0)]
+        );
+    }
+
+    #[test]
+    fn test_disjunction_max_scorer_seek() {
+        let scorers = vec![
+            ConstScorer::new(VecDocSet::from(vec![1, 3, 5]), 1.0),
+            ConstScorer::new(VecDocSet::from(vec![3, 4]), 2.0),
+        ];
+        let mut scorer = DisjunctionMaxScorer::new(scorers, 0.0);
+        assert_eq!(scorer.seek(2), 3);
+        assert_eq!(scorer.score(), 2.0);
+        assert_eq!(scorer.seek(5), 5);
+        assert_eq!(scorer.advance(), TERMINATED);
+    }
+
+    #[test]
+    fn test_disjunction_max_query() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let body = schema_builder.add_text_field("body", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title => "a b", body => "c"));
+        index_writer.add_document(doc!(title => "a", body => "a"));
+        index_writer.add_document(doc!(title => "c"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let term_query = |field, text| -> Box<dyn Query> {
+            Box::new(TermQuery::new(
+                Term::from_field_text(field, text),
+                IndexRecordOption::WithFreqs,
+            ))
+        };
+        let disjuncts = || vec![term_query(title, "a"), term_query(body, "a")];
+        let title_score = searcher.search(&*term_query(title, "a"), &TopDocs::with_limit(2))?;
+        let body_score = searcher.search(&*term_query(body, "a"), &TopDocs::with_limit(1))?[0].0;
+        let title_score_doc_1 = title_score
+            .iter()
+            .find(|(_, doc_address)| *doc_address == DocAddress(0, 1))
+            .unwrap()
+            .0;
+        let query = DisjunctionMaxQuery::with_tie_breaker(disjuncts(), 0.1);
+        let mut top_docs = searcher.search(&query, &TopDocs::with_limit(3))?;
+        top_docs.sort_by_key(|(_, doc_address)| *doc_address);
+        assert_eq!(top_docs.len(), 2);
+        assert_eq!(top_docs[1].1, DocAddress(0, 1));
+        assert_nearly_equals!(
+            top_docs[1].0,
+            title_score_doc_1.max(body_score) + 0.1 * title_score_doc_1.min(body_score)
+        );
+        let explanation = query.explain(&searcher, DocAddress(0, 1))?;
+        assert_nearly_equals!(explanation.value(), top_docs[1].0);
+        assert_eq!(explanation.details().len(), 2);
+        assert!(query.explain(&searcher, DocAddress(0, 2)).is_err());
+
+        // Composable within a `BooleanQuery`
+        let boolean_query = BooleanQuery::new(vec![
+            (Occur::Must, Box::new(DisjunctionMaxQuery::new(disjuncts()))),
+            (Occur::MustNot, term_query(body, "a")),
+        ]);
+        let top_docs = searcher.search(&boolean_query, &TopDocs::with_limit(3))?;
+        assert_eq!(top_docs.len(), 1);
+        assert_eq!(top_docs[0].1, DocAddress(0, 0));
+        Ok(())
+    }
+}
@@ -78,6 +78,10 @@ where
     fn size_hint(&self) -> u32 {
         self.underlying_docset.size_hint()
     }
+
+    fn docs_skipped(&self) -> u64 {
+        self.underlying_docset.docs_skipped() + self.excluding_docset.docs_skipped()
+    }
 }
 
 impl<TScorer, TDocSetExclude> Scorer for Exclude<TScorer, TDocSetExclude>
@@ -0,0 +1,283 @@
+use crate::common::BitSet;
+use crate::core::Searcher;
+use crate::core::SegmentReader;
+use crate::query::explanation::does_not_match;
+use crate::query::{AllQuery, BooleanQuery, ConstScorer, Occur};
+use crate::query::{BitSetDocSet, Explanation};
+use crate::query::{Query, Scorer, Weight};
+use crate::schema::{Cardinality, Field, FieldType, IndexRecordOption};
+use crate::{DocId, Score, TantivyError};
+
+/// `ExistsQuery` matches all documents having at least one value for a given field.
+///
+/// Matched documents all get a constant `Score` of one.
+///
+/// The field must either be indexed, or be a multivalued fast field:
+/// - for indexed fields, the documents of all of the terms of the field are matched.
+///   Documents whose values do not produce any token (e.g. an empty text) are not matched.
+/// - for multivalued fast fields, the documents with at least one value are matched.
+///
+/// Single-valued fast fields that are not indexed are not supported, as documents
+/// without a value get a default value in their fast field.
+///
+/// The query parser supports this query with the `_exists_:fieldname` syntax.
+///
+/// # Example
+///
+/// ```rust
+/// use tantivy::collector::Count;
+/// use tantivy::query::{ExistsQuery, MissingQuery};
+/// use tantivy::schema::{Schema, INDEXED};
+/// use tantivy::{doc, Index};
+/// # fn test() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let price = schema_builder.add_u64_field("price", INDEXED);
+/// let index = Index::create_in_ram(schema_builder.build());
+/// let mut index_writer = index.writer_with_num_threads(1, 10_000_000)?;
+/// index_writer.add_document(doc!(price => 3u64));
+/// index_writer.add_document(doc!());
+/// index_writer.commit()?;
+///
+/// let searcher = index.reader()?.searcher();
+/// assert_eq!(searcher.search(&ExistsQuery::new(price), &Count)?, 1);
+/// assert_eq!(searcher.search(&MissingQuery::new(price), &Count)?, 1);
+/// Ok(())
+/// # }
+/// # assert!(test().is_ok());
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExistsQuery {
+    field: Field,
+}
+
+impl ExistsQuery {
+    /// Creates a new `ExistsQuery` over the given field.
+    pub fn new(field: Field) -> ExistsQuery {
+        ExistsQuery { field }
+    }
+
+    /// Field to search over
+    pub fn field(&self) -> Field {
+        self.field
+    }
+}
+
+impl Query for ExistsQuery {
+    fn weight(
+        &self,
+        searcher: &Searcher,
+        _scoring_enabled: bool,
+    ) -> crate::Result<Box<dyn Weight>> {
+        let field_entry = searcher.schema().get_field_entry(self.field);
+        let source = if field_entry.is_indexed() {
+            ExistsSource::Postings
+        } else if is_multivalued_fast_field(field_entry.field_type()) {
+            ExistsSource::MultiValuedFastField
+        } else {
+            return Err(TantivyError::SchemaError(format!(
+                "Field {:?} is neither indexed nor a multivalued fast field.",
+                field_entry.name()
+            )));
+        };
+        Ok(Box::new(ExistsWeight {
+            field: self.field,
+            source,
+        }))
+    }
+}
+
+fn is_multivalued_fast_field(field_type: &FieldType) -> bool {
+    match field_type {
+        FieldType::U64(options)
+        | FieldType::I64(options)
+        | FieldType::F64(options)
+        | FieldType::Date(options) => {
+            options.get_fastfield_cardinality() == Some(Cardinality::MultiValues)
+        }
+        _ => false,
+    }
+}
+
+enum ExistsSource {
+    Postings,
+    MultiValuedFastField,
+}
+
+struct ExistsWeight {
+    field: Field,
+    source: ExistsSource,
+}
+
+impl Weight for ExistsWeight {
+    fn scorer(&self, reader: &SegmentReader, boost: Score) -> crate::Result<Box<dyn Scorer>> {
+        let max_doc = reader.max_doc();
+        let mut doc_bitset = BitSet::with_max_value(max_doc);
+        match self.source {
+            ExistsSource::Postings => {
+                let inverted_index = reader.inverted_index(self.field)?;
+                let mut term_stream = inverted_index.terms().stream()?;
+                while term_stream.advance() {
+                    let mut block_segment_postings = inverted_index
+                        .read_block_postings_from_terminfo(
+                            term_stream.value(),
+                            IndexRecordOption::Basic,
+                        )?;
+                    loop {
+                        let docs = block_segment_postings.docs();
+                        if docs.is_empty() {
+                            break;
+                        }
+                        for &doc in docs {
+                            doc_bitset.insert(doc);
+                        }
+                        block_segment_postings.advance();
+                    }
+                }
+            }
+            ExistsSource::MultiValuedFastField => {
+                let fast_field_reader = reader
+                    .fast_fields()
+                    .typed_fast_field_multi_reader::<u64>(self.field)?;
+                for doc in 0..max_doc {
+                    if fast_field_reader.num_vals(doc) > 0 {
+                        doc_bitset.insert(doc);
+                    }
+                }
+            }
+        }
+        let doc_bitset = BitSetDocSet::from(doc_bitset);
+        Ok(Box::new(ConstScorer::new(doc_bitset, boost)))
+    }
+
+    fn explain(&self, reader: &SegmentReader, doc: DocId) -> crate::Result<Explanation> {
+        let mut scorer = self.scorer(reader, 1.0)?;
+        if scorer.seek(doc) != doc {
+            return Err(does_not_match(doc));
+        }
+        Ok(Explanation::new("ExistsQuery", 1.0))
+    }
+}
+
+/// `MissingQuery` matches all documents without any value for a given field.
+///
+/// It is the complement of the [`ExistsQuery`](./struct.ExistsQuery.html) over the same field,
+/// and has the same requirements on the field.
+///
+/// Matched documents all get a constant `Score` of one.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MissingQuery {
+    field: Field,
+}
+
+impl MissingQuery {
+    /// Creates a new `MissingQuery` over the given field.
+    pub fn new(field: Field) -> MissingQuery {
+        MissingQuery { field }
+    }
+
+    /// Field to search over
+    pub fn field(&self) -> Field {
+        self.field
+    }
+}
+
+impl Query for MissingQuery {
+    fn weight(&self, searcher: &Searcher, scoring_enabled: bool) -> crate::Result<Box<dyn Weight>> {
+        let all_query: Box<dyn Query> = Box::new(AllQuery);
+        let exists_query: Box<dyn Query> = Box::new(ExistsQuery::new(self.field));
+        BooleanQuery::new(vec![
+            (Occur::Must, all_query),
+            (Occur::MustNot, exists_query),
+        ])
+        .weight(searcher, scoring_enabled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ExistsQuery, MissingQuery};
+    use crate::collector::{Count, DocSetCollector};
+    use crate::query::{Query, QueryParser};
+    use crate::schema::{Cardinality, IntOptions, Schema, FAST, STRING, TEXT};
+    use crate::{DocAddress, Index, TantivyError};
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_exists_and_missing_queries() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let sku = schema_builder.add_text_field("sku", STRING);
+        let tags = schema_builder.add_u64_field(
+            "tags",
+            IntOptions::default().set_fast(Cardinality::MultiValues),
+        );
+        let price = schema_builder.add_u64_field("price", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title => "a", sku => "x", tags => 1u64, tags => 2u64));
+        index_writer.add_document(doc!(title => "b", price => 3u64));
+        index_writer.commit()?;
+        index_writer.add_document(doc!(sku => "y", tags => 3u64));
+        index_writer.add_document(doc!(price => 4u64));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let docs = |query: &dyn Query| -> crate::Result<HashSet<DocAddress>> {
+            searcher.search(query, &DocSetCollector)
+        };
+        let addresses = |addresses: &[(u32, u32)]| -> HashSet<DocAddress> {
+            addresses
+                .iter()
+                .map(|&(segment_ord, doc)| DocAddress(segment_ord, doc))
+                .collect()
+        };
+        assert_eq!(
+            docs(&ExistsQuery::new(title))?,
+            addresses(&[(0, 0), (0, 1)])
+        );
+        assert_eq!(
+            docs(&MissingQuery::new(title))?,
+            addresses(&[(1, 0), (1, 1)])
+        );
+        assert_eq!(docs(&ExistsQuery::new(sku))?, addresses(&[(0, 0), (1, 0)]));
+        assert_eq!(docs(&MissingQuery::new(sku))?, addresses(&[(0, 1), (1, 1)]));
+        assert_eq!(docs(&ExistsQuery::new(tags))?, addresses(&[(0, 0), (1, 0)]));
+        assert_eq!(
+            docs(&MissingQuery::new(tags))?,
+            addresses(&[(0, 1), (1, 1)])
+        );
+        assert!(matches!(
+            searcher.search(&ExistsQuery::new(price), &Count),
+            Err(TantivyError::SchemaError(_))
+        ));
+
+        let query_parser = QueryParser::for_index(&index, vec![title]);
+        let query = query_parser.parse_query("_exists_:sku")?;
+        assert_eq!(docs(query.as_ref())?, addresses(&[(0, 0), (1, 0)]));
+        let query = query_parser.parse_query("* -_exists_:sku")?;
+        assert_eq!(docs(query.as_ref())?, addresses(&[(0, 1), (1, 1)]));
+        let query = query_parser.parse_query("a OR _exists_:tags")?;
+        assert_eq!(docs(query.as_ref())?, addresses(&[(0, 0), (1, 0)]));
+        assert!(query_parser.parse_query("_exists_:unknown").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_exists_query_with_deletes() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let sku = schema_builder.add_text_field("sku", STRING);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(sku => "x"));
+        index_writer.add_document(doc!(sku => "y"));
+        index_writer.add_document(doc!());
+        index_writer.commit()?;
+        index_writer.delete_term(crate::Term::from_field_text(sku, "x"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.search(&ExistsQuery::new(sku), &Count)?, 1);
+        assert_eq!(searcher.search(&MissingQuery::new(sku), &Count)?, 1);
+        Ok(())
+    }
+}
@@ -1,5 +1,7 @@
-use crate::{DocId, Score, TantivyError};
+use crate::query::Query;
+use crate::{DocAddress, DocId, Score, Searcher, TantivyError};
 use serde::Serialize;
+use serde_json::Value as JsonValue;
 use std::fmt;
 
 pub(crate) fn does_not_match(doc: DocId) -> TantivyError {
@@ -11,6 +13,11 @@ pub(crate) fn does_not_match(doc: DocId) -> TantivyError {
 ///
 /// `.to_pretty_json()` can be useful to print out a human readable
 /// representation of this tree when debugging a given score.
+/// `Display` renders it as an indented tree, with one node per line.
+///
+/// The explanation of a `BooleanQuery` has one child per clause, describing
+/// its `Occur`, whether it matches the document and its contribution to the score,
+/// with the explanation of the clause query as a child.
 #[derive(Clone, Serialize)]
 pub struct Explanation {
     value: Score,
@@ -26,6 +33,12 @@ impl fmt::Debug for Explanation {
     }
 }
 
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
 impl Explanation {
     /// Creates a new explanation object.
     pub fn new<T: ToString>(description: T, value: Score) -> Explanation {
@@ -37,11 +50,33 @@ impl Explanation {
         }
     }
 
+    /// Returns the explanation of the score of the document `doc_address` for `query`.
+    ///
+    /// This is the same as [`Searcher::explain`](../struct.Searcher.html#method.explain):
+    /// it returns an error if the document does not match the query.
+    pub fn from_query(
+        searcher: &Searcher,
+        query: &dyn Query,
+        doc_address: DocAddress,
+    ) -> crate::Result<Explanation> {
+        searcher.explain(query, doc_address)
+    }
+
     /// Returns the value associated to the current node.
     pub fn value(&self) -> Score {
         self.value
     }
 
+    /// Returns the description of the current node.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Returns the details (the children) of the current node.
+    pub fn details(&self) -> &[Explanation] {
+        &self.details
+    }
+
     /// Add some detail, explaining some part of the current node formula.
     ///
     /// Details are treated as child of the current node.
@@ -63,4 +98,83 @@ impl Explanation {
     pub fn to_pretty_json(&self) -> String {
         serde_json::to_string_pretty(self).unwrap()
     }
+
+    /// Returns the explanation tree in the format of the response of the
+    /// Elasticsearch `_explain` API, so that it can be displayed by the same tools.
+    ///
+    /// Each node is an object with a `value`, a `description` and a `details` array.
+    /// The format has no room for the context of a node: it is appended to its
+    /// description, between parentheses.
+    pub fn to_json(&self) -> JsonValue {
+        let mut description = self.description.clone();
+        for context in &self.context {
+            description.push_str(&format!(" ({})", context));
+        }
+        let details: Vec<JsonValue> = self.details.iter().map(Explanation::to_json).collect();
+        serde_json::json!({
+            "value": self.value,
+            "description": description,
+            "details": details,
+        })
+    }
+
+    fn fmt_indented(&self, f: &mut fmt::Formatter, depth: usize) -> fmt::Result {
+        writeln!(
+            f,
+            "{:indent$}{} = {}",
+            "",
+            self.value,
+            self.description,
+            indent = 2 * depth
+        )?;
+        for context in &self.context {
+            writeln!(f, "{:indent$}  ({})", "", context, indent = 2 * depth)?;
+        }
+        for detail in &self.details {
+            detail.fmt_indented(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Explanation;
+    use serde_json::json;
+
+    #[test]
+    fn test_explanation_display() {
+        let mut explanation = Explanation::new("product of", 6.0);
+        let mut left = Explanation::new("sum of", 2.0);
+        left.add_const("one", 1.0);
+        left.add_const("one", 1.0);
+        explanation.add_detail(left);
+        explanation.add_const("three", 3.0);
+        explanation.add_context("some context".to_string());
+        assert_eq!(explanation.details().len(), 2);
+        assert_eq!(explanation.details()[0].description(), "sum of");
+        assert_eq!(
+            explanation.to_string(),
+            "6 = product of\n  (some context)\n  2 = sum of\n    1 = one\n    1 = one\n  3 = three\n"
+        );
+    }
+
+    #[test]
+    fn test_explanation_to_json() {
+        let mut explanation = Explanation::new("sum of", 3.0);
+        explanation.add_const("one", 1.0);
+        explanation.add_const("two", 2.0);
+        explanation.add_context("some context".to_string());
+        assert_eq!(
+            explanation.to_json(),
+            json!({
+                "value": 3.0,
+                "description": "sum of (some context)",
+                "details": [
+                    {"value": 1.0, "description": "one", "details": []},
+                    {"value": 2.0, "description": "two", "details": []},
+                ],
+            })
+        );
+    }
 }
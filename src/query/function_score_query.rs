@@ -0,0 +1,489 @@
+use crate::fastfield::{DeleteBitSet, FastFieldReader};
+use crate::query::explanation::does_not_match;
+use crate::query::{Explanation, Query, Scorer, Weight};
+use crate::schema::Field;
+use crate::{DocId, DocSet, Score, Searcher, SegmentReader, Term};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::sync::Arc;
+
+/// A `SegmentScoreFunction` computes the score of the documents
+/// of a specific segment.
+///
+/// It is the segment local version of the [`ScoreFunction`](./trait.ScoreFunction.html).
+pub trait SegmentScoreFunction: Send + 'static {
+    /// Computes the score of the document `doc`, given the score
+    /// emitted by the underlying query.
+    fn score(&self, doc: DocId, inner_score: Score) -> Score;
+}
+
+/// `ScoreFunction` makes it possible to modify the score of the documents
+/// matched by a [`FunctionScoreQuery`](./struct.FunctionScoreQuery.html).
+///
+/// The `ScoreFunction` itself does not make much of the computation itself.
+/// Instead, it helps constructing `SegmentScoreFunction` instances that will
+/// compute the score at a segment scale.
+pub trait ScoreFunction: Send + Sync + fmt::Debug + 'static {
+    /// Builds the score function for a specific segment.
+    ///
+    /// This is typically where fast field readers are opened.
+    fn segment_score_function(
+        &self,
+        segment_reader: &SegmentReader,
+    ) -> crate::Result<Box<dyn SegmentScoreFunction>>;
+}
+
+/// Shape of the decay applied by a
+/// [`FieldValueScoreFunction`](./struct.FieldValueScoreFunction.html).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DecayFunction {
+    /// The factor decreases linearly with the distance to the origin,
+    /// and reaches 0 at a distance of `scale / (1 - decay)`.
+    Linear,
+    /// The factor is `decay ^ (distance / scale)`.
+    Exponential,
+}
+
+/// `FieldValueScoreFunction` multiplies the score of the documents
+/// by a factor decaying with the distance between the value of
+/// a `u64` fast field and an origin.
+///
+/// At the origin, the factor is 1. At a distance of `scale`, the factor
+/// is `decay`.
+///
+/// A typical usage is to boost recent documents, given a timestamp field.
+///
+/// # Example
+///
+/// ```rust
+/// use tantivy::collector::TopDocs;
+/// use tantivy::query::{AllQuery, DecayFunction, FieldValueScoreFunction, FunctionScoreQuery};
+/// use tantivy::schema::{Schema, FAST};
+/// use tantivy::{doc, DocAddress, Index};
+/// # fn test() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let timestamp = schema_builder.add_u64_field("timestamp", FAST);
+/// let schema = schema_builder.build();
+///
+/// let index = Index::create_in_ram(schema);
+/// let mut index_writer = index.writer_with_num_threads(1, 10_000_000)?;
+/// index_writer.add_document(doc!(timestamp => 1_000u64));
+/// index_writer.add_document(doc!(timestamp => 10_000u64));
+/// index_writer.add_document(doc!(timestamp => 5_000u64));
+/// index_writer.commit()?;
+///
+/// let searcher = index.reader()?.searcher();
+/// let recency = FieldValueScoreFunction::new(timestamp, DecayFunction::Exponential, 10_000, 1_000);
+/// let query = FunctionScoreQuery::new(Box::new(AllQuery), Box::new(recency));
+/// let top_docs = searcher.search(&query, &TopDocs::with_limit(3))?;
+/// let docs: Vec<DocAddress> = top_docs.into_iter().map(|(_, doc)| doc).collect();
+/// assert_eq!(docs, vec![DocAddress(0, 1), DocAddress(0, 2), DocAddress(0, 0)]);
+/// Ok(())
+/// # }
+/// # assert!(test().is_ok());
+/// ```
+#[derive(Clone, Debug)]
+pub struct FieldValueScoreFunction {
+    field: Field,
+    decay_function: DecayFunction,
+    origin: u64,
+    scale: u64,
+    decay: f64,
+}
+
+impl FieldValueScoreFunction {
+    /// Creates a new `FieldValueScoreFunction`, with a `decay` of 0.5.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scale` is 0.
+    pub fn new(
+        field: Field,
+        decay_function: DecayFunction,
+        origin: u64,
+        scale: u64,
+    ) -> FieldValueScoreFunction {
+        assert!(scale > 0, "The scale of a decay function must be positive.");
+        FieldValueScoreFunction {
+            field,
+            decay_function,
+            origin,
+            scale,
+            decay: 0.5,
+        }
+    }
+
+    /// Sets the factor applied at a distance of `scale` from the origin.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `decay` is not strictly between 0 and 1.
+    pub fn with_decay(mut self, decay: f64) -> FieldValueScoreFunction {
+        assert!(
+            decay > 0.0 && decay < 1.0,
+            "The decay must be strictly between 0 and 1."
+        );
+        self.decay = decay;
+        self
+    }
+
+    /// Returns the factor associated to a given fast field value.
+    fn factor(&self, val: u64) -> f64 {
+        let distance = (val as f64 - self.origin as f64).abs();
+        let scale = self.scale as f64;
+        match self.decay_function {
+            DecayFunction::Linear => {
+                let zero_distance = scale / (1.0 - self.decay);
+                ((zero_distance - distance) / zero_distance).max(0.0)
+            }
+            DecayFunction::Exponential => self.decay.powf(distance / scale),
+        }
+    }
+}
+
+impl ScoreFunction for FieldValueScoreFunction {
+    fn segment_score_function(
+        &self,
+        segment_reader: &SegmentReader,
+    ) -> crate::Result<Box<dyn SegmentScoreFunction>> {
+        let fast_field_reader = segment_reader.fast_fields().u64(self.field)?;
+        Ok(Box::new(FieldValueSegmentScoreFunction {
+            score_function: self.clone(),
+            fast_field_reader,
+        }))
+    }
+}
+
+struct FieldValueSegmentScoreFunction {
+    score_function: FieldValueScoreFunction,
+    fast_field_reader: FastFieldReader<u64>,
+}
+
+impl SegmentScoreFunction for FieldValueSegmentScoreFunction {
+    fn score(&self, doc: DocId, inner_score: Score) -> Score {
+        let val = self.fast_field_reader.get(doc);
+        inner_score * self.score_function.factor(val) as Score
+    }
+}
+
+/// `DocumentBoostScoreFunction` multiplies the score of the documents
+/// by the value of the document boost field of the schema.
+///
+/// It is applied by the `Searcher` to all of the queries of the indexes
+/// having a document boost field.
+#[derive(Clone, Debug)]
+pub(crate) struct DocumentBoostScoreFunction {
+    field: Field,
+}
+
+impl DocumentBoostScoreFunction {
+    pub(crate) fn new(field: Field) -> DocumentBoostScoreFunction {
+        DocumentBoostScoreFunction { field }
+    }
+
+    /// Wraps `weight` so that its scores get multiplied by the document boosts.
+    pub(crate) fn boost_weight(self, weight: Box<dyn Weight>) -> Box<dyn Weight> {
+        Box::new(FunctionScoreWeight {
+            weight,
+            score_function: Arc::new(self),
+        })
+    }
+}
+
+impl ScoreFunction for DocumentBoostScoreFunction {
+    fn segment_score_function(
+        &self,
+        segment_reader: &SegmentReader,
+    ) -> crate::Result<Box<dyn SegmentScoreFunction>> {
+        let fast_field_reader = segment_reader.fast_fields().f64(self.field)?;
+        Ok(Box::new(DocumentBoostSegmentScoreFunction {
+            fast_field_reader,
+        }))
+    }
+}
+
+struct DocumentBoostSegmentScoreFunction {
+    fast_field_reader: FastFieldReader<f64>,
+}
+
+impl SegmentScoreFunction for DocumentBoostSegmentScoreFunction {
+    fn score(&self, doc: DocId, inner_score: Score) -> Score {
+        inner_score * self.fast_field_reader.get(doc) as Score
+    }
+}
+
+/// `FunctionScoreQuery` is a wrapper over a query that overrides its score
+/// with a [`ScoreFunction`](./trait.ScoreFunction.html).
+///
+/// The document set matched by the `FunctionScoreQuery` is strictly the same as
+/// the underlying query.
+///
+/// See [`FieldValueScoreFunction`](./struct.FieldValueScoreFunction.html) for an example.
+pub struct FunctionScoreQuery {
+    query: Box<dyn Query>,
+    score_function: Arc<dyn ScoreFunction>,
+}
+
+impl FunctionScoreQuery {
+    /// Builds a function score query.
+    pub fn new(
+        query: Box<dyn Query>,
+        score_function: Box<dyn ScoreFunction>,
+    ) -> FunctionScoreQuery {
+        FunctionScoreQuery {
+            query,
+            score_function: Arc::from(score_function),
+        }
+    }
+}
+
+impl Clone for FunctionScoreQuery {
+    fn clone(&self) -> Self {
+        FunctionScoreQuery {
+            query: self.query.box_clone(),
+            score_function: self.score_function.clone(),
+        }
+    }
+}
+
+impl fmt::Debug for FunctionScoreQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "FunctionScore(query={:?}, function={:?})",
+            self.query, self.score_function
+        )
+    }
+}
+
+impl Query for FunctionScoreQuery {
+    fn weight(&self, searcher: &Searcher, scoring_enabled: bool) -> crate::Result<Box<dyn Weight>> {
+        let weight = self.query.weight(searcher, scoring_enabled)?;
+        if !scoring_enabled {
+            return Ok(weight);
+        }
+        Ok(Box::new(FunctionScoreWeight {
+            weight,
+            score_function: self.score_function.clone(),
+        }))
+    }
+
+    fn query_terms(&self, term_set: &mut BTreeSet<Term>) {
+        self.query.query_terms(term_set)
+    }
+}
+
+struct FunctionScoreWeight {
+    weight: Box<dyn Weight>,
+    score_function: Arc<dyn ScoreFunction>,
+}
+
+impl Weight for FunctionScoreWeight {
+    fn scorer(&self, reader: &SegmentReader, boost: Score) -> crate::Result<Box<dyn Scorer>> {
+        let underlying = self.weight.scorer(reader, boost)?;
+        let segment_score_function = self.score_function.segment_score_function(reader)?;
+        Ok(Box::new(FunctionScorer {
+            underlying,
+            segment_score_function,
+        }))
+    }
+
+    fn explain(&self, reader: &SegmentReader, doc: DocId) -> crate::Result<Explanation> {
+        let mut scorer = self.scorer(reader, 1.0)?;
+        if scorer.seek(doc) != doc {
+            return Err(does_not_match(doc));
+        }
+        let mut explanation = Explanation::new("FunctionScore of ...", scorer.score());
+        explanation.add_context(format!("{:?}", self.score_function));
+        explanation.add_detail(self.weight.explain(reader, doc)?);
+        Ok(explanation)
+    }
+
+    fn count(&self, reader: &SegmentReader) -> crate::Result<u32> {
+        self.weight.count(reader)
+    }
+}
+
+struct FunctionScorer {
+    underlying: Box<dyn Scorer>,
+    segment_score_function: Box<dyn SegmentScoreFunction>,
+}
+
+impl DocSet for FunctionScorer {
+    fn advance(&mut self) -> DocId {
+        self.underlying.advance()
+    }
+
+    fn seek(&mut self, target: DocId) -> DocId {
+        self.underlying.seek(target)
+    }
+
+    fn fill_buffer(&mut self, buffer: &mut [DocId]) -> usize {
+        self.underlying.fill_buffer(buffer)
+    }
+
+    fn doc(&self) -> DocId {
+        self.underlying.doc()
+    }
+
+    fn size_hint(&self) -> u32 {
+        self.underlying.size_hint()
+    }
+
+    fn docs_skipped(&self) -> u64 {
+        self.underlying.docs_skipped()
+    }
+
+    fn count(&mut self, delete_bitset: &DeleteBitSet) -> u32 {
+        self.underlying.count(delete_bitset)
+    }
+
+    fn count_including_deleted(&mut self) -> u32 {
+        self.underlying.count_including_deleted()
+    }
+}
+
+impl Scorer for FunctionScorer {
+    fn score(&mut self) -> Score {
+        let inner_score = self.underlying.score();
+        self.segment_score_function
+            .score(self.underlying.doc(), inner_score)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DecayFunction, FieldValueScoreFunction, FunctionScoreQuery};
+    use crate::assert_nearly_equals;
+    use crate::collector::{Count, TopDocs};
+    use crate::query::{AllQuery, BoostQuery, Query, TermQuery};
+    use crate::schema::{Document, IndexRecordOption, Schema, FAST, INDEXED, TEXT};
+    use crate::{DocAddress, Index, Term};
+
+    fn create_index(timestamps: &[u64]) -> crate::Result<Index> {
+        let mut schema_builder = Schema::builder();
+        let timestamp_field = schema_builder.add_u64_field("timestamp", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        for &timestamp in timestamps {
+            index_writer.add_document(doc!(timestamp_field => timestamp));
+        }
+        index_writer.commit()?;
+        Ok(index)
+    }
+
+    fn scores(index: &Index, query: &dyn Query) -> crate::Result<Vec<f32>> {
+        let searcher = index.reader()?.searcher();
+        let mut top_docs = searcher.search(query, &TopDocs::with_limit(10))?;
+        top_docs.sort_by_key(|&(_, doc_address)| doc_address);
+        Ok(top_docs.into_iter().map(|(score, _)| score).collect())
+    }
+
+    #[test]
+    fn test_function_score_query_exponential() -> crate::Result<()> {
+        let index = create_index(&[100, 90, 80, 110, 0])?;
+        let timestamp_field = index.schema().get_field("timestamp").unwrap();
+        let score_function =
+            FieldValueScoreFunction::new(timestamp_field, DecayFunction::Exponential, 100, 10);
+        let query = FunctionScoreQuery::new(
+            Box::new(BoostQuery::new(Box::new(AllQuery), 2.0)),
+            Box::new(score_function),
+        );
+        let scores = scores(&index, &query)?;
+        assert_eq!(scores.len(), 5);
+        assert_nearly_equals!(scores[0], 2.0);
+        assert_nearly_equals!(scores[1], 1.0);
+        assert_nearly_equals!(scores[2], 0.5);
+        assert_nearly_equals!(scores[3], 1.0);
+        assert!(scores[4] < 0.01);
+        Ok(())
+    }
+
+    #[test]
+    fn test_function_score_query_linear() -> crate::Result<()> {
+        let index = create_index(&[100, 90, 80, 110, 0])?;
+        let timestamp_field = index.schema().get_field("timestamp").unwrap();
+        let score_function =
+            FieldValueScoreFunction::new(timestamp_field, DecayFunction::Linear, 100, 10)
+                .with_decay(0.75);
+        let query = FunctionScoreQuery::new(Box::new(AllQuery), Box::new(score_function));
+        let scores = scores(&index, &query)?;
+        assert_eq!(scores.len(), 5);
+        assert_nearly_equals!(scores[0], 1.0);
+        assert_nearly_equals!(scores[1], 0.75);
+        assert_nearly_equals!(scores[2], 0.5);
+        assert_nearly_equals!(scores[3], 0.75);
+        assert_nearly_equals!(scores[4], 0.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_function_score_query_explain() -> crate::Result<()> {
+        let index = create_index(&[100, 90])?;
+        let timestamp_field = index.schema().get_field("timestamp").unwrap();
+        let searcher = index.reader()?.searcher();
+        let score_function =
+            FieldValueScoreFunction::new(timestamp_field, DecayFunction::Exponential, 100, 10);
+        let query = FunctionScoreQuery::new(Box::new(AllQuery), Box::new(score_function));
+        let explanation = query.explain(&searcher, DocAddress(0, 1))?;
+        assert_nearly_equals!(explanation.value(), 0.5);
+        assert_eq!(explanation.details()[0].description(), "AllQuery");
+        assert_eq!(searcher.search(&query, &Count)?, 2);
+        assert_eq!(searcher.search(&query.clone(), &Count)?, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_function_score_query_not_a_fast_field() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let timestamp_field = schema_builder.add_u64_field("timestamp", INDEXED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(timestamp_field => 1u64));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let score_function =
+            FieldValueScoreFunction::new(timestamp_field, DecayFunction::Linear, 0, 10);
+        let query = FunctionScoreQuery::new(Box::new(AllQuery), Box::new(score_function));
+        assert!(searcher.search(&query, &TopDocs::with_limit(1)).is_err());
+        // Scores are not computed when counting.
+        assert_eq!(searcher.search(&query, &Count)?, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_document_boost() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let boost = schema_builder.add_document_boost_field("boost", ());
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title => "beer"));
+        index_writer.add_document_with_boost(doc!(title => "beer"), 3.0)?;
+        index_writer.add_document(doc!(title => "beer", boost => 0.5f64));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let query = TermQuery::new(
+            Term::from_field_text(title, "beer"),
+            IndexRecordOption::Basic,
+        );
+        let top_docs = searcher.search(&query, &TopDocs::with_limit(3))?;
+        let docs: Vec<DocAddress> = top_docs.iter().map(|&(_, doc)| doc).collect();
+        assert_eq!(
+            docs,
+            vec![DocAddress(0, 1), DocAddress(0, 0), DocAddress(0, 2)]
+        );
+        assert_nearly_equals!(top_docs[0].0, 3.0 * top_docs[1].0);
+        assert_nearly_equals!(top_docs[2].0, 0.5 * top_docs[1].0);
+        let explanation = searcher.explain(&query, DocAddress(0, 1))?;
+        assert_nearly_equals!(explanation.value(), top_docs[0].0);
+        assert_eq!(searcher.search(&query, &Count)?, 3);
+
+        let index_without_boost = create_index(&[1])?;
+        let index_writer = index_without_boost.writer_for_tests()?;
+        assert!(index_writer
+            .add_document_with_boost(Document::new(), 2.0)
+            .is_err());
+        Ok(())
+    }
+}
@@ -5,6 +5,7 @@ use crate::TantivyError::InvalidArgument;
 use levenshtein_automata::{Distance, LevenshteinAutomatonBuilder, DFA};
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
+use std::fmt;
 use std::ops::Range;
 use tantivy_fst::Automaton;
 
@@ -49,9 +50,35 @@ static LEV_BUILDER: Lazy<HashMap<(u8, bool), LevenshteinAutomatonBuilder>> = Laz
     lev_builder_cache
 });
 
+/// Builds the DFA matching the strings within `distance` edits of `text`,
+/// or of a prefix of `text` if `prefix` is true.
+pub(crate) fn build_dfa(
+    text: &str,
+    distance: u8,
+    transposition_cost_one: bool,
+    prefix: bool,
+) -> crate::Result<DFA> {
+    // LEV_BUILDER is a HashMap, whose `get` method returns an Option
+    match LEV_BUILDER.get(&(distance, transposition_cost_one)) {
+        Some(automaton_builder) => Ok(if prefix {
+            automaton_builder.build_prefix_dfa(text)
+        } else {
+            automaton_builder.build_dfa(text)
+        }),
+        None => Err(InvalidArgument(format!(
+            "Levenshtein distance of {} is not allowed. Choose a value in the {:?} range",
+            distance, VALID_LEVENSHTEIN_DISTANCE_RANGE
+        ))),
+    }
+}
+
 /// A Fuzzy Query matches all of the documents
 /// containing a specific term that is within
 /// Levenshtein distance
+///
+/// The distance is counted in Unicode scalar value edits, not in bytes:
+/// `cafe` is at a distance of 1 of `café`, and `東京府` of `東京都`.
+///
 /// ```rust
 /// use tantivy::collector::{Count, TopDocs};
 /// use tantivy::query::FuzzyTermQuery;
@@ -95,6 +122,7 @@ static LEV_BUILDER: Lazy<HashMap<(u8, bool), LevenshteinAutomatonBuilder>> = Laz
 /// # assert!(example().is_ok());
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FuzzyTermQuery {
     /// What term are we searching
     term: Term,
@@ -108,6 +136,8 @@ pub struct FuzzyTermQuery {
 
 impl FuzzyTermQuery {
     /// Creates a new Fuzzy Query
+    ///
+    /// `distance` is the maximum number of Unicode scalar value edits.
     pub fn new(term: Term, distance: u8, transposition_cost_one: bool) -> FuzzyTermQuery {
         FuzzyTermQuery {
             term,
@@ -128,25 +158,22 @@ impl FuzzyTermQuery {
     }
 
     fn specialized_weight(&self) -> crate::Result<AutomatonWeight<DFAWrapper>> {
-        // LEV_BUILDER is a HashMap, whose `get` method returns an Option
-        match LEV_BUILDER.get(&(self.distance, false)) {
-            // Unwrap the option and build the Ok(AutomatonWeight)
-            Some(automaton_builder) => {
-                let automaton = if self.prefix {
-                    automaton_builder.build_prefix_dfa(self.term.text())
-                } else {
-                    automaton_builder.build_dfa(self.term.text())
-                };
-                Ok(AutomatonWeight::new(
-                    self.term.field(),
-                    DFAWrapper(automaton),
-                ))
-            }
-            None => Err(InvalidArgument(format!(
-                "Levenshtein distance of {} is not allowed. Choose a value in the {:?} range",
-                self.distance, VALID_LEVENSHTEIN_DISTANCE_RANGE
-            ))),
+        let automaton = build_dfa(self.term.text(), self.distance, false, self.prefix)?;
+        Ok(AutomatonWeight::new(
+            self.term.field(),
+            DFAWrapper(automaton),
+        ))
+    }
+}
+
+/// Displays the query like `0:coffee~1`, with a trailing `*` for prefix queries.
+impl fmt::Display for FuzzyTermQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.term)?;
+        if self.prefix {
+            write!(f, "*")?;
         }
+        write!(f, "~{}", self.distance)
     }
 }
 
@@ -166,7 +193,7 @@ mod test {
     use crate::assert_nearly_equals;
     use crate::collector::TopDocs;
     use crate::schema::Schema;
-    use crate::schema::TEXT;
+    use crate::schema::{STRING, TEXT};
     use crate::Index;
     use crate::Term;
 
@@ -226,4 +253,34 @@ mod test {
             assert_nearly_equals!(1.0, score);
         }
     }
+
+    #[test]
+    pub fn test_fuzzy_term_non_ascii() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let word_field = schema_builder.add_text_field("word", STRING);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(word_field => "café"));
+        index_writer.add_document(doc!(word_field => "straße"));
+        index_writer.add_document(doc!(word_field => "東京都"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let count = |text: &str, distance: u8| -> crate::Result<usize> {
+            let term = Term::from_field_text(word_field, text);
+            let fuzzy_query = FuzzyTermQuery::new(term, distance, true);
+            Ok(searcher
+                .search(&fuzzy_query, &TopDocs::with_limit(3))?
+                .len())
+        };
+        // Each of these differs by a single multi-byte char.
+        assert_eq!(count("cafe", 1)?, 1);
+        assert_eq!(count("strase", 1)?, 1);
+        assert_eq!(count("東京府", 1)?, 1);
+        assert_eq!(count("東京", 1)?, 1);
+        // Replacing `ß` with `ss` takes two edits.
+        assert_eq!(count("strasse", 1)?, 0);
+        assert_eq!(count("strasse", 2)?, 1);
+        assert_eq!(count("cofe", 1)?, 0);
+        Ok(())
+    }
 }
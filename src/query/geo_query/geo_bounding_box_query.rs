@@ -0,0 +1,102 @@
+use super::geo_weight::{GeoWeight, QuantizedBox};
+use crate::query::{Query, Weight};
+use crate::schema::{quantize_lat, quantize_lon, Field, GeoPoint};
+use crate::Searcher;
+
+/// `GeoBoundingBoxQuery` matches all of the documents whose geo point
+/// is within a latitude/longitude box.
+///
+/// The box is defined by its top left and bottom right corners.
+/// If the longitude of the top left corner is greater than the longitude
+/// of the bottom right corner, the box is considered to cross the antimeridian.
+///
+/// Matched documents all get a constant `Score` of one.
+///
+/// # Implementation
+///
+/// The geo point field needs to be a fast field.
+/// For each segment, the fast field of all of the documents is scanned.
+///
+/// # Example
+///
+/// ```rust
+/// use tantivy::collector::Count;
+/// use tantivy::query::GeoBoundingBoxQuery;
+/// use tantivy::schema::{GeoPoint, Schema, FAST};
+/// use tantivy::{doc, Index};
+/// # fn test() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let location = schema_builder.add_geo_point_field("location", FAST);
+/// let schema = schema_builder.build();
+///
+/// let index = Index::create_in_ram(schema);
+/// let mut index_writer = index.writer_with_num_threads(1, 10_000_000)?;
+/// index_writer.add_document(doc!(location => GeoPoint::new(48.8566, 2.3522)));
+/// index_writer.add_document(doc!(location => GeoPoint::new(51.5074, -0.1278)));
+/// index_writer.add_document(doc!(location => GeoPoint::new(40.7128, -74.0060)));
+/// index_writer.commit()?;
+///
+/// let reader = index.reader()?;
+/// let searcher = reader.searcher();
+/// let europe = GeoBoundingBoxQuery::new(
+///     location,
+///     GeoPoint::new(60.0, -10.0),
+///     GeoPoint::new(35.0, 30.0),
+/// );
+/// assert_eq!(searcher.search(&europe, &Count)?, 2);
+/// Ok(())
+/// # }
+/// # assert!(test().is_ok());
+/// ```
+#[derive(Clone, Debug)]
+pub struct GeoBoundingBoxQuery {
+    field: Field,
+    top_left: GeoPoint,
+    bottom_right: GeoPoint,
+}
+
+impl GeoBoundingBoxQuery {
+    /// Creates a new `GeoBoundingBoxQuery` given the top left
+    /// and the bottom right corners of the box.
+    pub fn new(field: Field, top_left: GeoPoint, bottom_right: GeoPoint) -> GeoBoundingBoxQuery {
+        GeoBoundingBoxQuery {
+            field,
+            top_left,
+            bottom_right,
+        }
+    }
+
+    /// Field to search over
+    pub fn field(&self) -> Field {
+        self.field
+    }
+
+    /// The top left corner of the box.
+    pub fn top_left(&self) -> GeoPoint {
+        self.top_left
+    }
+
+    /// The bottom right corner of the box.
+    pub fn bottom_right(&self) -> GeoPoint {
+        self.bottom_right
+    }
+}
+
+impl Query for GeoBoundingBoxQuery {
+    fn weight(
+        &self,
+        _searcher: &Searcher,
+        _scoring_enabled: bool,
+    ) -> crate::Result<Box<dyn Weight>> {
+        let quantized_box = QuantizedBox {
+            lat_range: quantize_lat(self.bottom_right.lat)..=quantize_lat(self.top_left.lat),
+            lon_min: quantize_lon(self.top_left.lon),
+            lon_max: quantize_lon(self.bottom_right.lon),
+        };
+        Ok(Box::new(GeoWeight::new(
+            self.field,
+            quantized_box,
+            |_: &GeoPoint| true,
+        )))
+    }
+}
@@ -0,0 +1,172 @@
+use super::geo_weight::{GeoWeight, QuantizedBox};
+use crate::query::{Query, Weight};
+use crate::schema::{
+    quantize_lat, quantize_lon, Field, GeoPoint, EARTH_RADIUS_KM, MAX_QUANTIZED_VALUE,
+};
+use crate::Searcher;
+
+/// Computes a box containing all of the points within `distance_km` of `origin`.
+///
+/// The box is widened by one quantization step on each side, so that
+/// rounding never excludes a matching point.
+fn bounding_box(origin: &GeoPoint, distance_km: f64) -> QuantizedBox {
+    let angular_distance = distance_km / EARTH_RADIUS_KM;
+    let delta_lat = angular_distance.to_degrees();
+    let lat_min = origin.lat - delta_lat;
+    let lat_max = origin.lat + delta_lat;
+    let lat_range = quantize_lat(lat_min).saturating_sub(1)
+        ..=quantize_lat(lat_max)
+            .saturating_add(1)
+            .min(MAX_QUANTIZED_VALUE);
+    if lat_min <= -90.0 || lat_max >= 90.0 {
+        // The circle contains a pole.
+        return QuantizedBox {
+            lat_range,
+            lon_min: 0,
+            lon_max: MAX_QUANTIZED_VALUE,
+        };
+    }
+    let delta_lon = (angular_distance.sin() / origin.lat.to_radians().cos())
+        .min(1.0)
+        .asin()
+        .to_degrees();
+    let mut lon_min = origin.lon - delta_lon;
+    let mut lon_max = origin.lon + delta_lon;
+    if lon_min < -180.0 {
+        lon_min += 360.0;
+    }
+    if lon_max > 180.0 {
+        lon_max -= 360.0;
+    }
+    QuantizedBox {
+        lat_range,
+        lon_min: quantize_lon(lon_min).saturating_sub(1),
+        lon_max: quantize_lon(lon_max)
+            .saturating_add(1)
+            .min(MAX_QUANTIZED_VALUE),
+    }
+}
+
+/// `GeoDistanceQuery` matches all of the documents whose geo point
+/// is within a given distance of an origin point.
+///
+/// Distances are great-circle distances, computed with the haversine
+/// formula and expressed in kilometers.
+///
+/// Matched documents all get a constant `Score` of one.
+///
+/// # Implementation
+///
+/// The geo point field needs to be a fast field.
+/// For each segment, the fast field of all of the documents is scanned.
+/// Documents outside of a box surrounding the circle are discarded
+/// before the distance gets computed.
+///
+/// # Example
+///
+/// ```rust
+/// use tantivy::collector::Count;
+/// use tantivy::query::GeoDistanceQuery;
+/// use tantivy::schema::{GeoPoint, Schema, FAST};
+/// use tantivy::{doc, Index};
+/// # fn test() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let location = schema_builder.add_geo_point_field("location", FAST);
+/// let schema = schema_builder.build();
+///
+/// let index = Index::create_in_ram(schema);
+/// let mut index_writer = index.writer_with_num_threads(1, 10_000_000)?;
+/// index_writer.add_document(doc!(location => GeoPoint::new(48.8566, 2.3522)));
+/// index_writer.add_document(doc!(location => GeoPoint::new(51.5074, -0.1278)));
+/// index_writer.add_document(doc!(location => GeoPoint::new(40.7128, -74.0060)));
+/// index_writer.commit()?;
+///
+/// let reader = index.reader()?;
+/// let searcher = reader.searcher();
+/// let paris = GeoPoint::new(48.8566, 2.3522);
+/// let query = GeoDistanceQuery::new(location, paris, 500.0);
+/// assert_eq!(searcher.search(&query, &Count)?, 2);
+/// Ok(())
+/// # }
+/// # assert!(test().is_ok());
+/// ```
+#[derive(Clone, Debug)]
+pub struct GeoDistanceQuery {
+    field: Field,
+    origin: GeoPoint,
+    distance_km: f64,
+}
+
+impl GeoDistanceQuery {
+    /// Creates a new `GeoDistanceQuery` matching the points
+    /// within `distance_km` kilometers of `origin`.
+    pub fn new(field: Field, origin: GeoPoint, distance_km: f64) -> GeoDistanceQuery {
+        GeoDistanceQuery {
+            field,
+            origin,
+            distance_km,
+        }
+    }
+
+    /// Field to search over
+    pub fn field(&self) -> Field {
+        self.field
+    }
+
+    /// The point distances are computed from.
+    pub fn origin(&self) -> GeoPoint {
+        self.origin
+    }
+
+    /// The maximum distance, in kilometers.
+    pub fn distance_km(&self) -> f64 {
+        self.distance_km
+    }
+}
+
+impl Query for GeoDistanceQuery {
+    fn weight(
+        &self,
+        _searcher: &Searcher,
+        _scoring_enabled: bool,
+    ) -> crate::Result<Box<dyn Weight>> {
+        let origin = self.origin;
+        let distance_km = self.distance_km;
+        Ok(Box::new(GeoWeight::new(
+            self.field,
+            bounding_box(&origin, distance_km),
+            move |geo_point: &GeoPoint| origin.distance_km(geo_point) <= distance_km,
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::bounding_box;
+    use crate::schema::{quantize_lat, quantize_lon, GeoPoint, MAX_QUANTIZED_VALUE};
+
+    #[test]
+    fn test_bounding_box() {
+        let quantized_box = bounding_box(&GeoPoint::new(0.0, 0.0), 111.0);
+        assert!(quantized_box.lat_range.contains(&quantize_lat(0.99)));
+        assert!(!quantized_box.lat_range.contains(&quantize_lat(1.01)));
+        assert!(quantized_box.lon_min < quantize_lon(-0.99));
+        assert!(quantized_box.lon_max > quantize_lon(0.99));
+        assert!(quantized_box.lon_max < quantize_lon(1.01));
+    }
+
+    #[test]
+    fn test_bounding_box_crossing_antimeridian() {
+        let quantized_box = bounding_box(&GeoPoint::new(0.0, 179.5), 111.0);
+        assert!(quantized_box.lon_min > quantized_box.lon_max);
+        assert!(quantized_box.lon_max > quantize_lon(-179.51));
+    }
+
+    #[test]
+    fn test_bounding_box_containing_pole() {
+        let quantized_box = bounding_box(&GeoPoint::new(89.5, 0.0), 111.0);
+        assert_eq!(quantized_box.lon_min, 0);
+        assert_eq!(quantized_box.lon_max, MAX_QUANTIZED_VALUE);
+        assert_eq!(*quantized_box.lat_range.end(), MAX_QUANTIZED_VALUE);
+    }
+}
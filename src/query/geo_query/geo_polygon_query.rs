@@ -0,0 +1,267 @@
+use super::geo_weight::{GeoWeight, QuantizedBox};
+use crate::query::{EmptyWeight, Query, Weight};
+use crate::schema::{quantize_lat, quantize_lon, Field, GeoPoint};
+use crate::Searcher;
+use crate::TantivyError;
+use serde_json::Value as JsonValue;
+
+/// Computes the box containing all of the vertices of `polygon`.
+fn bounding_box(polygon: &[GeoPoint]) -> QuantizedBox {
+    let mut lat_min = f64::MAX;
+    let mut lat_max = f64::MIN;
+    let mut lon_min = f64::MAX;
+    let mut lon_max = f64::MIN;
+    for vertex in polygon {
+        lat_min = lat_min.min(vertex.lat);
+        lat_max = lat_max.max(vertex.lat);
+        lon_min = lon_min.min(vertex.lon);
+        lon_max = lon_max.max(vertex.lon);
+    }
+    QuantizedBox {
+        lat_range: quantize_lat(lat_min)..=quantize_lat(lat_max),
+        lon_min: quantize_lon(lon_min),
+        lon_max: quantize_lon(lon_max),
+    }
+}
+
+/// Returns true iff `point` is within the ring, by ray casting along its parallel.
+fn ring_contains(ring: &[GeoPoint], point: &GeoPoint) -> bool {
+    let mut inside = false;
+    let mut previous_vertex = if let Some(last_vertex) = ring.last() {
+        last_vertex
+    } else {
+        return false;
+    };
+    for vertex in ring {
+        if (vertex.lat > point.lat) != (previous_vertex.lat > point.lat) {
+            let edge_lon = vertex.lon
+                + (point.lat - vertex.lat) * (previous_vertex.lon - vertex.lon)
+                    / (previous_vertex.lat - vertex.lat);
+            if point.lon < edge_lon {
+                inside = !inside;
+            }
+        }
+        previous_vertex = vertex;
+    }
+    inside
+}
+
+fn invalid_geojson(msg: &str) -> TantivyError {
+    TantivyError::InvalidArgument(format!("Invalid GeoJSON polygon: {}", msg))
+}
+
+/// Parses a GeoJSON linear ring, i.e. an array of `[lon, lat]` positions.
+fn parse_geojson_ring(ring: &JsonValue) -> crate::Result<Vec<GeoPoint>> {
+    let positions = ring
+        .as_array()
+        .ok_or_else(|| invalid_geojson("a ring is not an array"))?;
+    positions
+        .iter()
+        .map(|position| {
+            let coordinates = position
+                .as_array()
+                .filter(|coordinates| coordinates.len() >= 2)
+                .ok_or_else(|| invalid_geojson("a position is not an array of coordinates"))?;
+            match (coordinates[0].as_f64(), coordinates[1].as_f64()) {
+                (Some(lon), Some(lat)) => Ok(GeoPoint::new(lat, lon)),
+                _ => Err(invalid_geojson("a coordinate is not a number")),
+            }
+        })
+        .collect()
+}
+
+/// `GeoPolygonQuery` matches all of the documents whose geo point
+/// is within a polygon.
+///
+/// The polygon is defined by its vertices, and may have holes.
+/// The last vertex is implicitly joined to the first one: as in GeoJSON,
+/// it may also repeat the first vertex. The polygon cannot cross the antimeridian.
+///
+/// Matched documents all get a constant `Score` of one.
+///
+/// # Implementation
+///
+/// The geo point field needs to be a fast field.
+/// For each segment, the fast field of all of the documents is scanned.
+/// The points within the bounding box of the polygon are then tested with
+/// the even-odd rule, so that the winding order of the vertices does not matter.
+///
+/// # Example
+///
+/// ```rust
+/// use tantivy::collector::Count;
+/// use tantivy::query::GeoPolygonQuery;
+/// use tantivy::schema::{GeoPoint, Schema, FAST};
+/// use tantivy::{doc, Index};
+/// # fn test() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let location = schema_builder.add_geo_point_field("location", FAST);
+/// let schema = schema_builder.build();
+///
+/// let index = Index::create_in_ram(schema);
+/// let mut index_writer = index.writer_with_num_threads(1, 10_000_000)?;
+/// index_writer.add_document(doc!(location => GeoPoint::new(48.8566, 2.3522)));
+/// index_writer.add_document(doc!(location => GeoPoint::new(50.8503, 4.3517)));
+/// index_writer.commit()?;
+///
+/// let reader = index.reader()?;
+/// let searcher = reader.searcher();
+/// let triangle = GeoPolygonQuery::new(
+///     location,
+///     vec![
+///         GeoPoint::new(52.5, -1.0),
+///         GeoPoint::new(48.0, 5.0),
+///         GeoPoint::new(52.5, 5.0),
+///     ],
+/// );
+/// assert_eq!(searcher.search(&triangle, &Count)?, 1);
+/// Ok(())
+/// # }
+/// # assert!(test().is_ok());
+/// ```
+#[derive(Clone, Debug)]
+pub struct GeoPolygonQuery {
+    field: Field,
+    polygon: Vec<GeoPoint>,
+    holes: Vec<Vec<GeoPoint>>,
+}
+
+impl GeoPolygonQuery {
+    /// Creates a new `GeoPolygonQuery` given the vertices of the polygon.
+    ///
+    /// A polygon with less than 3 vertices does not match any document.
+    pub fn new(field: Field, polygon: Vec<GeoPoint>) -> GeoPolygonQuery {
+        GeoPolygonQuery {
+            field,
+            polygon,
+            holes: Vec::new(),
+        }
+    }
+
+    /// Creates a new `GeoPolygonQuery` out of a GeoJSON `Polygon` geometry,
+    /// or of a GeoJSON `Feature` whose geometry is a `Polygon`.
+    ///
+    /// The first ring of the polygon is its exterior, and the following ones are its holes.
+    /// Following the GeoJSON specification, positions are given as `[lon, lat]`.
+    ///
+    /// Returns `InvalidArgument` if `geojson` is not a valid GeoJSON polygon.
+    pub fn from_geojson(field: Field, geojson: &JsonValue) -> crate::Result<GeoPolygonQuery> {
+        let geometry = match geojson.get("type").and_then(JsonValue::as_str) {
+            Some("Feature") => geojson
+                .get("geometry")
+                .ok_or_else(|| invalid_geojson("the feature has no geometry"))?,
+            _ => geojson,
+        };
+        if geometry.get("type").and_then(JsonValue::as_str) != Some("Polygon") {
+            return Err(invalid_geojson("the geometry is not a Polygon"));
+        }
+        let mut rings = geometry
+            .get("coordinates")
+            .and_then(JsonValue::as_array)
+            .ok_or_else(|| invalid_geojson("the coordinates are not an array of rings"))?
+            .iter()
+            .map(parse_geojson_ring)
+            .collect::<crate::Result<Vec<Vec<GeoPoint>>>>()?
+            .into_iter();
+        let polygon = rings
+            .next()
+            .ok_or_else(|| invalid_geojson("the polygon has no ring"))?;
+        Ok(GeoPolygonQuery {
+            field,
+            polygon,
+            holes: rings.collect(),
+        })
+    }
+
+    /// Adds a hole to the polygon, given its vertices.
+    pub fn add_hole(&mut self, hole: Vec<GeoPoint>) {
+        self.holes.push(hole);
+    }
+
+    /// Field to search over
+    pub fn field(&self) -> Field {
+        self.field
+    }
+
+    /// The vertices of the polygon.
+    pub fn polygon(&self) -> &[GeoPoint] {
+        &self.polygon[..]
+    }
+
+    /// The vertices of the holes of the polygon.
+    pub fn holes(&self) -> &[Vec<GeoPoint>] {
+        &self.holes[..]
+    }
+}
+
+impl Query for GeoPolygonQuery {
+    fn weight(
+        &self,
+        _searcher: &Searcher,
+        _scoring_enabled: bool,
+    ) -> crate::Result<Box<dyn Weight>> {
+        if self.polygon.len() < 3 {
+            return Ok(Box::new(EmptyWeight));
+        }
+        let polygon = self.polygon.clone();
+        let holes = self.holes.clone();
+        Ok(Box::new(GeoWeight::new(
+            self.field,
+            bounding_box(&polygon),
+            move |geo_point: &GeoPoint| {
+                ring_contains(&polygon, geo_point)
+                    && !holes.iter().any(|hole| ring_contains(hole, geo_point))
+            },
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bounding_box, ring_contains};
+    use crate::schema::{quantize_lat, quantize_lon, GeoPoint};
+
+    #[test]
+    fn test_ring_contains() {
+        let square = vec![
+            GeoPoint::new(0.0, 0.0),
+            GeoPoint::new(0.0, 10.0),
+            GeoPoint::new(10.0, 10.0),
+            GeoPoint::new(10.0, 0.0),
+            GeoPoint::new(0.0, 0.0),
+        ];
+        assert!(ring_contains(&square, &GeoPoint::new(5.0, 5.0)));
+        assert!(!ring_contains(&square, &GeoPoint::new(5.0, 11.0)));
+        assert!(!ring_contains(&square, &GeoPoint::new(-1.0, 5.0)));
+        let mut reversed_square = square.clone();
+        reversed_square.reverse();
+        assert!(ring_contains(&reversed_square, &GeoPoint::new(5.0, 5.0)));
+        // Concave polygon, with a notch on its top side.
+        let notched = vec![
+            GeoPoint::new(0.0, 0.0),
+            GeoPoint::new(10.0, 0.0),
+            GeoPoint::new(5.0, 5.0),
+            GeoPoint::new(10.0, 10.0),
+            GeoPoint::new(0.0, 10.0),
+        ];
+        assert!(ring_contains(&notched, &GeoPoint::new(2.0, 5.0)));
+        assert!(!ring_contains(&notched, &GeoPoint::new(8.0, 5.0)));
+        assert!(ring_contains(&notched, &GeoPoint::new(8.0, 1.0)));
+        assert!(!ring_contains(&[], &GeoPoint::new(0.0, 0.0)));
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let quantized_box = bounding_box(&[
+            GeoPoint::new(-5.0, 20.0),
+            GeoPoint::new(10.0, 30.0),
+            GeoPoint::new(0.0, 25.0),
+        ]);
+        assert_eq!(
+            quantized_box.lat_range,
+            quantize_lat(-5.0)..=quantize_lat(10.0)
+        );
+        assert_eq!(quantized_box.lon_min, quantize_lon(20.0));
+        assert_eq!(quantized_box.lon_max, quantize_lon(30.0));
+    }
+}
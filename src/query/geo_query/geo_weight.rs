@@ -0,0 +1,147 @@
+use crate::common::BitSet;
+use crate::core::SegmentReader;
+use crate::query::explanation::does_not_match;
+use crate::query::{BitSetDocSet, ConstScorer, Explanation, Scorer, Weight};
+use crate::schema::{deinterleave, interleave, Field, GeoPoint, MAX_QUANTIZED_VALUE};
+use crate::{DocId, Score};
+use std::ops::RangeInclusive;
+
+/// A latitude/longitude box, expressed in quantized coordinates.
+///
+/// If `lon_min` is greater than `lon_max`, the box crosses the antimeridian.
+#[derive(Clone, Debug)]
+pub(crate) struct QuantizedBox {
+    pub lat_range: RangeInclusive<u32>,
+    pub lon_min: u32,
+    pub lon_max: u32,
+}
+
+impl QuantizedBox {
+    fn crosses_antimeridian(&self) -> bool {
+        self.lon_min > self.lon_max
+    }
+
+    /// Range of the encoded points that may be within the box.
+    ///
+    /// The Z-order code being increasing with respect to both coordinates,
+    /// all of the points within the box have a code within this range.
+    fn code_range(&self) -> RangeInclusive<u64> {
+        let (lon_min, lon_max) = if self.crosses_antimeridian() {
+            (0, MAX_QUANTIZED_VALUE)
+        } else {
+            (self.lon_min, self.lon_max)
+        };
+        interleave(*self.lat_range.start(), lon_min)..=interleave(*self.lat_range.end(), lon_max)
+    }
+
+    fn contains(&self, lat: u32, lon: u32) -> bool {
+        if !self.lat_range.contains(&lat) {
+            return false;
+        }
+        if self.crosses_antimeridian() {
+            lon >= self.lon_min || lon <= self.lon_max
+        } else {
+            lon >= self.lon_min && lon <= self.lon_max
+        }
+    }
+}
+
+/// Weight shared by the geo queries.
+///
+/// For each segment, the geo point fast field is scanned and the
+/// documents whose point is within `quantized_box` and matches `filter`
+/// are appended to a `BitSet`.
+pub(crate) struct GeoWeight<TFilter> {
+    field: Field,
+    quantized_box: QuantizedBox,
+    filter: TFilter,
+}
+
+impl<TFilter> GeoWeight<TFilter>
+where
+    TFilter: Fn(&GeoPoint) -> bool + Send + Sync + 'static,
+{
+    pub fn new(field: Field, quantized_box: QuantizedBox, filter: TFilter) -> GeoWeight<TFilter> {
+        GeoWeight {
+            field,
+            quantized_box,
+            filter,
+        }
+    }
+}
+
+impl<TFilter> Weight for GeoWeight<TFilter>
+where
+    TFilter: Fn(&GeoPoint) -> bool + Send + Sync + 'static,
+{
+    fn scorer(&self, reader: &SegmentReader, boost: Score) -> crate::Result<Box<dyn Scorer>> {
+        let max_doc = reader.max_doc();
+        let mut doc_bitset = BitSet::with_max_value(max_doc);
+        let fast_field_reader = reader.fast_fields().geo_point(self.field)?;
+        let code_range = self.quantized_box.code_range();
+        for doc in 0..max_doc {
+            let code = fast_field_reader.get(doc);
+            if !code_range.contains(&code) {
+                continue;
+            }
+            let (lat, lon) = if let Some(quantized_point) = deinterleave(code) {
+                quantized_point
+            } else {
+                continue;
+            };
+            if !self.quantized_box.contains(lat, lon) {
+                continue;
+            }
+            if let Some(geo_point) = GeoPoint::from_u64(code) {
+                if (self.filter)(&geo_point) {
+                    doc_bitset.insert(doc);
+                }
+            }
+        }
+        let doc_bitset = BitSetDocSet::from(doc_bitset);
+        Ok(Box::new(ConstScorer::new(doc_bitset, boost)))
+    }
+
+    fn explain(&self, reader: &SegmentReader, doc: DocId) -> crate::Result<Explanation> {
+        let mut scorer = self.scorer(reader, 1.0)?;
+        if scorer.seek(doc) != doc {
+            return Err(does_not_match(doc));
+        }
+        Ok(Explanation::new("GeoQuery", 1.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QuantizedBox;
+    use crate::schema::{interleave, quantize_lat, quantize_lon};
+
+    fn quantized_box(lat_min: f64, lat_max: f64, lon_min: f64, lon_max: f64) -> QuantizedBox {
+        QuantizedBox {
+            lat_range: quantize_lat(lat_min)..=quantize_lat(lat_max),
+            lon_min: quantize_lon(lon_min),
+            lon_max: quantize_lon(lon_max),
+        }
+    }
+
+    #[test]
+    fn test_quantized_box_contains() {
+        let quantized_box = quantized_box(10.0, 20.0, -5.0, 5.0);
+        assert!(quantized_box.contains(quantize_lat(15.0), quantize_lon(0.0)));
+        assert!(quantized_box.contains(quantize_lat(10.0), quantize_lon(5.0)));
+        assert!(!quantized_box.contains(quantize_lat(21.0), quantize_lon(0.0)));
+        assert!(!quantized_box.contains(quantize_lat(15.0), quantize_lon(6.0)));
+        let code = interleave(quantize_lat(15.0), quantize_lon(0.0));
+        assert!(quantized_box.code_range().contains(&code));
+    }
+
+    #[test]
+    fn test_quantized_box_crossing_antimeridian() {
+        let quantized_box = quantized_box(-10.0, 10.0, 170.0, -170.0);
+        assert!(quantized_box.contains(quantize_lat(0.0), quantize_lon(175.0)));
+        assert!(quantized_box.contains(quantize_lat(0.0), quantize_lon(-175.0)));
+        assert!(!quantized_box.contains(quantize_lat(0.0), quantize_lon(0.0)));
+        let code = interleave(quantize_lat(0.0), quantize_lon(-175.0));
+        assert!(quantized_box.code_range().contains(&code));
+    }
+}
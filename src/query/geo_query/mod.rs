@@ -0,0 +1,261 @@
+mod geo_bounding_box_query;
+mod geo_distance_query;
+mod geo_polygon_query;
+mod geo_weight;
+
+pub use self::geo_bounding_box_query::GeoBoundingBoxQuery;
+pub use self::geo_distance_query::GeoDistanceQuery;
+pub use self::geo_polygon_query::GeoPolygonQuery;
+
+#[cfg(test)]
+mod tests {
+    use super::{GeoBoundingBoxQuery, GeoDistanceQuery, GeoPolygonQuery};
+    use crate::collector::{Count, DocSetCollector};
+    use crate::query::Query;
+    use crate::schema::{Field, GeoPoint, Schema, FAST, STORED, STRING};
+    use crate::{DocAddress, Index, Searcher, Term};
+    use futures::executor::block_on;
+
+    const CITIES: &[(&str, f64, f64)] = &[
+        ("paris", 48.8566, 2.3522),
+        ("london", 51.5074, -0.1278),
+        ("brussels", 50.8503, 4.3517),
+        ("new_york", 40.7128, -74.0060),
+        ("sydney", -33.8688, 151.2093),
+        ("suva", -18.1416, 178.4419),
+        ("apia", -13.8333, -171.7667),
+    ];
+
+    fn create_index() -> crate::Result<(Index, Field, Field)> {
+        let mut schema_builder = Schema::builder();
+        let name_field = schema_builder.add_text_field("name", STRING | STORED);
+        let location_field = schema_builder.add_geo_point_field("location", FAST | STORED);
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_for_tests()?;
+            for (i, &(name, lat, lon)) in CITIES.iter().enumerate() {
+                index_writer.add_document(doc!(
+                    name_field => name,
+                    location_field => GeoPoint::new(lat, lon),
+                ));
+                if i == 3 {
+                    index_writer.commit()?;
+                }
+            }
+            index_writer.add_document(doc!(name_field => "nowhere"));
+            index_writer.commit()?;
+        }
+        Ok((index, name_field, location_field))
+    }
+
+    fn matching_names(
+        searcher: &Searcher,
+        name_field: Field,
+        query: &dyn Query,
+    ) -> crate::Result<Vec<String>> {
+        let mut names = Vec::new();
+        for doc_address in searcher.search(query, &DocSetCollector)? {
+            let doc = searcher.doc(doc_address)?;
+            names.push(
+                doc.get_first(name_field)
+                    .unwrap()
+                    .text()
+                    .unwrap()
+                    .to_string(),
+            );
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    #[test]
+    fn test_geo_bounding_box_query() -> crate::Result<()> {
+        let (index, name_field, location_field) = create_index()?;
+        let searcher = index.reader()?.searcher();
+        let query = |top_left: (f64, f64), bottom_right: (f64, f64)| {
+            GeoBoundingBoxQuery::new(
+                location_field,
+                GeoPoint::new(top_left.0, top_left.1),
+                GeoPoint::new(bottom_right.0, bottom_right.1),
+            )
+        };
+        assert_eq!(
+            matching_names(&searcher, name_field, &query((52.0, -1.0), (48.0, 3.0)))?,
+            vec!["london", "paris"]
+        );
+        assert_eq!(
+            matching_names(&searcher, name_field, &query((0.0, 170.0), (-20.0, -170.0)))?,
+            vec!["apia", "suva"]
+        );
+        assert_eq!(
+            searcher.search(&query((90.0, -180.0), (-90.0, 180.0)), &Count)?,
+            CITIES.len()
+        );
+        assert_eq!(
+            searcher.search(&query((10.0, 10.0), (0.0, 20.0)), &Count)?,
+            0
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_geo_distance_query() -> crate::Result<()> {
+        let (index, name_field, location_field) = create_index()?;
+        let searcher = index.reader()?.searcher();
+        let paris = GeoPoint::new(48.8566, 2.3522);
+        let query = |distance_km: f64| GeoDistanceQuery::new(location_field, paris, distance_km);
+        assert_eq!(
+            matching_names(&searcher, name_field, &query(1.0))?,
+            vec!["paris"]
+        );
+        assert_eq!(
+            matching_names(&searcher, name_field, &query(300.0))?,
+            vec!["brussels", "paris"]
+        );
+        assert_eq!(
+            matching_names(&searcher, name_field, &query(350.0))?,
+            vec!["brussels", "london", "paris"]
+        );
+        assert_eq!(searcher.search(&query(25_000.0), &Count)?, CITIES.len());
+        let suva = GeoPoint::new(-18.1416, 178.4419);
+        let query = GeoDistanceQuery::new(location_field, suva, 1_200.0);
+        assert_eq!(
+            matching_names(&searcher, name_field, &query)?,
+            vec!["apia", "suva"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_geo_polygon_query() -> crate::Result<()> {
+        let (index, name_field, location_field) = create_index()?;
+        let searcher = index.reader()?.searcher();
+        let polygon = |vertices: &[(f64, f64)]| -> Vec<GeoPoint> {
+            vertices
+                .iter()
+                .map(|&(lat, lon)| GeoPoint::new(lat, lon))
+                .collect()
+        };
+        // The bounding box of the triangle contains london, paris and brussels.
+        let triangle = GeoPolygonQuery::new(
+            location_field,
+            polygon(&[(52.5, -1.0), (48.0, 5.0), (52.5, 5.0)]),
+        );
+        assert_eq!(
+            matching_names(&searcher, name_field, &triangle)?,
+            vec!["brussels"]
+        );
+        let mut square = GeoPolygonQuery::new(
+            location_field,
+            polygon(&[(48.0, -1.0), (48.0, 5.0), (52.5, 5.0), (52.5, -1.0)]),
+        );
+        assert_eq!(
+            matching_names(&searcher, name_field, &square)?,
+            vec!["brussels", "london", "paris"]
+        );
+        square.add_hole(polygon(&[
+            (48.5, 2.0),
+            (48.5, 2.7),
+            (49.2, 2.7),
+            (49.2, 2.0),
+        ]));
+        assert_eq!(
+            matching_names(&searcher, name_field, &square)?,
+            vec!["brussels", "london"]
+        );
+        let segment = GeoPolygonQuery::new(location_field, polygon(&[(40.0, -80.0), (60.0, 10.0)]));
+        assert_eq!(searcher.search(&segment, &Count)?, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_geo_polygon_query_from_geojson() -> crate::Result<()> {
+        let (index, name_field, location_field) = create_index()?;
+        let searcher = index.reader()?.searcher();
+        let feature = serde_json::json!({
+            "type": "Feature",
+            "properties": {},
+            "geometry": {
+                "type": "Polygon",
+                "coordinates": [
+                    [[-1.0, 48.0], [5.0, 48.0], [5.0, 52.5], [-1.0, 52.5], [-1.0, 48.0]],
+                    [[2.0, 48.5], [2.0, 49.2], [2.7, 49.2], [2.7, 48.5], [2.0, 48.5]]
+                ]
+            }
+        });
+        let query = GeoPolygonQuery::from_geojson(location_field, &feature)?;
+        assert_eq!(query.polygon()[1], GeoPoint::new(48.0, 5.0));
+        assert_eq!(query.holes().len(), 1);
+        assert_eq!(
+            matching_names(&searcher, name_field, &query)?,
+            vec!["brussels", "london"]
+        );
+        let query = GeoPolygonQuery::from_geojson(location_field, &feature["geometry"])?;
+        assert_eq!(
+            matching_names(&searcher, name_field, &query)?,
+            vec!["brussels", "london"]
+        );
+        for invalid_geojson in &[
+            serde_json::json!({"type": "Point", "coordinates": [2.35, 48.85]}),
+            serde_json::json!({"type": "Polygon", "coordinates": []}),
+            serde_json::json!({"type": "Polygon", "coordinates": [[[2.35]]]}),
+            serde_json::json!({"type": "Polygon", "coordinates": [[["2.35", 48.85]]]}),
+            serde_json::json!({"type": "Feature"}),
+        ] {
+            assert!(matches!(
+                GeoPolygonQuery::from_geojson(location_field, invalid_geojson),
+                Err(crate::TantivyError::InvalidArgument(_))
+            ));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_geo_query_after_merge() -> crate::Result<()> {
+        let (index, name_field, location_field) = create_index()?;
+        {
+            let segment_ids = index.searchable_segment_ids()?;
+            let mut index_writer = index.writer_for_tests()?;
+            index_writer.delete_term(Term::from_field_text(name_field, "brussels"));
+            index_writer.commit()?;
+            block_on(index_writer.merge(&segment_ids))?;
+        }
+        let reader = index.reader()?;
+        let searcher = reader.searcher();
+        assert_eq!(searcher.segment_readers().len(), 1);
+        let paris = GeoPoint::new(48.8566, 2.3522);
+        let query = GeoDistanceQuery::new(location_field, paris, 350.0);
+        assert_eq!(
+            matching_names(&searcher, name_field, &query)?,
+            vec!["london", "paris"]
+        );
+        let paris_query = GeoDistanceQuery::new(location_field, paris, 1.0);
+        let doc_addresses: Vec<DocAddress> = searcher
+            .search(&paris_query, &DocSetCollector)?
+            .into_iter()
+            .collect();
+        assert_eq!(doc_addresses.len(), 1);
+        let doc = searcher.doc(doc_addresses[0])?;
+        assert_eq!(
+            doc.get_first(location_field).unwrap().geo_point_value(),
+            Some(paris)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_geo_query_requires_fast_field() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let location_field = schema_builder.add_geo_point_field("location", STORED);
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_for_tests()?;
+            index_writer.add_document(doc!(location_field => GeoPoint::new(0.0, 0.0)));
+            index_writer.commit()?;
+        }
+        let searcher = index.reader()?.searcher();
+        let query = GeoDistanceQuery::new(location_field, GeoPoint::new(0.0, 0.0), 1.0);
+        assert!(searcher.search(&query, &Count).is_err());
+        Ok(())
+    }
+}
@@ -1,4 +1,6 @@
 use crate::docset::{DocSet, TERMINATED};
+use crate::fastfield::DeleteBitSet;
+use crate::postings::BlockIntersector;
 use crate::query::term_query::TermScorer;
 use crate::query::EmptyScorer;
 use crate::query::Scorer;
@@ -93,6 +95,23 @@ impl<TDocSet: DocSet> Intersection<TDocSet, TDocSet> {
     }
 }
 
+impl Intersection<TermScorer, Box<dyn Scorer>> {
+    /// Counts the documents of the intersection of exactly two terms,
+    /// by intersecting their posting lists one block at a time.
+    ///
+    /// Returns `None` if the intersection has more than two terms.
+    pub(crate) fn count_term_pair(&mut self, delete_bitset: Option<&DeleteBitSet>) -> Option<u32> {
+        if !self.others.is_empty() {
+            return None;
+        }
+        Some(BlockIntersector::default().count_intersection(
+            self.left.postings_mut(),
+            self.right.postings_mut(),
+            delete_bitset,
+        ))
+    }
+}
+
 impl<TDocSet: DocSet, TOtherDocSet: DocSet> DocSet for Intersection<TDocSet, TOtherDocSet> {
     fn advance(&mut self) -> DocId {
         let (left, right) = (&mut self.left, &mut self.right);
@@ -145,6 +164,16 @@ impl<TDocSet: DocSet, TOtherDocSet: DocSet> DocSet for Intersection<TDocSet, TOt
     fn size_hint(&self) -> u32 {
         self.left.size_hint()
     }
+
+    fn docs_skipped(&self) -> u64 {
+        self.left.docs_skipped()
+            + self.right.docs_skipped()
+            + self
+                .others
+                .iter()
+                .map(|docset| docset.docs_skipped())
+                .sum::<u64>()
+    }
 }
 
 impl<TScorer, TOtherScorer> Scorer for Intersection<TScorer, TOtherScorer>
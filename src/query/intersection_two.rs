@@ -4,24 +4,19 @@ use DocId;
 use Score;
 use SkipResult;
 
-
 /// Creates a `DocSet` that iterate through the intersection of two `DocSet`s.
 pub struct IntersectionTwoTerms<TDocSet> {
     left: TDocSet,
-    right: TDocSet
+    right: TDocSet,
 }
 
 impl<TDocSet: DocSet> IntersectionTwoTerms<TDocSet> {
     pub fn new(left: TDocSet, right: TDocSet) -> IntersectionTwoTerms<TDocSet> {
-        IntersectionTwoTerms {
-            left,
-            right
-        }
+        IntersectionTwoTerms { left, right }
     }
 }
 
 impl<TDocSet: DocSet> DocSet for IntersectionTwoTerms<TDocSet> {
-
     fn advance(&mut self) -> bool {
         let (left, right) = (&mut self.left, &mut self.right);
         if !left.advance() {
@@ -61,6 +56,10 @@ impl<TDocSet: DocSet> DocSet for IntersectionTwoTerms<TDocSet> {
     fn size_hint(&self) -> u32 {
         self.left.size_hint().min(self.right.size_hint())
     }
+
+    fn docs_skipped(&self) -> u64 {
+        self.left.docs_skipped() + self.right.docs_skipped()
+    }
 }
 
 impl<TScorer: Scorer> Scorer for IntersectionTwoTerms<TScorer> {
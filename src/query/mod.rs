@@ -4,29 +4,44 @@ mod all_query;
 mod automaton_weight;
 mod bitset;
 mod bm25;
+mod bm25f_query;
 mod boolean_query;
 mod boost_query;
+mod const_score_query;
+mod disjunction_max_query;
 mod empty_query;
 mod exclude;
+mod exists_query;
 mod explanation;
+mod function_score_query;
 mod fuzzy_query;
+mod geo_query;
 mod intersection;
+mod more_like_this;
+mod multi_match_query;
 mod phrase_query;
 mod query;
+#[cfg(feature = "serde")]
+mod query_json;
 mod query_parser;
 mod range_query;
 mod regex_query;
 mod reqopt_scorer;
 mod scorer;
+mod span_query;
 mod term_query;
+mod term_set_query;
 mod union;
+mod vector_query;
 mod weight;
+mod wildcard_query;
 
 #[cfg(test)]
 mod vec_docset;
 
 pub(crate) mod score_combiner;
 pub(crate) use self::bm25::BM25Weight;
+pub use self::bm25::{BM25Params, Similarity};
 pub use self::intersection::Intersection;
 pub use self::union::Union;
 
@@ -36,35 +51,57 @@ pub use self::vec_docset::VecDocSet;
 pub use self::all_query::{AllQuery, AllScorer, AllWeight};
 pub use self::automaton_weight::AutomatonWeight;
 pub use self::bitset::BitSetDocSet;
+pub use self::bm25f_query::{BM25FClause, BM25FQuery};
 pub use self::boolean_query::BooleanQuery;
 pub use self::boost_query::BoostQuery;
+pub use self::const_score_query::ConstScoreQuery;
+pub use self::disjunction_max_query::DisjunctionMaxQuery;
 pub use self::empty_query::{EmptyQuery, EmptyScorer, EmptyWeight};
 pub use self::exclude::Exclude;
+pub use self::exists_query::{ExistsQuery, MissingQuery};
 pub use self::explanation::Explanation;
-#[cfg(test)]
-pub(crate) use self::fuzzy_query::DFAWrapper;
+pub(crate) use self::function_score_query::DocumentBoostScoreFunction;
+pub use self::function_score_query::{
+    DecayFunction, FieldValueScoreFunction, FunctionScoreQuery, ScoreFunction, SegmentScoreFunction,
+};
 pub use self::fuzzy_query::FuzzyTermQuery;
+pub(crate) use self::fuzzy_query::{build_dfa, DFAWrapper};
+pub use self::geo_query::{GeoBoundingBoxQuery, GeoDistanceQuery, GeoPolygonQuery};
 pub use self::intersection::intersect_scorers;
-pub use self::phrase_query::PhraseQuery;
+pub(crate) use self::more_like_this::more_like_this_query;
+pub use self::more_like_this::MoreLikeThisParams;
+pub use self::multi_match_query::{MultiMatchQuery, MultiMatchStrategy};
+pub use self::phrase_query::{MultiFieldPhraseQuery, PhraseQuery};
 pub use self::query::{Query, QueryClone};
+#[cfg(feature = "serde")]
+pub use self::query_json::QueryJson;
 pub use self::query_parser::QueryParser;
 pub use self::query_parser::QueryParserError;
-pub use self::range_query::RangeQuery;
+pub use self::query_parser::Substitution;
+pub use self::range_query::{RangeQuery, RangeValue};
 pub use self::regex_query::RegexQuery;
 pub use self::reqopt_scorer::RequiredOptionalScorer;
 pub use self::scorer::ConstScorer;
 pub use self::scorer::Scorer;
+pub use self::span_query::{SpanNearQuery, SpanOrQuery, SpanTermQuery};
 pub use self::term_query::TermQuery;
+pub use self::term_set_query::TermSetQuery;
+pub use self::vector_query::{HybridQuery, KnnQuery};
 pub use self::weight::Weight;
+pub use self::wildcard_query::WildcardQuery;
 pub use tantivy_query_grammar::Occur;
 
 #[cfg(test)]
 mod tests {
-    use crate::query::QueryParser;
-    use crate::schema::{Schema, TEXT};
+    use crate::query::{
+        BooleanQuery, BoostQuery, FuzzyTermQuery, Occur, PhraseQuery, Query, QueryParser,
+        RangeQuery, RegexQuery, TermQuery,
+    };
+    use crate::schema::{Field, IndexRecordOption, Schema, INDEXED, TEXT};
     use crate::Index;
     use crate::Term;
     use std::collections::BTreeSet;
+    use std::ops::Bound;
 
     #[test]
     fn test_query_terms() {
@@ -121,4 +158,71 @@ mod tests {
             assert_eq!(vec![&term_a, &term_b], terms);
         }
     }
+
+    #[test]
+    fn test_query_display() {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let body = schema_builder.add_text_field("body", TEXT);
+        let price = schema_builder.add_u64_field("price", INDEXED);
+        let term_query = |field: Field, text: &str| -> Box<dyn Query> {
+            Box::new(TermQuery::new(
+                Term::from_field_text(field, text),
+                IndexRecordOption::Basic,
+            ))
+        };
+        let boolean_query = BooleanQuery::new(vec![
+            (Occur::Must, term_query(title, "coffee")),
+            (Occur::Should, term_query(body, "tea")),
+            (Occur::MustNot, term_query(body, "milk")),
+        ]);
+        assert_eq!(format!("{}", boolean_query), "+0:coffee 1:tea -1:milk");
+        let nested_query = BooleanQuery::with_minimum_should_match(
+            vec![
+                (Occur::Should, Box::new(boolean_query) as Box<dyn Query>),
+                (
+                    Occur::Should,
+                    Box::new(BoostQuery::new(term_query(title, "cake"), 2.0)),
+                ),
+            ],
+            1,
+        );
+        assert_eq!(
+            format!("{}", nested_query),
+            "(+0:coffee 1:tea -1:milk) (0:cake)^2~1"
+        );
+        let mut phrase_query = PhraseQuery::new(vec![
+            Term::from_field_text(body, "part"),
+            Term::from_field_text(body, "time"),
+        ]);
+        assert_eq!(format!("{}", phrase_query), "1:\"part time\"");
+        phrase_query.set_slop(1);
+        assert_eq!(format!("{}", phrase_query), "1:\"part time\"~1");
+        assert_eq!(
+            format!("{}", RangeQuery::new_u64(price, 3..5)),
+            "2:[3 TO 5}"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                RangeQuery::new_str_bounds(title, Bound::Excluded("a"), Bound::Unbounded)
+            ),
+            "0:{a TO *]"
+        );
+        let fuzzy_query = FuzzyTermQuery::new(Term::from_field_text(title, "cofee"), 1, true);
+        assert_eq!(format!("{}", fuzzy_query), "0:cofee~1");
+        let regex_query = RegexQuery::from_pattern("co[a-z]\\d*", title).unwrap();
+        assert_eq!(format!("{}", regex_query), "0:/co[a-z]\\d*/");
+        assert_eq!(
+            format!("{}", Term::from_field_u64(price, 1)),
+            "2:[0, 0, 0, 0, 0, 0, 0, 1]"
+        );
+        assert_eq!(
+            format!(
+                "{:?}",
+                TermQuery::new(Term::from_field_text(title, "a"), IndexRecordOption::Basic)
+            ),
+            "TermQuery(Term(field=0,bytes=[97]))"
+        );
+    }
 }
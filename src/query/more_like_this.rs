@@ -0,0 +1,148 @@
+use crate::query::bm25::idf;
+use crate::query::{BooleanQuery, Occur, Query, TermQuery};
+use crate::schema::{Field, IndexRecordOption, Term, Value};
+use crate::{DocAddress, Score, Searcher};
+use std::collections::HashMap;
+
+/// Parameters of the [`Searcher::more_like_this`](../struct.Searcher.html#method.more_like_this)
+/// query generation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MoreLikeThisParams {
+    /// Terms appearing less than `min_term_freq` times in the source document are ignored.
+    pub min_term_freq: usize,
+    /// Terms appearing in less than `min_doc_freq` documents of the index are ignored.
+    pub min_doc_freq: u64,
+    /// Maximum number of terms in the generated query.
+    pub max_query_terms: usize,
+    /// Terms with less than `min_word_length` characters are ignored.
+    pub min_word_length: usize,
+}
+
+impl Default for MoreLikeThisParams {
+    fn default() -> MoreLikeThisParams {
+        MoreLikeThisParams {
+            min_term_freq: 2,
+            min_doc_freq: 5,
+            max_query_terms: 25,
+            min_word_length: 0,
+        }
+    }
+}
+
+/// Returns the most interesting terms of the stored document,
+/// sorted by decreasing `tf * idf` score.
+fn interesting_terms(
+    searcher: &Searcher,
+    doc_address: DocAddress,
+    fields: &[Field],
+    params: &MoreLikeThisParams,
+) -> crate::Result<Vec<(Term, Score)>> {
+    let doc = searcher.doc(doc_address)?;
+    let mut term_freqs: HashMap<Term, usize> = HashMap::new();
+    for &field in fields {
+        let tokenizer = searcher.index().tokenizer_for_field(field)?;
+        for text in doc.get_all(field).flat_map(Value::text) {
+            let mut token_stream = tokenizer.token_stream(text);
+            while let Some(token) = token_stream.next() {
+                if token.text.chars().count() < params.min_word_length {
+                    continue;
+                }
+                let term = Term::from_field_text(field, &token.text);
+                *term_freqs.entry(term).or_insert(0) += 1;
+            }
+        }
+    }
+    let num_docs = searcher.num_docs();
+    let mut scored_terms: Vec<(Term, Score)> = Vec::new();
+    for (term, term_freq) in term_freqs {
+        if term_freq < params.min_term_freq {
+            continue;
+        }
+        let doc_freq = searcher.doc_freq(&term)?;
+        if doc_freq == 0 || doc_freq < params.min_doc_freq {
+            continue;
+        }
+        // Deleted documents may still be accounted for in the doc freq.
+        let score = term_freq as Score * idf(doc_freq.min(num_docs), num_docs);
+        scored_terms.push((term, score));
+    }
+    scored_terms.sort_by(|(left_term, left_score), (right_term, right_score)| {
+        right_score
+            .partial_cmp(left_score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| left_term.cmp(right_term))
+    });
+    scored_terms.truncate(params.max_query_terms);
+    Ok(scored_terms)
+}
+
+pub(crate) fn more_like_this_query(
+    searcher: &Searcher,
+    doc_address: DocAddress,
+    fields: &[Field],
+    params: &MoreLikeThisParams,
+) -> crate::Result<Box<dyn Query>> {
+    let subqueries: Vec<(Occur, Box<dyn Query>)> =
+        interesting_terms(searcher, doc_address, fields, params)?
+            .into_iter()
+            .map(|(term, _score)| {
+                let term_query: Box<dyn Query> =
+                    Box::new(TermQuery::new(term, IndexRecordOption::WithFreqs));
+                (Occur::Should, term_query)
+            })
+            .collect();
+    Ok(Box::new(BooleanQuery::new(subqueries)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{interesting_terms, MoreLikeThisParams};
+    use crate::collector::TopDocs;
+    use crate::schema::{Schema, STORED, TEXT};
+    use crate::{DocAddress, Index, Term};
+
+    #[test]
+    fn test_more_like_this() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT | STORED);
+        let body = schema_builder.add_text_field("body", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer
+            .add_document(doc!(title => "the old man and the sea, the man", body => "fish"));
+        index_writer.add_document(doc!(title => "the sea"));
+        index_writer.add_document(doc!(title => "the old man"));
+        index_writer.add_document(doc!(title => "the man"));
+        index_writer.add_document(doc!(title => "a whale"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let params = MoreLikeThisParams {
+            min_term_freq: 1,
+            min_doc_freq: 1,
+            max_query_terms: 3,
+            min_word_length: 3,
+        };
+        let terms = interesting_terms(&searcher, DocAddress(0, 0), &[title, body], &params)?;
+        let term_texts: Vec<&str> = terms.iter().map(|(term, _)| term.text()).collect();
+        // `body` is not stored, and `the` is too common to make it in the top 3.
+        assert_eq!(term_texts, vec!["and", "man", "old"]);
+        assert!(terms
+            .iter()
+            .all(|(term, _)| term.field() == title && term != &Term::from_field_text(title, "a")));
+
+        let query = searcher.more_like_this(DocAddress(0, 0), &[title], &params)?;
+        let top_docs = searcher.search(&query, &TopDocs::with_limit(10))?;
+        let docs: Vec<DocAddress> = top_docs.into_iter().map(|(_, doc)| doc).collect();
+        assert_eq!(
+            docs,
+            vec![DocAddress(0, 0), DocAddress(0, 2), DocAddress(0, 3)]
+        );
+
+        let default_query =
+            searcher.more_like_this(DocAddress(0, 0), &[title], &MoreLikeThisParams::default())?;
+        assert!(searcher
+            .search(&default_query, &TopDocs::with_limit(10))?
+            .is_empty());
+        Ok(())
+    }
+}
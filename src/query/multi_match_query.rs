@@ -0,0 +1,418 @@
+use crate::query::bm25::BM25Weight;
+use crate::query::disjunction_max_query::DisjunctionMaxWeight;
+use crate::query::term_query::TermWeight;
+use crate::query::{
+    BooleanQuery, BoostQuery, DisjunctionMaxQuery, Occur, Query, TermQuery, Weight,
+};
+use crate::schema::{Field, IndexRecordOption, Term};
+use crate::{Index, Score, Searcher};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Strategy used by a [`MultiMatchQuery`](./struct.MultiMatchQuery.html)
+/// to combine the scores of its fields.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MultiMatchStrategy {
+    /// The score of a document is the score of its best matching field,
+    /// as computed by a [`DisjunctionMaxQuery`](./struct.DisjunctionMaxQuery.html).
+    BestFields,
+    /// The score of a document is the sum of the scores of its matching fields.
+    MostFields,
+    /// The fields are searched as if they were a single field:
+    /// each token is scored in its best matching field, and the scores of the tokens are summed.
+    ///
+    /// The document frequency of a token is blended across the fields, so that a token
+    /// that is rare in one of the fields but common in another one does not get
+    /// an exaggerated score.
+    CrossFields,
+}
+
+/// `MultiMatchQuery` searches a text over several fields.
+///
+/// The text is tokenized with the tokenizer of each of the fields, and each field
+/// is searched for any of the resulting tokens.
+/// The [`MultiMatchStrategy`](./enum.MultiMatchStrategy.html) defines how the scores of
+/// the different fields are combined.
+///
+/// All fields must be indexed text fields.
+///
+/// ```rust
+/// use tantivy::collector::TopDocs;
+/// use tantivy::doc;
+/// use tantivy::query::{MultiMatchQuery, MultiMatchStrategy};
+/// use tantivy::schema::{Schema, TEXT};
+/// use tantivy::Index;
+///
+/// # fn main() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let title = schema_builder.add_text_field("title", TEXT);
+/// let body = schema_builder.add_text_field("body", TEXT);
+/// let index = Index::create_in_ram(schema_builder.build());
+/// let mut index_writer = index.writer(3_000_000)?;
+/// index_writer.add_document(doc!(title => "The Diary of a Young Girl", body => "girl"));
+/// index_writer.add_document(doc!(title => "The Diary of Muadib"));
+/// index_writer.commit()?;
+///
+/// let searcher = index.reader()?.searcher();
+/// let query = MultiMatchQuery::new(
+///     &index,
+///     vec![(title, 2.0), (body, 1.0)],
+///     "young girl",
+///     MultiMatchStrategy::BestFields,
+/// )?;
+/// let top_docs = searcher.search(&query, &TopDocs::with_limit(2))?;
+/// assert_eq!(top_docs.len(), 1);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct MultiMatchQuery {
+    query_str: String,
+    strategy: MultiMatchStrategy,
+    field_terms: Vec<FieldTerms>,
+}
+
+#[derive(Clone, Debug)]
+struct FieldTerms {
+    field: Field,
+    boost: Score,
+    terms: Vec<(usize, Term)>,
+}
+
+impl MultiMatchQuery {
+    /// Creates a new `MultiMatchQuery` searching `query_str` over the given
+    /// fields, each of them coming with its boost.
+    ///
+    /// `query_str` is tokenized with the tokenizer of each of the fields, as registered
+    /// in the `TokenizerManager` of the `index`.
+    ///
+    /// Returns an error if one of the fields is not an indexed text field,
+    /// or if its tokenizer is not registered.
+    pub fn new(
+        index: &Index,
+        fields: Vec<(Field, Score)>,
+        query_str: &str,
+        strategy: MultiMatchStrategy,
+    ) -> crate::Result<MultiMatchQuery> {
+        let mut field_terms = Vec::with_capacity(fields.len());
+        for (field, boost) in fields {
+            let tokenizer = index.tokenizer_for_field(field)?;
+            let mut terms: Vec<(usize, Term)> = Vec::new();
+            tokenizer.token_stream(query_str).process(&mut |token| {
+                terms.push((token.position, Term::from_field_text(field, &token.text)));
+            });
+            field_terms.push(FieldTerms {
+                field,
+                boost,
+                terms,
+            });
+        }
+        Ok(MultiMatchQuery {
+            query_str: query_str.to_string(),
+            strategy,
+            field_terms,
+        })
+    }
+
+    /// Returns the text searched by the query.
+    pub fn query_str(&self) -> &str {
+        &self.query_str
+    }
+
+    /// Returns the strategy used to combine the scores of the fields.
+    pub fn strategy(&self) -> MultiMatchStrategy {
+        self.strategy
+    }
+
+    /// Returns the searched fields, with their boosts.
+    pub fn fields(&self) -> Vec<(Field, Score)> {
+        self.field_terms
+            .iter()
+            .map(|field_terms| (field_terms.field, field_terms.boost))
+            .collect()
+    }
+
+    /// Builds the query matching any of the tokens in a single field.
+    fn field_query(field_terms: &FieldTerms) -> Box<dyn Query> {
+        let terms: Vec<Term> = field_terms
+            .terms
+            .iter()
+            .map(|(_, term)| term.clone())
+            .collect();
+        let query = Box::new(BooleanQuery::new_multiterms_query(terms));
+        if (field_terms.boost - 1.0).abs() < Score::EPSILON {
+            query
+        } else {
+            Box::new(BoostQuery::new(query, field_terms.boost))
+        }
+    }
+
+    fn to_query(&self) -> Box<dyn Query> {
+        match self.strategy {
+            MultiMatchStrategy::BestFields => Box::new(DisjunctionMaxQuery::new(
+                self.field_terms.iter().map(Self::field_query).collect(),
+            )),
+            MultiMatchStrategy::MostFields => Box::new(BooleanQuery::new(
+                self.field_terms
+                    .iter()
+                    .map(|field_terms| (Occur::Should, Self::field_query(field_terms)))
+                    .collect(),
+            )),
+            MultiMatchStrategy::CrossFields => {
+                // Tokens are matched across fields by their position in `query_str`.
+                let mut terms_per_position: BTreeMap<usize, Vec<(Term, Score)>> = BTreeMap::new();
+                for field_terms in &self.field_terms {
+                    for (position, term) in &field_terms.terms {
+                        terms_per_position
+                            .entry(*position)
+                            .or_default()
+                            .push((term.clone(), field_terms.boost));
+                    }
+                }
+                Box::new(BooleanQuery::new(
+                    terms_per_position
+                        .into_values()
+                        .map(|terms| {
+                            let query: Box<dyn Query> = Box::new(BlendedTermQuery { terms });
+                            (Occur::Should, query)
+                        })
+                        .collect(),
+                ))
+            }
+        }
+    }
+}
+
+impl Query for MultiMatchQuery {
+    fn weight(&self, searcher: &Searcher, scoring_enabled: bool) -> crate::Result<Box<dyn Weight>> {
+        self.to_query().weight(searcher, scoring_enabled)
+    }
+
+    fn query_terms(&self, term_set: &mut BTreeSet<Term>) {
+        for field_terms in &self.field_terms {
+            term_set.extend(field_terms.terms.iter().map(|(_, term)| term.clone()));
+        }
+    }
+}
+
+/// Query matching any of its terms, scoring a document with its best matching term.
+///
+/// All of the terms are scored as if they had the document frequency of the most
+/// frequent of them.
+#[derive(Clone, Debug)]
+struct BlendedTermQuery {
+    terms: Vec<(Term, Score)>,
+}
+
+impl Query for BlendedTermQuery {
+    fn weight(&self, searcher: &Searcher, scoring_enabled: bool) -> crate::Result<Box<dyn Weight>> {
+        if !scoring_enabled {
+            let query = DisjunctionMaxQuery::new(
+                self.terms
+                    .iter()
+                    .map(|(term, _)| -> Box<dyn Query> {
+                        Box::new(TermQuery::new(term.clone(), IndexRecordOption::Basic))
+                    })
+                    .collect(),
+            );
+            return query.weight(searcher, scoring_enabled);
+        }
+        let mut max_doc_freq = 0u64;
+        for (term, _) in &self.terms {
+            max_doc_freq = max_doc_freq.max(searcher.doc_freq(term)?);
+        }
+        let weights = self
+            .terms
+            .iter()
+            .map(|(term, boost)| -> crate::Result<Box<dyn Weight>> {
+                let bm25_weight = BM25Weight::for_term_with_doc_freq(searcher, term, max_doc_freq)?
+                    .boost_by(*boost);
+                Ok(Box::new(TermWeight::new(
+                    term.clone(),
+                    IndexRecordOption::WithFreqs,
+                    bm25_weight,
+                    true,
+                )))
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+        Ok(Box::new(DisjunctionMaxWeight::new(weights, 0.0)))
+    }
+
+    fn query_terms(&self, term_set: &mut BTreeSet<Term>) {
+        term_set.extend(self.terms.iter().map(|(term, _)| term.clone()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MultiMatchQuery, MultiMatchStrategy};
+    use crate::collector::{Count, TopDocs};
+    use crate::query::{BooleanQuery, DisjunctionMaxQuery, Query, TermQuery};
+    use crate::schema::{IndexRecordOption, Schema, STRING, TEXT};
+    use crate::{DocAddress, Index, Term};
+    use std::collections::BTreeSet;
+
+    fn create_index() -> crate::Result<Index> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let body = schema_builder.add_text_field("body", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title => "Old Man", body => "the sea"));
+        index_writer.add_document(doc!(title => "The Old Sea", body => "old man and the sea"));
+        index_writer.add_document(doc!(title => "Moby Dick", body => "the whale"));
+        index_writer.add_document(doc!(title => "Old Man", body => "the old man"));
+        index_writer.commit()?;
+        Ok(index)
+    }
+
+    #[test]
+    fn test_multi_match_query_strategies() -> crate::Result<()> {
+        let index = create_index()?;
+        let schema = index.schema();
+        let title = schema.get_field("title").unwrap();
+        let body = schema.get_field("body").unwrap();
+        let searcher = index.reader()?.searcher();
+        let term_query = |field, text| -> Box<dyn Query> {
+            Box::new(TermQuery::new(
+                Term::from_field_text(field, text),
+                IndexRecordOption::WithFreqs,
+            ))
+        };
+        let top_docs = |query: &dyn Query| -> crate::Result<Vec<(f32, DocAddress)>> {
+            searcher.search(query, &TopDocs::with_limit(10))
+        };
+        let field_query = |field| -> Box<dyn Query> {
+            Box::new(BooleanQuery::new_multiterms_query(vec![
+                Term::from_field_text(field, "old"),
+                Term::from_field_text(field, "sea"),
+            ]))
+        };
+        let fields = vec![(title, 1.0), (body, 1.0)];
+
+        let best_fields = MultiMatchQuery::new(
+            &index,
+            fields.clone(),
+            "Old SEA",
+            MultiMatchStrategy::BestFields,
+        )?;
+        let expected = DisjunctionMaxQuery::new(vec![field_query(title), field_query(body)]);
+        assert_eq!(top_docs(&best_fields)?, top_docs(&expected)?);
+        assert_eq!(searcher.search(&best_fields, &Count)?, 3);
+
+        let most_fields = MultiMatchQuery::new(
+            &index,
+            fields.clone(),
+            "Old SEA",
+            MultiMatchStrategy::MostFields,
+        )?;
+        let expected = BooleanQuery::new_multiterms_query(vec![
+            Term::from_field_text(title, "old"),
+            Term::from_field_text(title, "sea"),
+            Term::from_field_text(body, "old"),
+            Term::from_field_text(body, "sea"),
+        ]);
+        assert_eq!(top_docs(&most_fields)?, top_docs(&expected)?);
+
+        let cross_fields =
+            MultiMatchQuery::new(&index, fields, "whale", MultiMatchStrategy::CrossFields)?;
+        assert_eq!(top_docs(&cross_fields)?.len(), 1);
+        // `whale` only appears in the body, so its blended document frequency
+        // is its document frequency in the body.
+        let (score, _) = top_docs(&cross_fields)?[0];
+        let (expected_score, _) = top_docs(term_query(body, "whale").as_ref())?[0];
+        assert!((score - expected_score).abs() < 1e-5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_multi_match_query_cross_fields_blends_doc_freqs() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let body = schema_builder.add_text_field("body", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title => "rare", body => "x"));
+        for _ in 0..3 {
+            index_writer.add_document(doc!(title => "x", body => "rare"));
+        }
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let scores = |strategy| -> crate::Result<Vec<f32>> {
+            let query =
+                MultiMatchQuery::new(&index, vec![(title, 1.0), (body, 1.0)], "rare", strategy)?;
+            let mut scores = vec![0.0; 4];
+            for (score, doc_address) in searcher.search(&query, &TopDocs::with_limit(10))? {
+                scores[doc_address.1 as usize] = score;
+            }
+            Ok(scores)
+        };
+        // `rare` is rare in the title, but not in the body.
+        let most_fields_scores = scores(MultiMatchStrategy::MostFields)?;
+        assert!(most_fields_scores[0] > most_fields_scores[1]);
+        let cross_fields_scores = scores(MultiMatchStrategy::CrossFields)?;
+        assert!(cross_fields_scores.iter().all(|&score| score > 0.0));
+        assert!((cross_fields_scores[0] - cross_fields_scores[1]).abs() < 1e-5);
+        assert!((cross_fields_scores[1] - most_fields_scores[1]).abs() < 1e-5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_multi_match_query_boost() -> crate::Result<()> {
+        let index = create_index()?;
+        let schema = index.schema();
+        let title = schema.get_field("title").unwrap();
+        let body = schema.get_field("body").unwrap();
+        let searcher = index.reader()?.searcher();
+        for &strategy in &[
+            MultiMatchStrategy::BestFields,
+            MultiMatchStrategy::MostFields,
+            MultiMatchStrategy::CrossFields,
+        ] {
+            let title_first =
+                MultiMatchQuery::new(&index, vec![(title, 10.0), (body, 1.0)], "moby", strategy)?;
+            assert_eq!(title_first.fields(), vec![(title, 10.0), (body, 1.0)]);
+            let top_docs = searcher.search(&title_first, &TopDocs::with_limit(10))?;
+            assert_eq!(top_docs.len(), 1);
+            assert_eq!(top_docs[0].1, DocAddress(0, 2));
+            let boosted = MultiMatchQuery::new(&index, vec![(body, 3.0)], "sea", strategy)?;
+            let unboosted = MultiMatchQuery::new(&index, vec![(body, 1.0)], "sea", strategy)?;
+            let boosted_top_docs = searcher.search(&boosted, &TopDocs::with_limit(10))?;
+            let unboosted_top_docs = searcher.search(&unboosted, &TopDocs::with_limit(10))?;
+            assert_eq!(boosted_top_docs.len(), 2);
+            for (boosted, unboosted) in boosted_top_docs.iter().zip(unboosted_top_docs.iter()) {
+                assert_eq!(boosted.1, unboosted.1);
+                assert!((boosted.0 - 3.0 * unboosted.0).abs() < 1e-5);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_multi_match_query_requires_text_fields() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let id = schema_builder.add_u64_field("id", crate::schema::INDEXED);
+        let sku = schema_builder.add_text_field("sku", STRING);
+        let index = Index::create_in_ram(schema_builder.build());
+        assert!(MultiMatchQuery::new(
+            &index,
+            vec![(title, 1.0), (id, 1.0)],
+            "a",
+            MultiMatchStrategy::BestFields
+        )
+        .is_err());
+        let query = MultiMatchQuery::new(
+            &index,
+            vec![(title, 1.0), (sku, 1.0)],
+            "Hello World",
+            MultiMatchStrategy::MostFields,
+        )?;
+        assert_eq!(query.query_str(), "Hello World");
+        assert_eq!(query.strategy(), MultiMatchStrategy::MostFields);
+        let mut terms = BTreeSet::new();
+        query.query_terms(&mut terms);
+        assert!(terms.contains(&Term::from_field_text(sku, "Hello World")));
+        assert!(terms.contains(&Term::from_field_text(title, "hello")));
+        Ok(())
+    }
+}
@@ -1,7 +1,9 @@
+mod multi_field_phrase_query;
 mod phrase_query;
 mod phrase_scorer;
 mod phrase_weight;
 
+pub use self::multi_field_phrase_query::MultiFieldPhraseQuery;
 pub use self::phrase_query::PhraseQuery;
 pub use self::phrase_scorer::PhraseScorer;
 pub use self::phrase_weight::PhraseWeight;
@@ -14,7 +16,7 @@ pub mod tests {
     use crate::collector::tests::{TEST_COLLECTOR_WITHOUT_SCORE, TEST_COLLECTOR_WITH_SCORE};
     use crate::core::Index;
     use crate::query::Weight;
-    use crate::schema::{Schema, Term, TEXT};
+    use crate::schema::{Field, Schema, Term, TEXT};
     use crate::DocId;
     use crate::{DocAddress, TERMINATED};
 
@@ -246,4 +248,86 @@ pub mod tests {
         assert!(test_query(vec![(0, "a"), (2, "d")]).is_empty());
         assert_eq!(test_query(vec![(1, "a"), (3, "c")]), vec![0]);
     }
+
+    #[test]
+    pub fn test_phrase_query_position_gap() -> crate::Result<()> {
+        use crate::collector::Count;
+        use crate::schema::{IndexRecordOption, TextFieldIndexing, TextOptions};
+        let text_options = |position_gap: Option<u32>| {
+            let mut indexing = TextFieldIndexing::default()
+                .set_index_option(IndexRecordOption::WithFreqsAndPositions);
+            if let Some(position_gap) = position_gap {
+                indexing = indexing.set_position_gap(position_gap);
+            }
+            TextOptions::default().set_indexing_options(indexing)
+        };
+        let mut schema_builder = Schema::builder();
+        let default_gap = schema_builder.add_text_field("default_gap", text_options(None));
+        let gap_1 = schema_builder.add_text_field("gap_1", text_options(Some(1)));
+        let gap_10 = schema_builder.add_text_field("gap_10", text_options(Some(10)));
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(
+            default_gap => "hello world", default_gap => "foo bar",
+            gap_1 => "hello world", gap_1 => "foo bar",
+            gap_10 => "hello world", gap_10 => "foo bar",
+        ));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let count = |field, offsets_and_texts: &[(usize, &str)]| -> crate::Result<usize> {
+            let terms = offsets_and_texts
+                .iter()
+                .map(|&(offset, text)| (offset, Term::from_field_text(field, text)))
+                .collect();
+            searcher.search(&PhraseQuery::new_with_offset(terms), &Count)
+        };
+        assert_eq!(count(default_gap, &[(0, "world"), (1, "foo")])?, 0);
+        assert_eq!(count(default_gap, &[(0, "world"), (2, "foo")])?, 1);
+        assert_eq!(count(gap_1, &[(0, "world"), (1, "foo")])?, 1);
+        assert_eq!(count(gap_10, &[(0, "world"), (10, "foo")])?, 1);
+        assert_eq!(count(gap_10, &[(0, "world"), (2, "foo")])?, 0);
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_phrase_query_from_text() -> crate::Result<()> {
+        let index = create_index(&["a b c", "a x b c", "a x y b c", "b a c", "c c c"]);
+        let text_field = index.schema().get_field("text").unwrap();
+        let searcher = index.reader()?.searcher();
+        let test_query = |text: &str, slop: u32| -> crate::Result<Vec<DocId>> {
+            let phrase_query = PhraseQuery::from_text(text_field, text, &index, slop)?;
+            assert_eq!(phrase_query.slop(), slop);
+            let docs = searcher
+                .search(&phrase_query, &TEST_COLLECTOR_WITH_SCORE)?
+                .docs()
+                .iter()
+                .map(|doc_address| doc_address.1)
+                .collect();
+            Ok(docs)
+        };
+        assert_eq!(test_query("A B", 0)?, vec![0]);
+        assert_eq!(test_query("a, b c", 0)?, vec![0]);
+        assert_eq!(test_query("a b", 1)?, vec![0, 1]);
+        assert_eq!(test_query("a b c", 2)?, vec![0, 1, 2]);
+        assert_eq!(test_query("b a", 1)?, vec![3]);
+        assert!(matches!(
+            PhraseQuery::from_text(text_field, "a", &index, 0),
+            Err(crate::TantivyError::InvalidArgument(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_phrase_query_terms_with_offsets() {
+        let field = Field::from_field_id(0);
+        let term_a = Term::from_field_text(field, "a");
+        let term_b = Term::from_field_text(field, "b");
+        let phrase_query =
+            PhraseQuery::new_with_offset(vec![(3, term_b.clone()), (1, term_a.clone())]);
+        assert_eq!(
+            phrase_query.phrase_terms_with_offsets(),
+            &[(1, term_a.clone()), (3, term_b.clone())]
+        );
+        assert_eq!(phrase_query.phrase_terms(), vec![term_a, term_b]);
+    }
 }
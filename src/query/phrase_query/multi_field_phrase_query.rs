@@ -0,0 +1,294 @@
+use super::PhraseScorer;
+use crate::core::searcher::Searcher;
+use crate::core::SegmentReader;
+use crate::docset::DocSet;
+use crate::fieldnorm::FieldNormReader;
+use crate::postings::{Postings, SegmentPostings};
+use crate::query::bm25::BM25Weight;
+use crate::query::explanation::does_not_match;
+use crate::query::{EmptyScorer, Explanation, Query, Scorer, Weight};
+use crate::schema::{Field, IndexRecordOption, Term};
+use crate::{DocId, Score, TantivyError};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// `MultiFieldPhraseQuery` matches a sequence of terms that may belong to different fields.
+///
+/// The positions of the fields are compared as if they were the positions of a
+/// single field. This is useful for fields whose positions are aligned, as when
+/// the tokens of a text are annotated in parallel fields: the query for
+/// `[word:"new", entity:"city"]` matches the documents where the word `new`
+/// is followed by a token tagged as a city.
+///
+/// An offset can be added to the positions of a field with
+/// [`set_field_offset`](#method.set_field_offset).
+///
+/// All of the fields require positions to be indexed. The phrases are scored
+/// with the fieldnorms of the field of the first term.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MultiFieldPhraseQuery {
+    phrase_terms: Vec<Term>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    field_offsets: BTreeMap<Field, u32>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    slop: u32,
+}
+
+impl MultiFieldPhraseQuery {
+    /// Creates a new `MultiFieldPhraseQuery` given a list of terms.
+    ///
+    /// There must be at least two terms. The offset of each term in the phrase
+    /// is its index in the vector.
+    pub fn new(phrase_terms: Vec<Term>) -> MultiFieldPhraseQuery {
+        assert!(
+            phrase_terms.len() > 1,
+            "A phrase query is required to have strictly more than one term."
+        );
+        MultiFieldPhraseQuery {
+            phrase_terms,
+            field_offsets: BTreeMap::new(),
+            slop: 0,
+        }
+    }
+
+    /// Adds `offset` to the positions of `field`, before they are compared
+    /// with the positions of the other fields.
+    ///
+    /// By default, the offset of a field is 0.
+    pub fn set_field_offset(&mut self, field: Field, offset: u32) {
+        self.field_offsets.insert(field, offset);
+    }
+
+    /// Sets the slop of the phrase query, i.e. the number of positions
+    /// allowed between two consecutive terms of the phrase.
+    ///
+    /// By default, the slop is 0, meaning that the terms must be adjacent.
+    pub fn set_slop(&mut self, slop: u32) {
+        self.slop = slop;
+    }
+
+    /// Returns the slop of the phrase query.
+    pub fn slop(&self) -> u32 {
+        self.slop
+    }
+
+    /// `Term`s in the phrase.
+    pub fn phrase_terms(&self) -> &[Term] {
+        &self.phrase_terms[..]
+    }
+
+    fn field_offset(&self, field: Field) -> u32 {
+        self.field_offsets.get(&field).cloned().unwrap_or(0)
+    }
+}
+
+impl Query for MultiFieldPhraseQuery {
+    fn weight(&self, searcher: &Searcher, scoring_enabled: bool) -> crate::Result<Box<dyn Weight>> {
+        let schema = searcher.schema();
+        for term in &self.phrase_terms {
+            let field_entry = schema.get_field_entry(term.field());
+            let has_positions = field_entry
+                .field_type()
+                .get_index_record_option()
+                .map(IndexRecordOption::has_positions)
+                .unwrap_or(false);
+            if !has_positions {
+                return Err(TantivyError::SchemaError(format!(
+                    "Applied phrase query on field {:?}, which does not have positions indexed",
+                    field_entry.name()
+                )));
+            }
+        }
+        let scoring_field = self.phrase_terms[0].field();
+        let similarity_weight =
+            BM25Weight::for_terms_of_fields(searcher, &self.phrase_terms, scoring_field)?;
+        let phrase_terms = self
+            .phrase_terms
+            .iter()
+            .map(|term| (self.field_offset(term.field()), term.clone()))
+            .collect();
+        Ok(Box::new(MultiFieldPhraseWeight {
+            phrase_terms,
+            scoring_field,
+            similarity_weight,
+            score_needed: scoring_enabled,
+            slop: self.slop,
+        }))
+    }
+
+    fn query_terms(&self, term_set: &mut BTreeSet<Term>) {
+        term_set.extend(self.phrase_terms.iter().cloned());
+    }
+}
+
+/// Postings whose positions are shifted by the offset of their field.
+struct FieldOffsetPostings {
+    postings: SegmentPostings,
+    field_offset: u32,
+}
+
+impl DocSet for FieldOffsetPostings {
+    fn advance(&mut self) -> DocId {
+        self.postings.advance()
+    }
+
+    fn seek(&mut self, target: DocId) -> DocId {
+        self.postings.seek(target)
+    }
+
+    fn doc(&self) -> DocId {
+        self.postings.doc()
+    }
+
+    fn size_hint(&self) -> u32 {
+        self.postings.size_hint()
+    }
+
+    fn docs_skipped(&self) -> u64 {
+        self.postings.docs_skipped()
+    }
+}
+
+impl Postings for FieldOffsetPostings {
+    fn term_freq(&self) -> u32 {
+        self.postings.term_freq()
+    }
+
+    fn positions_with_offset(&mut self, offset: u32, output: &mut Vec<u32>) {
+        self.postings
+            .positions_with_offset(offset + self.field_offset, output)
+    }
+}
+
+struct MultiFieldPhraseWeight {
+    // Offset of the field of each term, in the order of the phrase.
+    phrase_terms: Vec<(u32, Term)>,
+    scoring_field: Field,
+    similarity_weight: BM25Weight,
+    score_needed: bool,
+    slop: u32,
+}
+
+impl MultiFieldPhraseWeight {
+    fn fieldnorm_reader(&self, reader: &SegmentReader) -> crate::Result<FieldNormReader> {
+        reader.get_fieldnorms_reader(self.scoring_field)
+    }
+
+    fn phrase_scorer(
+        &self,
+        reader: &SegmentReader,
+        boost: Score,
+    ) -> crate::Result<Option<PhraseScorer<FieldOffsetPostings>>> {
+        let mut term_postings_list = Vec::with_capacity(self.phrase_terms.len());
+        for (ord, &(field_offset, ref term)) in self.phrase_terms.iter().enumerate() {
+            if let Some(postings) = reader
+                .inverted_index(term.field())?
+                .read_postings(term, IndexRecordOption::WithFreqsAndPositions)?
+            {
+                let postings = FieldOffsetPostings {
+                    postings,
+                    field_offset,
+                };
+                term_postings_list.push((ord, postings));
+            } else {
+                return Ok(None);
+            }
+        }
+        Ok(Some(PhraseScorer::new(
+            term_postings_list,
+            self.similarity_weight.boost_by(boost),
+            self.fieldnorm_reader(reader)?,
+            self.score_needed,
+            self.slop,
+        )))
+    }
+}
+
+impl Weight for MultiFieldPhraseWeight {
+    fn scorer(&self, reader: &SegmentReader, boost: Score) -> crate::Result<Box<dyn Scorer>> {
+        if let Some(scorer) = self.phrase_scorer(reader, boost)? {
+            Ok(Box::new(scorer))
+        } else {
+            Ok(Box::new(EmptyScorer))
+        }
+    }
+
+    fn explain(&self, reader: &SegmentReader, doc: DocId) -> crate::Result<Explanation> {
+        let mut scorer = if let Some(scorer) = self.phrase_scorer(reader, 1.0)? {
+            scorer
+        } else {
+            return Err(does_not_match(doc));
+        };
+        if scorer.seek(doc) != doc {
+            return Err(does_not_match(doc));
+        }
+        let fieldnorm_id = self.fieldnorm_reader(reader)?.fieldnorm_id(doc);
+        let mut explanation = Explanation::new("Phrase Scorer", scorer.score());
+        explanation.add_detail(
+            self.similarity_weight
+                .explain(fieldnorm_id, scorer.phrase_count()),
+        );
+        Ok(explanation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MultiFieldPhraseQuery;
+    use crate::collector::{Count, DocSetCollector};
+    use crate::query::Query;
+    use crate::schema::{Schema, STRING, TEXT};
+    use crate::{DocAddress, Index, TantivyError, Term};
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_multi_field_phrase_query() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let word = schema_builder.add_text_field("word", TEXT);
+        let entity = schema_builder.add_text_field("entity", TEXT);
+        let id = schema_builder.add_text_field("id", STRING);
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_for_tests()?;
+            index_writer.add_document(doc!(word => "born in new york", entity => "o o city city"));
+            index_writer.add_document(doc!(word => "new big york", entity => "o o city"));
+            index_writer.add_document(doc!(word => "york is new", entity => "city o o"));
+            index_writer.commit()?;
+        }
+        let searcher = index.reader()?.searcher();
+        let matching_docs = |query: &dyn Query| -> crate::Result<HashSet<DocAddress>> {
+            searcher.search(query, &DocSetCollector)
+        };
+
+        let mut query = MultiFieldPhraseQuery::new(vec![
+            Term::from_field_text(word, "new"),
+            Term::from_field_text(entity, "city"),
+        ]);
+        assert_eq!(
+            matching_docs(&query)?,
+            vec![DocAddress(0, 0)].into_iter().collect()
+        );
+        assert!(searcher.explain(&query, DocAddress(0, 0))?.value() > 0.0);
+        query.set_slop(1);
+        assert_eq!(matching_docs(&query)?.len(), 2);
+
+        // The word `york`, tagged as a city.
+        let mut query = MultiFieldPhraseQuery::new(vec![
+            Term::from_field_text(word, "york"),
+            Term::from_field_text(entity, "city"),
+        ]);
+        assert_eq!(searcher.search(&query, &Count)?, 0);
+        query.set_field_offset(entity, 1);
+        assert_eq!(searcher.search(&query, &Count)?, 3);
+
+        let query = MultiFieldPhraseQuery::new(vec![
+            Term::from_field_text(word, "new"),
+            Term::from_field_text(id, "city"),
+        ]);
+        assert!(matches!(
+            searcher.search(&query, &Count),
+            Err(TantivyError::SchemaError(_))
+        ));
+        Ok(())
+    }
+}
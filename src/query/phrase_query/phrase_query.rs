@@ -5,7 +5,9 @@ use crate::query::Query;
 use crate::query::Weight;
 use crate::schema::IndexRecordOption;
 use crate::schema::{Field, Term};
+use crate::{Index, TantivyError};
 use std::collections::BTreeSet;
+use std::fmt;
 
 /// `PhraseQuery` matches a specific sequence of words.
 ///
@@ -21,10 +23,17 @@ use std::collections::BTreeSet;
 /// Using a `PhraseQuery` on a field requires positions
 /// to be indexed for this field.
 ///
+/// A slop can be set, to allow for up to `slop` positions between
+/// two consecutive terms of the phrase.
+/// For instance, with a slop of 1, the phrase query for `"part job"`
+/// matches the first sentence above.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PhraseQuery {
     field: Field,
     phrase_terms: Vec<(usize, Term)>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    slop: u32,
 }
 
 impl PhraseQuery {
@@ -55,9 +64,54 @@ impl PhraseQuery {
         PhraseQuery {
             field,
             phrase_terms: terms,
+            slop: 0,
         }
     }
 
+    /// Creates a new `PhraseQuery` by tokenizing `text` with the analyzer
+    /// registered for `field` in `index`.
+    ///
+    /// The terms are given the positions of their tokens, so that the
+    /// tokens removed by the analyzer, e.g. stop words, leave a gap in the phrase.
+    ///
+    /// Returns an error if `field` is not a text field, or if `text`
+    /// produces fewer than two tokens.
+    pub fn from_text(
+        field: Field,
+        text: &str,
+        index: &Index,
+        slop: u32,
+    ) -> crate::Result<PhraseQuery> {
+        let tokenizer = index.tokenizer_for_field(field)?;
+        let mut terms: Vec<(usize, Term)> = Vec::new();
+        tokenizer.token_stream(text).process(&mut |token| {
+            terms.push((token.position, Term::from_field_text(field, &token.text)));
+        });
+        if terms.len() < 2 {
+            return Err(TantivyError::InvalidArgument(format!(
+                "A phrase query requires at least two terms, {:?} has {}",
+                text,
+                terms.len()
+            )));
+        }
+        let mut phrase_query = PhraseQuery::new_with_offset(terms);
+        phrase_query.set_slop(slop);
+        Ok(phrase_query)
+    }
+
+    /// Sets the slop of the phrase query, i.e. the number of positions
+    /// allowed between two consecutive terms of the phrase.
+    ///
+    /// By default, the slop is 0, meaning that the terms must be adjacent.
+    pub fn set_slop(&mut self, slop: u32) {
+        self.slop = slop;
+    }
+
+    /// Returns the slop of the phrase query.
+    pub fn slop(&self) -> u32 {
+        self.slop
+    }
+
     /// The `Field` this `PhraseQuery` is targeting.
     pub fn field(&self) -> Field {
         self.field
@@ -71,6 +125,11 @@ impl PhraseQuery {
             .collect::<Vec<Term>>()
     }
 
+    /// `Term`s in the phrase with their offsets, sorted by offset.
+    pub fn phrase_terms_with_offsets(&self) -> &[(usize, Term)] {
+        &self.phrase_terms[..]
+    }
+
     /// Returns the `PhraseWeight` for the given phrase query given a specific `searcher`.
     ///
     /// This function is the same as `.weight(...)` except it returns
@@ -100,10 +159,29 @@ impl PhraseQuery {
             self.phrase_terms.clone(),
             bm25_weight,
             scoring_enabled,
+            self.slop,
         ))
     }
 }
 
+/// Displays the query like `0:"part time"~1`.
+impl fmt::Display for PhraseQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:\"", self.field.field_id())?;
+        for (ord, (_, term)) in self.phrase_terms.iter().enumerate() {
+            if ord > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", String::from_utf8_lossy(term.value_bytes()))?;
+        }
+        write!(f, "\"")?;
+        if self.slop > 0 {
+            write!(f, "~{}", self.slop)?;
+        }
+        Ok(())
+    }
+}
+
 impl Query for PhraseQuery {
     /// Create the weight associated to a query.
     ///
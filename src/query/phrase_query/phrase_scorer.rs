@@ -40,17 +40,24 @@ impl<TPostings: Postings> DocSet for PostingsWithOffset<TPostings> {
     fn size_hint(&self) -> u32 {
         self.postings.size_hint()
     }
+
+    fn docs_skipped(&self) -> u64 {
+        self.postings.docs_skipped()
+    }
 }
 
 pub struct PhraseScorer<TPostings: Postings> {
     intersection_docset: Intersection<PostingsWithOffset<TPostings>, PostingsWithOffset<TPostings>>,
     num_terms: usize,
+    // Index, in `intersection_docset`, of the docset of each term of the phrase.
+    term_ords: Vec<usize>,
     left: Vec<u32>,
     right: Vec<u32>,
     phrase_count: u32,
     fieldnorm_reader: FieldNormReader,
     similarity_weight: BM25Weight,
     score_needed: bool,
+    slop: u32,
 }
 
 /// Returns true iff the two sorted array contain a common element
@@ -130,12 +137,46 @@ fn intersection(left: &mut [u32], right: &[u32]) -> usize {
     count
 }
 
+/// Intersects the sorted positions `left` of a term with the sorted
+/// positions `right` of the next term of the phrase, allowing up to `slop`
+/// positions between them, and outputs the resulting positions of the
+/// next term in `left`.
+///
+/// Returns the length of the intersection
+fn intersection_with_slop(left: &mut [u32], right: &[u32], slop: u32) -> usize {
+    let mut left_i = 0;
+    let mut right_i = 0;
+    let mut count = 0;
+    let left_len = left.len();
+    let right_len = right.len();
+    while left_i < left_len && right_i < right_len {
+        let left_val = left[left_i];
+        let right_val = right[right_i];
+        if left_val < right_val.saturating_sub(slop) {
+            left_i += 1;
+        } else if left_val <= right_val {
+            // Skips to the closest position of `left` preceding `right_val`.
+            while left_i + 1 < left_len && left[left_i + 1] <= right_val {
+                left_i += 1;
+            }
+            left[count] = right_val;
+            count += 1;
+            left_i += 1;
+            right_i += 1;
+        } else {
+            right_i += 1;
+        }
+    }
+    count
+}
+
 impl<TPostings: Postings> PhraseScorer<TPostings> {
     pub fn new(
         term_postings: Vec<(usize, TPostings)>,
         similarity_weight: BM25Weight,
         fieldnorm_reader: FieldNormReader,
         score_needed: bool,
+        slop: u32,
     ) -> PhraseScorer<TPostings> {
         let max_offset = term_postings
             .iter()
@@ -143,21 +184,35 @@ impl<TPostings: Postings> PhraseScorer<TPostings> {
             .max()
             .unwrap_or(0);
         let num_docsets = term_postings.len();
-        let postings_with_offsets = term_postings
+        let mut postings_with_offsets = term_postings
             .into_iter()
             .map(|(offset, postings)| {
                 PostingsWithOffset::new(postings, (max_offset - offset) as u32)
             })
+            .enumerate()
+            .collect::<Vec<_>>();
+        // `Intersection` orders its docsets by size hint. We sort them
+        // beforehand, so as to know where the docset of each term ends up.
+        postings_with_offsets.sort_by_key(|(_, postings)| postings.size_hint());
+        let mut term_ords = vec![0; num_docsets];
+        for (docset_ord, &(term_ord, _)) in postings_with_offsets.iter().enumerate() {
+            term_ords[term_ord] = docset_ord;
+        }
+        let postings_with_offsets = postings_with_offsets
+            .into_iter()
+            .map(|(_, postings)| postings)
             .collect::<Vec<_>>();
         let mut scorer = PhraseScorer {
             intersection_docset: Intersection::new(postings_with_offsets),
             num_terms: num_docsets,
+            term_ords,
             left: Vec::with_capacity(100),
             right: Vec::with_capacity(100),
             phrase_count: 0u32,
             similarity_weight,
             fieldnorm_reader,
             score_needed,
+            slop,
         };
         if scorer.doc() != TERMINATED && !scorer.phrase_match() {
             scorer.advance();
@@ -170,7 +225,11 @@ impl<TPostings: Postings> PhraseScorer<TPostings> {
     }
 
     fn phrase_match(&mut self) -> bool {
-        if self.score_needed {
+        if self.slop > 0 {
+            let count = self.compute_sloppy_phrase_count();
+            self.phrase_count = count;
+            count > 0u32
+        } else if self.score_needed {
             let count = self.compute_phrase_count();
             self.phrase_count = count;
             count > 0u32
@@ -226,6 +285,27 @@ impl<TPostings: Postings> PhraseScorer<TPostings> {
             .positions(&mut self.right);
         intersection_count(&self.left[..intersection_len], &self.right[..]) as u32
     }
+
+    fn compute_sloppy_phrase_count(&mut self) -> u32 {
+        self.intersection_docset
+            .docset_mut_specialized(self.term_ords[0])
+            .positions(&mut self.left);
+        let mut intersection_len = self.left.len();
+        for i in 1..self.num_terms {
+            self.intersection_docset
+                .docset_mut_specialized(self.term_ords[i])
+                .positions(&mut self.right);
+            intersection_len = intersection_with_slop(
+                &mut self.left[..intersection_len],
+                &self.right[..],
+                self.slop,
+            );
+            if intersection_len == 0 {
+                return 0u32;
+            }
+        }
+        intersection_len as u32
+    }
 }
 
 impl<TPostings: Postings> DocSet for PhraseScorer<TPostings> {
@@ -254,6 +334,10 @@ impl<TPostings: Postings> DocSet for PhraseScorer<TPostings> {
     fn size_hint(&self) -> u32 {
         self.intersection_docset.size_hint()
     }
+
+    fn docs_skipped(&self) -> u64 {
+        self.intersection_docset.docs_skipped()
+    }
 }
 
 impl<TPostings: Postings> Scorer for PhraseScorer<TPostings> {
@@ -267,7 +351,7 @@ impl<TPostings: Postings> Scorer for PhraseScorer<TPostings> {
 
 #[cfg(test)]
 mod tests {
-    use super::{intersection, intersection_count};
+    use super::{intersection, intersection_count, intersection_with_slop};
 
     fn test_intersection_sym(left: &[u32], right: &[u32], expected: &[u32]) {
         test_intersection_aux(left, right, expected);
@@ -290,6 +374,22 @@ mod tests {
         test_intersection_sym(&[5, 7], &[1, 5, 10, 12], &[5]);
         test_intersection_sym(&[1, 5, 6, 9, 10, 12], &[6, 8, 9, 12], &[6, 9, 12]);
     }
+
+    fn test_intersection_with_slop_aux(left: &[u32], right: &[u32], slop: u32, expected: &[u32]) {
+        let mut left_vec = Vec::from(left);
+        let count = intersection_with_slop(&mut left_vec[..], right, slop);
+        assert_eq!(&left_vec[..count], expected);
+    }
+
+    #[test]
+    fn test_intersection_with_slop() {
+        test_intersection_with_slop_aux(&[1], &[1], 0, &[1]);
+        test_intersection_with_slop_aux(&[1], &[2], 0, &[]);
+        test_intersection_with_slop_aux(&[1], &[2], 1, &[2]);
+        test_intersection_with_slop_aux(&[2], &[1], 1, &[]);
+        test_intersection_with_slop_aux(&[1, 2, 3], &[3, 10], 2, &[3]);
+        test_intersection_with_slop_aux(&[1, 6, 9], &[3, 7, 12], 2, &[3, 7]);
+    }
 }
 
 #[cfg(all(test, feature = "unstable"))]
@@ -16,6 +16,7 @@ pub struct PhraseWeight {
     phrase_terms: Vec<(usize, Term)>,
     similarity_weight: BM25Weight,
     score_needed: bool,
+    slop: u32,
 }
 
 impl PhraseWeight {
@@ -24,11 +25,13 @@ impl PhraseWeight {
         phrase_terms: Vec<(usize, Term)>,
         similarity_weight: BM25Weight,
         score_needed: bool,
+        slop: u32,
     ) -> PhraseWeight {
         PhraseWeight {
             phrase_terms,
             similarity_weight,
             score_needed,
+            slop,
         }
     }
 
@@ -61,6 +64,7 @@ impl PhraseWeight {
                 similarity_weight,
                 fieldnorm_reader,
                 self.score_needed,
+                self.slop,
             )))
         } else {
             let mut term_postings_list = Vec::new();
@@ -79,6 +83,7 @@ impl PhraseWeight {
                 similarity_weight,
                 fieldnorm_reader,
                 self.score_needed,
+                self.slop,
             )))
         }
     }
@@ -140,4 +145,24 @@ mod tests {
         assert_eq!(phrase_scorer.phrase_count(), 1);
         assert_eq!(phrase_scorer.advance(), TERMINATED);
     }
+
+    #[test]
+    pub fn test_sloppy_phrase_count() -> crate::Result<()> {
+        let index = create_index(&["a b a x b", "a x x b"]);
+        let text_field = index.schema().get_field("text").unwrap();
+        let searcher = index.reader()?.searcher();
+        let mut phrase_query = PhraseQuery::new(vec![
+            Term::from_field_text(text_field, "a"),
+            Term::from_field_text(text_field, "b"),
+        ]);
+        phrase_query.set_slop(1);
+        let phrase_weight = phrase_query.phrase_weight(&searcher, false)?;
+        let mut phrase_scorer = phrase_weight
+            .phrase_scorer(searcher.segment_reader(0u32), 1.0)?
+            .unwrap();
+        assert_eq!(phrase_scorer.doc(), 0);
+        assert_eq!(phrase_scorer.phrase_count(), 2);
+        assert_eq!(phrase_scorer.advance(), TERMINATED);
+        Ok(())
+    }
 }
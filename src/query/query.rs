@@ -1,6 +1,9 @@
 use super::Weight;
 use crate::core::searcher::Searcher;
 use crate::query::Explanation;
+use crate::query::{
+    BooleanQuery, BoostQuery, FuzzyTermQuery, PhraseQuery, RangeQuery, RegexQuery, TermQuery,
+};
 use crate::DocAddress;
 use crate::Term;
 use downcast_rs::impl_downcast;
@@ -69,6 +72,30 @@ pub trait Query: QueryClone + Send + Sync + downcast_rs::Downcast + fmt::Debug {
     /// Extract all of the terms associated to the query and insert them in the
     /// term set given in arguments.
     fn query_terms(&self, _term_set: &mut BTreeSet<Term>) {}
+
+    /// Returns a canonical form of the query, matching the same documents
+    /// with the same scores.
+    ///
+    /// Nested `BooleanQuery`s that are equivalent to their inlined clauses
+    /// are flattened, and duplicate `Should` clauses are merged into one
+    /// clause whose boost is the sum of their boosts.
+    /// Scores may only differ by the rounding of floating point additions.
+    ///
+    /// By default, the query is simply cloned.
+    fn normalize(&self) -> Box<dyn Query> {
+        self.box_clone()
+    }
+
+    /// Returns the key identifying the query in a
+    /// [`QueryCache`](../struct.QueryCache.html).
+    ///
+    /// Two queries with the same key must have the same `Weight`, so the key
+    /// has to reflect all of the parameters of the query.
+    ///
+    /// By default, the query has no key and its `Weight` is never cached.
+    fn cache_key(&self) -> Option<String> {
+        None
+    }
 }
 
 /// Implements `box_clone`.
@@ -98,6 +125,14 @@ impl Query for Box<dyn Query> {
     fn query_terms(&self, term_set: &mut BTreeSet<Term<Vec<u8>>>) {
         self.as_ref().query_terms(term_set);
     }
+
+    fn normalize(&self) -> Box<dyn Query> {
+        self.as_ref().normalize()
+    }
+
+    fn cache_key(&self) -> Option<String> {
+        self.as_ref().cache_key()
+    }
 }
 
 impl QueryClone for Box<dyn Query> {
@@ -107,3 +142,28 @@ impl QueryClone for Box<dyn Query> {
 }
 
 impl_downcast!(Query);
+
+/// Writes the compact, Lucene-like, representation of a query used by the
+/// `Display` implementations of the queries, like `+0:coffee 1:tea`.
+///
+/// Nested `BooleanQuery`s are wrapped in parentheses.
+/// The queries without a `Display` implementation are written with `Debug`.
+pub(crate) fn fmt_query(query: &dyn Query, f: &mut fmt::Formatter) -> fmt::Result {
+    if let Some(boolean_query) = query.downcast_ref::<BooleanQuery>() {
+        write!(f, "({})", boolean_query)
+    } else if let Some(term_query) = query.downcast_ref::<TermQuery>() {
+        write!(f, "{}", term_query)
+    } else if let Some(phrase_query) = query.downcast_ref::<PhraseQuery>() {
+        write!(f, "{}", phrase_query)
+    } else if let Some(range_query) = query.downcast_ref::<RangeQuery>() {
+        write!(f, "{}", range_query)
+    } else if let Some(fuzzy_query) = query.downcast_ref::<FuzzyTermQuery>() {
+        write!(f, "{}", fuzzy_query)
+    } else if let Some(regex_query) = query.downcast_ref::<RegexQuery>() {
+        write!(f, "{}", regex_query)
+    } else if let Some(boost_query) = query.downcast_ref::<BoostQuery>() {
+        write!(f, "{}", boost_query)
+    } else {
+        write!(f, "{:?}", query)
+    }
+}
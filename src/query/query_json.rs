@@ -0,0 +1,421 @@
+use crate::query::{
+    AllQuery, BooleanQuery, BoostQuery, DisjunctionMaxQuery, EmptyQuery, ExistsQuery,
+    FuzzyTermQuery, MissingQuery, Occur, PhraseQuery, Query, RangeQuery, TermQuery, TermSetQuery,
+    WildcardQuery,
+};
+use crate::{Score, TantivyError};
+use serde::de::Error as _;
+use serde::ser::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Serializable representation of a query.
+///
+/// `QueryJson` is the interchange format of the queries, used to store,
+/// cache or send a query without going through the `QueryParser`.
+/// It is internally tagged: the serialized object has a `type` key holding
+/// the kind of the query.
+///
+/// The terms are serialized as their raw bytes. A serialized query is
+/// therefore only meaningful for a schema with the same fields and types.
+///
+/// The `RegexQuery`, and the queries that are built around a function or
+/// a compiled automaton, cannot be converted into a `QueryJson`.
+///
+/// ```rust
+/// use tantivy::query::{BooleanQuery, Occur, Query, QueryJson, TermQuery};
+/// use tantivy::schema::{IndexRecordOption, Schema, TEXT};
+/// use tantivy::Term;
+///
+/// # fn main() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let title = schema_builder.add_text_field("title", TEXT);
+/// let _schema = schema_builder.build();
+///
+/// let term_query: Box<dyn Query> = Box::new(TermQuery::new(
+///     Term::from_field_text(title, "diary"),
+///     IndexRecordOption::Basic,
+/// ));
+/// let query = BooleanQuery::new(vec![(Occur::Must, term_query)]);
+///
+/// let query_json = QueryJson::from_query(&query)?;
+/// let json = serde_json::to_string(&query_json)?;
+/// let deserialized_query_json: QueryJson = serde_json::from_str(&json)?;
+/// let deserialized_query = deserialized_query_json.into_query();
+/// assert_eq!(format!("{:?}", deserialized_query), format!("{:?}", query));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum QueryJson {
+    /// See [`AllQuery`](./struct.AllQuery.html).
+    All,
+    /// See [`EmptyQuery`](./struct.EmptyQuery.html).
+    Empty,
+    /// See [`TermQuery`](./struct.TermQuery.html).
+    Term(TermQuery),
+    /// See [`TermSetQuery`](./struct.TermSetQuery.html).
+    TermSet(TermSetQuery),
+    /// See [`RangeQuery`](./struct.RangeQuery.html).
+    Range(RangeQuery),
+    /// See [`PhraseQuery`](./struct.PhraseQuery.html).
+    Phrase(PhraseQuery),
+    /// See [`FuzzyTermQuery`](./struct.FuzzyTermQuery.html).
+    Fuzzy(FuzzyTermQuery),
+    /// See [`WildcardQuery`](./struct.WildcardQuery.html).
+    Wildcard(WildcardQuery),
+    /// See [`ExistsQuery`](./struct.ExistsQuery.html).
+    Exists(ExistsQuery),
+    /// See [`MissingQuery`](./struct.MissingQuery.html).
+    Missing(MissingQuery),
+    /// See [`BooleanQuery`](./struct.BooleanQuery.html).
+    Boolean {
+        /// Clauses of the query.
+        subqueries: Vec<(Occur, QueryJson)>,
+        /// Minimum number of `Should` clauses a document is required to match.
+        #[serde(default)]
+        minimum_should_match: usize,
+    },
+    /// See [`BoostQuery`](./struct.BoostQuery.html).
+    Boost {
+        /// Boosted query.
+        query: Box<QueryJson>,
+        /// Boost factor.
+        boost: Score,
+    },
+    /// See [`DisjunctionMaxQuery`](./struct.DisjunctionMaxQuery.html).
+    DisjunctionMax {
+        /// Subqueries of the query.
+        disjuncts: Vec<QueryJson>,
+        /// Weight of the scores of the subqueries that are not the maximum.
+        #[serde(default)]
+        tie_breaker: Score,
+    },
+}
+
+impl QueryJson {
+    /// Builds the `QueryJson` representing `query`.
+    ///
+    /// Returns an error if `query`, or one of its subqueries,
+    /// does not have a serializable representation.
+    pub fn from_query(query: &dyn Query) -> crate::Result<QueryJson> {
+        if query.is::<AllQuery>() {
+            return Ok(QueryJson::All);
+        }
+        if query.is::<EmptyQuery>() {
+            return Ok(QueryJson::Empty);
+        }
+        if let Some(term_query) = query.downcast_ref::<TermQuery>() {
+            return Ok(QueryJson::Term(term_query.clone()));
+        }
+        if let Some(term_set_query) = query.downcast_ref::<TermSetQuery>() {
+            return Ok(QueryJson::TermSet(term_set_query.clone()));
+        }
+        if let Some(range_query) = query.downcast_ref::<RangeQuery>() {
+            return Ok(QueryJson::Range(range_query.clone()));
+        }
+        if let Some(phrase_query) = query.downcast_ref::<PhraseQuery>() {
+            return Ok(QueryJson::Phrase(phrase_query.clone()));
+        }
+        if let Some(fuzzy_query) = query.downcast_ref::<FuzzyTermQuery>() {
+            return Ok(QueryJson::Fuzzy(fuzzy_query.clone()));
+        }
+        if let Some(wildcard_query) = query.downcast_ref::<WildcardQuery>() {
+            return Ok(QueryJson::Wildcard(wildcard_query.clone()));
+        }
+        if let Some(exists_query) = query.downcast_ref::<ExistsQuery>() {
+            return Ok(QueryJson::Exists(exists_query.clone()));
+        }
+        if let Some(missing_query) = query.downcast_ref::<MissingQuery>() {
+            return Ok(QueryJson::Missing(missing_query.clone()));
+        }
+        if let Some(boolean_query) = query.downcast_ref::<BooleanQuery>() {
+            let subqueries = boolean_query
+                .clauses()
+                .iter()
+                .map(|(occur, subquery)| Ok((*occur, QueryJson::from_query(subquery.as_ref())?)))
+                .collect::<crate::Result<_>>()?;
+            return Ok(QueryJson::Boolean {
+                subqueries,
+                minimum_should_match: boolean_query.minimum_should_match(),
+            });
+        }
+        if let Some(boost_query) = query.downcast_ref::<BoostQuery>() {
+            return Ok(QueryJson::Boost {
+                query: Box::new(QueryJson::from_query(boost_query.query())?),
+                boost: boost_query.boost(),
+            });
+        }
+        if let Some(disjunction_max_query) = query.downcast_ref::<DisjunctionMaxQuery>() {
+            let disjuncts = disjunction_max_query
+                .disjuncts()
+                .iter()
+                .map(|disjunct| QueryJson::from_query(disjunct.as_ref()))
+                .collect::<crate::Result<_>>()?;
+            return Ok(QueryJson::DisjunctionMax {
+                disjuncts,
+                tie_breaker: disjunction_max_query.tie_breaker(),
+            });
+        }
+        Err(TantivyError::InvalidArgument(format!(
+            "The query {:?} cannot be serialized.",
+            query
+        )))
+    }
+
+    /// Builds the query represented by this `QueryJson`.
+    pub fn into_query(self) -> Box<dyn Query> {
+        match self {
+            QueryJson::All => Box::new(AllQuery),
+            QueryJson::Empty => Box::new(EmptyQuery),
+            QueryJson::Term(term_query) => Box::new(term_query),
+            // Rebuilding the query sorts its terms, as expected by its weight.
+            QueryJson::TermSet(term_set_query) => Box::new(TermSetQuery::new(
+                term_set_query.field(),
+                term_set_query.terms().to_vec(),
+            )),
+            QueryJson::Range(range_query) => Box::new(range_query),
+            QueryJson::Phrase(phrase_query) => Box::new(phrase_query),
+            QueryJson::Fuzzy(fuzzy_query) => Box::new(fuzzy_query),
+            QueryJson::Wildcard(wildcard_query) => Box::new(wildcard_query),
+            QueryJson::Exists(exists_query) => Box::new(exists_query),
+            QueryJson::Missing(missing_query) => Box::new(missing_query),
+            QueryJson::Boolean {
+                subqueries,
+                minimum_should_match,
+            } => Box::new(boolean_query(subqueries, minimum_should_match)),
+            QueryJson::Boost { query, boost } => Box::new(boost_query(*query, boost)),
+            QueryJson::DisjunctionMax {
+                disjuncts,
+                tie_breaker,
+            } => Box::new(disjunction_max_query(disjuncts, tie_breaker)),
+        }
+    }
+}
+
+fn boolean_query(subqueries: Vec<(Occur, QueryJson)>, minimum_should_match: usize) -> BooleanQuery {
+    let subqueries = subqueries
+        .into_iter()
+        .map(|(occur, subquery)| (occur, subquery.into_query()))
+        .collect();
+    BooleanQuery::with_minimum_should_match(subqueries, minimum_should_match)
+}
+
+fn boost_query(query: QueryJson, boost: Score) -> BoostQuery {
+    BoostQuery::new(query.into_query(), boost)
+}
+
+fn disjunction_max_query(disjuncts: Vec<QueryJson>, tie_breaker: Score) -> DisjunctionMaxQuery {
+    let disjuncts = disjuncts.into_iter().map(QueryJson::into_query).collect();
+    DisjunctionMaxQuery::with_tie_breaker(disjuncts, tie_breaker)
+}
+
+// The queries holding some subqueries are serialized as their `QueryJson`.
+
+impl Serialize for BooleanQuery {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        QueryJson::from_query(self)
+            .map_err(S::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for BooleanQuery {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<BooleanQuery, D::Error> {
+        match QueryJson::deserialize(deserializer)? {
+            QueryJson::Boolean {
+                subqueries,
+                minimum_should_match,
+            } => Ok(boolean_query(subqueries, minimum_should_match)),
+            _ => Err(D::Error::custom("expected a boolean query")),
+        }
+    }
+}
+
+impl Serialize for BoostQuery {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        QueryJson::from_query(self)
+            .map_err(S::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for BoostQuery {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<BoostQuery, D::Error> {
+        match QueryJson::deserialize(deserializer)? {
+            QueryJson::Boost { query, boost } => Ok(boost_query(*query, boost)),
+            _ => Err(D::Error::custom("expected a boost query")),
+        }
+    }
+}
+
+impl Serialize for DisjunctionMaxQuery {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        QueryJson::from_query(self)
+            .map_err(S::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for DisjunctionMaxQuery {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<DisjunctionMaxQuery, D::Error> {
+        match QueryJson::deserialize(deserializer)? {
+            QueryJson::DisjunctionMax {
+                disjuncts,
+                tie_breaker,
+            } => Ok(disjunction_max_query(disjuncts, tie_breaker)),
+            _ => Err(D::Error::custom("expected a disjunction max query")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QueryJson;
+    use crate::collector::TopDocs;
+    use crate::query::{
+        BooleanQuery, BoostQuery, DisjunctionMaxQuery, ExistsQuery, FuzzyTermQuery, MissingQuery,
+        Occur, PhraseQuery, Query, RangeQuery, RegexQuery, TermQuery, TermSetQuery, WildcardQuery,
+    };
+    use crate::schema::{IndexRecordOption, Schema, INDEXED, TEXT};
+    use crate::{Index, Term};
+
+    #[test]
+    fn test_query_json_round_trip() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let body = schema_builder.add_text_field("body", TEXT);
+        let year = schema_builder.add_u64_field("year", INDEXED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(
+            doc!(title => "The Name of the Wind", body => "a young wizard", year => 2007u64),
+        );
+        index_writer.add_document(
+            doc!(title => "The Wise Man's Fear", body => "the wizard grows old", year => 2011u64),
+        );
+        index_writer.add_document(
+            doc!(title => "The Wind in the Willows", body => "a toad", year => 1908u64),
+        );
+        index_writer.add_document(
+            doc!(title => "A Wizard of Earthsea", body => "a young wizard", year => 1968u64),
+        );
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+
+        let term = |field, text| Term::from_field_text(field, text);
+        let term_query = |field, text| -> Box<dyn Query> {
+            Box::new(TermQuery::new(
+                term(field, text),
+                IndexRecordOption::WithFreqs,
+            ))
+        };
+        let query = BooleanQuery::with_minimum_should_match(
+            vec![
+                (
+                    Occur::Should,
+                    Box::new(DisjunctionMaxQuery::with_tie_breaker(
+                        vec![term_query(title, "wind"), term_query(body, "wizard")],
+                        0.3,
+                    )),
+                ),
+                (
+                    Occur::Should,
+                    Box::new(BoostQuery::new(
+                        Box::new(PhraseQuery::new(vec![
+                            term(body, "young"),
+                            term(body, "wizard"),
+                        ])),
+                        2.0,
+                    )),
+                ),
+                (
+                    Occur::Should,
+                    Box::new(FuzzyTermQuery::new(term(title, "willow"), 1, true)),
+                ),
+                (
+                    Occur::Should,
+                    Box::new(TermSetQuery::new(
+                        title,
+                        vec![term(title, "fear"), term(title, "earthsea")],
+                    )),
+                ),
+                (Occur::Should, Box::new(WildcardQuery::new(body, "gr*"))),
+                (Occur::Should, Box::new(ExistsQuery::new(year))),
+                (Occur::MustNot, Box::new(MissingQuery::new(body))),
+                (
+                    Occur::MustNot,
+                    Box::new(RangeQuery::new_u64(year, 1900..1950)),
+                ),
+            ],
+            1,
+        );
+        let json = serde_json::to_string(&QueryJson::from_query(&query)?)?;
+        let query_json: QueryJson = serde_json::from_str(&json)?;
+        let deserialized_query = query_json.into_query();
+        assert_eq!(format!("{:?}", deserialized_query), format!("{:?}", query));
+
+        let top_docs = searcher.search(&query, &TopDocs::with_limit(10))?;
+        assert_eq!(top_docs.len(), 3);
+        assert_eq!(
+            searcher.search(&deserialized_query, &TopDocs::with_limit(10))?,
+            top_docs
+        );
+
+        let deserialized_boolean_query: BooleanQuery =
+            serde_json::from_str(&serde_json::to_string(&query)?)?;
+        assert_eq!(
+            searcher.search(&deserialized_boolean_query, &TopDocs::with_limit(10))?,
+            top_docs
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_json_format() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let all_but_title = BooleanQuery::new(vec![
+            (Occur::Should, Box::new(crate::query::AllQuery)),
+            (
+                Occur::MustNot,
+                Box::new(TermQuery::new(
+                    Term::from_field_text(title, "a"),
+                    IndexRecordOption::Basic,
+                )),
+            ),
+        ]);
+        assert_eq!(
+            serde_json::to_value(&all_but_title)?,
+            serde_json::json!({
+                "type": "Boolean",
+                "subqueries": [
+                    ["Should", { "type": "All" }],
+                    [
+                        "MustNot",
+                        {
+                            "type": "Term",
+                            "term": [0, 0, 0, 0, 97],
+                            "index_record_option": "basic"
+                        }
+                    ]
+                ],
+                "minimum_should_match": 0
+            })
+        );
+        assert!(serde_json::from_str::<BoostQuery>(r#"{"type": "All"}"#).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_json_unsupported_query() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let regex_query: Box<dyn Query> = Box::new(RegexQuery::from_pattern("a+", title)?);
+        assert!(QueryJson::from_query(regex_query.as_ref()).is_err());
+        let boosted_regex_query = BoostQuery::new(regex_query, 2.0);
+        assert!(QueryJson::from_query(&boosted_regex_query).is_err());
+        assert!(serde_json::to_string(&boosted_regex_query).is_err());
+        Ok(())
+    }
+}
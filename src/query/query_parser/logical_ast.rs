@@ -10,12 +10,20 @@ use std::ops::Bound;
 pub enum LogicalLiteral {
     Term(Term),
     Phrase(Vec<(usize, Term)>),
+    /// Phrase in which several terms may share the same position,
+    /// or whose terms may be apart from their positions by up to the given slop.
+    SpanPhrase(Vec<(usize, Vec<Term>)>, u32),
     Range {
         field: Field,
         value_type: Type,
         lower: Bound<Term>,
         upper: Bound<Term>,
     },
+    Wildcard {
+        field: Field,
+        pattern: String,
+    },
+    Exists(Field),
     All,
 }
 
@@ -76,11 +84,29 @@ impl fmt::Debug for LogicalLiteral {
         match *self {
             LogicalLiteral::Term(ref term) => write!(formatter, "{:?}", term),
             LogicalLiteral::Phrase(ref terms) => write!(formatter, "\"{:?}\"", terms),
+            LogicalLiteral::SpanPhrase(ref term_groups, slop) => {
+                write!(formatter, "\"{:?}\"", term_groups)?;
+                if slop > 0 {
+                    write!(formatter, "~{}", slop)?;
+                }
+                Ok(())
+            }
             LogicalLiteral::Range {
                 ref lower,
                 ref upper,
                 ..
             } => write!(formatter, "({:?} TO {:?})", lower, upper),
+            LogicalLiteral::Wildcard { field, ref pattern } => {
+                write!(
+                    formatter,
+                    "Wildcard(field={},pattern={:?})",
+                    field.field_id(),
+                    pattern
+                )
+            }
+            LogicalLiteral::Exists(field) => {
+                write!(formatter, "Exists(field={})", field.field_id())
+            }
             LogicalLiteral::All => write!(formatter, "*"),
         }
     }
@@ -3,3 +3,4 @@ mod query_parser;
 pub mod logical_ast;
 pub use self::query_parser::QueryParser;
 pub use self::query_parser::QueryParserError;
+pub use self::query_parser::Substitution;
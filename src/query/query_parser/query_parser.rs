@@ -2,30 +2,45 @@ use super::logical_ast::*;
 use crate::core::Index;
 use crate::query::BooleanQuery;
 use crate::query::EmptyQuery;
+use crate::query::ExistsQuery;
 use crate::query::Occur;
 use crate::query::PhraseQuery;
 use crate::query::Query;
 use crate::query::RangeQuery;
 use crate::query::TermQuery;
+use crate::query::WildcardQuery;
 use crate::query::{AllQuery, BoostQuery};
+use crate::query::{SpanNearQuery, SpanOrQuery, SpanTermQuery};
+use crate::schema::{ip_addr_to_ipv6, FieldType, Term, Type};
 use crate::schema::{Facet, IndexRecordOption};
 use crate::schema::{Field, Schema};
-use crate::schema::{FieldType, Term};
 use crate::tokenizer::TokenizerManager;
 use crate::Score;
+use crate::Searcher;
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::net::{AddrParseError, IpAddr, Ipv6Addr};
 use std::num::{ParseFloatError, ParseIntError};
 use std::ops::Bound;
 use std::str::FromStr;
 use tantivy_query_grammar::{UserInputAST, UserInputBound, UserInputLeaf};
 
+/// Pseudo field name of the exists queries, as in `_exists_:price`.
+const EXISTS_FIELD_NAME: &str = "_exists_";
+
 /// Possible error that may happen when parsing a query.
 #[derive(Debug, PartialEq, Eq, Error)]
 pub enum QueryParserError {
     /// Error in the query syntax
     #[error("Syntax Error")]
-    SyntaxError,
+    SyntaxError {
+        /// Byte offset in the query at which the parsing failed.
+        position: usize,
+        /// Description of what was expected at `position`, like `"digit"`.
+        expected: Vec<String>,
+        /// Character found at `position`, or `None` if the end of the query was reached.
+        got: Option<char>,
+    },
     /// `FieldDoesNotExist(field_name: String)`
     /// The query references a field that is not in the schema
     #[error("File does not exists: '{0:?}'")]
@@ -55,7 +70,10 @@ pub enum QueryParserError {
     FieldNotIndexed(String),
     /// A phrase query was requested for a field that does not
     /// have any positions indexed.
-    #[error("The field '{0:?}' does not have positions indexed")]
+    #[error(
+        "The field '{0}' does not have positions indexed: phrase queries require the field \
+         to be indexed with `IndexRecordOption::WithFreqsAndPositions`"
+    )]
     FieldDoesNotHavePositionsIndexed(String),
     /// The tokenizer for the given field is unknown
     /// The two argument strings are the name of the field, the name of the tokenizer
@@ -68,6 +86,14 @@ pub enum QueryParserError {
     /// The format for the date field is not RFC 3339 compliant.
     #[error("The date field has an invalid format")]
     DateFormatError(chrono::ParseError),
+    /// The query contains a term for an ip address field, but the value
+    /// is not a valid ip address.
+    #[error("Expected a valid ip address: '{0:?}'")]
+    ExpectedIpAddr(AddrParseError),
+    /// The query contains a CIDR block for an ip address field,
+    /// but its prefix length is invalid.
+    #[error("Invalid CIDR notation: '{0:?}'")]
+    InvalidCidr(String),
 }
 
 impl From<ParseIntError> for QueryParserError {
@@ -82,6 +108,12 @@ impl From<ParseFloatError> for QueryParserError {
     }
 }
 
+impl From<AddrParseError> for QueryParserError {
+    fn from(err: AddrParseError) -> QueryParserError {
+        QueryParserError::ExpectedIpAddr(err)
+    }
+}
+
 impl From<chrono::ParseError> for QueryParserError {
     fn from(err: chrono::ParseError) -> QueryParserError {
         QueryParserError::DateFormatError(err)
@@ -148,9 +180,24 @@ fn trim_ast(logical_ast: LogicalAST) -> Option<LogicalAST> {
 ///
 /// * must terms: By prepending a term by a `+`, a term can be made required for the search.
 ///
+/// * `+` and `-` also apply to groups: `+(coffee OR tea) cake` requires one of `coffee`
+///   or `tea`, and `-(milk sugar)` excludes the documents matching `milk` or `sugar`.
+///
 /// * phrase terms: Quoted terms become phrase searches on fields that have positions indexed.
 ///   e.g., `title:"Barack Obama"` will only find documents that have "barack" immediately followed
 ///   by "obama".
+///   A slop can be given to a phrase with the `~` suffix, e.g., `title:"Barack Obama"~2`
+///   will also find documents in which up to two words stand between "barack" and "obama".
+///   The terms must still appear in the order of the phrase.
+///   Sloppy phrases become a [`SpanNearQuery`](./struct.SpanNearQuery.html).
+///   Within a phrase, a standalone `*` stands for exactly one word, whatever it is:
+///   `title:"barack * obama"` matches "barack hussein obama" but not "barack obama".
+///   In a sloppy phrase, the wildcards are accounted for in the slop.
+///
+/// * synonyms: If the tokenizer of a field emits several tokens at the same position,
+///   as the [`SynonymFilter`](../tokenizer/struct.SynonymFilter.html) does, these tokens
+///   are searched as a [`SpanOrQuery`](./struct.SpanOrQuery.html), matching any of them.
+///   Phrases containing such tokens become a [`SpanNearQuery`](./struct.SpanNearQuery.html).
 ///
 /// * range terms: Range searches can be done by specifying the start and end bound. These can be
 ///   inclusive or exclusive. e.g., `title:[a TO c}` will find all documents whose title contains
@@ -159,12 +206,30 @@ fn trim_ast(logical_ast: LogicalAST) -> Option<LogicalAST> {
 ///
 /// * date values: The query parser supports rfc3339 formatted dates. For example "2002-10-02T15:00:00.05Z"
 ///
+/// * wildcard terms: Unquoted terms containing `*` or `?` are expanded against the terms
+///   of the field. `*` matches any sequence of characters, `?` matches exactly one character.
+///   e.g., `title:prog*` will match documents whose title contains `program` or `progress`.
+///   The parts of the pattern surrounding the wildcards go through the tokenizer of the field,
+///   so that `Prog*` will be lowercased if the field is lowercased.
+///   See [`WildcardQuery`](./struct.WildcardQuery.html).
+///
+/// * ip addresses: Ip address fields accept IPv4 and IPv6 addresses, as well as CIDR blocks.
+///   e.g., `ip:192.168.0.0/24` will match all of the addresses of the subnet.
+///   IPv6 addresses contain `:` and need to be quoted, e.g., `ip:"2001:db8::/32"`.
+///
+/// * exists query: `_exists_:price` will match all documents having at least one value
+///   in the `price` field. It can be negated to match the documents without any value,
+///   e.g., `* -_exists_:price`. See [`ExistsQuery`](./struct.ExistsQuery.html).
+///
 /// *  all docs query: A plain `*` will match all documents in the index.
 ///
 /// Parts of the queries can be boosted by appending `^boostfactor`.
 /// For instance, `"SRE"^2.0 OR devops^0.4` will boost documents containing `SRE` instead of
 /// devops. Negative boosts are not allowed.
 ///
+/// The boost of a term query can also be written after its field name:
+/// `title^3:coffee body:coffee` is the same as `title:coffee^3 body:coffee`.
+///
 /// It is also possible to define a boost for a some specific field, at the query parser level.
 /// (See [`set_boost(...)`](#method.set_field_boost) ). Typically you may want to boost a title
 /// field.
@@ -175,6 +240,77 @@ pub struct QueryParser {
     conjunction_by_default: bool,
     tokenizer_manager: TokenizerManager,
     boost: HashMap<Field, Score>,
+    field_aliases: HashMap<String, Vec<Field>>,
+}
+
+/// A term of a query replaced by the closest term of the index, by
+/// [`QueryParser::parse_with_suggestions`](./struct.QueryParser.html#method.parse_with_suggestions).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Substitution {
+    /// Term of the query, as produced by the tokenizer of the field.
+    pub original: String,
+    /// Term of the index replacing it.
+    pub suggested: String,
+    /// Field of the term.
+    pub field: Field,
+}
+
+/// Collects mutable references to all of the terms of the text
+/// matching parts of a logical AST.
+fn terms_mut<'a>(logical_ast: &'a mut LogicalAST, terms: &mut Vec<&'a mut Term>) {
+    match logical_ast {
+        LogicalAST::Clause(clauses) => {
+            for (_, sub_ast) in clauses.iter_mut() {
+                terms_mut(sub_ast, terms);
+            }
+        }
+        LogicalAST::Boost(sub_ast, _) => terms_mut(sub_ast, terms),
+        LogicalAST::Leaf(literal) => match &mut **literal {
+            LogicalLiteral::Term(term) => terms.push(term),
+            LogicalLiteral::Phrase(phrase_terms) => {
+                terms.extend(phrase_terms.iter_mut().map(|(_, term)| term));
+            }
+            LogicalLiteral::SpanPhrase(term_groups, _) => {
+                for (_, term_group) in term_groups.iter_mut() {
+                    terms.extend(term_group.iter_mut());
+                }
+            }
+            LogicalLiteral::Range { .. }
+            | LogicalLiteral::Wildcard { .. }
+            | LogicalLiteral::Exists(_)
+            | LogicalLiteral::All => {}
+        },
+    }
+}
+
+/// Expands a CIDR block (e.g. `192.168.0.0/24`) into the range
+/// spanning the first and the last addresses of the subnet.
+fn compute_cidr_range(field: Field, cidr: &str) -> Result<LogicalLiteral, QueryParserError> {
+    let invalid_cidr = || QueryParserError::InvalidCidr(cidr.to_string());
+    let slash_pos = cidr.find('/').ok_or_else(invalid_cidr)?;
+    let ip_addr = IpAddr::from_str(&cidr[..slash_pos])?;
+    let prefix_len = u32::from_str(&cidr[slash_pos + 1..]).map_err(|_| invalid_cidr())?;
+    let prefix_len = match ip_addr {
+        // IPv4 addresses are stored as IPv4-mapped IPv6 addresses.
+        IpAddr::V4(_) if prefix_len <= 32 => prefix_len + 96,
+        IpAddr::V6(_) if prefix_len <= 128 => prefix_len,
+        _ => return Err(invalid_cidr()),
+    };
+    let mask = u128::MAX.checked_shl(128 - prefix_len).unwrap_or(0u128);
+    let first_addr = u128::from(ip_addr_to_ipv6(ip_addr)) & mask;
+    let last_addr = first_addr | !mask;
+    Ok(LogicalLiteral::Range {
+        field,
+        value_type: Type::IpAddr,
+        lower: Bound::Included(Term::from_field_ip_addr(
+            field,
+            IpAddr::V6(Ipv6Addr::from(first_addr)),
+        )),
+        upper: Bound::Included(Term::from_field_ip_addr(
+            field,
+            IpAddr::V6(Ipv6Addr::from(last_addr)),
+        )),
+    })
 }
 
 fn all_negative(ast: &LogicalAST) -> bool {
@@ -203,6 +339,7 @@ impl QueryParser {
             tokenizer_manager,
             conjunction_by_default: false,
             boost: Default::default(),
+            field_aliases: Default::default(),
         }
     }
 
@@ -210,8 +347,18 @@ impl QueryParser {
     ///  * an index
     ///  * a set of default - fields used to search if no field is specifically defined
     ///   in the query.
+    ///
+    /// The field aliases registered with
+    /// [`Index::set_field_alias`](../struct.Index.html#method.set_field_alias)
+    /// are loaded from the last commit of the index.
     pub fn for_index(index: &Index, default_fields: Vec<Field>) -> QueryParser {
-        QueryParser::new(index.schema(), default_fields, index.tokenizers().clone())
+        let mut query_parser =
+            QueryParser::new(index.schema(), default_fields, index.tokenizers().clone());
+        match index.field_aliases() {
+            Ok(field_aliases) => query_parser.field_aliases = field_aliases,
+            Err(err) => warn!("Failed to load the field aliases of the index: {:?}", err),
+        }
+        query_parser
     }
 
     /// Set the default way to compose queries to a conjunction.
@@ -234,6 +381,15 @@ impl QueryParser {
         self.boost.insert(field, boost);
     }
 
+    /// Sets `alias` as an alias for `fields`.
+    ///
+    /// A query on the alias, like `content:coffee`, is expanded into the
+    /// union of the queries on each of the fields, like `title:coffee body:coffee`.
+    /// The fields of the schema take precedence over the aliases of the same name.
+    pub fn set_field_alias(&mut self, alias: &str, fields: Vec<Field>) {
+        self.field_aliases.insert(alias.to_string(), fields);
+    }
+
     /// Parse a query
     ///
     /// Note that `parse_query` returns an error if the input
@@ -245,14 +401,74 @@ impl QueryParser {
     /// Implementing a lenient mode for this query parser is tracked
     /// in [Issue 5](https://github.com/fulmicoton/tantivy/issues/5)
     pub fn parse_query(&self, query: &str) -> Result<Box<dyn Query>, QueryParserError> {
+        #[cfg(feature = "otel")]
+        let _span = crate::otel::start_parse_query_span(query);
         let logical_ast = self.parse_query_to_logical_ast(query)?;
         Ok(convert_to_query(logical_ast))
     }
 
+    /// Parse a query, replacing its misspelled terms by the closest
+    /// terms of the index.
+    ///
+    /// A term of a text field is considered misspelled if no document of `searcher`
+    /// contains it, in any of the fields it is searched in. It is then replaced
+    /// by the term of the same field within two edits with the highest document
+    /// frequency, as returned by
+    /// [`Searcher::suggest_corrections`](../struct.Searcher.html#method.suggest_corrections),
+    /// if any. The substitutions are returned along with the query,
+    /// so that they can be displayed as a "Did you mean" suggestion.
+    pub fn parse_with_suggestions(
+        &self,
+        searcher: &Searcher,
+        query: &str,
+    ) -> crate::Result<(Box<dyn Query>, Vec<Substitution>)> {
+        let mut logical_ast = self.parse_query_to_logical_ast(query)?;
+        let mut terms = Vec::new();
+        terms_mut(&mut logical_ast, &mut terms);
+        let mut doc_freqs = Vec::with_capacity(terms.len());
+        let mut known_texts = HashSet::new();
+        for term in &terms {
+            let doc_freq = searcher.doc_freq(term)?;
+            if doc_freq > 0 {
+                known_texts.insert(term.value_bytes().to_vec());
+            }
+            doc_freqs.push(doc_freq);
+        }
+        let mut substitutions: Vec<Substitution> = Vec::new();
+        for (term, doc_freq) in terms.into_iter().zip(doc_freqs) {
+            let field = term.field();
+            let value_type = self.schema.get_field_entry(field).field_type().value_type();
+            if doc_freq > 0 || value_type != Type::Str || known_texts.contains(term.value_bytes()) {
+                continue;
+            }
+            let original = term.text().to_string();
+            let suggested = match searcher.suggest_corrections(field, &original, 2, 1)?.pop() {
+                Some(suggested) => suggested,
+                None => continue,
+            };
+            *term = Term::from_field_text(field, &suggested);
+            if !substitutions.iter().any(|substitution| {
+                substitution.field == field && substitution.original == original
+            }) {
+                substitutions.push(Substitution {
+                    original,
+                    suggested,
+                    field,
+                });
+            }
+        }
+        Ok((convert_to_query(logical_ast), substitutions))
+    }
+
     /// Parse the user query into an AST.
     fn parse_query_to_logical_ast(&self, query: &str) -> Result<LogicalAST, QueryParserError> {
-        let user_input_ast =
-            tantivy_query_grammar::parse_query(query).map_err(|_| QueryParserError::SyntaxError)?;
+        let user_input_ast = tantivy_query_grammar::parse_query(query).map_err(|error| {
+            QueryParserError::SyntaxError {
+                position: error.position,
+                expected: error.expected,
+                got: error.got,
+            }
+        })?;
         self.compute_logical_ast(user_input_ast)
     }
 
@@ -262,6 +478,38 @@ impl QueryParser {
             .ok_or_else(|| QueryParserError::FieldDoesNotExist(String::from(field_name)))
     }
 
+    /// Returns the fields `field_name` is an alias for, unless it is the name of a field.
+    fn alias_fields(&self, field_name: &str) -> Option<&[Field]> {
+        if self.schema.get_field(field_name).is_some() {
+            return None;
+        }
+        self.field_aliases.get(field_name).map(|fields| &fields[..])
+    }
+
+    /// Resolves a field name that may be followed by a path within a json object field,
+    /// as in `attributes.author.name`.
+    ///
+    /// Returns the field and the json path, which is empty if `field_path` is a field name.
+    fn resolve_field_path<'a>(
+        &self,
+        field_path: &'a str,
+    ) -> Result<(Field, &'a str), QueryParserError> {
+        if let Some(field) = self.schema.get_field(field_path) {
+            return Ok((field, ""));
+        }
+        field_path
+            .match_indices('.')
+            .rev()
+            .find_map(|(offset, _)| {
+                let field = self.schema.get_field(&field_path[..offset])?;
+                match self.schema.get_field_entry(field).field_type() {
+                    FieldType::JsonObject(_) => Some((field, &field_path[offset + 1..])),
+                    _ => None,
+                }
+            })
+            .ok_or_else(|| QueryParserError::FieldDoesNotExist(String::from(field_path)))
+    }
+
     fn compute_logical_ast(
         &self,
         user_input_ast: UserInputAST,
@@ -323,11 +571,30 @@ impl QueryParser {
                                     option.tokenizer().to_string(),
                                 )
                             })?;
+                    let has_positions = field_type
+                        .get_index_record_option()
+                        .map(IndexRecordOption::has_positions)
+                        .unwrap_or(false);
+                    let wildcard_offsets = if has_positions {
+                        phrase_wildcard_offsets(phrase)
+                    } else {
+                        Vec::new()
+                    };
                     let mut terms: Vec<(usize, Term)> = Vec::new();
+                    let mut num_wildcard_tokens = 0;
                     let mut token_stream = tokenizer.token_stream(phrase);
                     token_stream.process(&mut |token| {
+                        if wildcard_offsets.contains(&token.offset_from) {
+                            // The tokenizer kept the wildcard as a token.
+                            num_wildcard_tokens += 1;
+                            return;
+                        }
+                        let num_wildcards = wildcard_offsets
+                            .iter()
+                            .filter(|&&offset| offset < token.offset_from)
+                            .count();
                         let term = Term::from_field_text(field, &token.text);
-                        terms.push((token.position, term));
+                        terms.push((token.position + num_wildcards - num_wildcard_tokens, term));
                     });
                     if terms.is_empty() {
                         Ok(vec![])
@@ -366,20 +633,149 @@ impl QueryParser {
                 let term = Term::from_field_bytes(field, &bytes);
                 Ok(vec![(0, term)])
             }
+            FieldType::IpAddr(_) => {
+                let ip_addr = IpAddr::from_str(phrase)?;
+                let term = Term::from_field_ip_addr(field, ip_addr);
+                Ok(vec![(0, term)])
+            }
+            FieldType::JsonObject(_) => self.compute_terms_for_json(field, "", phrase),
+            FieldType::GeoPoint(_) | FieldType::DenseVector(_) | FieldType::DocumentBoost(_) => {
+                Err(QueryParserError::FieldNotIndexed(
+                    field_entry.name().to_string(),
+                ))
+            }
+        }
+    }
+
+    /// Tokenizes `phrase` into terms of the string values at `json_path`
+    /// of a json object field.
+    fn compute_terms_for_json(
+        &self,
+        field: Field,
+        json_path: &str,
+        phrase: &str,
+    ) -> Result<Vec<(usize, Term)>, QueryParserError> {
+        let field_entry = self.schema.get_field_entry(field);
+        let option = match *field_entry.field_type() {
+            FieldType::JsonObject(ref json_object_options) => {
+                json_object_options.get_indexing_options()
+            }
+            _ => None,
         }
+        .ok_or_else(|| QueryParserError::FieldNotIndexed(field_entry.name().to_string()))?;
+        let tokenizer = self
+            .tokenizer_manager
+            .get(option.tokenizer())
+            .ok_or_else(|| {
+                QueryParserError::UnknownTokenizer(
+                    field_entry.name().to_string(),
+                    option.tokenizer().to_string(),
+                )
+            })?;
+        let mut terms: Vec<(usize, Term)> = Vec::new();
+        tokenizer.token_stream(phrase).process(&mut |token| {
+            let term = Term::from_json_path_text(field, json_path, &token.text);
+            terms.push((token.position, term));
+        });
+        if terms.len() > 1 && !option.index_option().has_positions() {
+            return Err(QueryParserError::FieldDoesNotHavePositionsIndexed(
+                field_entry.name().to_string(),
+            ));
+        }
+        Ok(terms)
+    }
+
+    /// Computes the query of a literal on the values at `json_path` of a json object field.
+    ///
+    /// A literal that is a number matches both the number and its text.
+    fn compute_logical_ast_for_json_leaf(
+        &self,
+        field: Field,
+        json_path: &str,
+        phrase: &str,
+        slop: u32,
+    ) -> Result<Option<LogicalAST>, QueryParserError> {
+        let terms = self.compute_terms_for_json(field, json_path, phrase)?;
+        let mut asts: Vec<LogicalAST> = Vec::new();
+        if let Some(text_literal) = terms_to_literal(terms, slop) {
+            asts.push(LogicalAST::Leaf(Box::new(text_literal)));
+        }
+        if let Ok(number) = serde_json::from_str::<serde_json::Number>(phrase) {
+            let term = Term::from_json_path_number(field, json_path, &number);
+            asts.push(LogicalAST::Leaf(Box::new(LogicalLiteral::Term(term))));
+        }
+        Ok(match asts.len() {
+            0 => None,
+            1 => asts.pop(),
+            _ => Some(LogicalAST::Clause(
+                asts.into_iter().map(|ast| (Occur::Should, ast)).collect(),
+            )),
+        })
     }
 
     fn compute_logical_ast_for_leaf(
         &self,
         field: Field,
         phrase: &str,
+        slop: u32,
     ) -> Result<Option<LogicalLiteral>, QueryParserError> {
+        if let FieldType::IpAddr(_) = self.schema.get_field_entry(field).field_type() {
+            if phrase.contains('/') {
+                return Ok(Some(compute_cidr_range(field, phrase)?));
+            }
+        }
         let terms = self.compute_terms_for_string(field, phrase)?;
-        match &terms[..] {
-            [] => Ok(None),
-            [(_, term)] => Ok(Some(LogicalLiteral::Term(term.clone()))),
-            _ => Ok(Some(LogicalLiteral::Phrase(terms.clone()))),
+        Ok(terms_to_literal(terms, slop))
+    }
+
+    /// Runs the parts of a wildcard pattern that are not wildcards
+    /// through the tokenizer of the field.
+    ///
+    /// Returns `None` if the field is not a text field.
+    fn compute_wildcard_pattern(
+        &self,
+        field: Field,
+        pattern: &str,
+    ) -> Result<Option<String>, QueryParserError> {
+        let field_entry = self.schema.get_field_entry(field);
+        let indexing_options = match *field_entry.field_type() {
+            FieldType::Str(ref str_options) => str_options.get_indexing_options(),
+            _ => return Ok(None),
+        };
+        let option = indexing_options
+            .ok_or_else(|| QueryParserError::FieldNotIndexed(field_entry.name().to_string()))?;
+        let tokenizer = self
+            .tokenizer_manager
+            .get(option.tokenizer())
+            .ok_or_else(|| {
+                QueryParserError::UnknownTokenizer(
+                    field_entry.name().to_string(),
+                    option.tokenizer().to_string(),
+                )
+            })?;
+        let normalize_segment = |segment: &str| {
+            let mut normalized_segment = String::new();
+            tokenizer.token_stream(segment).process(&mut |token| {
+                normalized_segment.push_str(&token.text);
+            });
+            if normalized_segment.is_empty() {
+                // e.g. the segment was a stop word.
+                segment.to_string()
+            } else {
+                normalized_segment
+            }
+        };
+        let mut normalized_pattern = String::with_capacity(pattern.len());
+        let mut segment_start = 0;
+        for (offset, c) in pattern.char_indices() {
+            if c == '*' || c == '?' {
+                normalized_pattern.push_str(&normalize_segment(&pattern[segment_start..offset]));
+                normalized_pattern.push(c);
+                segment_start = offset + 1;
+            }
         }
+        normalized_pattern.push_str(&normalize_segment(&pattern[segment_start..]));
+        Ok(Some(normalized_pattern))
     }
 
     fn default_occur(&self) -> Occur {
@@ -422,7 +818,10 @@ impl QueryParser {
                     Ok(Cow::from(&self.default_fields[..]))
                 }
             }
-            Some(ref field) => Ok(Cow::from(vec![self.resolve_field_name(&*field)?])),
+            Some(ref field) => match self.alias_fields(field) {
+                Some(alias_fields) => Ok(Cow::from(alias_fields)),
+                None => Ok(Cow::from(vec![self.resolve_field_name(field)?])),
+            },
         }
     }
 
@@ -458,29 +857,54 @@ impl QueryParser {
         leaf: UserInputLeaf,
     ) -> Result<LogicalAST, QueryParserError> {
         match leaf {
+            UserInputLeaf::Literal(literal)
+                if literal.field_name.as_deref() == Some(EXISTS_FIELD_NAME) =>
+            {
+                let field = self.resolve_field_name(&literal.phrase)?;
+                Ok(LogicalAST::Leaf(Box::new(LogicalLiteral::Exists(field))))
+            }
             UserInputLeaf::Literal(literal) => {
-                let term_phrases: Vec<(Field, String)> = match literal.field_name {
-                    Some(ref field_name) => {
-                        let field = self.resolve_field_name(field_name)?;
-                        vec![(field, literal.phrase.clone())]
-                    }
+                let term_phrases: Vec<(Field, &str, String)> = match literal.field_name {
+                    Some(ref field_name) => match self.alias_fields(field_name) {
+                        Some(alias_fields) => alias_fields
+                            .iter()
+                            .map(|&field| (field, "", literal.phrase.clone()))
+                            .collect(),
+                        None => {
+                            let (field, json_path) = self.resolve_field_path(field_name)?;
+                            vec![(field, json_path, literal.phrase.clone())]
+                        }
+                    },
                     None => {
                         if self.default_fields.is_empty() {
                             return Err(QueryParserError::NoDefaultFieldDeclared);
                         } else {
                             self.default_fields
                                 .iter()
-                                .map(|default_field| (*default_field, literal.phrase.clone()))
-                                .collect::<Vec<(Field, String)>>()
+                                .map(|default_field| (*default_field, "", literal.phrase.clone()))
+                                .collect::<Vec<(Field, &str, String)>>()
                         }
                     }
                 };
                 let mut asts: Vec<LogicalAST> = Vec::new();
-                for (field, phrase) in term_phrases {
-                    if let Some(ast) = self.compute_logical_ast_for_leaf(field, &phrase)? {
+                for (field, json_path, phrase) in term_phrases {
+                    let ast_opt = if let FieldType::JsonObject(_) =
+                        self.schema.get_field_entry(field).field_type()
+                    {
+                        self.compute_logical_ast_for_json_leaf(
+                            field,
+                            json_path,
+                            &phrase,
+                            literal.slop,
+                        )?
+                    } else {
+                        self.compute_logical_ast_for_leaf(field, &phrase, literal.slop)?
+                            .map(|leaf_literal| LogicalAST::Leaf(Box::new(leaf_literal)))
+                    };
+                    if let Some(ast) = ast_opt {
                         // Apply some field specific boost defined at the query parser level.
                         let boost = self.field_boost(field);
-                        asts.push(LogicalAST::Leaf(Box::new(ast)).boost(boost));
+                        asts.push(ast.boost(boost));
                     }
                 }
                 let result_ast: LogicalAST = if asts.len() == 1 {
@@ -491,6 +915,27 @@ impl QueryParser {
                 Ok(result_ast)
             }
             UserInputLeaf::All => Ok(LogicalAST::Leaf(Box::new(LogicalLiteral::All))),
+            UserInputLeaf::Wildcard { field, pattern } => {
+                let fields = self.resolved_fields(&field)?;
+                let mut asts: Vec<LogicalAST> = Vec::new();
+                for &field in fields.iter() {
+                    let literal_opt = match self.compute_wildcard_pattern(field, &pattern)? {
+                        Some(pattern) => Some(LogicalLiteral::Wildcard { field, pattern }),
+                        // Wildcards are only supported on text fields.
+                        None => self.compute_logical_ast_for_leaf(field, &pattern, 0)?,
+                    };
+                    if let Some(literal) = literal_opt {
+                        let boost = self.field_boost(field);
+                        asts.push(LogicalAST::Leaf(Box::new(literal)).boost(boost));
+                    }
+                }
+                let result_ast: LogicalAST = if asts.len() == 1 {
+                    asts.into_iter().next().unwrap()
+                } else {
+                    LogicalAST::Clause(asts.into_iter().map(|ast| (Occur::Should, ast)).collect())
+                };
+                Ok(result_ast)
+            }
             UserInputLeaf::Range {
                 field,
                 lower,
@@ -528,12 +973,83 @@ impl QueryParser {
     }
 }
 
+/// Builds the literal matching the terms of a phrase, as returned by the tokenizer.
+fn terms_to_literal(terms: Vec<(usize, Term)>, slop: u32) -> Option<LogicalLiteral> {
+    if let Some(term_groups) = group_terms_by_position(&terms) {
+        return Some(LogicalLiteral::SpanPhrase(term_groups, slop));
+    }
+    match &terms[..] {
+        [] => None,
+        [(_, term)] => Some(LogicalLiteral::Term(term.clone())),
+        // `PhraseQuery` requires its terms to be at their exact positions.
+        _ if slop > 0 => {
+            let term_groups = terms
+                .into_iter()
+                .map(|(position, term)| (position, vec![term]))
+                .collect();
+            Some(LogicalLiteral::SpanPhrase(term_groups, slop))
+        }
+        _ => Some(LogicalLiteral::Phrase(terms)),
+    }
+}
+
+/// Returns the byte offsets of the `*` words of a phrase,
+/// each of them standing for exactly one word.
+fn phrase_wildcard_offsets(phrase: &str) -> Vec<usize> {
+    phrase
+        .match_indices('*')
+        .map(|(offset, _)| offset)
+        .filter(|&offset| {
+            let previous_char = phrase[..offset].chars().next_back();
+            let next_char = phrase[offset + 1..].chars().next();
+            !matches!(previous_char, Some(c) if !c.is_whitespace())
+                && !matches!(next_char, Some(c) if !c.is_whitespace())
+        })
+        .collect()
+}
+
+/// Groups the terms sharing the same position, as emitted by the `SynonymFilter`
+/// for instance.
+///
+/// Returns `None` if all of the terms have a different position.
+fn group_terms_by_position(terms: &[(usize, Term)]) -> Option<Vec<(usize, Vec<Term>)>> {
+    let mut term_groups: BTreeMap<usize, Vec<Term>> = BTreeMap::new();
+    for (position, term) in terms {
+        let term_group = term_groups.entry(*position).or_default();
+        if !term_group.contains(term) {
+            term_group.push(term.clone());
+        }
+    }
+    if term_groups.len() == terms.len() {
+        return None;
+    }
+    Some(term_groups.into_iter().collect())
+}
+
+fn convert_span_phrase_to_query(term_groups: Vec<(usize, Vec<Term>)>, slop: u32) -> Box<dyn Query> {
+    let first_position = term_groups[0].0;
+    let last_position = term_groups[term_groups.len() - 1].0;
+    let mut clauses: Vec<SpanOrQuery> = term_groups
+        .into_iter()
+        .map(|(_, terms)| SpanOrQuery::new(terms.into_iter().map(SpanTermQuery::new).collect()))
+        .collect();
+    if clauses.len() == 1 {
+        return Box::new(clauses.pop().unwrap());
+    }
+    // Positions left empty (e.g. by a stop word filter) are accounted for in the slop.
+    let slop = (last_position - first_position + 1 - clauses.len()) as u32 + slop;
+    Box::new(SpanNearQuery::new(clauses, slop, true))
+}
+
 fn convert_literal_to_query(logical_literal: LogicalLiteral) -> Box<dyn Query> {
     match logical_literal {
         LogicalLiteral::Term(term) => Box::new(TermQuery::new(term, IndexRecordOption::WithFreqs)),
         LogicalLiteral::Phrase(term_with_offsets) => {
             Box::new(PhraseQuery::new_with_offset(term_with_offsets))
         }
+        LogicalLiteral::SpanPhrase(term_groups, slop) => {
+            convert_span_phrase_to_query(term_groups, slop)
+        }
         LogicalLiteral::Range {
             field,
             value_type,
@@ -542,6 +1058,10 @@ fn convert_literal_to_query(logical_literal: LogicalLiteral) -> Box<dyn Query> {
         } => Box::new(RangeQuery::new_term_bounds(
             field, value_type, &lower, &upper,
         )),
+        LogicalLiteral::Wildcard { field, pattern } => {
+            Box::new(WildcardQuery::new(field, &pattern))
+        }
+        LogicalLiteral::Exists(field) => Box::new(ExistsQuery::new(field)),
         LogicalLiteral::All => Box::new(AllQuery),
     }
 }
@@ -576,15 +1096,20 @@ mod test {
     use super::super::logical_ast::*;
     use super::QueryParser;
     use super::QueryParserError;
+    use super::Substitution;
+    use crate::collector::Count;
     use crate::query::Query;
     use crate::schema::Field;
     use crate::schema::{IndexRecordOption, TextFieldIndexing, TextOptions};
     use crate::schema::{Schema, Term, INDEXED, STORED, STRING, TEXT};
     use crate::tokenizer::{
-        LowerCaser, SimpleTokenizer, StopWordFilter, TextAnalyzer, TokenizerManager,
+        LowerCaser, SimpleTokenizer, StopWordFilter, SynonymFilter, SynonymMap, TextAnalyzer,
+        TokenizerManager,
     };
     use crate::Index;
     use matches::assert_matches;
+    use std::net::IpAddr;
+    use std::str::FromStr;
 
     fn make_schema() -> Schema {
         let mut schema_builder = Schema::builder();
@@ -608,6 +1133,13 @@ mod test {
         schema_builder.add_facet_field("facet");
         schema_builder.add_bytes_field("bytes", INDEXED);
         schema_builder.add_bytes_field("bytes_not_indexed", STORED);
+        let synonyms_indexing = TextFieldIndexing::default()
+            .set_tokenizer("en_with_synonyms")
+            .set_index_option(IndexRecordOption::WithFreqsAndPositions);
+        schema_builder.add_text_field(
+            "with_synonyms",
+            TextOptions::default().set_indexing_options(synonyms_indexing),
+        );
         schema_builder.build()
     }
 
@@ -624,6 +1156,16 @@ mod test {
                 .filter(LowerCaser)
                 .filter(StopWordFilter::remove(vec!["the".to_string()])),
         );
+        tokenizer_manager.register(
+            "en_with_synonyms",
+            TextAnalyzer::from(SimpleTokenizer)
+                .filter(LowerCaser)
+                .filter(StopWordFilter::remove(vec!["the".to_string()]))
+                .filter(SynonymFilter::new(
+                    SynonymMap::from_solr("sofa, couch").unwrap(),
+                    true,
+                )),
+        );
         QueryParser::new(schema, default_fields, tokenizer_manager)
     }
 
@@ -684,6 +1226,130 @@ mod test {
         );
     }
 
+    #[test]
+    pub fn test_parse_query_exists() {
+        test_parse_query_to_logical_ast_helper("_exists_:title", "Exists(field=0)", false);
+        test_parse_query_to_logical_ast_helper(
+            "-_exists_:signed title:a",
+            "(-Exists(field=2) Term(field=0,bytes=[97]))",
+            false,
+        );
+        assert_matches!(
+            parse_query_to_logical_ast("_exists_:nofield", false),
+            Err(QueryParserError::FieldDoesNotExist(_))
+        );
+    }
+
+    #[test]
+    pub fn test_parse_query_wildcard() {
+        test_parse_query_to_logical_ast_helper(
+            "Prog*",
+            "(Wildcard(field=0,pattern=\"prog*\") Wildcard(field=1,pattern=\"prog*\"))",
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "title:Te?t",
+            "Wildcard(field=0,pattern=\"te?t\")",
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "nottokenized:Te?t*",
+            "Wildcard(field=7,pattern=\"Te?t*\")",
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "with_stop_words:the*",
+            "Wildcard(field=8,pattern=\"the*\")",
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "+title:a*b -text:c",
+            "(+Wildcard(field=0,pattern=\"a*b\") -Term(field=1,bytes=[99]))",
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "title:\"prog*\"",
+            "Term(field=0,bytes=[112, 114, 111, 103])",
+            false,
+        );
+        assert_matches!(
+            parse_query_to_logical_ast("signed:1*", false),
+            Err(QueryParserError::ExpectedInt(_))
+        );
+        assert_matches!(
+            parse_query_to_logical_ast("notindexed_text:a*", false),
+            Err(QueryParserError::FieldNotIndexed(_))
+        );
+    }
+
+    #[test]
+    pub fn test_parse_query_wildcard_search() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title => "Programming in Rust"));
+        index_writer.add_document(doc!(title => "Progress report"));
+        index_writer.add_document(doc!(title => "A test"));
+        index_writer.add_document(doc!(title => "A text"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let query_parser = QueryParser::for_index(&index, vec![title]);
+        let count = |query: &str| -> crate::Result<usize> {
+            let query = query_parser.parse_query(query).unwrap();
+            searcher.search(&query, &crate::collector::Count)
+        };
+        assert_eq!(count("PROG*")?, 2);
+        assert_eq!(count("te?t")?, 2);
+        assert_eq!(count("te?t -*xt")?, 1);
+        assert_eq!(count("prog* AND rust")?, 1);
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_parse_with_suggestions() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let body = schema_builder.add_text_field("body", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title => "Programming in Rust", body => "memory safety"));
+        index_writer.add_document(doc!(title => "Rust for beginners", body => "ownership"));
+        index_writer.add_document(doc!(title => "Rest APIs", body => "http"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let query_parser = QueryParser::for_index(&index, vec![title, body]);
+        let count = |query: &dyn Query| searcher.search(query, &crate::collector::Count);
+
+        let (query, substitutions) =
+            query_parser.parse_with_suggestions(&searcher, "+progamming +Rust")?;
+        assert_eq!(
+            substitutions,
+            vec![Substitution {
+                original: "progamming".to_string(),
+                suggested: "programming".to_string(),
+                field: title,
+            }]
+        );
+        assert_eq!(count(query.as_ref())?, 1);
+
+        // "rust" is in the title, so it is not replaced in the body.
+        let (query, substitutions) = query_parser.parse_with_suggestions(&searcher, "rust")?;
+        assert!(substitutions.is_empty());
+        assert_eq!(count(query.as_ref())?, 2);
+
+        let (query, substitutions) =
+            query_parser.parse_with_suggestions(&searcher, "body:\"memry safety\"")?;
+        assert_eq!(substitutions.len(), 1);
+        assert_eq!(substitutions[0].suggested, "memory");
+        assert_eq!(count(query.as_ref())?, 1);
+
+        let (query, substitutions) = query_parser.parse_with_suggestions(&searcher, "zzzzzz")?;
+        assert!(substitutions.is_empty());
+        assert_eq!(count(query.as_ref())?, 0);
+        Ok(())
+    }
+
     #[test]
     pub fn test_parse_query_with_default_boost_and_custom_boost() {
         let mut query_parser = make_query_parser();
@@ -697,6 +1363,20 @@ mod test {
         );
     }
 
+    #[test]
+    pub fn test_parse_query_with_field_boost_syntax() {
+        test_parse_query_to_logical_ast_helper(
+            "title^3:a text^0.5:a",
+            "(Term(field=0,bytes=[97])^3 Term(field=1,bytes=[97])^0.5)",
+            false,
+        );
+        let query_parser = make_query_parser();
+        assert_eq!(
+            format!("{:?}", query_parser.parse_query("text^2:hello").unwrap()),
+            format!("{:?}", query_parser.parse_query("text:hello^2").unwrap())
+        );
+    }
+
     #[test]
     pub fn test_parse_nonindexed_field_yields_error() {
         let query_parser = make_query_parser();
@@ -826,6 +1506,49 @@ mod test {
         assert!(matches!(base64_err, QueryParserError::ExpectedBase64(_)));
     }
 
+    #[test]
+    pub fn test_parse_query_ip_addr_search() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let ip = schema_builder.add_ip_addr_field("ip", INDEXED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        for ip_addr in &["192.168.0.1", "192.168.0.255", "192.168.1.1", "2001:db8::1"] {
+            index_writer.add_document(doc!(ip => IpAddr::from_str(ip_addr).unwrap()));
+        }
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let query_parser = QueryParser::for_index(&index, vec![ip]);
+        let count = |query: &str| -> crate::Result<usize> {
+            let query = query_parser.parse_query(query).unwrap();
+            searcher.search(&query, &crate::collector::Count)
+        };
+        assert_eq!(count("192.168.0.1")?, 1);
+        assert_eq!(count("\"::ffff:192.168.0.1\"")?, 1);
+        assert_eq!(count("ip:192.168.0.0/24")?, 2);
+        assert_eq!(count("ip:192.168.0.0/16")?, 3);
+        assert_eq!(count("ip:0.0.0.0/0")?, 3);
+        assert_eq!(count("ip:\"2001:db8::/32\"")?, 1);
+        assert_eq!(count("ip:\"::/0\"")?, 4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_ip_addr_invalid() {
+        let mut schema_builder = Schema::builder();
+        schema_builder.add_ip_addr_field("ip", INDEXED);
+        let query_parser = QueryParser::new(
+            schema_builder.build(),
+            Vec::new(),
+            TokenizerManager::default(),
+        );
+        let error = query_parser.parse_query("ip:192.168.0.0/33").unwrap_err();
+        assert!(matches!(error, QueryParserError::InvalidCidr(_)));
+        let error = query_parser.parse_query("ip:192.168.0.0/a").unwrap_err();
+        assert!(matches!(error, QueryParserError::InvalidCidr(_)));
+        let error = query_parser.parse_query("ip:192.168.0.256").unwrap_err();
+        assert!(matches!(error, QueryParserError::ExpectedIpAddr(_)));
+    }
+
     #[test]
     fn test_parse_query_to_ast_ab_c() {
         test_parse_query_to_logical_ast_helper(
@@ -940,6 +1663,29 @@ mod test {
         );
     }
 
+    #[test]
+    pub fn test_query_parser_syntax_error() {
+        let query_parser = make_query_parser();
+        let error = query_parser.parse_query("(title:a b").err().unwrap();
+        assert_eq!(error.to_string(), "Syntax Error");
+        assert_eq!(
+            error,
+            QueryParserError::SyntaxError {
+                position: 10,
+                expected: vec!["whitespace".to_string(), "`)`".to_string()],
+                got: None,
+            }
+        );
+        assert_matches!(
+            query_parser.parse_query("title:a)"),
+            Err(QueryParserError::SyntaxError {
+                position: 7,
+                got: Some(')'),
+                ..
+            })
+        );
+    }
+
     #[test]
     pub fn test_query_parser_field_not_indexed() {
         let query_parser = make_query_parser();
@@ -981,10 +1727,18 @@ mod test {
             .tokenizers()
             .register("customtokenizer", SimpleTokenizer);
         let query_parser = QueryParser::for_index(&index, vec![title]);
+        let query_parser_error = query_parser.parse_query("title:\"happy tax\"").unwrap_err();
         assert_eq!(
-            query_parser.parse_query("title:\"happy tax\"").unwrap_err(),
+            query_parser_error,
             QueryParserError::FieldDoesNotHavePositionsIndexed("title".to_string())
         );
+        assert_eq!(
+            query_parser_error.to_string(),
+            "The field 'title' does not have positions indexed: phrase queries require the \
+             field to be indexed with `IndexRecordOption::WithFreqsAndPositions`"
+        );
+        // A phrase made of a single term is a term query.
+        assert!(query_parser.parse_query("title:\"happy\"").is_ok());
     }
 
     #[test]
@@ -1018,6 +1772,132 @@ mod test {
             .is_ok());
     }
 
+    #[test]
+    pub fn test_parse_query_with_synonyms() {
+        test_parse_query_to_logical_ast_helper(
+            "with_synonyms:Sofa",
+            "\"[(0, [Term(field=14,bytes=[115, 111, 102, 97]), \
+             Term(field=14,bytes=[99, 111, 117, 99, 104])])]\"",
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "with_synonyms:chair",
+            "Term(field=14,bytes=[99, 104, 97, 105, 114])",
+            false,
+        );
+        let query_parser = make_query_parser();
+        let query = query_parser.parse_query("with_synonyms:sofa").unwrap();
+        assert!(format!("{:?}", query).starts_with("SpanOrQuery"));
+        let query = query_parser
+            .parse_query("with_synonyms:\"red the sofa\"")
+            .unwrap();
+        let query_str = format!("{:?}", query);
+        assert!(query_str.starts_with("SpanNearQuery"));
+        assert!(query_str.contains("slop: 1"));
+    }
+
+    #[test]
+    pub fn test_parse_query_phrase_slop() -> crate::Result<()> {
+        test_parse_query_to_logical_ast_helper(
+            "title:\"a b\"~2",
+            "\"[(0, [Term(field=0,bytes=[97])]), \
+             (1, [Term(field=0,bytes=[98])])]\"~2",
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "title:\"a b\"~0",
+            "\"[(0, Term(field=0,bytes=[97])), \
+             (1, Term(field=0,bytes=[98]))]\"",
+            false,
+        );
+        test_parse_query_to_logical_ast_helper("title:\"a\"~2", "Term(field=0,bytes=[97])", false);
+        let query_parser = make_query_parser();
+        assert_matches!(
+            query_parser.parse_query("title:\"a b\"~-1"),
+            Err(QueryParserError::SyntaxError { .. })
+        );
+        assert_matches!(
+            query_parser.parse_query("title:\"a b\"~1.5"),
+            Err(QueryParserError::SyntaxError { .. })
+        );
+        let query_str = format!(
+            "{:?}",
+            query_parser.parse_query("with_synonyms:\"red the sofa\"~2")?
+        );
+        assert!(query_str.starts_with("SpanNearQuery"));
+        assert!(query_str.contains("slop: 3"));
+
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title => "barack obama"));
+        index_writer.add_document(doc!(title => "barack hussein obama"));
+        index_writer.add_document(doc!(title => "obama barack"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let query_parser = QueryParser::for_index(&index, vec![title]);
+        let count = |query: &str| -> crate::Result<usize> {
+            let query = query_parser.parse_query(query).unwrap();
+            searcher.search(&query, &Count)
+        };
+        assert_eq!(count("\"barack obama\"")?, 1);
+        assert_eq!(count("\"barack obama\"~1")?, 2);
+        assert_eq!(count("title:\"barack obama\"~5")?, 2);
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_parse_query_phrase_wildcard() -> crate::Result<()> {
+        test_parse_query_to_logical_ast_helper(
+            "title:\"a * b\"",
+            "\"[(0, Term(field=0,bytes=[97])), \
+             (2, Term(field=0,bytes=[98]))]\"",
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "title:\"* a b * * c *\"",
+            "\"[(1, Term(field=0,bytes=[97])), \
+             (2, Term(field=0,bytes=[98])), \
+             (5, Term(field=0,bytes=[99]))]\"",
+            false,
+        );
+        // Stop words keep their position.
+        test_parse_query_to_logical_ast_helper(
+            "with_stop_words:\"a the * b\"",
+            "\"[(0, Term(field=8,bytes=[97])), \
+             (3, Term(field=8,bytes=[98]))]\"",
+            false,
+        );
+        // Fields without positions are not split.
+        test_parse_query_to_logical_ast_helper(
+            "nottokenized:\"a * b\"",
+            "Term(field=7,bytes=[97, 32, 42, 32, 98])",
+            false,
+        );
+
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title => "barack obama"));
+        index_writer.add_document(doc!(title => "barack hussein obama"));
+        index_writer.add_document(doc!(title => "barack hussein junior obama"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let query_parser = QueryParser::for_index(&index, vec![title]);
+        let count = |query: &str| -> crate::Result<usize> {
+            let query = query_parser.parse_query(query).unwrap();
+            searcher.search(&query, &Count)
+        };
+        assert_eq!(count("\"barack * obama\"")?, 1);
+        assert_eq!(count("\"barack * * obama\"")?, 1);
+        // In a sloppy phrase, wildcards widen the slop.
+        assert_eq!(count("\"barack * obama\"~1")?, 3);
+        assert_eq!(count("\"barack hussein * obama\"")?, 1);
+        Ok(())
+    }
+
     #[test]
     pub fn test_query_parser_not_empty_but_no_tokens() {
         let query_parser = make_query_parser();
@@ -1101,4 +1981,69 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn test_parse_query_field_alias() {
+        let mut query_parser = make_query_parser();
+        let title = query_parser.schema.get_field("title").unwrap();
+        let text = query_parser.schema.get_field("text").unwrap();
+        query_parser.set_field_alias("content", vec![title, text]);
+        query_parser.set_field_alias("title", vec![text]);
+        let parse = |query: &str| {
+            format!(
+                "{:?}",
+                query_parser.parse_query_to_logical_ast(query).unwrap()
+            )
+        };
+        assert_eq!(
+            parse("content:a"),
+            "(Term(field=0,bytes=[97]) Term(field=1,bytes=[97]))"
+        );
+        assert_eq!(
+            parse("+content:a -title:b"),
+            "(+(Term(field=0,bytes=[97]) Term(field=1,bytes=[97])) -Term(field=0,bytes=[98]))"
+        );
+        assert_eq!(
+            parse("content:a*"),
+            "(Wildcard(field=0,pattern=\"a*\") Wildcard(field=1,pattern=\"a*\"))"
+        );
+        assert_matches!(
+            query_parser.parse_query("contents:a"),
+            Err(QueryParserError::FieldDoesNotExist(_))
+        );
+    }
+
+    #[test]
+    fn test_index_field_alias() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let body = schema_builder.add_text_field("body", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        assert!(index.field_aliases()?.is_empty());
+        index.set_field_alias("content", &[title, body])?;
+        assert_matches!(
+            index.set_field_alias("title", &[body]),
+            Err(crate::TantivyError::InvalidArgument(_))
+        );
+        assert_matches!(
+            index.set_field_alias("empty", &[]),
+            Err(crate::TantivyError::InvalidArgument(_))
+        );
+        assert_eq!(index.field_aliases()?["content"], vec![title, body]);
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title => "coffee", body => "tea"));
+        index_writer.add_document(doc!(title => "tea", body => "coffee"));
+        index_writer.add_document(doc!(title => "tea", body => "milk"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let query_parser = QueryParser::for_index(&index, vec![title]);
+        let count = |query: &str| -> crate::Result<usize> {
+            searcher.search(&query_parser.parse_query(query).unwrap(), &Count)
+        };
+        assert_eq!(count("content:coffee")?, 2);
+        assert_eq!(count("title:coffee")?, 1);
+        assert_eq!(count("content:tea -content:milk")?, 2);
+        assert!(index.schema().get_field("content").is_none());
+        Ok(())
+    }
 }
@@ -1,17 +1,21 @@
-use crate::common::BitSet;
+use crate::common::{self, BitSet};
 use crate::core::Searcher;
 use crate::core::SegmentReader;
 use crate::error::TantivyError;
 use crate::query::explanation::does_not_match;
-use crate::query::ConstScorer;
 use crate::query::{BitSetDocSet, Explanation};
+use crate::query::{ConstScoreQuery, ConstScorer};
 use crate::query::{Query, Scorer, Weight};
-use crate::schema::Type;
-use crate::schema::{Field, IndexRecordOption, Term};
+use crate::schema::{ipv6_to_ip_addr, Type};
+use crate::schema::{Cardinality, Field, FieldType, IndexRecordOption, Term};
 use crate::termdict::{TermDictionary, TermStreamer};
-use crate::{DocId, Score};
+use crate::{DateTime, DocId, DocSet, Score};
+use byteorder::{BigEndian, ByteOrder};
+use std::cmp;
 use std::collections::Bound;
+use std::fmt;
 use std::io;
+use std::net::{IpAddr, Ipv6Addr};
 use std::ops::Range;
 
 fn map_bound<TFrom, TTo, Transform: Fn(&TFrom) -> TTo>(
@@ -26,6 +30,100 @@ fn map_bound<TFrom, TTo, Transform: Fn(&TFrom) -> TTo>(
     }
 }
 
+/// A type of value that can bound a [`RangeQuery`](./struct.RangeQuery.html)
+/// built with [`RangeQuery::new`](./struct.RangeQuery.html#method.new).
+///
+/// It is implemented for `u64`, `i64`, `f64`, `DateTime`, `IpAddr`, `&str` and `&[u8]`.
+pub trait RangeValue {
+    /// The type of the fields the value can be searched in.
+    fn value_type() -> Type;
+
+    /// Builds the term of `field` for the value.
+    fn to_term(&self, field: Field) -> Term;
+}
+
+impl RangeValue for u64 {
+    fn value_type() -> Type {
+        Type::U64
+    }
+
+    fn to_term(&self, field: Field) -> Term {
+        Term::from_field_u64(field, *self)
+    }
+}
+
+impl RangeValue for i64 {
+    fn value_type() -> Type {
+        Type::I64
+    }
+
+    fn to_term(&self, field: Field) -> Term {
+        Term::from_field_i64(field, *self)
+    }
+}
+
+impl RangeValue for f64 {
+    fn value_type() -> Type {
+        Type::F64
+    }
+
+    fn to_term(&self, field: Field) -> Term {
+        Term::from_field_f64(field, *self)
+    }
+}
+
+impl RangeValue for DateTime {
+    fn value_type() -> Type {
+        Type::Date
+    }
+
+    fn to_term(&self, field: Field) -> Term {
+        Term::from_field_date(field, self)
+    }
+}
+
+impl RangeValue for IpAddr {
+    fn value_type() -> Type {
+        Type::IpAddr
+    }
+
+    fn to_term(&self, field: Field) -> Term {
+        Term::from_field_ip_addr(field, *self)
+    }
+}
+
+impl RangeValue for &str {
+    fn value_type() -> Type {
+        Type::Str
+    }
+
+    fn to_term(&self, field: Field) -> Term {
+        Term::from_field_text(field, self)
+    }
+}
+
+impl RangeValue for &[u8] {
+    fn value_type() -> Type {
+        Type::Bytes
+    }
+
+    fn to_term(&self, field: Field) -> Term {
+        Term::from_field_bytes(field, self)
+    }
+}
+
+/// Returns the smallest byte string greater than all of the byte strings starting with `prefix`.
+fn prefix_upper_bound(prefix: &[u8]) -> Bound<Vec<u8>> {
+    let mut upper_bound = prefix.to_vec();
+    while let Some(last_byte) = upper_bound.pop() {
+        if last_byte < u8::MAX {
+            upper_bound.push(last_byte + 1);
+            return Bound::Excluded(upper_bound);
+        }
+    }
+    Bound::Unbounded
+}
+
 /// `RangeQuery` match all documents that have at least one term within a defined range.
 ///
 /// Matched document will all get a constant `Score` of one.
@@ -35,6 +133,15 @@ fn map_bound<TFrom, TTo, Transform: Fn(&TFrom) -> TTo>(
 /// The current implement will iterate over the terms within the range
 /// and append all of the document cross into a `BitSet`.
 ///
+/// When scores are not required, e.g. when the query is wrapped in a
+/// [`ConstScoreQuery`](./struct.ConstScoreQuery.html) by
+/// [`RangeQuery::as_filter`](#method.as_filter), a range over a single-valued
+/// `u64`, `i64`, `f64` or date fast field may scan its fast field column instead.
+/// This is the case if the range matches more than 1/8 of the documents of the segment,
+/// or if the field is not indexed.
+/// The range must however exclude the default value `0`, as this value
+/// is recorded in the fast field of the documents without any value.
+///
 /// # Example
 ///
 /// ```rust
@@ -67,6 +174,7 @@ fn map_bound<TFrom, TTo, Transform: Fn(&TFrom) -> TTo>(
 /// # assert!(test().is_ok());
 /// ```
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RangeQuery {
     field: Field,
     value_type: Type,
@@ -75,6 +183,61 @@ pub struct RangeQuery {
 }
 
 impl RangeQuery {
+    /// Writes a bound decoded according to the `value_type` of the query.
+    fn fmt_bound_value(&self, bytes: &[u8], f: &mut fmt::Formatter) -> fmt::Result {
+        match self.value_type {
+            Type::U64 if bytes.len() == 8 => write!(f, "{}", BigEndian::read_u64(bytes)),
+            Type::I64 | Type::Date if bytes.len() == 8 => {
+                write!(f, "{}", common::u64_to_i64(BigEndian::read_u64(bytes)))
+            }
+            Type::F64 if bytes.len() == 8 => {
+                write!(f, "{}", common::u64_to_f64(BigEndian::read_u64(bytes)))
+            }
+            Type::IpAddr if bytes.len() == 16 => {
+                let mut octets = [0u8; 16];
+                octets.copy_from_slice(bytes);
+                write!(f, "{}", ipv6_to_ip_addr(Ipv6Addr::from(octets)))
+            }
+            Type::Str => write!(f, "{}", String::from_utf8_lossy(bytes)),
+            _ => write!(f, "{:?}", bytes),
+        }
+    }
+
+    /// Creates a new `RangeQuery` over `field`, with bounds of any of the
+    /// [`RangeValue`](./trait.RangeValue.html) types.
+    ///
+    /// The type of the field is inferred from the type of the bounds, so that
+    /// open-ended ranges do not require to build any `Term`.
+    ///
+    /// If the field is not of the type of the bounds, an error is returned
+    /// when the `Weight` object is created.
+    ///
+    /// ```rust
+    /// use std::ops::Bound;
+    /// use tantivy::query::RangeQuery;
+    /// use tantivy::schema::{Schema, INDEXED};
+    ///
+    /// let mut schema_builder = Schema::builder();
+    /// let year_field = schema_builder.add_i64_field("year", INDEXED);
+    /// let _schema = schema_builder.build();
+    ///
+    /// let after_1960 = RangeQuery::new(year_field, Bound::Included(1960i64), Bound::Unbounded);
+    /// assert_eq!(after_1960.left_bound(), Bound::Included(tantivy::Term::from_field_i64(year_field, 1960)));
+    /// ```
+    pub fn new<T: RangeValue>(
+        field: Field,
+        left_bound: Bound<T>,
+        right_bound: Bound<T>,
+    ) -> RangeQuery {
+        let make_term_val = |val: &T| val.to_term(field).value_bytes().to_owned();
+        RangeQuery {
+            field,
+            value_type: T::value_type(),
+            left_bound: map_bound(&left_bound, &make_term_val),
+            right_bound: map_bound(&right_bound, &make_term_val),
+        }
+    }
+
     /// Creates a new `RangeQuery` from bounded start and end terms.
     ///
     /// If the value type is not correct, something may go terribly wrong when
@@ -196,6 +359,46 @@ impl RangeQuery {
         )
     }
 
+    /// Create a new `RangeQuery` over an ip address field.
+    ///
+    /// The two `Bound` arguments make it possible to create more complex
+    /// ranges than semi-inclusive range.
+    ///
+    /// IPv4 bounds are mapped to IPv4-mapped IPv6 addresses, so that
+    /// they compare correctly with the addresses of the field.
+    ///
+    /// If the field is not of the type `IpAddr`, tantivy
+    /// will panic when the `Weight` object is created.
+    pub fn new_ip_addr_bounds(
+        field: Field,
+        left_bound: Bound<IpAddr>,
+        right_bound: Bound<IpAddr>,
+    ) -> RangeQuery {
+        let make_term_val = |val: &IpAddr| {
+            Term::from_field_ip_addr(field, *val)
+                .value_bytes()
+                .to_owned()
+        };
+        RangeQuery {
+            field,
+            value_type: Type::IpAddr,
+            left_bound: map_bound(&left_bound, &make_term_val),
+            right_bound: map_bound(&right_bound, &make_term_val),
+        }
+    }
+
+    /// Create a new `RangeQuery` over an ip address field.
+    ///
+    /// If the field is not of the type `IpAddr`, tantivy
+    /// will panic when the `Weight` object is created.
+    pub fn new_ip_addr(field: Field, range: Range<IpAddr>) -> RangeQuery {
+        RangeQuery::new_ip_addr_bounds(
+            field,
+            Bound::Included(range.start),
+            Bound::Excluded(range.end),
+        )
+    }
+
     /// Create a new `RangeQuery` over a `Str` field.
     ///
     /// The two `Bound` arguments make it possible to create more complex
@@ -225,6 +428,52 @@ impl RangeQuery {
         )
     }
 
+    /// Create a new `RangeQuery` over an indexed `Bytes` field.
+    ///
+    /// The values are compared lexicographically, byte per byte. This makes it possible
+    /// to search over opaque binary keys, like UUIDs or composite keys.
+    ///
+    /// The two `Bound` arguments make it possible to create more complex
+    /// ranges than semi-inclusive range.
+    ///
+    /// If the field is not of the type `Bytes`, tantivy
+    /// will panic when the `Weight` object is created.
+    pub fn new_bytes_bounds(field: Field, left: Bound<&[u8]>, right: Bound<&[u8]>) -> RangeQuery {
+        let make_term_val = |val: &&[u8]| val.to_vec();
+        RangeQuery {
+            field,
+            value_type: Type::Bytes,
+            left_bound: map_bound(&left, &make_term_val),
+            right_bound: map_bound(&right, &make_term_val),
+        }
+    }
+
+    /// Create a new `RangeQuery` over an indexed `Bytes` field.
+    ///
+    /// If the field is not of the type `Bytes`, tantivy
+    /// will panic when the `Weight` object is created.
+    pub fn new_bytes(field: Field, range: Range<&[u8]>) -> RangeQuery {
+        RangeQuery::new_bytes_bounds(
+            field,
+            Bound::Included(range.start),
+            Bound::Excluded(range.end),
+        )
+    }
+
+    /// Create a new `RangeQuery` matching the values of an indexed `Bytes` field
+    /// starting with `prefix`.
+    ///
+    /// If the field is not of the type `Bytes`, tantivy
+    /// will panic when the `Weight` object is created.
+    pub fn new_bytes_prefix(field: Field, prefix: &[u8]) -> RangeQuery {
+        RangeQuery {
+            field,
+            value_type: Type::Bytes,
+            left_bound: Bound::Included(prefix.to_vec()),
+            right_bound: prefix_upper_bound(prefix),
+        }
+    }
+
     /// Field to search over
     pub fn field(&self) -> Field {
         self.field
@@ -243,16 +492,88 @@ impl RangeQuery {
             Term::from_field_bytes(self.field, bytes)
         })
     }
+
+    /// Wraps the query in a `ConstScoreQuery` with a score of `0`, to use it as a filter.
+    ///
+    /// Over a single-valued fast field, the documents are then matched
+    /// by scanning the fast field column.
+    pub fn as_filter(self) -> ConstScoreQuery {
+        ConstScoreQuery::new(Box::new(self), 0.0)
+    }
+
+    /// Returns the inclusive range of the `u64` representation of the values
+    /// in the fast field column of the field, if it can be scanned instead of the postings.
+    fn fast_field_range(&self, field_type: &FieldType) -> Option<(u64, u64)> {
+        let int_options = match field_type {
+            FieldType::U64(options)
+            | FieldType::I64(options)
+            | FieldType::F64(options)
+            | FieldType::Date(options) => options,
+            _ => return None,
+        };
+        if int_options.get_fastfield_cardinality() != Some(Cardinality::SingleValue) {
+            return None;
+        }
+        let default_value = match field_type {
+            FieldType::U64(_) => 0u64,
+            FieldType::F64(_) => common::f64_to_u64(0.0f64),
+            _ => common::i64_to_u64(0i64),
+        };
+        let lower = match self.left_bound {
+            Bound::Included(ref bytes) => BigEndian::read_u64(bytes),
+            Bound::Excluded(ref bytes) => BigEndian::read_u64(bytes).checked_add(1)?,
+            Bound::Unbounded => 0u64,
+        };
+        let upper = match self.right_bound {
+            Bound::Included(ref bytes) => BigEndian::read_u64(bytes),
+            Bound::Excluded(ref bytes) => BigEndian::read_u64(bytes).checked_sub(1)?,
+            Bound::Unbounded => u64::MAX,
+        };
+        if lower > upper || (lower <= default_value && default_value <= upper) {
+            return None;
+        }
+        Some((lower, upper))
+    }
+}
+
+/// Displays the query like Lucene does, like `0:[3 TO 5}`.
+///
+/// Inclusive bounds are displayed with square brackets, exclusive ones with
+/// curly braces, and unbounded ones as `*`.
+impl fmt::Display for RangeQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:", self.field.field_id())?;
+        match &self.left_bound {
+            Bound::Included(bytes) => {
+                write!(f, "[")?;
+                self.fmt_bound_value(bytes, f)?;
+            }
+            Bound::Excluded(bytes) => {
+                write!(f, "{{")?;
+                self.fmt_bound_value(bytes, f)?;
+            }
+            Bound::Unbounded => write!(f, "[*")?,
+        }
+        write!(f, " TO ")?;
+        match &self.right_bound {
+            Bound::Included(bytes) => {
+                self.fmt_bound_value(bytes, f)?;
+                write!(f, "]")
+            }
+            Bound::Excluded(bytes) => {
+                self.fmt_bound_value(bytes, f)?;
+                write!(f, "}}")
+            }
+            Bound::Unbounded => write!(f, "*]"),
+        }
+    }
 }
 
 impl Query for RangeQuery {
-    fn weight(
-        &self,
-        searcher: &Searcher,
-        _scoring_enabled: bool,
-    ) -> crate::Result<Box<dyn Weight>> {
+    fn weight(&self, searcher: &Searcher, scoring_enabled: bool) -> crate::Result<Box<dyn Weight>> {
         let schema = searcher.schema();
-        let value_type = schema.get_field_entry(self.field).field_type().value_type();
+        let field_type = schema.get_field_entry(self.field).field_type();
+        let value_type = field_type.value_type();
         if value_type != self.value_type {
             let err_msg = format!(
                 "Create a range query of the type {:?}, when the field given was of type {:?}",
@@ -260,10 +581,17 @@ impl Query for RangeQuery {
             );
             return Err(TantivyError::SchemaError(err_msg));
         }
+        let fast_field_range = if scoring_enabled {
+            None
+        } else {
+            self.fast_field_range(field_type)
+        };
         Ok(Box::new(RangeWeight {
             field: self.field,
             left_bound: self.left_bound.clone(),
             right_bound: self.right_bound.clone(),
+            fast_field_range,
+            is_indexed: schema.get_field_entry(self.field).is_indexed(),
         }))
     }
 }
@@ -272,6 +600,8 @@ pub struct RangeWeight {
     field: Field,
     left_bound: Bound<Vec<u8>>,
     right_bound: Bound<Vec<u8>>,
+    fast_field_range: Option<(u64, u64)>,
+    is_indexed: bool,
 }
 
 impl RangeWeight {
@@ -290,10 +620,65 @@ impl RangeWeight {
         };
         term_stream_builder.into_stream()
     }
-}
 
-impl Weight for RangeWeight {
-    fn scorer(&self, reader: &SegmentReader, boost: Score) -> crate::Result<Box<dyn Scorer>> {
+    /// Returns true iff the terms within the range appear in more than 1/8 of the
+    /// documents of the segment.
+    ///
+    /// Scanning the fast field column is then faster than reading their postings.
+    fn is_dense(&self, reader: &SegmentReader) -> crate::Result<bool> {
+        let threshold = u64::from(reader.max_doc() / 8);
+        let inverted_index = reader.inverted_index(self.field)?;
+        let mut term_range = self.term_range(inverted_index.terms())?;
+        let mut num_docs = 0u64;
+        while term_range.advance() {
+            num_docs += u64::from(term_range.value().doc_freq);
+            if num_docs > threshold {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Collects the documents whose fast field value is within `lower..=upper`.
+    fn fast_field_filter(
+        &self,
+        reader: &SegmentReader,
+        lower: u64,
+        upper: u64,
+    ) -> crate::Result<BitSet> {
+        const BUFFER_LEN: u32 = 1_024;
+        let max_doc = reader.max_doc();
+        let mut doc_bitset = BitSet::with_max_value(max_doc);
+        let fast_field_reader = reader
+            .fast_fields()
+            .typed_fast_field_reader::<u64>(self.field)?;
+        if fast_field_reader.max_value() < lower || fast_field_reader.min_value() > upper {
+            return Ok(doc_bitset);
+        }
+        let mut vals = vec![0u64; BUFFER_LEN as usize];
+        let mut start = 0u32;
+        while start < max_doc {
+            let len = cmp::min(BUFFER_LEN, max_doc - start);
+            let vals = &mut vals[..len as usize];
+            fast_field_reader.get_range(start, vals);
+            for (doc, &val) in (start..).zip(vals.iter()) {
+                if lower <= val && val <= upper {
+                    doc_bitset.insert(doc);
+                }
+            }
+            start += len;
+        }
+        Ok(doc_bitset)
+    }
+
+    /// Returns the set of the documents of the segment matching the range,
+    /// deleted documents included.
+    fn doc_bitset(&self, reader: &SegmentReader) -> crate::Result<BitSet> {
+        if let Some((lower, upper)) = self.fast_field_range {
+            if !self.is_indexed || self.is_dense(reader)? {
+                return self.fast_field_filter(reader, lower, upper);
+            }
+        }
         let max_doc = reader.max_doc();
         let mut doc_bitset = BitSet::with_max_value(max_doc);
 
@@ -315,10 +700,25 @@ impl Weight for RangeWeight {
                 block_segment_postings.advance();
             }
         }
-        let doc_bitset = BitSetDocSet::from(doc_bitset);
+        Ok(doc_bitset)
+    }
+}
+
+impl Weight for RangeWeight {
+    fn scorer(&self, reader: &SegmentReader, boost: Score) -> crate::Result<Box<dyn Scorer>> {
+        let doc_bitset = BitSetDocSet::from(self.doc_bitset(reader)?);
         Ok(Box::new(ConstScorer::new(doc_bitset, boost)))
     }
 
+    fn count(&self, reader: &SegmentReader) -> crate::Result<u32> {
+        let doc_bitset = self.doc_bitset(reader)?;
+        if let Some(delete_bitset) = reader.delete_bitset() {
+            Ok(BitSetDocSet::from(doc_bitset).count(delete_bitset))
+        } else {
+            Ok(doc_bitset.len() as u32)
+        }
+    }
+
     fn explain(&self, reader: &SegmentReader, doc: DocId) -> crate::Result<Explanation> {
         let mut scorer = self.scorer(reader, 1.0)?;
         if scorer.seek(doc) != doc {
@@ -332,11 +732,13 @@ impl Weight for RangeWeight {
 mod tests {
 
     use super::RangeQuery;
-    use crate::collector::{Count, TopDocs};
-    use crate::query::QueryParser;
-    use crate::schema::{Document, Field, Schema, INDEXED, TEXT};
-    use crate::Index;
-    use std::collections::Bound;
+    use crate::collector::{Count, DocSetCollector, TopDocs};
+    use crate::query::{Query, QueryParser};
+    use crate::schema::{Document, Field, Schema, FAST, INDEXED, STORED, STRING, TEXT};
+    use crate::{DocAddress, Index, TantivyError, Term};
+    use std::collections::{Bound, HashSet};
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+    use std::str::FromStr;
 
     #[test]
     fn test_range_query_simple() {
@@ -482,6 +884,109 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_range_query_new() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let int_field = schema_builder.add_i64_field("int", INDEXED);
+        let str_field = schema_builder.add_text_field("str", STRING);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        for (val, text) in (-10i64..10).zip(["a", "b", "c", "d"].iter().cycle()) {
+            index_writer.add_document(doc!(int_field => val, str_field => *text));
+        }
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let count = |range_query: RangeQuery| -> crate::Result<usize> {
+            searcher.search(&range_query, &Count)
+        };
+        assert_eq!(
+            count(RangeQuery::new(
+                int_field,
+                Bound::Excluded(0i64),
+                Bound::Unbounded
+            ))?,
+            9
+        );
+        assert_eq!(
+            count(RangeQuery::new(
+                int_field,
+                Bound::Unbounded,
+                Bound::Included(-5i64)
+            ))?,
+            6
+        );
+        assert_eq!(
+            count(RangeQuery::new::<i64>(
+                int_field,
+                Bound::Unbounded,
+                Bound::Unbounded
+            ))?,
+            20
+        );
+        assert_eq!(
+            count(RangeQuery::new(
+                str_field,
+                Bound::Included("c"),
+                Bound::Unbounded
+            ))?,
+            10
+        );
+        assert!(matches!(
+            count(RangeQuery::new(
+                int_field,
+                Bound::Included(3u64),
+                Bound::Unbounded
+            )),
+            Err(TantivyError::SchemaError(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_range_query_bytes() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let key_field = schema_builder.add_bytes_field("key", INDEXED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        for key in &[
+            &[0x01u8, 0x00][..],
+            &[0x01, 0xff, 0x00],
+            &[0x01, 0xff, 0xff],
+            &[0x02],
+            &[0xff, 0xff],
+        ] {
+            index_writer.add_document(doc!(key_field => *key));
+        }
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let count = |range_query: RangeQuery| -> crate::Result<usize> {
+            searcher.search(&range_query, &Count)
+        };
+        assert_eq!(
+            count(RangeQuery::new_bytes(
+                key_field,
+                &[0x01, 0xff][..]..&[0x02][..]
+            ))?,
+            2
+        );
+        assert_eq!(
+            count(RangeQuery::new(
+                key_field,
+                Bound::Excluded(&[0x01, 0x00][..]),
+                Bound::Included(&[0x02][..])
+            ))?,
+            3
+        );
+        assert_eq!(count(RangeQuery::new_bytes_prefix(key_field, &[0x01]))?, 3);
+        assert_eq!(
+            count(RangeQuery::new_bytes_prefix(key_field, &[0x01, 0xff]))?,
+            2
+        );
+        assert_eq!(count(RangeQuery::new_bytes_prefix(key_field, &[0xff]))?, 1);
+        assert_eq!(count(RangeQuery::new_bytes_prefix(key_field, &[]))?, 5);
+        Ok(())
+    }
+
     #[test]
     fn test_bug_reproduce_range_query() -> crate::Result<()> {
         let mut schema_builder = Schema::builder();
@@ -505,4 +1010,147 @@ mod tests {
         assert_eq!(top_docs.len(), 1);
         Ok(())
     }
+
+    #[test]
+    fn test_range_ip_addr() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let ip_field = schema_builder.add_ip_addr_field("ip", INDEXED | STORED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(ip_field => IpAddr::from([10, 0, 0, 1])));
+        index_writer.add_document(doc!(ip_field => IpAddr::from([10, 0, 0, 20])));
+        index_writer.add_document(doc!(ip_field => IpAddr::from([10, 0, 1, 1])));
+        index_writer.add_document(doc!(ip_field => IpAddr::from_str("2001:db8::1").unwrap()));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let count = |range_query: RangeQuery| searcher.search(&range_query, &Count).unwrap();
+        let ipv4 = |a, b, c, d| IpAddr::from([a, b, c, d]);
+        assert_eq!(
+            count(RangeQuery::new_ip_addr(
+                ip_field,
+                ipv4(10, 0, 0, 0)..ipv4(10, 0, 1, 0)
+            )),
+            2
+        );
+        // IPv4 addresses and their IPv4-mapped IPv6 counterpart are equivalent.
+        let mapped_lower = IpAddr::from(Ipv4Addr::new(10, 0, 0, 1).to_ipv6_mapped());
+        assert_eq!(
+            count(RangeQuery::new_ip_addr_bounds(
+                ip_field,
+                Bound::Included(mapped_lower),
+                Bound::Included(ipv4(10, 0, 1, 1))
+            )),
+            3
+        );
+        assert_eq!(
+            count(RangeQuery::new_ip_addr_bounds(
+                ip_field,
+                Bound::Excluded(ipv4(10, 0, 0, 1)),
+                Bound::Unbounded
+            )),
+            3
+        );
+        let query = RangeQuery::new_ip_addr_bounds(
+            ip_field,
+            Bound::Included(IpAddr::from_str("2001:db8::").unwrap()),
+            Bound::Unbounded,
+        );
+        let top_docs = searcher.search(&query, &TopDocs::with_limit(10))?;
+        assert_eq!(top_docs.len(), 1);
+        let doc = searcher.doc(top_docs[0].1)?;
+        assert_eq!(
+            doc.get_first(ip_field).unwrap().ip_addr_value(),
+            Some(Ipv6Addr::from_str("2001:db8::1").unwrap())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_range_query_as_filter() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let price = schema_builder.add_u64_field("price", INDEXED | FAST);
+        let temperature = schema_builder.add_i64_field("temperature", INDEXED | FAST);
+        let rank = schema_builder.add_u64_field("rank", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        for i in 0..300u64 {
+            index_writer.add_document(doc!(
+                price => i,
+                temperature => i as i64 - 150,
+                rank => i % 3,
+            ));
+        }
+        index_writer.add_document(doc!());
+        index_writer.commit()?;
+        index_writer.delete_term(Term::from_field_u64(price, 20));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        // `TopDocs` requires scores, so it goes through the postings.
+        let scored_docs = |query: &dyn Query| -> crate::Result<HashSet<DocAddress>> {
+            let top_docs = searcher.search(query, &TopDocs::with_limit(1_000))?;
+            Ok(top_docs.into_iter().map(|(_, doc)| doc).collect())
+        };
+        let queries = vec![
+            RangeQuery::new_u64(price, 10..30),
+            RangeQuery::new_u64(price, 0..30),
+            RangeQuery::new_u64_bounds(price, Bound::Excluded(250), Bound::Unbounded),
+            RangeQuery::new_u64(price, 500..600),
+            RangeQuery::new_i64(temperature, -100..-10),
+            RangeQuery::new_i64(temperature, -10..10),
+            RangeQuery::new_i64_bounds(temperature, Bound::Excluded(0), Bound::Included(100)),
+        ];
+        for query in queries {
+            let expected = scored_docs(&query)?;
+            let filter = query.as_filter();
+            assert_eq!(searcher.search(&filter, &DocSetCollector)?, expected);
+            assert_eq!(filter.count(&searcher)?, expected.len());
+            let top_docs = searcher.search(&filter, &TopDocs::with_limit(1_000))?;
+            assert_eq!(top_docs.len(), expected.len());
+            assert!(top_docs.iter().all(|(score, _)| *score == 0.0));
+        }
+        // Fast fields that are not indexed can only be filtered.
+        let ranked = RangeQuery::new_u64(rank, 1..3);
+        assert_eq!(
+            searcher.search(&ranked, &TopDocs::with_limit(1_000))?.len(),
+            0
+        );
+        // The deleted document of price 20 has a rank of 2.
+        assert_eq!(searcher.search(&ranked.as_filter(), &Count)?, 199);
+        Ok(())
+    }
+
+    #[test]
+    fn test_range_query_fast_field_range() {
+        let mut schema_builder = Schema::builder();
+        let price = schema_builder.add_u64_field("price", INDEXED | FAST);
+        let weight = schema_builder.add_f64_field("weight", FAST);
+        let year = schema_builder.add_u64_field("year", INDEXED);
+        let schema = schema_builder.build();
+        let fast_field_range = |query: RangeQuery| {
+            query.fast_field_range(schema.get_field_entry(query.field()).field_type())
+        };
+        assert_eq!(
+            fast_field_range(RangeQuery::new_u64(price, 10..20)),
+            Some((10, 19))
+        );
+        assert_eq!(
+            fast_field_range(RangeQuery::new_u64_bounds(
+                price,
+                Bound::Excluded(10),
+                Bound::Unbounded
+            )),
+            Some((11, u64::MAX))
+        );
+        // The range includes the default value of the documents without a value.
+        assert_eq!(fast_field_range(RangeQuery::new_u64(price, 0..20)), None);
+        assert_eq!(
+            fast_field_range(RangeQuery::new_f64(weight, -1.0..1.0)),
+            None
+        );
+        assert!(fast_field_range(RangeQuery::new_f64(weight, 1.0..2.0)).is_some());
+        // Empty range.
+        assert_eq!(fast_field_range(RangeQuery::new_u64(price, 20..20)), None);
+        // Not a fast field.
+        assert_eq!(fast_field_range(RangeQuery::new_u64(year, 10..20)), None);
+    }
 }
@@ -3,8 +3,10 @@ use crate::query::{AutomatonWeight, Query, Weight};
 use crate::schema::Field;
 use crate::Searcher;
 use std::clone::Clone;
+use std::collections::HashSet;
+use std::fmt;
 use std::sync::Arc;
-use tantivy_fst::Regex;
+use tantivy_fst::{Automaton, Regex};
 
 /// A Regex Query matches all of the documents
 /// containing a specific term that matches
@@ -63,6 +65,32 @@ impl RegexQuery {
         Ok(RegexQuery::from_regex(regex, field))
     }
 
+    /// Creates a new RegexQuery from a given pattern, rejecting the patterns
+    /// whose automaton has more than `max_states` states.
+    ///
+    /// Regexes such as `(a|b)*a(a|b){20}` compile to automata that are
+    /// exponentially larger than their pattern. Applications running regexes
+    /// supplied by their users can use this constructor to bound the cost of
+    /// a query.
+    ///
+    /// Independently of `max_states`, patterns that require more than 1,000
+    /// states are always rejected when they are compiled.
+    pub fn from_pattern_with_max_states(
+        regex_pattern: &str,
+        field: Field,
+        max_states: usize,
+    ) -> crate::Result<Self> {
+        let query = RegexQuery::from_pattern(regex_pattern, field)?;
+        let num_states = num_reachable_states(&query.regex, max_states);
+        if num_states > max_states {
+            return Err(TantivyError::InvalidArgument(format!(
+                "Regex {:?} requires more than {} automaton states.",
+                regex_pattern, max_states
+            )));
+        }
+        Ok(query)
+    }
+
     /// Creates a new RegexQuery from a fully built Regex
     pub fn from_regex<T: Into<Arc<Regex>>>(regex: T, field: Field) -> Self {
         RegexQuery {
@@ -76,6 +104,53 @@ impl RegexQuery {
     }
 }
 
+// Returns the number of states of the automaton reachable from its start state.
+//
+// The exploration stops as soon as more than `limit` states have been reached.
+fn num_reachable_states(regex: &Regex, limit: usize) -> usize {
+    let mut seen = HashSet::new();
+    let mut stack = vec![regex.start()];
+    while let Some(state) = stack.pop() {
+        if seen.len() > limit {
+            break;
+        }
+        if !regex.can_match(&state) || !seen.insert(state) {
+            continue;
+        }
+        for byte in 0..=255u8 {
+            stack.push(regex.accept(&state, byte));
+        }
+    }
+    seen.len()
+}
+
+/// Displays the query like `0:/co.*ee/`.
+impl fmt::Display for RegexQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}:/{}/",
+            self.field.field_id(),
+            regex_pattern(&self.regex)
+        )
+    }
+}
+
+// Returns the pattern the regex was built from.
+//
+// `tantivy_fst::Regex` only exposes its pattern through its `Debug` implementation,
+// whose first line is `Regex("<pattern>")`.
+fn regex_pattern(regex: &Regex) -> String {
+    let debug_str = format!("{:?}", regex);
+    let quoted_pattern = debug_str
+        .lines()
+        .next()
+        .and_then(|line| line.strip_prefix("Regex("))
+        .and_then(|line| line.strip_suffix(')'))
+        .unwrap_or("");
+    serde_json::from_str(quoted_pattern).unwrap_or_else(|_| quoted_pattern.to_string())
+}
+
 impl Query for RegexQuery {
     fn weight(
         &self,
@@ -88,12 +163,12 @@ impl Query for RegexQuery {
 
 #[cfg(test)]
 mod test {
-    use super::RegexQuery;
+    use super::{num_reachable_states, RegexQuery};
     use crate::assert_nearly_equals;
     use crate::collector::TopDocs;
     use crate::schema::TEXT;
     use crate::schema::{Field, Schema};
-    use crate::{Index, IndexReader};
+    use crate::{Index, IndexReader, TantivyError};
     use std::sync::Arc;
     use tantivy_fst::Regex;
 
@@ -174,4 +249,27 @@ mod test {
 
         verify_regex_query(matching_one, matching_zero, reader.clone());
     }
+
+    #[test]
+    pub fn test_regex_query_max_states() {
+        let (reader, field) = build_test_index();
+        let regex = Regex::new("jap[ao]n").unwrap();
+        // j, a, p, [ao], n and the final state.
+        assert_eq!(num_reachable_states(&regex, 100), 6);
+        assert_eq!(num_reachable_states(&regex, 2), 3);
+
+        let matching_one = RegexQuery::from_pattern_with_max_states("jap[ao]n", field, 6).unwrap();
+        let matching_zero =
+            RegexQuery::from_pattern_with_max_states("jap[A-Z]n", field, 6).unwrap();
+        verify_regex_query(matching_one, matching_zero, reader);
+
+        assert!(matches!(
+            RegexQuery::from_pattern_with_max_states("jap[ao]n", field, 5),
+            Err(TantivyError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            RegexQuery::from_pattern_with_max_states("(a|b)*a(a|b){5}", field, 20),
+            Err(TantivyError::InvalidArgument(_))
+        ));
+    }
 }
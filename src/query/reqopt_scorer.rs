@@ -58,6 +58,10 @@ where
     fn size_hint(&self) -> u32 {
         self.req_scorer.size_hint()
     }
+
+    fn docs_skipped(&self) -> u64 {
+        self.req_scorer.docs_skipped() + self.opt_scorer.docs_skipped()
+    }
 }
 
 impl<TReqScorer, TOptScorer, TScoreCombiner> Scorer
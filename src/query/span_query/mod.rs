@@ -0,0 +1,222 @@
+mod span_collector;
+mod span_near_query;
+mod span_near_scorer;
+mod span_near_weight;
+mod span_or_query;
+mod span_or_scorer;
+mod span_or_weight;
+mod span_term_query;
+mod union_postings;
+
+pub use self::span_collector::{Span, SpanCollector};
+pub use self::span_near_query::SpanNearQuery;
+pub use self::span_near_scorer::SpanNearScorer;
+pub use self::span_near_weight::SpanNearWeight;
+pub use self::span_or_query::SpanOrQuery;
+pub use self::span_or_scorer::SpanOrScorer;
+pub use self::span_or_weight::SpanOrWeight;
+pub use self::span_term_query::SpanTermQuery;
+pub use self::union_postings::UnionPostings;
+
+#[cfg(test)]
+mod tests {
+    use super::{SpanNearQuery, SpanOrQuery, SpanTermQuery};
+    use crate::collector::{Count, TopDocs};
+    use crate::query::{Query, QueryParser};
+    use crate::schema::{Schema, Term, STRING, TEXT};
+    use crate::tokenizer::{LowerCaser, SimpleTokenizer, SynonymFilter, SynonymMap, TextAnalyzer};
+    use crate::{DocAddress, Index};
+
+    fn create_index(texts: &[&'static str]) -> crate::Result<Index> {
+        let mut schema_builder = Schema::builder();
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        let mut index_writer = index.writer_for_tests()?;
+        for &text in texts {
+            index_writer.add_document(doc!(text_field => text));
+        }
+        index_writer.commit()?;
+        Ok(index)
+    }
+
+    fn span_near_query(index: &Index, words: &[&str], slop: u32, in_order: bool) -> SpanNearQuery {
+        let text_field = index.schema().get_field("text").unwrap();
+        let clauses = words
+            .iter()
+            .map(|word| SpanTermQuery::new(Term::from_field_text(text_field, word)))
+            .collect();
+        SpanNearQuery::new(clauses, slop, in_order)
+    }
+
+    fn span_or_query(index: &Index, words: &[&str]) -> SpanOrQuery {
+        let text_field = index.schema().get_field("text").unwrap();
+        let clauses = words
+            .iter()
+            .map(|word| SpanTermQuery::new(Term::from_field_text(text_field, word)))
+            .collect();
+        SpanOrQuery::new(clauses)
+    }
+
+    fn matching_docs(index: &Index, query: &dyn Query) -> crate::Result<Vec<u32>> {
+        let searcher = index.reader()?.searcher();
+        let mut docs: Vec<u32> = searcher
+            .search(query, &TopDocs::with_limit(10))?
+            .into_iter()
+            .map(|(_, DocAddress(_, doc))| doc)
+            .collect();
+        docs.sort();
+        Ok(docs)
+    }
+
+    #[test]
+    fn test_span_near_query_in_order() -> crate::Result<()> {
+        let index = create_index(&["a b c", "a c b", "b a", "a x x x b", "c"])?;
+        let query = |slop| span_near_query(&index, &["a", "b"], slop, true);
+        assert_eq!(matching_docs(&index, &query(0))?, vec![0]);
+        assert_eq!(matching_docs(&index, &query(1))?, vec![0, 1]);
+        assert_eq!(matching_docs(&index, &query(2))?, vec![0, 1]);
+        assert_eq!(matching_docs(&index, &query(3))?, vec![0, 1, 3]);
+        let three_clauses = span_near_query(&index, &["a", "b", "c"], 0, true);
+        assert_eq!(matching_docs(&index, &three_clauses)?, vec![0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_span_near_query_unordered() -> crate::Result<()> {
+        let index = create_index(&["a b c", "a c b", "b a", "a x x x b", "c"])?;
+        let query = |slop| span_near_query(&index, &["a", "b"], slop, false);
+        assert_eq!(matching_docs(&index, &query(0))?, vec![0, 2]);
+        assert_eq!(matching_docs(&index, &query(1))?, vec![0, 1, 2]);
+        assert_eq!(matching_docs(&index, &query(3))?, vec![0, 1, 2, 3]);
+        let three_clauses = span_near_query(&index, &["c", "b", "a"], 0, false);
+        assert_eq!(matching_docs(&index, &three_clauses)?, vec![0, 1]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_span_near_query_repeated_term() -> crate::Result<()> {
+        let index = create_index(&["a b", "a b a", "a x a"])?;
+        let in_order = span_near_query(&index, &["a", "a"], 1, true);
+        assert_eq!(matching_docs(&index, &in_order)?, vec![1, 2]);
+        let unordered = span_near_query(&index, &["a", "a"], 0, false);
+        assert!(matching_docs(&index, &unordered)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_span_near_query_score() -> crate::Result<()> {
+        let index = create_index(&["a b x x x x x a b", "a b x x x x x x x", "x"])?;
+        let searcher = index.reader()?.searcher();
+        let query = span_near_query(&index, &["a", "b"], 0, true);
+        let top_docs = searcher.search(&query, &TopDocs::with_limit(2))?;
+        assert_eq!(top_docs.len(), 2);
+        // The first document contains the span twice.
+        assert_eq!(top_docs[0].1, DocAddress(0, 0));
+        assert!(top_docs[0].0 > top_docs[1].0);
+        let explanation = query.explain(&searcher, DocAddress(0, 0))?;
+        assert_eq!(explanation.value(), top_docs[0].0);
+        assert!(query.explain(&searcher, DocAddress(0, 2)).is_err());
+        assert_eq!(searcher.search(&query, &Count)?, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_span_near_query_no_positions() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let id_field = schema_builder.add_text_field("id", STRING);
+        let index = Index::create_in_ram(schema_builder.build());
+        let searcher = index.reader()?.searcher();
+        let query = SpanNearQuery::new(
+            vec![
+                SpanTermQuery::new(Term::from_field_text(id_field, "a")),
+                SpanTermQuery::new(Term::from_field_text(id_field, "b")),
+            ],
+            1,
+            true,
+        );
+        assert!(matches!(
+            searcher.search(&query, &Count),
+            Err(crate::TantivyError::SchemaError(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_span_or_query() -> crate::Result<()> {
+        let index = create_index(&["a b", "c", "b c", "d"])?;
+        let query = span_or_query(&index, &["a", "c", "x"]);
+        assert_eq!(matching_docs(&index, &query)?, vec![0, 1, 2]);
+        let query = span_or_query(&index, &["x"]);
+        assert!(matching_docs(&index, &query)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_span_or_query_score() -> crate::Result<()> {
+        let index = create_index(&["a b x", "a x x", "x x x"])?;
+        let searcher = index.reader()?.searcher();
+        let query = span_or_query(&index, &["a", "b"]);
+        let top_docs = searcher.search(&query, &TopDocs::with_limit(3))?;
+        assert_eq!(top_docs.len(), 2);
+        assert_eq!(top_docs[0].1, DocAddress(0, 0));
+        assert!(top_docs[0].0 > top_docs[1].0);
+        let explanation = query.explain(&searcher, DocAddress(0, 0))?;
+        assert_eq!(explanation.value(), top_docs[0].0);
+        assert!(query.explain(&searcher, DocAddress(0, 2)).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_span_near_query_with_span_or_clauses() -> crate::Result<()> {
+        let index = create_index(&["red sofa", "red couch", "red chair", "couch red"])?;
+        let text_field = index.schema().get_field("text").unwrap();
+        let query = SpanNearQuery::new(
+            vec![
+                SpanTermQuery::new(Term::from_field_text(text_field, "red")).into(),
+                span_or_query(&index, &["sofa", "couch"]),
+            ],
+            0,
+            true,
+        );
+        assert_eq!(matching_docs(&index, &query)?, vec![0, 1]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_span_or_query_no_positions() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let id_field = schema_builder.add_text_field("id", STRING);
+        let index = Index::create_in_ram(schema_builder.build());
+        let searcher = index.reader()?.searcher();
+        let query = SpanOrQuery::new(vec![SpanTermQuery::new(Term::from_field_text(
+            id_field, "a",
+        ))]);
+        assert!(matches!(
+            searcher.search(&query, &Count),
+            Err(crate::TantivyError::SchemaError(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_time_synonyms() -> crate::Result<()> {
+        let index = create_index(&["a red sofa", "a red couch", "a red chair", "a couch"])?;
+        let text_field = index.schema().get_field("text").unwrap();
+        let synonym_analyzer = TextAnalyzer::from(SimpleTokenizer)
+            .filter(LowerCaser)
+            .filter(SynonymFilter::new(
+                SynonymMap::from_solr("sofa, couch")?,
+                true,
+            ));
+        // The documents were indexed without synonyms:
+        // they are only expanded at query time.
+        index.tokenizers().register("default", synonym_analyzer);
+        let query_parser = QueryParser::for_index(&index, vec![text_field]);
+        let query = query_parser.parse_query("sofa")?;
+        assert_eq!(matching_docs(&index, &query)?, vec![0, 1, 3]);
+        let query = query_parser.parse_query("\"red sofa\"")?;
+        assert_eq!(matching_docs(&index, &query)?, vec![0, 1]);
+        Ok(())
+    }
+}
@@ -0,0 +1,146 @@
+/// A span of positions within a document.
+///
+/// `start` is inclusive and `end` is exclusive.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Span {
+    /// Position of the first token of the span.
+    pub start: u32,
+    /// Position following the last token of the span.
+    pub end: u32,
+}
+
+/// Records the spans of a document matching a near constraint.
+///
+/// For each clause, the positions of its term within the current document
+/// are loaded in `clause_positions`. `collect` then computes the spans
+/// where one position of each clause can be picked such that at most `slop`
+/// positions are not covered by any clause.
+///
+/// For a given start, only the narrowest span is recorded.
+pub struct SpanCollector {
+    clause_positions: Vec<Vec<u32>>,
+    spans: Vec<Span>,
+    used_positions: Vec<u32>,
+}
+
+impl SpanCollector {
+    /// Creates a new `SpanCollector` for the given number of clauses.
+    pub fn new(num_clauses: usize) -> SpanCollector {
+        SpanCollector {
+            clause_positions: (0..num_clauses).map(|_| Vec::with_capacity(100)).collect(),
+            spans: Vec::new(),
+            used_positions: Vec::with_capacity(num_clauses),
+        }
+    }
+
+    /// Returns the buffer in which the positions of the `ord`-th clause
+    /// should be written.
+    pub fn clause_positions_mut(&mut self, ord: usize) -> &mut Vec<u32> {
+        &mut self.clause_positions[ord]
+    }
+
+    /// Spans recorded by the last call to `collect`, sorted by start.
+    pub fn spans(&self) -> &[Span] {
+        &self.spans[..]
+    }
+
+    /// Computes the matching spans and returns the number of spans found.
+    pub fn collect(&mut self, slop: u32, in_order: bool) -> usize {
+        self.spans.clear();
+        let num_clauses = self.clause_positions.len() as u32;
+        let num_anchor_clauses = if in_order {
+            1
+        } else {
+            self.clause_positions.len()
+        };
+        for anchor_ord in 0..num_anchor_clauses {
+            for anchor_id in 0..self.clause_positions[anchor_ord].len() {
+                let start = self.clause_positions[anchor_ord][anchor_id];
+                let end_opt = if in_order {
+                    self.ordered_end(start)
+                } else {
+                    self.unordered_end(anchor_ord, start)
+                };
+                if let Some(end) = end_opt {
+                    if end - start - num_clauses <= slop {
+                        self.spans.push(Span { start, end });
+                    }
+                }
+            }
+        }
+        if !in_order {
+            self.spans.sort();
+            self.spans.dedup();
+        }
+        self.spans.len()
+    }
+
+    /// Picks, clause after clause, the first position following the position
+    /// picked for the previous clause.
+    fn ordered_end(&self, start: u32) -> Option<u32> {
+        let mut last = start;
+        for positions in &self.clause_positions[1..] {
+            last = *positions.iter().find(|&&position| position > last)?;
+        }
+        Some(last + 1)
+    }
+
+    /// Picks, for each clause, the first position after `start`
+    /// that has not been picked for another clause yet.
+    fn unordered_end(&mut self, anchor_ord: usize, start: u32) -> Option<u32> {
+        self.used_positions.clear();
+        self.used_positions.push(start);
+        let mut last = start;
+        for (ord, positions) in self.clause_positions.iter().enumerate() {
+            if ord == anchor_ord {
+                continue;
+            }
+            let used_positions = &self.used_positions;
+            let position = *positions
+                .iter()
+                .find(|&position| *position >= start && !used_positions.contains(position))?;
+            self.used_positions.push(position);
+            last = last.max(position);
+        }
+        Some(last + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Span, SpanCollector};
+
+    fn collect(clause_positions: &[&[u32]], slop: u32, in_order: bool) -> Vec<(u32, u32)> {
+        let mut span_collector = SpanCollector::new(clause_positions.len());
+        for (ord, positions) in clause_positions.iter().enumerate() {
+            span_collector
+                .clause_positions_mut(ord)
+                .extend_from_slice(positions);
+        }
+        let num_spans = span_collector.collect(slop, in_order);
+        assert_eq!(num_spans, span_collector.spans().len());
+        span_collector
+            .spans()
+            .iter()
+            .map(|&Span { start, end }| (start, end))
+            .collect()
+    }
+
+    #[test]
+    fn test_span_collector_in_order() {
+        assert_eq!(collect(&[&[0], &[1]], 0, true), vec![(0, 2)]);
+        assert_eq!(collect(&[&[1], &[0]], 10, true), vec![]);
+        assert_eq!(collect(&[&[0, 5], &[3, 6]], 0, true), vec![(5, 7)]);
+        assert_eq!(collect(&[&[0, 5], &[3, 6]], 2, true), vec![(0, 4), (5, 7)]);
+        assert_eq!(collect(&[&[0, 2], &[1, 3], &[4]], 1, true), vec![(2, 5)]);
+    }
+
+    #[test]
+    fn test_span_collector_unordered() {
+        assert_eq!(collect(&[&[1], &[0]], 0, false), vec![(0, 2)]);
+        assert_eq!(collect(&[&[3], &[0]], 1, false), vec![]);
+        assert_eq!(collect(&[&[3], &[0]], 2, false), vec![(0, 4)]);
+        assert_eq!(collect(&[&[2], &[0, 4]], 1, false), vec![(0, 3), (2, 5)]);
+        assert_eq!(collect(&[&[0, 1], &[0, 1]], 0, false), vec![(0, 2)]);
+    }
+}
@@ -0,0 +1,179 @@
+use super::{SpanNearWeight, SpanOrQuery};
+use crate::core::Searcher;
+use crate::query::bm25::BM25Weight;
+use crate::query::Query;
+use crate::query::Weight;
+use crate::schema::IndexRecordOption;
+use crate::schema::{Field, Term};
+use std::collections::BTreeSet;
+
+/// `SpanNearQuery` matches documents in which the spans of its clauses
+/// are near each other.
+///
+/// A document matches if one position can be picked for each clause such that
+/// at most `slop` positions, between the first and the last one, are
+/// not covered by a clause.
+///
+/// For instance, the query for `"part"` and `"job"` with a slop of 1
+/// will match the sentence
+///
+/// **Alan just got a part time job.**
+///
+/// A clause is either a [`SpanTermQuery`](./struct.SpanTermQuery.html), or a
+/// [`SpanOrQuery`](./struct.SpanOrQuery.html) matching any of its terms.
+///
+/// If `in_order` is true, the clauses are required to appear in the order
+/// they were given in. Otherwise, `"job"` followed by `"part"` also matches.
+///
+/// The score is computed using BM25, using the number of matching spans as
+/// the term frequency.
+///
+/// Using a `SpanNearQuery` on a field requires positions
+/// to be indexed for this field.
+///
+/// # Example
+///
+/// ```rust
+/// use tantivy::collector::Count;
+/// use tantivy::query::{SpanNearQuery, SpanTermQuery};
+/// use tantivy::schema::{Schema, TEXT};
+/// use tantivy::{doc, Index, Term};
+/// # fn test() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let body = schema_builder.add_text_field("body", TEXT);
+/// let schema = schema_builder.build();
+///
+/// let index = Index::create_in_ram(schema);
+/// let mut index_writer = index.writer_with_num_threads(1, 10_000_000)?;
+/// index_writer.add_document(doc!(body => "Alan just got a part time job."));
+/// index_writer.add_document(doc!(body => "This job is only part of the deal."));
+/// index_writer.add_document(doc!(body => "This is my favorite part of the job."));
+/// index_writer.commit()?;
+///
+/// let searcher = index.reader()?.searcher();
+/// let span_near_query = |in_order| {
+///     SpanNearQuery::new(
+///         vec![
+///             SpanTermQuery::new(Term::from_field_text(body, "part")),
+///             SpanTermQuery::new(Term::from_field_text(body, "job")),
+///         ],
+///         3,
+///         in_order,
+///     )
+/// };
+/// assert_eq!(searcher.search(&span_near_query(true), &Count)?, 2);
+/// assert_eq!(searcher.search(&span_near_query(false), &Count)?, 3);
+/// Ok(())
+/// # }
+/// # assert!(test().is_ok());
+/// ```
+#[derive(Clone, Debug)]
+pub struct SpanNearQuery {
+    field: Field,
+    clauses: Vec<SpanOrQuery>,
+    slop: u32,
+    in_order: bool,
+}
+
+impl SpanNearQuery {
+    /// Creates a new `SpanNearQuery`.
+    ///
+    /// There must be at least two clauses, and all of them
+    /// must belong to the same field.
+    pub fn new<T: Into<SpanOrQuery>>(clauses: Vec<T>, slop: u32, in_order: bool) -> SpanNearQuery {
+        let clauses: Vec<SpanOrQuery> = clauses.into_iter().map(Into::into).collect();
+        assert!(
+            clauses.len() > 1,
+            "A span near query is required to have strictly more than one clause."
+        );
+        let field = clauses[0].field();
+        assert!(
+            clauses[1..].iter().all(|clause| clause.field() == field),
+            "All clauses from a span near query must belong to the same field"
+        );
+        SpanNearQuery {
+            field,
+            clauses,
+            slop,
+            in_order,
+        }
+    }
+
+    /// The `Field` this `SpanNearQuery` is targeting.
+    pub fn field(&self) -> Field {
+        self.field
+    }
+
+    /// The clauses of this query.
+    pub fn clauses(&self) -> &[SpanOrQuery] {
+        &self.clauses[..]
+    }
+
+    /// Maximum number of positions, between the first and the last
+    /// clause, that are not covered by a clause.
+    pub fn slop(&self) -> u32 {
+        self.slop
+    }
+
+    /// Whether the clauses are required to appear in order.
+    pub fn in_order(&self) -> bool {
+        self.in_order
+    }
+
+    fn terms(&self) -> Vec<Term> {
+        self.clauses.iter().flat_map(SpanOrQuery::terms).collect()
+    }
+
+    /// Returns the `SpanNearWeight` for the given span near query given a specific `searcher`.
+    pub(crate) fn span_near_weight(&self, searcher: &Searcher) -> crate::Result<SpanNearWeight> {
+        check_positions_indexed(searcher, self.field, "span near")?;
+        let bm25_weight = BM25Weight::for_terms(searcher, &self.terms())?;
+        let clause_terms = self.clauses.iter().map(SpanOrQuery::terms).collect();
+        Ok(SpanNearWeight::new(
+            clause_terms,
+            self.slop,
+            self.in_order,
+            bm25_weight,
+        ))
+    }
+}
+
+/// Returns an error if positions are not indexed for `field`.
+pub(crate) fn check_positions_indexed(
+    searcher: &Searcher,
+    field: Field,
+    query_name: &str,
+) -> crate::Result<()> {
+    let schema = searcher.schema();
+    let field_entry = schema.get_field_entry(field);
+    let has_positions = field_entry
+        .field_type()
+        .get_index_record_option()
+        .map(IndexRecordOption::has_positions)
+        .unwrap_or(false);
+    if !has_positions {
+        let field_name = field_entry.name();
+        return Err(crate::TantivyError::SchemaError(format!(
+            "Applied {} query on field {:?}, which does not have positions indexed",
+            query_name, field_name
+        )));
+    }
+    Ok(())
+}
+
+impl Query for SpanNearQuery {
+    fn weight(
+        &self,
+        searcher: &Searcher,
+        _scoring_enabled: bool,
+    ) -> crate::Result<Box<dyn Weight>> {
+        let span_near_weight = self.span_near_weight(searcher)?;
+        Ok(Box::new(span_near_weight))
+    }
+
+    fn query_terms(&self, term_set: &mut BTreeSet<Term>) {
+        for clause in &self.clauses {
+            clause.query_terms(term_set);
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use super::{Span, SpanCollector};
+use crate::docset::{DocSet, TERMINATED};
+use crate::fieldnorm::FieldNormReader;
+use crate::postings::Postings;
+use crate::query::bm25::BM25Weight;
+use crate::query::{Intersection, Scorer};
+use crate::{DocId, Score};
+
+/// Postings of a clause, along with the ordinal of the clause.
+///
+/// The `Intersection` reorders its `DocSet`s, so the ordinal is required
+/// to know which clause the positions belong to.
+struct PostingsWithOrd<TPostings> {
+    ord: usize,
+    postings: TPostings,
+}
+
+impl<TPostings: Postings> DocSet for PostingsWithOrd<TPostings> {
+    fn advance(&mut self) -> DocId {
+        self.postings.advance()
+    }
+
+    fn seek(&mut self, target: DocId) -> DocId {
+        self.postings.seek(target)
+    }
+
+    fn doc(&self) -> DocId {
+        self.postings.doc()
+    }
+
+    fn size_hint(&self) -> u32 {
+        self.postings.size_hint()
+    }
+}
+
+/// Scorer of the `SpanNearQuery`.
+///
+/// Candidate documents are the documents containing all of the terms.
+/// The span constraint is then checked on each of them.
+pub struct SpanNearScorer<TPostings: Postings> {
+    intersection_docset: Intersection<PostingsWithOrd<TPostings>, PostingsWithOrd<TPostings>>,
+    num_clauses: usize,
+    slop: u32,
+    in_order: bool,
+    span_collector: SpanCollector,
+    fieldnorm_reader: FieldNormReader,
+    similarity_weight: BM25Weight,
+}
+
+impl<TPostings: Postings> SpanNearScorer<TPostings> {
+    pub fn new(
+        term_postings: Vec<TPostings>,
+        slop: u32,
+        in_order: bool,
+        similarity_weight: BM25Weight,
+        fieldnorm_reader: FieldNormReader,
+    ) -> SpanNearScorer<TPostings> {
+        let num_clauses = term_postings.len();
+        let postings_with_ords = term_postings
+            .into_iter()
+            .enumerate()
+            .map(|(ord, postings)| PostingsWithOrd { ord, postings })
+            .collect::<Vec<_>>();
+        let mut scorer = SpanNearScorer {
+            intersection_docset: Intersection::new(postings_with_ords),
+            num_clauses,
+            slop,
+            in_order,
+            span_collector: SpanCollector::new(num_clauses),
+            fieldnorm_reader,
+            similarity_weight,
+        };
+        if scorer.doc() != TERMINATED && !scorer.span_match() {
+            scorer.advance();
+        }
+        scorer
+    }
+
+    /// Spans matched in the current document.
+    pub fn spans(&self) -> &[Span] {
+        self.span_collector.spans()
+    }
+
+    fn span_match(&mut self) -> bool {
+        for i in 0..self.num_clauses {
+            let postings_with_ord = self.intersection_docset.docset_mut_specialized(i);
+            postings_with_ord.postings.positions(
+                self.span_collector
+                    .clause_positions_mut(postings_with_ord.ord),
+            );
+        }
+        self.span_collector.collect(self.slop, self.in_order) > 0
+    }
+}
+
+impl<TPostings: Postings> DocSet for SpanNearScorer<TPostings> {
+    fn advance(&mut self) -> DocId {
+        loop {
+            let doc = self.intersection_docset.advance();
+            if doc == TERMINATED || self.span_match() {
+                return doc;
+            }
+        }
+    }
+
+    fn seek(&mut self, target: DocId) -> DocId {
+        debug_assert!(target >= self.doc());
+        let doc = self.intersection_docset.seek(target);
+        if doc == TERMINATED || self.span_match() {
+            return doc;
+        }
+        self.advance()
+    }
+
+    fn doc(&self) -> DocId {
+        self.intersection_docset.doc()
+    }
+
+    fn size_hint(&self) -> u32 {
+        self.intersection_docset.size_hint()
+    }
+}
+
+impl<TPostings: Postings> Scorer for SpanNearScorer<TPostings> {
+    fn score(&mut self) -> Score {
+        let doc = self.doc();
+        let fieldnorm_id = self.fieldnorm_reader.fieldnorm_id(doc);
+        let span_count = self.span_collector.spans().len() as u32;
+        self.similarity_weight.score(fieldnorm_id, span_count)
+    }
+}
@@ -0,0 +1,93 @@
+use super::span_or_weight::union_postings;
+use super::{SpanNearScorer, UnionPostings};
+use crate::core::SegmentReader;
+use crate::fieldnorm::FieldNormReader;
+use crate::postings::SegmentPostings;
+use crate::query::bm25::BM25Weight;
+use crate::query::explanation::does_not_match;
+use crate::query::Scorer;
+use crate::query::Weight;
+use crate::query::{EmptyScorer, Explanation};
+use crate::schema::Term;
+use crate::Score;
+use crate::{DocId, DocSet};
+
+pub struct SpanNearWeight {
+    clause_terms: Vec<Vec<Term>>,
+    slop: u32,
+    in_order: bool,
+    similarity_weight: BM25Weight,
+}
+
+impl SpanNearWeight {
+    /// Creates a new span near weight, given the terms of each of its clauses.
+    pub fn new(
+        clause_terms: Vec<Vec<Term>>,
+        slop: u32,
+        in_order: bool,
+        similarity_weight: BM25Weight,
+    ) -> SpanNearWeight {
+        SpanNearWeight {
+            clause_terms,
+            slop,
+            in_order,
+            similarity_weight,
+        }
+    }
+
+    fn fieldnorm_reader(&self, reader: &SegmentReader) -> crate::Result<FieldNormReader> {
+        let field = self.clause_terms[0][0].field();
+        reader.get_fieldnorms_reader(field)
+    }
+
+    fn span_near_scorer(
+        &self,
+        reader: &SegmentReader,
+        boost: Score,
+    ) -> crate::Result<Option<SpanNearScorer<UnionPostings<SegmentPostings>>>> {
+        let similarity_weight = self.similarity_weight.boost_by(boost);
+        let fieldnorm_reader = self.fieldnorm_reader(reader)?;
+        let mut clause_postings_list = Vec::new();
+        for terms in &self.clause_terms {
+            if let Some(postings) = union_postings(reader, terms)? {
+                clause_postings_list.push(postings);
+            } else {
+                return Ok(None);
+            }
+        }
+        Ok(Some(SpanNearScorer::new(
+            clause_postings_list,
+            self.slop,
+            self.in_order,
+            similarity_weight,
+            fieldnorm_reader,
+        )))
+    }
+}
+
+impl Weight for SpanNearWeight {
+    fn scorer(&self, reader: &SegmentReader, boost: Score) -> crate::Result<Box<dyn Scorer>> {
+        if let Some(scorer) = self.span_near_scorer(reader, boost)? {
+            Ok(Box::new(scorer))
+        } else {
+            Ok(Box::new(EmptyScorer))
+        }
+    }
+
+    fn explain(&self, reader: &SegmentReader, doc: DocId) -> crate::Result<Explanation> {
+        let mut scorer = if let Some(scorer) = self.span_near_scorer(reader, 1.0)? {
+            scorer
+        } else {
+            return Err(does_not_match(doc));
+        };
+        if scorer.seek(doc) != doc {
+            return Err(does_not_match(doc));
+        }
+        let fieldnorm_reader = self.fieldnorm_reader(reader)?;
+        let fieldnorm_id = fieldnorm_reader.fieldnorm_id(doc);
+        let span_count = scorer.spans().len() as u32;
+        let mut explanation = Explanation::new("Span Near Scorer", scorer.score());
+        explanation.add_detail(self.similarity_weight.explain(fieldnorm_id, span_count));
+        Ok(explanation)
+    }
+}
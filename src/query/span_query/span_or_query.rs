@@ -0,0 +1,132 @@
+use super::span_near_query::check_positions_indexed;
+use super::{SpanOrWeight, SpanTermQuery};
+use crate::core::Searcher;
+use crate::query::bm25::BM25Weight;
+use crate::query::Query;
+use crate::query::Weight;
+use crate::schema::{Field, Term};
+use std::collections::BTreeSet;
+
+/// `SpanOrQuery` matches the positions of any of its clauses.
+///
+/// Used on its own, it matches the documents containing any of the terms of
+/// its clauses. The score is computed using BM25, using the number of
+/// matching positions as the term frequency: a position matched by several
+/// clauses is only counted once.
+///
+/// It can also be used as a clause of a [`SpanNearQuery`](./struct.SpanNearQuery.html),
+/// to allow several terms at the same place.
+///
+/// The [`QueryParser`](./struct.QueryParser.html) emits a `SpanOrQuery` when
+/// the tokenizer of a field emits several tokens at the same position, as
+/// the `SynonymFilter` does.
+///
+/// Using a `SpanOrQuery` on a field requires positions
+/// to be indexed for this field.
+///
+/// # Example
+///
+/// ```rust
+/// use tantivy::collector::Count;
+/// use tantivy::query::{SpanNearQuery, SpanOrQuery, SpanTermQuery};
+/// use tantivy::schema::{Schema, TEXT};
+/// use tantivy::{doc, Index, Term};
+/// # fn test() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let body = schema_builder.add_text_field("body", TEXT);
+/// let schema = schema_builder.build();
+///
+/// let index = Index::create_in_ram(schema);
+/// let mut index_writer = index.writer_with_num_threads(1, 10_000_000)?;
+/// index_writer.add_document(doc!(body => "A red sofa."));
+/// index_writer.add_document(doc!(body => "A red couch."));
+/// index_writer.add_document(doc!(body => "A red chair."));
+/// index_writer.commit()?;
+///
+/// let searcher = index.reader()?.searcher();
+/// let sofa_or_couch = SpanOrQuery::new(vec![
+///     SpanTermQuery::new(Term::from_field_text(body, "sofa")),
+///     SpanTermQuery::new(Term::from_field_text(body, "couch")),
+/// ]);
+/// assert_eq!(searcher.search(&sofa_or_couch, &Count)?, 2);
+/// let red_sofa_or_couch = SpanNearQuery::new(
+///     vec![
+///         SpanTermQuery::new(Term::from_field_text(body, "red")).into(),
+///         sofa_or_couch,
+///     ],
+///     0,
+///     true,
+/// );
+/// assert_eq!(searcher.search(&red_sofa_or_couch, &Count)?, 2);
+/// Ok(())
+/// # }
+/// # assert!(test().is_ok());
+/// ```
+#[derive(Clone, Debug)]
+pub struct SpanOrQuery {
+    field: Field,
+    clauses: Vec<SpanTermQuery>,
+}
+
+impl SpanOrQuery {
+    /// Creates a new `SpanOrQuery`.
+    ///
+    /// There must be at least one clause, and all of them
+    /// must belong to the same field.
+    pub fn new(clauses: Vec<SpanTermQuery>) -> SpanOrQuery {
+        assert!(
+            !clauses.is_empty(),
+            "A span or query is required to have at least one clause."
+        );
+        let field = clauses[0].term().field();
+        assert!(
+            clauses[1..]
+                .iter()
+                .all(|clause| clause.term().field() == field),
+            "All clauses from a span or query must belong to the same field"
+        );
+        SpanOrQuery { field, clauses }
+    }
+
+    /// The `Field` this `SpanOrQuery` is targeting.
+    pub fn field(&self) -> Field {
+        self.field
+    }
+
+    /// The clauses of this query.
+    pub fn clauses(&self) -> &[SpanTermQuery] {
+        &self.clauses[..]
+    }
+
+    pub(crate) fn terms(&self) -> Vec<Term> {
+        self.clauses
+            .iter()
+            .map(|clause| clause.term().clone())
+            .collect()
+    }
+}
+
+impl From<SpanTermQuery> for SpanOrQuery {
+    fn from(span_term_query: SpanTermQuery) -> SpanOrQuery {
+        SpanOrQuery::new(vec![span_term_query])
+    }
+}
+
+impl Query for SpanOrQuery {
+    fn weight(
+        &self,
+        searcher: &Searcher,
+        _scoring_enabled: bool,
+    ) -> crate::Result<Box<dyn Weight>> {
+        check_positions_indexed(searcher, self.field, "span or")?;
+        let terms = self.terms();
+        let bm25_weight = BM25Weight::for_terms(searcher, &terms)?;
+        Ok(Box::new(SpanOrWeight::new(terms, bm25_weight)))
+    }
+
+    fn query_terms(&self, term_set: &mut BTreeSet<Term>) {
+        for clause in &self.clauses {
+            clause.query_terms(term_set);
+        }
+    }
+}
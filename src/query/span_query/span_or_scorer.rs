@@ -0,0 +1,62 @@
+use super::UnionPostings;
+use crate::docset::DocSet;
+use crate::fieldnorm::FieldNormReader;
+use crate::postings::Postings;
+use crate::query::bm25::BM25Weight;
+use crate::query::Scorer;
+use crate::{DocId, Score};
+
+/// Scorer of the `SpanOrQuery`.
+///
+/// Matching documents are the documents containing any of the terms.
+pub struct SpanOrScorer<TPostings: Postings> {
+    postings: UnionPostings<TPostings>,
+    fieldnorm_reader: FieldNormReader,
+    similarity_weight: BM25Weight,
+}
+
+impl<TPostings: Postings> SpanOrScorer<TPostings> {
+    pub fn new(
+        postings: UnionPostings<TPostings>,
+        similarity_weight: BM25Weight,
+        fieldnorm_reader: FieldNormReader,
+    ) -> SpanOrScorer<TPostings> {
+        SpanOrScorer {
+            postings,
+            fieldnorm_reader,
+            similarity_weight,
+        }
+    }
+
+    /// Number of positions matched in the current document.
+    pub fn num_positions(&self) -> u32 {
+        self.postings.term_freq()
+    }
+}
+
+impl<TPostings: Postings> DocSet for SpanOrScorer<TPostings> {
+    fn advance(&mut self) -> DocId {
+        self.postings.advance()
+    }
+
+    fn seek(&mut self, target: DocId) -> DocId {
+        self.postings.seek(target)
+    }
+
+    fn doc(&self) -> DocId {
+        self.postings.doc()
+    }
+
+    fn size_hint(&self) -> u32 {
+        self.postings.size_hint()
+    }
+}
+
+impl<TPostings: Postings> Scorer for SpanOrScorer<TPostings> {
+    fn score(&mut self) -> Score {
+        let doc = self.doc();
+        let fieldnorm_id = self.fieldnorm_reader.fieldnorm_id(doc);
+        self.similarity_weight
+            .score(fieldnorm_id, self.postings.term_freq())
+    }
+}
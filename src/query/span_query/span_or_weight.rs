@@ -0,0 +1,93 @@
+use super::{SpanOrScorer, UnionPostings};
+use crate::core::SegmentReader;
+use crate::fieldnorm::FieldNormReader;
+use crate::postings::SegmentPostings;
+use crate::query::bm25::BM25Weight;
+use crate::query::explanation::does_not_match;
+use crate::query::Scorer;
+use crate::query::Weight;
+use crate::query::{EmptyScorer, Explanation};
+use crate::schema::IndexRecordOption;
+use crate::schema::Term;
+use crate::Score;
+use crate::{DocId, DocSet};
+
+pub struct SpanOrWeight {
+    terms: Vec<Term>,
+    similarity_weight: BM25Weight,
+}
+
+impl SpanOrWeight {
+    /// Creates a new span or weight.
+    pub fn new(terms: Vec<Term>, similarity_weight: BM25Weight) -> SpanOrWeight {
+        SpanOrWeight {
+            terms,
+            similarity_weight,
+        }
+    }
+
+    fn fieldnorm_reader(&self, reader: &SegmentReader) -> crate::Result<FieldNormReader> {
+        let field = self.terms[0].field();
+        reader.get_fieldnorms_reader(field)
+    }
+
+    fn span_or_scorer(
+        &self,
+        reader: &SegmentReader,
+        boost: Score,
+    ) -> crate::Result<Option<SpanOrScorer<SegmentPostings>>> {
+        let similarity_weight = self.similarity_weight.boost_by(boost);
+        let fieldnorm_reader = self.fieldnorm_reader(reader)?;
+        let postings = union_postings(reader, &self.terms)?;
+        Ok(postings
+            .map(|postings| SpanOrScorer::new(postings, similarity_weight, fieldnorm_reader)))
+    }
+}
+
+/// Returns the union of the postings of `terms`,
+/// or `None` if none of the terms is in the segment.
+pub(crate) fn union_postings(
+    reader: &SegmentReader,
+    terms: &[Term],
+) -> crate::Result<Option<UnionPostings<SegmentPostings>>> {
+    let mut term_postings_list = Vec::new();
+    for term in terms {
+        if let Some(postings) = reader
+            .inverted_index(term.field())?
+            .read_postings(term, IndexRecordOption::WithFreqsAndPositions)?
+        {
+            term_postings_list.push(postings);
+        }
+    }
+    if term_postings_list.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(UnionPostings::new(term_postings_list)))
+}
+
+impl Weight for SpanOrWeight {
+    fn scorer(&self, reader: &SegmentReader, boost: Score) -> crate::Result<Box<dyn Scorer>> {
+        if let Some(scorer) = self.span_or_scorer(reader, boost)? {
+            Ok(Box::new(scorer))
+        } else {
+            Ok(Box::new(EmptyScorer))
+        }
+    }
+
+    fn explain(&self, reader: &SegmentReader, doc: DocId) -> crate::Result<Explanation> {
+        let mut scorer = if let Some(scorer) = self.span_or_scorer(reader, 1.0)? {
+            scorer
+        } else {
+            return Err(does_not_match(doc));
+        };
+        if scorer.seek(doc) != doc {
+            return Err(does_not_match(doc));
+        }
+        let fieldnorm_reader = self.fieldnorm_reader(reader)?;
+        let fieldnorm_id = fieldnorm_reader.fieldnorm_id(doc);
+        let num_positions = scorer.num_positions();
+        let mut explanation = Explanation::new("Span Or Scorer", scorer.score());
+        explanation.add_detail(self.similarity_weight.explain(fieldnorm_id, num_positions));
+        Ok(explanation)
+    }
+}
@@ -0,0 +1,39 @@
+use crate::core::Searcher;
+use crate::query::{Query, TermQuery, Weight};
+use crate::schema::{IndexRecordOption, Term};
+use std::collections::BTreeSet;
+
+/// `SpanTermQuery` matches the positions of a single term.
+///
+/// It is the building block of the other span queries,
+/// like [`SpanNearQuery`](./struct.SpanNearQuery.html).
+///
+/// Used on its own, it matches the same documents as
+/// a `TermQuery` on the same term.
+#[derive(Clone, Debug)]
+pub struct SpanTermQuery {
+    term: Term,
+}
+
+impl SpanTermQuery {
+    /// Creates a new `SpanTermQuery`.
+    pub fn new(term: Term) -> SpanTermQuery {
+        SpanTermQuery { term }
+    }
+
+    /// The `Term` this query is targeting.
+    pub fn term(&self) -> &Term {
+        &self.term
+    }
+}
+
+impl Query for SpanTermQuery {
+    fn weight(&self, searcher: &Searcher, scoring_enabled: bool) -> crate::Result<Box<dyn Weight>> {
+        TermQuery::new(self.term.clone(), IndexRecordOption::WithFreqsAndPositions)
+            .weight(searcher, scoring_enabled)
+    }
+
+    fn query_terms(&self, term_set: &mut BTreeSet<Term>) {
+        term_set.insert(self.term.clone());
+    }
+}
@@ -0,0 +1,90 @@
+use crate::docset::{DocSet, TERMINATED};
+use crate::postings::Postings;
+use crate::DocId;
+
+/// Union of the postings of several terms.
+///
+/// The positions of a document are the union of the positions
+/// of the terms it contains, and its term frequency is the number
+/// of these positions. A position shared by several terms is only counted once.
+pub struct UnionPostings<TPostings> {
+    postings: Vec<TPostings>,
+    doc: DocId,
+    positions: Vec<u32>,
+    positions_buffer: Vec<u32>,
+}
+
+impl<TPostings: Postings> UnionPostings<TPostings> {
+    pub fn new(postings: Vec<TPostings>) -> UnionPostings<TPostings> {
+        let mut union_postings = UnionPostings {
+            postings,
+            doc: TERMINATED,
+            positions: Vec::new(),
+            positions_buffer: Vec::new(),
+        };
+        union_postings.update_doc();
+        union_postings
+    }
+
+    /// Moves to the smallest doc of the postings, and loads its positions.
+    fn update_doc(&mut self) -> DocId {
+        self.doc = self
+            .postings
+            .iter()
+            .map(DocSet::doc)
+            .min()
+            .unwrap_or(TERMINATED);
+        self.positions.clear();
+        if self.doc != TERMINATED {
+            for postings in &mut self.postings {
+                if postings.doc() == self.doc {
+                    postings.positions(&mut self.positions_buffer);
+                    self.positions.extend_from_slice(&self.positions_buffer);
+                }
+            }
+            self.positions.sort_unstable();
+            self.positions.dedup();
+        }
+        self.doc
+    }
+}
+
+impl<TPostings: Postings> DocSet for UnionPostings<TPostings> {
+    fn advance(&mut self) -> DocId {
+        let doc = self.doc;
+        for postings in &mut self.postings {
+            if postings.doc() == doc {
+                postings.advance();
+            }
+        }
+        self.update_doc()
+    }
+
+    fn seek(&mut self, target: DocId) -> DocId {
+        for postings in &mut self.postings {
+            if postings.doc() < target {
+                postings.seek(target);
+            }
+        }
+        self.update_doc()
+    }
+
+    fn doc(&self) -> DocId {
+        self.doc
+    }
+
+    fn size_hint(&self) -> u32 {
+        self.postings.iter().map(DocSet::size_hint).sum()
+    }
+}
+
+impl<TPostings: Postings> Postings for UnionPostings<TPostings> {
+    fn term_freq(&self) -> u32 {
+        self.positions.len() as u32
+    }
+
+    fn positions_with_offset(&mut self, offset: u32, output: &mut Vec<u32>) {
+        output.clear();
+        output.extend(self.positions.iter().map(|&position| position + offset));
+    }
+}
@@ -180,6 +180,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_term_query_term() {
+        let term = Term::from_field_text(Field::from_field_id(1), "hello");
+        let term_query = TermQuery::new(term.clone(), IndexRecordOption::WithFreqs);
+        assert_eq!(term_query.term(), &term);
+        assert_eq!(term_query.into_term(), term);
+    }
+
     #[test]
     fn test_term_query_explain() -> crate::Result<()> {
         let mut schema_builder = Schema::builder();
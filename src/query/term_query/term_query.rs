@@ -57,6 +57,7 @@ use std::fmt;
 /// # assert!(test().is_ok());
 /// ```
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TermQuery {
     term: Term,
     index_record_option: IndexRecordOption,
@@ -68,6 +69,13 @@ impl fmt::Debug for TermQuery {
     }
 }
 
+/// Displays the query as its term, like `0:coffee`.
+impl fmt::Display for TermQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.term)
+    }
+}
+
 impl TermQuery {
     /// Creates a new term query.
     pub fn new(term: Term, segment_postings_options: IndexRecordOption) -> TermQuery {
@@ -82,6 +90,11 @@ impl TermQuery {
         &self.term
     }
 
+    /// Consumes the query and returns its `Term`.
+    pub fn into_term(self) -> Term {
+        self.term
+    }
+
     /// Returns a weight object.
     ///
     /// While `.weight(...)` returns a boxed trait object,
@@ -130,4 +143,12 @@ impl Query for TermQuery {
     fn query_terms(&self, term_set: &mut BTreeSet<Term>) {
         term_set.insert(self.term.clone());
     }
+
+    fn cache_key(&self) -> Option<String> {
+        Some(format!(
+            "TermQuery({:?},{:?})",
+            self.term.as_slice(),
+            self.index_record_option
+        ))
+    }
 }
@@ -28,6 +28,10 @@ impl TermScorer {
         }
     }
 
+    pub(crate) fn postings_mut(&mut self) -> &mut SegmentPostings {
+        &mut self.postings
+    }
+
     pub(crate) fn shallow_seek(&mut self, target_doc: DocId) {
         self.postings.block_cursor.shallow_seek(target_doc);
     }
@@ -77,6 +81,14 @@ impl TermScorer {
             .block_max_score(&self.fieldnorm_reader, &self.similarity_weight)
     }
 
+    /// Returns true iff `block_max_score` may be used to skip blocks.
+    ///
+    /// The block-max information is computed at indexing time with the default
+    /// BM25 parameters, and is not an upper bound of the scores with other parameters.
+    pub(crate) fn has_block_max_scores(&self) -> bool {
+        self.similarity_weight.has_default_params()
+    }
+
     pub fn term_freq(&self) -> u32 {
         self.postings.term_freq()
     }
@@ -96,7 +108,7 @@ impl TermScorer {
     }
 
     pub fn last_doc_in_block(&self) -> DocId {
-        self.postings.block_cursor.skip_reader.last_doc_in_block()
+        self.postings.block_cursor.last_doc_in_block()
     }
 }
 
@@ -116,6 +128,10 @@ impl DocSet for TermScorer {
     fn size_hint(&self) -> u32 {
         self.postings.size_hint()
     }
+
+    fn docs_skipped(&self) -> u64 {
+        self.postings.docs_skipped()
+    }
 }
 
 impl Scorer for TermScorer {
@@ -0,0 +1,243 @@
+use crate::common::BitSet;
+use crate::core::Searcher;
+use crate::core::SegmentReader;
+use crate::query::explanation::does_not_match;
+use crate::query::ConstScorer;
+use crate::query::{BitSetDocSet, Explanation};
+use crate::query::{Query, Scorer, Weight};
+use crate::schema::{Field, IndexRecordOption, Term};
+use crate::{DocId, Score};
+use std::collections::BTreeSet;
+
+/// `TermSetQuery` matches all documents containing at least one of the
+/// terms of a given set.
+///
+/// Matched documents all get a constant `Score` of one.
+///
+/// This is meant as a replacement for a `BooleanQuery` made of a
+/// very large number of `Should` `TermQuery` clauses, e.g. when
+/// filtering documents over a list of identifiers.
+///
+/// # Implementation
+///
+/// The terms are sorted and deduplicated when the query is created.
+/// For each segment, the terms are looked up in the term dictionary in order,
+/// and all of their documents are appended into a `BitSet`.
+///
+/// # Example
+///
+/// ```rust
+/// use tantivy::collector::Count;
+/// use tantivy::query::TermSetQuery;
+/// use tantivy::schema::{Schema, STRING};
+/// use tantivy::{doc, Index, Term};
+/// # fn test() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let sku_field = schema_builder.add_text_field("sku", STRING);
+/// let schema = schema_builder.build();
+///
+/// let index = Index::create_in_ram(schema);
+/// let mut index_writer = index.writer_with_num_threads(1, 10_000_000)?;
+/// for sku_id in 0..1_000 {
+///     index_writer.add_document(doc!(sku_field => format!("SKU-{}", sku_id)));
+/// }
+/// index_writer.commit()?;
+///
+/// let reader = index.reader()?;
+/// let searcher = reader.searcher();
+/// let terms = (0..1_000)
+///     .step_by(10)
+///     .map(|sku_id| Term::from_field_text(sku_field, &format!("SKU-{}", sku_id)));
+/// let query = TermSetQuery::new(sku_field, terms);
+/// assert_eq!(searcher.search(&query, &Count)?, 100);
+/// Ok(())
+/// # }
+/// # assert!(test().is_ok());
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TermSetQuery {
+    field: Field,
+    terms: Vec<Term>,
+}
+
+impl TermSetQuery {
+    /// Creates a new `TermSetQuery` over the given field.
+    ///
+    /// # Panics
+    ///
+    /// Panics if one of the terms does not belong to `field`.
+    pub fn new<T: IntoIterator<Item = Term>>(field: Field, terms: T) -> TermSetQuery {
+        let mut terms: Vec<Term> = terms.into_iter().collect();
+        for term in &terms {
+            assert_eq!(field, term.field());
+        }
+        terms.sort();
+        terms.dedup();
+        TermSetQuery { field, terms }
+    }
+
+    /// Field to search over
+    pub fn field(&self) -> Field {
+        self.field
+    }
+
+    /// The set of terms, sorted and deduplicated.
+    pub fn terms(&self) -> &[Term] {
+        &self.terms[..]
+    }
+}
+
+impl Query for TermSetQuery {
+    fn weight(
+        &self,
+        _searcher: &Searcher,
+        _scoring_enabled: bool,
+    ) -> crate::Result<Box<dyn Weight>> {
+        Ok(Box::new(TermSetWeight {
+            field: self.field,
+            terms: self.terms.clone(),
+        }))
+    }
+
+    fn query_terms(&self, term_set: &mut BTreeSet<Term>) {
+        term_set.extend(self.terms.iter().cloned());
+    }
+}
+
+pub struct TermSetWeight {
+    field: Field,
+    terms: Vec<Term>,
+}
+
+impl Weight for TermSetWeight {
+    fn scorer(&self, reader: &SegmentReader, boost: Score) -> crate::Result<Box<dyn Scorer>> {
+        let max_doc = reader.max_doc();
+        let mut doc_bitset = BitSet::with_max_value(max_doc);
+
+        let inverted_index = reader.inverted_index(self.field)?;
+        for term in &self.terms {
+            let term_info = if let Some(term_info) = inverted_index.get_term_info(term)? {
+                term_info
+            } else {
+                continue;
+            };
+            let mut block_segment_postings = inverted_index
+                .read_block_postings_from_terminfo(&term_info, IndexRecordOption::Basic)?;
+            loop {
+                let docs = block_segment_postings.docs();
+                if docs.is_empty() {
+                    break;
+                }
+                for &doc in docs {
+                    doc_bitset.insert(doc);
+                }
+                block_segment_postings.advance();
+            }
+        }
+        let doc_bitset = BitSetDocSet::from(doc_bitset);
+        Ok(Box::new(ConstScorer::new(doc_bitset, boost)))
+    }
+
+    fn explain(&self, reader: &SegmentReader, doc: DocId) -> crate::Result<Explanation> {
+        let mut scorer = self.scorer(reader, 1.0)?;
+        if scorer.seek(doc) != doc {
+            return Err(does_not_match(doc));
+        }
+        Ok(Explanation::new("TermSetQuery", 1.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TermSetQuery;
+    use crate::collector::{Count, TopDocs};
+    use crate::query::Query;
+    use crate::schema::{Schema, STRING, TEXT};
+    use crate::{DocAddress, Index, Term};
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_term_set_query() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let sku_field = schema_builder.add_text_field("sku", STRING);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        {
+            let mut index_writer = index.writer_for_tests()?;
+            index_writer.add_document(doc!(sku_field => "a"));
+            index_writer.add_document(doc!(sku_field => "b"));
+            index_writer.add_document(doc!(sku_field => "c"));
+            index_writer.commit()?;
+            index_writer.add_document(doc!(sku_field => "b"));
+            index_writer.add_document(doc!(sku_field => "d"));
+            index_writer.commit()?;
+        }
+        let searcher = index.reader()?.searcher();
+        let term_set_query = |skus: &[&str]| {
+            TermSetQuery::new(
+                sku_field,
+                skus.iter()
+                    .map(|sku| Term::from_field_text(sku_field, sku))
+                    .collect::<Vec<Term>>(),
+            )
+        };
+        assert_eq!(searcher.search(&term_set_query(&[]), &Count)?, 0);
+        assert_eq!(searcher.search(&term_set_query(&["e"]), &Count)?, 0);
+        assert_eq!(searcher.search(&term_set_query(&["b"]), &Count)?, 2);
+        assert_eq!(
+            searcher.search(&term_set_query(&["d", "b", "e"]), &Count)?,
+            3
+        );
+        assert_eq!(
+            searcher.search(&term_set_query(&["a", "a", "c"]), &Count)?,
+            2
+        );
+        let top_docs = searcher.search(&term_set_query(&["c", "d"]), &TopDocs::with_limit(3))?;
+        let mut doc_addresses: Vec<DocAddress> = top_docs
+            .into_iter()
+            .map(|(score, doc_address)| {
+                assert_eq!(score, 1.0);
+                doc_address
+            })
+            .collect();
+        doc_addresses.sort();
+        assert_eq!(doc_addresses, vec![DocAddress(0, 2), DocAddress(1, 1)]);
+        let explanation = term_set_query(&["c"]).explain(&searcher, DocAddress(0, 2))?;
+        assert_eq!(explanation.value(), 1.0);
+        assert!(term_set_query(&["c"])
+            .explain(&searcher, DocAddress(0, 1))
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_term_set_query_terms() {
+        let mut schema_builder = Schema::builder();
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let _schema = schema_builder.build();
+        let term_b = Term::from_field_text(text_field, "b");
+        let term_a = Term::from_field_text(text_field, "a");
+        let query = TermSetQuery::new(
+            text_field,
+            vec![term_b.clone(), term_a.clone(), term_b.clone()],
+        );
+        assert_eq!(query.terms(), &[term_a.clone(), term_b.clone()]);
+        let mut terms = BTreeSet::new();
+        query.query_terms(&mut terms);
+        assert_eq!(
+            terms.into_iter().collect::<Vec<Term>>(),
+            vec![term_a, term_b]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_term_set_query_wrong_field() {
+        let mut schema_builder = Schema::builder();
+        let title_field = schema_builder.add_text_field("title", TEXT);
+        let body_field = schema_builder.add_text_field("body", TEXT);
+        let _schema = schema_builder.build();
+        TermSetQuery::new(title_field, vec![Term::from_field_text(body_field, "a")]);
+    }
+}
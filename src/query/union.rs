@@ -36,6 +36,8 @@ pub struct Union<TScorer, TScoreCombiner = DoNothingCombiner> {
     offset: DocId,
     doc: DocId,
     score: Score,
+    // Documents skipped by the docsets that have been entirely consumed, and removed.
+    removed_docs_skipped: u64,
 }
 
 impl<TScorer, TScoreCombiner> From<Vec<TScorer>> for Union<TScorer, TScoreCombiner>
@@ -56,6 +58,7 @@ where
             offset: 0,
             doc: 0,
             score: 0.0,
+            removed_docs_skipped: 0u64,
         };
         if union.refill() {
             union.advance();
@@ -71,6 +74,7 @@ fn refill<TScorer: Scorer, TScoreCombiner: ScoreCombiner>(
     bitsets: &mut [TinySet; HORIZON_NUM_TINYBITSETS],
     score_combiner: &mut [TScoreCombiner; HORIZON as usize],
     min_doc: DocId,
+    removed_docs_skipped: &mut u64,
 ) {
     unordered_drain_filter(scorers, |scorer| {
         let horizon = min_doc + HORIZON as u32;
@@ -85,6 +89,7 @@ fn refill<TScorer: Scorer, TScoreCombiner: ScoreCombiner>(
             score_combiner[delta as usize].update(scorer);
             if scorer.advance() == TERMINATED {
                 // remove the docset, it has been entirely consumed.
+                *removed_docs_skipped += scorer.docs_skipped();
                 return true;
             }
         }
@@ -102,6 +107,7 @@ impl<TScorer: Scorer, TScoreCombiner: ScoreCombiner> Union<TScorer, TScoreCombin
                 &mut *self.bitsets,
                 &mut *self.scores,
                 min_doc,
+                &mut self.removed_docs_skipped,
             );
             true
         } else {
@@ -182,11 +188,16 @@ where
             // The target is outside of the buffered horizon.
             // advance all docsets to a doc >= to the target.
             #[cfg_attr(feature = "cargo-clippy", allow(clippy::clippy::collapsible_if))]
+            let removed_docs_skipped = &mut self.removed_docs_skipped;
             unordered_drain_filter(&mut self.docsets, |docset| {
                 if docset.doc() < target {
                     docset.seek(target);
                 }
-                docset.doc() == TERMINATED
+                if docset.doc() == TERMINATED {
+                    *removed_docs_skipped += docset.docs_skipped();
+                    return true;
+                }
+                false
             });
 
             // at this point all of the docsets
@@ -213,6 +224,15 @@ where
             .unwrap_or(0u32)
     }
 
+    fn docs_skipped(&self) -> u64 {
+        self.removed_docs_skipped
+            + self
+                .docsets
+                .iter()
+                .map(|docset| docset.docs_skipped())
+                .sum::<u64>()
+    }
+
     fn count_including_deleted(&mut self) -> u32 {
         if self.doc == TERMINATED {
             return 0;
@@ -0,0 +1,190 @@
+use super::knn_weight::KnnWeight;
+use super::KnnQuery;
+use crate::query::{Query, Weight};
+use crate::{DocAddress, Score, Searcher, TantivyError, Term};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+
+/// `HybridQuery` combines the scores of a keyword query and of a
+/// [`KnnQuery`](./struct.KnnQuery.html).
+///
+/// It matches the documents matched by either of the queries. Their score is
+/// `alpha * keyword_score + (1 - alpha) * vector_score`, where both scores are
+/// normalized to `[0, 1]` with a min-max normalization over the documents
+/// matched by their query. A document that is not matched by one of the queries
+/// gets a normalized score of 0 for it.
+///
+/// As BM25 scores and vector similarities do not have the same scale, the
+/// scores can only be normalized once both queries were evaluated over all
+/// of the segments: the matching documents and their scores are computed when
+/// the query `Weight` is built. The keyword query should therefore be selective.
+///
+/// Segments without vectors, e.g. segments written before the vector field was
+/// added to the schema, only contribute the documents matched by the keyword query.
+///
+/// # Example
+///
+/// ```rust
+/// use tantivy::collector::TopDocs;
+/// use tantivy::query::{HybridQuery, KnnQuery, QueryParser};
+/// use tantivy::schema::{DenseVectorOptions, Schema, VectorSimilarity, STORED, TEXT};
+/// use tantivy::{Document, Index};
+/// # fn test() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let title = schema_builder.add_text_field("title", TEXT | STORED);
+/// let embedding = schema_builder.add_dense_vector_field(
+///     "embedding",
+///     DenseVectorOptions::new(2, VectorSimilarity::Cosine),
+/// );
+/// let index = Index::create_in_ram(schema_builder.build());
+/// let mut index_writer = index.writer_with_num_threads(1, 10_000_000)?;
+/// for &(doc_title, vector) in &[("red apple", [0.0, 1.0]), ("green apple", [1.0, 0.0]), ("pear", [0.1, 1.0])] {
+///     let mut doc = Document::new();
+///     doc.add_text(title, doc_title);
+///     doc.add_dense_vector(embedding, vector.to_vec());
+///     index_writer.add_document(doc);
+/// }
+/// index_writer.commit()?;
+///
+/// let searcher = index.reader()?.searcher();
+/// let keyword_query = QueryParser::for_index(&index, vec![title]).parse_query("apple")?;
+/// let vector_query = KnnQuery::new(embedding, vec![0.0, 1.0], 2);
+/// let query = HybridQuery::new(keyword_query, vector_query, 0.5);
+/// let top_docs = searcher.search(&query, &TopDocs::with_limit(10))?;
+/// assert_eq!(top_docs.len(), 3);
+/// let best_doc = searcher.doc(top_docs[0].1)?;
+/// assert_eq!(best_doc.get_first(title).unwrap().text(), Some("red apple"));
+/// Ok(())
+/// # }
+/// # assert!(test().is_ok());
+/// ```
+pub struct HybridQuery {
+    keyword: Box<dyn Query>,
+    vector: KnnQuery,
+    alpha: Score,
+}
+
+impl HybridQuery {
+    /// Creates a new `HybridQuery`.
+    ///
+    /// `alpha` is the weight of the keyword query, between 0 and 1.
+    /// The vector query gets a weight of `1 - alpha`.
+    pub fn new(keyword: Box<dyn Query>, vector: KnnQuery, alpha: Score) -> HybridQuery {
+        HybridQuery {
+            keyword,
+            vector,
+            alpha,
+        }
+    }
+
+    /// Returns the keyword query.
+    pub fn keyword(&self) -> &dyn Query {
+        self.keyword.as_ref()
+    }
+
+    /// Returns the vector query.
+    pub fn vector(&self) -> &KnnQuery {
+        &self.vector
+    }
+
+    /// Returns the weight of the keyword query.
+    pub fn alpha(&self) -> Score {
+        self.alpha
+    }
+}
+
+impl Clone for HybridQuery {
+    fn clone(&self) -> Self {
+        HybridQuery {
+            keyword: self.keyword.box_clone(),
+            vector: self.vector.clone(),
+            alpha: self.alpha,
+        }
+    }
+}
+
+impl fmt::Debug for HybridQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Hybrid(keyword={:?}, vector={:?}, alpha={})",
+            self.keyword, self.vector, self.alpha
+        )
+    }
+}
+
+/// Returns the score of all of the documents matched by `query`.
+fn matching_docs(
+    searcher: &Searcher,
+    query: &dyn Query,
+) -> crate::Result<Vec<(DocAddress, Score)>> {
+    let weight = query.weight(searcher, true)?;
+    let mut docs = Vec::new();
+    for (segment_ord, segment_reader) in searcher.segment_readers().iter().enumerate() {
+        let delete_bitset_opt = segment_reader.delete_bitset();
+        weight.for_each(segment_reader, &mut |doc, score| {
+            if !matches!(delete_bitset_opt, Some(delete_bitset) if delete_bitset.is_deleted(doc)) {
+                docs.push((DocAddress(segment_ord as u32, doc), score));
+            }
+        })?;
+    }
+    Ok(docs)
+}
+
+/// Adds the scores of `docs`, normalized to `[0, 1]` and multiplied by `weight`,
+/// to `combined_scores`.
+fn add_normalized_scores(
+    docs: &[(DocAddress, Score)],
+    weight: Score,
+    combined_scores: &mut BTreeMap<DocAddress, Score>,
+) {
+    let min_score = docs
+        .iter()
+        .map(|&(_, score)| score)
+        .fold(Score::INFINITY, Score::min);
+    let max_score = docs
+        .iter()
+        .map(|&(_, score)| score)
+        .fold(Score::NEG_INFINITY, Score::max);
+    for &(doc_address, score) in docs {
+        let normalized_score = if max_score > min_score {
+            (score - min_score) / (max_score - min_score)
+        } else {
+            1.0
+        };
+        *combined_scores.entry(doc_address).or_insert(0.0) += weight * normalized_score;
+    }
+}
+
+impl Query for HybridQuery {
+    fn weight(
+        &self,
+        searcher: &Searcher,
+        _scoring_enabled: bool,
+    ) -> crate::Result<Box<dyn Weight>> {
+        if !(0.0..=1.0).contains(&self.alpha) {
+            return Err(TantivyError::InvalidArgument(format!(
+                "The alpha of a HybridQuery must be between 0 and 1, got {}.",
+                self.alpha
+            )));
+        }
+        let keyword_docs = matching_docs(searcher, self.keyword.as_ref())?;
+        let vector_docs = matching_docs(searcher, &self.vector)?;
+        let mut combined_scores = BTreeMap::new();
+        add_normalized_scores(&keyword_docs, self.alpha, &mut combined_scores);
+        add_normalized_scores(&vector_docs, 1.0 - self.alpha, &mut combined_scores);
+        let mut segment_docs = HashMap::new();
+        for (DocAddress(segment_ord, doc), score) in combined_scores {
+            let segment_id = searcher.segment_reader(segment_ord).segment_id();
+            segment_docs
+                .entry(segment_id)
+                .or_insert_with(Vec::new)
+                .push((doc, score));
+        }
+        Ok(Box::new(KnnWeight::new("HybridQuery score", segment_docs)))
+    }
+
+    fn query_terms(&self, term_set: &mut BTreeSet<Term>) {
+        self.keyword.query_terms(term_set)
+    }
+}
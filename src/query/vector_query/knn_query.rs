@@ -0,0 +1,162 @@
+use super::knn_weight::KnnWeight;
+use crate::collector::ComparableDoc;
+use crate::query::{Query, Weight};
+use crate::schema::{Field, FieldType};
+use crate::{DocAddress, Score, Searcher, TantivyError};
+use std::collections::{BinaryHeap, HashMap};
+
+/// `KnnQuery` matches the `k` documents whose vector is the most
+/// similar to a given query vector.
+///
+/// The similarity function is the one declared in the schema
+/// for the dense vector field.
+///
+/// The score of a matched document is the similarity between its vector
+/// and the query vector, so that the `KnnQuery` can be combined with other
+/// queries (e.g. in a `BooleanQuery`) for hybrid retrieval.
+///
+/// The `k` documents are the `k` nearest neighbors over the entire index,
+/// and not within each segment.
+///
+/// # Implementation
+///
+/// The search is exact: the vectors of all of the documents are scanned
+/// when the query `Weight` is built.
+///
+/// # Example
+///
+/// ```rust
+/// use tantivy::collector::TopDocs;
+/// use tantivy::query::KnnQuery;
+/// use tantivy::schema::{DenseVectorOptions, Schema, VectorSimilarity, STORED, TEXT};
+/// use tantivy::{Document, Index};
+/// # fn test() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let title = schema_builder.add_text_field("title", TEXT | STORED);
+/// let embedding = schema_builder.add_dense_vector_field(
+///     "embedding",
+///     DenseVectorOptions::new(2, VectorSimilarity::Cosine),
+/// );
+/// let schema = schema_builder.build();
+///
+/// let index = Index::create_in_ram(schema);
+/// let mut index_writer = index.writer_with_num_threads(1, 10_000_000)?;
+/// for &(doc_title, vector) in &[("north", [0.0, 1.0]), ("east", [1.0, 0.0]), ("north east", [1.0, 1.0])] {
+///     let mut doc = Document::new();
+///     doc.add_text(title, doc_title);
+///     doc.add_dense_vector(embedding, vector.to_vec());
+///     index_writer.add_document(doc);
+/// }
+/// index_writer.commit()?;
+///
+/// let reader = index.reader()?;
+/// let searcher = reader.searcher();
+/// let query = KnnQuery::new(embedding, vec![0.1, 1.0], 2);
+/// let top_docs = searcher.search(&query, &TopDocs::with_limit(10))?;
+/// assert_eq!(top_docs.len(), 2);
+/// let best_doc = searcher.doc(top_docs[0].1)?;
+/// assert_eq!(best_doc.get_first(title).unwrap().text(), Some("north"));
+/// Ok(())
+/// # }
+/// # assert!(test().is_ok());
+/// ```
+#[derive(Clone, Debug)]
+pub struct KnnQuery {
+    field: Field,
+    vector: Vec<f32>,
+    k: usize,
+}
+
+impl KnnQuery {
+    /// Creates a new `KnnQuery` matching the `k` documents
+    /// whose vector is the most similar to `vector`.
+    pub fn new(field: Field, vector: Vec<f32>, k: usize) -> KnnQuery {
+        KnnQuery { field, vector, k }
+    }
+
+    /// Field to search over
+    pub fn field(&self) -> Field {
+        self.field
+    }
+
+    /// The query vector.
+    pub fn vector(&self) -> &[f32] {
+        &self.vector
+    }
+
+    /// The number of nearest neighbors to match.
+    pub fn k(&self) -> usize {
+        self.k
+    }
+}
+
+impl Query for KnnQuery {
+    fn weight(
+        &self,
+        searcher: &Searcher,
+        _scoring_enabled: bool,
+    ) -> crate::Result<Box<dyn Weight>> {
+        let field_entry = searcher.schema().get_field_entry(self.field);
+        let dense_vector_options =
+            if let FieldType::DenseVector(dense_vector_options) = field_entry.field_type() {
+                dense_vector_options
+            } else {
+                return Err(TantivyError::SchemaError(format!(
+                    "Field {:?} is not a dense vector field.",
+                    field_entry.name()
+                )));
+            };
+        if self.vector.len() != dense_vector_options.dims() {
+            return Err(TantivyError::InvalidArgument(format!(
+                "Expected a query vector of {} dimensions, got {}.",
+                dense_vector_options.dims(),
+                self.vector.len()
+            )));
+        }
+        let similarity = dense_vector_options.similarity();
+        // Min-heap holding the `k` best documents seen so far.
+        let mut top_docs: BinaryHeap<ComparableDoc<Score, DocAddress>> =
+            BinaryHeap::with_capacity(self.k);
+        let mut vector = Vec::with_capacity(self.vector.len());
+        for (segment_ord, segment_reader) in searcher.segment_readers().iter().enumerate() {
+            if self.k == 0 {
+                break;
+            }
+            let vector_reader = segment_reader.fast_fields().dense_vector(self.field)?;
+            for doc in segment_reader.doc_ids_alive() {
+                vector_reader.get_vector(doc, &mut vector);
+                if vector.is_empty() {
+                    continue;
+                }
+                let score = similarity.compute(&self.vector, &vector);
+                let doc_address = DocAddress(segment_ord as u32, doc);
+                if top_docs.len() < self.k {
+                    top_docs.push(ComparableDoc {
+                        feature: score,
+                        doc: doc_address,
+                    });
+                } else if let Some(mut head) = top_docs.peek_mut() {
+                    if head.feature < score {
+                        *head = ComparableDoc {
+                            feature: score,
+                            doc: doc_address,
+                        };
+                    }
+                }
+            }
+        }
+        let mut segment_top_docs = HashMap::new();
+        for top_doc in top_docs {
+            let DocAddress(segment_ord, doc) = top_doc.doc;
+            let segment_id = searcher.segment_reader(segment_ord).segment_id();
+            segment_top_docs
+                .entry(segment_id)
+                .or_insert_with(Vec::new)
+                .push((doc, top_doc.feature));
+        }
+        Ok(Box::new(KnnWeight::new(
+            "KnnQuery similarity",
+            segment_top_docs,
+        )))
+    }
+}
@@ -0,0 +1,97 @@
+use crate::core::SegmentId;
+use crate::docset::{DocSet, TERMINATED};
+use crate::query::explanation::does_not_match;
+use crate::query::{EmptyScorer, Explanation, Scorer, Weight};
+use crate::{DocId, Score, SegmentReader};
+use std::collections::HashMap;
+
+/// Weight of the `KnnQuery` and of the `HybridQuery`.
+///
+/// The nearest neighbors are computed over the entire index when the
+/// weight is built. It holds, for each segment, the matching documents
+/// and their score.
+pub(crate) struct KnnWeight {
+    description: &'static str,
+    segment_top_docs: HashMap<SegmentId, Vec<(DocId, Score)>>,
+}
+
+impl KnnWeight {
+    /// Creates a weight matching the given documents.
+    ///
+    /// `description` is the description of the scores in their `Explanation`.
+    pub fn new(
+        description: &'static str,
+        mut segment_top_docs: HashMap<SegmentId, Vec<(DocId, Score)>>,
+    ) -> KnnWeight {
+        for top_docs in segment_top_docs.values_mut() {
+            top_docs.sort_by_key(|&(doc, _)| doc);
+        }
+        KnnWeight {
+            description,
+            segment_top_docs,
+        }
+    }
+
+    fn top_docs(&self, reader: &SegmentReader) -> &[(DocId, Score)] {
+        self.segment_top_docs
+            .get(&reader.segment_id())
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+}
+
+impl Weight for KnnWeight {
+    fn scorer(&self, reader: &SegmentReader, boost: Score) -> crate::Result<Box<dyn Scorer>> {
+        let top_docs = self.top_docs(reader);
+        if top_docs.is_empty() {
+            return Ok(Box::new(EmptyScorer));
+        }
+        Ok(Box::new(KnnScorer {
+            top_docs: top_docs.to_vec(),
+            cursor: 0,
+            boost,
+        }))
+    }
+
+    fn explain(&self, reader: &SegmentReader, doc: DocId) -> crate::Result<Explanation> {
+        let top_docs = self.top_docs(reader);
+        let score = top_docs
+            .binary_search_by_key(&doc, |&(top_doc, _)| top_doc)
+            .map(|pos| top_docs[pos].1)
+            .map_err(|_| does_not_match(doc))?;
+        Ok(Explanation::new(self.description, score))
+    }
+}
+
+/// Scorer iterating over the nearest neighbors of a segment.
+struct KnnScorer {
+    top_docs: Vec<(DocId, Score)>,
+    cursor: usize,
+    boost: Score,
+}
+
+impl DocSet for KnnScorer {
+    fn advance(&mut self) -> DocId {
+        if self.cursor < self.top_docs.len() {
+            self.cursor += 1;
+        }
+        self.doc()
+    }
+
+    fn doc(&self) -> DocId {
+        self.top_docs
+            .get(self.cursor)
+            .map(|&(doc, _)| doc)
+            .unwrap_or(TERMINATED)
+    }
+
+    fn size_hint(&self) -> u32 {
+        self.top_docs.len() as u32
+    }
+}
+
+impl Scorer for KnnScorer {
+    fn score(&mut self) -> Score {
+        self.top_docs[self.cursor].1 * self.boost
+    }
+}
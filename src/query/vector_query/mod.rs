@@ -0,0 +1,221 @@
+mod hybrid_query;
+mod knn_query;
+mod knn_weight;
+
+pub use self::hybrid_query::HybridQuery;
+pub use self::knn_query::KnnQuery;
+
+#[cfg(test)]
+mod tests {
+    use super::{HybridQuery, KnnQuery};
+    use crate::assert_nearly_equals;
+    use crate::collector::{Count, TopDocs};
+    use crate::query::{BooleanQuery, Occur, Query, TermQuery};
+    use crate::schema::{
+        DenseVectorOptions, Field, IndexRecordOption, Schema, Value, VectorSimilarity, STORED,
+        STRING,
+    };
+    use crate::{Index, Score, Searcher, TantivyError, Term};
+
+    const DOCS: &[(&str, &str, [f32; 2])] = &[
+        ("north", "a", [0.0, 1.0]),
+        ("east", "b", [1.0, 0.0]),
+        ("north_east", "a", [1.0, 1.0]),
+        ("south", "b", [0.0, -1.0]),
+        ("west", "a", [-2.0, 0.0]),
+    ];
+
+    fn create_index(similarity: VectorSimilarity) -> crate::Result<(Index, Field, Field, Field)> {
+        let mut schema_builder = Schema::builder();
+        let name = schema_builder.add_text_field("name", STRING | STORED);
+        let category = schema_builder.add_text_field("category", STRING);
+        let embedding = schema_builder
+            .add_dense_vector_field("embedding", DenseVectorOptions::new(2, similarity));
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_for_tests()?;
+            for (i, &(doc_name, doc_category, vector)) in DOCS.iter().enumerate() {
+                index_writer.add_document(doc!(
+                    name => doc_name,
+                    category => doc_category,
+                    embedding => Value::DenseVector(vector.to_vec()),
+                ));
+                if i == 2 {
+                    index_writer.commit()?;
+                }
+            }
+            index_writer.add_document(doc!(name => "nowhere", category => "a"));
+            index_writer.commit()?;
+        }
+        Ok((index, name, category, embedding))
+    }
+
+    fn top_names(
+        searcher: &Searcher,
+        name: Field,
+        query: &dyn Query,
+    ) -> crate::Result<Vec<String>> {
+        let mut names = Vec::new();
+        for (_, doc_address) in searcher.search(query, &TopDocs::with_limit(10))? {
+            let doc = searcher.doc(doc_address)?;
+            names.push(doc.get_first(name).unwrap().text().unwrap().to_string());
+        }
+        Ok(names)
+    }
+
+    #[test]
+    fn test_knn_query_cosine() -> crate::Result<()> {
+        let (index, name, _, embedding) = create_index(VectorSimilarity::Cosine)?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 2);
+        let query = KnnQuery::new(embedding, vec![0.1, 1.0], 3);
+        assert_eq!(
+            top_names(&searcher, name, &query)?,
+            vec!["north", "north_east", "east"]
+        );
+        assert_eq!(searcher.search(&query, &Count)?, 3);
+        let top_docs = searcher.search(&query, &TopDocs::with_limit(1))?;
+        let (score, doc_address) = top_docs[0];
+        assert!((score - 0.995).abs() < 1e-3);
+        let explanation = query.explain(&searcher, doc_address)?;
+        assert!((explanation.value() - score).abs() < 1e-6);
+        let all_query = KnnQuery::new(embedding, vec![0.1, 1.0], 10);
+        assert_eq!(searcher.search(&all_query, &Count)?, DOCS.len());
+        let no_query = KnnQuery::new(embedding, vec![0.1, 1.0], 0);
+        assert_eq!(searcher.search(&no_query, &Count)?, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_knn_query_dot_product() -> crate::Result<()> {
+        let (index, name, _, embedding) = create_index(VectorSimilarity::DotProduct)?;
+        let searcher = index.reader()?.searcher();
+        let query = KnnQuery::new(embedding, vec![-1.0, 0.5], 2);
+        assert_eq!(top_names(&searcher, name, &query)?, vec!["west", "north"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_knn_query_with_deletes() -> crate::Result<()> {
+        let (index, name, _, embedding) = create_index(VectorSimilarity::Cosine)?;
+        {
+            let mut index_writer = index.writer_for_tests()?;
+            index_writer.delete_term(Term::from_field_text(name, "north"));
+            index_writer.commit()?;
+        }
+        let searcher = index.reader()?.searcher();
+        let query = KnnQuery::new(embedding, vec![0.1, 1.0], 2);
+        assert_eq!(
+            top_names(&searcher, name, &query)?,
+            vec!["north_east", "east"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_knn_query_in_boolean_query() -> crate::Result<()> {
+        let (index, name, category, embedding) = create_index(VectorSimilarity::Cosine)?;
+        let searcher = index.reader()?.searcher();
+        let category_query: Box<dyn Query> = Box::new(TermQuery::new(
+            Term::from_field_text(category, "a"),
+            IndexRecordOption::Basic,
+        ));
+        let knn_query: Box<dyn Query> = Box::new(KnnQuery::new(embedding, vec![1.0, 0.1], 2));
+        let query = BooleanQuery::from(vec![
+            (Occur::Must, category_query),
+            (Occur::Must, knn_query),
+        ]);
+        assert_eq!(top_names(&searcher, name, &query)?, vec!["north_east"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_knn_query_errors() -> crate::Result<()> {
+        let (index, name, _, embedding) = create_index(VectorSimilarity::Cosine)?;
+        let searcher = index.reader()?.searcher();
+        let query = KnnQuery::new(embedding, vec![1.0, 2.0, 3.0], 2);
+        assert!(searcher.search(&query, &Count).is_err());
+        let query = KnnQuery::new(name, vec![1.0, 2.0], 2);
+        assert!(searcher.search(&query, &Count).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_hybrid_query() -> crate::Result<()> {
+        let (index, name, category, embedding) = create_index(VectorSimilarity::Cosine)?;
+        let searcher = index.reader()?.searcher();
+        let keyword_query: Box<dyn Query> = Box::new(TermQuery::new(
+            Term::from_field_text(category, "a"),
+            IndexRecordOption::Basic,
+        ));
+        let knn_query = KnnQuery::new(embedding, vec![0.1, 1.0], 3);
+        let query = HybridQuery::new(keyword_query.box_clone(), knn_query.clone(), 0.5);
+        assert_eq!(searcher.search(&query, &Count)?, 5);
+        let top_docs = searcher.search(&query, &TopDocs::with_limit(10))?;
+        let scores: Vec<Score> = top_docs.iter().map(|&(score, _)| score).collect();
+        assert_nearly_equals!(scores[0], 1.0);
+        assert_nearly_equals!(scores[1], 0.8766);
+        assert_nearly_equals!(scores[2], 0.5);
+        assert_nearly_equals!(scores[3], 0.5);
+        assert_nearly_equals!(scores[4], 0.0);
+        let names = top_names(&searcher, name, &query)?;
+        assert_eq!(&names[..2], &["north", "north_east"]);
+        assert_eq!(names[4], "east");
+        let explanation = query.explain(&searcher, top_docs[1].1)?;
+        assert_nearly_equals!(explanation.value(), 0.8766);
+
+        let vector_only_query = HybridQuery::new(keyword_query.box_clone(), knn_query.clone(), 0.0);
+        assert_eq!(
+            &top_names(&searcher, name, &vector_only_query)?[..3],
+            &["north", "north_east", "east"]
+        );
+        let invalid_query = HybridQuery::new(keyword_query, knn_query, 1.5);
+        assert!(matches!(
+            searcher.search(&invalid_query, &Count),
+            Err(TantivyError::InvalidArgument(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_hybrid_query_with_segments_without_vectors() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let name = schema_builder.add_text_field("name", STRING | STORED);
+        let mut index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_for_tests()?;
+            index_writer.add_document(doc!(name => "old"));
+            index_writer.commit()?;
+        }
+        let mut schema_builder = Schema::builder();
+        schema_builder.add_text_field("name", STRING | STORED);
+        let embedding = schema_builder.add_dense_vector_field(
+            "embedding",
+            DenseVectorOptions::new(2, VectorSimilarity::Cosine),
+        );
+        index.evolve_schema(schema_builder.build())?;
+        {
+            let mut index_writer = index.writer_for_tests()?;
+            index_writer.add_document(doc!(
+                name => "new",
+                embedding => Value::DenseVector(vec![0.0, 1.0]),
+            ));
+            index_writer.commit()?;
+        }
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 2);
+        let keyword_query: Box<dyn Query> = Box::new(TermQuery::new(
+            Term::from_field_text(name, "old"),
+            IndexRecordOption::Basic,
+        ));
+        let query = HybridQuery::new(
+            keyword_query,
+            KnnQuery::new(embedding, vec![0.0, 1.0], 5),
+            0.5,
+        );
+        let mut names = top_names(&searcher, name, &query)?;
+        names.sort();
+        assert_eq!(names, vec!["new", "old"]);
+        Ok(())
+    }
+}
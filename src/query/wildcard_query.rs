@@ -0,0 +1,176 @@
+use crate::error::TantivyError;
+use crate::query::{AutomatonWeight, Query, Weight};
+use crate::schema::Field;
+use crate::Searcher;
+use tantivy_fst::Regex;
+
+/// Default maximum number of terms a `WildcardQuery` may match within a segment.
+const DEFAULT_MAX_EXPANSIONS: usize = 1_024;
+
+/// Translates a wildcard pattern into an equivalent regular expression.
+fn wildcard_to_regex(pattern: &str) -> String {
+    let mut regex_pattern = String::with_capacity(pattern.len() * 2);
+    for c in pattern.chars() {
+        match c {
+            '*' => regex_pattern.push_str(".*"),
+            '?' => regex_pattern.push('.'),
+            '\\' | '.' | '+' | '(' | ')' | '|' | '[' | ']' | '{' | '}' | '^' | '$' | '#' | '&'
+            | '-' | '~' => {
+                regex_pattern.push('\\');
+                regex_pattern.push(c);
+            }
+            _ => regex_pattern.push(c),
+        }
+    }
+    regex_pattern
+}
+
+/// A Wildcard Query matches all of the documents
+/// containing a term that matches a wildcard pattern.
+///
+/// In the pattern, `*` matches any sequence of characters (including
+/// the empty sequence), and `?` matches exactly one character.
+///
+/// The pattern is matched against the terms of the term dictionary,
+/// as is. It is not processed by the tokenizer of the field.
+///
+/// In order to prevent a pattern like `a*` from matching a huge number of terms,
+/// the number of terms matched within a segment is bounded by `max_expansions`.
+/// Running the query returns an error if this limit is exceeded.
+///
+/// ```rust
+/// use tantivy::collector::Count;
+/// use tantivy::query::WildcardQuery;
+/// use tantivy::schema::{Schema, TEXT};
+/// use tantivy::{doc, Index};
+///
+/// # fn test() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let title = schema_builder.add_text_field("title", TEXT);
+/// let schema = schema_builder.build();
+/// let index = Index::create_in_ram(schema);
+/// {
+///     let mut index_writer = index.writer(3_000_000)?;
+///     index_writer.add_document(doc!(
+///         title => "The Name of the Wind",
+///     ));
+///     index_writer.add_document(doc!(
+///         title => "The Diary of Muadib",
+///     ));
+///     index_writer.add_document(doc!(
+///         title => "A Dairy Cow",
+///     ));
+///     index_writer.add_document(doc!(
+///         title => "The Diary of a Young Girl",
+///     ));
+///     index_writer.commit().unwrap();
+/// }
+///
+/// let reader = index.reader()?;
+/// let searcher = reader.searcher();
+///
+/// let query = WildcardQuery::new(title, "d??ry");
+/// assert_eq!(searcher.search(&query, &Count)?, 3);
+/// let query = WildcardQuery::new(title, "*i*y").with_max_expansions(1);
+/// assert!(searcher.search(&query, &Count).is_err());
+/// Ok(())
+/// # }
+/// # assert!(test().is_ok());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WildcardQuery {
+    field: Field,
+    pattern: String,
+    max_expansions: usize,
+}
+
+impl WildcardQuery {
+    /// Creates a new `WildcardQuery` from a given pattern.
+    pub fn new(field: Field, pattern: &str) -> WildcardQuery {
+        WildcardQuery {
+            field,
+            pattern: pattern.to_string(),
+            max_expansions: DEFAULT_MAX_EXPANSIONS,
+        }
+    }
+
+    /// Sets the maximum number of terms the pattern may match within a segment.
+    ///
+    /// Defaults to 1024.
+    pub fn with_max_expansions(mut self, max_expansions: usize) -> WildcardQuery {
+        self.max_expansions = max_expansions;
+        self
+    }
+
+    /// The `Field` this `WildcardQuery` is targeting.
+    pub fn field(&self) -> Field {
+        self.field
+    }
+
+    /// The wildcard pattern.
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    fn specialized_weight(&self) -> crate::Result<AutomatonWeight<Regex>> {
+        let regex = Regex::new(&wildcard_to_regex(&self.pattern))
+            .map_err(|_| TantivyError::InvalidArgument(self.pattern.clone()))?;
+        Ok(AutomatonWeight::new(self.field, regex).with_max_expansions(self.max_expansions))
+    }
+}
+
+impl Query for WildcardQuery {
+    fn weight(
+        &self,
+        _searcher: &Searcher,
+        _scoring_enabled: bool,
+    ) -> crate::Result<Box<dyn Weight>> {
+        Ok(Box::new(self.specialized_weight()?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{wildcard_to_regex, WildcardQuery};
+    use crate::collector::Count;
+    use crate::schema::{Schema, STRING};
+    use crate::Index;
+
+    #[test]
+    fn test_wildcard_to_regex() {
+        assert_eq!(wildcard_to_regex("prog*"), "prog.*");
+        assert_eq!(wildcard_to_regex("te?t"), "te.t");
+        assert_eq!(wildcard_to_regex("a.b*(c)"), "a\\.b.*\\(c\\)");
+    }
+
+    #[test]
+    fn test_wildcard_query() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let sku_field = schema_builder.add_text_field("sku", STRING);
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_for_tests()?;
+            for sku in &["ab.1", "ab.2", "abc", "b.1", "Ab.3"] {
+                index_writer.add_document(doc!(sku_field => *sku));
+            }
+            index_writer.commit()?;
+        }
+        let searcher = index.reader()?.searcher();
+        let count =
+            |pattern: &str| searcher.search(&WildcardQuery::new(sku_field, pattern), &Count);
+        assert_eq!(count("ab*")?, 3);
+        assert_eq!(count("ab.?")?, 2);
+        assert_eq!(count("a?.?")?, 2);
+        assert_eq!(count("*.1")?, 2);
+        assert_eq!(count("*")?, 5);
+        assert_eq!(count("ab")?, 0);
+        assert_eq!(count("abc")?, 1);
+        assert_eq!(count("[ab]*")?, 0);
+        let limited_query = WildcardQuery::new(sku_field, "ab*").with_max_expansions(3);
+        assert_eq!(searcher.search(&limited_query, &Count)?, 3);
+        let limited_query = WildcardQuery::new(sku_field, "ab*").with_max_expansions(2);
+        assert!(searcher.search(&limited_query, &Count).is_err());
+        Ok(())
+    }
+}
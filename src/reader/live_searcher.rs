@@ -0,0 +1,200 @@
+use super::{IndexReader, LeasedItem, ReloadPolicy};
+use crate::collector::Collector;
+use crate::query::Query;
+use crate::{Index, Opstamp, Searcher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+
+/// Opstamps shared by a `LiveSearcher` and the `IndexWriter`s it is registered to.
+pub(crate) struct LiveSearcherState {
+    // Opstamp of the last commit notified by an `IndexWriter`.
+    committed_opstamp: AtomicU64,
+    // Opstamp of the commit that was last loaded, at least.
+    loaded_opstamp: AtomicU64,
+    // Serializes the reloads, so that a commit is only loaded once.
+    reload_lock: Mutex<()>,
+}
+
+impl LiveSearcherState {
+    fn notify_commit(&self, opstamp: Opstamp) {
+        self.committed_opstamp.fetch_max(opstamp, Ordering::Release);
+    }
+}
+
+/// List of the `LiveSearcher`s registered to an `IndexWriter`.
+///
+/// The `LiveSearcher`s are not kept alive by the `IndexWriter`.
+#[derive(Clone, Default)]
+pub(crate) struct LiveSearcherRegistry(Arc<Mutex<Vec<Weak<LiveSearcherState>>>>);
+
+impl LiveSearcherRegistry {
+    pub fn register(&self, live_searcher: &LiveSearcher) {
+        let mut states = self.0.lock().unwrap();
+        states.retain(|state| state.strong_count() > 0);
+        states.push(Arc::downgrade(&live_searcher.state));
+    }
+
+    /// Notifies the registered `LiveSearcher`s that the commit `opstamp` succeeded.
+    pub fn notify_commit(&self, opstamp: Opstamp) {
+        let mut states = self.0.lock().unwrap();
+        states.retain(|state| {
+            if let Some(state) = state.upgrade() {
+                state.notify_commit(opstamp);
+                true
+            } else {
+                false
+            }
+        });
+    }
+}
+
+/// `LiveSearcher` searches the last commit of the index, without having to reload it.
+///
+/// It wraps an `IndexReader` with `ReloadPolicy::Manual`. Once registered with
+/// [`IndexWriter::register_live_searcher`](../struct.IndexWriter.html#method.register_live_searcher),
+/// it is notified synchronously by each successful commit of the `IndexWriter`,
+/// and the next search reloads the index. No thread watches or polls the index:
+/// checking for a new commit costs an atomic load per search.
+///
+/// It is meant for a single process indexing and searching the same index.
+/// The commits of the `IndexWriter`s it is not registered to are only seen
+/// after an explicit [`reload`](#method.reload).
+///
+/// `Clone` shares the underlying `IndexReader`.
+///
+/// ```rust
+/// use tantivy::collector::Count;
+/// use tantivy::query::AllQuery;
+/// use tantivy::schema::{Schema, TEXT};
+/// use tantivy::{doc, Index, LiveSearcher};
+///
+/// # fn main() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let title = schema_builder.add_text_field("title", TEXT);
+/// let index = Index::create_in_ram(schema_builder.build());
+/// let live_searcher = LiveSearcher::new(&index)?;
+/// let mut index_writer = index.writer(3_000_000)?;
+/// index_writer.register_live_searcher(&live_searcher);
+///
+/// index_writer.add_document(doc!(title => "The Name of the Wind"));
+/// index_writer.commit()?;
+/// assert_eq!(live_searcher.search(&AllQuery, &Count)?, 1);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct LiveSearcher {
+    reader: IndexReader,
+    state: Arc<LiveSearcherState>,
+}
+
+impl LiveSearcher {
+    /// Creates a `LiveSearcher` on the last commit of `index`.
+    pub fn new(index: &Index) -> crate::Result<LiveSearcher> {
+        let loaded_opstamp = index.load_metas()?.opstamp;
+        let reader = index
+            .reader_builder()
+            .reload_policy(ReloadPolicy::Manual)
+            .try_into()?;
+        let state = LiveSearcherState {
+            loaded_opstamp: AtomicU64::new(loaded_opstamp),
+            committed_opstamp: AtomicU64::new(loaded_opstamp),
+            reload_lock: Mutex::new(()),
+        };
+        Ok(LiveSearcher {
+            reader,
+            state: Arc::new(state),
+        })
+    }
+
+    /// Returns the underlying `IndexReader`.
+    pub fn reader(&self) -> &IndexReader {
+        &self.reader
+    }
+
+    /// Reloads the last commit of the index, regardless of the notifications.
+    pub fn reload(&self) -> crate::Result<()> {
+        let _reload_guard = self.state.reload_lock.lock().unwrap();
+        self.reader.reload()
+    }
+
+    fn reload_if_committed(&self) -> crate::Result<()> {
+        let committed_opstamp = self.state.committed_opstamp.load(Ordering::Acquire);
+        if committed_opstamp <= self.state.loaded_opstamp.load(Ordering::Acquire) {
+            return Ok(());
+        }
+        let _reload_guard = self.state.reload_lock.lock().unwrap();
+        // Another thread may have reloaded the commit in the meantime.
+        if committed_opstamp <= self.state.loaded_opstamp.load(Ordering::Acquire) {
+            return Ok(());
+        }
+        self.reader.reload()?;
+        self.state
+            .loaded_opstamp
+            .fetch_max(committed_opstamp, Ordering::Release);
+        Ok(())
+    }
+
+    /// Returns a searcher on the last commit notified to the `LiveSearcher`,
+    /// reloading the index if needed.
+    ///
+    /// As with [`IndexReader::searcher`](./struct.IndexReader.html#method.searcher),
+    /// the same searcher must be used for a given query.
+    pub fn searcher(&self) -> crate::Result<LeasedItem<Searcher>> {
+        self.reload_if_committed()?;
+        Ok(self.reader.searcher())
+    }
+
+    /// Runs a query on the last commit notified to the `LiveSearcher`,
+    /// reloading the index if needed.
+    pub fn search<C: Collector>(
+        &self,
+        query: &dyn Query,
+        collector: &C,
+    ) -> crate::Result<C::Fruit> {
+        self.searcher()?.search(query, collector)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LiveSearcher;
+    use crate::collector::Count;
+    use crate::query::AllQuery;
+    use crate::schema::{Schema, TEXT};
+    use crate::Index;
+
+    #[test]
+    fn test_live_searcher() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let live_searcher = LiveSearcher::new(&index)?;
+        let unregistered_live_searcher = LiveSearcher::new(&index)?;
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.register_live_searcher(&live_searcher);
+        {
+            // Dropped searchers are unregistered.
+            let dropped_live_searcher = LiveSearcher::new(&index)?;
+            index_writer.register_live_searcher(&dropped_live_searcher);
+        }
+        assert_eq!(live_searcher.search(&AllQuery, &Count)?, 0);
+
+        index_writer.add_document(doc!(text => "hello"));
+        index_writer.commit()?;
+        assert_eq!(live_searcher.search(&AllQuery, &Count)?, 1);
+        assert_eq!(live_searcher.clone().searcher()?.num_docs(), 1);
+        assert_eq!(unregistered_live_searcher.search(&AllQuery, &Count)?, 0);
+
+        index_writer.add_document(doc!(text => "happy"));
+        index_writer.add_document(doc!(text => "tax payer"));
+        index_writer.rollback()?;
+        index_writer.add_document(doc!(text => "world"));
+        index_writer.commit()?;
+        assert_eq!(live_searcher.search(&AllQuery, &Count)?, 2);
+
+        unregistered_live_searcher.reload()?;
+        assert_eq!(unregistered_live_searcher.search(&AllQuery, &Count)?, 2);
+        Ok(())
+    }
+}
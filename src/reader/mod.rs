@@ -1,15 +1,32 @@
+mod live_searcher;
 mod pool;
+mod query_cache;
+mod segment_info;
+mod warm_up;
 
+pub use self::live_searcher::LiveSearcher;
+pub(crate) use self::live_searcher::LiveSearcherRegistry;
 pub use self::pool::LeasedItem;
 use self::pool::Pool;
-use crate::core::Segment;
+pub use self::query_cache::QueryCache;
+pub(crate) use self::query_cache::SearcherGeneration;
+pub use self::segment_info::{FieldStats, SegmentInfo};
+use crate::core::searcher::SearcherSegments;
+use crate::core::ChecksumVerifier;
+use crate::core::{Segment, SegmentId};
 use crate::directory::WatchHandle;
 use crate::directory::META_LOCK;
 use crate::directory::{Directory, WatchCallback};
+use crate::indexer::CancellationToken;
+use crate::query::BM25Params;
+use crate::schema::Field;
 use crate::Index;
+use crate::Opstamp;
 use crate::Searcher;
-use crate::SegmentReader;
-use std::sync::Arc;
+use crossbeam::channel::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, Weak};
+use std::thread;
+use std::time::Duration;
 use std::{convert::TryInto, io};
 
 /// Defines when a new version of the index should be reloaded.
@@ -27,6 +44,61 @@ pub enum ReloadPolicy {
     /// The index is reloaded within milliseconds after a new commit is available.
     /// This is made possible by watching changes in the `meta.json` file.
     OnCommit, // TODO add NEAR_REAL_TIME(target_ms)
+    /// The index is reloaded every `interval`, if its segments changed since
+    /// the last time they were checked.
+    ///
+    /// The reload is done by a background thread, which stops when the
+    /// `IndexReader` and its clones are dropped.
+    Scheduled {
+        /// Time between two checks of the segments of the index.
+        interval: Duration,
+    },
+}
+
+/// Defines when the segments of the index are opened.
+///
+/// Opening the readers of the segments requires to read their term dictionaries,
+/// fast field and field norm headers, for all of their fields. On indexes with
+/// hundreds of segments, this adds a significant latency to loading a new version
+/// of the index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SegmentOpening {
+    /// The segment readers are opened when the index is reloaded.
+    Eager,
+    /// The segment readers are opened when a searcher of the new version of the index
+    /// first needs them, typically by the first search.
+    ///
+    /// The files of the segments are still opened when the index is reloaded,
+    /// so that they cannot be garbage collected in the meantime.
+    ///
+    /// See also [`IndexReader::warm_all_segments`](./struct.IndexReader.html#method.warm_all_segments).
+    Lazy,
+}
+
+/// Defines when the checksums of the files of the segments are verified.
+///
+/// All of the files of a segment end with a footer storing the CRC-32 checksum of
+/// their content. Verifying it requires to read the whole file, but detects the
+/// corrupted files, e.g. by bit rot, which could otherwise silently produce wrong
+/// results. A corrupted file yields a `TantivyError::DataCorruption` error.
+///
+/// Each file is only verified once by a given `IndexReader`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerifyMode {
+    /// The checksums are not verified.
+    Disabled,
+    /// The checksums of the files of the segments are verified when the index is
+    /// reloaded, before the segments are opened.
+    ///
+    /// If a file is corrupted, the reload fails and the searchers of the previous
+    /// version of the index are kept.
+    OnOpen,
+    /// The checksums of the files of a segment are verified when its
+    /// `SegmentReader` is opened.
+    ///
+    /// With `SegmentOpening::Lazy`, this is done by the first search on a new
+    /// version of the index, which then returns the error.
+    OnAccess,
 }
 
 /// `IndexReader` builder
@@ -40,10 +112,26 @@ pub enum ReloadPolicy {
 /// - `reload_policy` (by default `ReloadPolicy::OnCommit`):
 ///
 ///   See [`ReloadPolicy`](./enum.ReloadPolicy.html) for more details.
+/// - `bm25_params` (by default `k1=1.2`, `b=0.75`):
+///
+///   See [`BM25Params`](./query/struct.BM25Params.html) for more details.
+/// - `query_cache` (by default, none):
+///
+///   See [`QueryCache`](./struct.QueryCache.html) for more details.
+/// - `segment_opening` (by default `SegmentOpening::Eager`):
+///
+///   See [`SegmentOpening`](./enum.SegmentOpening.html) for more details.
+/// - `verify_mode` (by default `VerifyMode::Disabled`):
+///
+///   See [`VerifyMode`](./enum.VerifyMode.html) for more details.
 #[derive(Clone)]
 pub struct IndexReaderBuilder {
     num_searchers: usize,
     reload_policy: ReloadPolicy,
+    segment_opening: SegmentOpening,
+    verify_mode: VerifyMode,
+    bm25_params: BM25Params,
+    query_cache: Option<QueryCache>,
     index: Index,
 }
 
@@ -52,6 +140,10 @@ impl IndexReaderBuilder {
         IndexReaderBuilder {
             num_searchers: num_cpus::get(),
             reload_policy: ReloadPolicy::OnCommit,
+            segment_opening: SegmentOpening::Eager,
+            verify_mode: VerifyMode::Disabled,
+            bm25_params: BM25Params::default(),
+            query_cache: None,
             index,
         }
     }
@@ -65,16 +157,27 @@ impl IndexReaderBuilder {
         let inner_reader = InnerIndexReader {
             index: self.index,
             num_searchers: self.num_searchers,
+            segment_opening: self.segment_opening,
+            verify_mode: self.verify_mode,
+            checksum_verifier: Arc::new(ChecksumVerifier::default()),
+            bm25_params: self.bm25_params,
+            query_cache: self.query_cache,
+            generation: Mutex::new(SearcherGeneration::new_reader()),
             searcher_pool: Pool::new(),
         };
         inner_reader.reload()?;
         let inner_reader_arc = Arc::new(inner_reader);
         let watch_handle_opt: Option<WatchHandle>;
+        let mut scheduled_reload_opt = None;
         match self.reload_policy {
             ReloadPolicy::Manual => {
                 // No need to set anything...
                 watch_handle_opt = None;
             }
+            ReloadPolicy::Scheduled { interval } => {
+                watch_handle_opt = None;
+                scheduled_reload_opt = Some(spawn_scheduled_reload(&inner_reader_arc, interval)?);
+            }
             ReloadPolicy::OnCommit => {
                 let inner_reader_arc_clone = inner_reader_arc.clone();
                 let callback = move || {
@@ -95,6 +198,7 @@ impl IndexReaderBuilder {
         Ok(IndexReader {
             inner: inner_reader_arc,
             watch_handle_opt,
+            _scheduled_reload_opt: scheduled_reload_opt,
         })
     }
 
@@ -106,11 +210,43 @@ impl IndexReaderBuilder {
         self
     }
 
+    /// Sets when the segments are opened.
+    ///
+    /// See [`SegmentOpening`](./enum.SegmentOpening.html) for more details.
+    pub fn segment_opening(mut self, segment_opening: SegmentOpening) -> IndexReaderBuilder {
+        self.segment_opening = segment_opening;
+        self
+    }
+
+    /// Sets when the checksums of the files of the segments are verified.
+    ///
+    /// See [`VerifyMode`](./enum.VerifyMode.html) for more details.
+    pub fn verify_mode(mut self, verify_mode: VerifyMode) -> IndexReaderBuilder {
+        self.verify_mode = verify_mode;
+        self
+    }
+
     /// Sets the number of `Searcher` in the searcher pool.
     pub fn num_searchers(mut self, num_searchers: usize) -> IndexReaderBuilder {
         self.num_searchers = num_searchers;
         self
     }
+
+    /// Sets the parameters of the BM25 similarity used by the searchers.
+    ///
+    /// See [`BM25Params`](./query/struct.BM25Params.html) for more details.
+    pub fn bm25_params(mut self, bm25_params: BM25Params) -> IndexReaderBuilder {
+        self.bm25_params = bm25_params;
+        self
+    }
+
+    /// Sets the cache of the `Weight`s of the queries run by the searchers.
+    ///
+    /// See [`QueryCache`](./struct.QueryCache.html) for more details.
+    pub fn query_cache(mut self, query_cache: QueryCache) -> IndexReaderBuilder {
+        self.query_cache = Some(query_cache);
+        self
+    }
 }
 
 impl TryInto<IndexReader> for IndexReaderBuilder {
@@ -123,40 +259,111 @@ impl TryInto<IndexReader> for IndexReaderBuilder {
 
 struct InnerIndexReader {
     num_searchers: usize,
+    segment_opening: SegmentOpening,
+    verify_mode: VerifyMode,
+    checksum_verifier: Arc<ChecksumVerifier>,
+    bm25_params: BM25Params,
+    query_cache: Option<QueryCache>,
+    generation: Mutex<SearcherGeneration>,
     searcher_pool: Pool<Searcher>,
     index: Index,
 }
 
 impl InnerIndexReader {
     fn reload(&self) -> crate::Result<()> {
-        let segment_readers: Vec<SegmentReader> = {
+        let (searcher_segments, commit_payload) = {
             let _meta_lock = self.index.directory().acquire_lock(&META_LOCK)?;
-            let searchable_segments = self.searchable_segments()?;
-            searchable_segments
-                .iter()
-                .map(SegmentReader::open)
-                .collect::<crate::Result<_>>()?
+            let index_meta = self.index.load_metas()?;
+            let searchable_segments: Vec<Segment> = index_meta
+                .segments
+                .into_iter()
+                .map(|segment_meta| self.index.segment(segment_meta))
+                .collect();
+            let searcher_segments = Arc::new(SearcherSegments::open(
+                &searchable_segments,
+                self.segment_opening,
+                self.verify_mode,
+                &self.checksum_verifier,
+            )?);
+            (searcher_segments, index_meta.payload.map(Arc::from))
         };
         let schema = self.index.schema();
+        let generation = {
+            let mut last_generation = self.generation.lock().unwrap();
+            *last_generation = last_generation.next();
+            *last_generation
+        };
         let searchers: Vec<Searcher> = std::iter::repeat_with(|| {
-            Searcher::new(schema.clone(), self.index.clone(), segment_readers.clone())
+            Searcher::new(
+                schema.clone(),
+                self.index.clone(),
+                searcher_segments.clone(),
+                self.bm25_params,
+            )
+            .map(|searcher| {
+                searcher
+                    .with_query_cache(self.query_cache.clone(), generation)
+                    .with_commit_payload(commit_payload.clone())
+            })
         })
         .take(self.num_searchers)
         .collect::<io::Result<_>>()?;
         self.searcher_pool.publish_new_generation(searchers);
+        if let Some(query_cache) = &self.query_cache {
+            query_cache.evict_generations_before(generation);
+        }
         Ok(())
     }
 
-    /// Returns the list of segments that are searchable
-    fn searchable_segments(&self) -> crate::Result<Vec<Segment>> {
-        self.index.searchable_segments()
-    }
-
     fn searcher(&self) -> LeasedItem<Searcher> {
         self.searcher_pool.acquire()
     }
 }
 
+/// Identifies a version of the segments of the index, deletes included.
+fn segments_fingerprint(index: &Index) -> crate::Result<Vec<(SegmentId, Option<Opstamp>)>> {
+    Ok(index
+        .searchable_segment_metas()?
+        .iter()
+        .map(|segment_meta| (segment_meta.id(), segment_meta.delete_opstamp()))
+        .collect())
+}
+
+/// Spawns the thread reloading `inner_reader` every `interval` for `ReloadPolicy::Scheduled`.
+///
+/// The thread stops once the returned `Sender` and its clones are dropped.
+fn spawn_scheduled_reload(
+    inner_reader: &Arc<InnerIndexReader>,
+    interval: Duration,
+) -> crate::Result<Sender<()>> {
+    let (stop_sender, stop_receiver) = channel::bounded::<()>(0);
+    let inner_reader_weak: Weak<InnerIndexReader> = Arc::downgrade(inner_reader);
+    let mut last_fingerprint = segments_fingerprint(&inner_reader.index)?;
+    thread::Builder::new()
+        .name("thrd-tantivy-scheduled-reload".to_string())
+        .spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = stop_receiver.recv_timeout(interval) {
+                let inner_reader = if let Some(inner_reader) = inner_reader_weak.upgrade() {
+                    inner_reader
+                } else {
+                    return;
+                };
+                let reload_res =
+                    segments_fingerprint(&inner_reader.index).and_then(|fingerprint| {
+                        if fingerprint != last_fingerprint {
+                            inner_reader.reload()?;
+                            last_fingerprint = fingerprint;
+                        }
+                        Ok(())
+                    });
+                if let Err(err) = reload_res {
+                    error!("Error while reloading searcher on schedule. {:?}", err);
+                }
+            }
+        })?;
+    Ok(stop_sender)
+}
+
 /// `IndexReader` is your entry point to read and search the index.
 ///
 /// It controls when a new version of the index should be loaded and lends
@@ -168,6 +375,8 @@ impl InnerIndexReader {
 pub struct IndexReader {
     inner: Arc<InnerIndexReader>,
     watch_handle_opt: Option<WatchHandle>,
+    // Dropping the last clone stops the thread of `ReloadPolicy::Scheduled`.
+    _scheduled_reload_opt: Option<Sender<()>>,
 }
 
 impl IndexReader {
@@ -183,6 +392,9 @@ impl IndexReader {
     /// every commit should be rapidly reflected on your `IndexReader` and you should
     /// not need to call `reload()` at all.
     ///
+    /// With `ReloadPolicy::Manual`, this is the only way to make new commits visible:
+    /// a read replica can for instance call it once a replication checkpoint is reached.
+    ///
     /// This automatic reload can take 10s of milliseconds to kick in however, and in unit tests
     /// it can be nice to deterministically force the reload of searchers.
     pub fn reload(&self) -> crate::Result<()> {
@@ -202,4 +414,53 @@ impl IndexReader {
     pub fn searcher(&self) -> LeasedItem<Searcher> {
         self.inner.searcher()
     }
+
+    /// Opens all of the segments of the last loaded version of the index,
+    /// in parallel.
+    ///
+    /// With `SegmentOpening::Lazy`, this makes it possible to pay for the opening
+    /// of the segments before the first search, and to get the possible errors.
+    /// It does nothing if the segments are already opened.
+    pub fn warm_all_segments(&self) -> crate::Result<()> {
+        self.searcher().warm()
+    }
+
+    /// Reads the parts of the files of the last loaded version of the index that are
+    /// used to search `fields`, so as to load them in the page cache of the OS.
+    ///
+    /// These are the term dictionaries, postings, positions, fast fields and
+    /// field norms of `fields`. Warming them up after the process starts avoids
+    /// paying for the page faults during the first searches.
+    ///
+    /// This method blocks until all of the files are read: it is meant to be
+    /// called from a background thread. Once `cancellation_token` is cancelled,
+    /// it stops and returns `TantivyError::Cancelled`.
+    pub fn warm_up(
+        &self,
+        fields: &[Field],
+        cancellation_token: &CancellationToken,
+    ) -> crate::Result<()> {
+        let file_slices = self.searcher().warm_up_file_slices(Some(fields))?;
+        warm_up::read_file_slices(&file_slices, cancellation_token)
+    }
+
+    /// Reads all of the files of the last loaded version of the index,
+    /// so as to load them in the page cache of the OS.
+    ///
+    /// See [`IndexReader::warm_up`](#method.warm_up).
+    pub fn warm_all(&self, cancellation_token: &CancellationToken) -> crate::Result<()> {
+        let file_slices = self.searcher().warm_up_file_slices(None)?;
+        warm_up::read_file_slices(&file_slices, cancellation_token)
+    }
+
+    /// Returns the statistics of the segments of the last loaded version of the index.
+    ///
+    /// With `SegmentOpening::Lazy`, this opens the segments.
+    pub fn segment_infos(&self) -> crate::Result<Vec<SegmentInfo>> {
+        self.searcher()
+            .open_segment_readers()?
+            .iter()
+            .map(SegmentInfo::for_segment_reader)
+            .collect()
+    }
 }
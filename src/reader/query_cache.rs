@@ -0,0 +1,367 @@
+use crate::core::SegmentReader;
+use crate::query::{Explanation, Query, Scorer, Weight};
+use crate::{DocId, Score};
+use lru::LruCache;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+static NEXT_READER_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Identifies the `Searcher`s published by a reload of an `IndexReader`.
+///
+/// The id of the reader is unique over the process, so that
+/// the `IndexReader`s sharing a `QueryCache` do not share
+/// their `Weight`s.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub(crate) struct SearcherGeneration {
+    reader_id: usize,
+    generation: usize,
+}
+
+impl SearcherGeneration {
+    /// Returns the generation before the first reload of a new reader.
+    pub(crate) fn new_reader() -> SearcherGeneration {
+        SearcherGeneration {
+            reader_id: NEXT_READER_ID.fetch_add(1, Ordering::Relaxed),
+            generation: 0,
+        }
+    }
+
+    /// Returns the generation following `self`, for the same reader.
+    pub(crate) fn next(self) -> SearcherGeneration {
+        SearcherGeneration {
+            reader_id: self.reader_id,
+            generation: self.generation + 1,
+        }
+    }
+}
+
+#[derive(Clone, Hash, PartialEq, Eq)]
+struct QueryCacheKey {
+    generation: SearcherGeneration,
+    query: String,
+    scoring_enabled: bool,
+}
+
+/// Cache of the `Weight`s of the queries run by the `Searcher`s of an `IndexReader`.
+///
+/// Building the `Weight` of a query requires to look its terms up in the term dictionaries
+/// and to compute their statistics. When the same queries are run over and over again,
+/// their `Weight`s can be reused as long as the searched segments do not change.
+///
+/// The `Weight`s are cached per `IndexReader` and per generation of `Searcher`s:
+/// reloading the `IndexReader` evicts the `Weight`s of its previous generations.
+/// Beyond `capacity` entries, the least recently used `Weight` is evicted.
+///
+/// Queries are identified by their [`Query::cache_key`](./query/trait.Query.html#method.cache_key).
+/// The `Weight`s of the queries without a key are not cached.
+///
+/// Cloning a `QueryCache` is cheap: the clone shares the cached `Weight`s and the counters.
+///
+/// ```rust
+/// use tantivy::collector::Count;
+/// use tantivy::query::TermQuery;
+/// use tantivy::schema::{IndexRecordOption, Schema, TEXT};
+/// use tantivy::{doc, Index, QueryCache, Term};
+///
+/// # fn main() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let title = schema_builder.add_text_field("title", TEXT);
+/// let index = Index::create_in_ram(schema_builder.build());
+/// let mut index_writer = index.writer(3_000_000)?;
+/// index_writer.add_document(doc!(title => "The Old Man and the Sea"));
+/// index_writer.commit()?;
+///
+/// let query_cache = QueryCache::new(1_000);
+/// let reader = index.reader_builder().query_cache(query_cache.clone()).try_into()?;
+/// let query = TermQuery::new(
+///     Term::from_field_text(title, "sea"),
+///     IndexRecordOption::Basic,
+/// );
+/// for _ in 0..3 {
+///     assert_eq!(reader.searcher().search(&query, &Count)?, 1);
+/// }
+/// assert_eq!(query_cache.num_misses(), 1);
+/// assert_eq!(query_cache.num_hits(), 2);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct QueryCache {
+    weights: Arc<Mutex<LruCache<QueryCacheKey, Arc<dyn Weight>>>>,
+    num_hits: Arc<AtomicU64>,
+    num_misses: Arc<AtomicU64>,
+}
+
+impl QueryCache {
+    /// Creates a cache holding up to `capacity` `Weight`s.
+    ///
+    /// A `capacity` of `0` disables the cache.
+    pub fn new(capacity: usize) -> QueryCache {
+        QueryCache {
+            weights: Arc::new(Mutex::new(LruCache::new(capacity))),
+            num_hits: Arc::default(),
+            num_misses: Arc::default(),
+        }
+    }
+
+    /// Returns the maximum number of `Weight`s held by the cache.
+    pub fn capacity(&self) -> usize {
+        self.weights.lock().unwrap().cap()
+    }
+
+    /// Returns the number of `Weight`s currently held by the cache.
+    pub fn len(&self) -> usize {
+        self.weights.lock().unwrap().len()
+    }
+
+    /// Returns true iff the cache does not hold any `Weight`.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of searches that reused a cached `Weight`.
+    pub fn num_hits(&self) -> u64 {
+        self.num_hits.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of searches that had to build their `Weight`.
+    pub fn num_misses(&self) -> u64 {
+        self.num_misses.load(Ordering::Relaxed)
+    }
+
+    /// Returns the cached `Weight` of `query`, or builds it with `build_weight`
+    /// and caches it.
+    ///
+    /// The lock is not held while the `Weight` is built, so that
+    /// concurrent searches do not wait for each other.
+    pub(crate) fn get_or_build(
+        &self,
+        generation: SearcherGeneration,
+        query: &dyn Query,
+        scoring_enabled: bool,
+        build_weight: impl FnOnce() -> crate::Result<Box<dyn Weight>>,
+    ) -> crate::Result<Box<dyn Weight>> {
+        let query_key = match query.cache_key() {
+            Some(query_key) => query_key,
+            None => return build_weight(),
+        };
+        let key = QueryCacheKey {
+            generation,
+            query: query_key,
+            scoring_enabled,
+        };
+        if let Some(weight) = self.weights.lock().unwrap().get(&key) {
+            self.num_hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(Box::new(CachedWeight(weight.clone())));
+        }
+        self.num_misses.fetch_add(1, Ordering::Relaxed);
+        let weight: Arc<dyn Weight> = Arc::from(build_weight()?);
+        self.weights.lock().unwrap().put(key, weight.clone());
+        Ok(Box::new(CachedWeight(weight)))
+    }
+
+    /// Evicts the `Weight`s of the generations of the same reader older than `generation`.
+    pub(crate) fn evict_generations_before(&self, generation: SearcherGeneration) {
+        let mut weights = self.weights.lock().unwrap();
+        let obsolete_keys: Vec<QueryCacheKey> = weights
+            .iter()
+            .filter(|(key, _)| {
+                key.generation.reader_id == generation.reader_id
+                    && key.generation.generation < generation.generation
+            })
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in &obsolete_keys {
+            weights.pop(key);
+        }
+    }
+}
+
+/// `Weight` shared with the `QueryCache`.
+struct CachedWeight(Arc<dyn Weight>);
+
+impl Weight for CachedWeight {
+    fn scorer(&self, reader: &SegmentReader, boost: Score) -> crate::Result<Box<dyn Scorer>> {
+        self.0.scorer(reader, boost)
+    }
+
+    fn explain(&self, reader: &SegmentReader, doc: DocId) -> crate::Result<Explanation> {
+        self.0.explain(reader, doc)
+    }
+
+    fn count(&self, reader: &SegmentReader) -> crate::Result<u32> {
+        self.0.count(reader)
+    }
+
+    fn for_each(
+        &self,
+        reader: &SegmentReader,
+        callback: &mut dyn FnMut(DocId, Score),
+    ) -> crate::Result<()> {
+        self.0.for_each(reader, callback)
+    }
+
+    fn for_each_pruning(
+        &self,
+        threshold: Score,
+        reader: &SegmentReader,
+        callback: &mut dyn FnMut(DocId, Score) -> Score,
+    ) -> crate::Result<()> {
+        self.0.for_each_pruning(threshold, reader, callback)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QueryCache;
+    use crate::collector::{Count, TopDocs};
+    use crate::query::TermQuery;
+    use crate::schema::{IndexRecordOption, Schema, TEXT};
+    use crate::{Index, ReloadPolicy, Term};
+
+    #[test]
+    fn test_query_cache() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text => "a b"));
+        index_writer.add_document(doc!(text => "a"));
+        index_writer.commit()?;
+        let query_cache = QueryCache::new(2);
+        let reader = index
+            .reader_builder()
+            .reload_policy(ReloadPolicy::Manual)
+            .query_cache(query_cache.clone())
+            .try_into()?;
+        let term_query = |word: &str| {
+            TermQuery::new(
+                Term::from_field_text(text, word),
+                IndexRecordOption::WithFreqs,
+            )
+        };
+        let searcher = reader.searcher();
+        assert!(searcher.query_cache().is_some());
+        assert_eq!(searcher.search(&term_query("a"), &Count)?, 2);
+        assert_eq!(searcher.search(&term_query("a"), &Count)?, 2);
+        assert_eq!((query_cache.num_hits(), query_cache.num_misses()), (1, 1));
+        // Counting does not require scoring, so the scoring weight is a different entry.
+        assert_eq!(
+            searcher
+                .search(&term_query("a"), &TopDocs::with_limit(10))?
+                .len(),
+            2
+        );
+        assert_eq!((query_cache.num_hits(), query_cache.num_misses()), (1, 2));
+        assert_eq!(searcher.search(&term_query("b"), &Count)?, 1);
+        assert_eq!(query_cache.len(), query_cache.capacity());
+        drop(searcher);
+
+        index_writer.add_document(doc!(text => "a"));
+        index_writer.commit()?;
+        reader.reload()?;
+        assert!(query_cache.is_empty());
+        assert_eq!(reader.searcher().search(&term_query("a"), &Count)?, 3);
+        assert_eq!((query_cache.num_hits(), query_cache.num_misses()), (1, 4));
+        assert_eq!(query_cache.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_cache_distinguishes_index_record_options() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text => "a a a b"));
+        index_writer.add_document(doc!(text => "a b"));
+        index_writer.commit()?;
+        let query_cache = QueryCache::new(10);
+        let reader = index
+            .reader_builder()
+            .query_cache(query_cache.clone())
+            .try_into()?;
+        let uncached_searcher = index.reader()?.searcher();
+        let term = Term::from_field_text(text, "a");
+        for &index_record_option in &[IndexRecordOption::Basic, IndexRecordOption::WithFreqs] {
+            let query = TermQuery::new(term.clone(), index_record_option);
+            assert_eq!(
+                reader.searcher().search(&query, &TopDocs::with_limit(2))?,
+                uncached_searcher.search(&query, &TopDocs::with_limit(2))?
+            );
+        }
+        assert_eq!((query_cache.num_hits(), query_cache.num_misses()), (0, 2));
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_cache_shared_by_readers() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let schema = schema_builder.build();
+        let index_a = Index::create_in_ram(schema.clone());
+        let mut index_writer_a = index_a.writer_for_tests()?;
+        index_writer_a.add_document(doc!(text => "a"));
+        index_writer_a.add_document(doc!(text => "b"));
+        index_writer_a.commit()?;
+        let index_b = Index::create_in_ram(schema);
+        let mut index_writer_b = index_b.writer_for_tests()?;
+        index_writer_b.add_document(doc!(text => "a b"));
+        index_writer_b.commit()?;
+        let query_cache = QueryCache::new(10);
+        let reader = |index: &Index| {
+            index
+                .reader_builder()
+                .reload_policy(ReloadPolicy::Manual)
+                .query_cache(query_cache.clone())
+                .try_into()
+        };
+        let reader_a = reader(&index_a)?;
+        let reader_b = reader(&index_b)?;
+        let query = TermQuery::new(
+            Term::from_field_text(text, "a"),
+            IndexRecordOption::WithFreqs,
+        );
+        for (index, reader) in &[(&index_a, &reader_a), (&index_b, &reader_b)] {
+            let uncached_searcher = index.reader()?.searcher();
+            assert_eq!(
+                reader.searcher().search(&query, &TopDocs::with_limit(1))?,
+                uncached_searcher.search(&query, &TopDocs::with_limit(1))?
+            );
+        }
+        assert_eq!((query_cache.num_hits(), query_cache.num_misses()), (0, 2));
+
+        // Reloading a reader does not evict the weights of the other reader.
+        reader_a.reload()?;
+        assert_eq!(query_cache.len(), 1);
+        reader_b.searcher().search(&query, &Count)?;
+        reader_b
+            .searcher()
+            .search(&query, &TopDocs::with_limit(1))?;
+        assert_eq!((query_cache.num_hits(), query_cache.num_misses()), (1, 3));
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_cache_disabled() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text => "a"));
+        index_writer.commit()?;
+        let query_cache = QueryCache::new(0);
+        let reader = index
+            .reader_builder()
+            .query_cache(query_cache.clone())
+            .try_into()?;
+        let query = TermQuery::new(Term::from_field_text(text, "a"), IndexRecordOption::Basic);
+        for _ in 0..2 {
+            assert_eq!(reader.searcher().search(&query, &Count)?, 1);
+        }
+        assert_eq!((query_cache.num_hits(), query_cache.num_misses()), (0, 2));
+        assert!(query_cache.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,135 @@
+use crate::core::{SegmentId, SegmentReader};
+use crate::schema::Field;
+use std::collections::HashMap;
+
+/// Statistics of an indexed field in a segment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FieldStats {
+    num_distinct_terms: u64,
+    total_term_freq: u64,
+}
+
+impl FieldStats {
+    /// Returns the number of distinct terms of the field in the segment.
+    pub fn num_distinct_terms(&self) -> u64 {
+        self.num_distinct_terms
+    }
+
+    /// Returns the number of tokens of the field in the segment,
+    /// that is the sum of the frequencies of all of its terms.
+    ///
+    /// The tokens of deleted documents are included.
+    pub fn total_term_freq(&self) -> u64 {
+        self.total_term_freq
+    }
+}
+
+/// Statistics of a segment, as returned by
+/// [`IndexReader::segment_infos`](./struct.IndexReader.html#method.segment_infos).
+///
+/// They make it possible to monitor the health of an index, e.g. to spot
+/// the segments with a large proportion of deleted documents.
+#[derive(Clone, Debug)]
+pub struct SegmentInfo {
+    segment_id: SegmentId,
+    num_docs: u32,
+    num_deleted_docs: u32,
+    disk_size_bytes: u64,
+    field_stats: HashMap<Field, FieldStats>,
+}
+
+impl SegmentInfo {
+    pub(crate) fn for_segment_reader(segment_reader: &SegmentReader) -> crate::Result<SegmentInfo> {
+        let mut field_stats = HashMap::new();
+        for (field, field_entry) in segment_reader.schema().fields() {
+            if !field_entry.is_indexed() {
+                continue;
+            }
+            let inverted_index = segment_reader.inverted_index(field)?;
+            field_stats.insert(
+                field,
+                FieldStats {
+                    num_distinct_terms: inverted_index.terms().num_terms() as u64,
+                    total_term_freq: inverted_index.total_num_tokens(),
+                },
+            );
+        }
+        Ok(SegmentInfo {
+            segment_id: segment_reader.segment_id(),
+            num_docs: segment_reader.num_docs(),
+            num_deleted_docs: segment_reader.num_deleted_docs(),
+            disk_size_bytes: segment_reader.space_usage()?.total() as u64,
+            field_stats,
+        })
+    }
+
+    /// Returns the id of the segment.
+    pub fn segment_id(&self) -> SegmentId {
+        self.segment_id
+    }
+
+    /// Returns the number of documents of the segment, excluding the deleted documents.
+    pub fn num_docs(&self) -> u32 {
+        self.num_docs
+    }
+
+    /// Returns the number of deleted documents of the segment.
+    pub fn num_deleted_docs(&self) -> u32 {
+        self.num_deleted_docs
+    }
+
+    /// Returns true iff some of the documents of the segment are deleted.
+    pub fn has_deletes(&self) -> bool {
+        self.num_deleted_docs > 0
+    }
+
+    /// Returns the number of bytes of the files of the segment.
+    pub fn disk_size_bytes(&self) -> u64 {
+        self.disk_size_bytes
+    }
+
+    /// Returns the statistics of the indexed fields of the segment.
+    pub fn field_stats(&self) -> &HashMap<Field, FieldStats> {
+        &self.field_stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::schema::{Schema, STORED, STRING, TEXT};
+    use crate::{Index, Term};
+
+    #[test]
+    fn test_segment_infos() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let id = schema_builder.add_text_field("id", STRING);
+        let text = schema_builder.add_text_field("text", TEXT);
+        let stored = schema_builder.add_text_field("stored", STORED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(id => "a", text => "hello happy world", stored => "x"));
+        index_writer.add_document(doc!(id => "b", text => "hello world"));
+        index_writer.add_document(doc!(id => "c", text => "hello"));
+        index_writer.commit()?;
+        index_writer.delete_term(Term::from_field_text(id, "c"));
+        index_writer.commit()?;
+
+        let segment_infos = index.reader()?.segment_infos()?;
+        assert_eq!(segment_infos.len(), 1);
+        let segment_info = &segment_infos[0];
+        assert_eq!(
+            segment_info.segment_id(),
+            index.searchable_segment_ids()?[0]
+        );
+        assert_eq!(segment_info.num_docs(), 2);
+        assert_eq!(segment_info.num_deleted_docs(), 1);
+        assert!(segment_info.has_deletes());
+        assert!(segment_info.disk_size_bytes() > 0);
+        let text_stats = segment_info.field_stats()[&text];
+        assert_eq!(text_stats.num_distinct_terms(), 3);
+        assert_eq!(text_stats.total_term_freq(), 6);
+        assert_eq!(segment_info.field_stats()[&id].num_distinct_terms(), 3);
+        assert!(!segment_info.field_stats().contains_key(&stored));
+        Ok(())
+    }
+}
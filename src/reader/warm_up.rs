@@ -0,0 +1,78 @@
+use crate::common::HasLen;
+use crate::directory::FileSlice;
+use crate::indexer::CancellationToken;
+use std::ptr;
+
+/// The files are read by chunks of this length, so that the cancellation
+/// token is checked regularly.
+const CHUNK_LEN: usize = 1 << 20;
+
+const PAGE_LEN: usize = 4_096;
+
+/// Reads all of `file_slices`, so that the OS loads them in its page cache.
+///
+/// With memory mapped files, reading the bytes does not copy them, and touching
+/// one byte per page is enough for the page to be loaded.
+pub(crate) fn read_file_slices(
+    file_slices: &[FileSlice],
+    cancellation_token: &CancellationToken,
+) -> crate::Result<()> {
+    for file_slice in file_slices {
+        let len = file_slice.len();
+        let mut start = 0;
+        while start < len {
+            cancellation_token.check()?;
+            let stop = (start + CHUNK_LEN).min(len);
+            let bytes = file_slice.read_bytes_slice(start, stop)?;
+            for byte in bytes.as_slice().iter().step_by(PAGE_LEN) {
+                // The volatile read prevents the compiler from optimizing the read away.
+                unsafe {
+                    ptr::read_volatile(byte);
+                }
+            }
+            start = stop;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::HasLen;
+    use crate::indexer::CancellationToken;
+    use crate::schema::{Schema, FAST, STORED, TEXT};
+    use crate::{Index, TantivyError};
+
+    #[test]
+    fn test_warm_up() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT | STORED);
+        let num = schema_builder.add_u64_field("num", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        for i in 0..100u64 {
+            index_writer.add_document(doc!(text => "hello happy tax payer", num => i));
+        }
+        index_writer.commit()?;
+        let reader = index.reader()?;
+        {
+            let searcher = reader.searcher();
+            assert!(!searcher.warm_up_file_slices(Some(&[text]))?.is_empty());
+            assert!(!searcher.warm_up_file_slices(Some(&[num]))?.is_empty());
+            let total_len = |fields| -> crate::Result<usize> {
+                let file_slices = searcher.warm_up_file_slices(fields)?;
+                Ok(file_slices.iter().map(|file_slice| file_slice.len()).sum())
+            };
+            assert!(total_len(Some(&[text]))? < total_len(None)?);
+        }
+        let cancellation_token = CancellationToken::new();
+        reader.warm_up(&[text, num], &cancellation_token)?;
+        reader.warm_all(&cancellation_token)?;
+        cancellation_token.cancel();
+        assert!(matches!(
+            reader.warm_all(&cancellation_token),
+            Err(TantivyError::Cancelled)
+        ));
+        Ok(())
+    }
+}
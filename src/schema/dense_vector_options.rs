@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+
+/// Similarity function used to compare dense vectors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VectorSimilarity {
+    /// Cosine of the angle between the two vectors.
+    ///
+    /// The similarity is within `[-1, 1]`. It is `0` if one of
+    /// the vectors is null.
+    #[serde(rename = "cosine")]
+    Cosine,
+    /// Dot product of the two vectors.
+    #[serde(rename = "dot_product")]
+    DotProduct,
+}
+
+fn dot_product(left: &[f32], right: &[f32]) -> f32 {
+    left.iter().zip(right).map(|(l, r)| l * r).sum()
+}
+
+impl VectorSimilarity {
+    /// Computes the similarity between two vectors.
+    ///
+    /// The higher the value, the more similar the vectors are.
+    pub fn compute(self, left: &[f32], right: &[f32]) -> f32 {
+        match self {
+            VectorSimilarity::Cosine => {
+                let norms = dot_product(left, left).sqrt() * dot_product(right, right).sqrt();
+                if norms == 0.0 {
+                    0.0
+                } else {
+                    dot_product(left, right) / norms
+                }
+            }
+            VectorSimilarity::DotProduct => dot_product(left, right),
+        }
+    }
+}
+
+/// Define how a dense vector field should be handled by tantivy.
+///
+/// Dense vector fields are not indexed in the inverted index.
+/// Their vectors are always stored in the fast fields, which are
+/// scanned by the `KnnQuery`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DenseVectorOptions {
+    dims: usize,
+    similarity: VectorSimilarity,
+    stored: bool,
+}
+
+impl DenseVectorOptions {
+    /// Creates the options of a dense vector field, given the number of
+    /// dimensions of its vectors and the similarity used to compare them.
+    pub fn new(dims: usize, similarity: VectorSimilarity) -> DenseVectorOptions {
+        DenseVectorOptions {
+            dims,
+            similarity,
+            stored: false,
+        }
+    }
+
+    /// Returns the number of dimensions of the vectors.
+    pub fn dims(&self) -> usize {
+        self.dims
+    }
+
+    /// Returns the similarity used to compare the vectors.
+    pub fn similarity(&self) -> VectorSimilarity {
+        self.similarity
+    }
+
+    /// Returns true iff the value is stored.
+    pub fn is_stored(&self) -> bool {
+        self.stored
+    }
+
+    /// Set the field as stored.
+    ///
+    /// Only the fields that are set as *stored* are
+    /// persisted into the Tantivy's store.
+    pub fn set_stored(mut self) -> DenseVectorOptions {
+        self.stored = true;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VectorSimilarity;
+
+    #[test]
+    fn test_vector_similarity() {
+        let similarity = VectorSimilarity::Cosine;
+        assert!((similarity.compute(&[1.0, 0.0], &[2.0, 0.0]) - 1.0).abs() < 1e-6);
+        assert!(similarity.compute(&[1.0, 0.0], &[0.0, 3.0]).abs() < 1e-6);
+        assert!((similarity.compute(&[1.0, 1.0], &[-1.0, -1.0]) + 1.0).abs() < 1e-6);
+        assert_eq!(similarity.compute(&[0.0, 0.0], &[1.0, 0.0]), 0.0);
+        let similarity = VectorSimilarity::DotProduct;
+        assert_eq!(similarity.compute(&[1.0, 2.0], &[3.0, -4.0]), -5.0);
+    }
+}
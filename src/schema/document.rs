@@ -3,8 +3,10 @@ use crate::common::BinarySerializable;
 use crate::common::VInt;
 use crate::tokenizer::PreTokenizedString;
 use crate::DateTime;
+use std::hash::{Hash, Hasher};
 use std::io::{self, Read, Write};
 use std::mem;
+use std::net::IpAddr;
 
 /// Tantivy's Document is the object that can
 /// be indexed and then searched for.
@@ -27,19 +29,31 @@ impl From<Vec<FieldValue>> for Document {
     }
 }
 
+impl Document {
+    /// Returns the `(field, value)` pairs, sorted: two documents are equal
+    /// iff they have the same pairs, regardless of their order.
+    fn canonical_field_values(&self) -> Vec<&FieldValue> {
+        let mut field_values: Vec<&FieldValue> = self.field_values.iter().collect();
+        field_values.sort();
+        field_values
+    }
+}
+
 impl PartialEq for Document {
     fn eq(&self, other: &Document) -> bool {
-        // super slow, but only here for tests
-        let mut self_field_values: Vec<&_> = self.field_values.iter().collect();
-        let mut other_field_values: Vec<&_> = other.field_values.iter().collect();
-        self_field_values.sort();
-        other_field_values.sort();
-        self_field_values.eq(&other_field_values)
+        self.field_values.len() == other.field_values.len()
+            && self.canonical_field_values() == other.canonical_field_values()
     }
 }
 
 impl Eq for Document {}
 
+impl Hash for Document {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.canonical_field_values().hash(state);
+    }
+}
+
 impl Document {
     /// Creates a new, empty document object
     pub fn new() -> Document {
@@ -113,6 +127,30 @@ impl Document {
         self.add(FieldValue::new(field, Value::Bytes(value.into())))
     }
 
+    /// Add an ip address field
+    pub fn add_ip_addr(&mut self, field: Field, value: IpAddr) {
+        self.add(FieldValue::new(field, Value::from(value)));
+    }
+
+    /// Add a geo point field
+    pub fn add_geo_point(&mut self, field: Field, value: GeoPoint) {
+        self.add(FieldValue::new(field, Value::GeoPoint(value)));
+    }
+
+    /// Add a dense vector field
+    pub fn add_dense_vector(&mut self, field: Field, value: Vec<f32>) {
+        self.add(FieldValue::new(field, Value::DenseVector(value)));
+    }
+
+    /// Add a json object field
+    pub fn add_json_object(
+        &mut self,
+        field: Field,
+        json_object: serde_json::Map<String, serde_json::Value>,
+    ) {
+        self.add(FieldValue::new(field, Value::JsonObject(json_object)));
+    }
+
     /// Add a field value
     pub fn add(&mut self, field_value: FieldValue) {
         self.field_values.push(field_value);
@@ -187,6 +225,24 @@ impl Document {
             }
         }
     }
+    /// Deserializes the values of the given `fields` of a document,
+    /// skipping the values of the other fields without decoding them.
+    pub(crate) fn deserialize_projected(
+        bytes: &mut &[u8],
+        fields: &[Field],
+    ) -> io::Result<Document> {
+        let num_field_values = VInt::deserialize(bytes)?.val() as usize;
+        let mut field_values = Vec::new();
+        for _ in 0..num_field_values {
+            let field = Field::deserialize(bytes)?;
+            if fields.contains(&field) {
+                field_values.push(FieldValue::new(field, Value::deserialize(bytes)?));
+            } else {
+                skip_value(bytes)?;
+            }
+        }
+        Ok(Document::from(field_values))
+    }
 }
 
 impl BinarySerializable for Document {
@@ -256,4 +312,72 @@ mod tests {
             _ => panic!("Incorrect variant of Value"),
         }
     }
+
+    #[test]
+    fn test_deserialize_projected() {
+        use crate::common::BinarySerializable;
+        use crate::DateTime;
+        use std::net::{IpAddr, Ipv4Addr};
+        use std::str::FromStr;
+
+        let field = Field::from_field_id;
+        let pre_tokenized_text = PreTokenizedString {
+            text: String::from("tokenized"),
+            tokens: Vec::new(),
+        };
+        let mut doc = Document::default();
+        doc.add_text(field(0), "title");
+        doc.add_u64(field(1), 1);
+        doc.add_i64(field(2), -2);
+        doc.add_f64(field(3), 3.5);
+        doc.add_date(
+            field(4),
+            &DateTime::from_str("2020-09-13T12:26:40+00:00").unwrap(),
+        );
+        doc.add_facet(field(5), "/a/b");
+        doc.add_bytes(field(6), vec![1u8, 2u8, 3u8]);
+        doc.add_geo_point(field(7), GeoPoint::new(48.8, 2.3));
+        doc.add_ip_addr(field(8), IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)));
+        doc.add_dense_vector(field(9), vec![1.0f32, 2.0f32]);
+        doc.add_pre_tokenized_text(field(10), &pre_tokenized_text);
+        doc.add_text(field(0), "second title");
+        let mut buffer = Vec::new();
+        doc.serialize(&mut buffer).unwrap();
+
+        for fields in &[
+            vec![],
+            vec![field(0)],
+            vec![field(1), field(10)],
+            vec![field(9)],
+        ] {
+            let projected_doc = Document::deserialize_projected(&mut &buffer[..], fields).unwrap();
+            let mut expected_doc = doc.clone();
+            expected_doc.filter_fields(|field| fields.contains(&field));
+            assert_eq!(projected_doc, expected_doc);
+        }
+        let all_fields: Vec<Field> = (0..11).map(field).collect();
+        let mut bytes = &buffer[..];
+        assert_eq!(
+            Document::deserialize_projected(&mut bytes, &all_fields).unwrap(),
+            doc
+        );
+        assert!(bytes.is_empty());
+        assert!(Document::deserialize_projected(&mut &buffer[..buffer.len() - 1], &[]).is_err());
+    }
+
+    #[test]
+    fn test_doc_eq_and_hash() {
+        use std::collections::HashSet;
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let score = schema_builder.add_f64_field("score", STORED);
+        let doc = doc!(title => "a", score => 1.5f64, title => "b");
+        let same_doc = doc!(title => "b", title => "a", score => 1.5f64);
+        let other_doc = doc!(title => "a", score => 1.5f64);
+        assert_eq!(doc, same_doc);
+        assert_ne!(doc, other_doc);
+        assert_ne!(doc!(title => "a", title => "a"), doc!(title => "a"));
+        let docs: HashSet<Document> = vec![doc, same_doc, other_doc].into_iter().collect();
+        assert_eq!(docs.len(), 2);
+    }
 }
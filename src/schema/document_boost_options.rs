@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use std::ops::BitOr;
+
+use super::flags::{SchemaFlagList, StoredFlag};
+
+/// Define how the document boost field should be handled by tantivy.
+///
+/// The document boost field is always a single-valued `f64` fast field.
+/// The documents without any value get a boost of `1.0`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DocumentBoostOptions {
+    stored: bool,
+}
+
+impl DocumentBoostOptions {
+    /// Returns true iff the value is stored.
+    pub fn is_stored(&self) -> bool {
+        self.stored
+    }
+
+    /// Set the field as stored.
+    ///
+    /// Only the fields that are set as *stored* are
+    /// persisted into the Tantivy's store.
+    pub fn set_stored(mut self) -> DocumentBoostOptions {
+        self.stored = true;
+        self
+    }
+}
+
+impl<T: Into<DocumentBoostOptions>> BitOr<T> for DocumentBoostOptions {
+    type Output = DocumentBoostOptions;
+
+    fn bitor(self, other: T) -> DocumentBoostOptions {
+        let other = other.into();
+        DocumentBoostOptions {
+            stored: self.stored | other.stored,
+        }
+    }
+}
+
+impl From<()> for DocumentBoostOptions {
+    fn from(_: ()) -> Self {
+        Self::default()
+    }
+}
+
+impl From<StoredFlag> for DocumentBoostOptions {
+    fn from(_: StoredFlag) -> Self {
+        DocumentBoostOptions { stored: true }
+    }
+}
+
+impl<Head, Tail> From<SchemaFlagList<Head, Tail>> for DocumentBoostOptions
+where
+    Head: Clone,
+    Tail: Clone,
+    Self: BitOr<Output = Self> + From<Head> + From<Tail>,
+{
+    fn from(head_tail: SchemaFlagList<Head, Tail>) -> Self {
+        Self::from(head_tail.head) | Self::from(head_tail.tail)
+    }
+}
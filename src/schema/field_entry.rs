@@ -2,10 +2,16 @@ use crate::schema::TextOptions;
 use crate::schema::{is_valid_field_name, IntOptions};
 
 use crate::schema::bytes_options::BytesOptions;
+use crate::schema::DenseVectorOptions;
+use crate::schema::DocumentBoostOptions;
 use crate::schema::FieldType;
+use crate::schema::GeoPointOptions;
+use crate::schema::IpAddrOptions;
+use crate::schema::JsonObjectOptions;
 use serde::de::{self, MapAccess, Visitor};
 use serde::ser::SerializeStruct;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value as JsonValue;
 use std::fmt;
 
 /// A `FieldEntry` represents a field and its configuration.
@@ -72,6 +78,16 @@ impl FieldEntry {
         }
     }
 
+    /// Creates a new ip address field entry in the schema, given
+    /// a name, and some options.
+    pub fn new_ip_addr(field_name: String, ip_addr_options: IpAddrOptions) -> FieldEntry {
+        assert!(is_valid_field_name(&field_name));
+        FieldEntry {
+            name: field_name,
+            field_type: FieldType::IpAddr(ip_addr_options),
+        }
+    }
+
     /// Creates a field entry for a facet.
     pub fn new_facet(field_name: String) -> FieldEntry {
         assert!(is_valid_field_name(&field_name));
@@ -89,6 +105,51 @@ impl FieldEntry {
         }
     }
 
+    /// Creates a field entry for a geo point field
+    pub fn new_geo_point(field_name: String, geo_point_options: GeoPointOptions) -> FieldEntry {
+        assert!(is_valid_field_name(&field_name));
+        FieldEntry {
+            name: field_name,
+            field_type: FieldType::GeoPoint(geo_point_options),
+        }
+    }
+
+    /// Creates a field entry for a dense vector field
+    pub fn new_dense_vector(
+        field_name: String,
+        dense_vector_options: DenseVectorOptions,
+    ) -> FieldEntry {
+        assert!(is_valid_field_name(&field_name));
+        FieldEntry {
+            name: field_name,
+            field_type: FieldType::DenseVector(dense_vector_options),
+        }
+    }
+
+    /// Creates a field entry for a document boost field
+    pub fn new_document_boost(
+        field_name: String,
+        document_boost_options: DocumentBoostOptions,
+    ) -> FieldEntry {
+        assert!(is_valid_field_name(&field_name));
+        FieldEntry {
+            name: field_name,
+            field_type: FieldType::DocumentBoost(document_boost_options),
+        }
+    }
+
+    /// Creates a field entry for a json object field
+    pub fn new_json_object(
+        field_name: String,
+        json_object_options: JsonObjectOptions,
+    ) -> FieldEntry {
+        assert!(is_valid_field_name(&field_name));
+        FieldEntry {
+            name: field_name,
+            field_type: FieldType::JsonObject(json_object_options),
+        }
+    }
+
     /// Returns the name of the field
     pub fn name(&self) -> &str {
         &self.name
@@ -109,6 +170,11 @@ impl FieldEntry {
             | FieldType::Date(ref options) => options.is_indexed(),
             FieldType::HierarchicalFacet => true,
             FieldType::Bytes(ref options) => options.is_indexed(),
+            FieldType::GeoPoint(_) => false,
+            FieldType::IpAddr(ref options) => options.is_indexed(),
+            FieldType::DenseVector(_) => false,
+            FieldType::DocumentBoost(_) => false,
+            FieldType::JsonObject(ref options) => options.is_indexed(),
         }
     }
 
@@ -119,6 +185,10 @@ impl FieldEntry {
             | FieldType::I64(ref options)
             | FieldType::Date(ref options)
             | FieldType::F64(ref options) => options.is_fast(),
+            FieldType::GeoPoint(ref options) => options.is_fast(),
+            FieldType::IpAddr(ref options) => options.is_fast(),
+            FieldType::DenseVector(_) => true,
+            FieldType::DocumentBoost(_) => true,
             _ => false,
         }
     }
@@ -134,6 +204,11 @@ impl FieldEntry {
             // TODO make stored hierarchical facet optional
             FieldType::HierarchicalFacet => true,
             FieldType::Bytes(ref options) => options.is_stored(),
+            FieldType::GeoPoint(ref options) => options.is_stored(),
+            FieldType::IpAddr(ref options) => options.is_stored(),
+            FieldType::DenseVector(ref options) => options.is_stored(),
+            FieldType::DocumentBoost(ref options) => options.is_stored(),
+            FieldType::JsonObject(ref options) => options.is_stored(),
         }
     }
 }
@@ -174,6 +249,26 @@ impl Serialize for FieldEntry {
                 s.serialize_field("type", "bytes")?;
                 s.serialize_field("options", options)?;
             }
+            FieldType::GeoPoint(ref options) => {
+                s.serialize_field("type", "geo_point")?;
+                s.serialize_field("options", options)?;
+            }
+            FieldType::IpAddr(ref options) => {
+                s.serialize_field("type", "ip_addr")?;
+                s.serialize_field("options", options)?;
+            }
+            FieldType::DenseVector(ref options) => {
+                s.serialize_field("type", "dense_vector")?;
+                s.serialize_field("options", options)?;
+            }
+            FieldType::DocumentBoost(ref options) => {
+                s.serialize_field("type", "document_boost")?;
+                s.serialize_field("options", options)?;
+            }
+            FieldType::JsonObject(ref options) => {
+                s.serialize_field("type", "json_object")?;
+                s.serialize_field("options", options)?;
+            }
         }
 
         s.end()
@@ -209,8 +304,10 @@ impl<'de> Deserialize<'de> for FieldEntry {
                 V: MapAccess<'de>,
             {
                 let mut name = None;
-                let mut ty = None;
-                let mut field_type = None;
+                let mut ty: Option<String> = None;
+                // The options are buffered, as they can come before the type,
+                // e.g. if the keys are sorted.
+                let mut options: Option<JsonValue> = None;
                 while let Some(key) = map.next_key()? {
                     match key {
                         Field::Name => {
@@ -223,43 +320,46 @@ impl<'de> Deserialize<'de> for FieldEntry {
                             if ty.is_some() {
                                 return Err(de::Error::duplicate_field("type"));
                             }
-                            let type_string = map.next_value::<String>()?;
-                            match type_string.as_str() {
-                                "hierarchical_facet" => {
-                                    field_type = Some(FieldType::HierarchicalFacet);
-                                }
-                                "text" | "u64" | "i64" | "f64" | "date" | "bytes" => {
-                                    // These types require additional options to create a field_type
-                                }
-                                _ => panic!("unhandled type"),
-                            }
-                            ty = Some(type_string);
+                            ty = Some(map.next_value()?);
                         }
-                        Field::Options => match ty {
-                            None => {
-                                let msg = "The `type` field must be \
-                                           specified before `options`";
-                                return Err(de::Error::custom(msg));
+                        Field::Options => {
+                            if options.is_some() {
+                                return Err(de::Error::duplicate_field("options"));
                             }
-                            Some(ref ty) => match ty.as_str() {
-                                "text" => field_type = Some(FieldType::Str(map.next_value()?)),
-                                "u64" => field_type = Some(FieldType::U64(map.next_value()?)),
-                                "i64" => field_type = Some(FieldType::I64(map.next_value()?)),
-                                "f64" => field_type = Some(FieldType::F64(map.next_value()?)),
-                                "date" => field_type = Some(FieldType::Date(map.next_value()?)),
-                                "bytes" => field_type = Some(FieldType::Bytes(map.next_value()?)),
-                                _ => {
-                                    let msg = format!("Unrecognised type {}", ty);
-                                    return Err(de::Error::custom(msg));
-                                }
-                            },
-                        },
+                            options = Some(map.next_value()?);
+                        }
                     }
                 }
 
                 let name = name.ok_or_else(|| de::Error::missing_field("name"))?;
-                ty.ok_or_else(|| de::Error::missing_field("ty"))?;
-                let field_type = field_type.ok_or_else(|| de::Error::missing_field("options"))?;
+                let ty = ty.ok_or_else(|| de::Error::missing_field("type"))?;
+                if ty == "hierarchical_facet" {
+                    return Ok(FieldEntry {
+                        name,
+                        field_type: FieldType::HierarchicalFacet,
+                    });
+                }
+                let options = options.ok_or_else(|| de::Error::missing_field("options"))?;
+                let field_type = match ty.as_str() {
+                    "text" => serde_json::from_value(options).map(FieldType::Str),
+                    "u64" => serde_json::from_value(options).map(FieldType::U64),
+                    "i64" => serde_json::from_value(options).map(FieldType::I64),
+                    "f64" => serde_json::from_value(options).map(FieldType::F64),
+                    "date" => serde_json::from_value(options).map(FieldType::Date),
+                    "bytes" => serde_json::from_value(options).map(FieldType::Bytes),
+                    "geo_point" => serde_json::from_value(options).map(FieldType::GeoPoint),
+                    "ip_addr" => serde_json::from_value(options).map(FieldType::IpAddr),
+                    "dense_vector" => serde_json::from_value(options).map(FieldType::DenseVector),
+                    "document_boost" => {
+                        serde_json::from_value(options).map(FieldType::DocumentBoost)
+                    }
+                    "json_object" => serde_json::from_value(options).map(FieldType::JsonObject),
+                    _ => {
+                        let msg = format!("Unrecognised type {}", ty);
+                        return Err(de::Error::custom(msg));
+                    }
+                }
+                .map_err(de::Error::custom)?;
 
                 Ok(FieldEntry { name, field_type })
             }
@@ -272,7 +372,7 @@ impl<'de> Deserialize<'de> for FieldEntry {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::schema::TEXT;
+    use crate::schema::{VectorSimilarity, FAST, INDEXED, STORED, STRING, TEXT};
     use serde_json;
 
     #[test]
@@ -309,4 +409,107 @@ mod tests {
             _ => panic!("expected FieldType::Str"),
         }
     }
+
+    #[test]
+    fn test_json_serialization_geo_point() {
+        let field_entry = FieldEntry::new_geo_point(String::from("location"), FAST.into());
+        let expected = r#"{
+  "name": "location",
+  "type": "geo_point",
+  "options": {
+    "fast": true,
+    "stored": false
+  }
+}"#;
+        let field_entry_json = serde_json::to_string_pretty(&field_entry).unwrap();
+        assert_eq!(expected, &field_entry_json);
+        let field_entry_deser: FieldEntry = serde_json::from_str(expected).unwrap();
+        assert_eq!(field_entry_deser, field_entry);
+        assert!(field_entry_deser.is_fast());
+        assert!(!field_entry_deser.is_indexed());
+    }
+
+    #[test]
+    fn test_json_serialization_ip_addr() {
+        let field_entry = FieldEntry::new_ip_addr(String::from("ip"), (INDEXED | FAST).into());
+        let expected = r#"{
+  "name": "ip",
+  "type": "ip_addr",
+  "options": {
+    "indexed": true,
+    "fast": true,
+    "stored": false
+  }
+}"#;
+        let field_entry_json = serde_json::to_string_pretty(&field_entry).unwrap();
+        assert_eq!(expected, &field_entry_json);
+        let field_entry_deser: FieldEntry = serde_json::from_str(expected).unwrap();
+        assert_eq!(field_entry_deser, field_entry);
+        assert!(field_entry_deser.is_indexed());
+        assert!(field_entry_deser.is_fast());
+    }
+
+    #[test]
+    fn test_json_serialization_json_object() {
+        let field_entry =
+            FieldEntry::new_json_object(String::from("attributes"), (STORED | STRING).into());
+        let expected = r#"{
+  "name": "attributes",
+  "type": "json_object",
+  "options": {
+    "stored": true,
+    "indexing": {
+      "record": "basic",
+      "tokenizer": "raw"
+    }
+  }
+}"#;
+        let field_entry_json = serde_json::to_string_pretty(&field_entry).unwrap();
+        assert_eq!(expected, &field_entry_json);
+        let field_entry_deser: FieldEntry = serde_json::from_str(expected).unwrap();
+        assert_eq!(field_entry_deser, field_entry);
+        assert!(field_entry_deser.is_indexed());
+        assert!(field_entry_deser.is_stored());
+    }
+
+    #[test]
+    fn test_json_serialization_dense_vector() {
+        let field_entry = FieldEntry::new_dense_vector(
+            String::from("embedding"),
+            DenseVectorOptions::new(3, VectorSimilarity::Cosine),
+        );
+        let expected = r#"{
+  "name": "embedding",
+  "type": "dense_vector",
+  "options": {
+    "dims": 3,
+    "similarity": "cosine",
+    "stored": false
+  }
+}"#;
+        let field_entry_json = serde_json::to_string_pretty(&field_entry).unwrap();
+        assert_eq!(expected, &field_entry_json);
+        let field_entry_deser: FieldEntry = serde_json::from_str(expected).unwrap();
+        assert_eq!(field_entry_deser, field_entry);
+        assert!(field_entry_deser.is_fast());
+        assert!(!field_entry_deser.is_indexed());
+    }
+
+    #[test]
+    fn test_json_serialization_document_boost() {
+        let field_entry = FieldEntry::new_document_boost(String::from("boost"), STORED.into());
+        let expected = r#"{
+  "name": "boost",
+  "type": "document_boost",
+  "options": {
+    "stored": true
+  }
+}"#;
+        let field_entry_json = serde_json::to_string_pretty(&field_entry).unwrap();
+        assert_eq!(expected, &field_entry_json);
+        let field_entry_deser: FieldEntry = serde_json::from_str(expected).unwrap();
+        assert_eq!(field_entry_deser, field_entry);
+        assert!(field_entry_deser.is_fast());
+        assert!(field_entry_deser.is_stored());
+    }
 }
@@ -1,12 +1,20 @@
 use crate::schema::bytes_options::BytesOptions;
+use crate::schema::DenseVectorOptions;
+use crate::schema::DocumentBoostOptions;
 use crate::schema::Facet;
+use crate::schema::GeoPoint;
+use crate::schema::GeoPointOptions;
 use crate::schema::IndexRecordOption;
+use crate::schema::IpAddrOptions;
+use crate::schema::JsonObjectOptions;
 use crate::schema::TextFieldIndexing;
 use crate::schema::Value;
 use crate::schema::{IntOptions, TextOptions};
 use crate::tokenizer::PreTokenizedString;
 use chrono::{FixedOffset, Utc};
 use serde_json::Value as JsonValue;
+use std::net::IpAddr;
+use std::str::FromStr;
 
 /// Possible error that may occur while parsing a field value
 /// At this point the JSON is known to be valid.
@@ -28,6 +36,7 @@ pub enum ValueParsingError {
 /// Contrary to FieldType, this does
 /// not include the way the field must be indexed.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Type {
     /// `&str`
     Str,
@@ -43,6 +52,14 @@ pub enum Type {
     HierarchicalFacet,
     /// `Vec<u8>`
     Bytes,
+    /// `tantivy::schema::GeoPoint`. Passed as a `{"lat": .., "lon": ..}` object in JSON.
+    GeoPoint,
+    /// `std::net::IpAddr`. Passed as a string in JSON.
+    IpAddr,
+    /// `Vec<f32>`. Passed as an array of numbers in JSON.
+    DenseVector,
+    /// `serde_json::Map<String, serde_json::Value>`. Passed as an object in JSON.
+    JsonObject,
 }
 
 /// A `FieldType` describes the type (text, u64) of a field as well as
@@ -63,6 +80,18 @@ pub enum FieldType {
     HierarchicalFacet,
     /// Bytes (one per document)
     Bytes(BytesOptions),
+    /// Geo point field type configuration
+    GeoPoint(GeoPointOptions),
+    /// Ip address field type configuration
+    IpAddr(IpAddrOptions),
+    /// Dense vector field type configuration
+    DenseVector(DenseVectorOptions),
+    /// Document boost field type configuration.
+    ///
+    /// Its values are `f64`.
+    DocumentBoost(DocumentBoostOptions),
+    /// Json object field type configuration
+    JsonObject(JsonObjectOptions),
 }
 
 impl FieldType {
@@ -76,6 +105,11 @@ impl FieldType {
             FieldType::Date(_) => Type::Date,
             FieldType::HierarchicalFacet => Type::HierarchicalFacet,
             FieldType::Bytes(_) => Type::Bytes,
+            FieldType::GeoPoint(_) => Type::GeoPoint,
+            FieldType::IpAddr(_) => Type::IpAddr,
+            FieldType::DenseVector(_) => Type::DenseVector,
+            FieldType::DocumentBoost(_) => Type::F64,
+            FieldType::JsonObject(_) => Type::JsonObject,
         }
     }
 
@@ -89,6 +123,29 @@ impl FieldType {
             FieldType::Date(ref date_options) => date_options.is_indexed(),
             FieldType::HierarchicalFacet => true,
             FieldType::Bytes(ref bytes_options) => bytes_options.is_indexed(),
+            FieldType::GeoPoint(_) => false,
+            FieldType::IpAddr(ref ip_addr_options) => ip_addr_options.is_indexed(),
+            FieldType::DenseVector(_) => false,
+            FieldType::DocumentBoost(_) => false,
+            FieldType::JsonObject(ref json_object_options) => json_object_options.is_indexed(),
+        }
+    }
+
+    /// returns true iff fieldnorms are recorded for the field.
+    ///
+    /// All indexed fields have fieldnorms, except the text fields
+    /// for which they were disabled with `TextFieldIndexing::set_fieldnorms`.
+    pub fn has_fieldnorms(&self) -> bool {
+        match *self {
+            FieldType::Str(ref text_options) => text_options
+                .get_indexing_options()
+                .map(TextFieldIndexing::fieldnorms)
+                .unwrap_or(false),
+            FieldType::JsonObject(ref json_object_options) => json_object_options
+                .get_indexing_options()
+                .map(TextFieldIndexing::fieldnorms)
+                .unwrap_or(false),
+            _ => self.is_indexed(),
         }
     }
 
@@ -119,6 +176,19 @@ impl FieldType {
                     None
                 }
             }
+            FieldType::GeoPoint(_) => None,
+            FieldType::IpAddr(ref ip_addr_options) => {
+                if ip_addr_options.is_indexed() {
+                    Some(IndexRecordOption::Basic)
+                } else {
+                    None
+                }
+            }
+            FieldType::DenseVector(_) => None,
+            FieldType::DocumentBoost(_) => None,
+            FieldType::JsonObject(ref json_object_options) => json_object_options
+                .get_indexing_options()
+                .map(TextFieldIndexing::index_option),
         }
     }
 
@@ -141,9 +211,33 @@ impl FieldType {
                     Ok(Value::Date(dt_with_fixed_tz.with_timezone(&Utc)))
                 }
                 FieldType::Str(_) => Ok(Value::Str(field_text.clone())),
-                FieldType::U64(_) | FieldType::I64(_) | FieldType::F64(_) => Err(
-                    ValueParsingError::TypeError(format!("Expected an integer, got {:?}", json)),
-                ),
+                FieldType::U64(_)
+                | FieldType::I64(_)
+                | FieldType::F64(_)
+                | FieldType::DocumentBoost(_) => Err(ValueParsingError::TypeError(format!(
+                    "Expected an integer, got {:?}",
+                    json
+                ))),
+                FieldType::GeoPoint(_) => Err(ValueParsingError::TypeError(format!(
+                    "Expected a geo point, got {:?}",
+                    json
+                ))),
+                FieldType::DenseVector(_) => Err(ValueParsingError::TypeError(format!(
+                    "Expected a dense vector, got {:?}",
+                    json
+                ))),
+                FieldType::JsonObject(_) => Err(ValueParsingError::TypeError(format!(
+                    "Expected a json object, got {:?}",
+                    json
+                ))),
+                FieldType::IpAddr(_) => {
+                    IpAddr::from_str(field_text).map(Value::from).map_err(|_| {
+                        ValueParsingError::TypeError(format!(
+                            "Expected an ip address, got {:?}",
+                            field_text
+                        ))
+                    })
+                }
                 FieldType::HierarchicalFacet => Ok(Value::Facet(Facet::from(field_text))),
                 FieldType::Bytes(_) => base64::decode(field_text).map(Value::Bytes).map_err(|_| {
                     ValueParsingError::InvalidBase64(format!(
@@ -169,7 +263,7 @@ impl FieldType {
                         Err(ValueParsingError::OverflowError(msg))
                     }
                 }
-                FieldType::F64(_) => {
+                FieldType::F64(_) | FieldType::DocumentBoost(_) => {
                     if let Some(field_val_f64) = field_val_num.as_f64() {
                         Ok(Value::F64(field_val_f64))
                     } else {
@@ -177,12 +271,66 @@ impl FieldType {
                         Err(ValueParsingError::OverflowError(msg))
                     }
                 }
-                FieldType::Str(_) | FieldType::HierarchicalFacet | FieldType::Bytes(_) => {
+                FieldType::Str(_)
+                | FieldType::HierarchicalFacet
+                | FieldType::Bytes(_)
+                | FieldType::IpAddr(_) => {
                     let msg = format!("Expected a string, got {:?}", json);
                     Err(ValueParsingError::TypeError(msg))
                 }
+                FieldType::GeoPoint(_) => {
+                    let msg = format!("Expected a geo point, got {:?}", json);
+                    Err(ValueParsingError::TypeError(msg))
+                }
+                FieldType::DenseVector(_) => {
+                    let msg = format!("Expected a dense vector, got {:?}", json);
+                    Err(ValueParsingError::TypeError(msg))
+                }
+                FieldType::JsonObject(_) => {
+                    let msg = format!("Expected a json object, got {:?}", json);
+                    Err(ValueParsingError::TypeError(msg))
+                }
+            },
+            JsonValue::Bool(field_val_bool) => match *self {
+                FieldType::U64(_) => Ok(Value::U64(u64::from(field_val_bool))),
+                _ => {
+                    let msg = format!(
+                        "Json value not supported error {:?}. Expected {:?}",
+                        json, self
+                    );
+                    Err(ValueParsingError::TypeError(msg))
+                }
             },
-            JsonValue::Object(_) => match *self {
+            JsonValue::Array(ref json_items) => match *self {
+                FieldType::DenseVector(ref options) => {
+                    let vector: Option<Vec<f32>> = json_items
+                        .iter()
+                        .map(|json_item| json_item.as_f64().map(|val| val as f32))
+                        .collect();
+                    match vector {
+                        Some(vector) if vector.len() == options.dims() => {
+                            Ok(Value::DenseVector(vector))
+                        }
+                        _ => {
+                            let msg = format!(
+                                "Expected a dense vector of {} numbers, got {:?}",
+                                options.dims(),
+                                json
+                            );
+                            Err(ValueParsingError::TypeError(msg))
+                        }
+                    }
+                }
+                _ => {
+                    let msg = format!(
+                        "Json value not supported error {:?}. Expected {:?}",
+                        json, self
+                    );
+                    Err(ValueParsingError::TypeError(msg))
+                }
+            },
+            JsonValue::Object(ref json_object) => match *self {
+                FieldType::JsonObject(_) => Ok(Value::JsonObject(json_object.clone())),
                 FieldType::Str(_) => {
                     if let Ok(tok_str_val) =
                         serde_json::from_value::<PreTokenizedString>(json.clone())
@@ -196,6 +344,16 @@ impl FieldType {
                         Err(ValueParsingError::TypeError(msg))
                     }
                 }
+                FieldType::GeoPoint(_) => match serde_json::from_value::<GeoPoint>(json.clone()) {
+                    Ok(geo_point) if geo_point.is_valid() => Ok(Value::GeoPoint(geo_point)),
+                    _ => {
+                        let msg = format!(
+                            "Json value {:?} cannot be translated to a valid GeoPoint.",
+                            json
+                        );
+                        Err(ValueParsingError::TypeError(msg))
+                    }
+                },
                 _ => {
                     let msg = format!(
                         "Json value not supported error {:?}. Expected {:?}",
@@ -219,12 +377,15 @@ impl FieldType {
 mod tests {
     use super::FieldType;
     use crate::schema::field_type::ValueParsingError;
+    use crate::schema::GeoPoint;
     use crate::schema::TextOptions;
     use crate::schema::Value;
+    use crate::schema::{DenseVectorOptions, VectorSimilarity};
     use crate::schema::{Schema, INDEXED};
     use crate::tokenizer::{PreTokenizedString, Token};
     use crate::{DateTime, Document};
     use chrono::{NaiveDate, NaiveDateTime, NaiveTime, Utc};
+    use std::net::Ipv4Addr;
 
     #[test]
     fn test_deserialize_json_date() {
@@ -251,6 +412,73 @@ mod tests {
         assert_eq!(doc_json, r#"{"date":["1982-09-17T13:20:00+00:00"]}"#);
     }
 
+    #[test]
+    fn test_geo_point_value_from_json() {
+        let field_type = FieldType::GeoPoint(Default::default());
+        let result = field_type
+            .value_from_json(&json!({"lat": 48.8566, "lon": 2.3522}))
+            .unwrap();
+        assert_eq!(result, Value::GeoPoint(GeoPoint::new(48.8566, 2.3522)));
+        assert!(field_type
+            .value_from_json(&json!({"lat": 91.0, "lon": 2.3522}))
+            .is_err());
+        assert!(field_type
+            .value_from_json(&json!({"lat": 48.8566}))
+            .is_err());
+        assert!(field_type.value_from_json(&json!(48.8566)).is_err());
+    }
+
+    #[test]
+    fn test_ip_addr_value_from_json() {
+        let field_type = FieldType::IpAddr(Default::default());
+        let result = field_type.value_from_json(&json!("192.168.0.1")).unwrap();
+        assert_eq!(
+            result,
+            Value::IpAddr(Ipv4Addr::new(192, 168, 0, 1).to_ipv6_mapped())
+        );
+        let result = field_type
+            .value_from_json(&json!("::ffff:192.168.0.1"))
+            .unwrap();
+        assert_eq!(
+            result,
+            Value::IpAddr(Ipv4Addr::new(192, 168, 0, 1).to_ipv6_mapped())
+        );
+        assert!(field_type.value_from_json(&json!("192.168.0.256")).is_err());
+        assert!(field_type.value_from_json(&json!(3)).is_err());
+    }
+
+    #[test]
+    fn test_dense_vector_value_from_json() {
+        let field_type =
+            FieldType::DenseVector(DenseVectorOptions::new(3, VectorSimilarity::Cosine));
+        let result = field_type.value_from_json(&json!([0.5, -1, 2.0])).unwrap();
+        assert_eq!(result, Value::DenseVector(vec![0.5, -1.0, 2.0]));
+        assert!(field_type.value_from_json(&json!([0.5, 1.0])).is_err());
+        assert!(field_type.value_from_json(&json!([0.5, "a", 1.0])).is_err());
+        assert!(field_type.value_from_json(&json!(0.5)).is_err());
+    }
+
+    #[test]
+    fn test_parse_document_dense_vector() {
+        let mut schema_builder = Schema::builder();
+        let embedding = schema_builder.add_dense_vector_field(
+            "embedding",
+            DenseVectorOptions::new(2, VectorSimilarity::DotProduct),
+        );
+        let schema = schema_builder.build();
+        let doc = schema
+            .parse_document(r#"{"embedding": [1.0, 2.0]}"#)
+            .unwrap();
+        assert_eq!(
+            doc.get_first(embedding).unwrap().dense_vector_value(),
+            Some(&[1.0f32, 2.0][..])
+        );
+        let doc_json = schema.to_json(&doc);
+        assert_eq!(doc_json, r#"{"embedding":[[1.0,2.0]]}"#);
+        let doc = schema.parse_document(&doc_json).unwrap();
+        assert_eq!(doc.get_all(embedding).count(), 1);
+    }
+
     #[test]
     fn test_bytes_value_from_json() {
         let result = FieldType::Bytes(Default::default())
@@ -4,7 +4,9 @@ use crate::schema::Value;
 use std::io::{self, Read, Write};
 
 /// `FieldValue` holds together a `Field` and its `Value`.
-#[derive(Debug, Clone, Ord, PartialEq, Eq, PartialOrd, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Debug, Clone, Ord, PartialEq, Eq, PartialOrd, Hash, serde::Serialize, serde::Deserialize,
+)]
 pub struct FieldValue {
     field: Field,
     value: Value,
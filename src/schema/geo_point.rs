@@ -0,0 +1,182 @@
+use serde::{Deserialize, Serialize};
+
+/// Mean radius of the earth, in kilometers.
+pub(crate) const EARTH_RADIUS_KM: f64 = 6_371.0;
+
+/// Number of bits used to quantize the latitude and the longitude.
+const NUM_BITS_PER_COORDINATE: u32 = 31;
+
+/// Largest quantized value of a coordinate.
+pub(crate) const MAX_QUANTIZED_VALUE: u32 = (1u32 << NUM_BITS_PER_COORDINATE) - 1;
+
+/// Bit set on every encoded point.
+///
+/// It makes it possible to tell apart a document that has a point
+/// from a document with no point, whose fast field value is `0`.
+const HAS_POINT_MARKER: u64 = 1u64 << 63;
+
+/// A point on the surface of the earth, expressed in degrees.
+///
+/// The latitude is expected to be within `[-90, 90]` and the longitude
+/// within `[-180, 180]`. Coordinates outside of this range are clamped
+/// when the point is indexed.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct GeoPoint {
+    /// Latitude, in degrees.
+    pub lat: f64,
+    /// Longitude, in degrees.
+    pub lon: f64,
+}
+
+/// Spreads the bits of `val` over the even bits of a `u64`.
+fn spread_bits(val: u32) -> u64 {
+    let mut val = u64::from(val);
+    val = (val | (val << 16)) & 0x0000_FFFF_0000_FFFF;
+    val = (val | (val << 8)) & 0x00FF_00FF_00FF_00FF;
+    val = (val | (val << 4)) & 0x0F0F_0F0F_0F0F_0F0F;
+    val = (val | (val << 2)) & 0x3333_3333_3333_3333;
+    (val | (val << 1)) & 0x5555_5555_5555_5555
+}
+
+/// Inverse of `spread_bits`.
+fn compact_bits(val: u64) -> u32 {
+    let mut val = val & 0x5555_5555_5555_5555;
+    val = (val | (val >> 1)) & 0x3333_3333_3333_3333;
+    val = (val | (val >> 2)) & 0x0F0F_0F0F_0F0F_0F0F;
+    val = (val | (val >> 4)) & 0x00FF_00FF_00FF_00FF;
+    val = (val | (val >> 8)) & 0x0000_FFFF_0000_FFFF;
+    ((val | (val >> 16)) & 0x0000_0000_FFFF_FFFF) as u32
+}
+
+fn quantize(val: f64, min: f64, max: f64) -> u32 {
+    let val = val.max(min).min(max);
+    ((val - min) / (max - min) * f64::from(MAX_QUANTIZED_VALUE)).round() as u32
+}
+
+fn dequantize(val: u32, min: f64, max: f64) -> f64 {
+    min + f64::from(val) / f64::from(MAX_QUANTIZED_VALUE) * (max - min)
+}
+
+pub(crate) fn quantize_lat(lat: f64) -> u32 {
+    quantize(lat, -90.0, 90.0)
+}
+
+pub(crate) fn quantize_lon(lon: f64) -> u32 {
+    quantize(lon, -180.0, 180.0)
+}
+
+/// Interleaves the bits of a quantized latitude and longitude.
+///
+/// The resulting code is increasing with respect to both coordinates.
+pub(crate) fn interleave(lat_quantized: u32, lon_quantized: u32) -> u64 {
+    HAS_POINT_MARKER | (spread_bits(lat_quantized) << 1) | spread_bits(lon_quantized)
+}
+
+/// Returns the quantized latitude and longitude of an encoded point,
+/// or `None` if the code does not hold any point.
+pub(crate) fn deinterleave(code: u64) -> Option<(u32, u32)> {
+    if code & HAS_POINT_MARKER == 0 {
+        return None;
+    }
+    let code = code & !HAS_POINT_MARKER;
+    Some((compact_bits(code >> 1), compact_bits(code)))
+}
+
+impl GeoPoint {
+    /// Creates a new `GeoPoint` given a latitude and a longitude in degrees.
+    pub fn new(lat: f64, lon: f64) -> GeoPoint {
+        GeoPoint { lat, lon }
+    }
+
+    /// Returns true iff the latitude and the longitude are within their valid range.
+    pub fn is_valid(&self) -> bool {
+        (-90.0..=90.0).contains(&self.lat) && (-180.0..=180.0).contains(&self.lon)
+    }
+
+    /// Encodes the point as a `u64`, as stored in the fast field.
+    ///
+    /// Both coordinates are quantized over 31 bits, which
+    /// gives a precision of about 1cm, and their bits
+    /// are interleaved (Z-order curve).
+    pub fn to_u64(&self) -> u64 {
+        interleave(quantize_lat(self.lat), quantize_lon(self.lon))
+    }
+
+    /// Decodes a point encoded with `to_u64`.
+    ///
+    /// Returns `None` if `code` does not hold any point, which is the case
+    /// of the fast field value of a document without a point.
+    pub fn from_u64(code: u64) -> Option<GeoPoint> {
+        let (lat_quantized, lon_quantized) = deinterleave(code)?;
+        Some(GeoPoint {
+            lat: dequantize(lat_quantized, -90.0, 90.0),
+            lon: dequantize(lon_quantized, -180.0, 180.0),
+        })
+    }
+
+    /// Returns the great-circle distance between two points, in kilometers.
+    ///
+    /// The distance is computed using the haversine formula.
+    pub fn distance_km(&self, other: &GeoPoint) -> f64 {
+        let delta_lat = (other.lat - self.lat).to_radians();
+        let delta_lon = (other.lon - self.lon).to_radians();
+        let a = (delta_lat / 2.0).sin().powi(2)
+            + self.lat.to_radians().cos()
+                * other.lat.to_radians().cos()
+                * (delta_lon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_KM * a.sqrt().min(1.0).asin()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compact_bits, spread_bits, GeoPoint};
+
+    #[test]
+    fn test_spread_bits() {
+        assert_eq!(spread_bits(0b1011), 0b100_0101);
+        for &val in &[0u32, 1, 17, 1 << 30, u32::MAX] {
+            assert_eq!(compact_bits(spread_bits(val)), val);
+        }
+    }
+
+    #[test]
+    fn test_geo_point_u64_roundtrip() {
+        for &(lat, lon) in &[
+            (0.0, 0.0),
+            (48.8566, 2.3522),
+            (-33.8688, 151.2093),
+            (90.0, 180.0),
+            (-90.0, -180.0),
+        ] {
+            let point = GeoPoint::from_u64(GeoPoint::new(lat, lon).to_u64()).unwrap();
+            assert!((point.lat - lat).abs() < 1e-7);
+            assert!((point.lon - lon).abs() < 1e-7);
+        }
+        assert_eq!(GeoPoint::from_u64(0u64), None);
+    }
+
+    #[test]
+    fn test_geo_point_u64_is_increasing() {
+        let point = GeoPoint::new(10.0, 10.0);
+        assert!(GeoPoint::new(10.0, 11.0).to_u64() > point.to_u64());
+        assert!(GeoPoint::new(11.0, 10.0).to_u64() > point.to_u64());
+        assert!(GeoPoint::new(9.0, 10.0).to_u64() < point.to_u64());
+    }
+
+    #[test]
+    fn test_geo_point_distance() {
+        let paris = GeoPoint::new(48.8566, 2.3522);
+        let london = GeoPoint::new(51.5074, -0.1278);
+        let distance = paris.distance_km(&london);
+        assert!((distance - 343.5).abs() < 1.0);
+        assert_eq!(paris.distance_km(&paris), 0.0);
+    }
+
+    #[test]
+    fn test_geo_point_is_valid() {
+        assert!(GeoPoint::new(90.0, -180.0).is_valid());
+        assert!(!GeoPoint::new(90.1, 0.0).is_valid());
+        assert!(!GeoPoint::new(0.0, 180.1).is_valid());
+    }
+}
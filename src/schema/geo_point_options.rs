@@ -0,0 +1,112 @@
+use serde::{Deserialize, Serialize};
+use std::ops::BitOr;
+
+use super::flags::{FastFlag, SchemaFlagList, StoredFlag};
+
+/// Define how a geo point field should be handled by tantivy.
+///
+/// Geo point fields are not indexed in the inverted index.
+/// In order to be searchable with a `GeoBoundingBoxQuery`
+/// or a `GeoDistanceQuery`, they need to be declared as fast fields.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GeoPointOptions {
+    fast: bool,
+    stored: bool,
+}
+
+impl GeoPointOptions {
+    /// Returns true iff the value is a fast field.
+    pub fn is_fast(&self) -> bool {
+        self.fast
+    }
+
+    /// Returns true iff the value is stored.
+    pub fn is_stored(&self) -> bool {
+        self.stored
+    }
+
+    /// Set the field as a single-valued fast field.
+    ///
+    /// The point is encoded as a `u64`, by interleaving the bits
+    /// of its latitude and longitude.
+    /// If more than one point is associated to a document, only the last one is
+    /// kept.
+    pub fn set_fast(mut self) -> GeoPointOptions {
+        self.fast = true;
+        self
+    }
+
+    /// Set the field as stored.
+    ///
+    /// Only the fields that are set as *stored* are
+    /// persisted into the Tantivy's store.
+    pub fn set_stored(mut self) -> GeoPointOptions {
+        self.stored = true;
+        self
+    }
+}
+
+impl<T: Into<GeoPointOptions>> BitOr<T> for GeoPointOptions {
+    type Output = GeoPointOptions;
+
+    fn bitor(self, other: T) -> GeoPointOptions {
+        let other = other.into();
+        GeoPointOptions {
+            stored: self.stored | other.stored,
+            fast: self.fast | other.fast,
+        }
+    }
+}
+
+impl From<()> for GeoPointOptions {
+    fn from(_: ()) -> Self {
+        Self::default()
+    }
+}
+
+impl From<FastFlag> for GeoPointOptions {
+    fn from(_: FastFlag) -> Self {
+        GeoPointOptions {
+            stored: false,
+            fast: true,
+        }
+    }
+}
+
+impl From<StoredFlag> for GeoPointOptions {
+    fn from(_: StoredFlag) -> Self {
+        GeoPointOptions {
+            stored: true,
+            fast: false,
+        }
+    }
+}
+
+impl<Head, Tail> From<SchemaFlagList<Head, Tail>> for GeoPointOptions
+where
+    Head: Clone,
+    Tail: Clone,
+    Self: BitOr<Output = Self> + From<Head> + From<Tail>,
+{
+    fn from(head_tail: SchemaFlagList<Head, Tail>) -> Self {
+        Self::from(head_tail.head) | Self::from(head_tail.tail)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::schema::{GeoPointOptions, FAST, STORED};
+
+    #[test]
+    fn test_geo_point_options_flags() {
+        assert_eq!(GeoPointOptions::default().set_fast(), FAST.into());
+        assert_eq!(GeoPointOptions::default().set_stored(), STORED.into());
+        assert_eq!(
+            GeoPointOptions::default().set_fast().set_stored(),
+            (FAST | STORED).into()
+        );
+        assert!(!GeoPointOptions::default().is_fast());
+        assert!(!GeoPointOptions::default().is_stored());
+    }
+}
@@ -12,10 +12,18 @@ pub enum Cardinality {
     /// This is more memory and CPU expensive than the SingleValue solution.
     #[serde(rename = "multi")]
     MultiValues,
+    /// The document can have zero or one value associated to the document.
+    ///
+    /// Unlike with `SingleValue`, the documents without a value can be told apart
+    /// from the documents having the default value, using a
+    /// [`NullableFastFieldReader`](../fastfield/struct.NullableFastFieldReader.html).
+    #[serde(rename = "optional")]
+    Optional,
 }
 
 /// Define how an u64, i64, of f64 field should be handled by tantivy.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct IntOptions {
     indexed: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -0,0 +1,159 @@
+use serde::{Deserialize, Serialize};
+use std::ops::BitOr;
+
+use super::flags::{FastFlag, IndexedFlag, SchemaFlagList, StoredFlag};
+
+/// Define how an ip address field should be handled by tantivy.
+///
+/// IPv4 addresses are handled as IPv4-mapped IPv6 addresses,
+/// so that both kinds of addresses can be compared and ranged over.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct IpAddrOptions {
+    indexed: bool,
+    fast: bool,
+    stored: bool,
+}
+
+impl IpAddrOptions {
+    /// Returns true iff the value is indexed.
+    pub fn is_indexed(&self) -> bool {
+        self.indexed
+    }
+
+    /// Returns true iff the value is a fast field.
+    pub fn is_fast(&self) -> bool {
+        self.fast
+    }
+
+    /// Returns true iff the value is stored.
+    pub fn is_stored(&self) -> bool {
+        self.stored
+    }
+
+    /// Set the field as indexed.
+    ///
+    /// Setting an ip address as indexed will generate
+    /// a posting list for each address. This is required
+    /// to run a `RangeQuery` on the field.
+    pub fn set_indexed(mut self) -> IpAddrOptions {
+        self.indexed = true;
+        self
+    }
+
+    /// Set the field as a single-valued fast field.
+    ///
+    /// Addresses are stored as `u128` values.
+    /// If more than one value is associated to a fast field, only the first one is
+    /// kept.
+    pub fn set_fast(mut self) -> IpAddrOptions {
+        self.fast = true;
+        self
+    }
+
+    /// Set the field as stored.
+    ///
+    /// Only the fields that are set as *stored* are
+    /// persisted into the Tantivy's store.
+    pub fn set_stored(mut self) -> IpAddrOptions {
+        self.stored = true;
+        self
+    }
+}
+
+impl<T: Into<IpAddrOptions>> BitOr<T> for IpAddrOptions {
+    type Output = IpAddrOptions;
+
+    fn bitor(self, other: T) -> IpAddrOptions {
+        let other = other.into();
+        IpAddrOptions {
+            indexed: self.indexed | other.indexed,
+            stored: self.stored | other.stored,
+            fast: self.fast | other.fast,
+        }
+    }
+}
+
+impl From<()> for IpAddrOptions {
+    fn from(_: ()) -> Self {
+        Self::default()
+    }
+}
+
+impl From<FastFlag> for IpAddrOptions {
+    fn from(_: FastFlag) -> Self {
+        IpAddrOptions {
+            indexed: false,
+            stored: false,
+            fast: true,
+        }
+    }
+}
+
+impl From<StoredFlag> for IpAddrOptions {
+    fn from(_: StoredFlag) -> Self {
+        IpAddrOptions {
+            indexed: false,
+            stored: true,
+            fast: false,
+        }
+    }
+}
+
+impl From<IndexedFlag> for IpAddrOptions {
+    fn from(_: IndexedFlag) -> Self {
+        IpAddrOptions {
+            indexed: true,
+            stored: false,
+            fast: false,
+        }
+    }
+}
+
+impl<Head, Tail> From<SchemaFlagList<Head, Tail>> for IpAddrOptions
+where
+    Head: Clone,
+    Tail: Clone,
+    Self: BitOr<Output = Self> + From<Head> + From<Tail>,
+{
+    fn from(head_tail: SchemaFlagList<Head, Tail>) -> Self {
+        Self::from(head_tail.head) | Self::from(head_tail.tail)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::schema::{IpAddrOptions, FAST, INDEXED, STORED};
+
+    #[test]
+    fn test_ip_addr_option_fast_flag() {
+        assert_eq!(IpAddrOptions::default().set_fast(), FAST.into());
+        assert_eq!(IpAddrOptions::default().set_indexed(), INDEXED.into());
+        assert_eq!(IpAddrOptions::default().set_stored(), STORED.into());
+    }
+    #[test]
+    fn test_ip_addr_option_fast_flag_composition() {
+        assert_eq!(
+            IpAddrOptions::default().set_fast().set_stored(),
+            (FAST | STORED).into()
+        );
+        assert_eq!(
+            IpAddrOptions::default().set_indexed().set_fast(),
+            (INDEXED | FAST).into()
+        );
+        assert_eq!(
+            IpAddrOptions::default().set_stored().set_indexed(),
+            (STORED | INDEXED).into()
+        );
+    }
+
+    #[test]
+    fn test_ip_addr_option_fast_() {
+        assert!(!IpAddrOptions::default().is_stored());
+        assert!(!IpAddrOptions::default().is_fast());
+        assert!(!IpAddrOptions::default().is_indexed());
+        assert!(IpAddrOptions::default().set_stored().is_stored());
+        assert!(IpAddrOptions::default().set_fast().is_fast());
+        assert!(IpAddrOptions::default().set_indexed().is_indexed());
+    }
+}
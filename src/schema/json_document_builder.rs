@@ -0,0 +1,98 @@
+use serde_json::{Map as JsonObject, Value as JsonValue};
+use std::collections::BTreeMap;
+
+/// Builds a JSON document, to be indexed with
+/// [`IndexWriter::add_json_document`](../struct.IndexWriter.html#method.add_json_document).
+///
+/// The fields are referred to by their name, and their values are converted
+/// to the type of their field when the document is added.
+///
+/// ```rust
+/// use tantivy::schema::{JsonDocumentBuilder, Schema, STORED, TEXT};
+///
+/// let mut schema_builder = Schema::builder();
+/// let title = schema_builder.add_text_field("title", TEXT | STORED);
+/// let tag = schema_builder.add_text_field("tag", TEXT | STORED);
+/// let schema = schema_builder.build();
+///
+/// let json = JsonDocumentBuilder::new()
+///     .add("title", "The Old Man and the Sea")
+///     .add("tag", "novel")
+///     .add("tag", "classic")
+///     .build();
+/// let doc = schema.json_value_to_doc(&json).unwrap();
+/// assert_eq!(doc.get_first(title).unwrap().text(), Some("The Old Man and the Sea"));
+/// assert_eq!(doc.get_all(tag).count(), 2);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct JsonDocumentBuilder {
+    field_values: BTreeMap<String, Vec<JsonValue>>,
+}
+
+impl JsonDocumentBuilder {
+    /// Creates a new, empty `JsonDocumentBuilder`.
+    pub fn new() -> JsonDocumentBuilder {
+        JsonDocumentBuilder::default()
+    }
+
+    /// Adds a value to the field named `field_name`.
+    ///
+    /// Adding several values to the same field makes it multivalued.
+    pub fn add<T: Into<JsonValue>>(mut self, field_name: &str, value: T) -> JsonDocumentBuilder {
+        self.field_values
+            .entry(field_name.to_string())
+            .or_default()
+            .push(value.into());
+        self
+    }
+
+    /// Returns the JSON object of the document.
+    ///
+    /// A field with a single value is mapped to this value, and a field
+    /// with several values to the array of its values.
+    pub fn build(self) -> JsonValue {
+        let mut json_obj = JsonObject::new();
+        for (field_name, mut values) in self.field_values {
+            let json_value = if values.len() == 1 {
+                values.pop().unwrap()
+            } else {
+                JsonValue::Array(values)
+            };
+            json_obj.insert(field_name, json_value);
+        }
+        JsonValue::Object(json_obj)
+    }
+}
+
+impl From<JsonDocumentBuilder> for JsonValue {
+    fn from(builder: JsonDocumentBuilder) -> JsonValue {
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::JsonDocumentBuilder;
+    use serde_json::json;
+
+    #[test]
+    fn test_json_document_builder() {
+        let json = JsonDocumentBuilder::new()
+            .add("title", "hello")
+            .add("count", 3u64)
+            .add("embedding", vec![0.5, 1.0])
+            .add("tag", "a")
+            .add("tag", "b")
+            .build();
+        assert_eq!(
+            json,
+            json!({
+                "title": "hello",
+                "count": 3,
+                "embedding": [0.5, 1.0],
+                "tag": ["a", "b"],
+            })
+        );
+        assert_eq!(JsonDocumentBuilder::new().build(), json!({}));
+    }
+}
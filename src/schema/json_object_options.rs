@@ -0,0 +1,86 @@
+use crate::schema::flags::SchemaFlagList;
+use crate::schema::{TextFieldIndexing, TextOptions};
+use serde::{Deserialize, Serialize};
+
+/// Define how a json object field should be handled by tantivy.
+///
+/// The leaves of the objects are indexed under their dotted path:
+/// the string values are tokenized with the tokenizer of the
+/// `TextFieldIndexing`, and the numbers are indexed as is.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct JsonObjectOptions {
+    stored: bool,
+    indexing: Option<TextFieldIndexing>,
+}
+
+impl JsonObjectOptions {
+    /// Returns true iff the json object is to be stored.
+    pub fn is_stored(&self) -> bool {
+        self.stored
+    }
+
+    /// Returns true iff the leaves of the json object are to be indexed.
+    pub fn is_indexed(&self) -> bool {
+        self.indexing.is_some()
+    }
+
+    /// Returns the indexing options of the string values of the object.
+    pub fn get_indexing_options(&self) -> Option<&TextFieldIndexing> {
+        self.indexing.as_ref()
+    }
+
+    /// Sets the field as stored
+    pub fn set_stored(mut self) -> JsonObjectOptions {
+        self.stored = true;
+        self
+    }
+
+    /// Sets the field as indexed, with the specific indexing options.
+    ///
+    /// The tokenizer and the `IndexRecordOption` apply to the string values.
+    /// The numbers are never tokenized.
+    pub fn set_indexing_options(mut self, indexing: TextFieldIndexing) -> JsonObjectOptions {
+        self.indexing = Some(indexing);
+        self
+    }
+}
+
+impl From<TextOptions> for JsonObjectOptions {
+    fn from(text_options: TextOptions) -> JsonObjectOptions {
+        JsonObjectOptions {
+            stored: text_options.is_stored(),
+            indexing: text_options.get_indexing_options().cloned(),
+        }
+    }
+}
+
+impl<Head, Tail> From<SchemaFlagList<Head, Tail>> for JsonObjectOptions
+where
+    Head: Clone,
+    Tail: Clone,
+    TextOptions: From<SchemaFlagList<Head, Tail>>,
+{
+    fn from(head_tail: SchemaFlagList<Head, Tail>) -> Self {
+        JsonObjectOptions::from(TextOptions::from(head_tail))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::schema::{JsonObjectOptions, STORED, TEXT};
+
+    #[test]
+    fn test_json_object_options() {
+        let options = JsonObjectOptions::from(STORED | TEXT);
+        assert!(options.is_stored());
+        assert!(options.is_indexed());
+        assert_eq!(
+            options.get_indexing_options().unwrap().tokenizer(),
+            "default"
+        );
+        let options = JsonObjectOptions::from(STORED);
+        assert!(options.is_stored());
+        assert!(!options.is_indexed());
+    }
+}
@@ -112,10 +112,18 @@ mod field_type;
 mod field_value;
 
 mod bytes_options;
+mod dense_vector_options;
+mod document_boost_options;
 mod field;
+mod geo_point;
+mod geo_point_options;
 mod index_record_option;
 mod int_options;
+mod ip_addr_options;
+mod json_document_builder;
+mod json_object_options;
 mod named_field_document;
+mod partial_document;
 mod text_options;
 mod value;
 
@@ -123,14 +131,16 @@ mod flags;
 
 pub use self::named_field_document::NamedFieldDocument;
 pub use self::schema::DocParsingError;
-pub use self::schema::{Schema, SchemaBuilder};
+pub use self::schema::{Schema, SchemaBuilder, SchemaVersion};
 pub use self::value::Value;
+pub(crate) use self::value::{ip_addr_to_ipv6, ipv6_to_ip_addr, skip_value};
 
 pub use self::facet::Facet;
 pub(crate) use self::facet::FACET_SEP_BYTE;
 
 pub use self::document::Document;
 pub use self::field::Field;
+pub use self::partial_document::PartialDocument;
 pub use self::term::Term;
 
 pub use self::field_entry::FieldEntry;
@@ -138,15 +148,27 @@ pub use self::field_type::{FieldType, Type};
 pub use self::field_value::FieldValue;
 
 pub use self::index_record_option::IndexRecordOption;
+pub use self::text_options::TermFrequencyMode;
 pub use self::text_options::TextFieldIndexing;
 pub use self::text_options::TextOptions;
+pub(crate) use self::text_options::DEFAULT_POSITION_GAP;
 pub use self::text_options::STRING;
 pub use self::text_options::TEXT;
 
 pub use self::bytes_options::BytesOptions;
+pub use self::dense_vector_options::{DenseVectorOptions, VectorSimilarity};
+pub use self::document_boost_options::DocumentBoostOptions;
 pub use self::flags::{FAST, INDEXED, STORED};
+pub use self::geo_point::GeoPoint;
+pub(crate) use self::geo_point::{
+    deinterleave, interleave, quantize_lat, quantize_lon, EARTH_RADIUS_KM, MAX_QUANTIZED_VALUE,
+};
+pub use self::geo_point_options::GeoPointOptions;
 pub use self::int_options::Cardinality;
 pub use self::int_options::IntOptions;
+pub use self::ip_addr_options::IpAddrOptions;
+pub use self::json_document_builder::JsonDocumentBuilder;
+pub use self::json_object_options::JsonObjectOptions;
 
 use once_cell::sync::Lazy;
 use regex::Regex;
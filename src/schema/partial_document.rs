@@ -0,0 +1,99 @@
+use super::*;
+use std::collections::BTreeMap;
+
+/// A `PartialDocument` holds the new values of some of the fields
+/// of a document.
+///
+/// It is used to update a document without having to provide all
+/// of its fields. See [`IndexWriter::update_document`](../struct.IndexWriter.html#method.update_document).
+///
+/// The values of a field of the `PartialDocument` replace all of the
+/// values of this field in the updated document. The other fields are left untouched.
+#[derive(Clone, Debug, Default)]
+pub struct PartialDocument {
+    field_values: BTreeMap<Field, Vec<Value>>,
+}
+
+impl PartialDocument {
+    /// Creates a new, empty `PartialDocument`.
+    pub fn new() -> PartialDocument {
+        PartialDocument::default()
+    }
+
+    /// Returns the number of fields updated by this `PartialDocument`.
+    pub fn len(&self) -> usize {
+        self.field_values.len()
+    }
+
+    /// Returns true iff the `PartialDocument` does not update any field.
+    pub fn is_empty(&self) -> bool {
+        self.field_values.is_empty()
+    }
+
+    /// Sets the value of `field`.
+    ///
+    /// The values previously associated to `field`
+    /// in this `PartialDocument` are discarded.
+    pub fn set<T: Into<Value>>(&mut self, field: Field, value: T) {
+        self.field_values.insert(field, vec![value.into()]);
+    }
+
+    /// Adds a value to `field`.
+    ///
+    /// This is useful to update a field that has more than one value.
+    pub fn add<T: Into<Value>>(&mut self, field: Field, value: T) {
+        self.field_values
+            .entry(field)
+            .or_default()
+            .push(value.into());
+    }
+
+    /// Removes all of the values of `field` from the updated document.
+    pub fn clear(&mut self, field: Field) {
+        self.field_values.insert(field, Vec::new());
+    }
+
+    /// Applies the updates to `document`, and returns the updated document.
+    pub fn apply(&self, mut document: Document) -> Document {
+        document.filter_fields(|field| !self.field_values.contains_key(&field));
+        for (&field, values) in &self.field_values {
+            for value in values {
+                document.add(FieldValue::new(field, value.clone()));
+            }
+        }
+        document
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PartialDocument;
+    use crate::schema::{Schema, STORED, TEXT};
+
+    #[test]
+    fn test_partial_document_apply() {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let tags = schema_builder.add_text_field("tags", TEXT);
+        let count = schema_builder.add_u64_field("count", STORED);
+        let document = doc!(title => "hello", tags => "a", tags => "b", count => 1u64);
+        let mut updates = PartialDocument::new();
+        assert!(updates.is_empty());
+        updates.set(count, 3u64);
+        updates.set(count, 2u64);
+        updates.add(tags, "c");
+        updates.add(tags, "d");
+        assert_eq!(updates.len(), 2);
+        let updated_document = updates.apply(document.clone());
+        assert_eq!(
+            updated_document,
+            doc!(title => "hello", tags => "c", tags => "d", count => 2u64)
+        );
+        let mut updates = PartialDocument::new();
+        updates.clear(tags);
+        assert_eq!(
+            updates.apply(document),
+            doc!(title => "hello", count => 1u64)
+        );
+    }
+}
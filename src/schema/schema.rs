@@ -5,6 +5,7 @@ use std::sync::Arc;
 
 use super::*;
 use crate::schema::bytes_options::BytesOptions;
+use crate::TantivyError;
 use serde::de::{SeqAccess, Visitor};
 use serde::ser::SerializeSeq;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
@@ -170,6 +171,98 @@ impl SchemaBuilder {
         self.add_field(field_entry)
     }
 
+    /// Adds a new ip address field.
+    /// Returns the associated field handle
+    ///
+    /// IPv4 and IPv6 addresses can be mixed within the same field:
+    /// IPv4 addresses are handled as IPv4-mapped IPv6 addresses.
+    ///
+    /// # Caution
+    ///
+    /// Appending two fields with the same name
+    /// will result in the shadowing of the first
+    /// by the second one.
+    /// The first field will get a field id
+    /// but only the second one will be indexed.
+    pub fn add_ip_addr_field<T: Into<IpAddrOptions>>(
+        &mut self,
+        field_name_str: &str,
+        field_options: T,
+    ) -> Field {
+        let field_name = String::from(field_name_str);
+        let field_entry = FieldEntry::new_ip_addr(field_name, field_options.into());
+        self.add_field(field_entry)
+    }
+
+    /// Adds a geo point field to the schema.
+    ///
+    /// Geo point fields are not indexed in the inverted index.
+    /// They need to be declared as fast fields to be searchable
+    /// with a `GeoBoundingBoxQuery` or a `GeoDistanceQuery`.
+    pub fn add_geo_point_field<T: Into<GeoPointOptions>>(
+        &mut self,
+        field_name: &str,
+        field_options: T,
+    ) -> Field {
+        let field_entry = FieldEntry::new_geo_point(field_name.to_string(), field_options.into());
+        self.add_field(field_entry)
+    }
+
+    /// Adds a dense vector field to the schema.
+    ///
+    /// Dense vector fields are not indexed in the inverted index.
+    /// Their vectors are stored in the fast fields, and can be
+    /// searched with a `KnnQuery`.
+    pub fn add_dense_vector_field<T: Into<DenseVectorOptions>>(
+        &mut self,
+        field_name: &str,
+        field_options: T,
+    ) -> Field {
+        let field_entry =
+            FieldEntry::new_dense_vector(field_name.to_string(), field_options.into());
+        self.add_field(field_entry)
+    }
+
+    /// Adds a json object field to the schema.
+    ///
+    /// The values of the field are json objects, whose schema is not declared.
+    /// Their leaves are indexed under their dotted path, and can be searched
+    /// with a query like `attributes.author.name:alice`.
+    /// See [`JsonObjectOptions`](./struct.JsonObjectOptions.html).
+    pub fn add_json_field<T: Into<JsonObjectOptions>>(
+        &mut self,
+        field_name: &str,
+        field_options: T,
+    ) -> Field {
+        let field_entry = FieldEntry::new_json_object(field_name.to_string(), field_options.into());
+        self.add_field(field_entry)
+    }
+
+    /// Adds the document boost field to the schema.
+    ///
+    /// The value of this field multiplies the score of the document
+    /// at search time. See [`DocumentBoostOptions`](./struct.DocumentBoostOptions.html).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the schema already has a document boost field.
+    pub fn add_document_boost_field<T: Into<DocumentBoostOptions>>(
+        &mut self,
+        field_name: &str,
+        field_options: T,
+    ) -> Field {
+        assert!(
+            !self
+                .fields
+                .iter()
+                .any(|field_entry| matches!(field_entry.field_type(), FieldType::DocumentBoost(_))),
+            "A schema can only have one document boost field."
+        );
+        let field_entry =
+            FieldEntry::new_document_boost(field_name.to_string(), field_options.into());
+        self.add_field(field_entry)
+    }
+
     /// Adds a field entry to the schema in build.
     pub fn add_field(&mut self, field_entry: FieldEntry) -> Field {
         let field = Field::from_field_id(self.fields.len() as u32);
@@ -189,6 +282,22 @@ impl SchemaBuilder {
     }
 }
 
+/// Version of a `Schema`.
+///
+/// The schema of an existing index can only evolve by appending new fields
+/// (see [`Index::evolve_schema`](../struct.Index.html#method.evolve_schema)).
+/// The version of a schema is therefore its number of fields: a segment written
+/// with a given version contains none of the fields added afterwards.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub struct SchemaVersion(u32);
+
+impl SchemaVersion {
+    /// Returns true iff `field` was already part of the schema at this version.
+    pub fn has_field(self, field: Field) -> bool {
+        field.field_id() < self.0
+    }
+}
+
 struct InnerSchema {
     fields: Vec<FieldEntry>,
     fields_map: HashMap<String, Field>, // transient
@@ -202,6 +311,12 @@ impl PartialEq for InnerSchema {
 
 impl Eq for InnerSchema {}
 
+impl fmt::Debug for Schema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.0.fields.iter()).finish()
+    }
+}
+
 /// Tantivy has a very strict schema.
 /// You need to specify in advance, whether a field is indexed or not,
 /// stored or not, and RAM-based or not.
@@ -222,6 +337,9 @@ impl Eq for InnerSchema {}
 /// let schema = schema_builder.build();
 ///
 /// ```
+///
+/// Two schemas are equal iff they have the same fields, in the same order,
+/// with the same options.
 #[derive(Clone, Eq, PartialEq)]
 pub struct Schema(Arc<InnerSchema>);
 
@@ -236,6 +354,11 @@ impl Schema {
         self.get_field_entry(field).name()
     }
 
+    /// Returns the version of the schema.
+    pub fn version(&self) -> SchemaVersion {
+        SchemaVersion(self.0.fields.len() as u32)
+    }
+
     /// Return the list of all the `Field`s.
     pub fn fields(&self) -> impl Iterator<Item = (Field, &FieldEntry)> {
         self.0
@@ -245,6 +368,15 @@ impl Schema {
             .map(|(field_id, field_entry)| (Field::from_field_id(field_id as u32), field_entry))
     }
 
+    /// Returns the document boost field, if the schema has one.
+    pub fn document_boost_field(&self) -> Option<Field> {
+        self.fields()
+            .find(|(_, field_entry)| {
+                matches!(field_entry.field_type(), FieldType::DocumentBoost(_))
+            })
+            .map(|(field, _)| field)
+    }
+
     /// Creates a new builder.
     pub fn builder() -> SchemaBuilder {
         SchemaBuilder::default()
@@ -296,6 +428,18 @@ impl Schema {
         serde_json::to_string(&self.to_named_doc(doc)).expect("doc encoding failed. This is a bug")
     }
 
+    /// Decodes a schema from its JSON representation,
+    /// as serialized with `serde_json::to_value(&schema)`.
+    ///
+    /// All of the options of the fields are preserved by a round-trip.
+    ///
+    /// Returns `SchemaError` if the JSON is not a valid schema, in particular
+    /// if it contains a key that tantivy does not know about.
+    pub fn from_json(json: &JsonValue) -> crate::Result<Schema> {
+        Schema::deserialize(json)
+            .map_err(|err| TantivyError::SchemaError(format!("Invalid schema: {}", err)))
+    }
+
     /// Build a document object from a json-object.
     pub fn parse_document(&self, doc_json: &str) -> Result<Document, DocParsingError> {
         let json_obj: JsonObject<String, JsonValue> =
@@ -313,26 +457,62 @@ impl Schema {
             let field = self
                 .get_field(field_name)
                 .ok_or_else(|| DocParsingError::NoSuchFieldInSchema(field_name.clone()))?;
-            let field_entry = self.get_field_entry(field);
-            let field_type = field_entry.field_type();
-            match *json_value {
-                JsonValue::Array(ref json_items) => {
-                    for json_item in json_items {
-                        let value = field_type
-                            .value_from_json(json_item)
-                            .map_err(|e| DocParsingError::ValueError(field_name.clone(), e))?;
-                        doc.add(FieldValue::new(field, value));
-                    }
-                }
-                _ => {
+            self.add_json_values(&mut doc, field, field_name, json_value)?;
+        }
+        Ok(doc)
+    }
+
+    /// Builds a document from a JSON object.
+    ///
+    /// The keys of the object are the names of the fields, and the values are
+    /// converted to the type of their field, as in
+    /// [`parse_document`](#method.parse_document). An array adds each of its
+    /// items as a value of the field.
+    ///
+    /// Unlike `parse_document`, the keys that are not the name of a field of
+    /// the schema are skipped.
+    pub fn json_value_to_doc(&self, json: &JsonValue) -> Result<Document, DocParsingError> {
+        let json_obj = json
+            .as_object()
+            .ok_or_else(|| DocParsingError::NotJSONObject(format!("{:.20}", json.to_string())))?;
+        let mut doc = Document::default();
+        for (field_name, json_value) in json_obj.iter() {
+            if let Some(field) = self.get_field(field_name) {
+                self.add_json_values(&mut doc, field, field_name, json_value)?;
+            }
+        }
+        Ok(doc)
+    }
+
+    fn add_json_values(
+        &self,
+        doc: &mut Document,
+        field: Field,
+        field_name: &str,
+        json_value: &JsonValue,
+    ) -> Result<(), DocParsingError> {
+        let field_type = self.get_field_entry(field).field_type();
+        match *json_value {
+            // A dense vector is itself an array of numbers.
+            JsonValue::Array(ref json_items)
+                if field_type.value_type() != Type::DenseVector
+                    || json_items.iter().all(JsonValue::is_array) =>
+            {
+                for json_item in json_items {
                     let value = field_type
-                        .value_from_json(json_value)
-                        .map_err(|e| DocParsingError::ValueError(field_name.clone(), e))?;
+                        .value_from_json(json_item)
+                        .map_err(|e| DocParsingError::ValueError(field_name.to_string(), e))?;
                     doc.add(FieldValue::new(field, value));
                 }
             }
+            _ => {
+                let value = field_type
+                    .value_from_json(json_value)
+                    .map_err(|e| DocParsingError::ValueError(field_name.to_string(), e))?;
+                doc.add(FieldValue::new(field, value));
+            }
         }
-        Ok(doc)
+        Ok(())
     }
 }
 
@@ -391,6 +571,9 @@ pub enum DocParsingError {
     /// The payload given is not valid JSON.
     #[error("The provided string is not valid JSON")]
     NotJSON(String),
+    /// The JSON value given is not an object.
+    #[error("The provided JSON value is not an object: {0:?}")]
+    NotJSONObject(String),
     /// One of the value node could not be parsed.
     #[error("The field '{0:?}' could not be parsed: {1:?}")]
     ValueError(String, ValueParsingError),
@@ -665,7 +848,10 @@ mod tests {
             );
             assert_matches!(
                 json_err,
-                Err(DocParsingError::ValueError(_, ValueParsingError::TypeError(_)))
+                Err(DocParsingError::ValueError(
+                    _,
+                    ValueParsingError::TypeError(_)
+                ))
             );
         }
         {
@@ -680,7 +866,10 @@ mod tests {
             );
             assert_matches!(
                 json_err,
-                Err(DocParsingError::ValueError(_, ValueParsingError::OverflowError(_)))
+                Err(DocParsingError::ValueError(
+                    _,
+                    ValueParsingError::OverflowError(_)
+                ))
             );
         }
         {
@@ -695,7 +884,10 @@ mod tests {
             );
             assert!(!matches!(
                 json_err,
-                Err(DocParsingError::ValueError(_, ValueParsingError::OverflowError(_)))
+                Err(DocParsingError::ValueError(
+                    _,
+                    ValueParsingError::OverflowError(_)
+                ))
             ));
         }
         {
@@ -710,7 +902,10 @@ mod tests {
             );
             assert_matches!(
                 json_err,
-                Err(DocParsingError::ValueError(_, ValueParsingError::OverflowError(_)))
+                Err(DocParsingError::ValueError(
+                    _,
+                    ValueParsingError::OverflowError(_)
+                ))
             );
         }
         {
@@ -814,4 +1009,254 @@ mod tests {
 ]"#;
         assert_eq!(schema_json, expected);
     }
+
+    #[test]
+    fn test_schema_eq() {
+        let build_schema = |title_options: TextOptions| {
+            let mut schema_builder = Schema::builder();
+            schema_builder.add_text_field("title", title_options);
+            schema_builder.add_u64_field("count", FAST);
+            schema_builder.build()
+        };
+        let schema = build_schema(TEXT);
+        assert_eq!(schema.clone(), schema);
+        assert_eq!(build_schema(TEXT), schema);
+        assert_ne!(build_schema(TEXT | STORED), schema);
+    }
+
+    #[test]
+    fn test_schema_from_json() {
+        let mut schema_builder = Schema::builder();
+        schema_builder.add_text_field("title", TEXT | STORED);
+        schema_builder.add_u64_field("count", FAST);
+        let schema = schema_builder.build();
+        let schema_json = serde_json::to_value(&schema).unwrap();
+        assert_eq!(Schema::from_json(&schema_json).unwrap(), schema);
+
+        let mut unknown_key_json = schema_json.clone();
+        unknown_key_json[1]["options"]["boost"] = serde_json::json!(2.0);
+        assert!(matches!(
+            Schema::from_json(&unknown_key_json),
+            Err(crate::TantivyError::SchemaError(_))
+        ));
+        let mut unknown_type_json = schema_json;
+        unknown_type_json[0]["type"] = serde_json::json!("u8");
+        assert!(matches!(
+            Schema::from_json(&unknown_type_json),
+            Err(crate::TantivyError::SchemaError(_))
+        ));
+    }
+
+    mod proptests {
+        use crate::query::Similarity;
+        use crate::schema::*;
+        use proptest::prelude::*;
+
+        fn text_indexing_strategy() -> impl Strategy<Value = TextFieldIndexing> {
+            (
+                prop_oneof![Just("default"), Just("raw"), Just("en_stem")],
+                prop_oneof![
+                    Just(IndexRecordOption::Basic),
+                    Just(IndexRecordOption::WithFreqs),
+                    Just(IndexRecordOption::WithFreqsAndPositions)
+                ],
+                proptest::option::of(1u32..10),
+                any::<bool>(),
+                prop_oneof![Just(Similarity::BM25), Just(Similarity::BM25Smooth)],
+                any::<bool>(),
+                prop_oneof![
+                    Just(TermFrequencyMode::Count),
+                    Just(TermFrequencyMode::Binary)
+                ],
+            )
+                .prop_map(
+                    |(
+                        tokenizer,
+                        record,
+                        position_gap,
+                        fieldnorms,
+                        similarity,
+                        term_vectors,
+                        term_frequency_mode,
+                    )| {
+                        let mut indexing = TextFieldIndexing::default()
+                            .set_tokenizer(tokenizer)
+                            .set_index_option(record)
+                            .set_fieldnorms(fieldnorms)
+                            .set_similarity(similarity)
+                            .set_term_vectors(term_vectors)
+                            .set_term_frequency_mode(term_frequency_mode);
+                        if let Some(position_gap) = position_gap {
+                            indexing = indexing.set_position_gap(position_gap);
+                        }
+                        indexing
+                    },
+                )
+        }
+
+        fn int_options_strategy() -> impl Strategy<Value = IntOptions> {
+            (
+                any::<bool>(),
+                any::<bool>(),
+                proptest::option::of(prop_oneof![
+                    Just(Cardinality::SingleValue),
+                    Just(Cardinality::MultiValues),
+                    Just(Cardinality::Optional)
+                ]),
+            )
+                .prop_map(|(indexed, stored, fast)| {
+                    let mut options = IntOptions::default();
+                    if indexed {
+                        options = options.set_indexed();
+                    }
+                    if stored {
+                        options = options.set_stored();
+                    }
+                    if let Some(cardinality) = fast {
+                        options = options.set_fast(cardinality);
+                    }
+                    options
+                })
+        }
+
+        fn field_type_strategy() -> impl Strategy<Value = FieldType> {
+            let flags = (any::<bool>(), any::<bool>(), any::<bool>());
+            prop_oneof![
+                (
+                    proptest::option::of(text_indexing_strategy()),
+                    any::<bool>()
+                )
+                    .prop_map(|(indexing, stored)| {
+                        let mut options = TextOptions::default();
+                        if let Some(indexing) = indexing {
+                            options = options.set_indexing_options(indexing);
+                        }
+                        if stored {
+                            options = options.set_stored();
+                        }
+                        FieldType::Str(options)
+                    }),
+                int_options_strategy().prop_map(FieldType::U64),
+                int_options_strategy().prop_map(FieldType::I64),
+                int_options_strategy().prop_map(FieldType::F64),
+                int_options_strategy().prop_map(FieldType::Date),
+                Just(FieldType::HierarchicalFacet),
+                flags.prop_map(|(indexed, fast, stored)| {
+                    let mut options = BytesOptions::default();
+                    if indexed {
+                        options = options.set_indexed();
+                    }
+                    if fast {
+                        options = options.set_fast();
+                    }
+                    if stored {
+                        options = options.set_stored();
+                    }
+                    FieldType::Bytes(options)
+                }),
+                flags.prop_map(|(indexed, fast, stored)| {
+                    let mut options = IpAddrOptions::default();
+                    if indexed {
+                        options = options.set_indexed();
+                    }
+                    if fast {
+                        options = options.set_fast();
+                    }
+                    if stored {
+                        options = options.set_stored();
+                    }
+                    FieldType::IpAddr(options)
+                }),
+                flags.prop_map(|(_, fast, stored)| {
+                    let mut options = GeoPointOptions::default();
+                    if fast {
+                        options = options.set_fast();
+                    }
+                    if stored {
+                        options = options.set_stored();
+                    }
+                    FieldType::GeoPoint(options)
+                }),
+                (
+                    1usize..1_000,
+                    prop_oneof![
+                        Just(VectorSimilarity::Cosine),
+                        Just(VectorSimilarity::DotProduct)
+                    ],
+                    any::<bool>()
+                )
+                    .prop_map(|(dims, similarity, stored)| {
+                        let mut options = DenseVectorOptions::new(dims, similarity);
+                        if stored {
+                            options = options.set_stored();
+                        }
+                        FieldType::DenseVector(options)
+                    }),
+                (
+                    proptest::option::of(text_indexing_strategy()),
+                    any::<bool>()
+                )
+                    .prop_map(|(indexing, stored)| {
+                        let mut options = JsonObjectOptions::default();
+                        if let Some(indexing) = indexing {
+                            options = options.set_indexing_options(indexing);
+                        }
+                        if stored {
+                            options = options.set_stored();
+                        }
+                        FieldType::JsonObject(options)
+                    }),
+            ]
+        }
+
+        fn field_entry(field_name: String, field_type: FieldType) -> FieldEntry {
+            match field_type {
+                FieldType::Str(options) => FieldEntry::new_text(field_name, options),
+                FieldType::U64(options) => FieldEntry::new_u64(field_name, options),
+                FieldType::I64(options) => FieldEntry::new_i64(field_name, options),
+                FieldType::F64(options) => FieldEntry::new_f64(field_name, options),
+                FieldType::Date(options) => FieldEntry::new_date(field_name, options),
+                FieldType::HierarchicalFacet => FieldEntry::new_facet(field_name),
+                FieldType::Bytes(options) => FieldEntry::new_bytes(field_name, options),
+                FieldType::IpAddr(options) => FieldEntry::new_ip_addr(field_name, options),
+                FieldType::GeoPoint(options) => FieldEntry::new_geo_point(field_name, options),
+                FieldType::DenseVector(options) => {
+                    FieldEntry::new_dense_vector(field_name, options)
+                }
+                FieldType::DocumentBoost(options) => {
+                    FieldEntry::new_document_boost(field_name, options)
+                }
+                FieldType::JsonObject(options) => FieldEntry::new_json_object(field_name, options),
+            }
+        }
+
+        proptest! {
+            #[test]
+            fn test_schema_json_roundtrip(
+                field_types in proptest::collection::vec(field_type_strategy(), 0..20),
+                document_boost in proptest::option::of(any::<bool>()),
+            ) {
+                let mut schema_builder = Schema::builder();
+                for (ord, field_type) in field_types.into_iter().enumerate() {
+                    schema_builder.add_field(field_entry(format!("field{}", ord), field_type));
+                }
+                if let Some(stored) = document_boost {
+                    let options = if stored {
+                        DocumentBoostOptions::default().set_stored()
+                    } else {
+                        DocumentBoostOptions::default()
+                    };
+                    schema_builder.add_document_boost_field("boost", options);
+                }
+                let schema = schema_builder.build();
+                let schema_json = serde_json::to_value(&schema).unwrap();
+                let deserialized_schema = Schema::from_json(&schema_json).unwrap();
+                let field_entries: Vec<&FieldEntry> = schema.fields().map(|(_, entry)| entry).collect();
+                let deserialized_field_entries: Vec<&FieldEntry> =
+                    deserialized_schema.fields().map(|(_, entry)| entry).collect();
+                prop_assert_eq!(field_entries, deserialized_field_entries);
+                prop_assert_eq!(deserialized_schema, schema);
+            }
+        }
+    }
 }
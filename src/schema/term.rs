@@ -2,17 +2,30 @@ use std::fmt;
 
 use super::Field;
 use crate::common;
+use crate::schema::ip_addr_to_ipv6;
 use crate::schema::Facet;
 use crate::DateTime;
+use std::net::{IpAddr, Ipv6Addr};
 use std::str;
 
 /// Size (in bytes) of the buffer of a int field.
 const INT_TERM_LEN: usize = 4 + 8;
 
+/// Byte following the path of the terms of a json object field.
+pub(crate) const JSON_END_OF_PATH: u8 = 0u8;
+
+/// Type codes of the values of the terms of a json object field,
+/// written right after `JSON_END_OF_PATH`.
+const JSON_TEXT_CODE: u8 = b's';
+const JSON_U64_CODE: u8 = b'u';
+const JSON_I64_CODE: u8 = b'i';
+const JSON_F64_CODE: u8 = b'f';
+
 /// Term represents the value that the token can take.
 ///
 /// It actually wraps a `Vec<u8>`.
 #[derive(Clone, PartialEq, PartialOrd, Ord, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Term<B = Vec<u8>>(B)
 where
     B: AsRef<[u8]>;
@@ -103,6 +116,90 @@ impl Term {
         term
     }
 
+    /// Builds a term given a field, and an ip address.
+    ///
+    /// IPv4 addresses are mapped to IPv4-mapped IPv6 addresses.
+    pub fn from_field_ip_addr(field: Field, ip_addr: IpAddr) -> Term {
+        let mut term = Term::for_field(field);
+        term.set_ip_addr(ip_addr_to_ipv6(ip_addr));
+        term
+    }
+
+    /// Builds a term of a json object field, given the dotted path of a leaf
+    /// of the object, like `author.name`, and a text token.
+    ///
+    /// The term consists of the field, the path, `0u8`, a type code and the value.
+    pub fn from_json_path_text(field: Field, path: &str, text: &str) -> Term {
+        let mut term = Term::for_field(field);
+        term.set_json_path_text(path, text);
+        term
+    }
+
+    /// Builds a term of a json object field, given the dotted path of a leaf
+    /// and a u64-value.
+    pub fn from_json_path_u64(field: Field, path: &str, val: u64) -> Term {
+        Term::from_json_path_value(field, path, JSON_U64_CODE, &val.to_be_bytes())
+    }
+
+    /// Builds a term of a json object field, given the dotted path of a leaf
+    /// and a i64-value.
+    pub fn from_json_path_i64(field: Field, path: &str, val: i64) -> Term {
+        let val_u64 = common::i64_to_u64(val);
+        Term::from_json_path_value(field, path, JSON_I64_CODE, &val_u64.to_be_bytes())
+    }
+
+    /// Builds a term of a json object field, given the dotted path of a leaf
+    /// and a f64-value.
+    pub fn from_json_path_f64(field: Field, path: &str, val: f64) -> Term {
+        let val_u64 = common::f64_to_u64(val);
+        Term::from_json_path_value(field, path, JSON_F64_CODE, &val_u64.to_be_bytes())
+    }
+
+    /// Builds the term of a number of a json object.
+    ///
+    /// Numbers are indexed as `i64` if they are integers fitting in a `i64`
+    /// (including the floats without a fractional part), as `u64` if they are
+    /// larger integers, and as `f64` otherwise, so that `3` and `3.0` match.
+    pub(crate) fn from_json_path_number(
+        field: Field,
+        path: &str,
+        number: &serde_json::Number,
+    ) -> Term {
+        if let Some(val) = number.as_i64() {
+            return Term::from_json_path_i64(field, path, val);
+        }
+        if let Some(val) = number.as_u64() {
+            return Term::from_json_path_u64(field, path, val);
+        }
+        let val = number.as_f64().unwrap_or(f64::NAN);
+        if val.fract() == 0.0 && val >= i64::MIN as f64 && val < i64::MAX as f64 {
+            Term::from_json_path_i64(field, path, val as i64)
+        } else {
+            Term::from_json_path_f64(field, path, val)
+        }
+    }
+
+    fn from_json_path_value(field: Field, path: &str, type_code: u8, value: &[u8]) -> Term {
+        let mut term = Term::for_field(field);
+        term.set_json_path(path, type_code);
+        term.0.extend_from_slice(value);
+        term
+    }
+
+    /// Sets the path and the type code of a json object term, keeping the field untouched.
+    fn set_json_path(&mut self, path: &str, type_code: u8) {
+        self.0.truncate(4);
+        self.0.extend_from_slice(path.as_bytes());
+        self.0.push(JSON_END_OF_PATH);
+        self.0.push(type_code);
+    }
+
+    /// Sets the path and the text token of a json object term, keeping the field untouched.
+    pub(crate) fn set_json_path_text(&mut self, path: &str, text: &str) {
+        self.set_json_path(path, JSON_TEXT_CODE);
+        self.0.extend_from_slice(text.as_bytes());
+    }
+
     /// Creates a new Term for a given field.
     pub(crate) fn for_field(field: Field) -> Term {
         let mut term = Term(Vec::with_capacity(100));
@@ -137,6 +234,14 @@ impl Term {
         self.set_u64(common::f64_to_u64(val));
     }
 
+    /// Sets an ip address value in the term.
+    ///
+    /// Addresses are serialized using their (16-byte) BigEndian
+    /// representation, which preserves their natural order.
+    pub fn set_ip_addr(&mut self, ip_addr: Ipv6Addr) {
+        self.set_bytes(&ip_addr.octets());
+    }
+
     /// Sets the value of a `Bytes` field.
     pub fn set_bytes(&mut self, bytes: &[u8]) {
         self.0.resize(4, 0u8);
@@ -240,6 +345,21 @@ impl fmt::Debug for Term {
     }
 }
 
+/// Displays the term as `field_id:value`, like `0:coffee`.
+///
+/// The field is displayed by its id, as the schema is not known to the term.
+/// The value is displayed as text if it is valid utf-8 without control
+/// characters, and as bytes otherwise.
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:", self.field().field_id())?;
+        match str::from_utf8(self.value_bytes()) {
+            Ok(text) if !text.chars().any(char::is_control) => write!(f, "{}", text),
+            _ => write!(f, "{:?}", self.value_bytes()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -272,4 +392,36 @@ mod tests {
             assert_eq!(term.as_slice()[11], (983u64 % 256u64) as u8);
         }
     }
+
+    #[test]
+    pub fn test_json_term() {
+        let mut schema_builder = Schema::builder();
+        let attributes = schema_builder.add_json_field("attributes", TEXT);
+        let term = Term::from_json_path_text(attributes, "author.name", "alice");
+        assert_eq!(term.field(), attributes);
+        assert_eq!(term.value_bytes(), b"author.name\0salice");
+        let term = Term::from_json_path_i64(attributes, "year", 2);
+        assert_eq!(&term.value_bytes()[..6], b"year\0i");
+        assert_eq!(term.value_bytes().len(), 6 + 8);
+        let three = serde_json::Number::from(3u64);
+        let three_float = serde_json::Number::from_f64(3.0).unwrap();
+        assert_eq!(
+            Term::from_json_path_number(attributes, "year", &three),
+            Term::from_json_path_i64(attributes, "year", 3)
+        );
+        assert_eq!(
+            Term::from_json_path_number(attributes, "year", &three_float),
+            Term::from_json_path_i64(attributes, "year", 3)
+        );
+        let big = serde_json::Number::from(u64::MAX);
+        assert_eq!(
+            Term::from_json_path_number(attributes, "year", &big),
+            Term::from_json_path_u64(attributes, "year", u64::MAX)
+        );
+        let half = serde_json::Number::from_f64(0.5).unwrap();
+        assert_eq!(
+            Term::from_json_path_number(attributes, "year", &half),
+            Term::from_json_path_f64(attributes, "year", 0.5)
+        );
+    }
 }
@@ -1,3 +1,4 @@
+use crate::query::Similarity;
 use crate::schema::flags::SchemaFlagList;
 use crate::schema::flags::StoredFlag;
 use crate::schema::IndexRecordOption;
@@ -5,8 +6,13 @@ use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 use std::ops::BitOr;
 
+/// Default increment of the position between two values of a field.
+/// (See `TextFieldIndexing::set_position_gap`.)
+pub(crate) const DEFAULT_POSITION_GAP: u32 = 2;
+
 /// Define how a text field should be handled by tantivy.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct TextOptions {
     indexing: Option<TextFieldIndexing>,
     stored: bool,
@@ -45,16 +51,74 @@ impl Default for TextOptions {
     }
 }
 
+/// Defines the term frequency recorded for the terms of a text field.
+///
+/// The term frequency is the number of occurrences of a term within a document.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TermFrequencyMode {
+    /// The term frequency is the number of occurrences of the term.
+    #[serde(rename = "count")]
+    Count,
+    /// Each term is considered to appear at most once per document: the
+    /// term frequency is always 1.
+    ///
+    /// This is useful to score near-duplicate detection or diversity queries,
+    /// in which repeating a term should not make a document more relevant.
+    #[serde(rename = "binary")]
+    Binary,
+}
+
+impl Default for TermFrequencyMode {
+    /// The term frequency is the number of occurrences of the term by default.
+    fn default() -> TermFrequencyMode {
+        TermFrequencyMode::Count
+    }
+}
+
+impl TermFrequencyMode {
+    pub(crate) fn is_default(&self) -> bool {
+        *self == TermFrequencyMode::default()
+    }
+}
+
 /// Configuration defining indexing for a text field.
 ///
 /// It defines
 /// - the amount of information that should be stored about the presence of a term in a document.
 /// Essentially, should we store the term frequency and/or the positions (See [`IndexRecordOption`](./enum.IndexRecordOption.html)).
 /// - the name of the `Tokenizer` that should be used to process the field.
+/// - the gap between the positions of the tokens of two consecutive values of the field.
+/// - whether fieldnorms should be recorded for the field.
+/// - the similarity used to score the matches in the field.
+/// - whether term vectors should be stored for the field.
+/// - how the term frequencies are recorded.
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct TextFieldIndexing {
     record: IndexRecordOption,
     tokenizer: Cow<'static, str>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    position_gap: Option<u32>,
+    #[serde(default = "default_fieldnorms", skip_serializing_if = "is_true")]
+    fieldnorms: bool,
+    #[serde(default, skip_serializing_if = "Similarity::is_default")]
+    similarity: Similarity,
+    #[serde(default, skip_serializing_if = "is_false")]
+    term_vectors: bool,
+    #[serde(default, skip_serializing_if = "TermFrequencyMode::is_default")]
+    term_frequency_mode: TermFrequencyMode,
+}
+
+fn default_fieldnorms() -> bool {
+    true
+}
+
+fn is_true(val: &bool) -> bool {
+    *val
+}
+
+fn is_false(val: &bool) -> bool {
+    !*val
 }
 
 impl Default for TextFieldIndexing {
@@ -62,6 +126,11 @@ impl Default for TextFieldIndexing {
         TextFieldIndexing {
             tokenizer: Cow::Borrowed("default"),
             record: IndexRecordOption::Basic,
+            position_gap: None,
+            fieldnorms: true,
+            similarity: Similarity::BM25,
+            term_vectors: false,
+            term_frequency_mode: TermFrequencyMode::Count,
         }
     }
 }
@@ -92,6 +161,102 @@ impl TextFieldIndexing {
     pub fn index_option(&self) -> IndexRecordOption {
         self.record
     }
+
+    /// Sets the increment of the position between the last token of a value
+    /// of the field and the first token of the next value of the same document.
+    ///
+    /// With a gap of 1, the values are indexed as if they were concatenated,
+    /// and phrase queries match across values. Larger gaps prevent the phrase queries,
+    /// up to a slop of `position_gap - 2`, from matching across values.
+    /// The default gap is 2.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `position_gap` is 0, as the tokens of consecutive values
+    /// would then share the same position.
+    pub fn set_position_gap(mut self, position_gap: u32) -> TextFieldIndexing {
+        assert!(position_gap > 0, "The position gap must be at least 1.");
+        self.position_gap = Some(position_gap);
+        self
+    }
+
+    /// Returns the increment of the position between two values of the field.
+    pub fn position_gap(&self) -> u32 {
+        self.position_gap.unwrap_or(DEFAULT_POSITION_GAP)
+    }
+
+    /// Sets whether fieldnorms should be recorded for the field.
+    ///
+    /// Fieldnorms take one byte per document, and are used to favor the
+    /// matches in short values when scoring. They are of no use for fields
+    /// whose length does not matter, like identifiers or keywords.
+    /// Without fieldnorms, all documents are scored as if the field
+    /// had a length of 1.
+    ///
+    /// Fieldnorms are recorded by default.
+    pub fn set_fieldnorms(mut self, fieldnorms: bool) -> TextFieldIndexing {
+        self.fieldnorms = fieldnorms;
+        self
+    }
+
+    /// Returns true iff fieldnorms are recorded for the field.
+    pub fn fieldnorms(&self) -> bool {
+        self.fieldnorms
+    }
+
+    /// Sets the similarity used to score the matches in the field.
+    ///
+    /// See [`Similarity`](../query/enum.Similarity.html). The similarity is only
+    /// used at search time. BM25 is used by default.
+    pub fn set_similarity(mut self, similarity: Similarity) -> TextFieldIndexing {
+        self.similarity = similarity;
+        self
+    }
+
+    /// Returns the similarity used to score the matches in the field.
+    pub fn similarity(&self) -> Similarity {
+        self.similarity
+    }
+
+    /// Sets whether term vectors should be stored for the field.
+    ///
+    /// The term vector of a document lists the terms of the field in the document,
+    /// with the position and the offsets of each of their occurrences.
+    /// Term vectors are stored in a separate file of the segment, and can be read
+    /// with [`Searcher::term_vectors`](../struct.Searcher.html#method.term_vectors).
+    /// They make it possible to highlight a document without tokenizing it again.
+    ///
+    /// Term vectors are not stored by default.
+    pub fn set_term_vectors(mut self, term_vectors: bool) -> TextFieldIndexing {
+        self.term_vectors = term_vectors;
+        self
+    }
+
+    /// Returns true iff term vectors are stored for the field.
+    pub fn term_vectors(&self) -> bool {
+        self.term_vectors
+    }
+
+    /// Sets how the term frequencies of the field are recorded.
+    ///
+    /// With `TermFrequencyMode::Binary`, the term frequency of all of the terms
+    /// is 1, and the scores ignore how many times a term appears in a document.
+    /// If positions are indexed, the actual number of occurrences is still needed
+    /// to decode them: it is recorded, and replaced by 1 when scoring.
+    ///
+    /// The term frequencies count the occurrences by default.
+    pub fn set_term_frequency_mode(
+        mut self,
+        term_frequency_mode: TermFrequencyMode,
+    ) -> TextFieldIndexing {
+        self.term_frequency_mode = term_frequency_mode;
+        self
+    }
+
+    /// Returns how the term frequencies of the field are recorded.
+    pub fn term_frequency_mode(&self) -> TermFrequencyMode {
+        self.term_frequency_mode
+    }
 }
 
 /// The field will be untokenized and indexed
@@ -99,6 +264,11 @@ pub const STRING: TextOptions = TextOptions {
     indexing: Some(TextFieldIndexing {
         tokenizer: Cow::Borrowed("raw"),
         record: IndexRecordOption::Basic,
+        position_gap: None,
+        fieldnorms: true,
+        similarity: Similarity::BM25,
+        term_vectors: false,
+        term_frequency_mode: TermFrequencyMode::Count,
     }),
     stored: false,
 };
@@ -108,6 +278,11 @@ pub const TEXT: TextOptions = TextOptions {
     indexing: Some(TextFieldIndexing {
         tokenizer: Cow::Borrowed("default"),
         record: IndexRecordOption::WithFreqsAndPositions,
+        position_gap: None,
+        fieldnorms: true,
+        similarity: Similarity::BM25,
+        term_vectors: false,
+        term_frequency_mode: TermFrequencyMode::Count,
     }),
     stored: false,
 };
@@ -152,6 +327,7 @@ where
 
 #[cfg(test)]
 mod tests {
+    use crate::query::Similarity;
     use crate::schema::*;
 
     #[test]
@@ -169,6 +345,70 @@ mod tests {
                 if text_options.get_indexing_options().unwrap().tokenizer() == "default"));
     }
 
+    #[test]
+    fn test_position_gap() {
+        assert_eq!(TextFieldIndexing::default().position_gap(), 2);
+        assert_eq!(
+            TextFieldIndexing::default()
+                .set_position_gap(100)
+                .position_gap(),
+            100
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "The position gap must be at least 1.")]
+    fn test_position_gap_zero_is_forbidden() {
+        TextFieldIndexing::default().set_position_gap(0);
+    }
+
+    #[test]
+    fn test_fieldnorms() {
+        assert!(TextFieldIndexing::default().fieldnorms());
+        let indexing = TextFieldIndexing::default().set_fieldnorms(false);
+        assert!(!indexing.fieldnorms());
+        let json = serde_json::to_string(&indexing).unwrap();
+        assert!(json.contains("\"fieldnorms\":false"));
+        let deserialized: TextFieldIndexing = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, indexing);
+        // Fieldnorms default to true when they are not specified.
+        let json = serde_json::to_string(&TextFieldIndexing::default()).unwrap();
+        assert!(!json.contains("fieldnorms"));
+        let deserialized: TextFieldIndexing = serde_json::from_str(&json).unwrap();
+        assert!(deserialized.fieldnorms());
+    }
+
+    #[test]
+    fn test_similarity() {
+        assert_eq!(TextFieldIndexing::default().similarity(), Similarity::BM25);
+        let indexing = TextFieldIndexing::default().set_similarity(Similarity::BM25Smooth);
+        let json = serde_json::to_string(&indexing).unwrap();
+        assert!(json.contains("\"similarity\":\"bm25_smooth\""));
+        let deserialized: TextFieldIndexing = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.similarity(), Similarity::BM25Smooth);
+        let json = serde_json::to_string(&TextFieldIndexing::default()).unwrap();
+        assert!(!json.contains("similarity"));
+    }
+
+    #[test]
+    fn test_term_frequency_mode() {
+        assert_eq!(
+            TextFieldIndexing::default().term_frequency_mode(),
+            TermFrequencyMode::Count
+        );
+        let indexing =
+            TextFieldIndexing::default().set_term_frequency_mode(TermFrequencyMode::Binary);
+        let json = serde_json::to_string(&indexing).unwrap();
+        assert!(json.contains("\"term_frequency_mode\":\"binary\""));
+        let deserialized: TextFieldIndexing = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            deserialized.term_frequency_mode(),
+            TermFrequencyMode::Binary
+        );
+        let json = serde_json::to_string(&TextFieldIndexing::default()).unwrap();
+        assert!(!json.contains("term_frequency_mode"));
+    }
+
     #[test]
     fn test_cmp_index_record_option() {
         assert!(IndexRecordOption::WithFreqsAndPositions > IndexRecordOption::WithFreqs);
@@ -1,13 +1,23 @@
+use crate::common::f64_to_u64;
 use crate::schema::Facet;
+use crate::schema::GeoPoint;
 use crate::tokenizer::PreTokenizedString;
 use crate::DateTime;
-use serde::de::Visitor;
+use serde::de::value::MapAccessDeserializer;
+use serde::de::{MapAccess, Visitor};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use std::{cmp::Ordering, fmt};
+use serde_json::Map as JsonObject;
+use std::hash::{Hash, Hasher};
+use std::net::{IpAddr, Ipv6Addr};
+use std::{cmp::Ordering, fmt, mem};
 
 /// Value represents the value of a any field.
 /// It is an enum over all over all of the possible field type.
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Floats (including the coordinates of geo points and the components of
+/// dense vectors) are compared and hashed according to their bit representation:
+/// `NaN` is equal to itself, and `0.0` differs from `-0.0`.
+#[derive(Debug, Clone)]
 pub enum Value {
     /// The str type is used for any text information.
     Str(String),
@@ -25,9 +35,99 @@ pub enum Value {
     Facet(Facet),
     /// Arbitrarily sized byte array
     Bytes(Vec<u8>),
+    /// Geo point, given as a latitude and a longitude
+    GeoPoint(GeoPoint),
+    /// Ip address. IPv4 addresses are represented as IPv4-mapped IPv6 addresses.
+    IpAddr(Ipv6Addr),
+    /// Dense vector of `f32`
+    DenseVector(Vec<f32>),
+    /// Json object, whose leaves are indexed under their dotted path.
+    JsonObject(JsonObject<String, serde_json::Value>),
+}
+
+/// Maps IPv4 addresses to IPv4-mapped IPv6 addresses.
+pub(crate) fn ip_addr_to_ipv6(ip_addr: IpAddr) -> Ipv6Addr {
+    match ip_addr {
+        IpAddr::V4(ipv4_addr) => ipv4_addr.to_ipv6_mapped(),
+        IpAddr::V6(ipv6_addr) => ipv6_addr,
+    }
+}
+
+/// Inverse of `ip_addr_to_ipv6`: IPv4-mapped IPv6 addresses are
+/// turned back into IPv4 addresses.
+pub(crate) fn ipv6_to_ip_addr(ipv6_addr: Ipv6Addr) -> IpAddr {
+    match ipv6_addr.segments() {
+        [0, 0, 0, 0, 0, 0xffff, _, _] => {
+            let octets = ipv6_addr.octets();
+            IpAddr::from([octets[12], octets[13], octets[14], octets[15]])
+        }
+        _ => IpAddr::V6(ipv6_addr),
+    }
+}
+
+fn geo_point_bits(geo_point: &GeoPoint) -> (u64, u64) {
+    (geo_point.lat.to_bits(), geo_point.lon.to_bits())
+}
+
+fn dense_vector_bits(vector: &[f32]) -> impl Iterator<Item = u32> + '_ {
+    vector.iter().map(|val| val.to_bits())
+}
+
+/// Json objects are hashed and ordered according to their serialization,
+/// as `serde_json::Value` is neither `Hash` nor `Ord`.
+fn json_object_text(json_object: &JsonObject<String, serde_json::Value>) -> String {
+    serde_json::to_string(json_object).unwrap_or_default()
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Str(l), Value::Str(r)) => l == r,
+            (Value::PreTokStr(l), Value::PreTokStr(r)) => l == r,
+            (Value::U64(l), Value::U64(r)) => l == r,
+            (Value::I64(l), Value::I64(r)) => l == r,
+            (Value::F64(l), Value::F64(r)) => l.to_bits() == r.to_bits(),
+            (Value::Date(l), Value::Date(r)) => l == r,
+            (Value::Facet(l), Value::Facet(r)) => l == r,
+            (Value::Bytes(l), Value::Bytes(r)) => l == r,
+            (Value::GeoPoint(l), Value::GeoPoint(r)) => geo_point_bits(l) == geo_point_bits(r),
+            (Value::IpAddr(l), Value::IpAddr(r)) => l == r,
+            (Value::DenseVector(l), Value::DenseVector(r)) => {
+                dense_vector_bits(l).eq(dense_vector_bits(r))
+            }
+            (Value::JsonObject(l), Value::JsonObject(r)) => l == r,
+            _ => false,
+        }
+    }
 }
 
 impl Eq for Value {}
+
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state);
+        match self {
+            Value::Str(text) => text.hash(state),
+            Value::PreTokStr(pre_tokenized_string) => pre_tokenized_string.hash(state),
+            Value::U64(val) => val.hash(state),
+            Value::I64(val) => val.hash(state),
+            Value::F64(val) => val.to_bits().hash(state),
+            Value::Date(date) => date.hash(state),
+            Value::Facet(facet) => facet.hash(state),
+            Value::Bytes(bytes) => bytes.hash(state),
+            Value::GeoPoint(geo_point) => geo_point_bits(geo_point).hash(state),
+            Value::IpAddr(ip_addr) => ip_addr.hash(state),
+            Value::DenseVector(vector) => {
+                vector.len().hash(state);
+                for bits in dense_vector_bits(vector) {
+                    bits.hash(state);
+                }
+            }
+            Value::JsonObject(json_object) => json_object_text(json_object).hash(state),
+        }
+    }
+}
+
 impl PartialOrd for Value {
     fn partial_cmp(&self, other: &Value) -> Option<Ordering> {
         Some(self.cmp(other))
@@ -43,10 +143,26 @@ impl Ord for Value {
             (Value::Date(l), Value::Date(r)) => l.cmp(r),
             (Value::Facet(l), Value::Facet(r)) => l.cmp(r),
             (Value::Bytes(l), Value::Bytes(r)) => l.cmp(r),
+            (Value::GeoPoint(l), Value::GeoPoint(r)) => l
+                .to_u64()
+                .cmp(&r.to_u64())
+                .then_with(|| geo_point_bits(l).cmp(&geo_point_bits(r))),
+            (Value::IpAddr(l), Value::IpAddr(r)) => l.cmp(r),
+            (Value::DenseVector(l), Value::DenseVector(r)) => {
+                let to_u64 = |val: &f32| f64_to_u64(f64::from(*val));
+                l.iter().map(to_u64).cmp(r.iter().map(to_u64))
+            }
+            (Value::JsonObject(l), Value::JsonObject(r)) => {
+                json_object_text(l).cmp(&json_object_text(r))
+            }
             (Value::F64(l), Value::F64(r)) => {
                 match (l.is_nan(), r.is_nan()) {
-                    (false, false) => l.partial_cmp(r).unwrap(), // only fail on NaN
-                    (true, true) => Ordering::Equal,
+                    // `-0.0` is ordered before `0.0`, to be consistent with equality.
+                    (false, false) => l
+                        .partial_cmp(r)
+                        .unwrap() // only fail on NaN
+                        .then_with(|| f64_to_u64(*l).cmp(&f64_to_u64(*r))),
+                    (true, true) => l.to_bits().cmp(&r.to_bits()),
                     (true, false) => Ordering::Less, // we define NaN as less than -∞
                     (false, true) => Ordering::Greater,
                 }
@@ -65,6 +181,14 @@ impl Ord for Value {
             (_, Value::Date(_)) => Ordering::Greater,
             (Value::Facet(_), _) => Ordering::Less,
             (_, Value::Facet(_)) => Ordering::Greater,
+            (Value::Bytes(_), _) => Ordering::Less,
+            (_, Value::Bytes(_)) => Ordering::Greater,
+            (Value::GeoPoint(_), _) => Ordering::Less,
+            (_, Value::GeoPoint(_)) => Ordering::Greater,
+            (Value::IpAddr(_), _) => Ordering::Less,
+            (_, Value::IpAddr(_)) => Ordering::Greater,
+            (Value::DenseVector(_), _) => Ordering::Less,
+            (_, Value::DenseVector(_)) => Ordering::Greater,
         }
     }
 }
@@ -83,6 +207,12 @@ impl Serialize for Value {
             Value::Date(ref date) => serializer.serialize_str(&date.to_rfc3339()),
             Value::Facet(ref facet) => facet.serialize(serializer),
             Value::Bytes(ref bytes) => serializer.serialize_bytes(bytes),
+            Value::GeoPoint(ref geo_point) => geo_point.serialize(serializer),
+            Value::IpAddr(ip_addr) => {
+                serializer.serialize_str(&ipv6_to_ip_addr(ip_addr).to_string())
+            }
+            Value::DenseVector(ref vector) => vector.serialize(serializer),
+            Value::JsonObject(ref json_object) => json_object.serialize(serializer),
         }
     }
 }
@@ -120,6 +250,14 @@ impl<'de> Deserialize<'de> for Value {
             fn visit_string<E>(self, v: String) -> Result<Self::Value, E> {
                 Ok(Value::Str(v))
             }
+
+            fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let json_object = JsonObject::deserialize(MapAccessDeserializer::new(map))?;
+                Ok(Value::JsonObject(json_object))
+            }
         }
 
         deserializer.deserialize_any(ValueVisitor)
@@ -192,6 +330,52 @@ impl Value {
         }
     }
 
+    /// Returns the GeoPoint-value, provided the value is of the `GeoPoint` type.
+    ///
+    /// Returns None if the value is not of type `GeoPoint`.
+    pub fn geo_point_value(&self) -> Option<GeoPoint> {
+        if let Value::GeoPoint(geo_point) = self {
+            Some(*geo_point)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the IpAddr-value, provided the value is of the `IpAddr` type.
+    ///
+    /// IPv4 addresses are returned as IPv4-mapped IPv6 addresses.
+    ///
+    /// Returns None if the value is not of type `IpAddr`.
+    pub fn ip_addr_value(&self) -> Option<Ipv6Addr> {
+        if let Value::IpAddr(ip_addr) = self {
+            Some(*ip_addr)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the dense vector, provided the value is of the `DenseVector` type.
+    ///
+    /// Returns None if the value is not of type `DenseVector`.
+    pub fn dense_vector_value(&self) -> Option<&[f32]> {
+        if let Value::DenseVector(vector) = self {
+            Some(vector)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the json object, provided the value is of the `JsonObject` type.
+    ///
+    /// Returns None if the value is not of type `JsonObject`.
+    pub fn json_object_value(&self) -> Option<&JsonObject<String, serde_json::Value>> {
+        if let Value::JsonObject(json_object) = self {
+            Some(json_object)
+        } else {
+            None
+        }
+    }
+
     /// Returns the Bytes-value, provided the value is of the `Bytes` type.
     ///
     /// Returns None if the value is not of type `Bytes`.
@@ -258,19 +442,46 @@ impl From<Vec<u8>> for Value {
     }
 }
 
+impl From<GeoPoint> for Value {
+    fn from(geo_point: GeoPoint) -> Value {
+        Value::GeoPoint(geo_point)
+    }
+}
+
+impl From<IpAddr> for Value {
+    fn from(ip_addr: IpAddr) -> Value {
+        Value::IpAddr(ip_addr_to_ipv6(ip_addr))
+    }
+}
+
+impl From<Ipv6Addr> for Value {
+    fn from(ip_addr: Ipv6Addr) -> Value {
+        Value::IpAddr(ip_addr)
+    }
+}
+
+impl From<JsonObject<String, serde_json::Value>> for Value {
+    fn from(json_object: JsonObject<String, serde_json::Value>) -> Value {
+        Value::JsonObject(json_object)
+    }
+}
+
 impl From<PreTokenizedString> for Value {
     fn from(pretokenized_string: PreTokenizedString) -> Value {
         Value::PreTokStr(pretokenized_string)
     }
 }
 
+pub(crate) use self::binary_serialize::skip_value;
+
 mod binary_serialize {
     use super::Value;
-    use crate::common::{f64_to_u64, u64_to_f64, BinarySerializable};
-    use crate::schema::Facet;
+    use crate::common::{f64_to_u64, u64_to_f64, BinarySerializable, VInt};
+    use crate::schema::{Facet, GeoPoint};
     use crate::tokenizer::PreTokenizedString;
     use chrono::{TimeZone, Utc};
     use std::io::{self, Read, Write};
+    use std::net::Ipv6Addr;
 
     const TEXT_CODE: u8 = 0;
     const U64_CODE: u8 = 1;
@@ -280,10 +491,53 @@ mod binary_serialize {
     const DATE_CODE: u8 = 5;
     const F64_CODE: u8 = 6;
     const EXT_CODE: u8 = 7;
+    const GEO_POINT_CODE: u8 = 8;
+    const IP_ADDR_CODE: u8 = 9;
+    const DENSE_VECTOR_CODE: u8 = 10;
 
     // extended types
 
     const TOK_STR_CODE: u8 = 0;
+    const JSON_OBJECT_CODE: u8 = 1;
+
+    /// Advances `bytes` past a serialized `Value`, without decoding it.
+    pub(crate) fn skip_value(bytes: &mut &[u8]) -> io::Result<()> {
+        let type_code = u8::deserialize(bytes)?;
+        let num_bytes = match type_code {
+            TEXT_CODE | HIERARCHICAL_FACET_CODE | BYTES_CODE => {
+                VInt::deserialize(bytes)?.val() as usize
+            }
+            U64_CODE | I64_CODE | F64_CODE | DATE_CODE => 8,
+            GEO_POINT_CODE | IP_ADDR_CODE => 16,
+            DENSE_VECTOR_CODE => VInt::deserialize(bytes)?.val() as usize * 4,
+            EXT_CODE => match u8::deserialize(bytes)? {
+                TOK_STR_CODE | JSON_OBJECT_CODE => VInt::deserialize(bytes)?.val() as usize,
+                ext_type_code => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!(
+                            "No extened field type is associated with code {:?}",
+                            ext_type_code
+                        ),
+                    ))
+                }
+            },
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("No field type is associated with code {:?}", type_code),
+                ))
+            }
+        };
+        if bytes.len() < num_bytes {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "Serialized value is truncated.",
+            ));
+        }
+        *bytes = &bytes[num_bytes..];
+        Ok(())
+    }
 
     impl BinarySerializable for Value {
         fn serialize<W: Write>(&self, writer: &mut W) -> io::Result<()> {
@@ -328,6 +582,31 @@ mod binary_serialize {
                     BYTES_CODE.serialize(writer)?;
                     bytes.serialize(writer)
                 }
+                Value::GeoPoint(ref geo_point) => {
+                    GEO_POINT_CODE.serialize(writer)?;
+                    f64_to_u64(geo_point.lat).serialize(writer)?;
+                    f64_to_u64(geo_point.lon).serialize(writer)
+                }
+                Value::IpAddr(ref ip_addr) => {
+                    IP_ADDR_CODE.serialize(writer)?;
+                    writer.write_all(&ip_addr.octets())
+                }
+                Value::DenseVector(ref vector) => {
+                    DENSE_VECTOR_CODE.serialize(writer)?;
+                    vector.serialize(writer)
+                }
+                Value::JsonObject(ref json_object) => {
+                    EXT_CODE.serialize(writer)?;
+                    JSON_OBJECT_CODE.serialize(writer)?;
+                    if let Ok(text) = serde_json::to_string(json_object) {
+                        text.serialize(writer)
+                    } else {
+                        Err(io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            "Failed to dump Value::JsonObject(_) to json.",
+                        ))
+                    }
+                }
             }
         }
         fn deserialize<R: Read>(reader: &mut R) -> io::Result<Self> {
@@ -355,6 +634,17 @@ mod binary_serialize {
                 }
                 HIERARCHICAL_FACET_CODE => Ok(Value::Facet(Facet::deserialize(reader)?)),
                 BYTES_CODE => Ok(Value::Bytes(Vec::<u8>::deserialize(reader)?)),
+                GEO_POINT_CODE => {
+                    let lat = u64_to_f64(u64::deserialize(reader)?);
+                    let lon = u64_to_f64(u64::deserialize(reader)?);
+                    Ok(Value::GeoPoint(GeoPoint::new(lat, lon)))
+                }
+                IP_ADDR_CODE => {
+                    let mut octets = [0u8; 16];
+                    reader.read_exact(&mut octets)?;
+                    Ok(Value::IpAddr(Ipv6Addr::from(octets)))
+                }
+                DENSE_VECTOR_CODE => Ok(Value::DenseVector(Vec::<f32>::deserialize(reader)?)),
                 EXT_CODE => {
                     let ext_type_code = u8::deserialize(reader)?;
                     match ext_type_code {
@@ -370,6 +660,17 @@ mod binary_serialize {
                                 ))
                             }
                         }
+                        JSON_OBJECT_CODE => {
+                            let str_val = String::deserialize(reader)?;
+                            if let Ok(json_object) = serde_json::from_str(&str_val) {
+                                Ok(Value::JsonObject(json_object))
+                            } else {
+                                Err(io::Error::new(
+                                    io::ErrorKind::InvalidData,
+                                    "Failed to parse string data as Value::JsonObject(_).",
+                                ))
+                            }
+                        }
                         _ => Err(io::Error::new(
                             io::ErrorKind::InvalidData,
                             format!(
@@ -390,8 +691,11 @@ mod binary_serialize {
 
 #[cfg(test)]
 mod tests {
-    use super::Value;
+    use super::{ip_addr_to_ipv6, ipv6_to_ip_addr, Value};
+    use crate::common::BinarySerializable;
+    use crate::schema::GeoPoint;
     use crate::DateTime;
+    use std::net::{IpAddr, Ipv4Addr};
     use std::str::FromStr;
 
     #[test]
@@ -400,4 +704,84 @@ mod tests {
         let serialized_value_json = serde_json::to_string_pretty(&value).unwrap();
         assert_eq!(serialized_value_json, r#""1996-12-20T00:39:57+00:00""#);
     }
+
+    #[test]
+    fn test_serialize_geo_point() {
+        let value = Value::GeoPoint(GeoPoint::new(48.8566, 2.3522));
+        let serialized_value_json = serde_json::to_string(&value).unwrap();
+        assert_eq!(serialized_value_json, r#"{"lat":48.8566,"lon":2.3522}"#);
+        let mut buffer = Vec::new();
+        value.serialize(&mut buffer).unwrap();
+        assert_eq!(Value::deserialize(&mut &buffer[..]).unwrap(), value);
+    }
+
+    #[test]
+    fn test_serialize_ip_addr() {
+        let ipv4_addr = IpAddr::from(Ipv4Addr::new(192, 168, 0, 1));
+        let value = Value::from(ipv4_addr);
+        assert_eq!(
+            value.ip_addr_value(),
+            Some(Ipv4Addr::new(192, 168, 0, 1).to_ipv6_mapped())
+        );
+        assert_eq!(serde_json::to_string(&value).unwrap(), r#""192.168.0.1""#);
+        let ipv6_value = Value::from(IpAddr::from_str("2001:db8::1").unwrap());
+        assert_eq!(
+            serde_json::to_string(&ipv6_value).unwrap(),
+            r#""2001:db8::1""#
+        );
+        let mut buffer = Vec::new();
+        value.serialize(&mut buffer).unwrap();
+        assert_eq!(Value::deserialize(&mut &buffer[..]).unwrap(), value);
+        assert_eq!(ipv6_to_ip_addr(ip_addr_to_ipv6(ipv4_addr)), ipv4_addr);
+    }
+
+    #[test]
+    fn test_serialize_dense_vector() {
+        let value = Value::DenseVector(vec![0.5, -1.0, 2.0]);
+        assert_eq!(value.dense_vector_value(), Some(&[0.5f32, -1.0, 2.0][..]));
+        assert_eq!(serde_json::to_string(&value).unwrap(), "[0.5,-1.0,2.0]");
+        let mut buffer = Vec::new();
+        value.serialize(&mut buffer).unwrap();
+        assert_eq!(Value::deserialize(&mut &buffer[..]).unwrap(), value);
+        assert!(Value::DenseVector(vec![0.5, -1.0]) < Value::DenseVector(vec![0.5, 1.0]));
+    }
+
+    #[test]
+    fn test_serialize_json_object() {
+        let json_object = serde_json::json!({"author": {"name": "Alice"}, "year": 2021});
+        let value = Value::from(json_object.as_object().unwrap().clone());
+        assert_eq!(value.json_object_value().unwrap()["year"], 2021);
+        assert_eq!(
+            serde_json::to_string(&value).unwrap(),
+            r#"{"author":{"name":"Alice"},"year":2021}"#
+        );
+        let mut buffer = Vec::new();
+        value.serialize(&mut buffer).unwrap();
+        assert_eq!(Value::deserialize(&mut &buffer[..]).unwrap(), value);
+    }
+
+    #[test]
+    fn test_value_eq_and_hash_floats_by_bits() {
+        use std::collections::HashSet;
+        assert_eq!(Value::F64(f64::NAN), Value::F64(f64::NAN));
+        assert_ne!(Value::F64(0.0), Value::F64(-0.0));
+        assert!(Value::F64(-0.0) < Value::F64(0.0));
+        assert_ne!(Value::F64(1.0), Value::U64(1));
+        assert_eq!(
+            Value::DenseVector(vec![f32::NAN, 1.0]),
+            Value::DenseVector(vec![f32::NAN, 1.0])
+        );
+        let values: HashSet<Value> = vec![
+            Value::F64(f64::NAN),
+            Value::F64(f64::NAN),
+            Value::F64(0.0),
+            Value::F64(-0.0),
+            Value::GeoPoint(GeoPoint::new(48.8, 2.3)),
+            Value::GeoPoint(GeoPoint::new(48.8, 2.3)),
+            Value::Str("a".to_string()),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(values.len(), 5);
+    }
 }
@@ -1,17 +1,91 @@
+//! Snippet generation and highlighting.
+//!
+//! See [`SnippetGenerator`](./struct.SnippetGenerator.html).
+//!
+//! tantivy does not expose a C API. Bindings to other languages wrap the
+//! `SnippetGenerator` on their side, and can rely on
+//! [`Snippet::highlighted_bounds`](./struct.Snippet.html#method.highlighted_bounds)
+//! and [`OffsetMode::Chars`](./enum.OffsetMode.html) to get the highlighted
+//! ranges as plain offsets in the unit used by their strings.
+
 use crate::query::Query;
-use crate::schema::Field;
 use crate::schema::Value;
-use crate::tokenizer::{TextAnalyzer, Token};
+use crate::schema::{Field, FieldType, TextFieldIndexing};
+use crate::termvector::TermVectors;
+use crate::tokenizer::{BoxTokenStream, TextAnalyzer, Token};
 use crate::Searcher;
-use crate::{Document, Score};
-use htmlescape::encode_minimal;
+use crate::{DocAddress, Document, Score, TantivyError};
 use std::cmp::Ordering;
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
+use std::fmt;
 
 const DEFAULT_MAX_NUM_CHARS: usize = 150;
 
+/// Defines the unit in which offsets are expressed.
+///
+/// Rust strings are indexed by bytes, which is what tantivy uses internally.
+/// Other languages (JavaScript, Python, ...) index their strings by characters,
+/// in which case offsets should be expressed in `Chars`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OffsetMode {
+    /// Offsets are byte offsets.
+    Bytes,
+    /// Offsets are unicode scalar value (`char`) offsets.
+    ///
+    /// Converting byte offsets to char offsets is O(n) in the length of the text.
+    Chars,
+}
+
+impl Default for OffsetMode {
+    /// Offsets are byte offsets by default, as the offsets of the tokens.
+    fn default() -> OffsetMode {
+        OffsetMode::Bytes
+    }
+}
+
+/// Translates byte offsets of a text into offsets expressed in the given `OffsetMode`.
+///
+/// Translating increasing offsets only requires one pass over the text.
+struct OffsetTranslator<'a> {
+    text: &'a str,
+    offset_mode: OffsetMode,
+    byte_offset: usize,
+    char_offset: usize,
+}
+
+impl<'a> OffsetTranslator<'a> {
+    fn new(text: &'a str, offset_mode: OffsetMode) -> OffsetTranslator<'a> {
+        OffsetTranslator {
+            text,
+            offset_mode,
+            byte_offset: 0,
+            char_offset: 0,
+        }
+    }
+
+    /// `byte_offset` is required to be on a char boundary of the text.
+    fn translate(&mut self, byte_offset: usize) -> usize {
+        match self.offset_mode {
+            OffsetMode::Bytes => byte_offset,
+            OffsetMode::Chars => {
+                if byte_offset < self.byte_offset {
+                    self.byte_offset = 0;
+                    self.char_offset = 0;
+                }
+                self.char_offset += self.text[self.byte_offset..byte_offset].chars().count();
+                self.byte_offset = byte_offset;
+                self.char_offset
+            }
+        }
+    }
+}
+
+/// A highlighted part of a text, expressed as a range of byte offsets.
+///
+/// With the `serde` feature, it serializes to `{"start": 10, "stop": 20}`.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct HighlightSection {
     start: usize,
     stop: usize,
@@ -26,21 +100,86 @@ impl HighlightSection {
     pub fn bounds(&self) -> (usize, usize) {
         (self.start, self.stop)
     }
+
+    /// Returns true if the token overlaps with or directly follows
+    /// the section.
+    fn overlaps_or_touches(&self, token: &Token) -> bool {
+        token.offset_from <= self.stop && token.offset_to >= self.start
+    }
+
+    /// Extends the section so that it also covers the token.
+    fn extend(&mut self, token: &Token) {
+        self.start = self.start.min(token.offset_from);
+        self.stop = self.stop.max(token.offset_to);
+    }
+}
+
+/// A part of a text, highlighted or not, expressed as a range of byte offsets.
+///
+/// See `SnippetGenerator::text_sections`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextSection {
+    start: usize,
+    stop: usize,
+    highlighted: bool,
+}
+
+impl TextSection {
+    /// Returns the bounds of the `TextSection`.
+    pub fn bounds(&self) -> (usize, usize) {
+        (self.start, self.stop)
+    }
+
+    /// Returns true if the section is highlighted.
+    pub fn is_highlighted(&self) -> bool {
+        self.highlighted
+    }
+}
+
+/// Sorts the sections, merges the overlapping or touching ones,
+/// and clips them to `[start, stop)`, removing the empty ones.
+///
+/// Tokenizers emitting overlapping tokens (ngrams, synonyms, ...) may
+/// produce sections that overlap or come out of order.
+fn normalize_sections(
+    sections: &[HighlightSection],
+    start: usize,
+    stop: usize,
+) -> Vec<(usize, usize)> {
+    let mut bounds: Vec<(usize, usize)> = sections
+        .iter()
+        .map(|section| (section.start.max(start), section.stop.min(stop)))
+        .filter(|(section_start, section_stop)| section_start < section_stop)
+        .collect();
+    bounds.sort();
+    let mut merged: Vec<(usize, usize)> = Vec::with_capacity(bounds.len());
+    for (section_start, section_stop) in bounds {
+        match merged.last_mut() {
+            Some(last) if section_start <= last.1 => {
+                last.1 = last.1.max(section_stop);
+            }
+            _ => {
+                merged.push((section_start, section_stop));
+            }
+        }
+    }
+    merged
 }
 
 #[derive(Debug)]
-pub struct FragmentCandidate {
+struct FragmentCandidate {
     score: Score,
     start_offset: usize,
     stop_offset: usize,
-    num_chars: usize,
     highlighted: Vec<HighlightSection>,
+    previous_token_highlighted: bool,
 }
 
 impl FragmentCandidate {
     /// Create a basic `FragmentCandidate`
     ///
-    /// `score`, `num_chars` are set to 0
+    /// `score` is set to 0
     /// and `highlighted` is set to empty vec
     /// stop_offset is set to start_offset, which is taken as a param.
     fn new(start_offset: usize) -> FragmentCandidate {
@@ -48,8 +187,8 @@ impl FragmentCandidate {
             score: 0.0,
             start_offset,
             stop_offset: start_offset,
-            num_chars: 0,
             highlighted: vec![],
+            previous_token_highlighted: false,
         }
     }
 
@@ -58,23 +197,52 @@ impl FragmentCandidate {
     /// taking the token and terms, the token is added to the fragment.
     /// if the token is one of the terms, the score
     /// and highlighted fields are updated in the fragment.
-    fn try_add_token(&mut self, token: &Token, terms: &BTreeMap<String, Score>) {
-        self.stop_offset = token.offset_to;
+    ///
+    /// If the token overlaps with the last highlighted section, or if
+    /// `merge_adjacent` is true and the previous token of the fragment
+    /// was also highlighted, the last highlighted section is extended
+    /// to cover this token instead of pushing a new section.
+    fn try_add_token(
+        &mut self,
+        token: &Token,
+        terms: &BTreeMap<String, Score>,
+        merge_adjacent: bool,
+    ) {
+        self.stop_offset = self.stop_offset.max(token.offset_to);
 
         if let Some(&score) = terms.get(&token.text.to_lowercase()) {
             self.score += score;
-            self.highlighted
-                .push(HighlightSection::new(token.offset_from, token.offset_to));
+            match self.highlighted.last_mut() {
+                Some(last)
+                    if last.overlaps_or_touches(token)
+                        || (merge_adjacent && self.previous_token_highlighted) =>
+                {
+                    last.extend(token);
+                }
+                _ => {
+                    self.highlighted
+                        .push(HighlightSection::new(token.offset_from, token.offset_to));
+                }
+            }
+            self.previous_token_highlighted = true;
+        } else {
+            self.previous_token_highlighted = false;
         }
     }
 }
 
 /// `Snippet`
 /// Contains a fragment of a document, and some highlighed parts inside it.
+///
+/// With the `serde` feature, it serializes to a JSON object with camelCase keys:
+/// `{"fragments": "...", "highlighted": [{"start": 0, "stop": 4}], "startOffset": 0}`.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct Snippet {
     fragments: String,
     highlighted: Vec<HighlightSection>,
+    start_offset: usize,
 }
 
 const HIGHLIGHTEN_PREFIX: &str = "<b>";
@@ -86,25 +254,23 @@ impl Snippet {
         Snippet {
             fragments: String::new(),
             highlighted: Vec::new(),
+            start_offset: 0,
         }
     }
 
     /// Returns a hignlightned html from the `Snippet`.
     pub fn to_html(&self) -> String {
-        let mut html = String::new();
-        let mut start_from: usize = 0;
+        self.to_html_with_tags(HIGHLIGHTEN_PREFIX, HIGHLIGHTEN_POSTFIX)
+    }
 
-        for item in self.highlighted.iter() {
-            html.push_str(&encode_minimal(&self.fragments[start_from..item.start]));
-            html.push_str(HIGHLIGHTEN_PREFIX);
-            html.push_str(&encode_minimal(&self.fragments[item.start..item.stop]));
-            html.push_str(HIGHLIGHTEN_POSTFIX);
-            start_from = item.stop;
-        }
-        html.push_str(&encode_minimal(
-            &self.fragments[start_from..self.fragments.len()],
-        ));
-        html
+    /// Returns a hignlightned html from the `Snippet`, using `open_tag` and
+    /// `close_tag` to surround the highlighted parts (e.g. `<mark>` and `</mark>`).
+    ///
+    /// The tags are not escaped.
+    pub fn to_html_with_tags(&self, open_tag: &str, close_tag: &str) -> String {
+        // The sections of a snippet are always within the bounds of its fragment.
+        render_html(&self.fragments, &self.highlighted, open_tag, close_tag)
+            .expect("Snippet highlighted sections should be valid")
     }
 
     /// Returns a fragment from the `Snippet`.
@@ -113,9 +279,213 @@ impl Snippet {
     }
 
     /// Returns a list of higlighted positions from the `Snippet`.
+    ///
+    /// The positions are relative to the fragment.
     pub fn highlighted(&self) -> &[HighlightSection] {
         &self.highlighted
     }
+
+    /// Returns the bounds of the highlighted positions, relative to the
+    /// fragment, and expressed in the given `OffsetMode`.
+    pub fn highlighted_bounds(&self, offset_mode: OffsetMode) -> Vec<(usize, usize)> {
+        let mut translator = OffsetTranslator::new(&self.fragments, offset_mode);
+        self.highlighted
+            .iter()
+            .map(|section| {
+                let start = translator.translate(section.start);
+                let stop = translator.translate(section.stop);
+                (start, stop)
+            })
+            .collect()
+    }
+
+    /// Returns the byte bounds `(start, stop)` of the fragment
+    /// within the original text.
+    ///
+    /// An empty snippet has bounds `(0, 0)`.
+    pub fn fragment_bounds(&self) -> (usize, usize) {
+        (self.start_offset, self.start_offset + self.fragments.len())
+    }
+}
+
+/// Iterator over the `HighlightSection`s of a text.
+///
+/// The sections are computed lazily as the underlying token stream
+/// is consumed. See `SnippetGenerator::highlight_sections`.
+///
+/// Overlapping tokens are merged into a single section. Since the sections
+/// are emitted on the fly, they are only guaranteed to be sorted if the
+/// tokenizer emits its tokens in order.
+///
+/// By default, the bounds of the sections are byte offsets.
+/// See `.with_offset_mode(...)` to express them in chars.
+pub struct HighlightSections<'a> {
+    token_stream: BoxTokenStream<'a>,
+    terms: &'a BTreeMap<String, Score>,
+    merge_adjacent: bool,
+    pending: Option<HighlightSection>,
+    translator: OffsetTranslator<'a>,
+    // If true, the iteration stops at the first invalid token, and its error is kept.
+    fail_on_invalid_token: bool,
+    invalid_token_error: Option<TantivyError>,
+}
+
+impl<'a> HighlightSections<'a> {
+    /// Sets the unit in which the bounds of the emitted sections are expressed.
+    pub fn with_offset_mode(mut self, offset_mode: OffsetMode) -> HighlightSections<'a> {
+        self.translator.offset_mode = offset_mode;
+        self
+    }
+
+    fn translate(&mut self, section: HighlightSection) -> HighlightSection {
+        let start = self.translator.translate(section.start);
+        let stop = self.translator.translate(section.stop);
+        HighlightSection::new(start, stop)
+    }
+
+    fn next_byte_section(&mut self) -> Option<HighlightSection> {
+        while let Some(token) = self.token_stream.next() {
+            if !is_valid_token(token, self.translator.text) {
+                if self.fail_on_invalid_token {
+                    self.invalid_token_error =
+                        Some(invalid_token_error(token, self.translator.text));
+                    return None;
+                }
+                continue;
+            }
+            if !self.terms.contains_key(&token.text.to_lowercase()) {
+                // A non-matching token ends the pending section, if any.
+                if let Some(section) = self.pending.take() {
+                    return Some(section);
+                }
+                continue;
+            }
+            match self.pending.as_mut() {
+                Some(section) if self.merge_adjacent || section.overlaps_or_touches(token) => {
+                    section.extend(token);
+                }
+                _ => {
+                    let section = HighlightSection::new(token.offset_from, token.offset_to);
+                    if let Some(previous) = self.pending.replace(section) {
+                        return Some(previous);
+                    }
+                }
+            }
+        }
+        self.pending.take()
+    }
+}
+
+impl<'a> Iterator for HighlightSections<'a> {
+    type Item = HighlightSection;
+
+    fn next(&mut self) -> Option<HighlightSection> {
+        let section = self.next_byte_section()?;
+        Some(self.translate(section))
+    }
+}
+
+fn escape_html<W: fmt::Write>(text: &str, writer: &mut W) -> fmt::Result {
+    let mut start = 0;
+    for (offset, c) in text.char_indices() {
+        let entity = match c {
+            '"' => "&quot;",
+            '&' => "&amp;",
+            '\'' => "&#x27;",
+            '<' => "&lt;",
+            '>' => "&gt;",
+            _ => continue,
+        };
+        writer.write_str(&text[start..offset])?;
+        writer.write_str(entity)?;
+        start = offset + 1;
+    }
+    writer.write_str(&text[start..])
+}
+
+/// Renders `text` as html, surrounding the highlighted `sections`
+/// with `open_tag` and `close_tag`.
+///
+/// All of the text (highlighted or not) is html escaped. The tags are not.
+///
+/// Returns an error if the sections are not sorted, overlap,
+/// or are not within the bounds of `text`.
+pub fn render_html(
+    text: &str,
+    sections: &[HighlightSection],
+    open_tag: &str,
+    close_tag: &str,
+) -> crate::Result<String> {
+    let mut html = String::with_capacity(text.len());
+    render_html_to(text, sections, open_tag, close_tag, &mut html)?;
+    Ok(html)
+}
+
+/// Same as [`render_html`](./fn.render_html.html), but writes the html into
+/// an existing `fmt::Write`.
+///
+/// Nothing is written if the sections are invalid.
+pub fn render_html_to<W: fmt::Write>(
+    text: &str,
+    sections: &[HighlightSection],
+    open_tag: &str,
+    close_tag: &str,
+    writer: &mut W,
+) -> crate::Result<()> {
+    let mut start_from = 0;
+    for section in sections {
+        if section.start < start_from || section.start > section.stop {
+            return Err(TantivyError::InvalidArgument(format!(
+                "Highlight section {:?} is not sorted or overlaps with the previous one",
+                section.bounds()
+            )));
+        }
+        if section.stop > text.len()
+            || !text.is_char_boundary(section.start)
+            || !text.is_char_boundary(section.stop)
+        {
+            return Err(TantivyError::InvalidArgument(format!(
+                "Highlight section {:?} is not within the bounds of the text (len={})",
+                section.bounds(),
+                text.len()
+            )));
+        }
+        start_from = section.stop;
+    }
+    let render = |writer: &mut W| -> fmt::Result {
+        let mut start_from = 0;
+        for section in sections {
+            escape_html(&text[start_from..section.start], writer)?;
+            writer.write_str(open_tag)?;
+            escape_html(&text[section.start..section.stop], writer)?;
+            writer.write_str(close_tag)?;
+            start_from = section.stop;
+        }
+        escape_html(&text[start_from..], writer)
+    };
+    render(writer)
+        .map_err(|_| TantivyError::SystemError("Failed to write highlighted html".to_string()))
+}
+
+/// Returns true if the offsets of the token delimit a part of `text`.
+///
+/// The other tokens are ignored: they may be emitted by a buggy tokenizer,
+/// or come from term vectors that do not match the stored text.
+fn is_valid_token(token: &Token, text: &str) -> bool {
+    token.offset_from <= token.offset_to
+        && token.offset_to <= text.len()
+        && text.is_char_boundary(token.offset_from)
+        && text.is_char_boundary(token.offset_to)
+}
+
+fn invalid_token_error(token: &Token, text: &str) -> TantivyError {
+    TantivyError::InvalidArgument(format!(
+        "Token {:?} has offsets {}..{}, which do not delimit a part of the text (len={})",
+        token.text,
+        token.offset_from,
+        token.offset_to,
+        text.len()
+    ))
 }
 
 /// Returns a non-empty list of "good" fragments.
@@ -139,29 +509,132 @@ impl Snippet {
 ///
 /// Fragments must be valid in the sense that `&text[fragment.start..fragment.stop]`\
 /// has to be a valid string.
+///
+/// If `merge_adjacent` is true, matching tokens that directly follow each other
+/// in the token stream are reported as a single highlighted section.
 fn search_fragments<'a>(
     tokenizer: &TextAnalyzer,
     text: &'a str,
     terms: &BTreeMap<String, Score>,
     max_num_chars: usize,
+    merge_adjacent: bool,
 ) -> Vec<FragmentCandidate> {
     let mut token_stream = tokenizer.token_stream(text);
-    let mut fragment = FragmentCandidate::new(0);
-    let mut fragments: Vec<FragmentCandidate> = vec![];
+    let mut fragment_search = FragmentSearch::new(terms, max_num_chars, merge_adjacent);
+    while let Some(next) = token_stream.next() {
+        if is_valid_token(next, text) {
+            fragment_search.add_token(next);
+        }
+    }
+    fragment_search.fragments()
+}
+
+/// Same as `search_fragments`, except that it fails with `TantivyError::InvalidArgument`
+/// at the first token whose offsets do not delimit a part of `text`.
+fn try_search_fragments(
+    tokenizer: &TextAnalyzer,
+    text: &str,
+    terms: &BTreeMap<String, Score>,
+    max_num_chars: usize,
+    merge_adjacent: bool,
+) -> crate::Result<Vec<FragmentCandidate>> {
+    let mut token_stream = tokenizer.token_stream(text);
+    let mut fragment_search = FragmentSearch::new(terms, max_num_chars, merge_adjacent);
     while let Some(next) = token_stream.next() {
-        if (next.offset_to - fragment.start_offset) > max_num_chars {
+        if !is_valid_token(next, text) {
+            return Err(invalid_token_error(next, text));
+        }
+        fragment_search.add_token(next);
+    }
+    Ok(fragment_search.fragments())
+}
+
+/// Same as `search_fragments`, except that the tokens are read from the term vectors
+/// of the text rather than obtained by tokenizing it.
+///
+/// `value_starts` are the offsets of the values of the field in the text, in the
+/// text as it was indexed, i.e. without the separators inserted between the values.
+fn search_fragments_in_term_vectors(
+    term_vectors: &TermVectors,
+    value_starts: &[usize],
+    text: &str,
+    terms: &BTreeMap<String, Score>,
+    max_num_chars: usize,
+    merge_adjacent: bool,
+) -> Vec<FragmentCandidate> {
+    let mut tokens: Vec<Token> = term_vectors
+        .iter()
+        .flat_map(|(term, occurrences)| {
+            occurrences.iter().map(move |occurrence| {
+                // Values are joined with a space in the text.
+                let num_separators = value_starts
+                    .iter()
+                    .skip(1)
+                    .take_while(|&&value_start| value_start <= occurrence.start_offset)
+                    .count();
+                Token {
+                    offset_from: occurrence.start_offset + num_separators,
+                    offset_to: occurrence.end_offset + num_separators,
+                    position: occurrence.position as usize,
+                    text: term.to_string(),
+                    position_length: 1,
+                }
+            })
+        })
+        .filter(|token| is_valid_token(token, text))
+        .collect();
+    tokens.sort_by_key(|token| (token.offset_from, token.position));
+    let mut fragment_search = FragmentSearch::new(terms, max_num_chars, merge_adjacent);
+    for token in &tokens {
+        fragment_search.add_token(token);
+    }
+    fragment_search.fragments()
+}
+
+/// Groups a sequence of tokens into `FragmentCandidate`s of at most `max_num_chars`.
+struct FragmentSearch<'a> {
+    terms: &'a BTreeMap<String, Score>,
+    max_num_chars: usize,
+    merge_adjacent: bool,
+    fragment: FragmentCandidate,
+    fragments: Vec<FragmentCandidate>,
+}
+
+impl<'a> FragmentSearch<'a> {
+    fn new(
+        terms: &'a BTreeMap<String, Score>,
+        max_num_chars: usize,
+        merge_adjacent: bool,
+    ) -> FragmentSearch<'a> {
+        FragmentSearch {
+            terms,
+            max_num_chars,
+            merge_adjacent,
+            fragment: FragmentCandidate::new(0),
+            fragments: vec![],
+        }
+    }
+
+    fn add_token(&mut self, token: &Token) {
+        if token.offset_to.saturating_sub(self.fragment.start_offset) > self.max_num_chars {
+            let fragment = std::mem::replace(
+                &mut self.fragment,
+                FragmentCandidate::new(token.offset_from),
+            );
             if fragment.score > 0.0 {
-                fragments.push(fragment)
+                self.fragments.push(fragment)
             };
-            fragment = FragmentCandidate::new(next.offset_from);
         }
-        fragment.try_add_token(next, &terms);
-    }
-    if fragment.score > 0.0 {
-        fragments.push(fragment)
+        self.fragment
+            .try_add_token(token, self.terms, self.merge_adjacent);
     }
 
-    fragments
+    fn fragments(mut self) -> Vec<FragmentCandidate> {
+        if self.fragment.score > 0.0 {
+            self.fragments.push(self.fragment)
+        }
+        self.fragments
+    }
 }
 
 /// Returns a Snippet
@@ -182,27 +655,25 @@ fn select_best_fragment_combination(fragments: &[FragmentCandidate], text: &str)
     });
     if let Some(fragment) = best_fragment_opt {
         let fragment_text = &text[fragment.start_offset..fragment.stop_offset];
-        let highlighted = fragment
-            .highlighted
-            .iter()
-            .map(|item| {
-                HighlightSection::new(
-                    item.start - fragment.start_offset,
-                    item.stop - fragment.start_offset,
-                )
-            })
-            .collect();
+        let highlighted = normalize_sections(
+            &fragment.highlighted,
+            fragment.start_offset,
+            fragment.stop_offset,
+        )
+        .into_iter()
+        .map(|(start, stop)| {
+            HighlightSection::new(start - fragment.start_offset, stop - fragment.start_offset)
+        })
+        .collect();
         Snippet {
             fragments: fragment_text.to_string(),
             highlighted,
+            start_offset: fragment.start_offset,
         }
     } else {
         // when there no fragments to chose from,
         // for now create a empty snippet
-        Snippet {
-            fragments: String::new(),
-            highlighted: vec![],
-        }
+        Snippet::empty()
     }
 }
 
@@ -251,7 +722,9 @@ pub struct SnippetGenerator {
     terms_text: BTreeMap<String, Score>,
     tokenizer: TextAnalyzer,
     field: Field,
+    term_vectors: bool,
     max_num_chars: usize,
+    merge_adjacent: bool,
 }
 
 impl SnippetGenerator {
@@ -275,11 +748,20 @@ impl SnippetGenerator {
             }
         }
         let tokenizer = searcher.index().tokenizer_for_field(field)?;
+        let term_vectors = match searcher.schema().get_field_entry(field).field_type() {
+            FieldType::Str(text_options) => text_options
+                .get_indexing_options()
+                .map(TextFieldIndexing::term_vectors)
+                .unwrap_or(false),
+            _ => false,
+        };
         Ok(SnippetGenerator {
             terms_text,
             tokenizer,
             field,
+            term_vectors,
             max_num_chars: DEFAULT_MAX_NUM_CHARS,
+            merge_adjacent: true,
         })
     }
 
@@ -288,11 +770,92 @@ impl SnippetGenerator {
         self.max_num_chars = max_num_chars;
     }
 
+    /// Sets whether matching tokens that are adjacent in the token stream
+    /// should be highlighted as a single section. (e.g. `<b>old man</b>`
+    /// rather than `<b>old</b> <b>man</b>`)
+    ///
+    /// Defaults to true.
+    pub fn set_merge_adjacent_highlights(&mut self, merge_adjacent: bool) {
+        self.merge_adjacent = merge_adjacent;
+    }
+
     #[cfg(test)]
-    pub fn terms_text(&self) -> &BTreeMap<String, Score> {
+    pub(crate) fn terms_text(&self) -> &BTreeMap<String, Score> {
         &self.terms_text
     }
 
+    /// Returns an iterator over the highlighted sections of the entire text.
+    ///
+    /// Contrary to `.snippet(...)`, no fragment is selected, and the bounds
+    /// of the sections are relative to `text`. The sections are computed
+    /// lazily, which makes it possible to highlight a long text without
+    /// materializing all of its sections.
+    pub fn highlight_sections<'a>(&'a self, text: &'a str) -> HighlightSections<'a> {
+        HighlightSections {
+            token_stream: self.tokenizer.token_stream(text),
+            terms: &self.terms_text,
+            merge_adjacent: self.merge_adjacent,
+            pending: None,
+            translator: OffsetTranslator::new(text, OffsetMode::Bytes),
+            fail_on_invalid_token: false,
+            invalid_token_error: None,
+        }
+    }
+
+    /// Same as `.highlight_sections(text).with_offset_mode(offset_mode)`, except that
+    /// the sections are collected, and that it fails with `TantivyError::InvalidArgument`
+    /// if the tokenizer emits a token whose offsets do not delimit a part of `text`,
+    /// instead of ignoring it.
+    pub fn try_highlight_sections(
+        &self,
+        text: &str,
+        offset_mode: OffsetMode,
+    ) -> crate::Result<Vec<HighlightSection>> {
+        let mut highlight_sections = self.highlight_sections(text).with_offset_mode(offset_mode);
+        highlight_sections.fail_on_invalid_token = true;
+        let sections: Vec<HighlightSection> = highlight_sections.by_ref().collect();
+        match highlight_sections.invalid_token_error {
+            Some(err) => Err(err),
+            None => Ok(sections),
+        }
+    }
+
+    /// Splits the entire text into sections, alternating between the highlighted
+    /// sections and the gaps between them.
+    ///
+    /// The sections are the same as the ones of `.highlight_sections(...)`, sorted
+    /// and merged when they overlap. Together with the gaps, they cover `text`
+    /// without overlapping, which makes it easy to render the highlighted text
+    /// with a custom markup.
+    pub fn text_sections(&self, text: &str) -> Vec<TextSection> {
+        let highlighted: Vec<HighlightSection> = self.highlight_sections(text).collect();
+        let mut text_sections = Vec::new();
+        let mut start = 0;
+        for (section_start, section_stop) in normalize_sections(&highlighted, 0, text.len()) {
+            if start < section_start {
+                text_sections.push(TextSection {
+                    start,
+                    stop: section_start,
+                    highlighted: false,
+                });
+            }
+            text_sections.push(TextSection {
+                start: section_start,
+                stop: section_stop,
+                highlighted: true,
+            });
+            start = section_stop;
+        }
+        if start < text.len() {
+            text_sections.push(TextSection {
+                start,
+                stop: text.len(),
+                highlighted: false,
+            });
+        }
+        text_sections
+    }
+
     /// Generates a snippet for the given `Document`.
     ///
     /// This method extract the text associated to the `SnippetGenerator`'s field
@@ -306,23 +869,94 @@ impl SnippetGenerator {
         self.snippet(&text)
     }
 
+    /// Generates a snippet for the document at the given address.
+    ///
+    /// The text of the field is read from the store. If the field stores term vectors,
+    /// the tokens of the text are read from its term vectors instead of tokenizing it again,
+    /// which is cheaper for long texts.
+    pub fn snippet_from_doc_address(
+        &self,
+        searcher: &Searcher,
+        doc_address: DocAddress,
+    ) -> crate::Result<Snippet> {
+        let doc = searcher.doc_projected(doc_address, &[self.field])?;
+        // The offsets of pre-tokenized values can not be mapped back to the text.
+        if !self.term_vectors || doc.get_all(self.field).any(|value| value.text().is_none()) {
+            return Ok(self.snippet_from_doc(&doc));
+        }
+        let term_vectors = searcher.term_vectors(self.field, doc_address)?;
+        if term_vectors.is_empty() {
+            // The segment may have been written before term vectors were introduced.
+            return Ok(self.snippet_from_doc(&doc));
+        }
+        let values: Vec<&str> = doc.get_all(self.field).flat_map(Value::text).collect();
+        let mut value_starts = Vec::with_capacity(values.len());
+        let mut value_start = 0;
+        for value in &values {
+            value_starts.push(value_start);
+            value_start += value.len();
+        }
+        let text = values.join(" ");
+        let fragment_candidates = search_fragments_in_term_vectors(
+            &term_vectors,
+            &value_starts,
+            &text,
+            &self.terms_text,
+            self.max_num_chars,
+            self.merge_adjacent,
+        );
+        Ok(select_best_fragment_combination(
+            &fragment_candidates[..],
+            &text,
+        ))
+    }
+
     /// Generates a snippet for the given text.
     pub fn snippet(&self, text: &str) -> Snippet {
-        let fragment_candidates =
-            search_fragments(&self.tokenizer, &text, &self.terms_text, self.max_num_chars);
+        let fragment_candidates = search_fragments(
+            &self.tokenizer,
+            &text,
+            &self.terms_text,
+            self.max_num_chars,
+            self.merge_adjacent,
+        );
         select_best_fragment_combination(&fragment_candidates[..], &text)
     }
+
+    /// Same as `.snippet(...)`, except that it fails with `TantivyError::InvalidArgument`
+    /// if the tokenizer emits a token whose offsets do not delimit a part of `text`,
+    /// instead of ignoring it.
+    pub fn try_snippet(&self, text: &str) -> crate::Result<Snippet> {
+        let fragment_candidates = try_search_fragments(
+            &self.tokenizer,
+            text,
+            &self.terms_text,
+            self.max_num_chars,
+            self.merge_adjacent,
+        )?;
+        Ok(select_best_fragment_combination(
+            &fragment_candidates[..],
+            text,
+        ))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{search_fragments, select_best_fragment_combination};
+    use super::{
+        render_html, render_html_to, search_fragments, select_best_fragment_combination,
+        FragmentCandidate, HighlightSection, OffsetMode, TextSection,
+    };
     use crate::query::QueryParser;
     use crate::schema::{IndexRecordOption, Schema, TextFieldIndexing, TextOptions, TEXT};
-    use crate::tokenizer::SimpleTokenizer;
-    use crate::Index;
+    use crate::tokenizer::{
+        BoxTokenStream, NgramTokenizer, PreTokenizedStream, PreTokenizedString, SimpleTokenizer,
+        StopWordFilter, TextAnalyzer, Token, Tokenizer,
+    };
     use crate::SnippetGenerator;
+    use crate::{DocAddress, Index};
     use maplit::btreemap;
+    use proptest::prelude::*;
     use std::collections::BTreeMap;
     use std::iter::Iterator;
 
@@ -346,7 +980,8 @@ Survey in 2016, 2017, and 2018."#;
             String::from("rust") => 1.0,
             String::from("language") => 0.9
         };
-        let fragments = search_fragments(&From::from(SimpleTokenizer), TEST_TEXT, &terms, 100);
+        let fragments =
+            search_fragments(&From::from(SimpleTokenizer), TEST_TEXT, &terms, 100, true);
         assert_eq!(fragments.len(), 7);
         {
             let first = &fragments[0];
@@ -366,6 +1001,25 @@ Survey in 2016, 2017, and 2018."#;
         )
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_snippet_serde() {
+        let terms = btreemap! { String::from("rust") => 1.0 };
+        let fragments = search_fragments(
+            &From::from(SimpleTokenizer),
+            "rust is fun",
+            &terms,
+            100,
+            true,
+        );
+        let snippet = select_best_fragment_combination(&fragments[..], "rust is fun");
+        let snippet_json = serde_json::to_string(&snippet).unwrap();
+        assert_eq!(
+            snippet_json,
+            r#"{"fragments":"rust is fun","highlighted":[{"start":0,"stop":4}],"startOffset":0}"#
+        );
+    }
+
     #[test]
     fn test_snippet_scored_fragment() {
         {
@@ -373,7 +1027,8 @@ Survey in 2016, 2017, and 2018."#;
                 String::from("rust") =>1.0,
                 String::from("language") => 0.9
             };
-            let fragments = search_fragments(&From::from(SimpleTokenizer), TEST_TEXT, &terms, 20);
+            let fragments =
+                search_fragments(&From::from(SimpleTokenizer), TEST_TEXT, &terms, 20, true);
             {
                 let first = &fragments[0];
                 assert_eq!(first.score, 1.0);
@@ -387,7 +1042,8 @@ Survey in 2016, 2017, and 2018."#;
                 String::from("rust") =>0.9,
                 String::from("language") => 1.0
             };
-            let fragments = search_fragments(&From::from(SimpleTokenizer), TEST_TEXT, &terms, 20);
+            let fragments =
+                search_fragments(&From::from(SimpleTokenizer), TEST_TEXT, &terms, 20, true);
             //assert_eq!(fragments.len(), 7);
             {
                 let first = &fragments[0];
@@ -406,7 +1062,7 @@ Survey in 2016, 2017, and 2018."#;
         let mut terms = BTreeMap::new();
         terms.insert(String::from("c"), 1.0);
 
-        let fragments = search_fragments(&From::from(SimpleTokenizer), &text, &terms, 3);
+        let fragments = search_fragments(&From::from(SimpleTokenizer), &text, &terms, 3, true);
 
         assert_eq!(fragments.len(), 1);
         {
@@ -418,6 +1074,7 @@ Survey in 2016, 2017, and 2018."#;
 
         let snippet = select_best_fragment_combination(&fragments[..], &text);
         assert_eq!(snippet.fragments, "c d");
+        assert_eq!(snippet.fragment_bounds(), (4, 7));
         assert_eq!(snippet.to_html(), "<b>c</b> d");
     }
 
@@ -428,7 +1085,7 @@ Survey in 2016, 2017, and 2018."#;
         let mut terms = BTreeMap::new();
         terms.insert(String::from("f"), 1.0);
 
-        let fragments = search_fragments(&From::from(SimpleTokenizer), &text, &terms, 3);
+        let fragments = search_fragments(&From::from(SimpleTokenizer), &text, &terms, 3, true);
 
         assert_eq!(fragments.len(), 2);
         {
@@ -451,7 +1108,7 @@ Survey in 2016, 2017, and 2018."#;
         terms.insert(String::from("f"), 1.0);
         terms.insert(String::from("a"), 0.9);
 
-        let fragments = search_fragments(&From::from(SimpleTokenizer), &text, &terms, 7);
+        let fragments = search_fragments(&From::from(SimpleTokenizer), &text, &terms, 7, true);
 
         assert_eq!(fragments.len(), 2);
         {
@@ -466,6 +1123,156 @@ Survey in 2016, 2017, and 2018."#;
         assert_eq!(snippet.to_html(), "e <b>f</b> g");
     }
 
+    #[test]
+    fn test_snippet_with_tied_fragments_picks_the_earliest() {
+        let text = "a b c d a b";
+
+        let mut terms = BTreeMap::new();
+        terms.insert(String::from("a"), 1.0);
+
+        let fragments = search_fragments(&From::from(SimpleTokenizer), text, &terms, 3, true);
+        assert_eq!(fragments.len(), 2);
+
+        let snippet = select_best_fragment_combination(&fragments[..], text);
+        assert_eq!(snippet.fragments, "a b");
+        assert_eq!(snippet.fragment_bounds(), (0, 3));
+        assert_eq!(snippet.to_html(), "<b>a</b> b");
+    }
+
+    #[test]
+    fn test_snippet_merges_adjacent_highlights() {
+        let text = "the old man and the sea";
+
+        let mut terms = BTreeMap::new();
+        terms.insert(String::from("old"), 1.0);
+        terms.insert(String::from("man"), 1.0);
+        terms.insert(String::from("sea"), 1.0);
+
+        let fragments = search_fragments(&From::from(SimpleTokenizer), text, &terms, 100, true);
+        let snippet = select_best_fragment_combination(&fragments[..], text);
+        assert_eq!(snippet.highlighted().len(), 2);
+        assert_eq!(snippet.to_html(), "the <b>old man</b> and the <b>sea</b>");
+        assert_eq!(
+            snippet.to_html_with_tags("<mark>", "</mark>"),
+            "the <mark>old man</mark> and the <mark>sea</mark>"
+        );
+
+        let fragments = search_fragments(&From::from(SimpleTokenizer), text, &terms, 100, false);
+        let snippet = select_best_fragment_combination(&fragments[..], text);
+        assert_eq!(snippet.highlighted().len(), 3);
+        assert_eq!(
+            snippet.to_html(),
+            "the <b>old</b> <b>man</b> and the <b>sea</b>"
+        );
+    }
+
+    #[test]
+    fn test_snippet_merges_overlapping_highlights() {
+        let text = "abcd";
+
+        let mut terms = BTreeMap::new();
+        terms.insert(String::from("ab"), 1.0);
+        terms.insert(String::from("bc"), 1.0);
+
+        let fragments = search_fragments(
+            &From::from(NgramTokenizer::all_ngrams(2, 2)),
+            text,
+            &terms,
+            100,
+            false,
+        );
+        let snippet = select_best_fragment_combination(&fragments[..], text);
+        assert_eq!(snippet.highlighted().len(), 1);
+        assert_eq!(snippet.to_html(), "<b>abc</b>d");
+    }
+
+    #[test]
+    fn test_render_html() {
+        let text = "<a> & b'c";
+        let sections = vec![HighlightSection::new(0, 3), HighlightSection::new(6, 9)];
+        assert_eq!(
+            render_html(text, &sections, "<mark>", "</mark>").unwrap(),
+            "<mark>&lt;a&gt;</mark> &amp; <mark>b&#x27;c</mark>"
+        );
+        assert_eq!(
+            render_html(text, &[], "<b>", "</b>").unwrap(),
+            "&lt;a&gt; &amp; b&#x27;c"
+        );
+        let mut html = String::from("...");
+        render_html_to(text, &sections[1..], "<em>", "</em>", &mut html).unwrap();
+        assert_eq!(html, "...&lt;a&gt; &amp; <em>b&#x27;c</em>");
+    }
+
+    #[test]
+    fn test_render_html_invalid_sections() {
+        let text = "abcé";
+        assert!(render_html(text, &[HighlightSection::new(2, 6)], "<b>", "</b>").is_err());
+        assert!(render_html(text, &[HighlightSection::new(0, 4)], "<b>", "</b>").is_err());
+        let unsorted = vec![HighlightSection::new(2, 3), HighlightSection::new(0, 1)];
+        assert!(render_html(text, &unsorted, "<b>", "</b>").is_err());
+        let mut html = String::new();
+        assert!(render_html_to(
+            text,
+            &[HighlightSection::new(3, 1)],
+            "<b>",
+            "</b>",
+            &mut html
+        )
+        .is_err());
+        assert!(html.is_empty());
+    }
+
+    fn token(offset_from: usize, offset_to: usize, text: &str) -> Token {
+        Token {
+            offset_from,
+            offset_to,
+            text: text.to_string(),
+            ..Token::default()
+        }
+    }
+
+    #[test]
+    fn test_snippet_highlighted_bounds_in_chars() {
+        let text = "été à la plage";
+
+        let mut terms = BTreeMap::new();
+        terms.insert(String::from("plage"), 1.0);
+
+        let fragments = search_fragments(&From::from(SimpleTokenizer), text, &terms, 100, true);
+        let snippet = select_best_fragment_combination(&fragments[..], text);
+        assert_eq!(
+            snippet.highlighted_bounds(OffsetMode::Bytes),
+            vec![(12, 17)]
+        );
+        assert_eq!(snippet.highlighted_bounds(OffsetMode::Chars), vec![(9, 14)]);
+    }
+
+    proptest! {
+        #[test]
+        fn test_snippet_highlights_never_overlap(
+            tokens in proptest::collection::vec((0usize..90, 1usize..10, any::<bool>()), 0..30),
+            merge_adjacent in any::<bool>()
+        ) {
+            let text = "a".repeat(100);
+            let terms = btreemap! { String::from("x") => 1.0 };
+            let mut fragment = FragmentCandidate::new(0);
+            for (offset_from, len, matching) in tokens {
+                let token_text = if matching { "x" } else { "y" };
+                fragment.try_add_token(&token(offset_from, offset_from + len, token_text), &terms, merge_adjacent);
+            }
+            let snippet = select_best_fragment_combination(&[fragment], &text);
+            let bounds: Vec<(usize, usize)> = snippet.highlighted().iter().map(|section| section.bounds()).collect();
+            for &(start, stop) in &bounds {
+                prop_assert!(start < stop);
+                prop_assert!(stop <= snippet.fragments().len());
+            }
+            for window in bounds.windows(2) {
+                prop_assert!(window[0].1 < window[1].0);
+            }
+            snippet.to_html();
+        }
+    }
+
     #[test]
     fn test_snippet_with_term_not_in_text() {
         let text = "a b c d";
@@ -473,21 +1280,121 @@ Survey in 2016, 2017, and 2018."#;
         let mut terms = BTreeMap::new();
         terms.insert(String::from("z"), 1.0);
 
-        let fragments = search_fragments(&From::from(SimpleTokenizer), &text, &terms, 3);
+        let fragments = search_fragments(&From::from(SimpleTokenizer), &text, &terms, 3, true);
 
         assert_eq!(fragments.len(), 0);
 
         let snippet = select_best_fragment_combination(&fragments[..], &text);
         assert_eq!(snippet.fragments, "");
+        assert_eq!(snippet.fragment_bounds(), (0, 0));
         assert_eq!(snippet.to_html(), "");
     }
 
+    #[test]
+    fn test_snippet_with_tokenizer_emitting_no_tokens() {
+        let text = "the a the";
+        let stop_words = vec![String::from("the"), String::from("a")];
+        let tokenizer =
+            TextAnalyzer::from(SimpleTokenizer).filter(StopWordFilter::remove(stop_words));
+        let mut terms = BTreeMap::new();
+        terms.insert(String::from("the"), 1.0);
+        let fragments = search_fragments(&tokenizer, text, &terms, 3, true);
+        assert!(fragments.is_empty());
+        let snippet = select_best_fragment_combination(&fragments[..], text);
+        assert_eq!(snippet.fragments, "");
+        assert!(snippet.highlighted().is_empty());
+        assert_eq!(snippet.to_html(), "");
+    }
+
+    /// Emits the tokens of the `SimpleTokenizer`, the last one ending past the text.
+    #[derive(Clone)]
+    struct OutOfBoundsTokenizer;
+
+    impl Tokenizer for OutOfBoundsTokenizer {
+        fn token_stream<'a>(&self, text: &'a str) -> BoxTokenStream<'a> {
+            let mut tokens = Vec::new();
+            SimpleTokenizer
+                .token_stream(text)
+                .process(&mut |token| tokens.push(token.clone()));
+            if let Some(last_token) = tokens.last_mut() {
+                last_token.offset_to = text.len() + 10;
+            }
+            let text = text.to_string();
+            PreTokenizedStream::from(PreTokenizedString { text, tokens }).into()
+        }
+    }
+
+    #[test]
+    fn test_snippet_ignores_tokens_out_of_the_text() -> crate::Result<()> {
+        let text = "rust is fun";
+        let mut terms = BTreeMap::new();
+        terms.insert(String::from("rust"), 1.0);
+        terms.insert(String::from("fun"), 1.0);
+        let tokenizer = TextAnalyzer::from(OutOfBoundsTokenizer);
+        let fragments = search_fragments(&tokenizer, text, &terms, 100, false);
+        let snippet = select_best_fragment_combination(&fragments[..], text);
+        assert_eq!(snippet.to_html(), "<b>rust</b> is");
+        let mut schema_builder = Schema::builder();
+        let text_field = schema_builder.add_text_field(
+            "text",
+            TextOptions::default()
+                .set_indexing_options(TextFieldIndexing::default().set_tokenizer("out_of_bounds")),
+        );
+        let index = Index::create_in_ram(schema_builder.build());
+        index
+            .tokenizers()
+            .register("out_of_bounds", OutOfBoundsTokenizer);
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text_field => text));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let query = QueryParser::for_index(&index, vec![text_field]).parse_query("rust fun")?;
+        let snippet_generator = SnippetGenerator::create(&searcher, &*query, text_field)?;
+        let sections: Vec<(usize, usize)> = snippet_generator
+            .highlight_sections(text)
+            .with_offset_mode(OffsetMode::Chars)
+            .map(|section| section.bounds())
+            .collect();
+        assert_eq!(sections, vec![(0, 4)]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_snippet_fails_on_tokens_out_of_the_text() -> crate::Result<()> {
+        let text = "rust is fun";
+        let mut schema_builder = Schema::builder();
+        let text_field = schema_builder.add_text_field(
+            "text",
+            TextOptions::default()
+                .set_indexing_options(TextFieldIndexing::default().set_tokenizer("out_of_bounds")),
+        );
+        let index = Index::create_in_ram(schema_builder.build());
+        index
+            .tokenizers()
+            .register("out_of_bounds", OutOfBoundsTokenizer);
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text_field => text));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let query = QueryParser::for_index(&index, vec![text_field]).parse_query("rust fun")?;
+        let snippet_generator = SnippetGenerator::create(&searcher, &*query, text_field)?;
+        assert!(matches!(
+            snippet_generator.try_snippet(text),
+            Err(crate::TantivyError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            snippet_generator.try_highlight_sections(text, OffsetMode::Chars),
+            Err(crate::TantivyError::InvalidArgument(_))
+        ));
+        Ok(())
+    }
+
     #[test]
     fn test_snippet_with_no_terms() {
         let text = "a b c d";
 
         let terms = BTreeMap::new();
-        let fragments = search_fragments(&From::from(SimpleTokenizer), &text, &terms, 3);
+        let fragments = search_fragments(&From::from(SimpleTokenizer), &text, &terms, 3, true);
         assert_eq!(fragments.len(), 0);
 
         let snippet = select_best_fragment_combination(&fragments[..], &text);
@@ -579,6 +1486,118 @@ Survey in 2016, 2017, and 2018."#;
             snippet_generator.set_max_num_chars(90);
             let snippet = snippet_generator.snippet(TEST_TEXT);
             assert_eq!(snippet.to_html(), "<b>Rust</b> is syntactically similar to C++[according to whom?],\nbut its <b>designers</b> intend it to");
+            let try_snippet = snippet_generator.try_snippet(TEST_TEXT).unwrap();
+            assert_eq!(try_snippet.to_html(), snippet.to_html());
+        }
+    }
+
+    #[test]
+    fn test_snippet_generator_highlight_sections() {
+        let mut schema_builder = Schema::builder();
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        {
+            let mut index_writer = index.writer_for_tests().unwrap();
+            index_writer.add_document(doc!(text_field => "the old man and the sea"));
+            index_writer.commit().unwrap();
+        }
+        let searcher = index.reader().unwrap().searcher();
+        let query_parser = QueryParser::for_index(&index, vec![text_field]);
+        let query = query_parser.parse_query("old man sea").unwrap();
+        let mut snippet_generator =
+            SnippetGenerator::create(&searcher, &*query, text_field).unwrap();
+        let text = "An old man, a man of the sea.";
+        let sections: Vec<(usize, usize)> = snippet_generator
+            .highlight_sections(text)
+            .map(|section| section.bounds())
+            .collect();
+        assert_eq!(sections, vec![(3, 10), (14, 17), (25, 28)]);
+        snippet_generator.set_merge_adjacent_highlights(false);
+        let sections: Vec<(usize, usize)> = snippet_generator
+            .highlight_sections(text)
+            .map(|section| section.bounds())
+            .collect();
+        assert_eq!(sections, vec![(3, 6), (7, 10), (14, 17), (25, 28)]);
+        let sections: Vec<(usize, usize)> = snippet_generator
+            .highlight_sections("Éé old man")
+            .with_offset_mode(OffsetMode::Chars)
+            .map(|section| section.bounds())
+            .collect();
+        assert_eq!(sections, vec![(3, 6), (7, 10)]);
+        assert_eq!(
+            snippet_generator.highlight_sections("nothing here").count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_snippet_generator_text_sections() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text_field => "the old man and the sea"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let query = QueryParser::for_index(&index, vec![text_field]).parse_query("old sea")?;
+        let snippet_generator = SnippetGenerator::create(&searcher, &*query, text_field)?;
+        let text = "An old man of the sea";
+        let sections: Vec<(usize, usize, bool)> = snippet_generator
+            .text_sections(text)
+            .iter()
+            .map(|section| {
+                let (start, stop) = section.bounds();
+                (start, stop, section.is_highlighted())
+            })
+            .collect();
+        assert_eq!(
+            sections,
+            vec![(0, 3, false), (3, 6, true), (6, 18, false), (18, 21, true)]
+        );
+        assert_eq!(
+            snippet_generator.text_sections("nothing here"),
+            vec![TextSection {
+                start: 0,
+                stop: 12,
+                highlighted: false
+            }]
+        );
+        assert!(snippet_generator.text_sections("").is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_snippet_from_term_vectors() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text_options = TextOptions::default()
+            .set_indexing_options(
+                TextFieldIndexing::default()
+                    .set_tokenizer("en_stem")
+                    .set_term_vectors(true),
+            )
+            .set_stored();
+        let text_field = schema_builder.add_text_field("text", text_options);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text_field => "The old man", text_field => TEST_TEXT));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let query_parser = QueryParser::for_index(&index, vec![text_field]);
+        let query = query_parser.parse_query("rust design old")?;
+        let mut snippet_generator = SnippetGenerator::create(&searcher, &*query, text_field)?;
+        let doc_address = DocAddress(0, 0);
+        let doc = searcher.doc(doc_address)?;
+        for &max_num_chars in &[150, 90, 20] {
+            snippet_generator.set_max_num_chars(max_num_chars);
+            let snippet = snippet_generator.snippet_from_doc_address(&searcher, doc_address)?;
+            assert_eq!(
+                snippet.to_html(),
+                snippet_generator.snippet_from_doc(&doc).to_html()
+            );
         }
+        let snippet = snippet_generator.snippet_from_doc_address(&searcher, doc_address)?;
+        assert_eq!(snippet.to_html(), "The <b>old</b> man <b>Rust</b> is");
+        Ok(())
     }
 }
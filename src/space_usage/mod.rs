@@ -75,6 +75,9 @@ pub struct SegmentSpaceUsage {
 
     store: StoreSpaceUsage,
 
+    #[serde(default)]
+    term_vectors: ByteCount,
+
     deletes: ByteCount,
 
     total: ByteCount,
@@ -91,6 +94,7 @@ impl SegmentSpaceUsage {
         fast_fields: PerFieldSpaceUsage,
         fieldnorms: PerFieldSpaceUsage,
         store: StoreSpaceUsage,
+        term_vectors: ByteCount,
         deletes: ByteCount,
     ) -> SegmentSpaceUsage {
         let total = termdict.total()
@@ -99,6 +103,7 @@ impl SegmentSpaceUsage {
             + fast_fields.total()
             + fieldnorms.total()
             + store.total()
+            + term_vectors
             + deletes;
         SegmentSpaceUsage {
             num_docs,
@@ -109,6 +114,7 @@ impl SegmentSpaceUsage {
             fast_fields,
             fieldnorms,
             store,
+            term_vectors,
             deletes,
             total,
         }
@@ -130,6 +136,7 @@ impl SegmentSpaceUsage {
             TERMS => PerField(self.termdict().clone()),
             STORE => Store(self.store().clone()),
             DELETE => Basic(self.deletes()),
+            TERMVECTORS => Basic(self.term_vectors()),
         }
     }
 
@@ -173,6 +180,11 @@ impl SegmentSpaceUsage {
         &self.store
     }
 
+    /// Space usage for the term vectors
+    pub fn term_vectors(&self) -> ByteCount {
+        self.term_vectors
+    }
+
     /// Space usage for document deletions
     pub fn deletes(&self) -> ByteCount {
         self.deletes
@@ -0,0 +1,11 @@
+use std::io;
+
+pub fn compress(uncompressed: &[u8], compressed: &mut Vec<u8>, level: i32) -> io::Result<()> {
+    compressed.clear();
+    zstd::stream::copy_encode(uncompressed, compressed, level)
+}
+
+pub fn decompress(compressed: &[u8], decompressed: &mut Vec<u8>) -> io::Result<()> {
+    decompressed.clear();
+    zstd::stream::copy_decode(compressed, decompressed)
+}
@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+use std::io;
+
+/// Compression of the blocks of the doc store.
+///
+/// The compression of the new segments is set in the
+/// [`IndexSettings`](../struct.IndexSettings.html), and recorded in the meta of
+/// each segment: segments written with different compressions can be read
+/// and merged together.
+///
+/// `Lz4`, `Brotli` and `Zstd` respectively require the `lz4-compression`,
+/// `brotli-compression` and `zstd-compression` features.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StoredFieldCompression {
+    /// The blocks are not compressed.
+    ///
+    /// This is the fastest option for small and poorly compressible stored fields,
+    /// like identifiers or urls.
+    None,
+    /// Snappy compression.
+    Snappy,
+    /// LZ4 compression.
+    Lz4,
+    /// Brotli compression.
+    Brotli,
+    /// Zstd compression, at the given level.
+    ///
+    /// Levels range from 1 to 22. Higher levels compress better, but slower.
+    /// Decompression speed does not depend much on the level.
+    Zstd {
+        /// Compression level.
+        level: i32,
+    },
+}
+
+impl Default for StoredFieldCompression {
+    /// The default compression depends on the compression features:
+    /// `lz4-compression` and `brotli-compression` make `Lz4` and `Brotli` the default.
+    /// Otherwise, the default is `Snappy`.
+    fn default() -> StoredFieldCompression {
+        if cfg!(feature = "lz4") {
+            StoredFieldCompression::Lz4
+        } else if cfg!(feature = "brotli") {
+            StoredFieldCompression::Brotli
+        } else {
+            StoredFieldCompression::Snappy
+        }
+    }
+}
+
+impl StoredFieldCompression {
+    /// Returns true iff the compression is available in this build of tantivy.
+    pub fn is_supported(self) -> bool {
+        match self {
+            StoredFieldCompression::None | StoredFieldCompression::Snappy => true,
+            StoredFieldCompression::Lz4 => cfg!(feature = "lz4"),
+            StoredFieldCompression::Brotli => cfg!(feature = "brotli"),
+            StoredFieldCompression::Zstd { .. } => cfg!(feature = "zstd"),
+        }
+    }
+
+    fn unsupported(self) -> io::Error {
+        let feature = match self {
+            StoredFieldCompression::Lz4 => "lz4-compression",
+            StoredFieldCompression::Brotli => "brotli-compression",
+            _ => "zstd-compression",
+        };
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "The {:?} compression of the doc store requires the `{}` feature.",
+                self, feature
+            ),
+        )
+    }
+
+    pub(crate) fn compress(self, uncompressed: &[u8], compressed: &mut Vec<u8>) -> io::Result<()> {
+        match self {
+            StoredFieldCompression::None => {
+                compressed.clear();
+                compressed.extend_from_slice(uncompressed);
+                Ok(())
+            }
+            StoredFieldCompression::Snappy => {
+                super::compression_snap::compress(uncompressed, compressed)
+            }
+            #[cfg(feature = "lz4")]
+            StoredFieldCompression::Lz4 => {
+                super::compression_lz4::compress(uncompressed, compressed)
+            }
+            #[cfg(feature = "brotli")]
+            StoredFieldCompression::Brotli => {
+                super::compression_brotli::compress(uncompressed, compressed)
+            }
+            #[cfg(feature = "zstd")]
+            StoredFieldCompression::Zstd { level } => {
+                super::compression_zstd::compress(uncompressed, compressed, level)
+            }
+            #[allow(unreachable_patterns)]
+            _ => Err(self.unsupported()),
+        }
+    }
+
+    pub(crate) fn decompress(
+        self,
+        compressed: &[u8],
+        decompressed: &mut Vec<u8>,
+    ) -> io::Result<()> {
+        match self {
+            StoredFieldCompression::None => {
+                decompressed.clear();
+                decompressed.extend_from_slice(compressed);
+                Ok(())
+            }
+            StoredFieldCompression::Snappy => {
+                super::compression_snap::decompress(compressed, decompressed)
+            }
+            #[cfg(feature = "lz4")]
+            StoredFieldCompression::Lz4 => {
+                super::compression_lz4::decompress(compressed, decompressed)
+            }
+            #[cfg(feature = "brotli")]
+            StoredFieldCompression::Brotli => {
+                super::compression_brotli::decompress(compressed, decompressed)
+            }
+            #[cfg(feature = "zstd")]
+            StoredFieldCompression::Zstd { .. } => {
+                super::compression_zstd::decompress(compressed, decompressed)
+            }
+            #[allow(unreachable_patterns)]
+            _ => Err(self.unsupported()),
+        }
+    }
+}
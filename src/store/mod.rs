@@ -5,9 +5,13 @@ A field needs to be marked as stored in the schema in
 order to be handled in the `Store`.
 
 Internally, documents (or rather their stored fields) are serialized to a buffer.
-When the buffer exceeds 16K, the buffer is compressed using `brotli`, `LZ4` or `snappy`
+When the buffer exceeds 16K, the buffer is compressed
 and the resulting block is written to disk.
 
+The compression is picked in the [`IndexSettings`](../struct.IndexSettings.html),
+among the [`StoredFieldCompression`](./enum.StoredFieldCompression.html) variants,
+and recorded in the meta of each segment.
+
 One can then request for a specific `DocId`.
 A skip list helps navigating to the right block,
 decompresses it entirely and returns the document within it.
@@ -31,6 +35,17 @@ and should rely on either
 - at the index level, the
 [`Searcher`'s `doc` method](../struct.Searcher.html#method.doc)
 
+# Storing the documents out of the local disk
+
+The store of a segment is a single file, with the `.store` extension,
+read and written through the [`Directory`](../directory/trait.Directory.html)
+of the index like the other files of the segment. Large stored fields
+can be kept on another storage than the inverted index by a `Directory`
+dispatching the `.store` files to a blob storage, and the other files to an
+[`MmapDirectory`](../directory/struct.MmapDirectory.html). The
+[`RangeReaderDirectory`](../directory/struct.RangeReaderDirectory.html) reads
+the blocks of such files with range requests, and caches them.
+
 !*/
 
 mod index;
@@ -39,29 +54,41 @@ mod writer;
 pub use self::reader::StoreReader;
 pub use self::writer::StoreWriter;
 
+pub use self::compressors::StoredFieldCompression;
+
 #[cfg(all(feature = "lz4", feature = "brotli"))]
 compile_error!("feature `lz4` or `brotli` must not be enabled together.");
 
+mod compressors;
+
 #[cfg(feature = "lz4")]
 mod compression_lz4;
-#[cfg(feature = "lz4")]
-pub use self::compression_lz4::COMPRESSION;
-#[cfg(feature = "lz4")]
-use self::compression_lz4::{compress, decompress};
 
 #[cfg(feature = "brotli")]
 mod compression_brotli;
-#[cfg(feature = "brotli")]
-pub use self::compression_brotli::COMPRESSION;
-#[cfg(feature = "brotli")]
-use self::compression_brotli::{compress, decompress};
 
-#[cfg(not(any(feature = "lz4", feature = "brotli")))]
+#[cfg(feature = "zstd")]
+mod compression_zstd;
+
 mod compression_snap;
+
+/// Name of the default compression scheme of the doc store.
+///
+/// This name is appended to the version string of tantivy.
+#[cfg(feature = "lz4")]
+pub const COMPRESSION: &str = "lz4";
+
+/// Name of the default compression scheme of the doc store.
+///
+/// This name is appended to the version string of tantivy.
+#[cfg(feature = "brotli")]
+pub const COMPRESSION: &str = "brotli";
+
+/// Name of the default compression scheme of the doc store.
+///
+/// This name is appended to the version string of tantivy.
 #[cfg(not(any(feature = "lz4", feature = "brotli")))]
-pub use self::compression_snap::COMPRESSION;
-#[cfg(not(any(feature = "lz4", feature = "brotli")))]
-use self::compression_snap::{compress, decompress};
+pub const COMPRESSION: &str = "snappy";
 
 #[cfg(test)]
 pub mod tests {
@@ -75,6 +102,18 @@ pub mod tests {
     use std::path::Path;
 
     pub fn write_lorem_ipsum_store(writer: WritePtr, num_docs: usize) -> Schema {
+        write_lorem_ipsum_store_with_compression(
+            writer,
+            num_docs,
+            StoredFieldCompression::default(),
+        )
+    }
+
+    fn write_lorem_ipsum_store_with_compression(
+        writer: WritePtr,
+        num_docs: usize,
+        compression: StoredFieldCompression,
+    ) -> Schema {
         let mut schema_builder = Schema::builder();
         let field_body = schema_builder.add_text_field("body", TextOptions::default().set_stored());
         let field_title =
@@ -91,7 +130,7 @@ pub mod tests {
              laborum.",
         );
         {
-            let mut store_writer = StoreWriter::new(writer);
+            let mut store_writer = StoreWriter::with_compression(writer, compression);
             for i in 0..num_docs {
                 let mut fields: Vec<FieldValue> = Vec::new();
                 {
@@ -134,6 +173,63 @@ pub mod tests {
         }
         Ok(())
     }
+
+    fn test_store_with_compression(compression: StoredFieldCompression) -> crate::Result<()> {
+        let path = Path::new("store");
+        let directory = RAMDirectory::create();
+        let store_wrt = directory.open_write(path)?;
+        let schema = write_lorem_ipsum_store_with_compression(store_wrt, 1_000, compression);
+        let field_title = schema.get_field("title").unwrap();
+        let store_file = directory.open_read(path)?;
+        let store = StoreReader::open_with_compression(store_file, compression)?;
+        assert_eq!(store.compression(), compression);
+        for i in (0..1_000).step_by(7) {
+            assert_eq!(
+                store.get(i)?.get_first(field_title).unwrap().text(),
+                Some(format!("Doc {}", i).as_str())
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_store_no_compression() -> crate::Result<()> {
+        test_store_with_compression(StoredFieldCompression::None)
+    }
+
+    #[test]
+    fn test_store_snappy() -> crate::Result<()> {
+        test_store_with_compression(StoredFieldCompression::Snappy)
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_store_zstd() -> crate::Result<()> {
+        test_store_with_compression(StoredFieldCompression::Zstd { level: 1 })?;
+        test_store_with_compression(StoredFieldCompression::Zstd { level: 19 })
+    }
+
+    #[test]
+    fn test_store_unsupported_compression() -> crate::Result<()> {
+        let unsupported_compression = [
+            StoredFieldCompression::Lz4,
+            StoredFieldCompression::Brotli,
+            StoredFieldCompression::Zstd { level: 3 },
+        ]
+        .iter()
+        .cloned()
+        .find(|compression| !compression.is_supported());
+        if let Some(compression) = unsupported_compression {
+            let directory = RAMDirectory::create();
+            let mut store_writer = StoreWriter::with_compression(
+                directory.open_write(Path::new("store"))?,
+                compression,
+            );
+            store_writer.store(&Document::default())?;
+            assert!(store_writer.close().is_err());
+        }
+        Ok(())
+    }
 }
 
 #[cfg(all(test, feature = "unstable"))]
@@ -1,9 +1,9 @@
-use super::decompress;
 use super::index::SkipIndex;
+use super::StoredFieldCompression;
 use crate::common::VInt;
 use crate::common::{BinarySerializable, HasLen};
 use crate::directory::{FileSlice, OwnedBytes};
-use crate::schema::Document;
+use crate::schema::{Document, Field};
 use crate::space_usage::StoreSpaceUsage;
 use crate::store::index::Checkpoint;
 use crate::DocId;
@@ -20,6 +20,7 @@ type Block = Arc<Vec<u8>>;
 type BlockCache = Arc<Mutex<LruCache<u64, Block>>>;
 
 /// Reads document off tantivy's [`Store`](./index.html)
+#[derive(Clone)]
 pub struct StoreReader {
     data: FileSlice,
     cache: BlockCache,
@@ -27,11 +28,20 @@ pub struct StoreReader {
     cache_misses: Arc<AtomicUsize>,
     skip_index: Arc<SkipIndex>,
     space_usage: StoreSpaceUsage,
+    compression: StoredFieldCompression,
 }
 
 impl StoreReader {
-    /// Opens a store reader
+    /// Opens a store reader, whose blocks are compressed with the default compression.
     pub fn open(store_file: FileSlice) -> io::Result<StoreReader> {
+        StoreReader::open_with_compression(store_file, StoredFieldCompression::default())
+    }
+
+    /// Opens a store reader, whose blocks are compressed with the given compression.
+    pub fn open_with_compression(
+        store_file: FileSlice,
+        compression: StoredFieldCompression,
+    ) -> io::Result<StoreReader> {
         let (data_file, offset_index_file) = split_file(store_file)?;
         let index_data = offset_index_file.read_bytes()?;
         let space_usage = StoreSpaceUsage::new(data_file.len(), offset_index_file.len());
@@ -43,9 +53,15 @@ impl StoreReader {
             cache_misses: Default::default(),
             skip_index: Arc::new(skip_index),
             space_usage,
+            compression,
         })
     }
 
+    /// Returns the compression of the blocks of the store.
+    pub fn compression(&self) -> StoredFieldCompression {
+        self.compression
+    }
+
     pub(crate) fn block_checkpoints(&self) -> impl Iterator<Item = Checkpoint> + '_ {
         self.skip_index.checkpoints()
     }
@@ -77,7 +93,8 @@ impl StoreReader {
 
         let compressed_block = self.compressed_block(checkpoint)?;
         let mut decompressed_block = vec![];
-        decompress(compressed_block.as_slice(), &mut decompressed_block)?;
+        self.compression
+            .decompress(compressed_block.as_slice(), &mut decompressed_block)?;
 
         let block = Arc::new(decompressed_block);
         self.cache
@@ -96,6 +113,29 @@ impl StoreReader {
     /// It should not be called to score documents
     /// for instance.
     pub fn get(&self, doc_id: DocId) -> crate::Result<Document> {
+        self.deserialize_doc(doc_id, |doc_bytes| Document::deserialize(doc_bytes))
+    }
+
+    /// Reads the values of the given `fields` of a document.
+    ///
+    /// The block of the document still needs to be decompressed, but the values of
+    /// the other fields are skipped without being decoded.
+    pub fn get_projected(&self, doc_id: DocId, fields: &[Field]) -> crate::Result<Document> {
+        self.deserialize_doc(doc_id, |doc_bytes| {
+            Document::deserialize_projected(doc_bytes, fields)
+        })
+    }
+
+    /// Reads the serialized bytes of a given document.
+    pub(crate) fn get_document_bytes(&self, doc_id: DocId) -> crate::Result<Vec<u8>> {
+        self.deserialize_doc(doc_id, |doc_bytes| Ok(doc_bytes.to_vec()))
+    }
+
+    fn deserialize_doc<T>(
+        &self,
+        doc_id: DocId,
+        deserialize: impl FnOnce(&mut &[u8]) -> io::Result<T>,
+    ) -> crate::Result<T> {
         let checkpoint = self.block_checkpoint(doc_id).ok_or_else(|| {
             crate::TantivyError::InvalidArgument(format!("Failed to lookup Doc #{}.", doc_id))
         })?;
@@ -107,7 +147,7 @@ impl StoreReader {
 
         let doc_length = VInt::deserialize(&mut cursor)?.val() as usize;
         cursor = &cursor[..doc_length];
-        Ok(Document::deserialize(&mut cursor)?)
+        Ok(deserialize(&mut cursor)?)
     }
 
     /// Summarize total space usage of this store reader.
@@ -201,4 +241,29 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_store_get_projected() -> crate::Result<()> {
+        let directory = RAMDirectory::create();
+        let path = Path::new("store");
+        let writer = directory.open_write(path)?;
+        let schema = write_lorem_ipsum_store(writer, 500);
+        let title = schema.get_field("title").unwrap();
+        let body = schema.get_field("body").unwrap();
+        let store = StoreReader::open(directory.open_read(path)?)?;
+        for &doc_id in &[0, 123, 499] {
+            let doc = store.get_projected(doc_id, &[title])?;
+            assert_eq!(doc.len(), 1);
+            assert_eq!(
+                get_text_field(&doc, &title),
+                Some(format!("Doc {}", doc_id).as_str())
+            );
+            assert_eq!(
+                store.get_projected(doc_id, &[title, body])?,
+                store.get(doc_id)?
+            );
+        }
+        assert!(store.get_projected(499, &[])?.is_empty());
+        Ok(())
+    }
 }
@@ -1,6 +1,5 @@
-use super::compress;
 use super::index::SkipIndexBuilder;
-use super::StoreReader;
+use super::{StoreReader, StoredFieldCompression};
 use crate::common::CountingWriter;
 use crate::common::{BinarySerializable, VInt};
 use crate::directory::TerminatingWrite;
@@ -27,14 +26,20 @@ pub struct StoreWriter {
     writer: CountingWriter<WritePtr>,
     intermediary_buffer: Vec<u8>,
     current_block: Vec<u8>,
+    compression: StoredFieldCompression,
 }
 
 impl StoreWriter {
-    /// Create a store writer.
+    /// Create a store writer, compressing its blocks with the default compression.
     ///
     /// The store writer will writes blocks on disc as
     /// document are added.
     pub fn new(writer: WritePtr) -> StoreWriter {
+        StoreWriter::with_compression(writer, StoredFieldCompression::default())
+    }
+
+    /// Create a store writer, compressing its blocks with the given compression.
+    pub fn with_compression(writer: WritePtr, compression: StoredFieldCompression) -> StoreWriter {
         StoreWriter {
             doc: 0,
             first_doc_in_block: 0,
@@ -42,9 +47,15 @@ impl StoreWriter {
             writer: CountingWriter::wrap(writer),
             intermediary_buffer: Vec::new(),
             current_block: Vec::new(),
+            compression,
         }
     }
 
+    /// Returns the compression of the blocks of the store.
+    pub fn compression(&self) -> StoredFieldCompression {
+        self.compression
+    }
+
     /// Store a new document.
     ///
     /// The document id is implicitely the number of times
@@ -57,6 +68,17 @@ impl StoreWriter {
         VInt(doc_num_bytes as u64).serialize(&mut self.current_block)?;
         self.current_block
             .write_all(&self.intermediary_buffer[..])?;
+        self.end_doc()
+    }
+
+    /// Store a new document, given as its serialized bytes.
+    pub(crate) fn store_bytes(&mut self, doc_bytes: &[u8]) -> io::Result<()> {
+        VInt(doc_bytes.len() as u64).serialize(&mut self.current_block)?;
+        self.current_block.write_all(doc_bytes)?;
+        self.end_doc()
+    }
+
+    fn end_doc(&mut self) -> io::Result<()> {
         self.doc += 1;
         if self.current_block.len() > BLOCK_SIZE {
             self.write_and_compress_block()?;
@@ -101,7 +123,8 @@ impl StoreWriter {
     fn write_and_compress_block(&mut self) -> io::Result<()> {
         assert!(self.doc > 0);
         self.intermediary_buffer.clear();
-        compress(&self.current_block[..], &mut self.intermediary_buffer)?;
+        self.compression
+            .compress(&self.current_block[..], &mut self.intermediary_buffer)?;
         let start_offset = self.writer.written_bytes();
         self.writer.write_all(&self.intermediary_buffer)?;
         let end_offset = self.writer.written_bytes();
@@ -24,4 +24,7 @@ mod term_info_store;
 mod termdict;
 
 pub use self::streamer::{TermStreamer, TermStreamerBuilder};
+#[cfg(test)]
+pub(crate) use self::termdict::downgrade_to_index_format_v3;
+pub(crate) use self::termdict::upgrade_from_index_format_v3;
 pub use self::termdict::{TermDictionary, TermDictionaryBuilder};
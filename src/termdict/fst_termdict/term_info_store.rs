@@ -1,7 +1,7 @@
 use crate::common::compute_num_bits;
 use crate::common::{bitpacker::BitPacker, BinarySerializable, FixedSize};
 use crate::directory::{FileSlice, OwnedBytes};
-use crate::postings::TermInfo;
+use crate::postings::{PostingFormat, TermInfo};
 use crate::termdict::TermOrdinal;
 use byteorder::{ByteOrder, LittleEndian};
 use std::cmp;
@@ -16,6 +16,7 @@ struct TermInfoBlockMeta {
     doc_freq_nbits: u8,
     postings_offset_nbits: u8,
     positions_idx_nbits: u8,
+    posting_format_nbits: u8,
 }
 
 impl BinarySerializable for TermInfoBlockMeta {
@@ -26,6 +27,7 @@ impl BinarySerializable for TermInfoBlockMeta {
             self.doc_freq_nbits,
             self.postings_offset_nbits,
             self.positions_idx_nbits,
+            self.posting_format_nbits,
         ])?;
         Ok(())
     }
@@ -33,7 +35,7 @@ impl BinarySerializable for TermInfoBlockMeta {
     fn deserialize<R: Read>(reader: &mut R) -> io::Result<Self> {
         let offset = u64::deserialize(reader)?;
         let ref_term_info = TermInfo::deserialize(reader)?;
-        let mut buffer = [0u8; 3];
+        let mut buffer = [0u8; NUM_BITS_LEN];
         reader.read_exact(&mut buffer)?;
         Ok(TermInfoBlockMeta {
             offset,
@@ -41,18 +43,25 @@ impl BinarySerializable for TermInfoBlockMeta {
             doc_freq_nbits: buffer[0],
             postings_offset_nbits: buffer[1],
             positions_idx_nbits: buffer[2],
+            posting_format_nbits: buffer[3],
         })
     }
 }
 
+/// The block meta ends with the numbers of bits of the doc freqs, postings offsets,
+/// positions idxs and posting formats of the term infos of the block, one byte each.
+const NUM_BITS_LEN: usize = 4;
+
 impl FixedSize for TermInfoBlockMeta {
-    const SIZE_IN_BYTES: usize =
-        u64::SIZE_IN_BYTES + TermInfo::SIZE_IN_BYTES + 3 * u8::SIZE_IN_BYTES;
+    const SIZE_IN_BYTES: usize = u64::SIZE_IN_BYTES + TermInfo::SIZE_IN_BYTES + NUM_BITS_LEN;
 }
 
 impl TermInfoBlockMeta {
     fn num_bits(&self) -> u8 {
-        self.doc_freq_nbits + self.postings_offset_nbits + self.positions_idx_nbits
+        self.doc_freq_nbits
+            + self.postings_offset_nbits
+            + self.positions_idx_nbits
+            + self.posting_format_nbits
     }
 
     // Here inner_offset is the offset within the block, WITHOUT the first term_info.
@@ -67,6 +76,7 @@ impl TermInfoBlockMeta {
         let posting_stop_addr = posting_start_addr + num_bits;
         let doc_freq_addr = posting_start_addr + self.postings_offset_nbits as usize;
         let positions_idx_addr = doc_freq_addr + self.doc_freq_nbits as usize;
+        let posting_format_addr = positions_idx_addr + self.positions_idx_nbits as usize;
 
         let postings_start_offset = self.ref_term_info.postings_start_offset
             + extract_bits(data, posting_start_addr, self.postings_offset_nbits);
@@ -75,12 +85,19 @@ impl TermInfoBlockMeta {
         let doc_freq = extract_bits(data, doc_freq_addr, self.doc_freq_nbits) as u32;
         let positions_idx = self.ref_term_info.positions_idx
             + extract_bits(data, positions_idx_addr, self.positions_idx_nbits);
+        let posting_format =
+            if extract_bits(data, posting_format_addr, self.posting_format_nbits) == 0 {
+                PostingFormat::Compressed
+            } else {
+                PostingFormat::Roaring
+            };
 
         TermInfo {
             doc_freq,
             postings_start_offset,
             postings_stop_offset,
             positions_idx,
+            posting_format,
         }
     }
 }
@@ -178,6 +195,11 @@ fn bitpack_serialize<W: Write>(
         term_info_block_meta.positions_idx_nbits,
         write,
     )?;
+    bit_packer.write(
+        u64::from(term_info.posting_format.code()),
+        term_info_block_meta.posting_format_nbits,
+        write,
+    )?;
     Ok(())
 }
 
@@ -218,6 +240,16 @@ impl TermInfoStoreWriter {
         let max_doc_freq_nbits: u8 = compute_num_bits(u64::from(max_doc_freq));
         let max_postings_offset_nbits = compute_num_bits(max_postings_offset);
         let max_positions_idx_nbits = compute_num_bits(max_positions_idx);
+        // Blocks in which all of the posting lists are compressed do not
+        // need to store their format.
+        let posting_format_nbits = if self.term_infos[1..]
+            .iter()
+            .all(|term_info| term_info.posting_format == PostingFormat::Compressed)
+        {
+            0u8
+        } else {
+            1u8
+        };
 
         let term_info_block_meta = TermInfoBlockMeta {
             offset: self.buffer_term_infos.len() as u64,
@@ -225,6 +257,7 @@ impl TermInfoStoreWriter {
             doc_freq_nbits: max_doc_freq_nbits,
             postings_offset_nbits: max_postings_offset_nbits,
             positions_idx_nbits: max_positions_idx_nbits,
+            posting_format_nbits,
         };
 
         term_info_block_meta.serialize(&mut self.buffer_block_metas)?;
@@ -273,18 +306,113 @@ impl TermInfoStoreWriter {
     }
 }
 
+/// Number of bytes of a `TermInfo` of a block meta written before the index format 4,
+/// when term infos did not store the format of their posting list.
+const V3_TERM_INFO_NUM_BYTES: usize = TermInfo::SIZE_IN_BYTES - u8::SIZE_IN_BYTES;
+
+/// Number of bytes of a block meta written before the index format 4. It has
+/// no number of bits for the posting formats either.
+const V3_BLOCK_META_NUM_BYTES: usize =
+    u64::SIZE_IN_BYTES + V3_TERM_INFO_NUM_BYTES + NUM_BITS_LEN - 1;
+
+/// Converts a term info store written before the index format 4 to the current format.
+///
+/// All of the posting lists of these older stores are `Compressed`. Only their block
+/// metas change: the bitpacked term infos of a block whose posting formats are encoded
+/// on 0 bits are unchanged.
+pub(crate) fn upgrade_from_index_format_v3(data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut cursor = data;
+    let len = u64::deserialize(&mut cursor)? as usize;
+    let num_terms = u64::deserialize(&mut cursor)?;
+    let corrupted_block_metas = || {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Invalid term info store: corrupted block metas.",
+        )
+    };
+    if len > cursor.len() {
+        return Err(corrupted_block_metas());
+    }
+    let (block_metas, term_infos) = cursor.split_at(len);
+    let block_metas = block_metas.chunks_exact(V3_BLOCK_META_NUM_BYTES);
+    if !block_metas.remainder().is_empty() {
+        return Err(corrupted_block_metas());
+    }
+    let mut upgraded_block_metas: Vec<u8> =
+        Vec::with_capacity(block_metas.len() * TermInfoBlockMeta::SIZE_IN_BYTES);
+    for block_meta in block_metas {
+        let (offset_and_ref_term_info, num_bits) =
+            block_meta.split_at(u64::SIZE_IN_BYTES + V3_TERM_INFO_NUM_BYTES);
+        upgraded_block_metas.extend_from_slice(offset_and_ref_term_info);
+        upgraded_block_metas.push(PostingFormat::Compressed.code());
+        upgraded_block_metas.extend_from_slice(num_bits);
+        // posting_format_nbits
+        upgraded_block_metas.push(0u8);
+    }
+    let mut upgraded = Vec::with_capacity(16 + upgraded_block_metas.len() + term_infos.len());
+    (upgraded_block_metas.len() as u64).serialize(&mut upgraded)?;
+    num_terms.serialize(&mut upgraded)?;
+    upgraded.extend_from_slice(&upgraded_block_metas);
+    upgraded.extend_from_slice(term_infos);
+    Ok(upgraded)
+}
+
+/// Converts a term info store to the format used before the index format 4.
+///
+/// Fails if one of the posting lists is not `Compressed`, as the older
+/// formats cannot express it.
+#[cfg(test)]
+pub(crate) fn downgrade_to_index_format_v3(data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut cursor = data;
+    let len = u64::deserialize(&mut cursor)? as usize;
+    let num_terms = u64::deserialize(&mut cursor)?;
+    let (mut block_metas, term_infos) = cursor.split_at(len);
+    let mut downgraded_block_metas = Vec::new();
+    while !block_metas.is_empty() {
+        let block_meta = TermInfoBlockMeta::deserialize(&mut block_metas)?;
+        if block_meta.posting_format_nbits != 0
+            || block_meta.ref_term_info.posting_format != PostingFormat::Compressed
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Only compressed posting lists can be written in the index format 3.",
+            ));
+        }
+        let mut offset_and_ref_term_info = Vec::new();
+        block_meta.offset.serialize(&mut offset_and_ref_term_info)?;
+        block_meta
+            .ref_term_info
+            .serialize(&mut offset_and_ref_term_info)?;
+        downgraded_block_metas.extend_from_slice(
+            &offset_and_ref_term_info[..u64::SIZE_IN_BYTES + V3_TERM_INFO_NUM_BYTES],
+        );
+        downgraded_block_metas.extend_from_slice(&[
+            block_meta.doc_freq_nbits,
+            block_meta.postings_offset_nbits,
+            block_meta.positions_idx_nbits,
+        ]);
+    }
+    let mut downgraded = Vec::new();
+    (downgraded_block_metas.len() as u64).serialize(&mut downgraded)?;
+    num_terms.serialize(&mut downgraded)?;
+    downgraded.extend_from_slice(&downgraded_block_metas);
+    downgraded.extend_from_slice(term_infos);
+    Ok(downgraded)
+}
+
 #[cfg(test)]
 mod tests {
 
     use super::extract_bits;
     use super::TermInfoBlockMeta;
+    use super::{downgrade_to_index_format_v3, upgrade_from_index_format_v3};
     use super::{TermInfoStore, TermInfoStoreWriter};
     use crate::common;
     use crate::common::bitpacker::BitPacker;
     use crate::common::compute_num_bits;
     use crate::common::BinarySerializable;
     use crate::directory::FileSlice;
-    use crate::postings::TermInfo;
+    use crate::postings::{PostingFormat, TermInfo};
 
     #[test]
     fn test_term_info_block() {
@@ -317,10 +445,12 @@ mod tests {
                 postings_start_offset: 51,
                 postings_stop_offset: 57u64,
                 positions_idx: 3584,
+                posting_format: PostingFormat::Roaring,
             },
             doc_freq_nbits: 10,
             postings_offset_nbits: 5,
             positions_idx_nbits: 11,
+            posting_format_nbits: 1,
         };
         let mut buffer: Vec<u8> = Vec::new();
         term_info_block_meta.serialize(&mut buffer).unwrap();
@@ -340,6 +470,11 @@ mod tests {
                 postings_start_offset: offset(i),
                 postings_stop_offset: offset(i + 1),
                 positions_idx: (i * 7) as u64,
+                posting_format: if i % 3 == 0 && i < 600 {
+                    PostingFormat::Roaring
+                } else {
+                    PostingFormat::Compressed
+                },
             };
             store_writer.write_term_info(&term_info)?;
             term_infos.push(term_info);
@@ -357,4 +492,50 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_upgrade_from_index_format_v3() -> crate::Result<()> {
+        let mut store_writer = TermInfoStoreWriter::new();
+        let mut term_infos = vec![];
+        for i in 0..1000u64 {
+            let term_info = TermInfo {
+                doc_freq: (i % 17) as u32 + 1,
+                postings_start_offset: i * 11,
+                postings_stop_offset: i * 11 + 11,
+                positions_idx: i * 3,
+                posting_format: PostingFormat::Compressed,
+            };
+            store_writer.write_term_info(&term_info)?;
+            term_infos.push(term_info);
+        }
+        let mut buffer = Vec::new();
+        store_writer.serialize(&mut buffer)?;
+        let downgraded = downgrade_to_index_format_v3(&buffer)?;
+        // 4 blocks, each losing the posting format of its reference term info
+        // and the number of bits of the posting formats.
+        assert_eq!(buffer.len() - downgraded.len(), 4 * 2);
+        assert_eq!(upgrade_from_index_format_v3(&downgraded)?, buffer);
+        let term_info_store =
+            TermInfoStore::open(FileSlice::from(upgrade_from_index_format_v3(&downgraded)?))?;
+        for (term_ord, term_info) in term_infos.iter().enumerate() {
+            assert_eq!(&term_info_store.get(term_ord as u64), term_info);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_downgrade_roaring_to_index_format_v3_fails() -> crate::Result<()> {
+        let mut store_writer = TermInfoStoreWriter::new();
+        store_writer.write_term_info(&TermInfo {
+            doc_freq: 3,
+            postings_start_offset: 0,
+            postings_stop_offset: 10,
+            positions_idx: 0,
+            posting_format: PostingFormat::Roaring,
+        })?;
+        let mut buffer = Vec::new();
+        store_writer.serialize(&mut buffer)?;
+        assert!(downgrade_to_index_format_v3(&buffer).is_err());
+        Ok(())
+    }
 }
@@ -1,4 +1,4 @@
-use super::term_info_store::{TermInfoStore, TermInfoStoreWriter};
+use super::term_info_store::{self, TermInfoStore, TermInfoStoreWriter};
 use super::{TermStreamer, TermStreamerBuilder};
 use crate::common::{BinarySerializable, CountingWriter};
 use crate::directory::{FileSlice, OwnedBytes};
@@ -92,6 +92,45 @@ fn open_fst_index(fst_file: FileSlice) -> crate::Result<tantivy_fst::Map<OwnedBy
     Ok(tantivy_fst::Map::from(fst))
 }
 
+/// Rewrites the term info store of a term dictionary file with `convert`.
+fn convert_term_info_store(
+    file: &[u8],
+    convert: impl FnOnce(&[u8]) -> io::Result<Vec<u8>>,
+) -> io::Result<Vec<u8>> {
+    let invalid_data = || {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Term dictionary file is corrupted.",
+        )
+    };
+    if file.len() < 8 {
+        return Err(invalid_data());
+    }
+    let (main_bytes, mut footer_len_bytes) = file.split_at(file.len() - 8);
+    let footer_size = u64::deserialize(&mut footer_len_bytes)? as usize;
+    if footer_size > main_bytes.len() {
+        return Err(invalid_data());
+    }
+    let (fst_bytes, term_info_store_bytes) = main_bytes.split_at(main_bytes.len() - footer_size);
+    let converted_term_info_store = convert(term_info_store_bytes)?;
+    let mut converted = Vec::with_capacity(fst_bytes.len() + converted_term_info_store.len() + 8);
+    converted.extend_from_slice(fst_bytes);
+    converted.extend_from_slice(&converted_term_info_store);
+    (converted_term_info_store.len() as u64).serialize(&mut converted)?;
+    Ok(converted)
+}
+
+/// Converts a term dictionary file written before the index format 4 to the current format.
+pub(crate) fn upgrade_from_index_format_v3(file: &[u8]) -> io::Result<Vec<u8>> {
+    convert_term_info_store(file, term_info_store::upgrade_from_index_format_v3)
+}
+
+/// Converts a term dictionary file to the format used before the index format 4.
+#[cfg(test)]
+pub(crate) fn downgrade_to_index_format_v3(file: &[u8]) -> io::Result<Vec<u8>> {
+    convert_term_info_store(file, term_info_store::downgrade_to_index_format_v3)
+}
+
 static EMPTY_TERM_DICT_FILE: Lazy<FileSlice> = Lazy::new(|| {
     let term_dictionary_data: Vec<u8> = TermDictionaryBuilder::create(Vec::<u8>::new())
         .expect("Creating a TermDictionaryBuilder in a Vec<u8> should never fail")
@@ -106,6 +145,11 @@ static EMPTY_TERM_DICT_FILE: Lazy<FileSlice> = Lazy::new(|| {
 /// The `Fst` crate is used to associate terms to their
 /// respective `TermOrdinal`. The `TermInfoStore` then makes it
 /// possible to fetch the associated `TermInfo`.
+///
+/// The `Fst` is not deserialized: it is traversed directly from the bytes
+/// of the file. With the `MmapDirectory`, these bytes are the memory-mapped file
+/// itself, so that only the pages of the `Fst` visited by the queries are
+/// loaded in memory by the OS.
 pub struct TermDictionary {
     fst_index: tantivy_fst::Map<OwnedBytes>,
     term_info_store: TermInfoStore,
@@ -201,4 +245,14 @@ impl TermDictionary {
         let stream_builder = self.fst_index.search(automaton);
         TermStreamerBuilder::<A>::new(self, stream_builder)
     }
+
+    /// Returns the `Fst` map associating each term to its `TermOrdinal`.
+    ///
+    /// This gives a read-only access to the underlying `Fst`, e.g. to run
+    /// automata that are not expressible with [`.search(...)`](#method.search).
+    /// The `TermInfo` of a term can then be fetched with
+    /// [`.term_info_from_ord(...)`](#method.term_info_from_ord).
+    pub fn as_fst(&self) -> &tantivy_fst::Map<OwnedBytes> {
+        &self.fst_index
+    }
 }
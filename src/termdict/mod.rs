@@ -25,6 +25,10 @@ use tantivy_fst::automaton::AlwaysMatch;
 mod fst_termdict;
 use fst_termdict as termdict;
 
+#[cfg(test)]
+pub(crate) use self::termdict::downgrade_to_index_format_v3;
+pub(crate) use self::termdict::upgrade_from_index_format_v3;
+
 mod merger;
 
 #[cfg(test)]
@@ -1,7 +1,7 @@
 use super::{TermDictionary, TermDictionaryBuilder, TermStreamer};
 
 use crate::directory::{Directory, FileSlice, RAMDirectory, TerminatingWrite};
-use crate::postings::TermInfo;
+use crate::postings::{PostingFormat, TermInfo};
 
 use std::path::PathBuf;
 use std::str;
@@ -15,6 +15,7 @@ fn make_term_info(term_ord: u64) -> TermInfo {
         postings_start_offset: offset(term_ord),
         postings_stop_offset: offset(term_ord + 1),
         positions_idx: offset(term_ord) * 2u64,
+        posting_format: PostingFormat::default(),
     }
 }
 
@@ -429,3 +430,38 @@ fn test_automaton_search() -> crate::Result<()> {
     assert!(!range.advance());
     Ok(())
 }
+
+#[test]
+fn test_as_fst() -> crate::Result<()> {
+    use tantivy_fst::automaton::Subsequence;
+    use tantivy_fst::{IntoStreamer, Streamer};
+
+    let directory = RAMDirectory::create();
+    let path = PathBuf::from("TermDictionary");
+    {
+        let write = directory.open_write(&path)?;
+        let mut term_dictionary_builder = TermDictionaryBuilder::create(write)?;
+        for (term_ord, term) in ["Slovakia", "Slovenia", "Spain", "Sweden"]
+            .iter()
+            .enumerate()
+        {
+            term_dictionary_builder.insert(term.as_bytes(), &make_term_info(term_ord as u64))?;
+        }
+        term_dictionary_builder.finish()?.terminate()?;
+    }
+    let term_dict = TermDictionary::open(directory.open_read(&path)?)?;
+    let fst = term_dict.as_fst();
+    assert_eq!(fst.len(), term_dict.num_terms());
+
+    let mut stream = fst.search(Subsequence::new("Sen")).into_stream();
+    let mut terms = Vec::new();
+    while let Some((term, term_ord)) = stream.next() {
+        assert_eq!(
+            term_dict.term_info_from_ord(term_ord),
+            make_term_info(term_ord)
+        );
+        terms.push(str::from_utf8(term).unwrap().to_string());
+    }
+    assert_eq!(terms, vec!["Slovenia", "Sweden"]);
+    Ok(())
+}
@@ -0,0 +1,174 @@
+/*!
+Term vectors of the documents.
+
+The term vector of a document lists, for a given field, the terms of the
+document along with the position and the offsets of each of their occurrences.
+Term vectors are only recorded for the text fields that enable them with
+[`TextFieldIndexing::set_term_vectors`](../schema/struct.TextFieldIndexing.html#method.set_term_vectors).
+
+They are stored in the `.termvec` file of each segment, using the same
+block-compressed, row-oriented layout as the [`store`](../store/index.html).
+The record of a document is itself laid out in columns: the terms first, then the
+delta-encoded positions, the start offsets and the lengths of all of the occurrences.
+
+Term vectors are read with
+[`Searcher::term_vectors`](../struct.Searcher.html#method.term_vectors).
+They make it possible to highlight a document without tokenizing its text again.
+*/
+
+mod reader;
+mod writer;
+
+pub(crate) use self::reader::TermVectorsReader;
+pub(crate) use self::writer::{TermVectorsTokenStream, TermVectorsWriter};
+
+use std::collections::BTreeMap;
+
+/// An occurrence of a term in a document.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TermOccurrence {
+    /// Position of the token in the field.
+    pub position: u32,
+    /// Offset (in bytes) of the first byte of the token in the text of the field.
+    pub start_offset: usize,
+    /// Offset (in bytes) of the last byte of the token in the text of the field + 1.
+    pub end_offset: usize,
+}
+
+/// Term vector of a field of a document.
+///
+/// It associates the terms of the field to their occurrences, sorted by position.
+///
+/// The offsets of the occurrences are relative to the concatenation of
+/// the values of the field, in the order they were added to the document.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TermVectors {
+    terms: BTreeMap<String, Vec<TermOccurrence>>,
+}
+
+impl TermVectors {
+    pub(crate) fn from_terms(terms: BTreeMap<String, Vec<TermOccurrence>>) -> TermVectors {
+        TermVectors { terms }
+    }
+
+    /// Returns the occurrences of a term, or `None` if the term does not
+    /// appear in the field.
+    pub fn get(&self, term: &str) -> Option<&[TermOccurrence]> {
+        self.terms.get(term).map(Vec::as_slice)
+    }
+
+    /// Iterates over the terms of the field, in lexicographic order,
+    /// along with their occurrences.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[TermOccurrence])> {
+        self.terms
+            .iter()
+            .map(|(term, occurrences)| (term.as_str(), occurrences.as_slice()))
+    }
+
+    /// Returns the number of distinct terms of the field.
+    pub fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// Returns true iff the field does not have any term.
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TermOccurrence;
+    use crate::schema::{Schema, TextFieldIndexing, TextOptions, STRING, TEXT};
+    use crate::{DocAddress, Index, TantivyError, Term};
+
+    fn occurrence(position: u32, start_offset: usize, end_offset: usize) -> TermOccurrence {
+        TermOccurrence {
+            position,
+            start_offset,
+            end_offset,
+        }
+    }
+
+    #[test]
+    fn test_term_vectors() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text_options = TextOptions::default()
+            .set_indexing_options(TextFieldIndexing::default().set_term_vectors(true));
+        let title = schema_builder.add_text_field("title", text_options);
+        let body = schema_builder.add_text_field("body", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title => "Hello happy tax payer", body => "hello"));
+        index_writer.add_document(doc!(body => "no title"));
+        index_writer.add_document(doc!(title => "hello world", title => "hello"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+
+        let term_vectors = searcher.term_vectors(title, DocAddress(0, 0))?;
+        assert_eq!(term_vectors.len(), 4);
+        let terms: Vec<&str> = term_vectors.iter().map(|(term, _)| term).collect();
+        assert_eq!(terms, vec!["happy", "hello", "payer", "tax"]);
+        assert_eq!(term_vectors.get("hello"), Some(&[occurrence(0, 0, 5)][..]));
+        assert_eq!(
+            term_vectors.get("payer"),
+            Some(&[occurrence(3, 16, 21)][..])
+        );
+        assert_eq!(term_vectors.get("world"), None);
+
+        assert!(searcher.term_vectors(title, DocAddress(0, 1))?.is_empty());
+
+        let term_vectors = searcher.term_vectors(title, DocAddress(0, 2))?;
+        assert_eq!(
+            term_vectors.get("hello"),
+            Some(&[occurrence(0, 0, 5), occurrence(3, 11, 16)][..])
+        );
+        assert_eq!(term_vectors.get("world"), Some(&[occurrence(1, 6, 11)][..]));
+
+        assert!(matches!(
+            searcher.term_vectors(body, DocAddress(0, 0)),
+            Err(TantivyError::SchemaError(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_term_vectors_after_merge() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let id = schema_builder.add_text_field("id", STRING);
+        let text_options = TextOptions::default()
+            .set_indexing_options(TextFieldIndexing::default().set_term_vectors(true));
+        let text = schema_builder.add_text_field("text", text_options);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(id => "a", text => "first document"));
+        index_writer.add_document(doc!(id => "b", text => "second document"));
+        index_writer.commit()?;
+        index_writer.add_document(doc!(id => "c", text => "third document"));
+        index_writer.commit()?;
+        index_writer.delete_term(Term::from_field_text(id, "a"));
+        index_writer.commit()?;
+        let segment_ids = index.searchable_segment_ids()?;
+        futures::executor::block_on(index_writer.merge(&segment_ids))?;
+        index_writer.wait_merging_threads()?;
+
+        let reader = index.reader()?;
+        reader.reload()?;
+        let searcher = reader.searcher();
+        assert_eq!(searcher.segment_readers().len(), 1);
+        let term_vectors: Vec<Vec<String>> = (0..2)
+            .map(|doc| {
+                let term_vectors = searcher.term_vectors(text, DocAddress(0, doc)).unwrap();
+                term_vectors
+                    .iter()
+                    .map(|(term, _)| term.to_string())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            term_vectors,
+            vec![vec!["document", "second"], vec!["document", "third"]]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,112 @@
+use super::{TermOccurrence, TermVectors};
+use crate::common::{BinarySerializable, VInt};
+use crate::directory::FileSlice;
+use crate::schema::Field;
+use crate::store::{StoreReader, StoredFieldCompression};
+use crate::DocId;
+use std::collections::BTreeMap;
+use std::io;
+
+/// Reads the term vectors of a segment.
+#[derive(Clone)]
+pub(crate) struct TermVectorsReader {
+    store_reader: StoreReader,
+}
+
+impl TermVectorsReader {
+    pub fn open(
+        file: FileSlice,
+        compression: StoredFieldCompression,
+    ) -> io::Result<TermVectorsReader> {
+        let store_reader = StoreReader::open_with_compression(file, compression)?;
+        Ok(TermVectorsReader { store_reader })
+    }
+
+    pub(crate) fn store_reader(&self) -> &StoreReader {
+        &self.store_reader
+    }
+
+    pub fn compression(&self) -> StoredFieldCompression {
+        self.store_reader.compression()
+    }
+
+    /// Returns the serialized record of a document.
+    pub fn get_document_bytes(&self, doc_id: DocId) -> crate::Result<Vec<u8>> {
+        self.store_reader.get_document_bytes(doc_id)
+    }
+
+    /// Returns the term vectors of the `field` of a document.
+    pub fn get(&self, doc_id: DocId, field: Field) -> crate::Result<TermVectors> {
+        let doc_bytes = self.store_reader.get_document_bytes(doc_id)?;
+        Ok(deserialize_field(&doc_bytes, field)?)
+    }
+}
+
+fn read_vint(cursor: &mut &[u8]) -> io::Result<u64> {
+    Ok(VInt::deserialize(cursor)?.val())
+}
+
+fn truncated_record_error() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        "Term vectors record is truncated.",
+    )
+}
+
+fn deserialize_field(doc_bytes: &[u8], field: Field) -> io::Result<TermVectors> {
+    let mut cursor = doc_bytes;
+    let num_fields = read_vint(&mut cursor)?;
+    for _ in 0..num_fields {
+        let field_id = read_vint(&mut cursor)? as u32;
+        let num_bytes = read_vint(&mut cursor)? as usize;
+        if num_bytes > cursor.len() {
+            return Err(truncated_record_error());
+        }
+        let (field_bytes, rest) = cursor.split_at(num_bytes);
+        if field_id == field.field_id() {
+            return deserialize_terms(field_bytes);
+        }
+        cursor = rest;
+    }
+    Ok(TermVectors::default())
+}
+
+fn deserialize_terms(mut cursor: &[u8]) -> io::Result<TermVectors> {
+    let num_terms = read_vint(&mut cursor)? as usize;
+    let mut terms: Vec<(String, Vec<TermOccurrence>)> =
+        Vec::with_capacity(num_terms.min(cursor.len()));
+    for _ in 0..num_terms {
+        let term = String::deserialize(&mut cursor)?;
+        let num_occurrences = read_vint(&mut cursor)? as usize;
+        // Each occurrence takes at least one byte in each of the three columns.
+        if num_occurrences > cursor.len() {
+            return Err(truncated_record_error());
+        }
+        let occurrence = TermOccurrence {
+            position: 0,
+            start_offset: 0,
+            end_offset: 0,
+        };
+        terms.push((term, vec![occurrence; num_occurrences]));
+    }
+    for (_, occurrences) in &mut terms {
+        let mut position = 0u32;
+        for occurrence in occurrences.iter_mut() {
+            position += read_vint(&mut cursor)? as u32;
+            occurrence.position = position;
+        }
+    }
+    for (_, occurrences) in &mut terms {
+        for occurrence in occurrences.iter_mut() {
+            occurrence.start_offset = read_vint(&mut cursor)? as usize;
+        }
+    }
+    for (_, occurrences) in &mut terms {
+        for occurrence in occurrences.iter_mut() {
+            occurrence.end_offset = occurrence.start_offset + read_vint(&mut cursor)? as usize;
+        }
+    }
+    Ok(TermVectors::from_terms(
+        terms.into_iter().collect::<BTreeMap<_, _>>(),
+    ))
+}
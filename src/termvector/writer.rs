@@ -0,0 +1,162 @@
+use super::reader::TermVectorsReader;
+use super::TermOccurrence;
+use crate::common::{BinarySerializable, VInt};
+use crate::schema::Field;
+use crate::store::{StoreWriter, StoredFieldCompression};
+use crate::tokenizer::{Token, TokenStream, MAX_TOKEN_LEN};
+use std::collections::BTreeMap;
+use std::io;
+
+/// Writes the term vectors of a segment.
+///
+/// A record is written for each document, even if it has no term vectors,
+/// so that the record of a document can be looked up by its `DocId`.
+pub(crate) struct TermVectorsWriter {
+    store_writer: StoreWriter,
+    doc_fields: Vec<(Field, BTreeMap<String, Vec<TermOccurrence>>)>,
+    buffer: Vec<u8>,
+}
+
+impl TermVectorsWriter {
+    pub fn new(store_writer: StoreWriter) -> TermVectorsWriter {
+        TermVectorsWriter {
+            store_writer,
+            doc_fields: Vec::new(),
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Returns the compression of the blocks of the term vectors.
+    pub fn compression(&self) -> StoredFieldCompression {
+        self.store_writer.compression()
+    }
+
+    /// Records a token of the current document.
+    ///
+    /// The tokens of a field must be recorded in a row, by increasing position.
+    pub fn record(&mut self, field: Field, token: &Token) {
+        // Such tokens are not indexed either.
+        if token.text.len() > MAX_TOKEN_LEN {
+            return;
+        }
+        if self.doc_fields.last().map(|(last_field, _)| *last_field) != Some(field) {
+            self.doc_fields.push((field, BTreeMap::new()));
+        }
+        let (_, terms) = self.doc_fields.last_mut().unwrap();
+        let occurrence = TermOccurrence {
+            position: token.position as u32,
+            start_offset: token.offset_from,
+            end_offset: token.offset_to,
+        };
+        if let Some(occurrences) = terms.get_mut(&token.text) {
+            occurrences.push(occurrence);
+        } else {
+            terms.insert(token.text.clone(), vec![occurrence]);
+        }
+    }
+
+    /// Writes the record of the current document.
+    pub fn end_doc(&mut self) -> io::Result<()> {
+        self.buffer.clear();
+        serialize_doc(&self.doc_fields, &mut self.buffer)?;
+        self.doc_fields.clear();
+        self.store_writer.store_bytes(&self.buffer)
+    }
+
+    /// Writes a document record, as read by `TermVectorsReader::get_document_bytes`.
+    pub fn store_bytes(&mut self, doc_bytes: &[u8]) -> io::Result<()> {
+        self.store_writer.store_bytes(doc_bytes)
+    }
+
+    /// Stacks the records of a reader on top of the records written so far.
+    pub fn stack(&mut self, reader: &TermVectorsReader) -> io::Result<()> {
+        self.store_writer.stack(reader.store_reader())
+    }
+
+    pub fn close(self) -> io::Result<()> {
+        self.store_writer.close()
+    }
+}
+
+/// Wraps a `TokenStream` to record its tokens in a `TermVectorsWriter`.
+pub(crate) struct TermVectorsTokenStream<'a> {
+    token_stream: &'a mut dyn TokenStream,
+    field: Field,
+    term_vectors_writer: &'a mut TermVectorsWriter,
+}
+
+impl<'a> TermVectorsTokenStream<'a> {
+    pub fn new(
+        token_stream: &'a mut dyn TokenStream,
+        field: Field,
+        term_vectors_writer: &'a mut TermVectorsWriter,
+    ) -> TermVectorsTokenStream<'a> {
+        TermVectorsTokenStream {
+            token_stream,
+            field,
+            term_vectors_writer,
+        }
+    }
+}
+
+impl<'a> TokenStream for TermVectorsTokenStream<'a> {
+    fn advance(&mut self) -> bool {
+        if !self.token_stream.advance() {
+            return false;
+        }
+        self.term_vectors_writer
+            .record(self.field, self.token_stream.token());
+        true
+    }
+
+    fn token(&self) -> &Token {
+        self.token_stream.token()
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.token_stream.token_mut()
+    }
+}
+
+/// Serializes the term vectors of a document.
+///
+/// For each field, the field id and the length of the field record are followed
+/// by the terms with their number of occurrences, and then by three columns
+/// listing the positions (delta-encoded within each term), the start offsets
+/// and the lengths of the occurrences.
+fn serialize_doc(
+    doc_fields: &[(Field, BTreeMap<String, Vec<TermOccurrence>>)],
+    output: &mut Vec<u8>,
+) -> io::Result<()> {
+    VInt(doc_fields.len() as u64).serialize(output)?;
+    let mut field_buffer: Vec<u8> = Vec::new();
+    for (field, terms) in doc_fields {
+        field_buffer.clear();
+        VInt(terms.len() as u64).serialize(&mut field_buffer)?;
+        for (term, occurrences) in terms {
+            term.serialize(&mut field_buffer)?;
+            VInt(occurrences.len() as u64).serialize(&mut field_buffer)?;
+        }
+        for occurrences in terms.values() {
+            let mut previous_position = 0u32;
+            for occurrence in occurrences {
+                VInt(u64::from(occurrence.position - previous_position))
+                    .serialize(&mut field_buffer)?;
+                previous_position = occurrence.position;
+            }
+        }
+        for occurrence in terms.values().flatten() {
+            VInt(occurrence.start_offset as u64).serialize(&mut field_buffer)?;
+        }
+        for occurrence in terms.values().flatten() {
+            let len = occurrence
+                .end_offset
+                .saturating_sub(occurrence.start_offset);
+            VInt(len as u64).serialize(&mut field_buffer)?;
+        }
+        VInt(u64::from(field.field_id())).serialize(output)?;
+        VInt(field_buffer.len() as u64).serialize(output)?;
+        output.extend_from_slice(&field_buffer);
+    }
+    Ok(())
+}
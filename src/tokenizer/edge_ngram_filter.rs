@@ -0,0 +1,273 @@
+//! # Example
+//! ```rust
+//! use tantivy::tokenizer::*;
+//!
+//! let tokenizer = TextAnalyzer::from(SimpleTokenizer)
+//!   .filter(LowerCaser)
+//!   .filter(EdgeNgramFilter::new(2, 3, EdgeNgramSide::Front));
+//!
+//! let mut stream = tokenizer.token_stream("Hello");
+//! assert_eq!(stream.next().unwrap().text, "he");
+//! assert_eq!(stream.next().unwrap().text, "hel");
+//! assert!(stream.next().is_none());
+//! ```
+//!
+use super::{Token, TokenFilter, TokenStream};
+use crate::tokenizer::BoxTokenStream;
+
+/// Side of the token from which the edge n-grams are computed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EdgeNgramSide {
+    /// The n-grams are the prefixes of the token.
+    Front,
+    /// The n-grams are the suffixes of the token.
+    Back,
+}
+
+/// `EdgeNgramFilter` replaces each token by its n-grams anchored
+/// at the front (or the back) of the token.
+///
+/// The size of the n-grams is expressed in number of characters,
+/// ranging from `min_gram` to `max_gram`. Tokens shorter than `min_gram`
+/// are removed.
+/// All of the n-grams of a token have the same position as the original token.
+/// Their offsets are narrowed to the n-gram, assuming the token text
+/// has the same length as the original text.
+///
+/// Example: `hεllo` would be filtered as (min_gram: 2, max_gram: 4, side: `Front`)
+///
+/// | Term     | hε  | hεl | hεll |
+/// |----------|-----|-----|------|
+/// | Position | 0   | 0   | 0    |
+/// | Offsets  | 0,3 | 0,4 | 0,5  |
+///
+/// # Index analyzer vs query analyzer
+///
+/// This filter is meant to build autocomplete: at indexing time, all of the
+/// prefixes of the words are indexed, so that a prefix typed by a user
+/// can be searched as a plain term.
+///
+/// The user input, on the other hand, must not go through the filter:
+/// `hel` would otherwise be turned into `he` and `hel`, and match
+/// `help` as well as `heat`. The user input should therefore be processed by
+/// a query analyzer that is identical to the index analyzer, minus the
+/// `EdgeNgramFilter`.
+///
+/// ```rust
+/// use tantivy::collector::Count;
+/// use tantivy::query::TermQuery;
+/// use tantivy::schema::{IndexRecordOption, Schema, TextFieldIndexing, TextOptions};
+/// use tantivy::tokenizer::*;
+/// use tantivy::{doc, Index, Term};
+///
+/// # fn main() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let text_options = TextOptions::default().set_indexing_options(
+///     TextFieldIndexing::default()
+///         .set_tokenizer("autocomplete")
+///         .set_index_option(IndexRecordOption::Basic),
+/// );
+/// let title = schema_builder.add_text_field("title", text_options);
+/// let index = Index::create_in_ram(schema_builder.build());
+///
+/// // The index analyzer emits the prefixes of the words.
+/// let index_analyzer = TextAnalyzer::from(SimpleTokenizer)
+///     .filter(LowerCaser)
+///     .filter(EdgeNgramFilter::new(1, 10, EdgeNgramSide::Front));
+/// index.tokenizers().register("autocomplete", index_analyzer);
+///
+/// let mut index_writer = index.writer_with_num_threads(1, 10_000_000)?;
+/// index_writer.add_document(doc!(title => "Help"));
+/// index_writer.add_document(doc!(title => "Heat"));
+/// index_writer.commit()?;
+///
+/// // The query analyzer does not.
+/// let query_analyzer = TextAnalyzer::from(SimpleTokenizer).filter(LowerCaser);
+/// let mut token_stream = query_analyzer.token_stream("Hel");
+/// let token = token_stream.next().unwrap();
+/// let query = TermQuery::new(
+///     Term::from_field_text(title, &token.text),
+///     IndexRecordOption::Basic,
+/// );
+///
+/// let searcher = index.reader()?.searcher();
+/// assert_eq!(searcher.search(&query, &Count)?, 1);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct EdgeNgramFilter {
+    min_gram: usize,
+    max_gram: usize,
+    side: EdgeNgramSide,
+}
+
+impl EdgeNgramFilter {
+    /// Creates a new `EdgeNgramFilter` emitting n-grams of `min_gram` to
+    /// `max_gram` characters, taken from the given `side` of the tokens.
+    pub fn new(min_gram: usize, max_gram: usize, side: EdgeNgramSide) -> EdgeNgramFilter {
+        assert!(min_gram > 0, "min_gram must be greater than 0");
+        assert!(
+            min_gram <= max_gram,
+            "min_gram must not be greater than max_gram"
+        );
+        EdgeNgramFilter {
+            min_gram,
+            max_gram,
+            side,
+        }
+    }
+}
+
+impl TokenFilter for EdgeNgramFilter {
+    fn transform<'a>(&self, token_stream: BoxTokenStream<'a>) -> BoxTokenStream<'a> {
+        BoxTokenStream::from(EdgeNgramFilterStream {
+            tail: token_stream,
+            min_gram: self.min_gram,
+            max_gram: self.max_gram,
+            side: self.side,
+            char_frontiers: Vec::new(),
+            gram_len: self.min_gram,
+            token: Token::default(),
+        })
+    }
+}
+
+pub struct EdgeNgramFilterStream<'a> {
+    tail: BoxTokenStream<'a>,
+    min_gram: usize,
+    max_gram: usize,
+    side: EdgeNgramSide,
+    /// Byte offsets of the character boundaries of the current tail token,
+    /// including `0` and the length of its text.
+    char_frontiers: Vec<usize>,
+    /// Number of characters of the next n-gram.
+    gram_len: usize,
+    token: Token,
+}
+
+impl<'a> EdgeNgramFilterStream<'a> {
+    fn num_chars(&self) -> usize {
+        self.char_frontiers.len().saturating_sub(1)
+    }
+
+    fn emit_gram(&mut self) {
+        let num_chars = self.num_chars();
+        let source = self.tail.token();
+        let (start, end) = match self.side {
+            EdgeNgramSide::Front => (0, self.char_frontiers[self.gram_len]),
+            EdgeNgramSide::Back => (
+                self.char_frontiers[num_chars - self.gram_len],
+                source.text.len(),
+            ),
+        };
+        self.token.text.clear();
+        self.token.text.push_str(&source.text[start..end]);
+        self.token.position = source.position;
+        self.token.position_length = source.position_length;
+        match self.side {
+            EdgeNgramSide::Front => {
+                self.token.offset_from = source.offset_from;
+                self.token.offset_to = (source.offset_from + end).min(source.offset_to);
+            }
+            EdgeNgramSide::Back => {
+                let suffix_len = source.text.len() - start;
+                self.token.offset_from = source
+                    .offset_to
+                    .saturating_sub(suffix_len)
+                    .max(source.offset_from);
+                self.token.offset_to = source.offset_to;
+            }
+        }
+    }
+}
+
+impl<'a> TokenStream for EdgeNgramFilterStream<'a> {
+    fn advance(&mut self) -> bool {
+        loop {
+            if self.gram_len <= self.max_gram && self.gram_len <= self.num_chars() {
+                self.emit_gram();
+                self.gram_len += 1;
+                return true;
+            }
+            if !self.tail.advance() {
+                return false;
+            }
+            let text = &self.tail.token().text;
+            self.char_frontiers.clear();
+            self.char_frontiers
+                .extend(text.char_indices().map(|(offset, _)| offset));
+            self.char_frontiers.push(text.len());
+            self.gram_len = self.min_gram;
+        }
+    }
+
+    fn token(&self) -> &Token {
+        &self.token
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        &mut self.token
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EdgeNgramFilter, EdgeNgramSide};
+    use crate::tokenizer::tests::assert_token;
+    use crate::tokenizer::{SimpleTokenizer, TextAnalyzer, Token};
+
+    fn edge_ngram_helper(
+        text: &str,
+        min_gram: usize,
+        max_gram: usize,
+        side: EdgeNgramSide,
+    ) -> Vec<Token> {
+        let mut tokens = vec![];
+        let mut token_stream = TextAnalyzer::from(SimpleTokenizer)
+            .filter(EdgeNgramFilter::new(min_gram, max_gram, side))
+            .token_stream(text);
+        while token_stream.advance() {
+            tokens.push(token_stream.token().clone());
+        }
+        tokens
+    }
+
+    #[test]
+    fn test_edge_ngram_front() {
+        let tokens = edge_ngram_helper("hello a world", 2, 3, EdgeNgramSide::Front);
+        assert_eq!(tokens.len(), 4);
+        assert_token(&tokens[0], 0, "he", 0, 2);
+        assert_token(&tokens[1], 0, "hel", 0, 3);
+        assert_token(&tokens[2], 2, "wo", 8, 10);
+        assert_token(&tokens[3], 2, "wor", 8, 11);
+    }
+
+    #[test]
+    fn test_edge_ngram_back() {
+        let tokens = edge_ngram_helper("hello ab", 1, 10, EdgeNgramSide::Back);
+        assert_eq!(tokens.len(), 7);
+        assert_token(&tokens[0], 0, "o", 4, 5);
+        assert_token(&tokens[1], 0, "lo", 3, 5);
+        assert_token(&tokens[4], 0, "hello", 0, 5);
+        assert_token(&tokens[5], 1, "b", 7, 8);
+        assert_token(&tokens[6], 1, "ab", 6, 8);
+    }
+
+    #[test]
+    fn test_edge_ngram_multibyte() {
+        let tokens = edge_ngram_helper("hεllo", 1, 2, EdgeNgramSide::Front);
+        assert_eq!(tokens.len(), 2);
+        assert_token(&tokens[0], 0, "h", 0, 1);
+        assert_token(&tokens[1], 0, "hε", 0, 3);
+        let tokens = edge_ngram_helper("ĥεllö", 2, 2, EdgeNgramSide::Back);
+        assert_eq!(tokens.len(), 1);
+        assert_token(&tokens[0], 0, "lö", 5, 8);
+    }
+
+    #[test]
+    #[should_panic(expected = "min_gram must not be greater than max_gram")]
+    fn test_edge_ngram_invalid_sizes() {
+        EdgeNgramFilter::new(3, 2, EdgeNgramSide::Front);
+    }
+}
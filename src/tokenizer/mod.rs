@@ -119,14 +119,19 @@
 //!
 mod alphanum_only;
 mod ascii_folding_filter;
+mod edge_ngram_filter;
 mod facet_tokenizer;
 mod lower_caser;
 mod ngram_tokenizer;
 mod raw_tokenizer;
 mod remove_long;
+mod remove_short;
+mod shingle_filter;
 mod simple_tokenizer;
 mod stemmer;
 mod stop_word_filter;
+mod synonym_filter;
+mod token_enricher;
 mod token_stream_chain;
 mod tokenized_string;
 mod tokenizer;
@@ -134,22 +139,30 @@ mod tokenizer_manager;
 
 pub use self::alphanum_only::AlphaNumOnlyFilter;
 pub use self::ascii_folding_filter::AsciiFoldingFilter;
+pub use self::edge_ngram_filter::{EdgeNgramFilter, EdgeNgramSide};
 pub use self::facet_tokenizer::FacetTokenizer;
 pub use self::lower_caser::LowerCaser;
 pub use self::ngram_tokenizer::NgramTokenizer;
 pub use self::raw_tokenizer::RawTokenizer;
 pub use self::remove_long::RemoveLongFilter;
+pub use self::remove_short::RemoveShortFilter;
+pub use self::shingle_filter::ShingleFilter;
 pub use self::simple_tokenizer::SimpleTokenizer;
 pub use self::stemmer::{Language, Stemmer};
-pub use self::stop_word_filter::StopWordFilter;
+pub use self::stop_word_filter::{StopLanguage, StopWordFilter};
+pub use self::synonym_filter::{SynonymFilter, SynonymMap};
+pub use self::token_enricher::{
+    DictionaryMatchEnricher, EnrichmentFilter, PassthroughEnricher, TokenEnricher,
+};
 pub(crate) use self::token_stream_chain::TokenStreamChain;
 
 pub use self::tokenized_string::{PreTokenizedStream, PreTokenizedString};
 pub use self::tokenizer::{
-    BoxTokenFilter, BoxTokenStream, TextAnalyzer, Token, TokenFilter, TokenStream, Tokenizer,
+    AnnotatedToken, BoxTokenFilter, BoxTokenStream, TextAnalyzer, Token, TokenFilter, TokenStream,
+    Tokenizer,
 };
 
-pub use self::tokenizer_manager::TokenizerManager;
+pub use self::tokenizer_manager::{AnalyzerDescription, TokenizerManager};
 
 /// Maximum authorized len (in bytes) for a token.
 ///
@@ -161,7 +174,8 @@ pub const MAX_TOKEN_LEN: usize = u16::max_value() as usize - 4;
 #[cfg(test)]
 pub mod tests {
     use super::{
-        Language, LowerCaser, RemoveLongFilter, SimpleTokenizer, Stemmer, Token, TokenizerManager,
+        Language, LowerCaser, RemoveLongFilter, SimpleTokenizer, Stemmer, StopWordFilter, Token,
+        TokenizerManager,
     };
     use crate::tokenizer::TextAnalyzer;
 
@@ -252,6 +266,27 @@ pub mod tests {
         assert_token(&tokens[2], 2, "φορολογουμεν", 37, 63);
     }
 
+    #[test]
+    fn test_debug_annotated_token_stream() {
+        let analyzer = TextAnalyzer::from(SimpleTokenizer)
+            .filter(LowerCaser)
+            .filter(StopWordFilter::remove(vec!["the".to_string()]))
+            .filter(RemoveLongFilter::limit(40));
+        let text = "The QUICK fox";
+        let annotated_tokens = analyzer.debug_annotated_token_stream(text);
+        let tokens: Vec<Token> = annotated_tokens
+            .iter()
+            .map(|annotated_token| annotated_token.token.clone())
+            .collect();
+        assert_eq!(tokens, analyzer.debug_token_stream(text));
+        assert_token(&tokens[0], 1, "quick", 4, 9);
+        let stages: Vec<&str> = annotated_tokens
+            .iter()
+            .map(|annotated_token| annotated_token.stage)
+            .collect();
+        assert_eq!(stages, vec!["LowerCaser", "SimpleTokenizer"]);
+    }
+
     #[test]
     fn test_tokenizer_empty() {
         let tokenizer_manager = TokenizerManager::default();
@@ -0,0 +1,74 @@
+//! # Example
+//! ```rust
+//! use tantivy::tokenizer::*;
+//!
+//! let tokenizer = TextAnalyzer::from(SimpleTokenizer)
+//!   .filter(RemoveShortFilter::limit(2));
+//!
+//! let mut stream = tokenizer.token_stream("a nice day");
+//! // because `a` is less than 2 characters, it is filtered
+//! // out of the token stream.
+//! assert_eq!(stream.next().unwrap().text, "nice");
+//! assert_eq!(stream.next().unwrap().text, "day");
+//! assert!(stream.next().is_none());
+//! ```
+//!
+use super::{Token, TokenFilter, TokenStream};
+use crate::tokenizer::BoxTokenStream;
+
+/// `RemoveShortFilter` removes tokens that are shorter
+/// than a given number of bytes (in UTF-8 representation).
+///
+/// It is the counterpart of the `RemoveLongFilter`, and is useful to get rid of
+/// single characters and punctuation. As for any filter, the length is the one of
+/// the token produced by the preceding filters.
+#[derive(Clone)]
+pub struct RemoveShortFilter {
+    length_limit: usize,
+}
+
+impl RemoveShortFilter {
+    /// Creates a `RemoveShortFilter` given a limit in bytes of the UTF-8 representation.
+    pub fn limit(length_limit: usize) -> RemoveShortFilter {
+        RemoveShortFilter { length_limit }
+    }
+}
+
+impl<'a> RemoveShortFilterStream<'a> {
+    fn predicate(&self, token: &Token) -> bool {
+        token.text.len() >= self.token_length_limit
+    }
+}
+
+impl TokenFilter for RemoveShortFilter {
+    fn transform<'a>(&self, token_stream: BoxTokenStream<'a>) -> BoxTokenStream<'a> {
+        BoxTokenStream::from(RemoveShortFilterStream {
+            token_length_limit: self.length_limit,
+            tail: token_stream,
+        })
+    }
+}
+
+pub struct RemoveShortFilterStream<'a> {
+    token_length_limit: usize,
+    tail: BoxTokenStream<'a>,
+}
+
+impl<'a> TokenStream for RemoveShortFilterStream<'a> {
+    fn advance(&mut self) -> bool {
+        while self.tail.advance() {
+            if self.predicate(self.tail.token()) {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn token(&self) -> &Token {
+        self.tail.token()
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.tail.token_mut()
+    }
+}
@@ -0,0 +1,269 @@
+//! # Example
+//! ```rust
+//! use tantivy::tokenizer::*;
+//!
+//! let tokenizer = TextAnalyzer::from(SimpleTokenizer)
+//!   .filter(LowerCaser)
+//!   .filter(ShingleFilter::new(2, 2, false));
+//!
+//! let mut stream = tokenizer.token_stream("Please divide this");
+//! assert_eq!(stream.next().unwrap().text, "please_divide");
+//! assert_eq!(stream.next().unwrap().text, "divide_this");
+//! assert!(stream.next().is_none());
+//! ```
+//!
+use super::{Token, TokenFilter, TokenStream};
+use crate::tokenizer::BoxTokenStream;
+use std::collections::VecDeque;
+
+/// Separator between the words of a shingle.
+const SHINGLE_SEPARATOR: char = '_';
+
+/// `ShingleFilter` concatenates adjacent tokens into shingles (word n-grams).
+///
+/// Shingles are made of `min_shingle_size` to `max_shingle_size` tokens,
+/// joined by `_`. Indexing shingles makes it possible to match
+/// consecutive words as a single term, without relying on positions.
+///
+/// A shingle has the position and the starting offset of its first token, and its
+/// `position_length` is its number of tokens. If `output_unigrams` is true, the
+/// original tokens are emitted as well, each one before the shingles it starts.
+/// The positions of the unigrams are hence unchanged.
+///
+/// Example: `please divide this` would be filtered as (min_shingle_size: 2,
+/// max_shingle_size: 3, output_unigrams: true)
+///
+/// | Term     | please | please_divide | please_divide_this | divide | divide_this | this  |
+/// |----------|--------|---------------|--------------------|--------|-------------|-------|
+/// | Position | 0      | 0             | 0                  | 1      | 1           | 2     |
+/// | Offsets  | 0,6    | 0,13          | 0,18               | 7,13   | 7,18        | 14,18 |
+///
+/// By default, the positions left empty by a previous filter (e.g. a `StopWordFilter`)
+/// are ignored, and the tokens around them are joined as if they were adjacent.
+/// If a fill token is set, see [`set_fill_token`](#method.set_fill_token), it takes
+/// the place of the missing tokens, and of the tokens following the end of the token stream.
+///
+/// The values of a multi-valued field are filtered separately, so that a shingle
+/// never crosses the boundary between two values.
+#[derive(Clone)]
+pub struct ShingleFilter {
+    min_shingle_size: usize,
+    max_shingle_size: usize,
+    output_unigrams: bool,
+    fill_token: Option<String>,
+}
+
+impl ShingleFilter {
+    /// Creates a new `ShingleFilter`.
+    pub fn new(
+        min_shingle_size: usize,
+        max_shingle_size: usize,
+        output_unigrams: bool,
+    ) -> ShingleFilter {
+        assert!(
+            min_shingle_size >= 2,
+            "min_shingle_size must be greater than 1"
+        );
+        assert!(
+            min_shingle_size <= max_shingle_size,
+            "min_shingle_size must not be greater than max_shingle_size"
+        );
+        ShingleFilter {
+            min_shingle_size,
+            max_shingle_size,
+            output_unigrams,
+            fill_token: None,
+        }
+    }
+
+    /// Sets the token used in place of the missing tokens.
+    pub fn set_fill_token(mut self, fill_token: &str) -> ShingleFilter {
+        self.fill_token = Some(fill_token.to_string());
+        self
+    }
+}
+
+impl TokenFilter for ShingleFilter {
+    fn transform<'a>(&self, token_stream: BoxTokenStream<'a>) -> BoxTokenStream<'a> {
+        BoxTokenStream::from(ShingleFilterStream {
+            filter: self.clone(),
+            tail: token_stream,
+            tail_exhausted: false,
+            window: VecDeque::new(),
+            slots: Vec::new(),
+            output: VecDeque::new(),
+            token: Token::default(),
+        })
+    }
+}
+
+pub struct ShingleFilterStream<'a> {
+    filter: ShingleFilter,
+    tail: BoxTokenStream<'a>,
+    tail_exhausted: bool,
+    /// Tokens read from the tail. The first one is the start of the next shingles.
+    window: VecDeque<Token>,
+    /// Words of the longest shingle starting at the first token of the window.
+    /// `None` stands for the fill token.
+    slots: Vec<Option<usize>>,
+    output: VecDeque<Token>,
+    token: Token,
+}
+
+impl<'a> ShingleFilterStream<'a> {
+    fn fill_window(&mut self) {
+        while !self.tail_exhausted && self.window.len() < self.filter.max_shingle_size {
+            if self.tail.advance() {
+                self.window.push_back(self.tail.token().clone());
+            } else {
+                self.tail_exhausted = true;
+            }
+        }
+    }
+
+    /// Computes the words of the longest shingle starting at the first token of the window.
+    fn compute_slots(&mut self) {
+        let max_shingle_size = self.filter.max_shingle_size;
+        let has_fill_token = self.filter.fill_token.is_some();
+        self.slots.clear();
+        self.slots.push(Some(0));
+        for (ord, token) in self.window.iter().enumerate().skip(1) {
+            if has_fill_token {
+                let previous_position = self.window[ord - 1].position;
+                let num_missing = token.position.saturating_sub(previous_position + 1);
+                for _ in 0..num_missing {
+                    self.slots.push(None);
+                }
+            }
+            self.slots.push(Some(ord));
+        }
+        if has_fill_token {
+            // Pads the shingles at the end of the token stream.
+            while self.slots.len() < max_shingle_size {
+                self.slots.push(None);
+            }
+        }
+        self.slots.truncate(max_shingle_size);
+    }
+
+    fn emit_shingles(&mut self) {
+        let first_token = &self.window[0];
+        if self.filter.output_unigrams {
+            self.output.push_back(first_token.clone());
+        }
+        let fill_token = self.filter.fill_token.as_deref().unwrap_or("");
+        let mut text = String::new();
+        let mut offset_to = first_token.offset_to;
+        for (num_words, slot) in self.slots.iter().enumerate() {
+            if num_words > 0 {
+                text.push(SHINGLE_SEPARATOR);
+            }
+            match *slot {
+                Some(ord) => {
+                    let token = &self.window[ord];
+                    text.push_str(&token.text);
+                    offset_to = offset_to.max(token.offset_to);
+                }
+                None => text.push_str(fill_token),
+            }
+            let shingle_size = num_words + 1;
+            if shingle_size >= self.filter.min_shingle_size {
+                self.output.push_back(Token {
+                    offset_from: first_token.offset_from,
+                    offset_to,
+                    position: first_token.position,
+                    text: text.clone(),
+                    position_length: shingle_size,
+                });
+            }
+        }
+    }
+}
+
+impl<'a> TokenStream for ShingleFilterStream<'a> {
+    fn advance(&mut self) -> bool {
+        loop {
+            if let Some(token) = self.output.pop_front() {
+                self.token = token;
+                return true;
+            }
+            self.fill_window();
+            if self.window.is_empty() {
+                return false;
+            }
+            self.compute_slots();
+            self.emit_shingles();
+            self.window.pop_front();
+        }
+    }
+
+    fn token(&self) -> &Token {
+        &self.token
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        &mut self.token
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ShingleFilter;
+    use crate::tokenizer::tests::assert_token;
+    use crate::tokenizer::{SimpleTokenizer, StopWordFilter, TextAnalyzer, Token};
+
+    fn shingle_helper(texts: &[&str], shingle_filter: ShingleFilter) -> Vec<Token> {
+        let mut tokens = vec![];
+        let mut token_stream = TextAnalyzer::from(SimpleTokenizer)
+            .filter(StopWordFilter::remove(vec!["the".to_string()]))
+            .filter(shingle_filter)
+            .token_stream_texts(texts);
+        while token_stream.advance() {
+            tokens.push(token_stream.token().clone());
+        }
+        tokens
+    }
+
+    #[test]
+    fn test_shingle_filter() {
+        let tokens = shingle_helper(&["please divide this"], ShingleFilter::new(2, 3, true));
+        assert_eq!(tokens.len(), 6);
+        assert_token(&tokens[0], 0, "please", 0, 6);
+        assert_token(&tokens[1], 0, "please_divide", 0, 13);
+        assert_eq!(tokens[1].position_length, 2);
+        assert_token(&tokens[2], 0, "please_divide_this", 0, 18);
+        assert_eq!(tokens[2].position_length, 3);
+        assert_token(&tokens[3], 1, "divide", 7, 13);
+        assert_token(&tokens[4], 1, "divide_this", 7, 18);
+        assert_token(&tokens[5], 2, "this", 14, 18);
+    }
+
+    #[test]
+    fn test_shingle_filter_without_unigrams() {
+        let tokens = shingle_helper(&["a b c d"], ShingleFilter::new(3, 3, false));
+        assert_eq!(tokens.len(), 2);
+        assert_token(&tokens[0], 0, "a_b_c", 0, 5);
+        assert_token(&tokens[1], 1, "b_c_d", 2, 7);
+        assert!(shingle_helper(&["a"], ShingleFilter::new(2, 2, false)).is_empty());
+    }
+
+    #[test]
+    fn test_shingle_filter_fill_token() {
+        let shingle_filter = ShingleFilter::new(2, 2, false);
+        let tokens = shingle_helper(&["a the b"], shingle_filter.clone());
+        assert_eq!(tokens.len(), 1);
+        assert_token(&tokens[0], 0, "a_b", 0, 7);
+        let tokens = shingle_helper(&["a the b"], shingle_filter.set_fill_token("*"));
+        assert_eq!(tokens.len(), 2);
+        assert_token(&tokens[0], 0, "a_*", 0, 1);
+        assert_token(&tokens[1], 2, "b_*", 6, 7);
+    }
+
+    #[test]
+    fn test_shingle_filter_multivalued() {
+        let tokens = shingle_helper(&["a b", "c d"], ShingleFilter::new(2, 2, false));
+        assert_eq!(tokens.len(), 2);
+        assert_token(&tokens[0], 0, "a_b", 0, 3);
+        assert_token(&tokens[1], 2, "c_d", 3, 6);
+    }
+}
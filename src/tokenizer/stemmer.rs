@@ -2,6 +2,7 @@ use super::{Token, TokenFilter, TokenStream};
 use crate::tokenizer::BoxTokenStream;
 use rust_stemmers::{self, Algorithm};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 
 /// Available stemmer languages.
 #[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Copy, Clone)]
@@ -56,6 +57,9 @@ impl Language {
 /// `Stemmer` token filter. Several languages are supported, see `Language` for the available
 /// languages.
 /// Tokens are expected to be lowercased beforehand.
+///
+/// The stemmer only rewrites the text of the tokens: their offsets
+/// and positions are left untouched.
 #[derive(Clone)]
 pub struct Stemmer {
     stemmer_algorithm: Algorithm,
@@ -97,10 +101,10 @@ impl<'a> TokenStream for StemmerTokenStream<'a> {
         if !self.tail.advance() {
             return false;
         }
-        // TODO remove allocation
-        let stemmed_str: String = self.stemmer.stem(&self.token().text).into_owned();
-        self.token_mut().text.clear();
-        self.token_mut().text.push_str(&stemmed_str);
+        // The stemmer only allocates if the stem differs from the token text.
+        if let Cow::Owned(stemmed_str) = self.stemmer.stem(&self.tail.token().text) {
+            self.token_mut().text = stemmed_str;
+        }
         true
     }
 
@@ -112,3 +116,54 @@ impl<'a> TokenStream for StemmerTokenStream<'a> {
         self.tail.token_mut()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Language, Stemmer};
+    use crate::tokenizer::tests::assert_token;
+    use crate::tokenizer::{LowerCaser, SimpleTokenizer, TextAnalyzer, Token};
+
+    fn stem_helper(language: Language, text: &str) -> Vec<Token> {
+        let mut tokens = vec![];
+        let mut token_stream = TextAnalyzer::from(SimpleTokenizer)
+            .filter(LowerCaser)
+            .filter(Stemmer::new(language))
+            .token_stream(text);
+        while token_stream.advance() {
+            tokens.push(token_stream.token().clone());
+        }
+        tokens
+    }
+
+    fn stems(language: Language, text: &str) -> Vec<String> {
+        stem_helper(language, text)
+            .into_iter()
+            .map(|token| token.text)
+            .collect()
+    }
+
+    #[test]
+    fn test_stemmer_languages() {
+        assert_eq!(stems(Language::English, "Running dogs"), vec!["run", "dog"]);
+        assert_eq!(
+            stems(Language::French, "chevaux continuellement"),
+            vec!["cheval", "continuel"]
+        );
+        assert_eq!(stems(Language::German, "Häuser"), vec!["haus"]);
+        assert_eq!(stems(Language::Spanish, "corriendo"), vec!["corr"]);
+        assert_eq!(stems(Language::Italian, "abbandonata"), vec!["abbandon"]);
+        assert_eq!(stems(Language::Portuguese, "correndo"), vec!["corr"]);
+        assert_eq!(stems(Language::Dutch, "lichamelijk"), vec!["licham"]);
+        assert_eq!(stems(Language::Swedish, "flickorna"), vec!["flick"]);
+        assert_eq!(stems(Language::Russian, "книгами"), vec!["книг"]);
+    }
+
+    #[test]
+    fn test_stemmer_keeps_offsets() {
+        let tokens = stem_helper(Language::English, "The Running dogs");
+        assert_eq!(tokens.len(), 3);
+        assert_token(&tokens[0], 0, "the", 0, 3);
+        assert_token(&tokens[1], 1, "run", 4, 11);
+        assert_token(&tokens[2], 2, "dog", 12, 16);
+    }
+}
@@ -0,0 +1,207 @@
+//! # Example
+//! ```rust
+//! use tantivy::tokenizer::*;
+//!
+//! let tokenizer = TextAnalyzer::from(SimpleTokenizer)
+//!   .filter(StopWordFilter::remove(vec!["the".to_string(), "is".to_string()]));
+//!
+//! let mut stream = tokenizer.token_stream("the fox is crafty");
+//! assert_eq!(stream.next().unwrap().text, "fox");
+//! assert_eq!(stream.next().unwrap().text, "crafty");
+//! assert!(stream.next().is_none());
+//! ```
+//!
+//! Built-in lists are available for a few languages:
+//!
+//! ```rust
+//! use tantivy::tokenizer::*;
+//!
+//! let tokenizer = TextAnalyzer::from(SimpleTokenizer)
+//!   .filter(LowerCaser)
+//!   .filter(StopWordFilter::new(StopLanguage::French));
+//!
+//! let mut stream = tokenizer.token_stream("Le renard est rusé");
+//! {
+//!     let token = stream.next().unwrap();
+//!     assert_eq!(token.text, "renard");
+//!     assert_eq!(token.position, 1);
+//! }
+//! {
+//!     let token = stream.next().unwrap();
+//!     assert_eq!(token.text, "rusé");
+//!     assert_eq!(token.position, 3);
+//! }
+//! assert!(stream.next().is_none());
+//! ```
+mod stopwords;
+
+use super::{Token, TokenFilter, TokenStream};
+use crate::tokenizer::BoxTokenStream;
+use fnv::FnvHasher;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::hash::BuildHasherDefault;
+
+// configure our hashers for SPEED
+type StopWordHasher = BuildHasherDefault<FnvHasher>;
+type StopWordHashSet = HashSet<String, StopWordHasher>;
+
+/// Languages for which a built-in list of stop words is available.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[allow(missing_docs)]
+pub enum StopLanguage {
+    English,
+    French,
+    German,
+    Spanish,
+}
+
+impl StopLanguage {
+    fn words(self) -> &'static [&'static str] {
+        match self {
+            StopLanguage::English => stopwords::ENGLISH,
+            StopLanguage::French => stopwords::FRENCH,
+            StopLanguage::German => stopwords::GERMAN,
+            StopLanguage::Spanish => stopwords::SPANISH,
+        }
+    }
+}
+
+/// `TokenFilter` that removes stop words from a token stream
+///
+/// Removed tokens leave a gap in the positions of the token stream,
+/// so that a phrase query does not match across a removed stop word.
+/// Tokens are matched as is, and are expected to be lowercased beforehand.
+#[derive(Clone)]
+pub struct StopWordFilter {
+    words: StopWordHashSet,
+}
+
+impl StopWordFilter {
+    /// Creates a `StopWordFilter` given a list of words to remove
+    pub fn remove(words: Vec<String>) -> StopWordFilter {
+        let mut set = StopWordHashSet::default();
+
+        for word in words {
+            set.insert(word);
+        }
+
+        StopWordFilter { words: set }
+    }
+
+    /// Creates a `StopWordFilter` removing the built-in stop words of a given language.
+    pub fn new(language: StopLanguage) -> StopWordFilter {
+        StopWordFilter::remove(language.words().iter().map(|&s| s.to_string()).collect())
+    }
+
+    /// Creates a `StopWordFilter` given a set of words to remove
+    pub fn from_set(words: HashSet<String>) -> StopWordFilter {
+        StopWordFilter {
+            words: words.into_iter().collect(),
+        }
+    }
+}
+
+pub struct StopWordFilterStream<'a> {
+    words: StopWordHashSet,
+    tail: BoxTokenStream<'a>,
+}
+
+impl TokenFilter for StopWordFilter {
+    fn transform<'a>(&self, token_stream: BoxTokenStream<'a>) -> BoxTokenStream<'a> {
+        BoxTokenStream::from(StopWordFilterStream {
+            words: self.words.clone(),
+            tail: token_stream,
+        })
+    }
+}
+
+impl<'a> StopWordFilterStream<'a> {
+    fn predicate(&self, token: &Token) -> bool {
+        !self.words.contains(&token.text)
+    }
+}
+
+impl<'a> TokenStream for StopWordFilterStream<'a> {
+    fn advance(&mut self) -> bool {
+        while self.tail.advance() {
+            if self.predicate(self.tail.token()) {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn token(&self) -> &Token {
+        self.tail.token()
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.tail.token_mut()
+    }
+}
+
+impl Default for StopWordFilter {
+    fn default() -> StopWordFilter {
+        StopWordFilter::new(StopLanguage::English)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{StopLanguage, StopWordFilter};
+    use crate::tokenizer::tests::assert_token;
+    use crate::tokenizer::{LowerCaser, SimpleTokenizer, TextAnalyzer, Token};
+    use std::collections::HashSet;
+
+    fn stop_word_helper(stop_word_filter: StopWordFilter, text: &str) -> Vec<Token> {
+        let mut tokens = vec![];
+        let mut token_stream = TextAnalyzer::from(SimpleTokenizer)
+            .filter(LowerCaser)
+            .filter(stop_word_filter)
+            .token_stream(text);
+        while token_stream.advance() {
+            tokens.push(token_stream.token().clone());
+        }
+        tokens
+    }
+
+    #[test]
+    fn test_stop_word_filter_keeps_position_gaps() {
+        let tokens = stop_word_helper(StopWordFilter::default(), "The fox is at the door");
+        assert_eq!(tokens.len(), 2);
+        assert_token(&tokens[0], 1, "fox", 4, 7);
+        assert_token(&tokens[1], 5, "door", 18, 22);
+    }
+
+    #[test]
+    fn test_stop_word_filter_languages() {
+        let texts = |language, text| -> Vec<String> {
+            stop_word_helper(StopWordFilter::new(language), text)
+                .into_iter()
+                .map(|token| token.text)
+                .collect()
+        };
+        assert_eq!(texts(StopLanguage::English, "this is a fox"), vec!["fox"]);
+        assert_eq!(
+            texts(StopLanguage::French, "Où est le renard"),
+            vec!["où", "renard"]
+        );
+        assert_eq!(
+            texts(StopLanguage::German, "Der Fuchs ist schlau"),
+            vec!["fuchs", "schlau"]
+        );
+        assert_eq!(
+            texts(StopLanguage::Spanish, "El zorro es astuto"),
+            vec!["zorro", "astuto"]
+        );
+    }
+
+    #[test]
+    fn test_stop_word_filter_from_set() {
+        let words: HashSet<String> = vec!["fox".to_string()].into_iter().collect();
+        let tokens = stop_word_helper(StopWordFilter::from_set(words), "the fox");
+        assert_eq!(tokens.len(), 1);
+        assert_token(&tokens[0], 0, "the", 0, 3);
+    }
+}
@@ -0,0 +1,404 @@
+//! # Example
+//! ```rust
+//! use tantivy::tokenizer::*;
+//!
+//! let synonyms = SynonymMap::from_solr("sofa, couch, settee").unwrap();
+//! let tokenizer = TextAnalyzer::from(SimpleTokenizer)
+//!   .filter(LowerCaser)
+//!   .filter(SynonymFilter::new(synonyms, true));
+//!
+//! let mut stream = tokenizer.token_stream("Red sofa");
+//! assert_eq!(stream.next().unwrap().text, "red");
+//! assert_eq!(stream.next().unwrap().text, "sofa");
+//! {
+//!     let token = stream.next().unwrap();
+//!     assert_eq!(token.text, "couch");
+//!     assert_eq!(token.position, 1);
+//! }
+//! assert_eq!(stream.next().unwrap().text, "settee");
+//! assert!(stream.next().is_none());
+//! ```
+//!
+use super::{Token, TokenFilter, TokenStream};
+use crate::tokenizer::BoxTokenStream;
+use std::collections::{HashMap, VecDeque};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Separator between the words of a phrase, in the keys of the `SynonymMap`.
+const WORD_SEPARATOR: char = ' ';
+
+/// Maps words, or phrases of several words, to their synonyms.
+///
+/// A phrase is a sequence of words separated by whitespaces.
+/// Phrases are matched against the text of the tokens as is: if the
+/// `SynonymFilter` follows a `LowerCaser`, the phrases are expected
+/// to be lowercased.
+#[derive(Clone, Debug, Default)]
+pub struct SynonymMap {
+    synonyms: HashMap<String, Vec<Vec<String>>>,
+    max_phrase_len: usize,
+}
+
+fn split_words(phrase: &str) -> Vec<String> {
+    phrase.split_whitespace().map(ToString::to_string).collect()
+}
+
+fn split_phrases(phrases: &str) -> Vec<&str> {
+    phrases
+        .split(',')
+        .map(str::trim)
+        .filter(|phrase| !phrase.is_empty())
+        .collect()
+}
+
+impl SynonymMap {
+    /// Creates an empty `SynonymMap`.
+    pub fn new() -> SynonymMap {
+        SynonymMap::default()
+    }
+
+    /// Registers `synonym` as a synonym of `phrase`.
+    ///
+    /// The relation is not symmetric: `phrase` is not registered as
+    /// a synonym of `synonym`.
+    pub fn add_synonym(&mut self, phrase: &str, synonym: &str) {
+        let words = split_words(phrase);
+        let synonym_words = split_words(synonym);
+        if words.is_empty() || synonym_words.is_empty() {
+            return;
+        }
+        self.max_phrase_len = self.max_phrase_len.max(words.len());
+        let key = words.join(&WORD_SEPARATOR.to_string());
+        let synonyms = self.synonyms.entry(key).or_default();
+        if !synonyms.contains(&synonym_words) {
+            synonyms.push(synonym_words);
+        }
+    }
+
+    /// Parses synonyms expressed in the Solr format.
+    ///
+    /// Each line is either
+    /// - a list of equivalent phrases, separated by commas (e.g. `sofa, couch, settee`).
+    ///   Each phrase is registered as a synonym of all of the other phrases.
+    /// - an explicit mapping (e.g. `new york, big apple => ny`). The phrases on
+    ///   the right are registered as synonyms of each of the phrases on the left.
+    ///
+    /// Empty lines and lines starting with `#` are ignored.
+    pub fn from_solr(text: &str) -> crate::Result<SynonymMap> {
+        let mut synonym_map = SynonymMap::new();
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(separator_pos) = line.find("=>") {
+                let phrases = split_phrases(&line[..separator_pos]);
+                let synonyms = split_phrases(&line[separator_pos + 2..]);
+                if phrases.is_empty() || synonyms.is_empty() {
+                    return Err(crate::TantivyError::InvalidArgument(format!(
+                        "Invalid synonym mapping {:?}",
+                        line
+                    )));
+                }
+                for phrase in &phrases {
+                    for synonym in &synonyms {
+                        synonym_map.add_synonym(phrase, synonym);
+                    }
+                }
+            } else {
+                let phrases = split_phrases(line);
+                for phrase in &phrases {
+                    for synonym in &phrases {
+                        if phrase != synonym {
+                            synonym_map.add_synonym(phrase, synonym);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(synonym_map)
+    }
+
+    /// Reads a file expressing synonyms in the Solr format.
+    ///
+    /// See [`SynonymMap::from_solr`](#method.from_solr).
+    pub fn from_solr_file<P: AsRef<Path>>(path: P) -> crate::Result<SynonymMap> {
+        let text = std::fs::read_to_string(path)?;
+        SynonymMap::from_solr(&text)
+    }
+
+    /// Returns the number of phrases having synonyms.
+    pub fn len(&self) -> usize {
+        self.synonyms.len()
+    }
+
+    /// Returns true iff no synonym has been registered.
+    pub fn is_empty(&self) -> bool {
+        self.synonyms.is_empty()
+    }
+}
+
+impl From<HashMap<String, Vec<String>>> for SynonymMap {
+    fn from(synonyms: HashMap<String, Vec<String>>) -> SynonymMap {
+        let mut synonym_map = SynonymMap::new();
+        for (phrase, synonyms) in &synonyms {
+            for synonym in synonyms {
+                synonym_map.add_synonym(phrase, synonym);
+            }
+        }
+        synonym_map
+    }
+}
+
+/// `SynonymFilter` emits the synonyms of the tokens, as given by a `SynonymMap`.
+///
+/// The synonyms of a token are emitted right after it, with the same
+/// position and the same offsets.
+/// Multi-word phrases are matched over tokens with consecutive positions. The
+/// longest phrase is preferred. If the phrase is replaced by a single word synonym,
+/// its `position_length` is the number of words of the phrase. The words of a multi-word
+/// synonym get consecutive positions, starting from the position of the matched phrase.
+///
+/// If `expand` is true, the original tokens are kept, and the synonyms are emitted
+/// along them. Otherwise, the original tokens are replaced by their synonyms.
+///
+/// # Index time vs query time
+///
+/// Synonyms can be expanded at indexing time, by using the `SynonymFilter`
+/// in the tokenizer of the field. Changing the synonyms then requires to reindex
+/// the documents.
+///
+/// They can also be expanded at query time: when the tokenizer of a field emits
+/// several tokens at the same position, the `QueryParser` turns them into
+/// a [`SpanOrQuery`](../query/struct.SpanOrQuery.html), matching any of these tokens.
+/// Multi-word synonyms are better handled at indexing time.
+#[derive(Clone)]
+pub struct SynonymFilter {
+    synonyms: Arc<SynonymMap>,
+    expand: bool,
+}
+
+impl SynonymFilter {
+    /// Creates a new `SynonymFilter`.
+    pub fn new<T: Into<Arc<SynonymMap>>>(synonyms: T, expand: bool) -> SynonymFilter {
+        SynonymFilter {
+            synonyms: synonyms.into(),
+            expand,
+        }
+    }
+}
+
+impl TokenFilter for SynonymFilter {
+    fn transform<'a>(&self, token_stream: BoxTokenStream<'a>) -> BoxTokenStream<'a> {
+        BoxTokenStream::from(SynonymFilterStream {
+            synonyms: self.synonyms.clone(),
+            expand: self.expand,
+            tail: token_stream,
+            tail_exhausted: false,
+            pending: VecDeque::new(),
+            output: VecDeque::new(),
+            key: String::new(),
+            token: Token::default(),
+        })
+    }
+}
+
+pub struct SynonymFilterStream<'a> {
+    synonyms: Arc<SynonymMap>,
+    expand: bool,
+    tail: BoxTokenStream<'a>,
+    tail_exhausted: bool,
+    /// Tokens read from the tail that have not been matched yet.
+    pending: VecDeque<Token>,
+    /// Tokens ready to be emitted.
+    output: VecDeque<Token>,
+    /// Buffer used to build the phrase looked up in the `SynonymMap`.
+    key: String,
+    token: Token,
+}
+
+impl<'a> SynonymFilterStream<'a> {
+    fn fill_pending(&mut self) {
+        while !self.tail_exhausted && self.pending.len() < self.synonyms.max_phrase_len.max(1) {
+            if self.tail.advance() {
+                self.pending.push_back(self.tail.token().clone());
+            } else {
+                self.tail_exhausted = true;
+            }
+        }
+    }
+
+    /// Returns the number of words of the longest phrase, starting at the first
+    /// pending token, that has synonyms. `self.key` is then left set to this phrase.
+    ///
+    /// Returns 0 if there are no such phrases.
+    fn longest_match(&mut self) -> usize {
+        let max_len = self.synonyms.max_phrase_len.min(self.pending.len());
+        let first_position = self.pending[0].position;
+        for len in (1..=max_len).rev() {
+            let has_consecutive_positions = self
+                .pending
+                .iter()
+                .take(len)
+                .enumerate()
+                .all(|(i, token)| token.position == first_position + i);
+            if !has_consecutive_positions {
+                continue;
+            }
+            self.key.clear();
+            for (i, token) in self.pending.iter().take(len).enumerate() {
+                if i > 0 {
+                    self.key.push(WORD_SEPARATOR);
+                }
+                self.key.push_str(&token.text);
+            }
+            if self.synonyms.synonyms.contains_key(&self.key) {
+                return len;
+            }
+        }
+        0
+    }
+
+    fn emit_synonyms(&mut self, match_len: usize) {
+        let matched_tokens: Vec<Token> = self.pending.drain(..match_len).collect();
+        let first_token = &matched_tokens[0];
+        let last_token = &matched_tokens[match_len - 1];
+        let (position, offset_from, offset_to) = (
+            first_token.position,
+            first_token.offset_from,
+            last_token.offset_to,
+        );
+        if self.expand {
+            self.output.extend(matched_tokens);
+        }
+        for synonym in &self.synonyms.synonyms[&self.key] {
+            let position_length = if synonym.len() == 1 { match_len } else { 1 };
+            for (i, word) in synonym.iter().enumerate() {
+                self.output.push_back(Token {
+                    offset_from,
+                    offset_to,
+                    position: position + i,
+                    text: word.clone(),
+                    position_length,
+                });
+            }
+        }
+    }
+}
+
+impl<'a> TokenStream for SynonymFilterStream<'a> {
+    fn advance(&mut self) -> bool {
+        loop {
+            if let Some(token) = self.output.pop_front() {
+                self.token = token;
+                return true;
+            }
+            self.fill_pending();
+            if self.pending.is_empty() {
+                return false;
+            }
+            let match_len = self.longest_match();
+            if match_len == 0 {
+                let token = self.pending.pop_front().unwrap();
+                self.output.push_back(token);
+            } else {
+                self.emit_synonyms(match_len);
+            }
+        }
+    }
+
+    fn token(&self) -> &Token {
+        &self.token
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        &mut self.token
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SynonymFilter, SynonymMap};
+    use crate::tokenizer::tests::assert_token;
+    use crate::tokenizer::{LowerCaser, SimpleTokenizer, TextAnalyzer, Token};
+    use std::collections::HashMap;
+
+    fn synonym_helper(synonym_map: SynonymMap, expand: bool, text: &str) -> Vec<Token> {
+        let mut tokens = vec![];
+        let mut token_stream = TextAnalyzer::from(SimpleTokenizer)
+            .filter(LowerCaser)
+            .filter(SynonymFilter::new(synonym_map, expand))
+            .token_stream(text);
+        while token_stream.advance() {
+            tokens.push(token_stream.token().clone());
+        }
+        tokens
+    }
+
+    #[test]
+    fn test_synonym_map_from_solr() -> crate::Result<()> {
+        let synonym_map = SynonymMap::from_solr(
+            "# comment\n\nsofa, couch, settee\nnew york, big apple => ny\nsofa => couch\n",
+        )?;
+        assert_eq!(synonym_map.len(), 5);
+        assert_eq!(
+            synonym_map.synonyms["sofa"],
+            vec![vec!["couch".to_string()], vec!["settee".to_string()]]
+        );
+        assert_eq!(
+            synonym_map.synonyms["big apple"],
+            vec![vec!["ny".to_string()]]
+        );
+        assert_eq!(synonym_map.max_phrase_len, 2);
+        assert!(SynonymMap::from_solr("sofa =>").is_err());
+        assert!(SynonymMap::from_solr("=> sofa").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_synonym_filter_expand() {
+        let mut synonyms = HashMap::new();
+        synonyms.insert("sofa".to_string(), vec!["couch".to_string()]);
+        let tokens = synonym_helper(SynonymMap::from(synonyms), true, "a Sofa b");
+        assert_eq!(tokens.len(), 4);
+        assert_token(&tokens[0], 0, "a", 0, 1);
+        assert_token(&tokens[1], 1, "sofa", 2, 6);
+        assert_token(&tokens[2], 1, "couch", 2, 6);
+        assert_token(&tokens[3], 2, "b", 7, 8);
+    }
+
+    #[test]
+    fn test_synonym_filter_replace() {
+        let mut synonym_map = SynonymMap::new();
+        synonym_map.add_synonym("sofa", "couch");
+        synonym_map.add_synonym("sofa", "settee");
+        let tokens = synonym_helper(synonym_map, false, "sofa b");
+        assert_eq!(tokens.len(), 3);
+        assert_token(&tokens[0], 0, "couch", 0, 4);
+        assert_token(&tokens[1], 0, "settee", 0, 4);
+        assert_token(&tokens[2], 1, "b", 5, 6);
+    }
+
+    #[test]
+    fn test_synonym_filter_multi_word() -> crate::Result<()> {
+        let synonym_map = SynonymMap::from_solr("new york => ny\nyork => yorkshire")?;
+        let tokens = synonym_helper(synonym_map.clone(), true, "in New York city");
+        assert_eq!(tokens.len(), 5);
+        assert_token(&tokens[0], 0, "in", 0, 2);
+        assert_token(&tokens[1], 1, "new", 3, 6);
+        assert_token(&tokens[2], 2, "york", 7, 11);
+        assert_token(&tokens[3], 1, "ny", 3, 11);
+        assert_eq!(tokens[3].position_length, 2);
+        assert_token(&tokens[4], 3, "city", 12, 16);
+        let tokens = synonym_helper(synonym_map.clone(), false, "york new");
+        assert_eq!(tokens.len(), 2);
+        assert_token(&tokens[0], 0, "yorkshire", 0, 4);
+        assert_token(&tokens[1], 1, "new", 5, 8);
+        let tokens = synonym_helper(SynonymMap::from_solr("ny => new york")?, false, "ny");
+        assert_eq!(tokens.len(), 2);
+        assert_token(&tokens[0], 0, "new", 0, 2);
+        assert_token(&tokens[1], 1, "york", 0, 2);
+        Ok(())
+    }
+}
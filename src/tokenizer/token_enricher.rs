@@ -0,0 +1,318 @@
+//! # Example
+//! ```rust
+//! use tantivy::tokenizer::*;
+//!
+//! let enricher = DictionaryMatchEnricher::new(vec!["new york", "york"]);
+//! let tokenizer = TextAnalyzer::from(SimpleTokenizer)
+//!   .filter(LowerCaser)
+//!   .filter(EnrichmentFilter::new(enricher));
+//!
+//! let mut stream = tokenizer.token_stream("New York city");
+//! assert_eq!(stream.next().unwrap().text, "new");
+//! {
+//!     let token = stream.next().unwrap();
+//!     assert_eq!(token.text, "new_york");
+//!     assert_eq!(token.position, 0);
+//!     assert_eq!(token.position_length, 2);
+//! }
+//! assert_eq!(stream.next().unwrap().text, "york");
+//! assert_eq!(stream.next().unwrap().text, "york");
+//! assert_eq!(stream.next().unwrap().text, "city");
+//! assert!(stream.next().is_none());
+//! ```
+//!
+use super::{Token, TokenFilter, TokenStream};
+use crate::tokenizer::BoxTokenStream;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Separator between the words of a phrase, in the dictionary of a `DictionaryMatchEnricher`.
+const WORD_SEPARATOR: char = ' ';
+
+/// Separator between the words of the compound tokens emitted by a `DictionaryMatchEnricher`.
+const COMPOUND_SEPARATOR: char = '_';
+
+/// A `TokenEnricher` computes synthetic tokens from the tokens of a text,
+/// e.g. the named entities (persons, locations, organizations...) found
+/// by an external NER model.
+///
+/// The enricher is plugged into a `TextAnalyzer` with an
+/// [`EnrichmentFilter`](./struct.EnrichmentFilter.html).
+pub trait TokenEnricher: 'static + Send + Sync {
+    /// Returns the tokens to add to `tokens`.
+    ///
+    /// `tokens` are all of the tokens of the text, as emitted by the filters
+    /// preceding the `EnrichmentFilter`. The returned tokens should have the
+    /// position and the offsets of the tokens they are derived from.
+    fn enrich(&self, tokens: &[Token]) -> Vec<Token>;
+}
+
+/// `TokenEnricher` that does not add any token.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PassthroughEnricher;
+
+impl TokenEnricher for PassthroughEnricher {
+    fn enrich(&self, _tokens: &[Token]) -> Vec<Token> {
+        Vec::new()
+    }
+}
+
+/// `TokenEnricher` that adds a compound token for each sequence of tokens
+/// matching a phrase of its dictionary.
+///
+/// A phrase is a sequence of words separated by whitespaces. It is matched against
+/// the text of tokens with consecutive positions: if the `EnrichmentFilter` follows
+/// a `LowerCaser`, the phrases are expected to be lowercased.
+///
+/// The compound token is the words of the phrase joined by `_`, e.g. `new_york`.
+/// It has the position of the first matched token, and spans all of the matched
+/// tokens: its `position_length` is the number of words of the phrase.
+/// Overlapping matches all produce a compound token.
+#[derive(Clone, Debug, Default)]
+pub struct DictionaryMatchEnricher {
+    phrases: HashSet<String>,
+    max_phrase_len: usize,
+}
+
+impl DictionaryMatchEnricher {
+    /// Creates a `DictionaryMatchEnricher` matching the given phrases.
+    pub fn new<I, S>(phrases: I) -> DictionaryMatchEnricher
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut enricher = DictionaryMatchEnricher::default();
+        for phrase in phrases {
+            enricher.add_phrase(phrase.as_ref());
+        }
+        enricher
+    }
+
+    /// Adds a phrase to the dictionary.
+    pub fn add_phrase(&mut self, phrase: &str) {
+        let words: Vec<&str> = phrase.split_whitespace().collect();
+        if words.is_empty() {
+            return;
+        }
+        self.max_phrase_len = self.max_phrase_len.max(words.len());
+        self.phrases.insert(words.join(&WORD_SEPARATOR.to_string()));
+    }
+
+    /// Returns the number of phrases of the dictionary.
+    pub fn len(&self) -> usize {
+        self.phrases.len()
+    }
+
+    /// Returns true iff the dictionary is empty.
+    pub fn is_empty(&self) -> bool {
+        self.phrases.is_empty()
+    }
+}
+
+impl TokenEnricher for DictionaryMatchEnricher {
+    fn enrich(&self, tokens: &[Token]) -> Vec<Token> {
+        let mut compound_tokens = Vec::new();
+        let mut key = String::new();
+        for (start, first_token) in tokens.iter().enumerate() {
+            key.clear();
+            for (i, token) in tokens[start..].iter().take(self.max_phrase_len).enumerate() {
+                if token.position != first_token.position + i {
+                    break;
+                }
+                if i > 0 {
+                    key.push(WORD_SEPARATOR);
+                }
+                key.push_str(&token.text);
+                if self.phrases.contains(&key) {
+                    compound_tokens.push(Token {
+                        offset_from: first_token.offset_from,
+                        offset_to: token.offset_to,
+                        position: first_token.position,
+                        text: key.replace(WORD_SEPARATOR, &COMPOUND_SEPARATOR.to_string()),
+                        position_length: i + 1,
+                    });
+                }
+            }
+        }
+        compound_tokens
+    }
+}
+
+/// `EnrichmentFilter` adds the tokens computed by a
+/// [`TokenEnricher`](./trait.TokenEnricher.html) to the token stream.
+///
+/// The enricher is given all of the tokens of the text at once: the filter therefore
+/// buffers the entire token stream. It should be the last filter of the `TextAnalyzer`.
+///
+/// The tokens returned by the enricher are merged into the token stream according
+/// to their position. They are emitted right after the original tokens sharing
+/// their position.
+#[derive(Clone)]
+pub struct EnrichmentFilter {
+    enricher: Arc<dyn TokenEnricher>,
+}
+
+impl EnrichmentFilter {
+    /// Creates a new `EnrichmentFilter`.
+    pub fn new<E: TokenEnricher>(enricher: E) -> EnrichmentFilter {
+        EnrichmentFilter {
+            enricher: Arc::new(enricher),
+        }
+    }
+}
+
+impl TokenFilter for EnrichmentFilter {
+    fn transform<'a>(&self, token_stream: BoxTokenStream<'a>) -> BoxTokenStream<'a> {
+        BoxTokenStream::from(EnrichmentFilterStream {
+            enricher: self.enricher.clone(),
+            tail: Some(token_stream),
+            tokens: Vec::new().into_iter(),
+            token: Token::default(),
+        })
+    }
+}
+
+pub struct EnrichmentFilterStream<'a> {
+    enricher: Arc<dyn TokenEnricher>,
+    /// The token stream being enriched, until it is consumed.
+    tail: Option<BoxTokenStream<'a>>,
+    tokens: std::vec::IntoIter<Token>,
+    token: Token,
+}
+
+impl<'a> EnrichmentFilterStream<'a> {
+    fn enrich_tail(&mut self, mut tail: BoxTokenStream<'a>) {
+        let mut tokens = Vec::new();
+        while tail.advance() {
+            tokens.push(tail.token().clone());
+        }
+        let enriched_tokens = self.enricher.enrich(&tokens);
+        tokens.extend(enriched_tokens);
+        // The sort is stable: the original tokens remain first among the tokens
+        // sharing a position.
+        tokens.sort_by_key(|token| token.position);
+        self.tokens = tokens.into_iter();
+    }
+}
+
+impl<'a> TokenStream for EnrichmentFilterStream<'a> {
+    fn advance(&mut self) -> bool {
+        if let Some(tail) = self.tail.take() {
+            self.enrich_tail(tail);
+        }
+        if let Some(token) = self.tokens.next() {
+            self.token = token;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn token(&self) -> &Token {
+        &self.token
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        &mut self.token
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DictionaryMatchEnricher, EnrichmentFilter, PassthroughEnricher, TokenEnricher};
+    use crate::tokenizer::tests::assert_token;
+    use crate::tokenizer::{LowerCaser, SimpleTokenizer, TextAnalyzer, Token};
+
+    fn enrich_helper<E: TokenEnricher>(enricher: E, text: &str) -> Vec<Token> {
+        let mut tokens = vec![];
+        let mut token_stream = TextAnalyzer::from(SimpleTokenizer)
+            .filter(LowerCaser)
+            .filter(EnrichmentFilter::new(enricher))
+            .token_stream(text);
+        while token_stream.advance() {
+            tokens.push(token_stream.token().clone());
+        }
+        tokens
+    }
+
+    #[test]
+    fn test_passthrough_enricher() {
+        let tokens = enrich_helper(PassthroughEnricher, "Hello happy tax payer");
+        assert_eq!(tokens.len(), 4);
+        assert_token(&tokens[0], 0, "hello", 0, 5);
+        assert_token(&tokens[3], 3, "payer", 16, 21);
+    }
+
+    #[test]
+    fn test_dictionary_match_enricher() {
+        let enricher = DictionaryMatchEnricher::new(vec!["barack obama", "Ignored  ", "  "]);
+        assert_eq!(enricher.len(), 2);
+        let tokens = enrich_helper(enricher, "President Barack Obama met Barack");
+        assert_eq!(tokens.len(), 6);
+        assert_token(&tokens[0], 0, "president", 0, 9);
+        assert_token(&tokens[1], 1, "barack", 10, 16);
+        assert_token(&tokens[2], 1, "barack_obama", 10, 22);
+        assert_eq!(tokens[2].position_length, 2);
+        assert_token(&tokens[3], 2, "obama", 17, 22);
+        assert_token(&tokens[4], 3, "met", 23, 26);
+        assert_token(&tokens[5], 4, "barack", 27, 33);
+    }
+
+    #[test]
+    fn test_dictionary_match_enricher_requires_consecutive_positions() {
+        let enricher = DictionaryMatchEnricher::new(vec!["new york"]);
+        let tokens = vec![
+            Token {
+                position: 0,
+                text: "new".to_string(),
+                ..Token::default()
+            },
+            Token {
+                position: 2,
+                text: "york".to_string(),
+                ..Token::default()
+            },
+        ];
+        assert!(enricher.enrich(&tokens).is_empty());
+    }
+
+    #[test]
+    fn test_enrichment_filter_indexing() -> crate::Result<()> {
+        use crate::collector::Count;
+        use crate::query::{QueryParser, TermQuery};
+        use crate::schema::{IndexRecordOption, Schema, TextFieldIndexing, TextOptions};
+        use crate::{Index, Term};
+
+        let mut schema_builder = Schema::builder();
+        let text_options = TextOptions::default().set_indexing_options(
+            TextFieldIndexing::default()
+                .set_tokenizer("entities")
+                .set_index_option(IndexRecordOption::WithFreqsAndPositions),
+        );
+        let text = schema_builder.add_text_field("text", text_options);
+        let index = Index::create_in_ram(schema_builder.build());
+        index.tokenizers().register(
+            "entities",
+            TextAnalyzer::from(SimpleTokenizer)
+                .filter(LowerCaser)
+                .filter(EnrichmentFilter::new(DictionaryMatchEnricher::new(vec![
+                    "new york",
+                ]))),
+        );
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text => "I love New York city"));
+        index_writer.add_document(doc!(text => "York is new"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let query_parser = QueryParser::for_index(&index, vec![text]);
+        let count = |query: &str| searcher.search(&*query_parser.parse_query(query)?, &Count);
+        let entity_query = TermQuery::new(
+            Term::from_field_text(text, "new_york"),
+            IndexRecordOption::Basic,
+        );
+        assert_eq!(searcher.search(&entity_query, &Count)?, 1);
+        assert_eq!(count("\"love new york city\"")?, 1);
+        assert_eq!(count("york")?, 2);
+        Ok(())
+    }
+}
@@ -1,11 +1,13 @@
+use crate::schema::DEFAULT_POSITION_GAP;
 use crate::tokenizer::{BoxTokenStream, Token, TokenStream};
 use std::ops::DerefMut;
 
-const POSITION_GAP: usize = 2;
+const POSITION_GAP: usize = DEFAULT_POSITION_GAP as usize;
 
 pub(crate) struct TokenStreamChain<'a> {
     offsets: Vec<usize>,
     token_streams: Vec<BoxTokenStream<'a>>,
+    position_gap: usize,
     position_shift: usize,
     stream_idx: usize,
     token: Token,
@@ -20,10 +22,18 @@ impl<'a> TokenStreamChain<'a> {
             offsets,
             stream_idx: 0,
             token_streams,
+            position_gap: POSITION_GAP,
             position_shift: 0,
             token: Token::default(),
         }
     }
+
+    /// Sets the increment of the position between the last token
+    /// of a stream and the first token of the next stream.
+    pub fn with_position_gap(mut self, position_gap: usize) -> TokenStreamChain<'a> {
+        self.position_gap = position_gap;
+        self
+    }
 }
 
 impl<'a> TokenStream for TokenStreamChain<'a> {
@@ -41,7 +51,7 @@ impl<'a> TokenStream for TokenStreamChain<'a> {
                 return true;
             } else {
                 self.stream_idx += 1;
-                self.position_shift = self.token.position.wrapping_add(POSITION_GAP);
+                self.position_shift = self.token.position.wrapping_add(self.position_gap);
             }
         }
         false
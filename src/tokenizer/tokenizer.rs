@@ -2,11 +2,21 @@ use crate::tokenizer::TokenStreamChain;
 use serde::{Deserialize, Serialize};
 /// The tokenizer module contains all of the tools used to process
 /// text in `tantivy`.
+use std::any;
 use std::borrow::{Borrow, BorrowMut};
+use std::cell::RefCell;
 use std::ops::{Deref, DerefMut};
+use std::rc::Rc;
+
+/// Returns the name of the type `T`, without its module path nor its generic parameters.
+fn short_type_name<T: ?Sized>() -> &'static str {
+    let type_name = any::type_name::<T>();
+    let type_name = type_name.split('<').next().unwrap_or(type_name);
+    type_name.rsplit("::").next().unwrap_or(type_name)
+}
 
 /// Token
-#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, Hash)]
 pub struct Token {
     /// Offset (byte index) of the first character of the token.
     /// Offsets shall not be modified by token filters.
@@ -35,11 +45,63 @@ impl Default for Token {
     }
 }
 
+/// Token emitted by a `TextAnalyzer`, together with the stage of the analyzer that produced it.
+///
+/// See [`TextAnalyzer::debug_annotated_token_stream`](./struct.TextAnalyzer.html#method.debug_annotated_token_stream).
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct AnnotatedToken {
+    /// The token.
+    pub token: Token,
+    /// Name of the last stage that modified the token: the name of a token filter,
+    /// or the name of the tokenizer if no filter modified it.
+    pub stage: &'static str,
+}
+
+/// Token stream recording the last token emitted by a stage of a `TextAnalyzer`.
+struct StageRecorderStream<'a> {
+    tail: BoxTokenStream<'a>,
+    stage_ord: usize,
+    last_tokens: Rc<RefCell<Vec<Token>>>,
+}
+
+impl<'a> StageRecorderStream<'a> {
+    fn wrap(
+        tail: BoxTokenStream<'a>,
+        stage_ord: usize,
+        last_tokens: &Rc<RefCell<Vec<Token>>>,
+    ) -> BoxTokenStream<'a> {
+        BoxTokenStream::from(StageRecorderStream {
+            tail,
+            stage_ord,
+            last_tokens: last_tokens.clone(),
+        })
+    }
+}
+
+impl<'a> TokenStream for StageRecorderStream<'a> {
+    fn advance(&mut self) -> bool {
+        if !self.tail.advance() {
+            return false;
+        }
+        RefCell::borrow_mut(&self.last_tokens)[self.stage_ord].clone_from(self.tail.token());
+        true
+    }
+
+    fn token(&self) -> &Token {
+        self.tail.token()
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.tail.token_mut()
+    }
+}
+
 /// `TextAnalyzer` tokenizes an input text into tokens and modifies the resulting `TokenStream`.
 ///
 /// It simply wraps a `Tokenizer` and a list of `TokenFilter` that are applied sequentially.
 pub struct TextAnalyzer {
     tokenizer: Box<dyn Tokenizer>,
+    tokenizer_name: &'static str,
     token_filters: Vec<BoxTokenFilter>,
 }
 
@@ -57,10 +119,25 @@ impl TextAnalyzer {
     pub fn new<T: Tokenizer>(tokenizer: T, token_filters: Vec<BoxTokenFilter>) -> TextAnalyzer {
         TextAnalyzer {
             tokenizer: Box::new(tokenizer),
+            tokenizer_name: short_type_name::<T>(),
             token_filters,
         }
     }
 
+    /// Returns the name of the type of the tokenizer, e.g. `SimpleTokenizer`.
+    pub fn tokenizer_name(&self) -> &str {
+        self.tokenizer_name
+    }
+
+    /// Returns the names of the types of the token filters, in the order
+    /// they are applied, e.g. `["RemoveLongFilter", "LowerCaser"]`.
+    pub fn filter_names(&self) -> Vec<&str> {
+        self.token_filters
+            .iter()
+            .map(BoxTokenFilter::name)
+            .collect()
+    }
+
     /// Appends a token filter to the current tokenizer.
     ///
     /// The method consumes the current `TokenStream` and returns a
@@ -71,7 +148,7 @@ impl TextAnalyzer {
     /// ```rust
     /// use tantivy::tokenizer::*;
     ///
-    /// let en_stem = TextAnalyzer::from(SimpleTokenizer)
+    /// let analyzer = TextAnalyzer::from(SimpleTokenizer)
     ///     .filter(RemoveLongFilter::limit(40))
     ///     .filter(LowerCaser)
     ///     .filter(Stemmer::default());
@@ -115,12 +192,89 @@ impl TextAnalyzer {
         }
         token_stream
     }
+
+    /// Collects all of the tokens emitted for `text`, to inspect the output
+    /// of the analyzer when building or debugging it.
+    ///
+    /// The tokens are those of [`token_stream`](#method.token_stream), i.e. those
+    /// of the indexing path for a single text value. When indexing, the tokens longer
+    /// than `MAX_TOKEN_LEN` are dropped, and the positions of the values of a multivalued
+    /// field are shifted by the position gap of the field.
+    ///
+    /// ```rust
+    /// use tantivy::tokenizer::*;
+    ///
+    /// let analyzer = TextAnalyzer::from(SimpleTokenizer)
+    ///     .filter(LowerCaser)
+    ///     .filter(StopWordFilter::remove(vec!["the".to_string()]));
+    /// let tokens = analyzer.debug_token_stream("The Quick fox");
+    /// let texts: Vec<&str> = tokens.iter().map(|token| token.text.as_str()).collect();
+    /// assert_eq!(texts, vec!["quick", "fox"]);
+    /// assert_eq!(tokens[0].position, 1);
+    /// assert_eq!((tokens[0].offset_from, tokens[0].offset_to), (4, 9));
+    /// ```
+    pub fn debug_token_stream(&self, text: &str) -> Vec<Token> {
+        let mut tokens = Vec::new();
+        self.token_stream(text)
+            .process(&mut |token: &Token| tokens.push(token.clone()));
+        tokens
+    }
+
+    /// Collects the same tokens as [`debug_token_stream`](#method.debug_token_stream),
+    /// each annotated with the stage of the analyzer that last modified it.
+    ///
+    /// This is meant for tests and debugging, as it is slower than `token_stream`. A token is
+    /// attributed to the last stage whose output differs from the token it last read, so that a
+    /// token emitted by a filter from several input tokens (e.g. a shingle) is attributed to it.
+    ///
+    /// ```rust
+    /// use tantivy::tokenizer::*;
+    ///
+    /// let analyzer = TextAnalyzer::from(SimpleTokenizer)
+    ///     .filter(LowerCaser)
+    ///     .filter(Stemmer::default());
+    /// let annotated_tokens = analyzer.debug_annotated_token_stream("Hello dogs");
+    /// assert_eq!(annotated_tokens[0].token.text, "hello");
+    /// assert_eq!(annotated_tokens[0].stage, "LowerCaser");
+    /// assert_eq!(annotated_tokens[1].token.text, "dog");
+    /// assert_eq!(annotated_tokens[1].stage, "Stemmer");
+    ///
+    /// let annotated_tokens = TextAnalyzer::from(SimpleTokenizer).debug_annotated_token_stream("dogs");
+    /// assert_eq!(annotated_tokens[0].stage, "SimpleTokenizer");
+    /// ```
+    pub fn debug_annotated_token_stream(&self, text: &str) -> Vec<AnnotatedToken> {
+        let last_tokens = Rc::new(RefCell::new(vec![
+            Token::default();
+            self.token_filters.len() + 1
+        ]));
+        let mut token_stream =
+            StageRecorderStream::wrap(self.tokenizer.token_stream(text), 0, &last_tokens);
+        for (filter_ord, token_filter) in self.token_filters.iter().enumerate() {
+            let filtered_stream = token_filter.transform(token_stream);
+            token_stream = StageRecorderStream::wrap(filtered_stream, filter_ord + 1, &last_tokens);
+        }
+        let mut annotated_tokens = Vec::new();
+        while token_stream.advance() {
+            let last_tokens = RefCell::borrow(&last_tokens);
+            let stage = (1..last_tokens.len())
+                .rev()
+                .find(|&stage_ord| last_tokens[stage_ord] != last_tokens[stage_ord - 1])
+                .map(|stage_ord| self.token_filters[stage_ord - 1].name())
+                .unwrap_or(self.tokenizer_name);
+            annotated_tokens.push(AnnotatedToken {
+                token: token_stream.token().clone(),
+                stage,
+            });
+        }
+        annotated_tokens
+    }
 }
 
 impl Clone for TextAnalyzer {
     fn clone(&self) -> Self {
         TextAnalyzer {
             tokenizer: self.tokenizer.box_clone(),
+            tokenizer_name: self.tokenizer_name,
             token_filters: self
                 .token_filters
                 .iter()
@@ -200,7 +354,14 @@ impl<'a> DerefMut for BoxTokenStream<'a> {
 /// Simple wrapper of `Box<dyn TokenFilter + 'a>`.
 ///
 /// See `TokenStream` for more information.
-pub struct BoxTokenFilter(Box<dyn TokenFilter>);
+pub struct BoxTokenFilter(Box<dyn TokenFilter>, &'static str);
+
+impl BoxTokenFilter {
+    /// Returns the name of the type of the token filter, e.g. `LowerCaser`.
+    pub fn name(&self) -> &'static str {
+        self.1
+    }
+}
 
 impl Deref for BoxTokenFilter {
     type Target = dyn TokenFilter;
@@ -212,7 +373,7 @@ impl Deref for BoxTokenFilter {
 
 impl<T: TokenFilter> From<T> for BoxTokenFilter {
     fn from(tokenizer: T) -> BoxTokenFilter {
-        BoxTokenFilter(Box::new(tokenizer))
+        BoxTokenFilter(Box::new(tokenizer), short_type_name::<T>())
     }
 }
 
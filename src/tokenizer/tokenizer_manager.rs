@@ -5,9 +5,47 @@ use crate::tokenizer::RawTokenizer;
 use crate::tokenizer::RemoveLongFilter;
 use crate::tokenizer::SimpleTokenizer;
 use crate::tokenizer::Stemmer;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 
+/// Description of an analyzer registered in a `TokenizerManager`.
+///
+/// The tokenizer and the token filters are described by the names of their types.
+/// Their parameters (e.g. the limit of a `RemoveLongFilter`) are not part of the description.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnalyzerDescription {
+    /// Name under which the analyzer is registered, e.g. `default`.
+    pub name: String,
+    /// Name of the tokenizer, e.g. `SimpleTokenizer`.
+    pub tokenizer: String,
+    /// Names of the token filters, in the order they are applied.
+    pub filters: Vec<String>,
+    /// Human readable description of the pipeline,
+    /// e.g. `SimpleTokenizer | RemoveLongFilter | LowerCaser`.
+    pub configuration: String,
+}
+
+impl AnalyzerDescription {
+    fn new(name: &str, text_analyzer: &TextAnalyzer) -> AnalyzerDescription {
+        let filters: Vec<String> = text_analyzer
+            .filter_names()
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        let configuration = std::iter::once(text_analyzer.tokenizer_name())
+            .chain(filters.iter().map(String::as_str))
+            .collect::<Vec<&str>>()
+            .join(" | ");
+        AnalyzerDescription {
+            name: name.to_string(),
+            tokenizer: text_analyzer.tokenizer_name().to_string(),
+            filters,
+            configuration,
+        }
+    }
+}
+
 /// The tokenizer manager serves as a store for
 /// all of the pre-configured tokenizer pipelines.
 ///
@@ -20,6 +58,35 @@ use std::sync::{Arc, RwLock};
 ///  * `en_stem` : Like `default`, but also applies stemming on the
 ///  resulting tokens. Stemming can improve the recall of your
 ///  search engine.
+///
+/// The fields of the schema refer to their tokenizer by name
+/// (see `TextFieldIndexing::set_tokenizer`), so that several fields can share
+/// the same analyzer, and changing the analyzer registered under a name changes it
+/// for all of these fields.
+///
+/// Only the names are persisted with the schema: the analyzers themselves are code,
+/// and custom analyzers need to be registered again every time the index is opened.
+///
+/// There is no global registry of analyzers. A crate shipping custom `TokenFilter`s
+/// or analyzers can expose a function registering them in a given `TokenizerManager`,
+/// to be called by the application on the `Index::tokenizers()` of each index it opens.
+///
+/// ```rust
+/// use tantivy::schema::{Schema, TextFieldIndexing, TextOptions};
+/// use tantivy::tokenizer::{LowerCaser, SimpleTokenizer, TextAnalyzer};
+/// use tantivy::Index;
+///
+/// let text_options = TextOptions::default()
+///     .set_indexing_options(TextFieldIndexing::default().set_tokenizer("my_analyzer"));
+/// let mut schema_builder = Schema::builder();
+/// schema_builder.add_text_field("title", text_options.clone());
+/// schema_builder.add_text_field("body", text_options);
+/// let index = Index::create_in_ram(schema_builder.build());
+/// index.tokenizers().register(
+///     "my_analyzer",
+///     TextAnalyzer::from(SimpleTokenizer).filter(LowerCaser),
+/// );
+/// ```
 #[derive(Clone)]
 pub struct TokenizerManager {
     tokenizers: Arc<RwLock<HashMap<String, TextAnalyzer>>>,
@@ -46,6 +113,28 @@ impl TokenizerManager {
             .get(tokenizer_name)
             .cloned()
     }
+
+    /// Returns the description of the analyzer registered under `tokenizer_name`.
+    pub fn describe(&self, tokenizer_name: &str) -> Option<AnalyzerDescription> {
+        self.tokenizers
+            .read()
+            .expect("Acquiring the lock should never fail")
+            .get(tokenizer_name)
+            .map(|text_analyzer| AnalyzerDescription::new(tokenizer_name, text_analyzer))
+    }
+
+    /// Returns the descriptions of all of the registered analyzers, sorted by name.
+    pub fn descriptions(&self) -> Vec<AnalyzerDescription> {
+        let mut descriptions: Vec<AnalyzerDescription> = self
+            .tokenizers
+            .read()
+            .expect("Acquiring the lock should never fail")
+            .iter()
+            .map(|(name, text_analyzer)| AnalyzerDescription::new(name, text_analyzer))
+            .collect();
+        descriptions.sort_by(|left, right| left.name.cmp(&right.name));
+        descriptions
+    }
 }
 
 impl Default for TokenizerManager {